@@ -418,6 +418,7 @@ fn backends(c: &mut Criterion) {
                 writer_flags: naga::back::glsl::WriterFlags::empty(),
                 binding_map: Default::default(),
                 zero_initialize_workgroup_memory: true,
+                bounds_report: None,
             };
             for input in &inputs.inner {
                 let module = input.module.as_ref().unwrap();
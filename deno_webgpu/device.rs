@@ -525,6 +525,7 @@ impl GPUDevice {
             }),
             sample_count: descriptor.sample_count,
             multiview: None,
+            inherited_bind_group_layouts: Cow::Borrowed(&[]),
         };
 
         let res = wgpu_core::command::RenderBundleEncoder::new(&wgpu_descriptor, self.id, None);
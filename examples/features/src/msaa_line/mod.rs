@@ -87,6 +87,7 @@ impl Example {
                 depth_stencil: None,
                 sample_count,
                 multiview: None,
+                inherited_bind_group_layouts: &[],
             });
         encoder.set_pipeline(&pipeline);
         encoder.set_vertex_buffer(0, vertex_buffer.slice(..));
@@ -622,6 +622,7 @@ impl crate::framework::Example for Example {
                     }),
                     sample_count: 1,
                     multiview: None,
+                    inherited_bind_group_layouts: &[],
                 });
             encoder.set_pipeline(&terrain_pipeline);
             encoder.set_bind_group(0, &terrain_flipped_bind_group, &[]);
@@ -176,6 +176,20 @@ impl DeviceInterface for CustomDevice {
         wgpu::custom::DispatchComputePipeline::custom(CustomComputePipeline(module.0.clone()))
     }
 
+    fn create_render_pipeline_async(
+        &self,
+        _desc: &wgpu::RenderPipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn wgpu::custom::CreateRenderPipelineFuture>> {
+        unimplemented!()
+    }
+
+    fn create_compute_pipeline_async(
+        &self,
+        desc: &wgpu::ComputePipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn wgpu::custom::CreateComputePipelineFuture>> {
+        Box::pin(std::future::ready(Ok(self.create_compute_pipeline(desc))))
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         _desc: &wgpu::PipelineCacheDescriptor<'_>,
@@ -211,6 +225,28 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn video_decode_capabilities(
+        &self,
+        _codec: wgpu::VideoCodec,
+        _format: wgpu::TextureFormat,
+    ) -> Option<wgpu::VideoDecodeCapabilities> {
+        None
+    }
+
+    fn create_video_decoder(
+        &self,
+        _desc: &wgpu::VideoDecoderDescriptor<'_>,
+    ) -> wgpu::custom::DispatchVideoDecoder {
+        unimplemented!()
+    }
+
+    fn create_queue(
+        &self,
+        _desc: &wgpu::QueueDescriptor<'_>,
+    ) -> Result<wgpu::custom::DispatchQueue, wgpu::Error> {
+        unimplemented!()
+    }
+
     fn create_sampler(&self, _desc: &wgpu::SamplerDescriptor<'_>) -> wgpu::custom::DispatchSampler {
         unimplemented!()
     }
@@ -229,6 +265,13 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn create_reusable_command_encoder(
+        &self,
+        _desc: &wgpu::CommandEncoderDescriptor<'_>,
+    ) -> wgpu::custom::DispatchCommandEncoder {
+        unimplemented!()
+    }
+
     fn create_render_bundle_encoder(
         &self,
         _desc: &wgpu::RenderBundleEncoderDescriptor<'_>,
@@ -275,6 +318,18 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn get_memory_budget(&self) -> Option<wgpu::MemoryBudgetReport> {
+        unimplemented!()
+    }
+
+    fn set_memory_budget_callback(
+        &self,
+        _threshold: u8,
+        _callback: wgpu::custom::BoxMemoryBudgetCallback,
+    ) {
+        unimplemented!()
+    }
+
     fn destroy(&self) {
         unimplemented!()
     }
@@ -365,6 +420,29 @@ impl QueueInterface for CustomQueue {
     fn compact_blas(&self, _blas: &DispatchBlas) -> (Option<u64>, DispatchBlas) {
         unimplemented!()
     }
+
+    fn decode_video(
+        &self,
+        _decoder: &wgpu::custom::DispatchVideoDecoder,
+        _desc: &wgpu::VideoDecodeDescriptor<'_>,
+    ) {
+        unimplemented!()
+    }
+
+    fn bind_sparse(
+        &self,
+        _buffer_binds: &[wgpu::SparseBufferBind<'_>],
+        _texture_binds: &[wgpu::SparseTextureBind<'_>],
+    ) {
+        unimplemented!()
+    }
+
+    fn create_command_encoder(
+        &self,
+        _desc: &wgpu::CommandEncoderDescriptor<'_>,
+    ) -> wgpu::custom::DispatchCommandEncoder {
+        unimplemented!()
+    }
 }
 
 #[derive(Debug)]
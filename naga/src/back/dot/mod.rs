@@ -601,6 +601,7 @@ fn write_function_expressions(
                 level,
                 depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 edges.insert("image", image);
                 edges.insert("sampler", sampler);
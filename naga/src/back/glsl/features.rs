@@ -55,6 +55,10 @@ bitflags::bitflags! {
         const SUBGROUP_OPERATIONS = 1 << 24;
         /// Image atomics
         const TEXTURE_ATOMICS = 1 << 25;
+        /// Reading the current value of color attachments
+        const FRAMEBUFFER_FETCH = 1 << 26;
+        /// Reading the current value of the depth and stencil attachments
+        const FRAMEBUFFER_FETCH_DEPTH_STENCIL = 1 << 27;
     }
 }
 
@@ -124,6 +128,11 @@ impl FeaturesManager {
         check_feature!(DUAL_SOURCE_BLENDING, 330, 300 /* with extension */);
         check_feature!(SUBGROUP_OPERATIONS, 430, 310);
         check_feature!(TEXTURE_ATOMICS, 420, 310);
+        check_feature!(FRAMEBUFFER_FETCH, 130, 300 /* with extension */);
+        // Only available on ES, through an extension.
+        if self.0.contains(Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL) && !version.is_es() {
+            missing |= Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL;
+        }
         match version {
             Version::Embedded { is_webgl: true, .. } => check_feature!(MULTI_VIEW, 140, 300),
             _ => check_feature!(MULTI_VIEW, 140, 310),
@@ -254,6 +263,19 @@ impl FeaturesManager {
             writeln!(out, "#extension GL_EXT_blend_func_extended : require")?;
         }
 
+        if self.0.contains(Features::FRAMEBUFFER_FETCH) {
+            // https://registry.khronos.org/OpenGL/extensions/EXT/EXT_shader_framebuffer_fetch.txt
+            writeln!(out, "#extension GL_EXT_shader_framebuffer_fetch : require")?;
+        }
+
+        if self.0.contains(Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL) {
+            // https://registry.khronos.org/OpenGL/extensions/ARM/ARM_shader_framebuffer_fetch_depth_stencil.txt
+            writeln!(
+                out,
+                "#extension GL_ARM_shader_framebuffer_fetch_depth_stencil : require"
+            )?;
+        }
+
         if self.0.contains(Features::INSTANCE_INDEX) {
             if options.writer_flags.contains(WriterFlags::DRAW_PARAMETERS) {
                 // https://registry.khronos.org/OpenGL/extensions/ARB/ARB_shader_draw_parameters.txt
@@ -429,6 +451,10 @@ impl<W> Writer<'_, W> {
             }
         }
 
+        if self.reports_bounds {
+            self.features.request(Features::BUFFER_STORAGE);
+        }
+
         let mut push_constant_used = false;
 
         for (handle, global) in self.module.global_variables.iter() {
@@ -603,6 +629,9 @@ impl<W> Writer<'_, W> {
                     crate::BuiltIn::InstanceIndex | crate::BuiltIn::DrawID => {
                         self.features.request(Features::INSTANCE_INDEX)
                     }
+                    crate::BuiltIn::LastFragDepth | crate::BuiltIn::LastFragStencil => self
+                        .features
+                        .request(Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL),
                     _ => {}
                 },
                 Binding::Location {
@@ -621,6 +650,9 @@ impl<W> Writer<'_, W> {
                         self.features.request(Features::DUAL_SOURCE_BLENDING);
                     }
                 }
+                Binding::FramebufferFetch { .. } => {
+                    self.features.request(Features::FRAMEBUFFER_FETCH);
+                }
             }
         }
     }
@@ -493,6 +493,9 @@ pub const RESERVED_KEYWORDS: &[&str] = &[
     super::MODF_FUNCTION,
     super::FREXP_FUNCTION,
     super::FIRST_INSTANCE_BINDING,
+    super::BOUNDS_REPORT_BLOCK,
+    super::BOUNDS_REPORT_VARIABLE,
+    super::BOUNDS_REPORT_FUNCTION,
 ];
 
 /// The above set of reserved keywords, turned into a cached HashSet. This saves
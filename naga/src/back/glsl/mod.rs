@@ -86,6 +86,12 @@ const CLAMPED_LOD_SUFFIX: &str = "_clamped_lod";
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
 
+/// The names of the bounds report block, its instance, and the function
+/// recording accesses in it, for [`proc::BoundsCheckPolicy::Report`].
+pub(crate) const BOUNDS_REPORT_BLOCK: &str = "NagaBoundsReport";
+pub(crate) const BOUNDS_REPORT_VARIABLE: &str = "naga_bounds_report_buffer";
+pub(crate) const BOUNDS_REPORT_FUNCTION: &str = "naga_bounds_report";
+
 // Must match code in glsl_built_in
 pub const FIRST_INSTANCE_BINDING: &str = "naga_vs_first_instance";
 
@@ -327,6 +333,16 @@ pub struct Options {
     pub binding_map: BindingMap,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
+    /// Where to record out-of-bounds accesses made under
+    /// [`BoundsCheckPolicy::Report`].
+    ///
+    /// The bounds report is a shader storage block, bound like globals with
+    /// [`binding_map`], or by the name in [`ReflectionInfo::bounds_report`].
+    /// Writing a module under that policy without setting this is an error.
+    ///
+    /// [`BoundsCheckPolicy::Report`]: proc::BoundsCheckPolicy::Report
+    /// [`binding_map`]: Options::binding_map
+    pub bounds_report: Option<proc::BoundsReportOptions>,
 }
 
 impl Default for Options {
@@ -336,6 +352,7 @@ impl Default for Options {
             writer_flags: WriterFlags::ADJUST_COORDINATE_SPACE,
             binding_map: BindingMap::default(),
             zero_initialize_workgroup_memory: true,
+            bounds_report: None,
         }
     }
 }
@@ -379,6 +396,9 @@ pub struct ReflectionInfo {
     pub push_constant_items: Vec<PushConstantItem>,
     /// Number of user-defined clip planes. Only applicable to vertex shaders.
     pub clip_distance_count: u32,
+    /// Name of the bounds report block, if the shader records out-of-bounds
+    /// accesses in it. See [`Options::bounds_report`].
+    pub bounds_report: Option<String>,
 }
 
 /// Mapping between a texture and its sampler, if it exists.
@@ -481,6 +501,8 @@ impl VaryingOptions {
 /// - Varyings with location bindings are named `_S_location_X` where `S` is a
 ///   prefix identifying which pipeline stage the varying connects, and `X` is
 ///   the location.
+/// - Framebuffer fetch inputs share the name of the fragment output at the same
+///   location, which is declared `inout`.
 struct VaryingName<'a> {
     binding: &'a crate::Binding,
     stage: ShaderStage,
@@ -510,6 +532,9 @@ impl fmt::Display for VaryingName<'_> {
             crate::Binding::BuiltIn(built_in) => {
                 write!(f, "{}", glsl_built_in(built_in, self.options))
             }
+            crate::Binding::FramebufferFetch { location } => {
+                write!(f, "_fs2p_location{location}")
+            }
         }
     }
 }
@@ -625,6 +650,8 @@ pub struct Writer<'a, W> {
     varying: crate::FastHashMap<String, VaryingLocation>,
     /// Number of user-defined clip planes. Only non-zero for vertex shaders.
     clip_distance_count: u32,
+    /// Whether the module records out-of-bounds accesses in the bounds report.
+    reports_bounds: bool,
 }
 
 impl<'a, W: Write> Writer<'a, W> {
@@ -672,6 +699,21 @@ impl<'a, W: Write> Writer<'a, W> {
             &mut names,
         );
 
+        let reporting_functions = proc::reporting_functions(module, info, policies);
+        let reports_bounds = !reporting_functions.is_empty()
+            || proc::function_reports(
+                module,
+                &module.entry_points[ep_idx].function,
+                info.get_entry_point(ep_idx),
+                policies,
+                &reporting_functions,
+            );
+        if reports_bounds && options.bounds_report.is_none() {
+            return Err(Error::Custom(
+                "the `Report` bounds check policy requires `bounds_report` to be set".to_string(),
+            ));
+        }
+
         // Build the instance
         let mut this = Self {
             module,
@@ -693,6 +735,7 @@ impl<'a, W: Write> Writer<'a, W> {
             continue_ctx: back::continue_forward::ContinueCtx::default(),
             varying: Default::default(),
             clip_distance_count: 0,
+            reports_bounds,
         };
 
         // Find all features required to print this module
@@ -866,7 +909,8 @@ impl<'a, W: Write> Writer<'a, W> {
                         )?;
                     }
                 }
-                &crate::PredeclaredType::AtomicCompareExchangeWeakResult(_) => {
+                &crate::PredeclaredType::AtomicCompareExchangeWeakResult(_)
+                | &crate::PredeclaredType::SparseSampleResult(_) => {
                     // Handled by the general struct writing loop earlier.
                 }
             }
@@ -984,6 +1028,10 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
+        if let Some(bounds_report) = self.options.bounds_report.filter(|_| self.reports_bounds) {
+            self.write_bounds_report(bounds_report)?;
+        }
+
         for arg in self.entry_point.function.arguments.iter() {
             self.write_varying(arg.binding.as_ref(), arg.ty, false)?;
         }
@@ -1262,6 +1310,73 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Write the bounds report block and the function recording accesses in it.
+    ///
+    /// Under the [`Report`] bounds check policy, reported indices are passed
+    /// through this function, which restricts them like the [`Restrict`]
+    /// policy does, and records those it had to restrict. See
+    /// [`BoundsReportOptions`] for the layout of the bounds report.
+    ///
+    /// [`Report`]: proc::BoundsCheckPolicy::Report
+    /// [`Restrict`]: proc::BoundsCheckPolicy::Restrict
+    /// [`BoundsReportOptions`]: proc::BoundsReportOptions
+    fn write_bounds_report(&mut self, options: proc::BoundsReportOptions) -> BackendResult {
+        let capacity = proc::BOUNDS_REPORT_CAPACITY;
+        let shader_id = options.shader_id;
+        write!(self.out, "layout(std430")?;
+        if self.options.version.supports_explicit_locations() {
+            if let Some(binding) = self.options.binding_map.get(&options.binding) {
+                write!(self.out, ", binding = {binding}")?;
+            }
+        }
+        writeln!(
+            self.out,
+            ") buffer {BOUNDS_REPORT_BLOCK} {{ uint count; uvec4 records[{capacity}]; }} {BOUNDS_REPORT_VARIABLE};"
+        )?;
+        writeln!(
+            self.out,
+            "uint {BOUNDS_REPORT_FUNCTION}(uint index, uint length, uint location) {{
+    if (index < length) {{
+        return index;
+    }}
+    uint slot = atomicAdd({BOUNDS_REPORT_VARIABLE}.count, 1u);
+    if (slot < {capacity}u) {{
+        {BOUNDS_REPORT_VARIABLE}.records[slot] = uvec4({shader_id}u, location, index, length);
+    }}
+    return length - 1u;
+}}"
+        )?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    /// Write the `index` of the reported access `expr` to `base`, of `length`.
+    ///
+    /// See [`Writer::write_bounds_report`].
+    fn write_reported_index(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        base: Handle<crate::Expression>,
+        index: Handle<crate::Expression>,
+        length: proc::IndexableLength,
+        ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        write!(self.out, "{BOUNDS_REPORT_FUNCTION}(uint(")?;
+        self.write_expr(index, ctx)?;
+        write!(self.out, "), ")?;
+        match length {
+            proc::IndexableLength::Known(length) => write!(self.out, "{length}u")?,
+            proc::IndexableLength::Dynamic => {
+                write!(self.out, "uint(")?;
+                self.write_expr(base, ctx)?;
+                write!(self.out, ".length())")?;
+            }
+        }
+        let location = proc::bounds_report_location(ctx.expressions, expr);
+        write!(self.out, ", {location}u)")?;
+        Ok(())
+    }
+
     /// Helper method used to write non images/sampler globals
     ///
     /// # Notes
@@ -1585,10 +1700,15 @@ impl<'a, W: Write> Writer<'a, W> {
     /// shader stages are being connected, and choose the `in` or `out` storage
     /// qualifier.
     ///
+    /// Fragment outputs read back with [`FramebufferFetch`] are declared
+    /// `inout`. A fetched location without a matching output gets an `inout`
+    /// global of its own.
+    ///
     /// [`self.entry_point`]: Writer::entry_point
     /// [`self.entry_point.stage`]: crate::EntryPoint::stage
     /// [`Location`]: crate::Binding::Location
     /// [`BuiltIn`]: crate::Binding::BuiltIn
+    /// [`FramebufferFetch`]: crate::Binding::FramebufferFetch
     fn write_varying(
         &mut self,
         binding: Option<&crate::Binding>,
@@ -1660,7 +1780,25 @@ impl<'a, W: Write> Writer<'a, W> {
                 }
                 return Ok(());
             }
+            crate::Binding::FramebufferFetch { location } => {
+                match self.fragment_output_type(location) {
+                    Some(output_ty) if output_ty != ty => {
+                        return Err(Error::Custom(format!(
+                            "Framebuffer fetch at location {location} must have the same type \
+                             as the fragment output"
+                        )));
+                    }
+                    // Declared along with the output.
+                    Some(_) => return Ok(()),
+                    None => (location, None, None, None),
+                }
+            }
+        };
+        let fetched = match *binding {
+            crate::Binding::FramebufferFetch { .. } => true,
+            _ => output && self.is_framebuffer_fetched(location),
         };
+        let output = output || fetched;
 
         // Write the interpolation modifier if needed
         //
@@ -1718,7 +1856,12 @@ impl<'a, W: Write> Writer<'a, W> {
         }
 
         // Write the input/output qualifier.
-        write!(self.out, "{} ", if output { "out" } else { "in" })?;
+        let qualifier = match (output, fetched) {
+            (_, true) => "inout",
+            (true, false) => "out",
+            (false, false) => "in",
+        };
+        write!(self.out, "{qualifier} ")?;
 
         // Write the type
         // `write_type` adds no leading or trailing spaces
@@ -1745,6 +1888,70 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Calls `f` with the binding and type of every entry point argument or
+    /// result member, flattening structs.
+    fn for_each_io_binding(
+        &self,
+        output: bool,
+        mut f: impl FnMut(&crate::Binding, Handle<crate::Type>),
+    ) {
+        let function = &self.entry_point.function;
+        let bindings = if output {
+            function
+                .result
+                .as_ref()
+                .map(|result| (result.binding.as_ref(), result.ty))
+                .into_iter()
+                .collect::<Vec<_>>()
+        } else {
+            function
+                .arguments
+                .iter()
+                .map(|arg| (arg.binding.as_ref(), arg.ty))
+                .collect()
+        };
+        for (binding, ty) in bindings {
+            if let TypeInner::Struct { ref members, .. } = self.module.types[ty].inner {
+                for member in members {
+                    if let Some(ref binding) = member.binding {
+                        f(binding, member.ty);
+                    }
+                }
+            } else if let Some(binding) = binding {
+                f(binding, ty);
+            }
+        }
+    }
+
+    /// Returns the type of the fragment output at `location`, if there is one.
+    fn fragment_output_type(&self, location: u32) -> Option<Handle<crate::Type>> {
+        let mut output_ty = None;
+        self.for_each_io_binding(true, |binding, ty| {
+            if let crate::Binding::Location {
+                location: loc,
+                blend_src: None | Some(0),
+                ..
+            } = *binding
+            {
+                if loc == location {
+                    output_ty = Some(ty);
+                }
+            }
+        });
+        output_ty
+    }
+
+    /// Returns whether the entry point reads `location` with framebuffer fetch.
+    fn is_framebuffer_fetched(&self, location: u32) -> bool {
+        let mut fetched = false;
+        self.for_each_io_binding(false, |binding, _| {
+            if *binding == (crate::Binding::FramebufferFetch { location }) {
+                fetched = true;
+            }
+        });
+        fetched
+    }
+
     /// Helper method used to write functions (both entry points and regular functions)
     ///
     /// # Notes
@@ -2183,7 +2390,9 @@ impl<'a, W: Write> Writer<'a, W> {
                             ..
                         } = *ctx.resolve_type(image, &self.module.types)
                         {
-                            if let proc::BoundsCheckPolicy::Restrict = self.policies.image_load {
+                            if let proc::BoundsCheckPolicy::Restrict
+                            | proc::BoundsCheckPolicy::Report = self.policies.image_load
+                            {
                                 write!(self.out, "{level}")?;
                                 self.write_clamped_lod(ctx, handle, image, level_expr)?
                             }
@@ -2970,7 +3179,17 @@ impl<'a, W: Write> Writer<'a, W> {
             Expression::Access { base, index } => {
                 self.write_expr(base, ctx)?;
                 write!(self.out, "[")?;
-                self.write_expr(index, ctx)?;
+                match proc::reported_access(
+                    self.module,
+                    ctx.expressions,
+                    ctx.info,
+                    self.policies,
+                    base,
+                    index,
+                ) {
+                    Some(length) => self.write_reported_index(expr, base, index, length, ctx)?,
+                    None => self.write_expr(index, ctx)?,
+                }
                 write!(self.out, "]")?
             }
             // `AccessIndex` is the same as `Access` except that the index is a constant and it can
@@ -3058,7 +3277,14 @@ impl<'a, W: Write> Writer<'a, W> {
                 level,
                 depth_ref,
                 clamp_to_edge: _,
+                residency,
             } => {
+                if residency {
+                    return Err(Error::Custom(
+                        "Sampling with residency is not supported".to_string(),
+                    ));
+                }
+
                 let (dim, class, arrayed) = match *ctx.resolve_type(image, &self.module.types) {
                     TypeInner::Image {
                         dim,
@@ -3572,6 +3798,11 @@ impl<'a, W: Write> Writer<'a, W> {
                     Rf::IsNan => "isnan",
                     Rf::All => "all",
                     Rf::Any => "any",
+                    Rf::IsResident => {
+                        return Err(Error::Custom(
+                            "Residency codes are not supported".to_string(),
+                        ))
+                    }
                 };
                 write!(self.out, "{fun_name}(")?;
 
@@ -4724,7 +4955,7 @@ impl<'a, W: Write> Writer<'a, W> {
         // If we are using `Restrict` bounds checking we need to pass valid texel
         // coordinates, to do so we use the `clamp` function to get a value between
         // 0 and the image size - 1 (indexing begins at 0)
-        if let proc::BoundsCheckPolicy::Restrict = policy {
+        if let proc::BoundsCheckPolicy::Restrict | proc::BoundsCheckPolicy::Report = policy {
             write!(self.out, "clamp(")?;
         }
 
@@ -4733,7 +4964,7 @@ impl<'a, W: Write> Writer<'a, W> {
 
         // If we are using `Restrict` bounds checking we need to write the rest of the
         // clamp we initiated before writing the coordinates.
-        if let proc::BoundsCheckPolicy::Restrict = policy {
+        if let proc::BoundsCheckPolicy::Restrict | proc::BoundsCheckPolicy::Report = policy {
             // Write the min value 0
             if vector_size == 1 {
                 write!(self.out, ", 0")?;
@@ -5080,6 +5311,7 @@ impl<'a, W: Write> Writer<'a, W> {
             varying: mem::take(&mut self.varying),
             push_constant_items,
             clip_distance_count: self.clip_distance_count,
+            bounds_report: self.reports_bounds.then(|| BOUNDS_REPORT_BLOCK.to_string()),
         })
     }
 
@@ -5228,6 +5460,8 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::FrontFacing => "gl_FrontFacing",
         Bi::PrimitiveIndex => "uint(gl_PrimitiveID)",
         Bi::SampleIndex => "gl_SampleID",
+        Bi::LastFragDepth => "gl_LastFragDepthARM",
+        Bi::LastFragStencil => "uint(gl_LastFragStencilARM)",
         Bi::SampleMask => {
             if options.output {
                 "gl_SampleMask"
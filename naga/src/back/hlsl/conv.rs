@@ -180,7 +180,12 @@ impl crate::BuiltIn {
             Self::BaseInstance | Self::BaseVertex | Self::WorkGroupSize => {
                 return Err(Error::Unimplemented(format!("builtin {self:?}")))
             }
-            Self::PointSize | Self::ViewIndex | Self::PointCoord | Self::DrawID => {
+            Self::PointSize
+            | Self::ViewIndex
+            | Self::PointCoord
+            | Self::DrawID
+            | Self::LastFragDepth
+            | Self::LastFragStencil => {
                 return Err(Error::Custom(format!("Unsupported builtin {self:?}")))
             }
         })
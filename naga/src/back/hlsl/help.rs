@@ -1271,7 +1271,8 @@ impl<W: Write> super::Writer<'_, W> {
                     )?;
                     writeln!(self.out)?;
                 }
-                &crate::PredeclaredType::AtomicCompareExchangeWeakResult { .. } => {}
+                &crate::PredeclaredType::AtomicCompareExchangeWeakResult { .. }
+                | &crate::PredeclaredType::SparseSampleResult(_) => {}
             }
         }
         if module.special_types.ray_desc.is_some() {
@@ -1868,6 +1869,20 @@ impl<W: Write> super::Writer<'_, W> {
                         self.write_wrapped_array_length_function(wal)?;
                     }
                 }
+                crate::Expression::Access { base, index } => {
+                    // Reported accesses to runtime-sized arrays need their length.
+                    if let Some(crate::proc::IndexableLength::Dynamic) =
+                        self.reported_access(module, func_ctx, base, index)
+                    {
+                        let var_handle = super::storage::storage_global(func_ctx, base);
+                        let wal = WrappedArrayLength {
+                            writable: super::storage::is_writable(module, var_handle),
+                        };
+                        if self.wrapped.insert(WrappedType::ArrayLength(wal)) {
+                            self.write_wrapped_array_length_function(wal)?;
+                        }
+                    }
+                }
                 crate::Expression::ImageLoad { image, .. } => {
                     let class = match *func_ctx.resolve_type(image, &module.types) {
                         crate::TypeInner::Image { class, .. } => class,
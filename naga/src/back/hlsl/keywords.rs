@@ -836,6 +836,8 @@ pub const RESERVED: &[&str] = &[
     super::writer::F2I64_FUNCTION,
     super::writer::F2U64_FUNCTION,
     super::writer::IMAGE_LOAD_EXTERNAL_FUNCTION,
+    super::writer::BOUNDS_REPORT_VARIABLE,
+    super::writer::BOUNDS_REPORT_FUNCTION,
     super::writer::IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION,
 ];
 
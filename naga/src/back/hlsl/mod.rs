@@ -542,6 +542,20 @@ pub struct Options {
     /// If set, loops will have code injected into them, forcing the compiler
     /// to think the number of iterations is bounded.
     pub force_loop_bounding: bool,
+    /// Where to record out-of-bounds accesses, if they should be reported.
+    ///
+    /// This back end takes no [`BoundsCheckPolicies`], so setting this makes
+    /// it apply [`BoundsCheckPolicy::Report`] to indices into arrays, vectors,
+    /// matrices and buffers, instead of [`restrict_indexing`].
+    ///
+    /// The bounds report is a `RWByteAddressBuffer`, bound like globals with
+    /// [`binding_map`].
+    ///
+    /// [`BoundsCheckPolicies`]: crate::proc::BoundsCheckPolicies
+    /// [`BoundsCheckPolicy::Report`]: crate::proc::BoundsCheckPolicy::Report
+    /// [`restrict_indexing`]: Options::restrict_indexing
+    /// [`binding_map`]: Options::binding_map
+    pub bounds_report: Option<proc::BoundsReportOptions>,
 }
 
 impl Default for Options {
@@ -559,6 +573,7 @@ impl Default for Options {
             zero_initialize_workgroup_memory: true,
             restrict_indexing: true,
             force_loop_bounding: true,
+            bounds_report: None,
         }
     }
 }
@@ -55,12 +55,12 @@ correct type if necessary.
 [`Load` and `Store`]: https://github.com/microsoft/DirectXShaderCompiler/wiki/ByteAddressBuffer-Load-Store-Additions
 */
 
-use alloc::format;
+use alloc::{format, string::String};
 use core::{fmt, mem};
 
 use super::{super::FunctionCtx, BackendResult, Error};
 use crate::{
-    proc::{Alignment, NameKey, TypeResolution},
+    proc::{Alignment, IndexableLength, NameKey, TypeResolution},
     Handle,
 };
 
@@ -94,6 +94,20 @@ pub(super) enum SubAccess {
         value: Handle<crate::Expression>,
         stride: u32,
     },
+
+    /// Like [`Index`], for the [`Access`] expression `access`, whose
+    /// out-of-bounds indices are reported in [`Options::bounds_report`].
+    /// `length` is the length of the array, as HLSL.
+    ///
+    /// [`Index`]: SubAccess::Index
+    /// [`Access`]: crate::Expression::Access
+    /// [`Options::bounds_report`]: super::Options::bounds_report
+    ReportedIndex {
+        access: Handle<crate::Expression>,
+        value: Handle<crate::Expression>,
+        stride: u32,
+        length: String,
+    },
 }
 
 pub(super) enum StoreValue {
@@ -142,6 +156,15 @@ impl<W: fmt::Write> super::Writer<'_, W> {
                     self.write_expr(module, value, func_ctx)?;
                     write!(self.out, "*{stride}")?;
                 }
+                SubAccess::ReportedIndex {
+                    access,
+                    value,
+                    stride,
+                    ref length,
+                } => {
+                    self.write_reported_index(module, access, value, length, func_ctx)?;
+                    write!(self.out, "*{stride}")?;
+                }
             }
         }
         Ok(())
@@ -554,6 +577,35 @@ impl<W: fmt::Write> super::Writer<'_, W> {
         Ok(())
     }
 
+    /// Return HLSL for `length`, the length of the array `array` refers to.
+    ///
+    /// A dynamic length is computed from the size of the buffer, like
+    /// [`ArrayLength`] does.
+    ///
+    /// [`ArrayLength`]: crate::Expression::ArrayLength
+    fn storage_array_length(
+        &self,
+        module: &crate::Module,
+        array: Handle<crate::Expression>,
+        length: IndexableLength,
+        func_ctx: &FunctionCtx,
+    ) -> String {
+        match length {
+            IndexableLength::Known(length) => format!("{length}u"),
+            IndexableLength::Dynamic => {
+                let var_handle = storage_global(func_ctx, array);
+                let (offset, stride) = runtime_array_layout(module, var_handle);
+                let access_str = if is_writable(module, var_handle) {
+                    "RW"
+                } else {
+                    ""
+                };
+                let name = &self.names[&NameKey::GlobalVariable(var_handle)];
+                format!("((NagaBufferLength{access_str}({name}) - {offset}u) / {stride}u)")
+            }
+        }
+    }
+
     /// Set [`temp_access_chain`] to compute the byte offset of `cur_expr`.
     ///
     /// The `cur_expr` expression must be a reference to a global
@@ -629,7 +681,15 @@ impl<W: fmt::Write> super::Writer<'_, W> {
 
             let sub = match (parent, access_index) {
                 (Parent::Array { stride }, AccessIndex::Expression(value)) => {
-                    SubAccess::Index { value, stride }
+                    match self.reported_access(module, func_ctx, next_expr, value) {
+                        Some(length) => SubAccess::ReportedIndex {
+                            access: cur_expr,
+                            value,
+                            stride,
+                            length: self.storage_array_length(module, next_expr, length, func_ctx),
+                        },
+                        None => SubAccess::Index { value, stride },
+                    }
                 }
                 (Parent::Array { stride }, AccessIndex::Constant(index)) => {
                     SubAccess::Offset(stride * index)
@@ -645,3 +705,56 @@ impl<W: fmt::Write> super::Writer<'_, W> {
         }
     }
 }
+
+/// Return the [`Storage`] global that the access chain `expr` refers to.
+///
+/// [`Storage`]: crate::AddressSpace::Storage
+pub(super) fn storage_global(
+    func_ctx: &FunctionCtx,
+    mut expr: Handle<crate::Expression>,
+) -> Handle<crate::GlobalVariable> {
+    loop {
+        match func_ctx.expressions[expr] {
+            crate::Expression::GlobalVariable(handle) => return handle,
+            crate::Expression::Access { base, .. }
+            | crate::Expression::AccessIndex { base, .. } => expr = base,
+            ref other => unreachable!("Storage access of {other:?}"),
+        }
+    }
+}
+
+/// Return the offset and stride of the runtime-sized array in the
+/// [`Storage`] global `var_handle`.
+///
+/// [`Storage`]: crate::AddressSpace::Storage
+pub(super) fn runtime_array_layout(
+    module: &crate::Module,
+    var_handle: Handle<crate::GlobalVariable>,
+) -> (u32, u32) {
+    match module.types[module.global_variables[var_handle].ty].inner {
+        crate::TypeInner::Array { stride, .. } => (0, stride),
+        crate::TypeInner::Struct { ref members, .. } => {
+            let last = members.last().unwrap();
+            let stride = match module.types[last.ty].inner {
+                crate::TypeInner::Array { stride, .. } => stride,
+                _ => unreachable!(),
+            };
+            (last.offset, stride)
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Return `true` if the [`Storage`] global `var_handle` is writable, and so
+/// declared as a `RWByteAddressBuffer`.
+///
+/// [`Storage`]: crate::AddressSpace::Storage
+pub(super) fn is_writable(
+    module: &crate::Module,
+    var_handle: Handle<crate::GlobalVariable>,
+) -> bool {
+    match module.global_variables[var_handle].space {
+        crate::AddressSpace::Storage { access } => access.contains(crate::StorageAccess::STORE),
+        _ => false,
+    }
+}
@@ -11,7 +11,7 @@ use super::{
         WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedStructMatrixAccess,
         WrappedZeroValue,
     },
-    storage::StoreValue,
+    storage::{self, StoreValue},
     BackendResult, Error, FragmentEntryPoint, Options, PipelineOptions, ShaderModel,
 };
 use crate::{
@@ -46,6 +46,16 @@ pub(crate) const F2U64_FUNCTION: &str = "naga_f2u64";
 pub(crate) const IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION: &str =
     "nagaTextureSampleBaseClampToEdge";
 pub(crate) const IMAGE_LOAD_EXTERNAL_FUNCTION: &str = "nagaTextureLoadExternal";
+pub(crate) const BOUNDS_REPORT_VARIABLE: &str = "naga_bounds_report_buffer";
+pub(crate) const BOUNDS_REPORT_FUNCTION: &str = "naga_bounds_report";
+
+/// The policies [`Options::bounds_report`] applies.
+const BOUNDS_REPORT_POLICIES: proc::BoundsCheckPolicies = proc::BoundsCheckPolicies {
+    index: proc::BoundsCheckPolicy::Report,
+    buffer: proc::BoundsCheckPolicy::Report,
+    image_load: proc::BoundsCheckPolicy::Unchecked,
+    binding_array: proc::BoundsCheckPolicy::Unchecked,
+};
 
 enum Index {
     Expression(Handle<crate::Expression>),
@@ -97,7 +107,7 @@ impl InterfaceKey {
         match binding {
             Some(&crate::Binding::Location { location, .. }) => Self::Location(location),
             Some(&crate::Binding::BuiltIn(built_in)) => Self::BuiltIn(built_in),
-            None => Self::Other,
+            Some(&crate::Binding::FramebufferFetch { .. }) | None => Self::Other,
         }
     }
 }
@@ -253,6 +263,15 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
             }
 
+            // Sampling with residency stores the residency code into a
+            // member of the result, so the result needs a name.
+            if let Expression::ImageSample {
+                residency: true, ..
+            } = *expr
+            {
+                self.need_bake_expressions.insert(exp_handle);
+            }
+
             if let Expression::Derivative { axis, ctrl, expr } = *expr {
                 use crate::{DerivativeAxis as Axis, DerivativeControl as Ctrl};
                 if axis == Axis::Width && (ctrl == Ctrl::Coarse || ctrl == Ctrl::Fine) {
@@ -403,6 +422,19 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             writeln!(self.out)?;
         }
 
+        if let Some(bounds_report) = self.options.bounds_report {
+            let policies = BOUNDS_REPORT_POLICIES;
+            let reporting = proc::reporting_functions(module, module_info, policies);
+            let reports = !reporting.is_empty()
+                || module.entry_points.iter().enumerate().any(|(index, ep)| {
+                    let info = module_info.get_entry_point(index);
+                    proc::function_reports(module, &ep.function, info, policies, &reporting)
+                });
+            if reports {
+                self.write_bounds_report(bounds_report)?;
+            }
+        }
+
         let ep_range = get_entry_points(module, self.pipeline_options.entry_point.as_ref())
             .map_err(|(stage, name)| Error::EntryPointNotFound(stage, name))?;
 
@@ -554,7 +586,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     }
                 }
             }
-            crate::Binding::BuiltIn(_) => {}
+            crate::Binding::BuiltIn(_) | crate::Binding::FramebufferFetch { .. } => {}
         }
 
         Ok(())
@@ -584,6 +616,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     write!(self.out, " : {LOCATION_SEMANTIC}{location}")?;
                 }
             }
+            Some(crate::Binding::FramebufferFetch { .. }) => {
+                return Err(Error::Custom(
+                    "Framebuffer fetch is not supported in HLSL".into(),
+                ));
+            }
             _ => {}
         }
 
@@ -732,7 +769,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             for arg in frag_ep.func.arguments.iter() {
                 let mut push_if_location = |binding: &Option<crate::Binding>| match *binding {
                     Some(crate::Binding::Location { location, .. }) => fs_input_locs.push(location),
-                    Some(crate::Binding::BuiltIn(_)) | None => {}
+                    Some(crate::Binding::BuiltIn(_) | crate::Binding::FramebufferFetch { .. })
+                    | None => {}
                 };
 
                 // NOTE: We don't need to handle struct nesting. See note in
@@ -761,7 +799,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                             continue;
                         }
                     }
-                    Some(crate::Binding::BuiltIn(_)) | None => {}
+                    Some(crate::Binding::BuiltIn(_) | crate::Binding::FramebufferFetch { .. })
+                    | None => {}
                 }
             }
 
@@ -905,6 +944,90 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Write the bounds report buffer and the function recording accesses in it.
+    ///
+    /// Reported indices are passed through this function, which restricts
+    /// them like [`BoundsCheckPolicy::Restrict`] does, and records those it
+    /// had to restrict. See [`BoundsReportOptions`] for the layout of the
+    /// bounds report.
+    ///
+    /// [`BoundsCheckPolicy::Restrict`]: proc::BoundsCheckPolicy::Restrict
+    /// [`BoundsReportOptions`]: proc::BoundsReportOptions
+    fn write_bounds_report(&mut self, options: proc::BoundsReportOptions) -> BackendResult {
+        let capacity = proc::BOUNDS_REPORT_CAPACITY;
+        let shader_id = options.shader_id;
+        let bt = self
+            .options
+            .resolve_resource_binding(&options.binding)
+            .map_err(|err| Error::Custom(format!("bounds report: {err}")))?;
+        write!(
+            self.out,
+            "RWByteAddressBuffer {BOUNDS_REPORT_VARIABLE} : register(u{}",
+            bt.register
+        )?;
+        if bt.space != 0 {
+            write!(self.out, ", space{}", bt.space)?;
+        }
+        writeln!(self.out, ");")?;
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "uint {BOUNDS_REPORT_FUNCTION}(uint index, uint length, uint location)
+{{
+    if (index < length) {{
+        return index;
+    }}
+    uint slot;
+    {BOUNDS_REPORT_VARIABLE}.InterlockedAdd(0, 1u, slot);
+    if (slot < {capacity}u) {{
+        {BOUNDS_REPORT_VARIABLE}.Store4(16u + slot * 16u, uint4({shader_id}u, location, index, length));
+    }}
+    return length - 1u;
+}}"
+        )?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    /// Return the length to check the index of the [`Access`] expression
+    /// `base[index]` against, if [`Options::bounds_report`] reports it.
+    ///
+    /// [`Access`]: crate::Expression::Access
+    pub(super) fn reported_access(
+        &self,
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+        base: Handle<crate::Expression>,
+        index: Handle<crate::Expression>,
+    ) -> Option<index::IndexableLength> {
+        self.options.bounds_report?;
+        proc::reported_access(
+            module,
+            func_ctx.expressions,
+            func_ctx.info,
+            BOUNDS_REPORT_POLICIES,
+            base,
+            index,
+        )
+    }
+
+    /// Write `index`, passed through the function recording the out-of-bounds
+    /// accesses `expr` makes. `length` is the written length of the array.
+    pub(super) fn write_reported_index(
+        &mut self,
+        module: &Module,
+        expr: Handle<crate::Expression>,
+        index: Handle<crate::Expression>,
+        length: &str,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        write!(self.out, "{BOUNDS_REPORT_FUNCTION}(uint(")?;
+        self.write_expr(module, index, func_ctx)?;
+        let location = proc::bounds_report_location(func_ctx.expressions, expr);
+        write!(self.out, "), {length}, {location}u)")?;
+        Ok(())
+    }
+
     /// Helper method used to write global variables
     /// # Notes
     /// Always adds a newline
@@ -1995,7 +2118,14 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
                     if let Some(name) = expr_name {
                         write!(self.out, "{level}")?;
-                        self.write_named_expr(module, handle, name, handle, func_ctx)?;
+                        if let crate::Expression::ImageSample {
+                            residency: true, ..
+                        } = func_ctx.expressions[handle]
+                        {
+                            self.write_sparse_image_sample(module, handle, name, func_ctx, level)?;
+                        } else {
+                            self.write_named_expr(module, handle, name, handle, func_ctx)?;
+                        }
                     }
                 }
             }
@@ -3069,6 +3199,18 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         write!(self.out, "[")?;
                     }
 
+                    if let Some(length) = self.reported_access(module, func_ctx, base, index) {
+                        // Only arrays in storage buffers can have a dynamic
+                        // length, and those are accessed through `temp_access_chain`.
+                        let index::IndexableLength::Known(length) = length else {
+                            unreachable!()
+                        };
+                        let length = format!("{length}u");
+                        self.write_reported_index(module, expr, index, &length, func_ctx)?;
+                        write!(self.out, "]")?;
+                        return Ok(());
+                    }
+
                     let needs_bound_check = self.options.restrict_indexing
                         && !indexing_binding_array
                         && match resolved.pointer_space() {
@@ -3296,6 +3438,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 offset: None,
                 level: crate::SampleLevel::Zero,
                 depth_ref: None,
+                residency: false,
             } => {
                 write!(self.out, "{IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION}(")?;
                 self.write_expr(module, image, func_ctx)?;
@@ -3315,76 +3458,27 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
             } => {
-                if clamp_to_edge {
+                if residency {
                     return Err(Error::Custom(
-                        "ImageSample::clamp_to_edge should have been validated out".to_string(),
+                        "ImageSample::residency should have been baked".to_string(),
                     ));
                 }
-
-                use crate::SampleLevel as Sl;
-                const COMPONENTS: [&str; 4] = ["", "Green", "Blue", "Alpha"];
-
-                let (base_str, component_str) = match gather {
-                    Some(component) => ("Gather", COMPONENTS[component as usize]),
-                    None => ("Sample", ""),
-                };
-                let cmp_str = match depth_ref {
-                    Some(_) => "Cmp",
-                    None => "",
-                };
-                let level_str = match level {
-                    Sl::Zero if gather.is_none() => "LevelZero",
-                    Sl::Auto | Sl::Zero => "",
-                    Sl::Exact(_) => "Level",
-                    Sl::Bias(_) => "Bias",
-                    Sl::Gradient { .. } => "Grad",
-                };
-
-                self.write_expr(module, image, func_ctx)?;
-                write!(self.out, ".{base_str}{cmp_str}{component_str}{level_str}(")?;
-                self.write_expr(module, sampler, func_ctx)?;
-                write!(self.out, ", ")?;
-                self.write_texture_coordinates(
-                    "float",
+                self.write_image_sample(
+                    module,
+                    func_ctx,
+                    image,
+                    sampler,
+                    gather,
                     coordinate,
                     array_index,
+                    offset,
+                    level,
+                    depth_ref,
+                    clamp_to_edge,
                     None,
-                    module,
-                    func_ctx,
                 )?;
-
-                if let Some(depth_ref) = depth_ref {
-                    write!(self.out, ", ")?;
-                    self.write_expr(module, depth_ref, func_ctx)?;
-                }
-
-                match level {
-                    Sl::Auto | Sl::Zero => {}
-                    Sl::Exact(expr) => {
-                        write!(self.out, ", ")?;
-                        self.write_expr(module, expr, func_ctx)?;
-                    }
-                    Sl::Bias(expr) => {
-                        write!(self.out, ", ")?;
-                        self.write_expr(module, expr, func_ctx)?;
-                    }
-                    Sl::Gradient { x, y } => {
-                        write!(self.out, ", ")?;
-                        self.write_expr(module, x, func_ctx)?;
-                        write!(self.out, ", ")?;
-                        self.write_expr(module, y, func_ctx)?;
-                    }
-                }
-
-                if let Some(offset) = offset {
-                    write!(self.out, ", ")?;
-                    write!(self.out, "int2(")?; // work around https://github.com/microsoft/DirectXShaderCompiler/issues/5082#issuecomment-1540147807
-                    self.write_const_expression(module, offset, func_ctx.expressions)?;
-                    write!(self.out, ")")?;
-                }
-
-                write!(self.out, ")")?;
             }
             Expression::ImageQuery { image, query } => {
                 // use wrapped image query function
@@ -4181,26 +4275,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     _ => unreachable!(),
                 };
 
-                let var = &module.global_variables[var_handle];
-                let (offset, stride) = match module.types[var.ty].inner {
-                    TypeInner::Array { stride, .. } => (0, stride),
-                    TypeInner::Struct { ref members, .. } => {
-                        let last = members.last().unwrap();
-                        let stride = match module.types[last.ty].inner {
-                            TypeInner::Array { stride, .. } => stride,
-                            _ => unreachable!(),
-                        };
-                        (last.offset, stride)
-                    }
-                    _ => unreachable!(),
-                };
-
-                let storage_access = match var.space {
-                    crate::AddressSpace::Storage { access } => access,
-                    _ => crate::StorageAccess::default(),
-                };
+                let (offset, stride) = storage::runtime_array_layout(module, var_handle);
                 let wrapped_array_length = WrappedArrayLength {
-                    writable: storage_access.contains(crate::StorageAccess::STORE),
+                    writable: storage::is_writable(module, var_handle),
                 };
 
                 write!(self.out, "((")?;
@@ -4245,6 +4322,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Rf::Any => "any",
                     Rf::IsNan => "isnan",
                     Rf::IsInf => "isinf",
+                    Rf::IsResident => "CheckAccessFullyMapped",
                 };
                 write!(self.out, "{fun_str}(")?;
                 self.write_expr(module, argument, func_ctx)?;
@@ -4405,6 +4483,158 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         None
     }
 
+    /// Write a call sampling `image`, for an [`ImageSample`] expression.
+    ///
+    /// If `residency_code` is given, the overload storing the residency code
+    /// into it is called.
+    ///
+    /// [`ImageSample`]: crate::Expression::ImageSample
+    #[allow(clippy::too_many_arguments)]
+    fn write_image_sample(
+        &mut self,
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+        image: Handle<crate::Expression>,
+        sampler: Handle<crate::Expression>,
+        gather: Option<crate::SwizzleComponent>,
+        coordinate: Handle<crate::Expression>,
+        array_index: Option<Handle<crate::Expression>>,
+        offset: Option<Handle<crate::Expression>>,
+        level: crate::SampleLevel,
+        depth_ref: Option<Handle<crate::Expression>>,
+        clamp_to_edge: bool,
+        residency_code: Option<&str>,
+    ) -> BackendResult {
+        if clamp_to_edge {
+            return Err(Error::Custom(
+                "ImageSample::clamp_to_edge should have been validated out".to_string(),
+            ));
+        }
+
+        use crate::SampleLevel as Sl;
+        const COMPONENTS: [&str; 4] = ["", "Green", "Blue", "Alpha"];
+
+        let (base_str, component_str) = match gather {
+            Some(component) => ("Gather", COMPONENTS[component as usize]),
+            None => ("Sample", ""),
+        };
+        let cmp_str = match depth_ref {
+            Some(_) => "Cmp",
+            None => "",
+        };
+        let level_str = match level {
+            Sl::Zero if gather.is_none() => "LevelZero",
+            Sl::Auto | Sl::Zero => "",
+            Sl::Exact(_) => "Level",
+            Sl::Bias(_) => "Bias",
+            Sl::Gradient { .. } => "Grad",
+        };
+
+        self.write_expr(module, image, func_ctx)?;
+        write!(self.out, ".{base_str}{cmp_str}{component_str}{level_str}(")?;
+        self.write_expr(module, sampler, func_ctx)?;
+        write!(self.out, ", ")?;
+        self.write_texture_coordinates("float", coordinate, array_index, None, module, func_ctx)?;
+
+        if let Some(depth_ref) = depth_ref {
+            write!(self.out, ", ")?;
+            self.write_expr(module, depth_ref, func_ctx)?;
+        }
+
+        match level {
+            Sl::Auto | Sl::Zero => {}
+            Sl::Exact(expr) => {
+                write!(self.out, ", ")?;
+                self.write_expr(module, expr, func_ctx)?;
+            }
+            Sl::Bias(expr) => {
+                write!(self.out, ", ")?;
+                self.write_expr(module, expr, func_ctx)?;
+            }
+            Sl::Gradient { x, y } => {
+                write!(self.out, ", ")?;
+                self.write_expr(module, x, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, y, func_ctx)?;
+            }
+        }
+
+        if let Some(offset) = offset {
+            write!(self.out, ", ")?;
+            write!(self.out, "int2(")?; // work around https://github.com/microsoft/DirectXShaderCompiler/issues/5082#issuecomment-1540147807
+            self.write_const_expression(module, offset, func_ctx.expressions)?;
+            write!(self.out, ")")?;
+        } else if residency_code.is_some() {
+            write!(self.out, ", int2(0, 0)")?;
+        }
+
+        // The overloads returning the residency code also take the minimum
+        // level of detail to clamp to, unless the level is explicit.
+        if let Some(residency_code) = residency_code {
+            if !matches!(level, Sl::Zero | Sl::Exact(_)) {
+                write!(self.out, ", 0.0")?;
+            }
+            write!(self.out, ", {residency_code}")?;
+        }
+
+        write!(self.out, ")")?;
+
+        Ok(())
+    }
+
+    /// Write an [`ImageSample`] expression with [`residency`] as a local
+    /// named `name`, whose texel is set by the sampling call.
+    ///
+    /// [`ImageSample`]: crate::Expression::ImageSample
+    /// [`residency`]: crate::Expression::ImageSample::residency
+    fn write_sparse_image_sample(
+        &mut self,
+        module: &Module,
+        handle: Handle<crate::Expression>,
+        name: String,
+        func_ctx: &back::FunctionCtx,
+        level: back::Level,
+    ) -> BackendResult {
+        let crate::Expression::ImageSample {
+            image,
+            sampler,
+            gather,
+            coordinate,
+            array_index,
+            offset,
+            level: sample_level,
+            depth_ref,
+            clamp_to_edge,
+            residency: true,
+        } = func_ctx.expressions[handle]
+        else {
+            unreachable!();
+        };
+        let ty_handle = func_ctx.info[handle].ty.handle().unwrap();
+        let ty_name = &self.names[&NameKey::Type(ty_handle)];
+
+        writeln!(self.out, "{ty_name} {name};")?;
+        write!(self.out, "{level}{name}.texel = ")?;
+        self.write_image_sample(
+            module,
+            func_ctx,
+            image,
+            sampler,
+            gather,
+            coordinate,
+            array_index,
+            offset,
+            sample_level,
+            depth_ref,
+            clamp_to_edge,
+            Some(&format!("{name}.residency_code")),
+        )?;
+        writeln!(self.out, ";")?;
+        self.named_expressions.insert(handle, name);
+
+        Ok(())
+    }
+
     fn write_named_expr(
         &mut self,
         module: &Module,
@@ -358,6 +358,8 @@ pub const RESERVED: &[&str] = &[
     super::writer::IMAGE_SIZE_EXTERNAL_FUNCTION,
     super::writer::ARGUMENT_BUFFER_WRAPPER_STRUCT,
     super::writer::EXTERNAL_TEXTURE_WRAPPER_STRUCT,
+    super::writer::BOUNDS_REPORT_STRUCT,
+    super::writer::BOUNDS_REPORT_FUNCTION,
 ];
 
 /// The above set of reserved keywords, turned into a cached HashSet. This saves
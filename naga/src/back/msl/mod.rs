@@ -302,6 +302,16 @@ pub struct Options {
     /// If set, loops will have code injected into them, forcing the compiler
     /// to think the number of iterations is bounded.
     pub force_loop_bounding: bool,
+    /// Where to record out-of-bounds accesses made under
+    /// [`BoundsCheckPolicy::Report`].
+    ///
+    /// The bounds report is passed to entry points like a storage buffer
+    /// global with this binding, and must be in [`per_entry_point_map`].
+    /// Writing a module under that policy without setting this is an error.
+    ///
+    /// [`BoundsCheckPolicy::Report`]: index::BoundsCheckPolicy::Report
+    /// [`per_entry_point_map`]: Options::per_entry_point_map
+    pub bounds_report: Option<crate::proc::BoundsReportOptions>,
 }
 
 impl Default for Options {
@@ -315,6 +325,7 @@ impl Default for Options {
             bounds_check_policies: index::BoundsCheckPolicies::default(),
             zero_initialize_workgroup_memory: true,
             force_loop_bounding: true,
+            bounds_report: None,
         }
     }
 }
@@ -564,6 +575,15 @@ impl Options {
                     "Unexpected Binding::Location({location}) for the Uniform mode"
                 ))),
             },
+            crate::Binding::FramebufferFetch { location } => match mode {
+                LocationMode::FragmentInput => Ok(ResolvedBinding::Color {
+                    location,
+                    blend_src: None,
+                }),
+                _ => Err(Error::GenericValidation(format!(
+                    "Unexpected Binding::FramebufferFetch({location}) outside of fragment inputs"
+                ))),
+            },
         }
     }
 
@@ -685,9 +705,12 @@ impl ResolvedBinding {
                     Bi::SubgroupId => "simdgroup_index_in_threadgroup",
                     Bi::SubgroupSize => "threads_per_simdgroup",
                     Bi::SubgroupInvocationId => "thread_index_in_simdgroup",
-                    Bi::CullDistance | Bi::ViewIndex | Bi::DrawID => {
-                        return Err(Error::UnsupportedBuiltIn(built_in))
-                    }
+                    // Metal can only read color attachments.
+                    Bi::CullDistance
+                    | Bi::ViewIndex
+                    | Bi::DrawID
+                    | Bi::LastFragDepth
+                    | Bi::LastFragStencil => return Err(Error::UnsupportedBuiltIn(built_in)),
                 };
                 write!(out, "{name}")?;
             }
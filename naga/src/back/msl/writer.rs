@@ -78,6 +78,13 @@ pub(crate) const ARGUMENT_BUFFER_WRAPPER_STRUCT: &str = "NagaArgumentBufferWrapp
 /// allowing them to be conveniently passed to user-defined or wrapper
 /// functions. The struct is declared in [`Writer::write_type_defs`].
 pub(crate) const EXTERNAL_TEXTURE_WRAPPER_STRUCT: &str = "NagaExternalTextureWrapper";
+/// Name of the struct holding the bounds report, for
+/// [`crate::proc::BoundsCheckPolicy::Report`]. The struct and the function
+/// recording accesses in it are declared in [`Writer::put_bounds_report`].
+pub(crate) const BOUNDS_REPORT_STRUCT: &str = "NagaBoundsReport";
+pub(crate) const BOUNDS_REPORT_FUNCTION: &str = "naga_bounds_report";
+/// Name of the argument passing the bounds report to the functions that use it.
+const BOUNDS_REPORT_ARGUMENT: &str = "_bounds_report";
 
 /// Write the Metal name for a Naga numeric type: scalar, vector, or matrix.
 ///
@@ -727,6 +734,9 @@ struct ExpressionContext<'a> {
     guarded_indices: HandleSet<crate::Expression>,
     /// See [`Writer::gen_force_bounded_loop_statements`] for details.
     force_loop_bounding: bool,
+    /// The functions that take the bounds report as an argument, see
+    /// [`proc::reporting_functions`].
+    reporting_functions: &'a HandleSet<crate::Function>,
 }
 
 impl<'a> ExpressionContext<'a> {
@@ -1362,7 +1372,7 @@ impl<W: Write> Writer<W> {
         }
 
         match context.policies.image_load {
-            proc::BoundsCheckPolicy::Restrict => {
+            proc::BoundsCheckPolicy::Restrict | proc::BoundsCheckPolicy::Report => {
                 // Use the cached restricted level of detail, if any. Omit the
                 // level altogether for 1D textures.
                 if address.level.is_some() {
@@ -1996,6 +2006,7 @@ impl<W: Write> Writer<W> {
                 offset: None,
                 level: crate::SampleLevel::Zero,
                 depth_ref: None,
+                residency: false,
             } => {
                 write!(self.out, "{IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION}(")?;
                 self.put_expression(image, context, true)?;
@@ -2015,12 +2026,18 @@ impl<W: Write> Writer<W> {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
             } => {
                 if clamp_to_edge {
                     return Err(Error::GenericValidation(
                         "ImageSample::clamp_to_edge should have been validated out".to_string(),
                     ));
                 }
+                if residency {
+                    return Err(Error::CapabilityNotSupported(
+                        valid::Capabilities::SPARSE_RESIDENCY,
+                    ));
+                }
 
                 let main_op = match gather {
                     Some(_) => "gather",
@@ -2269,6 +2286,11 @@ impl<W: Write> Writer<W> {
                     crate::RelationalFunction::All => "all",
                     crate::RelationalFunction::IsNan => "isnan",
                     crate::RelationalFunction::IsInf => "isinf",
+                    crate::RelationalFunction::IsResident => {
+                        return Err(Error::CapabilityNotSupported(
+                            valid::Capabilities::SPARSE_RESIDENCY,
+                        ))
+                    }
                 };
                 write!(self.out, "{NAMESPACE}::{op}")?;
                 self.put_call_parameters(iter::once(argument), context)?;
@@ -3056,6 +3078,7 @@ impl<W: Write> Writer<W> {
                 }
 
                 self.put_subscripted_access_chain(
+                    chain,
                     base,
                     base_ty,
                     index::GuardedIndex::Expression(index),
@@ -3096,6 +3119,7 @@ impl<W: Write> Writer<W> {
                     }
                     _ => {
                         self.put_subscripted_access_chain(
+                            chain,
                             base,
                             base_ty,
                             index::GuardedIndex::Known(index),
@@ -3111,10 +3135,11 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
-    /// Write a `[]`-style access of `base` by `index`.
+    /// Write a `[]`-style access of `base` by `index`, for the expression `access`.
     ///
     /// If `policy` is [`Restrict`], then generate code as needed to force all index
-    /// values within bounds.
+    /// values within bounds. If it is [`Report`], also pass the index values that
+    /// need it through the [`BOUNDS_REPORT_FUNCTION`].
     ///
     /// The `base_ty` argument must be the type we are actually indexing, like [`Array`] or
     /// [`Vector`]. In other words, it's `base`'s type with any surrounding [`Pointer`]
@@ -3124,11 +3149,13 @@ impl<W: Write> Writer<W> {
     /// referencing vector components by name.
     ///
     /// [`Restrict`]: crate::proc::index::BoundsCheckPolicy::Restrict
+    /// [`Report`]: crate::proc::index::BoundsCheckPolicy::Report
     /// [`Array`]: crate::TypeInner::Array
     /// [`Vector`]: crate::TypeInner::Vector
     /// [`Pointer`]: crate::TypeInner::Pointer
     fn put_subscripted_access_chain(
         &mut self,
+        access: Handle<crate::Expression>,
         base: Handle<crate::Expression>,
         base_ty: &crate::TypeInner,
         index: index::GuardedIndex,
@@ -3152,16 +3179,38 @@ impl<W: Write> Writer<W> {
         write!(self.out, "[")?;
 
         // Decide whether this index needs to be clamped to fall within range.
-        let restriction_needed = if policy == index::BoundsCheckPolicy::Restrict {
-            context.access_needs_check(base, index)
-        } else {
-            None
+        let restriction_needed = match policy {
+            index::BoundsCheckPolicy::Restrict | index::BoundsCheckPolicy::Report => {
+                context.access_needs_check(base, index)
+            }
+            _ => None,
+        };
+        // Decide whether the index also needs to be reported.
+        let reported = match index {
+            index::GuardedIndex::Expression(index) => proc::reported_access(
+                context.module,
+                &context.function.expressions,
+                context.info,
+                context.policies,
+                base,
+                index,
+            )
+            .is_some(),
+            index::GuardedIndex::Known(_) => false,
         };
         if let Some(limit) = restriction_needed {
-            write!(self.out, "{NAMESPACE}::min(unsigned(")?;
+            if reported {
+                write!(self.out, "{BOUNDS_REPORT_FUNCTION}(unsigned(")?;
+            } else {
+                write!(self.out, "{NAMESPACE}::min(unsigned(")?;
+            }
             self.put_index(index, context, true)?;
             write!(self.out, "), ")?;
+            // The bounds report function takes the length, not the max index.
             match limit {
+                index::IndexableLength::Known(limit) if reported => {
+                    write!(self.out, "{limit}u")?;
+                }
                 index::IndexableLength::Known(limit) => {
                     write!(self.out, "{}u", limit - 1)?;
                 }
@@ -3170,8 +3219,15 @@ impl<W: Write> Writer<W> {
                         Error::GenericValidation("Could not find originating global".into())
                     })?;
                     self.put_dynamic_array_max_index(global, context)?;
+                    if reported {
+                        write!(self.out, " + 1u")?;
+                    }
                 }
             }
+            if reported {
+                let location = proc::bounds_report_location(&context.function.expressions, access);
+                write!(self.out, ", {location}u, {BOUNDS_REPORT_ARGUMENT}")?;
+            }
             write!(self.out, ")")?;
         } else {
             self.put_index(index, context, true)?;
@@ -3499,8 +3555,10 @@ impl<W: Write> Writer<W> {
             None => return Ok(()),
         };
 
-        if context.expression.policies.image_load != index::BoundsCheckPolicy::Restrict
-            || !context.expression.image_needs_lod(image)
+        if !matches!(
+            context.expression.policies.image_load,
+            index::BoundsCheckPolicy::Restrict | index::BoundsCheckPolicy::Report
+        ) || !context.expression.image_needs_lod(image)
         {
             return Ok(());
         }
@@ -3833,6 +3891,14 @@ impl<W: Write> Writer<W> {
                         needs_buffer_sizes |=
                             needs_array_length(var.ty, &context.expression.module.types);
                     }
+                    if context.expression.reporting_functions.contains(function) {
+                        if separate {
+                            write!(self.out, ", ")?;
+                        } else {
+                            separate = true;
+                        }
+                        write!(self.out, "{BOUNDS_REPORT_ARGUMENT}")?;
+                    }
                     if needs_buffer_sizes {
                         if separate {
                             write!(self.out, ", ")?;
@@ -4333,6 +4399,26 @@ impl<W: Write> Writer<W> {
         {
             self.put_default_constructible()?;
         }
+        let reporting_functions =
+            proc::reporting_functions(module, info, options.bounds_check_policies);
+        let reports_bounds = !reporting_functions.is_empty()
+            || module.entry_points.iter().enumerate().any(|(index, ep)| {
+                proc::function_reports(
+                    module,
+                    &ep.function,
+                    info.get_entry_point(index),
+                    options.bounds_check_policies,
+                    &reporting_functions,
+                )
+            });
+        if reports_bounds {
+            let Some(bounds_report) = options.bounds_report else {
+                return Err(Error::GenericValidation(
+                    "the `Report` bounds check policy requires `bounds_report` to be set".into(),
+                ));
+            };
+            self.put_bounds_report(bounds_report)?;
+        }
         writeln!(self.out)?;
 
         {
@@ -4373,7 +4459,13 @@ impl<W: Write> Writer<W> {
 
         self.write_type_defs(module)?;
         self.write_global_constants(module, info)?;
-        self.write_functions(module, info, options, pipeline_options)
+        self.write_functions(
+            module,
+            info,
+            options,
+            pipeline_options,
+            &reporting_functions,
+        )
     }
 
     /// Write the definition for the `DefaultConstructible` class.
@@ -4399,6 +4491,49 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Write the definitions for the bounds report struct and the function
+    /// recording accesses in it.
+    ///
+    /// Under the [`Report`] bounds check policy, reported indices are passed
+    /// through this function, which restricts them like the [`Restrict`]
+    /// policy does, and records those it had to restrict. See
+    /// [`BoundsReportOptions`] for the layout of the bounds report.
+    ///
+    /// [`Report`]: index::BoundsCheckPolicy::Report
+    /// [`Restrict`]: index::BoundsCheckPolicy::Restrict
+    /// [`BoundsReportOptions`]: proc::BoundsReportOptions
+    fn put_bounds_report(&mut self, options: proc::BoundsReportOptions) -> BackendResult {
+        let tab = back::INDENT;
+        let capacity = proc::BOUNDS_REPORT_CAPACITY;
+        let shader_id = options.shader_id;
+        writeln!(self.out, "struct {BOUNDS_REPORT_STRUCT} {{")?;
+        writeln!(self.out, "{tab}{NAMESPACE}::atomic_uint count;")?;
+        writeln!(self.out, "{tab}{NAMESPACE}::uint4 records[{capacity}];")?;
+        writeln!(self.out, "}};")?;
+        writeln!(self.out, "uint {BOUNDS_REPORT_FUNCTION}(")?;
+        writeln!(self.out, "{tab}uint index,")?;
+        writeln!(self.out, "{tab}uint length,")?;
+        writeln!(self.out, "{tab}uint location,")?;
+        writeln!(self.out, "{tab}device {BOUNDS_REPORT_STRUCT}& report")?;
+        writeln!(self.out, ") {{")?;
+        writeln!(self.out, "{tab}if (index < length) {{")?;
+        writeln!(self.out, "{tab}{tab}return index;")?;
+        writeln!(self.out, "{tab}}}")?;
+        writeln!(
+            self.out,
+            "{tab}uint slot = {NAMESPACE}::atomic_fetch_add_explicit(&report.count, 1u, {NAMESPACE}::memory_order_relaxed);"
+        )?;
+        writeln!(self.out, "{tab}if (slot < {capacity}u) {{")?;
+        writeln!(
+            self.out,
+            "{tab}{tab}report.records[slot] = {NAMESPACE}::uint4({shader_id}u, location, index, length);"
+        )?;
+        writeln!(self.out, "{tab}}}")?;
+        writeln!(self.out, "{tab}return length - 1u;")?;
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
     fn put_ray_query_type(&mut self) -> BackendResult {
         let tab = back::INDENT;
         writeln!(self.out, "struct {RAY_QUERY_TYPE} {{")?;
@@ -4667,6 +4802,7 @@ template <typename A>
                         )?;
                     }
                 }
+                &crate::PredeclaredType::SparseSampleResult(_) => {}
             }
         }
 
@@ -6311,6 +6447,7 @@ template <typename A>
                     level,
                     depth_ref,
                     clamp_to_edge,
+                    residency: _,
                 } => {
                     self.write_wrapped_image_sample(
                         module,
@@ -6343,6 +6480,7 @@ template <typename A>
         mod_info: &valid::ModuleInfo,
         options: &Options,
         pipeline_options: &PipelineOptions,
+        reporting_functions: &HandleSet<crate::Function>,
     ) -> Result<TranslationInfo, Error> {
         use back::msl::VertexFormat;
 
@@ -6459,6 +6597,7 @@ template <typename A>
 
             let fun_info = &mod_info[fun_handle];
             pass_through_globals.clear();
+            let reports_bounds = reporting_functions.contains(fun_handle);
             let mut needs_buffer_sizes = false;
             for (handle, var) in module.global_variables.iter() {
                 if !fun_info[handle].is_empty() {
@@ -6499,6 +6638,7 @@ template <typename A>
                 let separator = separate(
                     !pass_through_globals.is_empty()
                         || index + 1 != fun.arguments.len()
+                        || reports_bounds
                         || needs_buffer_sizes,
                 );
                 writeln!(
@@ -6519,13 +6659,23 @@ template <typename A>
 
                     reference: true,
                 };
-                let separator =
-                    separate(index + 1 != pass_through_globals.len() || needs_buffer_sizes);
+                let separator = separate(
+                    index + 1 != pass_through_globals.len() || reports_bounds || needs_buffer_sizes,
+                );
                 write!(self.out, "{}", back::INDENT)?;
                 tyvar.try_fmt(&mut self.out)?;
                 writeln!(self.out, "{separator}")?;
             }
 
+            if reports_bounds {
+                writeln!(
+                    self.out,
+                    "{}device {BOUNDS_REPORT_STRUCT}& {BOUNDS_REPORT_ARGUMENT}{}",
+                    back::INDENT,
+                    separate(needs_buffer_sizes)
+                )?;
+            }
+
             if needs_buffer_sizes {
                 writeln!(
                     self.out,
@@ -6551,6 +6701,7 @@ template <typename A>
                     mod_info,
                     pipeline_options,
                     force_loop_bounding: options.force_loop_bounding,
+                    reporting_functions,
                 },
                 result_struct: None,
             };
@@ -6631,6 +6782,17 @@ template <typename A>
                     .filter(|&(handle, _)| !fun_info[handle].is_empty())
                     .any(|(_, var)| needs_array_length(var.ty, &module.types));
 
+            // Does this entry point record out-of-bounds accesses?
+            let bounds_report = options.bounds_report.filter(|_| {
+                proc::function_reports(
+                    module,
+                    fun,
+                    fun_info,
+                    options.bounds_check_policies,
+                    reporting_functions,
+                )
+            });
+
             // skip this entry point if any global bindings are missing,
             // or their types are incompatible.
             if !options.fake_missing_bindings {
@@ -6692,6 +6854,13 @@ template <typename A>
                         ep_error = Some(err);
                     }
                 }
+                if let Some(ref bounds_report) = bounds_report {
+                    let br = &bounds_report.binding;
+                    match options.get_resource_binding_target(ep, br) {
+                        Some(target) if target.buffer.is_some() => {}
+                        _ => ep_error = Some(super::EntryPointError::MissingBindTarget(*br)),
+                    }
+                }
             }
 
             if let Some(err) = ep_error {
@@ -6918,7 +7087,10 @@ template <typename A>
             // struct.
             for &(ref name_key, ty, binding) in flattened_arguments.iter() {
                 let binding = match binding {
-                    Some(binding @ &crate::Binding::BuiltIn { .. }) => binding,
+                    Some(
+                        binding @ &(crate::Binding::BuiltIn { .. }
+                        | crate::Binding::FramebufferFetch { .. }),
+                    ) => binding,
                     _ => continue,
                 };
                 let name = match *name_key {
@@ -7231,6 +7403,24 @@ template <typename A>
                 }
             }
 
+            // The bounds report is passed like a storage buffer global.
+            if let Some(ref bounds_report) = bounds_report {
+                let separator = if is_first_argument {
+                    is_first_argument = false;
+                    ' '
+                } else {
+                    ','
+                };
+                write!(
+                    self.out,
+                    "{separator} device {BOUNDS_REPORT_STRUCT}& {BOUNDS_REPORT_ARGUMENT}"
+                )?;
+                if let Ok(resolved) = options.resolve_resource_binding(ep, &bounds_report.binding) {
+                    resolved.try_fmt(&mut self.out)?;
+                }
+                writeln!(self.out)?;
+            }
+
             // If this entry uses any variable-length arrays, their sizes are
             // passed as a final struct-typed argument.
             if needs_buffer_sizes {
@@ -7556,6 +7746,7 @@ template <typename A>
                     mod_info,
                     pipeline_options,
                     force_loop_bounding: options.force_loop_bounding,
+                    reporting_functions,
                 },
                 result_struct: Some(&stage_out_name),
             };
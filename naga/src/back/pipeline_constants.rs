@@ -478,6 +478,7 @@ fn adjust_expr(new_pos: &HandleVec<Expression, Handle<Expression>>, expr: &mut E
             ref mut depth_ref,
             gather: _,
             clamp_to_edge: _,
+            residency: _,
         } => {
             adjust(image);
             adjust(sampler);
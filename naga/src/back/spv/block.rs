@@ -1662,6 +1662,7 @@ impl BlockContext<'_> {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
             } => self.write_image_sample(
                 result_type_id,
                 image,
@@ -1673,6 +1674,7 @@ impl BlockContext<'_> {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
                 block,
             )?,
             crate::Expression::Select {
@@ -1762,6 +1764,13 @@ impl BlockContext<'_> {
                     Rf::Any => spirv::Op::Any,
                     Rf::IsNan => spirv::Op::IsNan,
                     Rf::IsInf => spirv::Op::IsInf,
+                    Rf::IsResident => {
+                        self.writer.require_any(
+                            "sparse residency",
+                            &[spirv::Capability::SparseResidency],
+                        )?;
+                        spirv::Op::ImageSparseTexelsResident
+                    }
                 };
                 let id = self.gen_id();
                 block
@@ -2119,8 +2128,13 @@ impl BlockContext<'_> {
                         self.is_nonuniform_binding_array_access(base, index);
 
                     let index = GuardedIndex::Expression(index);
-                    let index_id =
-                        self.write_access_chain_index(base, index, &mut accumulated_checks, block)?;
+                    let index_id = self.write_access_chain_index(
+                        expr_handle,
+                        base,
+                        index,
+                        &mut accumulated_checks,
+                        block,
+                    )?;
                     self.temp_list.push(index_id);
 
                     base
@@ -2142,6 +2156,7 @@ impl BlockContext<'_> {
                         // may be a runtime-sized array, so we still need to go
                         // through the bounds check process.
                         self.write_access_chain_index(
+                            expr_handle,
                             base,
                             GuardedIndex::Known(index),
                             &mut accumulated_checks,
@@ -2226,21 +2241,22 @@ impl BlockContext<'_> {
 
     /// Compute a single index operand to an `OpAccessChain` instruction.
     ///
-    /// Given that we are indexing `base` with `index`, apply the appropriate
-    /// bounds check policies, emitting code to `block` to clamp `index` or
-    /// determine whether it's in bounds. Return the SPIR-V instruction id of
-    /// the index value we should actually use.
+    /// Given that the expression `access` indexes `base` with `index`, apply
+    /// the appropriate bounds check policies, emitting code to `block` to clamp
+    /// `index` or determine whether it's in bounds. Return the SPIR-V
+    /// instruction id of the index value we should actually use.
     ///
     /// Extend `accumulated_checks` to include the results of any needed bounds
     /// checks. See [`BlockContext::extend_bounds_check_condition_chain`].
     fn write_access_chain_index(
         &mut self,
+        access: Handle<crate::Expression>,
         base: Handle<crate::Expression>,
         index: GuardedIndex,
         accumulated_checks: &mut Option<Word>,
         block: &mut Block,
     ) -> Result<Word, Error> {
-        match self.write_bounds_check(base, index, block)? {
+        match self.write_bounds_check(access, base, index, block)? {
             BoundsCheckResult::KnownInBounds(known_index) => {
                 // Even if the index is known, `OpAccessChain`
                 // requires expression operands, not literals.
@@ -745,7 +745,7 @@ impl BlockContext<'_> {
 
         // Perform the access, according to the bounds check policy.
         let access_id = match self.writer.bounds_check_policies.image_load {
-            crate::proc::BoundsCheckPolicy::Restrict => {
+            crate::proc::BoundsCheckPolicy::Restrict | crate::proc::BoundsCheckPolicy::Report => {
                 let (coords, level_id, sample_id) = self.write_restricted_coordinates(
                     image_id,
                     coordinates,
@@ -812,6 +812,7 @@ impl BlockContext<'_> {
         level: crate::SampleLevel,
         depth_ref: Option<Handle<crate::Expression>>,
         clamp_to_edge: bool,
+        residency: bool,
         block: &mut Block,
     ) -> Result<Word, Error> {
         use super::instructions::SampleLod;
@@ -1092,6 +1093,20 @@ impl BlockContext<'_> {
             main_instruction.add_operand(offset_id);
         }
 
+        // The sparse variants take the same operands, and return the residency
+        // code along with the texel, in the `SparseSampleResult` struct.
+        if residency {
+            self.writer
+                .require_any("sparse residency", &[spirv::Capability::SparseResidency])?;
+            main_instruction.op = match main_instruction.op {
+                spirv::Op::ImageSampleImplicitLod => spirv::Op::ImageSparseSampleImplicitLod,
+                spirv::Op::ImageSampleExplicitLod => spirv::Op::ImageSparseSampleExplicitLod,
+                _ => return Err(Error::Validation(
+                    "ImageSample::residency requires sampling without gather or depth comparison",
+                )),
+            };
+        }
+
         block.body.push(main_instruction);
 
         let id = if needs_sub_access {
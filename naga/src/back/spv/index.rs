@@ -5,7 +5,7 @@ Bounds-checking for SPIR-V output.
 use super::{
     helpers::{global_needs_wrapper, map_storage_class},
     selection::Selection,
-    Block, BlockContext, Error, IdGenerator, Instruction, Word,
+    Block, BlockContext, Error, IdGenerator, Instruction, NumericType, Word,
 };
 use crate::{
     arena::Handle,
@@ -376,6 +376,130 @@ impl BlockContext<'_> {
         Ok(BoundsCheckResult::Computed(restricted_index_id))
     }
 
+    /// Record an out-of-bounds index in the bounds report.
+    ///
+    /// This is used to implement [`BoundsCheckPolicy::Report`], along with
+    /// [`write_restricted_index`]. Add instructions to `block` that, if
+    /// `index` is not less than the length of `sequence`, count the `access`
+    /// and record it if the bounds report has room left.
+    ///
+    /// [`write_restricted_index`]: BlockContext::write_restricted_index
+    fn write_bounds_report(
+        &mut self,
+        access: Handle<crate::Expression>,
+        sequence: Handle<crate::Expression>,
+        index: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let (Some(variable_id), Some(options)) = (
+            self.writer.bounds_report_variable,
+            self.writer.bounds_report,
+        ) else {
+            return Err(Error::Validation("bounds report variable"));
+        };
+
+        let u32_type_id = self.writer.get_u32_type_id();
+        let length_id = match self.write_sequence_length(sequence, block)? {
+            MaybeKnown::Known(value) => self.get_index_constant(value),
+            MaybeKnown::Computed(id) => id,
+        };
+        let mut index_id = self.cached[index];
+        let index_inner = self.fun_info[index].ty.inner_with(&self.ir_module.types);
+        if index_inner.scalar_kind() == Some(crate::ScalarKind::Sint) {
+            let unsigned_id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                u32_type_id,
+                unsigned_id,
+                index_id,
+            ));
+            index_id = unsigned_id;
+        }
+
+        let out_of_bounds_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::UGreaterThanEqual,
+            self.writer.get_bool_type_id(),
+            out_of_bounds_id,
+            index_id,
+            length_id,
+        ));
+
+        let mut selection = Selection::start(block, ());
+        selection.if_true(self, out_of_bounds_id, ());
+
+        // Count the access, and find out where to record it.
+        let count_pointer_type_id =
+            self.get_pointer_type_id(u32_type_id, spirv::StorageClass::StorageBuffer);
+        let count_pointer_id = self.gen_id();
+        let zero_id = self.get_index_constant(0);
+        selection.block().body.push(Instruction::access_chain(
+            count_pointer_type_id,
+            count_pointer_id,
+            variable_id,
+            &[zero_id],
+        ));
+        let scope_id = self.get_scope_constant(spirv::Scope::Device as Word);
+        let semantics_id = self.get_index_constant(spirv::MemorySemantics::empty().bits());
+        let one_id = self.get_index_constant(1);
+        let slot_id = self.gen_id();
+        selection.block().body.push(Instruction::atomic_binary(
+            spirv::Op::AtomicIAdd,
+            u32_type_id,
+            slot_id,
+            count_pointer_id,
+            scope_id,
+            semantics_id,
+            one_id,
+        ));
+        let capacity_id = self.get_index_constant(crate::proc::BOUNDS_REPORT_CAPACITY);
+        let has_room_id = self.gen_id();
+        selection.block().body.push(Instruction::binary(
+            spirv::Op::ULessThan,
+            self.writer.get_bool_type_id(),
+            has_room_id,
+            slot_id,
+            capacity_id,
+        ));
+        selection.if_true(self, has_room_id, ());
+
+        // Record the access.
+        let record_type_id = self.get_numeric_type_id(NumericType::Vector {
+            size: crate::VectorSize::Quad,
+            scalar: crate::Scalar::U32,
+        });
+        let record_pointer_type_id =
+            self.get_pointer_type_id(record_type_id, spirv::StorageClass::StorageBuffer);
+        let record_pointer_id = self.gen_id();
+        selection.block().body.push(Instruction::access_chain(
+            record_pointer_type_id,
+            record_pointer_id,
+            variable_id,
+            &[one_id, slot_id],
+        ));
+        let shader_id = self.get_index_constant(options.shader_id);
+        let location_id = self.get_index_constant(crate::proc::bounds_report_location(
+            &self.ir_function.expressions,
+            access,
+        ));
+        let record_id = self.gen_id();
+        selection
+            .block()
+            .body
+            .push(Instruction::composite_construct(
+                record_type_id,
+                record_id,
+                &[shader_id, location_id, index_id, length_id],
+            ));
+        selection
+            .block()
+            .body
+            .push(Instruction::store(record_pointer_id, record_id, None));
+
+        selection.finish(self, ());
+        Ok(())
+    }
+
     /// Write an index bounds comparison to `block`, if needed.
     ///
     /// This is used to implement [`BoundsCheckPolicy::ReadZeroSkipWrite`].
@@ -502,16 +626,31 @@ impl BlockContext<'_> {
     ///     - restrict its value (for [`BoundsCheckPolicy::Restrict`]), or
     ///
     ///     - check whether it's in bounds (for
-    ///       [`BoundsCheckPolicy::ReadZeroSkipWrite`]).
+    ///       [`BoundsCheckPolicy::ReadZeroSkipWrite`]), or
+    ///
+    ///     - restrict its value and record it if it was out of bounds (for
+    ///       [`BoundsCheckPolicy::Report`]).
+    ///
+    /// `access` is the [`Access`] or [`AccessIndex`] expression applying
+    /// `index` to `base`.
     ///
     /// Return a [`BoundsCheckResult`] indicating how the index should be
     /// consumed. See that type's documentation for details.
+    ///
+    /// [`Access`]: crate::Expression::Access
+    /// [`AccessIndex`]: crate::Expression::AccessIndex
     pub(super) fn write_bounds_check(
         &mut self,
+        access: Handle<crate::Expression>,
         base: Handle<crate::Expression>,
         mut index: GuardedIndex,
         block: &mut Block,
     ) -> Result<BoundsCheckResult, Error> {
+        let reported_index = match (&self.ir_function.expressions[access], index) {
+            (&crate::Expression::Access { .. }, GuardedIndex::Expression(index)) => Some(index),
+            _ => None,
+        };
+
         // If the value of `index` is known at compile time, find it now.
         index.try_resolve_to_constant(&self.ir_function.expressions, self.ir_module);
 
@@ -530,6 +669,23 @@ impl BlockContext<'_> {
                 GuardedIndex::Known(value) => BoundsCheckResult::KnownInBounds(value),
                 GuardedIndex::Expression(expr) => BoundsCheckResult::Computed(self.cached[expr]),
             },
+            BoundsCheckPolicy::Report => {
+                let result = self.write_restricted_index(base, index, block)?;
+                if let Some(reported_index) = reported_index.filter(|&reported_index| {
+                    crate::proc::reported_access(
+                        self.ir_module,
+                        &self.ir_function.expressions,
+                        self.fun_info,
+                        self.writer.bounds_check_policies,
+                        base,
+                        reported_index,
+                    )
+                    .is_some()
+                }) {
+                    self.write_bounds_report(access, base, reported_index, block)?;
+                }
+                result
+            }
         })
     }
 
@@ -548,7 +704,7 @@ impl BlockContext<'_> {
         let base_id = self.cached[base];
         let index = GuardedIndex::Expression(index);
 
-        let result_id = match self.write_bounds_check(base, index, block)? {
+        let result_id = match self.write_bounds_check(expr_handle, base, index, block)? {
             BoundsCheckResult::KnownInBounds(known_index) => {
                 let result_id = self.gen_id();
                 block.body.push(Instruction::composite_extract(
@@ -63,6 +63,20 @@ struct Instruction {
 
 const BITS_PER_BYTE: crate::Bytes = 8;
 
+/// The binding and `InputAttachmentIndex` of the depth attachment read by
+/// [`BuiltIn::LastFragDepth`], in [`Options::input_attachment_descriptor_set`].
+///
+/// This follows the 8 color attachments.
+///
+/// [`BuiltIn::LastFragDepth`]: crate::BuiltIn::LastFragDepth
+pub const DEPTH_INPUT_ATTACHMENT_INDEX: u32 = 8;
+
+/// The binding and `InputAttachmentIndex` of the stencil attachment read by
+/// [`BuiltIn::LastFragStencil`], in [`Options::input_attachment_descriptor_set`].
+///
+/// [`BuiltIn::LastFragStencil`]: crate::BuiltIn::LastFragStencil
+pub const STENCIL_INPUT_ATTACHMENT_INDEX: u32 = 9;
+
 #[derive(Clone, Debug, Error)]
 pub enum Error {
     #[error("The requested entry point couldn't be found")]
@@ -747,6 +761,12 @@ pub struct Writer {
     zero_initialize_workgroup_memory: ZeroInitializeWorkgroupMemoryMode,
     force_loop_bounding: bool,
     use_storage_input_output_16: bool,
+    input_attachment_descriptor_set: Option<u32>,
+    bounds_report: Option<crate::proc::BoundsReportOptions>,
+    /// The bounds report variable, if the module needs one.
+    ///
+    /// See [`Writer::write_bounds_report_variable`].
+    bounds_report_variable: Option<Word>,
     void_type: Word,
     //TODO: convert most of these into vectors, addressable by handle indices
     lookup_type: crate::FastHashMap<LookupType, Word>,
@@ -863,6 +883,29 @@ pub struct Options<'a> {
     /// When false, `f16` I/O is polyfilled using `f32` types with conversions.
     pub use_storage_input_output_16: bool,
 
+    /// Descriptor set holding the input attachments read by
+    /// [`Binding::FramebufferFetch`] fragment inputs, and by the
+    /// [`LastFragDepth`] and [`LastFragStencil`] built-ins.
+    ///
+    /// The input attachment for color location `n` uses binding `n` and
+    /// `InputAttachmentIndex` `n` within this set. The depth and stencil
+    /// attachments use [`DEPTH_INPUT_ATTACHMENT_INDEX`] and
+    /// [`STENCIL_INPUT_ATTACHMENT_INDEX`] the same way. Writing a module that
+    /// uses framebuffer fetch without setting this is an error.
+    ///
+    /// [`Binding::FramebufferFetch`]: crate::Binding::FramebufferFetch
+    /// [`LastFragDepth`]: crate::BuiltIn::LastFragDepth
+    /// [`LastFragStencil`]: crate::BuiltIn::LastFragStencil
+    pub input_attachment_descriptor_set: Option<u32>,
+
+    /// Where to record out-of-bounds accesses made under
+    /// [`BoundsCheckPolicy::Report`].
+    ///
+    /// Writing a module under that policy without setting this is an error.
+    ///
+    /// [`BoundsCheckPolicy::Report`]: crate::proc::BoundsCheckPolicy::Report
+    pub bounds_report: Option<crate::proc::BoundsReportOptions>,
+
     pub debug_info: Option<DebugInfo<'a>>,
 }
 
@@ -883,6 +926,8 @@ impl Default for Options<'_> {
             zero_initialize_workgroup_memory: ZeroInitializeWorkgroupMemoryMode::Polyfill,
             force_loop_bounding: true,
             use_storage_input_output_16: true,
+            input_attachment_descriptor_set: None,
+            bounds_report: None,
             debug_info: None,
         }
     }
@@ -7,9 +7,10 @@ use super::{
     block::DebugInfoInner,
     helpers::{contains_builtin, global_needs_wrapper, map_storage_class},
     Block, BlockContext, CachedConstant, CachedExpressions, DebugInfo, EntryPointContext, Error,
-    Function, FunctionArgument, GlobalVariable, IdGenerator, Instruction, LocalImageType,
-    LocalType, LocalVariable, LogicalLayout, LookupFunctionType, LookupType, NumericType, Options,
-    PhysicalLayout, PipelineOptions, ResultMember, Writer, WriterFlags, BITS_PER_BYTE,
+    Function, FunctionArgument, GlobalVariable, IdGenerator, ImageTypeFlags, Instruction,
+    LocalImageType, LocalType, LocalVariable, LogicalLayout, LookupFunctionType, LookupType,
+    NumericType, Options, PhysicalLayout, PipelineOptions, ResultMember, Writer, WriterFlags,
+    BITS_PER_BYTE,
 };
 use crate::{
     arena::{Handle, HandleVec, UniqueArena},
@@ -79,6 +80,9 @@ impl Writer {
             zero_initialize_workgroup_memory: options.zero_initialize_workgroup_memory,
             force_loop_bounding: options.force_loop_bounding,
             use_storage_input_output_16: options.use_storage_input_output_16,
+            input_attachment_descriptor_set: options.input_attachment_descriptor_set,
+            bounds_report: options.bounds_report,
+            bounds_report_variable: None,
             void_type,
             lookup_type: crate::FastHashMap::default(),
             lookup_function: crate::FastHashMap::default(),
@@ -130,12 +134,15 @@ impl Writer {
             zero_initialize_workgroup_memory: self.zero_initialize_workgroup_memory,
             force_loop_bounding: self.force_loop_bounding,
             use_storage_input_output_16: self.use_storage_input_output_16,
+            input_attachment_descriptor_set: self.input_attachment_descriptor_set,
+            bounds_report: self.bounds_report,
             capabilities_available: take(&mut self.capabilities_available),
             binding_map: take(&mut self.binding_map),
 
             // Initialized afresh:
             id_gen,
             void_type,
+            bounds_report_variable: None,
             gl450_ext_inst_id,
 
             // Recycled:
@@ -720,7 +727,18 @@ impl Writer {
             };
 
             if let Some(ref mut iface) = interface {
-                let id = if let Some(ref binding) = argument.binding {
+                let id = if let Some(index) =
+                    argument.binding.as_ref().and_then(input_attachment_index)
+                {
+                    self.write_framebuffer_fetch(
+                        ir_module,
+                        iface,
+                        argument.name.as_deref(),
+                        argument.ty,
+                        index,
+                        &mut prelude.body,
+                    )?
+                } else if let Some(ref binding) = argument.binding {
                     let name = argument.name.as_deref();
 
                     let varying_id = self.write_varying(
@@ -752,6 +770,18 @@ impl Writer {
                         let type_id = self.get_handle_type_id(member.ty);
                         let name = member.name.as_deref();
                         let binding = member.binding.as_ref().unwrap();
+                        if let Some(index) = input_attachment_index(binding) {
+                            let id = self.write_framebuffer_fetch(
+                                ir_module,
+                                iface,
+                                name,
+                                member.ty,
+                                index,
+                                &mut prelude.body,
+                            )?;
+                            constituent_ids.push(id);
+                            continue;
+                        }
                         let varying_id = self.write_varying(
                             ir_module,
                             iface.stage,
@@ -1108,6 +1138,7 @@ impl Writer {
         entry_point: &crate::EntryPoint,
         info: &FunctionInfo,
         ir_module: &crate::Module,
+        reports_bounds: bool,
         debug_info: &Option<DebugInfoInner>,
     ) -> Result<Instruction, Error> {
         let mut interface_ids = Vec::new();
@@ -1121,6 +1152,12 @@ impl Writer {
             }),
             debug_info,
         )?;
+        if let Some(variable_id) = self.bounds_report_variable {
+            // Have to include the bounds report in the interface, like other globals
+            if reports_bounds && self.physical_layout.version >= 0x10400 {
+                interface_ids.push(variable_id);
+            }
+        }
 
         let exec_model = match entry_point.stage {
             crate::ShaderStage::Vertex => spirv::ExecutionModel::Vertex,
@@ -1884,6 +1921,113 @@ impl Writer {
         Some(next_id)
     }
 
+    /// Emit an input attachment read for a fragment input read with
+    /// framebuffer fetch.
+    ///
+    /// This declares a `SubpassData` image with binding and
+    /// `InputAttachmentIndex` `index` in
+    /// [`Options::input_attachment_descriptor_set`], reads the texel at the
+    /// current fragment into `body`, and returns the id of the value read,
+    /// converted to `ty`.
+    ///
+    /// [`Options::input_attachment_descriptor_set`]: super::Options::input_attachment_descriptor_set
+    fn write_framebuffer_fetch(
+        &mut self,
+        ir_module: &crate::Module,
+        iface: &mut FunctionInterface,
+        debug_name: Option<&str>,
+        ty: Handle<crate::Type>,
+        index: u32,
+        body: &mut Vec<Instruction>,
+    ) -> Result<Word, Error> {
+        let Some(descriptor_set) = self.input_attachment_descriptor_set else {
+            return Err(Error::Validation(
+                "framebuffer fetch requires `input_attachment_descriptor_set` to be set",
+            ));
+        };
+        self.require_any("framebuffer fetch", &[spirv::Capability::InputAttachment])?;
+
+        let (size, scalar) = match ir_module.types[ty].inner {
+            crate::TypeInner::Scalar(scalar) => (None, scalar),
+            crate::TypeInner::Vector { size, scalar } => (Some(size), scalar),
+            _ => return Err(Error::Validation("framebuffer fetch of a non-numeric type")),
+        };
+
+        let image_type_id = self.get_localtype_id(LocalType::Image(LocalImageType {
+            sampled_type: scalar,
+            dim: spirv::Dim::DimSubpassData,
+            flags: ImageTypeFlags::empty(),
+            image_format: spirv::ImageFormat::Unknown,
+        }));
+        let pointer_type_id =
+            self.get_pointer_type_id(image_type_id, spirv::StorageClass::UniformConstant);
+
+        let var_id = self.id_gen.next();
+        Instruction::variable(
+            pointer_type_id,
+            var_id,
+            spirv::StorageClass::UniformConstant,
+            None,
+        )
+        .to_words(&mut self.logical_layout.declarations);
+        if self.flags.contains(WriterFlags::DEBUG) {
+            if let Some(name) = debug_name {
+                self.debugs.push(Instruction::name(var_id, name));
+            }
+        }
+        self.decorate(var_id, spirv::Decoration::DescriptorSet, &[descriptor_set]);
+        self.decorate(var_id, spirv::Decoration::Binding, &[index]);
+        self.decorate(var_id, spirv::Decoration::InputAttachmentIndex, &[index]);
+        // Only `Input` and `Output` variables belong in the interface before SPIR-V 1.4.
+        if self.physical_layout.version >= 0x10400 {
+            iface.varying_ids.push(var_id);
+        }
+
+        let image_id = self.id_gen.next();
+        body.push(Instruction::load(image_type_id, image_id, var_id, None));
+
+        let coordinates_type_id = self.get_numeric_type_id(NumericType::Vector {
+            size: crate::VectorSize::Bi,
+            scalar: crate::Scalar::I32,
+        });
+        let coordinates_id = self.get_constant_null(coordinates_type_id);
+        let texel_type_id = self.get_numeric_type_id(NumericType::Vector {
+            size: crate::VectorSize::Quad,
+            scalar,
+        });
+        let texel_id = self.id_gen.next();
+        body.push(Instruction::image_fetch_or_read(
+            spirv::Op::ImageRead,
+            texel_type_id,
+            texel_id,
+            image_id,
+            coordinates_id,
+        ));
+
+        let type_id = self.get_handle_type_id(ty);
+        let id = match size {
+            Some(crate::VectorSize::Quad) => return Ok(texel_id),
+            Some(size) => {
+                let id = self.id_gen.next();
+                let components = [0, 1, 2, 3];
+                body.push(Instruction::vector_shuffle(
+                    type_id,
+                    id,
+                    texel_id,
+                    texel_id,
+                    &components[..size as usize],
+                ));
+                id
+            }
+            None => {
+                let id = self.id_gen.next();
+                body.push(Instruction::composite_extract(type_id, id, texel_id, &[0]));
+                id
+            }
+        };
+        Ok(id)
+    }
+
     /// Generate an `OpVariable` for one value in an [`EntryPoint`]'s IO interface.
     ///
     /// The [`Binding`]s of the arguments and result of an [`EntryPoint`]'s
@@ -2059,6 +2203,10 @@ impl Writer {
                         BuiltIn::SampleId
                     }
                     Bi::SampleMask => BuiltIn::SampleMask,
+                    Bi::LastFragDepth | Bi::LastFragStencil => {
+                        // Handled by `write_framebuffer_fetch`.
+                        return Err(Error::Validation("framebuffer fetch is not a varying"));
+                    }
                     // compute
                     Bi::GlobalInvocationId => BuiltIn::GlobalInvocationId,
                     Bi::LocalInvocationId => BuiltIn::LocalInvocationId,
@@ -2133,6 +2281,10 @@ impl Writer {
                     }
                 }
             }
+            crate::Binding::FramebufferFetch { .. } => {
+                // Handled by `write_framebuffer_fetch`.
+                return Err(Error::Validation("framebuffer fetch is not a varying"));
+            }
         }
 
         Ok(id)
@@ -2181,6 +2333,56 @@ impl Writer {
         }
     }
 
+    /// Declare the bounds report storage buffer.
+    ///
+    /// This is where accesses are recorded under [`BoundsCheckPolicy::Report`],
+    /// with the layout described in [`BoundsReportOptions`].
+    ///
+    /// [`BoundsCheckPolicy::Report`]: crate::proc::BoundsCheckPolicy::Report
+    /// [`BoundsReportOptions`]: crate::proc::BoundsReportOptions
+    fn write_bounds_report_variable(&mut self, options: crate::proc::BoundsReportOptions) -> Word {
+        use spirv::Decoration;
+
+        let u32_type_id = self.get_u32_type_id();
+        let record_type_id = self.get_numeric_type_id(NumericType::Vector {
+            size: crate::VectorSize::Quad,
+            scalar: crate::Scalar::U32,
+        });
+        let capacity_id = self.get_index_constant(crate::proc::BOUNDS_REPORT_CAPACITY);
+
+        let records_type_id = self.id_gen.next();
+        Instruction::type_array(records_type_id, record_type_id, capacity_id)
+            .to_words(&mut self.logical_layout.declarations);
+        self.decorate(records_type_id, Decoration::ArrayStride, &[16]);
+
+        let struct_type_id = self.id_gen.next();
+        Instruction::type_struct(struct_type_id, &[u32_type_id, records_type_id])
+            .to_words(&mut self.logical_layout.declarations);
+        self.decorate(struct_type_id, Decoration::Block, &[]);
+        for (index, offset) in [0, 16].into_iter().enumerate() {
+            self.annotations.push(Instruction::member_decorate(
+                struct_type_id,
+                index as Word,
+                Decoration::Offset,
+                &[offset],
+            ));
+        }
+
+        let class = spirv::StorageClass::StorageBuffer;
+        let pointer_type_id = self.get_pointer_type_id(struct_type_id, class);
+        let id = self.id_gen.next();
+        Instruction::variable(pointer_type_id, id, class, None)
+            .to_words(&mut self.logical_layout.declarations);
+        if self.flags.contains(WriterFlags::DEBUG) {
+            self.debugs
+                .push(Instruction::name(id, "naga_bounds_report"));
+        }
+        self.decorate(id, Decoration::DescriptorSet, &[options.binding.group]);
+        self.decorate(id, Decoration::Binding, &[options.binding.binding]);
+
+        id
+    }
+
     fn write_global_variable(
         &mut self,
         ir_module: &crate::Module,
@@ -2430,8 +2632,27 @@ impl Writer {
             }
         }
 
-        let has_storage_buffers =
-            ir_module
+        let reporting_functions =
+            crate::proc::reporting_functions(ir_module, mod_info, self.bounds_check_policies);
+        let reporting_entry_points: Vec<bool> = ir_module
+            .entry_points
+            .iter()
+            .enumerate()
+            .map(|(index, ep)| {
+                crate::proc::function_reports(
+                    ir_module,
+                    &ep.function,
+                    mod_info.get_entry_point(index),
+                    self.bounds_check_policies,
+                    &reporting_functions,
+                )
+            })
+            .collect();
+        let has_bounds_report =
+            !reporting_functions.is_empty() || reporting_entry_points.contains(&true);
+
+        let has_storage_buffers = has_bounds_report
+            || ir_module
                 .global_variables
                 .iter()
                 .any(|(_, var)| match var.space {
@@ -2538,6 +2759,15 @@ impl Writer {
             self.global_variables.insert(handle, gvar);
         }
 
+        if has_bounds_report {
+            let Some(options) = self.bounds_report else {
+                return Err(Error::Validation(
+                    "the `Report` bounds check policy requires `bounds_report` to be set",
+                ));
+            };
+            self.bounds_report_variable = Some(self.write_bounds_report_variable(options));
+        }
+
         // write all functions
         for (handle, ir_function) in ir_module.functions.iter() {
             let info = &mod_info[handle];
@@ -2574,8 +2804,13 @@ impl Writer {
                 continue;
             }
             let info = mod_info.get_entry_point(index);
-            let ep_instruction =
-                self.write_entry_point(ir_ep, info, ir_module, &debug_info_inner)?;
+            let ep_instruction = self.write_entry_point(
+                ir_ep,
+                info,
+                ir_module,
+                reporting_entry_points[index],
+                &debug_info_inner,
+            )?;
             ep_instruction.to_words(&mut self.logical_layout.entry_points);
         }
 
@@ -2660,6 +2895,21 @@ impl Writer {
     }
 }
 
+/// Returns the input attachment index a fragment input with `binding` reads,
+/// if it is read with framebuffer fetch.
+const fn input_attachment_index(binding: &crate::Binding) -> Option<u32> {
+    match *binding {
+        crate::Binding::FramebufferFetch { location } => Some(location),
+        crate::Binding::BuiltIn(crate::BuiltIn::LastFragDepth) => {
+            Some(super::DEPTH_INPUT_ATTACHMENT_INDEX)
+        }
+        crate::Binding::BuiltIn(crate::BuiltIn::LastFragStencil) => {
+            Some(super::STENCIL_INPUT_ATTACHMENT_INDEX)
+        }
+        _ => None,
+    }
+}
+
 #[test]
 fn test_write_physical_layout() {
     let mut writer = Writer::new(&Options::default()).unwrap();
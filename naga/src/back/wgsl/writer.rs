@@ -31,6 +31,7 @@ enum Attribute {
     Interpolate(Option<crate::Interpolation>, Option<crate::Sampling>),
     Location(u32),
     BlendSrc(u32),
+    Color(u32),
     Stage(ShaderStage),
     WorkGroupSize([u32; 3]),
 }
@@ -394,6 +395,7 @@ impl<W: Write> Writer<W> {
             match *attribute {
                 Attribute::Location(id) => write!(self.out, "@location({id}) ")?,
                 Attribute::BlendSrc(blend_src) => write!(self.out, "@blend_src({blend_src}) ")?,
+                Attribute::Color(location) => write!(self.out, "@color({location}) ")?,
                 Attribute::BuiltIn(builtin_attrib) => {
                     let builtin = builtin_attrib.to_wgsl_if_implemented()?;
                     write!(self.out, "@builtin({builtin}) ")?;
@@ -1321,9 +1323,11 @@ impl<W: Write> Writer<W> {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
             } => {
                 use crate::SampleLevel as Sl;
 
+                let prefix_sparse = if residency { "Sparse" } else { "" };
                 let suffix_cmp = match depth_ref {
                     Some(_) => "Compare",
                     None => "",
@@ -1336,7 +1340,10 @@ impl<W: Write> Writer<W> {
                     Sl::Gradient { .. } => "Grad",
                 };
 
-                write!(self.out, "textureSample{suffix_cmp}{suffix_level}(")?;
+                write!(
+                    self.out,
+                    "texture{prefix_sparse}Sample{suffix_cmp}{suffix_level}("
+                )?;
                 self.write_expr(module, image, func_ctx)?;
                 write!(self.out, ", ")?;
                 self.write_expr(module, sampler, func_ctx)?;
@@ -1395,6 +1402,7 @@ impl<W: Write> Writer<W> {
                 level: _,
                 depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 let suffix_cmp = match depth_ref {
                     Some(_) => "Compare",
@@ -1677,6 +1685,7 @@ impl<W: Write> Writer<W> {
                 let fun_name = match fun {
                     Rf::All => "all",
                     Rf::Any => "any",
+                    Rf::IsResident => "textureSparseIsResident",
                     _ => return Err(Error::UnsupportedRelationalFunction(fun)),
                 };
                 write!(self.out, "{fun_name}(")?;
@@ -1836,5 +1845,6 @@ fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
             Attribute::BlendSrc(blend_src),
             Attribute::Interpolate(interpolation, sampling),
         ],
+        crate::Binding::FramebufferFetch { location } => vec![Attribute::Color(location)],
     }
 }
@@ -17,6 +17,15 @@ impl ir::PredeclaredType {
             }
             Pt::ModfResult { size, scalar } => frexp_mod_name("modf", size, scalar),
             Pt::FrexpResult { size, scalar } => frexp_mod_name("frexp", size, scalar),
+            Pt::SparseSampleResult(scalar) => {
+                let kind = match scalar.kind {
+                    ir::ScalarKind::Sint => "i",
+                    ir::ScalarKind::Uint => "u",
+                    _ => "f",
+                };
+                let bits = 8 * scalar.width;
+                format!("__sparse_sample_result_vec4_{kind}{bits}")
+            }
         }
     }
 }
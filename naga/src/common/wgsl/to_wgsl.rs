@@ -180,6 +180,8 @@ impl TryToWgsl for crate::BuiltIn {
             Bi::SubgroupId => "subgroup_id",
             Bi::SubgroupSize => "subgroup_size",
             Bi::SubgroupInvocationId => "subgroup_invocation_id",
+            Bi::LastFragDepth => "last_frag_depth",
+            Bi::LastFragStencil => "last_frag_stencil",
 
             // Non-standard built-ins.
             Bi::BaseInstance
@@ -151,6 +151,7 @@ impl ExpressionTracer<'_> {
                 ref level,
                 depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 self.expressions_used
                     .insert_iter([image, sampler, coordinate]);
@@ -325,6 +326,7 @@ impl ModuleMap {
                 ref mut level,
                 ref mut depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 adjust(image);
                 adjust(sampler);
@@ -2086,6 +2086,7 @@ fn texture_call(
                 level,
                 depth_ref: comps.depth_ref,
                 clamp_to_edge: false,
+                residency: false,
             },
             meta,
         )?)
@@ -1420,7 +1420,9 @@ impl Context<'_> {
             } => {
                 let mut location = match binding {
                     crate::Binding::Location { location, .. } => location,
-                    crate::Binding::BuiltIn(_) => return Ok(()),
+                    crate::Binding::BuiltIn(_) | crate::Binding::FramebufferFetch { .. } => {
+                        return Ok(())
+                    }
                 };
 
                 let interpolation =
@@ -1455,7 +1457,9 @@ impl Context<'_> {
             TypeInner::Struct { ref members, .. } => {
                 let mut location = match binding {
                     crate::Binding::Location { location, .. } => location,
-                    crate::Binding::BuiltIn(_) => return Ok(()),
+                    crate::Binding::BuiltIn(_) | crate::Binding::FramebufferFetch { .. } => {
+                        return Ok(())
+                    }
                 };
 
                 for (i, member) in members.clone().into_iter().enumerate() {
@@ -752,6 +752,7 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
             level,
             depth_ref,
             clamp_to_edge: false,
+            residency: false,
         };
         let image_sample_handle = ctx.expressions.append(expr, self.span_from_with_op(start));
         let handle = if is_depth && depth_ref.is_none() {
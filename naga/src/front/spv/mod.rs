@@ -6034,7 +6034,9 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                             }
                         }
                     }
-                    Some(crate::Binding::Location { .. }) => None,
+                    Some(
+                        crate::Binding::Location { .. } | crate::Binding::FramebufferFetch { .. },
+                    ) => None,
                     None => match module.types[ty].inner {
                         crate::TypeInner::Struct { ref members, .. } => {
                             let mut components = Vec::with_capacity(members.len());
@@ -609,6 +609,46 @@ impl crate::Module {
                     },
                 }
             }
+            crate::PredeclaredType::SparseSampleResult(scalar) => {
+                let code_ty = self.types.insert(
+                    crate::Type {
+                        name: None,
+                        inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+                    },
+                    Span::UNDEFINED,
+                );
+                let texel_ty = self.types.insert(
+                    crate::Type {
+                        name: None,
+                        inner: crate::TypeInner::Vector {
+                            size: crate::VectorSize::Quad,
+                            scalar,
+                        },
+                    },
+                    Span::UNDEFINED,
+                );
+
+                crate::Type {
+                    name: Some(name),
+                    inner: crate::TypeInner::Struct {
+                        members: vec![
+                            crate::StructMember {
+                                name: Some("residency_code".to_string()),
+                                ty: code_ty,
+                                binding: None,
+                                offset: 0,
+                            },
+                            crate::StructMember {
+                                name: Some("texel".to_string()),
+                                ty: texel_ty,
+                                binding: None,
+                                offset: 16,
+                            },
+                        ],
+                        span: 32,
+                    },
+                }
+            }
         };
 
         let handle = self.types.insert(ty, Span::UNDEFINED);
@@ -1060,6 +1060,17 @@ impl Texture {
         })
     }
 
+    /// Map the name of a sampling function that also reports residency.
+    pub fn map_sparse(word: &str) -> Option<Self> {
+        Some(match word {
+            "textureSparseSample" => Self::Sample,
+            "textureSparseSampleBias" => Self::SampleBias,
+            "textureSparseSampleGrad" => Self::SampleGrad,
+            "textureSparseSampleLevel" => Self::SampleLevel,
+            _ => return None,
+        })
+    }
+
     pub const fn min_argument_count(&self) -> u32 {
         match *self {
             Self::Gather => 3,
@@ -2530,7 +2541,9 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 } else if let Some(fun) = conv::map_standard_fun(function.name) {
                     self.math_function_helper(span, fun, arguments, ctx)?
                 } else if let Some(fun) = Texture::map(function.name) {
-                    self.texture_sample_helper(fun, arguments, span, ctx)?
+                    self.texture_sample_helper(fun, false, arguments, span, ctx)?
+                } else if let Some(fun) = Texture::map_sparse(function.name) {
+                    self.texture_sample_helper(fun, true, arguments, span, ctx)?
                 } else if let Some((op, cop)) = conv::map_subgroup_operation(function.name) {
                     return Ok(Some(
                         self.subgroup_operation_helper(span, op, cop, arguments, ctx)?,
@@ -3467,6 +3480,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
     fn texture_sample_helper(
         &mut self,
         fun: Texture,
+        residency: bool,
         args: &[Handle<ast::Expression<'source>>],
         span: Span,
         ctx: &mut ExpressionContext<'source, '_, '_>,
@@ -3611,6 +3625,16 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
         args.finish()?;
 
+        // The result of sampling with residency is a predeclared struct. Other
+        // image classes are reported by the validator.
+        if let (true, ir::ImageClass::Sampled { kind, .. }) = (residency, class) {
+            ctx.module
+                .generate_predeclared_type(ir::PredeclaredType::SparseSampleResult(ir::Scalar {
+                    kind,
+                    width: 4,
+                }));
+        }
+
         Ok(ir::Expression::ImageSample {
             image,
             sampler,
@@ -3621,6 +3645,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             level,
             depth_ref,
             clamp_to_edge,
+            residency,
         })
     }
 
@@ -4073,6 +4098,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 binding.apply_default_interpolation(&ctx.module.types[ty].inner);
                 Some(binding)
             }
+            Some(ast::Binding::FramebufferFetch { location }) => {
+                Some(ir::Binding::FramebufferFetch {
+                    location: self.const_u32(location, &mut ctx.as_const())?.0,
+                })
+            }
             None => None,
         })
     }
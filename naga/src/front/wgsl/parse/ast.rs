@@ -153,6 +153,9 @@ pub enum Binding<'a> {
         sampling: Option<crate::Sampling>,
         blend_src: Option<Handle<Expression<'a>>>,
     },
+    FramebufferFetch {
+        location: Handle<Expression<'a>>,
+    },
 }
 
 #[derive(Debug)]
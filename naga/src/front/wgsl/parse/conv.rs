@@ -38,6 +38,8 @@ pub fn map_built_in(
         "primitive_index" => crate::BuiltIn::PrimitiveIndex,
         "sample_index" => crate::BuiltIn::SampleIndex,
         "sample_mask" => crate::BuiltIn::SampleMask,
+        "last_frag_depth" => crate::BuiltIn::LastFragDepth,
+        "last_frag_stencil" => crate::BuiltIn::LastFragStencil,
         // compute
         "global_invocation_id" => crate::BuiltIn::GlobalInvocationId,
         "local_invocation_id" => crate::BuiltIn::LocalInvocationId,
@@ -207,6 +209,7 @@ pub fn map_relational_fun(word: &str) -> Option<crate::RelationalFunction> {
     match word {
         "any" => Some(crate::RelationalFunction::Any),
         "all" => Some(crate::RelationalFunction::All),
+        "textureSparseIsResident" => Some(crate::RelationalFunction::IsResident),
         _ => None,
     }
 }
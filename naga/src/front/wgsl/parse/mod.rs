@@ -178,6 +178,7 @@ struct BindingParser<'a> {
     sampling: ParsedAttribute<crate::Sampling>,
     invariant: ParsedAttribute<bool>,
     blend_src: ParsedAttribute<Handle<ast::Expression<'a>>>,
+    color: ParsedAttribute<Handle<ast::Expression<'a>>>,
 }
 
 impl<'a> BindingParser<'a> {
@@ -237,12 +238,34 @@ impl<'a> BindingParser<'a> {
                     .set(parser.general_expression(lexer, ctx)?, name_span)?;
                 lexer.expect(Token::Paren(')'))?;
             }
+            "color" => {
+                lexer.expect(Token::Paren('('))?;
+                self.color
+                    .set(parser.general_expression(lexer, ctx)?, name_span)?;
+                lexer.expect(Token::Paren(')'))?;
+            }
             _ => return Err(Box::new(Error::UnknownAttribute(name_span))),
         }
         Ok(())
     }
 
     fn finish(self, span: Span) -> Result<'a, Option<ast::Binding<'a>>> {
+        if let Some(location) = self.color.value {
+            // `@color` can't be combined with any other IO attribute.
+            return match (
+                self.location.value,
+                self.built_in.value,
+                self.interpolation.value,
+                self.sampling.value,
+                self.invariant.value,
+                self.blend_src.value,
+            ) {
+                (None, None, None, None, None, None) => {
+                    Ok(Some(ast::Binding::FramebufferFetch { location }))
+                }
+                _ => Err(Box::new(Error::InconsistentBinding(span))),
+            };
+        }
         match (
             self.location.value,
             self.built_in.value,
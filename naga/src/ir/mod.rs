@@ -371,7 +371,9 @@ pub enum AddressSpace {
 #[cfg_attr(feature = "deserialize", derive(Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum BuiltIn {
-    Position { invariant: bool },
+    Position {
+        invariant: bool,
+    },
     ViewIndex,
     // vertex
     BaseInstance,
@@ -389,6 +391,18 @@ pub enum BuiltIn {
     PrimitiveIndex,
     SampleIndex,
     SampleMask,
+    /// The current value of the depth attachment, read by a fragment shader.
+    ///
+    /// Requires [`Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL`].
+    ///
+    /// [`Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL`]: crate::valid::Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL
+    LastFragDepth,
+    /// The current value of the stencil attachment, read by a fragment shader.
+    ///
+    /// Requires [`Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL`].
+    ///
+    /// [`Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL`]: crate::valid::Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL
+    LastFragStencil,
     // compute
     GlobalInvocationId,
     LocalInvocationId,
@@ -967,6 +981,15 @@ pub enum Binding {
         /// See <https://www.w3.org/TR/WGSL/#attribute-blend_src>
         blend_src: Option<u32>,
     },
+
+    /// The current value of a color attachment, read by a fragment shader.
+    ///
+    /// Only valid for fragment shader inputs. `location` is the index of the
+    /// color attachment, which is also the location of the fragment output
+    /// writing to it. Requires [`Capabilities::FRAMEBUFFER_FETCH`].
+    ///
+    /// [`Capabilities::FRAMEBUFFER_FETCH`]: crate::valid::Capabilities::FRAMEBUFFER_FETCH
+    FramebufferFetch { location: u32 },
 }
 
 /// Pipeline binding information for global resources.
@@ -1153,6 +1176,11 @@ pub enum RelationalFunction {
     Any,
     IsNan,
     IsInf,
+    /// Whether the residency code of a [`SparseSampleResult`] reports that
+    /// all the sampled texels are resident.
+    ///
+    /// [`SparseSampleResult`]: PredeclaredType::SparseSampleResult
+    IsResident,
 }
 
 /// Built-in shader function for math.
@@ -1541,6 +1569,15 @@ pub enum Expression {
         /// `coordinate` to the range `[half_texel, 1 - half_texel]`, regardless
         /// of `sampler`.
         clamp_to_edge: bool,
+        /// Whether the sampling operation also reports if the sampled texels
+        /// are resident, for partially resident images.
+        ///
+        /// If this is `true`, the result is a [`SparseSampleResult`] struct,
+        /// whose residency code can be checked with
+        /// [`RelationalFunction::IsResident`].
+        ///
+        /// [`SparseSampleResult`]: PredeclaredType::SparseSampleResult
+        residency: bool,
     },
 
     /// Load a texel from an image.
@@ -2316,7 +2353,8 @@ pub struct EntryPoint {
     pub function: Function,
 }
 
-/// Return types predeclared for the frexp, modf, and atomicCompareExchangeWeak built-in functions.
+/// Return types predeclared for the frexp, modf, and atomicCompareExchangeWeak built-in functions,
+/// and for sampling with [`residency`](Expression::ImageSample::residency).
 ///
 /// These cannot be spelled in WGSL source.
 ///
@@ -2335,6 +2373,8 @@ pub enum PredeclaredType {
         size: Option<VectorSize>,
         scalar: Scalar,
     },
+    /// A `u32` residency code, followed by the sampled `vec4` texel of the given scalar.
+    SparseSampleResult(Scalar),
 }
 
 /// Set of special types that can be optionally generated by the frontends.
@@ -0,0 +1,213 @@
+/*!
+Reporting of out-of-bounds accesses, for [`BoundsCheckPolicy::Report`].
+
+[`BoundsCheckPolicy::Report`]: super::BoundsCheckPolicy::Report
+*/
+
+use alloc::vec::Vec;
+
+use crate::arena::{Arena, Handle, HandleSet};
+use crate::proc::index::{access_needs_check, GuardedIndex};
+use crate::proc::{BoundsCheckPolicies, BoundsCheckPolicy, IndexableLength};
+use crate::{valid, Span};
+
+/// The number of records the bounds report can hold.
+///
+/// Out-of-bounds accesses beyond this number are counted, but not recorded.
+pub const BOUNDS_REPORT_CAPACITY: u32 = 16;
+
+/// The size in bytes of the bounds report.
+pub const BOUNDS_REPORT_SIZE: u32 = 16 + BOUNDS_REPORT_CAPACITY * 16;
+
+/// Where accesses under [`BoundsCheckPolicy::Report`] are recorded.
+///
+/// The other [`BoundsCheckPolicy`] variants make out-of-bounds accesses safe,
+/// but silently: the shader keeps running with some in-bounds index, or with a
+/// zero value. To help find the bugs these accesses reveal, back ends
+/// generating code for the `Report` policy restrict indices like [`Restrict`]
+/// does, and also record the accesses they had to restrict in a storage
+/// buffer, the *bounds report*, that the application can read back.
+///
+/// The bounds report has the layout of this WGSL structure:
+///
+/// ```wgsl
+/// struct BoundsReport {
+///     count: atomic<u32>,
+///     records: array<vec4<u32>, BOUNDS_REPORT_CAPACITY>,
+/// }
+/// ```
+///
+/// Each reported access increments `count` and, if there is still room,
+/// writes a record made of:
+///
+/// - [`BoundsReportOptions::shader_id`],
+/// - the start of the span of the [`Access`] expression in the source,
+/// - the offending index, converted to `u32`,
+/// - the length of the indexed value.
+///
+/// Only [`Access`] expressions, whose index is computed at run time, are
+/// reported. Accesses to binding arrays are not.
+///
+/// Use [`read_bounds_report`] to decode the contents of the buffer.
+///
+/// [`BoundsCheckPolicy`]: super::BoundsCheckPolicy
+/// [`BoundsCheckPolicy::Report`]: super::BoundsCheckPolicy::Report
+/// [`Restrict`]: super::BoundsCheckPolicy::Restrict
+/// [`Access`]: crate::Expression::Access
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct BoundsReportOptions {
+    /// The binding of the bounds report storage buffer.
+    ///
+    /// This must not be used by any global of the module. Back ends map it to
+    /// their own bindings like those of globals.
+    pub binding: crate::ResourceBinding,
+
+    /// The id recorded with each access, to tell which shader made it.
+    pub shader_id: u32,
+}
+
+/// An out-of-bounds access, read from a bounds report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundsReportRecord {
+    /// The [`BoundsReportOptions::shader_id`] of the shader that made the access.
+    pub shader_id: u32,
+    /// The span of the access in the source, of zero length.
+    pub span: Span,
+    /// The offending index, converted to `u32`.
+    ///
+    /// Negative signed indices show up as large unsigned values.
+    pub index: u32,
+    /// The number of elements of the indexed value.
+    pub length: u32,
+}
+
+/// Decode the contents of a bounds report.
+///
+/// Returns the number of out-of-bounds accesses, which may be larger than
+/// [`BOUNDS_REPORT_CAPACITY`], and the records of the first ones.
+pub fn read_bounds_report(words: &[u32]) -> (u32, Vec<BoundsReportRecord>) {
+    let Some((&count, records)) = words.split_first() else {
+        return (0, Vec::new());
+    };
+    let recorded = count.min(BOUNDS_REPORT_CAPACITY) as usize;
+    let records = records
+        .get(3..)
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .take(recorded)
+        .map(|record| BoundsReportRecord {
+            shader_id: record[0],
+            span: Span::new(record[1], record[1]),
+            index: record[2],
+            length: record[3],
+        })
+        .collect();
+    (count, records)
+}
+
+/// Return the length to check the index of the [`Access`] expression
+/// `base[index]` against, if `policies` make back ends report it.
+///
+/// [`Access`]: crate::Expression::Access
+pub(crate) fn reported_access(
+    module: &crate::Module,
+    expressions: &Arena<crate::Expression>,
+    info: &valid::FunctionInfo,
+    policies: BoundsCheckPolicies,
+    base: Handle<crate::Expression>,
+    index: Handle<crate::Expression>,
+) -> Option<IndexableLength> {
+    if policies.choose_policy(base, &module.types, info) != BoundsCheckPolicy::Report {
+        return None;
+    }
+    let mut base_inner = info[base].ty.inner_with(&module.types);
+    if let crate::TypeInner::Pointer { base, .. } = *base_inner {
+        base_inner = &module.types[base].inner;
+    }
+    if let crate::TypeInner::BindingArray { .. } = *base_inner {
+        return None;
+    }
+    access_needs_check(
+        base,
+        GuardedIndex::Expression(index),
+        module,
+        expressions,
+        info,
+    )
+}
+
+/// Return the location recorded for the access `expression`.
+pub(crate) fn location(
+    expressions: &Arena<crate::Expression>,
+    expression: Handle<crate::Expression>,
+) -> u32 {
+    expressions
+        .get_span(expression)
+        .to_range()
+        .map_or(0, |range| range.start as u32)
+}
+
+/// Return the functions of `module` that may report accesses under
+/// `policies`, directly or through the functions they call.
+pub(crate) fn reporting_functions(
+    module: &crate::Module,
+    info: &valid::ModuleInfo,
+    policies: BoundsCheckPolicies,
+) -> HandleSet<crate::Function> {
+    let mut reporting = HandleSet::for_arena(&module.functions);
+    if !policies.contains(BoundsCheckPolicy::Report) {
+        return reporting;
+    }
+    // Functions may only call the functions that precede them.
+    for (handle, function) in module.functions.iter() {
+        if function_reports(module, function, &info[handle], policies, &reporting) {
+            reporting.insert(handle);
+        }
+    }
+    reporting
+}
+
+/// Return `true` if `function` may report accesses under `policies`, directly
+/// or through the functions it calls, given the [`reporting_functions`].
+pub(crate) fn function_reports(
+    module: &crate::Module,
+    function: &crate::Function,
+    info: &valid::FunctionInfo,
+    policies: BoundsCheckPolicies,
+    reporting: &HandleSet<crate::Function>,
+) -> bool {
+    if !policies.contains(BoundsCheckPolicy::Report) {
+        return false;
+    }
+    let reports = function.expressions.iter().any(|(_, expression)| {
+        if let crate::Expression::Access { base, index } = *expression {
+            reported_access(module, &function.expressions, info, policies, base, index).is_some()
+        } else {
+            false
+        }
+    });
+    reports || calls_any(&function.body, reporting)
+}
+
+/// Return `true` if `block` calls any function of `functions`.
+fn calls_any(block: &crate::Block, functions: &HandleSet<crate::Function>) -> bool {
+    use crate::Statement as S;
+    block.iter().any(|statement| match *statement {
+        S::Call { function, .. } => functions.contains(function),
+        S::Block(ref block) => calls_any(block, functions),
+        S::If {
+            ref accept,
+            ref reject,
+            ..
+        } => calls_any(accept, functions) || calls_any(reject, functions),
+        S::Switch { ref cases, .. } => cases.iter().any(|case| calls_any(&case.body, functions)),
+        S::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => calls_any(body, functions) || calls_any(continuing, functions),
+        _ => false,
+    })
+}
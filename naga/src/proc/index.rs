@@ -60,6 +60,21 @@ pub enum BoundsCheckPolicy {
     /// possible. This is the default for Naga, as a translator, but consumers
     /// should consider defaulting to a safer behavior.
     Unchecked,
+
+    /// Like [`Restrict`], but out-of-bounds indices of [`Access`] expressions
+    /// are also recorded in the bounds report, see [`BoundsReportOptions`].
+    ///
+    /// This is meant for debugging. It only applies to the [`index`] and
+    /// [`buffer`] policies, and back ends treat it as `Restrict` elsewhere.
+    /// The bounds report is set with the `bounds_report` option of each back
+    /// end, which is required by this policy.
+    ///
+    /// [`Restrict`]: BoundsCheckPolicy::Restrict
+    /// [`Access`]: crate::Expression::Access
+    /// [`BoundsReportOptions`]: super::BoundsReportOptions
+    /// [`index`]: BoundsCheckPolicies::index
+    /// [`buffer`]: BoundsCheckPolicies::buffer
+    Report,
 }
 
 /// Policies for injecting bounds checks during code generation.
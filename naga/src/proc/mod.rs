@@ -2,6 +2,7 @@
 [`Module`](super::Module) processing functionality.
 */
 
+mod bounds_report;
 mod constant_evaluator;
 mod emitter;
 pub mod index;
@@ -12,6 +13,17 @@ mod terminator;
 mod type_methods;
 mod typifier;
 
+#[cfg_attr(
+    not(any(glsl_out, hlsl_out, msl_out, spv_out)),
+    allow(dead_code, unused_imports)
+)]
+pub(crate) use bounds_report::{
+    function_reports, location as bounds_report_location, reported_access, reporting_functions,
+};
+pub use bounds_report::{
+    read_bounds_report, BoundsReportOptions, BoundsReportRecord, BOUNDS_REPORT_CAPACITY,
+    BOUNDS_REPORT_SIZE,
+};
 pub use constant_evaluator::{
     ConstantEvaluator, ConstantEvaluatorError, ExpressionKind, ExpressionKindTracker,
 };
@@ -352,7 +364,7 @@ impl crate::Binding {
     pub const fn to_built_in(&self) -> Option<crate::BuiltIn> {
         match *self {
             crate::Binding::BuiltIn(built_in) => Some(built_in),
-            Self::Location { .. } => None,
+            Self::Location { .. } | Self::FramebufferFetch { .. } => None,
         }
     }
 }
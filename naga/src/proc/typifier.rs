@@ -480,6 +480,31 @@ impl<'a> ResolveContext<'a> {
                     return Err(ResolveError::InvalidPointer(pointer));
                 }
             },
+            crate::Expression::ImageSample {
+                image,
+                residency: true,
+                ..
+            } => match *past(image)?.inner_with(types) {
+                Ti::Image {
+                    class: crate::ImageClass::Sampled { kind, multi: false },
+                    ..
+                } => {
+                    let predeclared = crate::PredeclaredType::SparseSampleResult(crate::Scalar {
+                        kind,
+                        width: 4,
+                    });
+                    let result = *self
+                        .special_types
+                        .predeclared_types
+                        .get(&predeclared)
+                        .ok_or(ResolveError::MissingSpecialType)?;
+                    TypeResolution::Handle(result)
+                }
+                ref other => {
+                    log::error!("Image type {other:?}");
+                    return Err(ResolveError::InvalidImage(image));
+                }
+            },
             crate::Expression::ImageSample {
                 image,
                 gather: Some(_),
@@ -638,7 +663,9 @@ impl<'a> ResolveContext<'a> {
             crate::Expression::Select { accept, .. } => past(accept)?.clone(),
             crate::Expression::Derivative { expr, .. } => past(expr)?.clone(),
             crate::Expression::Relational { fun, argument } => match fun {
-                crate::RelationalFunction::All | crate::RelationalFunction::Any => {
+                crate::RelationalFunction::All
+                | crate::RelationalFunction::Any
+                | crate::RelationalFunction::IsResident => {
                     TypeResolution::Value(Ti::Scalar(crate::Scalar::BOOL))
                 }
                 crate::RelationalFunction::IsNan | crate::RelationalFunction::IsInf => {
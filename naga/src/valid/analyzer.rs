@@ -665,6 +665,7 @@ impl FunctionInfo {
                 level,
                 depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 let image_storage = GlobalOrArgument::from_expression(expression_arena, image)?;
                 let sampler_storage = GlobalOrArgument::from_expression(expression_arena, sampler)?;
@@ -125,6 +125,10 @@ pub enum ExpressionError {
     InvalidSampleLevelGradientType(crate::ImageDimension, Handle<crate::Expression>),
     #[error("Clamping sample coordinate to edge is not supported with {0}")]
     InvalidSampleClampCoordinateToEdge(alloc::string::String),
+    #[error("Sampling with residency is not supported with {0}")]
+    InvalidSampleResidency(alloc::string::String),
+    #[error("Residency code {0:?} is not a `u32` scalar")]
+    InvalidResidencyCode(Handle<crate::Expression>),
     #[error("Unable to cast")]
     InvalidCastArgument,
     #[error("Invalid argument count for {0:?}")]
@@ -416,6 +420,7 @@ impl super::Validator {
                 level,
                 depth_ref,
                 clamp_to_edge,
+                residency,
             } => {
                 // check the validity of expressions
                 let image_ty = Self::global_var_ty(module, function, image)?;
@@ -590,6 +595,42 @@ impl super::Validator {
                     }
                 }
 
+                // Residency is only reported when sampling 2d sampled images
+                // without gather, depth comparison, or clamping to edge.
+                if residency {
+                    if !self
+                        .capabilities
+                        .contains(super::Capabilities::SPARSE_RESIDENCY)
+                    {
+                        return Err(ExpressionError::MissingCapabilities(
+                            super::Capabilities::SPARSE_RESIDENCY,
+                        ));
+                    }
+                    if !matches!(class, crate::ImageClass::Sampled { multi: false, .. }) {
+                        return Err(ExpressionError::InvalidSampleResidency(alloc::format!(
+                            "image class `{class:?}`"
+                        )));
+                    }
+                    if dim != crate::ImageDimension::D2 {
+                        return Err(ExpressionError::InvalidSampleResidency(alloc::format!(
+                            "image dimension `{dim:?}`"
+                        )));
+                    }
+                    if gather.is_some() {
+                        return Err(ExpressionError::InvalidSampleResidency("gather".into()));
+                    }
+                    if depth_ref.is_some() {
+                        return Err(ExpressionError::InvalidSampleResidency(
+                            "depth comparison".into(),
+                        ));
+                    }
+                    if clamp_to_edge {
+                        return Err(ExpressionError::InvalidSampleResidency(
+                            "clamping to edge".into(),
+                        ));
+                    }
+                }
+
                 // External textures can only be sampled using clamp_to_edge.
                 if matches!(class, crate::ImageClass::External) && !clamp_to_edge {
                     return Err(ExpressionError::InvalidImageClass(class));
@@ -1050,6 +1091,20 @@ impl super::Validator {
                             return Err(ExpressionError::InvalidFloatArgument(argument));
                         }
                     },
+                    Rf::IsResident => {
+                        if !self
+                            .capabilities
+                            .contains(super::Capabilities::SPARSE_RESIDENCY)
+                        {
+                            return Err(ExpressionError::MissingCapabilities(
+                                super::Capabilities::SPARSE_RESIDENCY,
+                            ));
+                        }
+                        match *argument_inner {
+                            Ti::Scalar(Sc::U32) => {}
+                            _ => return Err(ExpressionError::InvalidResidencyCode(argument)),
+                        }
+                    }
                 }
                 ShaderStages::all()
             }
@@ -530,6 +530,7 @@ impl super::Validator {
                 level,
                 depth_ref,
                 clamp_to_edge: _,
+                residency: _,
             } => {
                 handle
                     .check_dep(image)?
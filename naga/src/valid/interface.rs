@@ -73,6 +73,8 @@ pub enum VaryingError {
     BindingCollision { location: u32 },
     #[error("Multiple bindings use the same `blend_src` {blend_src}")]
     BindingCollisionBlendSrc { blend_src: u32 },
+    #[error("The color attachment at location {location} is read more than once")]
+    FramebufferFetchCollision { location: u32 },
     #[error("Built-in {0:?} is present more than once")]
     DuplicateBuiltIn(crate::BuiltIn),
     #[error("Capability {0:?} is not supported")]
@@ -146,6 +148,7 @@ struct VaryingContext<'a> {
     type_info: &'a Vec<super::r#type::TypeInfo>,
     location_mask: &'a mut BitSet,
     blend_src_mask: &'a mut BitSet,
+    framebuffer_fetch_mask: &'a mut BitSet,
     built_ins: &'a mut crate::FastHashSet<crate::BuiltIn>,
     capabilities: Capabilities,
     flags: super::ValidationFlags,
@@ -182,6 +185,9 @@ impl VaryingContext<'_> {
                     Bi::PrimitiveIndex => Capabilities::PRIMITIVE_INDEX,
                     Bi::ViewIndex => Capabilities::MULTIVIEW,
                     Bi::SampleIndex => Capabilities::MULTISAMPLED_SHADING,
+                    Bi::LastFragDepth | Bi::LastFragStencil => {
+                        Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL
+                    }
                     Bi::NumSubgroups
                     | Bi::SubgroupId
                     | Bi::SubgroupSize
@@ -275,6 +281,14 @@ impl VaryingContext<'_> {
                         self.stage == St::Fragment,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
+                    Bi::LastFragDepth => (
+                        self.stage == St::Fragment && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::F32),
+                    ),
+                    Bi::LastFragStencil => (
+                        self.stage == St::Fragment && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
                     Bi::LocalInvocationIndex => (
                         self.stage == St::Compute && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
@@ -425,6 +439,25 @@ impl VaryingContext<'_> {
                     None => return Err(VaryingError::InvalidType(ty)),
                 }
             }
+            crate::Binding::FramebufferFetch { location } => {
+                if !self.capabilities.contains(Capabilities::FRAMEBUFFER_FETCH) {
+                    return Err(VaryingError::UnsupportedCapability(
+                        Capabilities::FRAMEBUFFER_FETCH,
+                    ));
+                }
+                if self.stage != crate::ShaderStage::Fragment || self.output {
+                    return Err(VaryingError::InvalidAttributeInStage("color", self.stage));
+                }
+                // Attachments hold numeric scalars or vectors.
+                match *ty_inner {
+                    Ti::Scalar(scalar) | Ti::Vector { scalar, .. }
+                        if scalar.kind != crate::ScalarKind::Bool && scalar.width == 4 => {}
+                    _ => return Err(VaryingError::InvalidType(ty)),
+                }
+                if !self.framebuffer_fetch_mask.insert(location as usize) {
+                    return Err(VaryingError::FramebufferFetchCollision { location });
+                }
+            }
         }
 
         Ok(())
@@ -703,6 +736,7 @@ impl super::Validator {
         }
 
         self.location_mask.clear();
+        self.framebuffer_fetch_mask.clear();
         let mut argument_built_ins = crate::FastHashSet::default();
         // TODO: add span info to function arguments
         for (index, fa) in ep.function.arguments.iter().enumerate() {
@@ -713,6 +747,7 @@ impl super::Validator {
                 type_info: &self.types,
                 location_mask: &mut self.location_mask,
                 blend_src_mask: &mut self.blend_src_mask,
+                framebuffer_fetch_mask: &mut self.framebuffer_fetch_mask,
                 built_ins: &mut argument_built_ins,
                 capabilities: self.capabilities,
                 flags: self.flags,
@@ -731,6 +766,7 @@ impl super::Validator {
                 type_info: &self.types,
                 location_mask: &mut self.location_mask,
                 blend_src_mask: &mut self.blend_src_mask,
+                framebuffer_fetch_mask: &mut self.framebuffer_fetch_mask,
                 built_ins: &mut result_built_ins,
                 capabilities: self.capabilities,
                 flags: self.flags,
@@ -170,6 +170,18 @@ bitflags::bitflags! {
         const SHADER_FLOAT16 = 1 << 26;
         /// Support for [`ImageClass::External`]
         const TEXTURE_EXTERNAL = 1 << 27;
+        /// Support for [`Binding::FramebufferFetch`].
+        const FRAMEBUFFER_FETCH = 1 << 28;
+        /// Support for sampling with [`residency`] and [`RelationalFunction::IsResident`].
+        ///
+        /// [`residency`]: crate::Expression::ImageSample::residency
+        /// [`RelationalFunction::IsResident`]: crate::RelationalFunction::IsResident
+        const SPARSE_RESIDENCY = 1 << 29;
+        /// Support for [`BuiltIn::LastFragDepth`] and [`BuiltIn::LastFragStencil`].
+        ///
+        /// [`BuiltIn::LastFragDepth`]: crate::BuiltIn::LastFragDepth
+        /// [`BuiltIn::LastFragStencil`]: crate::BuiltIn::LastFragStencil
+        const FRAMEBUFFER_FETCH_DEPTH_STENCIL = 1 << 30;
     }
 }
 
@@ -284,6 +296,7 @@ pub struct Validator {
     layouter: Layouter,
     location_mask: BitSet,
     blend_src_mask: BitSet,
+    framebuffer_fetch_mask: BitSet,
     ep_resource_bindings: FastHashSet<crate::ResourceBinding>,
     #[allow(dead_code)]
     switch_values: FastHashSet<crate::SwitchValue>,
@@ -505,6 +518,7 @@ impl Validator {
             layouter: Layouter::default(),
             location_mask: BitSet::new(),
             blend_src_mask: BitSet::new(),
+            framebuffer_fetch_mask: BitSet::new(),
             ep_resource_bindings: FastHashSet::default(),
             switch_values: FastHashSet::default(),
             valid_expression_list: Vec::new(),
@@ -531,6 +545,7 @@ impl Validator {
         self.layouter.clear();
         self.location_mask.clear();
         self.blend_src_mask.clear();
+        self.framebuffer_fetch_mask.clear();
         self.ep_resource_bindings.clear();
         self.switch_values.clear();
         self.valid_expression_list.clear();
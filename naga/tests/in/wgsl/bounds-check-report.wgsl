@@ -0,0 +1,58 @@
+// Tests for `naga::back::BoundsCheckPolicy::Report`.
+
+struct Globals {
+    a: array<f32, 10>,
+    v: vec4<f32>,
+    m: mat3x4<f32>,
+    d: array<f32>,
+}
+
+@group(0) @binding(0) var<storage, read_write> globals: Globals;
+
+var<workgroup> shared_values: array<u32, 64>;
+
+fn index_array(i: i32) -> f32 {
+    return globals.a[i];
+}
+
+fn index_dynamic_array(i: i32) -> f32 {
+    return globals.d[i];
+}
+
+fn index_vector(i: i32) -> f32 {
+    return globals.v[i];
+}
+
+fn index_matrix(i: i32, j: i32) -> f32 {
+    return globals.m[i][j];
+}
+
+fn index_in_bounds() -> f32 {
+    // Constant indices are checked at compile time, and never reported.
+    return globals.a[9] + globals.v[3];
+}
+
+fn index_local(i: u32) -> u32 {
+    var values = array<u32, 4>(1u, 2u, 3u, 4u);
+    return values[i];
+}
+
+fn call_index_array(i: i32) -> f32 {
+    return index_array(i);
+}
+
+@compute @workgroup_size(1)
+fn main(@builtin(local_invocation_index) index: u32) {
+    shared_values[index * 2u] = index;
+    globals.d[index] = call_index_array(i32(index))
+        + index_dynamic_array(i32(index))
+        + index_vector(i32(index))
+        + index_matrix(i32(index), 1)
+        + index_in_bounds()
+        + f32(index_local(index));
+}
+
+@compute @workgroup_size(1)
+fn unreported() {
+    globals.a[0] = index_in_bounds();
+}
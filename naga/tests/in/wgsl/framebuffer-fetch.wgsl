@@ -0,0 +1,23 @@
+@fragment
+fn blend(@color(0) dst: vec4<f32>, @location(0) src: vec4<f32>) -> @location(0) vec4<f32> {
+    return mix(dst, src, src.a);
+}
+
+struct FetchInput {
+    @builtin(position) position: vec4<f32>,
+    @color(1) normal: vec3<f32>,
+    @color(2) id: u32,
+}
+
+struct FetchOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) normal: vec3<f32>,
+}
+
+@fragment
+fn fetch_struct(in: FetchInput) -> FetchOutput {
+    var out: FetchOutput;
+    out.color = vec4<f32>(in.normal * f32(in.id), 1.0);
+    out.normal = normalize(in.normal);
+    return out;
+}
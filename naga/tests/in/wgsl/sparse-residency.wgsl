@@ -0,0 +1,33 @@
+@group(0) @binding(0)
+var image_2d: texture_2d<f32>;
+@group(0) @binding(1)
+var image_2d_array: texture_2d_array<f32>;
+@group(0) @binding(2)
+var sampler_reg: sampler;
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    var color = vec4(0.0);
+
+    let auto_level = textureSparseSample(image_2d, sampler_reg, uv);
+    if textureSparseIsResident(auto_level.residency_code) {
+        color += auto_level.texel;
+    }
+
+    let bias = textureSparseSampleBias(image_2d, sampler_reg, uv, 2.0, vec2(3, 1));
+    if textureSparseIsResident(bias.residency_code) {
+        color += bias.texel;
+    }
+
+    let grad = textureSparseSampleGrad(image_2d, sampler_reg, uv, vec2(0.5), vec2(0.25));
+    if textureSparseIsResident(grad.residency_code) {
+        color += grad.texel;
+    }
+
+    let exact = textureSparseSampleLevel(image_2d_array, sampler_reg, uv, 1, 2.5);
+    if textureSparseIsResident(exact.residency_code) {
+        color += exact.texel;
+    }
+
+    return color;
+}
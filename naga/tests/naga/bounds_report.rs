@@ -0,0 +1,179 @@
+/*!
+Tests for the `Report` bounds check policy.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "glsl-out"))]
+
+use naga::{
+    back::glsl,
+    proc::{read_bounds_report, BoundsCheckPolicies, BoundsCheckPolicy, BoundsReportOptions},
+    valid::{Capabilities, ValidationFlags, Validator},
+    ResourceBinding, ShaderStage,
+};
+
+const BOUNDS_REPORT: BoundsReportOptions = BoundsReportOptions {
+    binding: ResourceBinding {
+        group: 3,
+        binding: 0,
+    },
+    shader_id: 7,
+};
+
+const REPORT: BoundsCheckPolicies = BoundsCheckPolicies {
+    index: BoundsCheckPolicy::Report,
+    buffer: BoundsCheckPolicy::Report,
+    image_load: BoundsCheckPolicy::Restrict,
+    binding_array: BoundsCheckPolicy::Unchecked,
+};
+
+/// Write the entry point `ep` of `source` as GLSL, under `policies`.
+fn write_glsl(
+    source: &str,
+    stage: ShaderStage,
+    ep: &str,
+    policies: BoundsCheckPolicies,
+    bounds_report: Option<BoundsReportOptions>,
+) -> Result<String, glsl::Error> {
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let info = Validator::new(ValidationFlags::all(), Capabilities::default())
+        .validate(&module)
+        .unwrap();
+
+    let options = glsl::Options {
+        version: glsl::Version::Desktop(430),
+        bounds_report,
+        ..Default::default()
+    };
+    let pipeline_options = glsl::PipelineOptions {
+        shader_stage: stage,
+        entry_point: ep.to_string(),
+        multiview: None,
+    };
+    let mut output = String::new();
+    glsl::Writer::new(
+        &mut output,
+        &module,
+        &info,
+        &options,
+        &pipeline_options,
+        policies,
+    )?
+    .write()?;
+    Ok(output)
+}
+
+/// Return the number of accesses `output` reports.
+fn reported_accesses(output: &str) -> usize {
+    // Don't count the definition of the function.
+    output.matches("naga_bounds_report(uint(").count()
+}
+
+#[test]
+fn dynamic_accesses_are_reported() {
+    let output = write_glsl(
+        "
+        @group(0) @binding(0) var<storage, read_write> values: array<u32>;
+        @group(0) @binding(1) var<uniform> fixed: array<vec4<f32>, 4>;
+
+        fn local_value(i: i32) -> f32 {
+            var local = array<f32, 3>(1.0, 2.0, 3.0);
+            return local[i];
+        }
+
+        @compute @workgroup_size(1)
+        fn main(@builtin(local_invocation_index) i: u32) {
+            values[i] = u32(fixed[i][i] + local_value(i32(i)));
+            values[0] = u32(fixed[3].x);
+        }
+        ",
+        ShaderStage::Compute,
+        "main",
+        REPORT,
+        Some(BOUNDS_REPORT),
+    )
+    .unwrap();
+
+    // `values[i]`, `fixed[i]`, `fixed[i][i]` and `local[i]`, but not the
+    // accesses with constant indices.
+    assert_eq!(reported_accesses(&output), 4);
+    assert!(output.contains("buffer NagaBoundsReport"));
+    assert!(output.contains(".length())"));
+    assert!(output.contains("uvec4(7u,"));
+}
+
+#[test]
+fn other_policies_do_not_report() {
+    let source = "
+        @group(0) @binding(0) var<storage, read_write> values: array<u32, 4>;
+
+        @compute @workgroup_size(1)
+        fn main(@builtin(local_invocation_index) i: u32) {
+            values[i] = values[3];
+        }
+        ";
+    let output = write_glsl(
+        source,
+        ShaderStage::Compute,
+        "main",
+        BoundsCheckPolicies::default(),
+        Some(BOUNDS_REPORT),
+    )
+    .unwrap();
+    assert_eq!(reported_accesses(&output), 0);
+    assert!(!output.contains("NagaBoundsReport"));
+
+    // Modules without dynamic accesses don't need the bounds report either.
+    let output = write_glsl(
+        "
+        @group(0) @binding(0) var<storage, read_write> values: array<u32, 4>;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            values[2] = values[3];
+        }
+        ",
+        ShaderStage::Compute,
+        "main",
+        REPORT,
+        None,
+    )
+    .unwrap();
+    assert!(!output.contains("NagaBoundsReport"));
+}
+
+#[test]
+fn report_policy_requires_bounds_report() {
+    let result = write_glsl(
+        "
+        var<private> colors: array<vec4<f32>, 3>;
+
+        @fragment
+        fn fs(@builtin(sample_index) i: u32) -> @location(0) vec4<f32> {
+            return colors[i];
+        }
+        ",
+        ShaderStage::Fragment,
+        "fs",
+        REPORT,
+        None,
+    );
+    assert!(matches!(result, Err(glsl::Error::Custom(_))));
+}
+
+#[test]
+fn read_report() {
+    let mut words = vec![0; naga::proc::BOUNDS_REPORT_SIZE as usize / 4];
+    words[0] = 20;
+    words[4..8].copy_from_slice(&[7, 42, 5, 4]);
+    words[8..12].copy_from_slice(&[8, 50, 6, 3]);
+
+    let (count, records) = read_bounds_report(&words);
+    assert_eq!(count, 20);
+    assert_eq!(records.len(), naga::proc::BOUNDS_REPORT_CAPACITY as usize);
+    assert_eq!(records[0].shader_id, 7);
+    assert_eq!(records[0].span, naga::Span::new(42, 42));
+    assert_eq!((records[0].index, records[0].length), (5, 4));
+    assert_eq!(records[1].shader_id, 8);
+
+    assert!(read_bounds_report(&[]).1.is_empty());
+}
@@ -1,3 +1,4 @@
+mod bounds_report;
 mod example_wgsl;
 mod snapshots;
 mod spirv_capabilities;
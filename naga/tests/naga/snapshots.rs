@@ -102,9 +102,12 @@ struct SpirvOutParameters {
     clamp_frag_depth: bool,
     separate_entry_points: bool,
     use_storage_input_output_16: bool,
+    input_attachment_descriptor_set: Option<u32>,
     #[cfg(all(feature = "deserialize", spv_out))]
     #[serde(deserialize_with = "deserialize_binding_map")]
     binding_map: naga::back::spv::BindingMap,
+    #[cfg(feature = "deserialize")]
+    bounds_report: Option<naga::proc::BoundsReportOptions>,
 }
 
 impl Default for SpirvOutParameters {
@@ -118,8 +121,11 @@ impl Default for SpirvOutParameters {
             clamp_frag_depth: false,
             separate_entry_points: false,
             use_storage_input_output_16: true,
+            input_attachment_descriptor_set: None,
             #[cfg(all(feature = "deserialize", spv_out))]
             binding_map: naga::back::spv::BindingMap::default(),
+            #[cfg(feature = "deserialize")]
+            bounds_report: None,
         }
     }
 }
@@ -636,6 +642,8 @@ fn write_output_spv(
         zero_initialize_workgroup_memory: spv::ZeroInitializeWorkgroupMemoryMode::Polyfill,
         force_loop_bounding: true,
         use_storage_input_output_16: params.use_storage_input_output_16,
+        input_attachment_descriptor_set: params.input_attachment_descriptor_set,
+        bounds_report: params.bounds_report,
         debug_info,
     };
 
@@ -1893,6 +1893,184 @@ fn invalid_blend_src() {
     }
 }
 
+#[test]
+fn invalid_framebuffer_fetch() {
+    // Missing capability.
+    check_validation! {
+        "
+        @fragment
+        fn main(@color(0) dst: vec4<f32>) -> @location(0) vec4<f32> { return dst; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::UnsupportedCapability(Capabilities::FRAMEBUFFER_FETCH),
+                ),
+                ..
+            },
+        )
+    }
+
+    // Fetching in a vertex shader.
+    check_validation! {
+        "
+        @vertex
+        fn main(@color(0) dst: vec4<f32>) -> @builtin(position) vec4<f32> { return dst; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Vertex,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::InvalidAttributeInStage("color", naga::ShaderStage::Vertex),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH
+    }
+
+    // Fetching a non-numeric type.
+    check_validation! {
+        "
+        @fragment
+        fn main(@color(0) dst: vec4<bool>) -> @location(0) vec4<f32> { return vec4(0.0); }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::InvalidType(_),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH
+    }
+
+    // Fetching the same attachment twice.
+    check_validation! {
+        "
+        @fragment
+        fn main(@color(1) a: vec4<f32>, @color(1) b: vec4<f32>) -> @location(0) vec4<f32> { return a + b; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    1,
+                    naga::valid::VaryingError::FramebufferFetchCollision { location: 1 },
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH
+    }
+}
+
+#[test]
+fn invalid_framebuffer_fetch_depth_stencil() {
+    // Missing capability.
+    check_validation! {
+        "
+        @fragment
+        fn main(@builtin(last_frag_depth) depth: f32) -> @location(0) vec4<f32> { return vec4(depth); }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::UnsupportedCapability(Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL),
+                ),
+                ..
+            },
+        )
+    }
+
+    // Reading the stencil value as a float.
+    check_validation! {
+        "
+        @fragment
+        fn main(@builtin(last_frag_stencil) stencil: f32) -> @location(0) vec4<f32> { return vec4(stencil); }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::InvalidBuiltInType(naga::BuiltIn::LastFragStencil),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL
+    }
+
+    // Writing the depth attachment through it.
+    check_validation! {
+        "
+        @fragment
+        fn main() -> @builtin(last_frag_depth) f32 { return 0.0; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Result(
+                    naga::valid::VaryingError::InvalidBuiltInStage(naga::BuiltIn::LastFragDepth),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH_DEPTH_STENCIL
+    }
+}
+
+#[test]
+fn invalid_sparse_residency() {
+    // Missing capability.
+    check_validation! {
+        "
+        @group(0) @binding(0) var t: texture_2d<f32>;
+        @group(0) @binding(1) var s: sampler;
+        fn main() -> vec4<f32> {
+            return textureSparseSample(t, s, vec2(0.0)).texel;
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::Expression {
+                source: naga::valid::ExpressionError::MissingCapabilities(
+                    Capabilities::SPARSE_RESIDENCY
+                ),
+                ..
+            },
+            ..
+        })
+    }
+
+    // Sampling a 3d texture.
+    check_validation! {
+        "
+        @group(0) @binding(0) var t: texture_3d<f32>;
+        @group(0) @binding(1) var s: sampler;
+        fn main() -> vec4<f32> {
+            return textureSparseSample(t, s, vec3(0.0)).texel;
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::Expression {
+                source: naga::valid::ExpressionError::InvalidSampleResidency(_),
+                ..
+            },
+            ..
+        }),
+        Capabilities::SPARSE_RESIDENCY
+    }
+}
+
 #[test]
 fn invalid_access() {
     check_validation! {
@@ -0,0 +1,82 @@
+#version 430 core
+#extension GL_ARB_compute_shader : require
+#extension GL_ARB_shader_storage_buffer_object : require
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+layout(std430, binding = 0) buffer Globals_block_0Compute {
+    float a[10];
+    vec4 v;
+    mat3x4 m;
+    float d[];
+} _group_0_binding_0_cs;
+
+shared uint shared_values[64];
+
+layout(std430, binding = 1) buffer NagaBoundsReport { uint count; uvec4 records[16]; } naga_bounds_report_buffer;
+uint naga_bounds_report(uint index, uint length, uint location) {
+    if (index < length) {
+        return index;
+    }
+    uint slot = atomicAdd(naga_bounds_report_buffer.count, 1u);
+    if (slot < 16u) {
+        naga_bounds_report_buffer.records[slot] = uvec4(7u, location, index, length);
+    }
+    return length - 1u;
+}
+
+
+float index_array(int i) {
+    float _e4 = _group_0_binding_0_cs.a[naga_bounds_report(uint(i), 10u, 311u)];
+    return _e4;
+}
+
+float index_dynamic_array(int i_1) {
+    float _e4 = _group_0_binding_0_cs.d[naga_bounds_report(uint(i_1), uint(_group_0_binding_0_cs.d.length()), 379u)];
+    return _e4;
+}
+
+float index_vector(int i_2) {
+    float _e4 = _group_0_binding_0_cs.v[naga_bounds_report(uint(i_2), 4u, 440u)];
+    return _e4;
+}
+
+float index_matrix(int i_3, int j) {
+    float _e6 = _group_0_binding_0_cs.m[naga_bounds_report(uint(i_3), 3u, 509u)][naga_bounds_report(uint(j), 4u, 509u)];
+    return _e6;
+}
+
+float index_in_bounds() {
+    float _e3 = _group_0_binding_0_cs.a[9];
+    float _e7 = _group_0_binding_0_cs.v.w;
+    return (_e3 + _e7);
+}
+
+uint index_local(uint i_4) {
+    uint values[4] = uint[4](1u, 2u, 3u, 4u);
+    uint _e8 = values[naga_bounds_report(uint(i_4), 4u, 766u)];
+    return _e8;
+}
+
+float call_index_array(int i_5) {
+    float _e1 = index_array(i_5);
+    return _e1;
+}
+
+void main() {
+    if (gl_LocalInvocationID == uvec3(0u)) {
+        shared_values = uint[64](0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u, 0u);
+    }
+    memoryBarrierShared();
+    barrier();
+    uint index = gl_LocalInvocationIndex;
+    shared_values[naga_bounds_report(uint((index * 2u)), 64u, 934u)] = index;
+    float _e9 = call_index_array(int(index));
+    float _e11 = index_dynamic_array(int(index));
+    float _e14 = index_vector(int(index));
+    float _e18 = index_matrix(int(index), 1);
+    float _e20 = index_in_bounds();
+    uint _e22 = index_local(index);
+    _group_0_binding_0_cs.d[naga_bounds_report(uint(index), uint(_group_0_binding_0_cs.d.length()), 973u)] = (((((_e9 + _e11) + _e14) + _e18) + _e20) + float(_e22));
+    return;
+}
+
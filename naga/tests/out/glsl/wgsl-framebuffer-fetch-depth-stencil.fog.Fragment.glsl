@@ -0,0 +1,20 @@
+#version 300 es
+#extension GL_EXT_shader_framebuffer_fetch : require
+#extension GL_ARM_shader_framebuffer_fetch_depth_stencil : require
+
+precision highp float;
+precision highp int;
+
+struct StencilInput {
+    vec4 position;
+    uint stencil;
+};
+layout(location = 0) inout vec4 _fs2p_location0;
+
+void main() {
+    float depth = gl_LastFragDepthARM;
+    vec4 dst = _fs2p_location0;
+    _fs2p_location0 = vec4(mix(dst.xyz, vec3(0.5), depth), dst.w);
+    return;
+}
+
@@ -0,0 +1,21 @@
+#version 300 es
+#extension GL_ARM_shader_framebuffer_fetch_depth_stencil : require
+
+precision highp float;
+precision highp int;
+
+struct StencilInput {
+    vec4 position;
+    uint stencil;
+};
+layout(location = 0) out vec4 _fs2p_location0;
+
+void main() {
+    StencilInput in_ = StencilInput(gl_FragCoord, uint(gl_LastFragStencilARM));
+    if ((in_.stencil == 0u)) {
+        discard;
+    }
+    _fs2p_location0 = vec4(1.0, 0.0, 0.0, 1.0);
+    return;
+}
+
@@ -0,0 +1,25 @@
+#version 300 es
+#extension GL_EXT_shader_framebuffer_fetch : require
+
+precision highp float;
+precision highp int;
+
+struct FetchInput {
+    vec4 position;
+    vec3 normal;
+    uint id;
+};
+struct FetchOutput {
+    vec4 color;
+    vec3 normal;
+};
+smooth in vec4 _vs2fs_location0;
+layout(location = 0) inout vec4 _fs2p_location0;
+
+void main() {
+    vec4 dst = _fs2p_location0;
+    vec4 src = _vs2fs_location0;
+    _fs2p_location0 = mix(dst, src, src.w);
+    return;
+}
+
@@ -0,0 +1,30 @@
+#version 300 es
+#extension GL_EXT_shader_framebuffer_fetch : require
+
+precision highp float;
+precision highp int;
+
+struct FetchInput {
+    vec4 position;
+    vec3 normal;
+    uint id;
+};
+struct FetchOutput {
+    vec4 color;
+    vec3 normal;
+};
+layout(location = 2) inout uint _fs2p_location2;
+layout(location = 0) out vec4 _fs2p_location0;
+layout(location = 1) inout vec3 _fs2p_location1;
+
+void main() {
+    FetchInput in_ = FetchInput(gl_FragCoord, _fs2p_location1, _fs2p_location2);
+    FetchOutput out_ = FetchOutput(vec4(0.0), vec3(0.0));
+    out_.color = vec4((in_.normal * float(in_.id)), 1.0);
+    out_.normal = normalize(in_.normal);
+    FetchOutput _e12 = out_;
+    _fs2p_location0 = _e12.color;
+    _fs2p_location1 = _e12.normal;
+    return;
+}
+
@@ -0,0 +1,101 @@
+RWByteAddressBuffer globals : register(u0);
+groupshared uint shared_values[64];
+
+RWByteAddressBuffer naga_bounds_report_buffer : register(u0, space1);
+
+uint naga_bounds_report(uint index, uint length, uint location)
+{
+    if (index < length) {
+        return index;
+    }
+    uint slot;
+    naga_bounds_report_buffer.InterlockedAdd(0, 1u, slot);
+    if (slot < 16u) {
+        naga_bounds_report_buffer.Store4(16u + slot * 16u, uint4(7u, location, index, length));
+    }
+    return length - 1u;
+}
+
+float index_array(int i)
+{
+    float _e4 = asfloat(globals.Load(naga_bounds_report(uint(i), 10u, 311u)*4+0));
+    return _e4;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+float index_dynamic_array(int i_1)
+{
+    float _e4 = asfloat(globals.Load(naga_bounds_report(uint(i_1), ((NagaBufferLengthRW(globals) - 112u) / 4u), 379u)*4+112));
+    return _e4;
+}
+
+float index_vector(int i_2)
+{
+    float _e4 = asfloat(globals.Load(naga_bounds_report(uint(i_2), 4u, 440u)*4+48));
+    return _e4;
+}
+
+float index_matrix(int i_3, int j)
+{
+    float _e6 = asfloat(globals.Load(naga_bounds_report(uint(j), 4u, 509u)*4+naga_bounds_report(uint(i_3), 3u, 509u)*16+64));
+    return _e6;
+}
+
+float index_in_bounds()
+{
+    float _e3 = asfloat(globals.Load(36+0));
+    float _e7 = asfloat(globals.Load(12+48));
+    return (_e3 + _e7);
+}
+
+typedef uint ret_Constructarray4_uint_[4];
+ret_Constructarray4_uint_ Constructarray4_uint_(uint arg0, uint arg1, uint arg2, uint arg3) {
+    uint ret[4] = { arg0, arg1, arg2, arg3 };
+    return ret;
+}
+
+uint index_local(uint i_4)
+{
+    uint values[4] = Constructarray4_uint_(1u, 2u, 3u, 4u);
+
+    uint _e8 = values[naga_bounds_report(uint(i_4), 4u, 766u)];
+    return _e8;
+}
+
+float call_index_array(int i_5)
+{
+    const float _e1 = index_array(i_5);
+    return _e1;
+}
+
+[numthreads(1, 1, 1)]
+void main(uint index : SV_GroupIndex, uint3 __local_invocation_id : SV_GroupThreadID)
+{
+    if (all(__local_invocation_id == uint3(0u, 0u, 0u))) {
+        shared_values = (uint[64])0;
+    }
+    GroupMemoryBarrierWithGroupSync();
+    shared_values[naga_bounds_report(uint((index * 2u)), 64u, 934u)] = index;
+    const float _e9 = call_index_array(int(index));
+    const float _e11 = index_dynamic_array(int(index));
+    const float _e14 = index_vector(int(index));
+    const float _e18 = index_matrix(int(index), int(1));
+    const float _e20 = index_in_bounds();
+    const uint _e22 = index_local(index);
+    globals.Store(naga_bounds_report(uint(index), ((NagaBufferLengthRW(globals) - 112u) / 4u), 973u)*4+112, asuint((((((_e9 + _e11) + _e14) + _e18) + _e20) + float(_e22))));
+    return;
+}
+
+[numthreads(1, 1, 1)]
+void unreported()
+{
+    const float _e3 = index_in_bounds();
+    globals.Store(0+0, asuint(_e3));
+    return;
+}
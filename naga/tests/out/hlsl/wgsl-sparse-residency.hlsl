@@ -0,0 +1,51 @@
+struct _sparse_sample_result_vec4_f32_ {
+    uint residency_code;
+    int _pad1_0;
+    int _pad1_1;
+    int _pad1_2;
+    float4 texel;
+};
+
+Texture2D<float4> image_2d : register(t0);
+Texture2DArray<float4> image_2d_array : register(t1);
+SamplerState nagaSamplerHeap[2048]: register(s0, space0);
+SamplerComparisonState nagaComparisonSamplerHeap[2048]: register(s0, space1);
+StructuredBuffer<uint> nagaGroup0SamplerIndexArray : register(t0, space255);
+static const SamplerState sampler_reg = nagaSamplerHeap[nagaGroup0SamplerIndexArray[2]];
+
+struct FragmentInput_main {
+    float2 uv_1 : LOC0;
+};
+
+float4 main(FragmentInput_main fragmentinput_main) : SV_Target0
+{
+    float2 uv = fragmentinput_main.uv_1;
+    float4 color = (0.0).xxxx;
+
+    _sparse_sample_result_vec4_f32_ auto_level;
+    auto_level.texel = image_2d.Sample(sampler_reg, uv, int2(0, 0), 0.0, auto_level.residency_code);
+    if (CheckAccessFullyMapped(auto_level.residency_code)) {
+        float4 _e10 = color;
+        color = (_e10 + auto_level.texel);
+    }
+    _sparse_sample_result_vec4_f32_ bias;
+    bias.texel = image_2d.SampleBias(sampler_reg, uv, 2.0, int2(int2(int(3), int(1))), 0.0, bias.residency_code);
+    if (CheckAccessFullyMapped(bias.residency_code)) {
+        float4 _e22 = color;
+        color = (_e22 + bias.texel);
+    }
+    _sparse_sample_result_vec4_f32_ grad;
+    grad.texel = image_2d.SampleGrad(sampler_reg, uv, (0.5).xx, (0.25).xx, int2(0, 0), 0.0, grad.residency_code);
+    if (CheckAccessFullyMapped(grad.residency_code)) {
+        float4 _e34 = color;
+        color = (_e34 + grad.texel);
+    }
+    _sparse_sample_result_vec4_f32_ exact;
+    exact.texel = image_2d_array.SampleLevel(sampler_reg, float3(uv, int(1)), 2.5, int2(0, 0), exact.residency_code);
+    if (CheckAccessFullyMapped(exact.residency_code)) {
+        float4 _e44 = color;
+        color = (_e44 + exact.texel);
+    }
+    float4 _e46 = color;
+    return _e46;
+}
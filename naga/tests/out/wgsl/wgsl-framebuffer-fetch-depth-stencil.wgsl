@@ -0,0 +1,17 @@
+struct StencilInput {
+    @builtin(position) position: vec4<f32>,
+    @builtin(last_frag_stencil) stencil: u32,
+}
+
+@fragment 
+fn fog(@builtin(last_frag_depth) depth: f32, @color(0) dst: vec4<f32>) -> @location(0) vec4<f32> {
+    return vec4<f32>(mix(dst.xyz, vec3(0.5f), depth), dst.w);
+}
+
+@fragment 
+fn outline(in: StencilInput) -> @location(0) vec4<f32> {
+    if (in.stencil == 0u) {
+        discard;
+    }
+    return vec4<f32>(1f, 0f, 0f, 1f);
+}
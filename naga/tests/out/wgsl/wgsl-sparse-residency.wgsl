@@ -0,0 +1,34 @@
+@group(0) @binding(0) 
+var image_2d: texture_2d<f32>;
+@group(0) @binding(1) 
+var image_2d_array: texture_2d_array<f32>;
+@group(0) @binding(2) 
+var sampler_reg: sampler;
+
+@fragment 
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    var color: vec4<f32> = vec4(0f);
+
+    let auto_level = textureSparseSample(image_2d, sampler_reg, uv);
+    if textureSparseIsResident(auto_level.residency_code) {
+        let _e10 = color;
+        color = (_e10 + auto_level.texel);
+    }
+    let bias = textureSparseSampleBias(image_2d, sampler_reg, uv, 2f, vec2<i32>(3i, 1i));
+    if textureSparseIsResident(bias.residency_code) {
+        let _e22 = color;
+        color = (_e22 + bias.texel);
+    }
+    let grad = textureSparseSampleGrad(image_2d, sampler_reg, uv, vec2(0.5f), vec2(0.25f));
+    if textureSparseIsResident(grad.residency_code) {
+        let _e34 = color;
+        color = (_e34 + grad.texel);
+    }
+    let exact = textureSparseSampleLevel(image_2d_array, sampler_reg, uv, 1i, 2.5f);
+    if textureSparseIsResident(exact.residency_code) {
+        let _e44 = color;
+        color = (_e44 + exact.texel);
+    }
+    let _e46 = color;
+    return _e46;
+}
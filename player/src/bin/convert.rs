@@ -0,0 +1,44 @@
+//! Converts WebGPU traces between the RON and binary formats.
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    extern crate wgpu_core as wgc;
+
+    use std::{fs, path::PathBuf, process::exit};
+    use wgc::device::trace;
+
+    env_logger::init();
+
+    const HELP: &str = "\
+    Usage: convert <trace directory> <output directory>\n\
+    \n\
+    Convert a wgpu trace between the RON and binary formats. A RON trace is\n\
+    converted to the binary format, and a binary trace to RON.\n";
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [src, dst] = args.as_slice() else {
+        eprintln!("{HELP}");
+        exit(1);
+    };
+    let (src, dst) = (PathBuf::from(src), PathBuf::from(dst));
+    fs::create_dir_all(&dst).unwrap();
+
+    let result = if src.join(trace::binary::FILE_NAME).is_file() {
+        log::info!("Converting binary trace {src:?} to RON in {dst:?}");
+        player::convert_binary_to_ron(&src, &dst)
+    } else if src.join(trace::FILE_NAME).is_file() {
+        log::info!("Converting RON trace {src:?} to binary in {dst:?}");
+        player::convert_ron_to_binary(&src, &dst)
+    } else {
+        eprintln!("No trace found in {src:?}\n\n{HELP}");
+        exit(1);
+    };
+
+    if let Err(e) = result {
+        eprintln!("Conversion failed: {e}");
+        exit(1);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -11,6 +11,7 @@ fn main() {
 
     use std::{
         fs,
+        io::BufReader,
         path::{Path, PathBuf},
         process::exit,
     };
@@ -34,13 +35,74 @@ fn main() {
     Usage: play <trace directory> | <trace file>\n\
     \n\
     Play a wgpu trace from the specified file or directory. If the trace contains\n\
-    buffers, textures, or shaders, the directory form must be used.\n";
+    buffers, textures, or shaders, the directory form must be used for RON traces.\n\
+    Binary traces contain their data and can be played from the file directly.\n\
+    \n\
+    Traces recorded with `TraceOptions::record_outputs` or WGPU_TRACE_OUTPUTS=1 also\n\
+    contain the contents of buffers mapped for reading and of frames presented to\n\
+    surfaces configured with COPY_SRC.\n\
+    These are compared against the replay, and the player exits with status 2 if\n\
+    any of them differ.\n";
 
-    let (dir, trace) = match std::env::args().nth(1) {
-        Some(arg) if Path::new(&arg).is_dir() => (
-            PathBuf::from(arg.clone()),
-            PathBuf::from(arg).join(trace::FILE_NAME),
+    /// The actions of a trace, in either format.
+    enum TraceActions {
+        /// Actions of a RON trace in reverse order, and the directory holding its data.
+        Ron(Vec<trace::Action<'static>>, PathBuf),
+        /// A binary trace, read as it is played, and an action that was put back.
+        Binary(
+            player::BinaryTrace<BufReader<fs::File>>,
+            Option<Box<trace::Action<'static>>>,
         ),
+    }
+
+    impl TraceActions {
+        fn next(&mut self) -> Option<trace::Action<'static>> {
+            match self {
+                Self::Ron(actions, _) => actions.pop(),
+                Self::Binary(trace, peeked) => match peeked.take() {
+                    Some(action) => Some(*action),
+                    None => trace.next_action().unwrap(),
+                },
+            }
+        }
+
+        fn put_back(&mut self, action: trace::Action<'static>) {
+            match self {
+                Self::Ron(actions, _) => actions.push(action),
+                Self::Binary(_, peeked) => *peeked = Some(Box::new(action)),
+            }
+        }
+
+        /// Warns if the actions ran out in the middle of a binary trace frame.
+        fn check_end(&self) {
+            if let Self::Binary(trace, _) = self {
+                if let Err(e) = trace.finish() {
+                    log::warn!("The trace ends in the middle of a frame: {e}");
+                }
+            }
+        }
+    }
+
+    impl player::DataSource for TraceActions {
+        fn read(&self, name: &str) -> Vec<u8> {
+            match self {
+                Self::Ron(_, dir) => dir.as_path().read(name),
+                Self::Binary(trace, _) => trace.read(name),
+            }
+        }
+    }
+
+    let (dir, trace) = match std::env::args().nth(1) {
+        Some(arg) if Path::new(&arg).is_dir() => {
+            let dir = PathBuf::from(arg);
+            let binary_trace = dir.join(trace::binary::FILE_NAME);
+            if binary_trace.is_file() {
+                (dir, binary_trace)
+            } else {
+                let trace = dir.join(trace::FILE_NAME);
+                (dir, trace)
+            }
+        }
         Some(arg) if Path::new(&arg).is_file() => {
             (PathBuf::from("/nonexistent"), PathBuf::from(arg))
         }
@@ -51,10 +113,18 @@ fn main() {
     };
 
     log::info!("Loading trace '{trace:?}'");
-    let file = fs::File::open(trace).unwrap();
-    let mut actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
-    actions.reverse(); // allows us to pop from the top
-    log::info!("Found {} actions", actions.len());
+    let file = fs::File::open(&trace).unwrap();
+    let mut actions = if trace.extension().is_some_and(|ext| ext == "bin") {
+        TraceActions::Binary(
+            player::BinaryTrace::new(BufReader::new(file)).unwrap(),
+            None,
+        )
+    } else {
+        let mut actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
+        actions.reverse(); // allows us to pop from the top
+        log::info!("Found {} actions", actions.len());
+        TraceActions::Ron(actions, dir)
+    };
 
     #[cfg(feature = "winit")]
     let event_loop = {
@@ -83,15 +153,17 @@ fn main() {
     }
     .unwrap();
 
-    let (backends, device_desc) =
-        match actions.pop_if(|action| matches!(action, trace::Action::Init { .. })) {
-            Some(trace::Action::Init { desc, backend }) => {
-                log::info!("Initializing the device for backend: {backend:?}");
-                (wgt::Backends::from(backend), desc)
-            }
-            Some(_) => unreachable!(),
-            None => (wgt::Backends::all(), wgt::DeviceDescriptor::default()),
-        };
+    let (backends, device_desc) = match actions.next() {
+        Some(trace::Action::Init { desc, backend }) => {
+            log::info!("Initializing the device for backend: {backend:?}");
+            (wgt::Backends::from(backend), desc)
+        }
+        Some(action) => {
+            actions.put_back(action);
+            (wgt::Backends::all(), wgt::DeviceDescriptor::default())
+        }
+        None => (wgt::Backends::all(), wgt::DeviceDescriptor::default()),
+    };
 
     let adapter = global
         .request_adapter(
@@ -122,19 +194,32 @@ fn main() {
     {
         unsafe { global.device_start_graphics_debugger_capture(device) };
 
-        while let Some(action) = actions.pop() {
-            global.process(
+        let mut mismatches = Vec::new();
+        while let Some(action) = actions.next() {
+            if let Err(mismatch) = global.process(
                 device,
                 queue,
                 action,
-                &dir,
+                &actions,
                 &mut command_encoder_id_manager,
                 &mut command_buffer_id_manager,
-            );
+            ) {
+                log::error!("Output mismatch: {mismatch}");
+                mismatches.push(mismatch);
+            }
         }
 
+        actions.check_end();
         unsafe { global.device_stop_graphics_debugger_capture(device) };
         global.device_poll(device, wgt::PollType::wait()).unwrap();
+
+        if !mismatches.is_empty() {
+            eprintln!("{} recorded outputs were not reproduced:", mismatches.len());
+            for mismatch in &mismatches {
+                eprintln!("\t{mismatch}");
+            }
+            exit(2);
+        }
     }
     #[cfg(feature = "winit")]
     {
@@ -146,6 +231,7 @@ fn main() {
         let mut resize_config = None;
         let mut frame_count = 0;
         let mut done = false;
+        let mismatch_count = &std::cell::Cell::new(0);
         event_loop
             .run(move |event, target| {
                 target.set_control_flow(ControlFlow::Poll);
@@ -153,7 +239,7 @@ fn main() {
                 match event {
                     Event::WindowEvent { event, .. } => match event {
                         WindowEvent::RedrawRequested if resize_config.is_none() => {
-                            match actions.pop() {
+                            match actions.next() {
                                 Some(trace::Action::ConfigureSurface(_device_id, config)) => {
                                     log::info!("Configuring the surface");
                                     let current_size: (u32, u32) = window.inner_size().into();
@@ -187,17 +273,21 @@ fn main() {
                                     target.exit();
                                 }
                                 Some(action) => {
-                                    global.process(
+                                    if let Err(mismatch) = global.process(
                                         device,
                                         queue,
                                         action,
-                                        &dir,
+                                        &actions,
                                         &mut command_encoder_id_manager,
                                         &mut command_buffer_id_manager,
-                                    );
+                                    ) {
+                                        eprintln!("Output mismatch: {mismatch}");
+                                        mismatch_count.set(mismatch_count.get() + 1);
+                                    }
                                 }
                                 None => {
                                     if !done {
+                                        actions.check_end();
                                         println!("Finished the end at frame {frame_count}");
                                         done = true;
                                     }
@@ -233,6 +323,14 @@ fn main() {
                 }
             })
             .unwrap();
+
+        if mismatch_count.get() != 0 {
+            eprintln!(
+                "{} recorded outputs were not reproduced",
+                mismatch_count.get()
+            );
+            exit(2);
+        }
     }
 }
 
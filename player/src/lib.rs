@@ -8,7 +8,181 @@ extern crate wgpu_types as wgt;
 
 use wgc::{device::trace, identity::IdentityManager};
 
-use std::{borrow::Cow, fs, path::Path};
+use hashbrown::HashMap;
+use std::{
+    borrow::Cow,
+    fs,
+    io::{self, Read, Write as _},
+    path::Path,
+};
+
+/// Provides the contents of the data blobs that trace actions refer to by name.
+pub trait DataSource {
+    fn read(&self, name: &str) -> Vec<u8>;
+
+    fn read_to_string(&self, name: &str) -> String {
+        String::from_utf8(self.read(name)).unwrap()
+    }
+}
+
+/// Data blobs of a RON trace are stored as files in the trace directory.
+impl DataSource for Path {
+    fn read(&self, name: &str) -> Vec<u8> {
+        fs::read(self.join(name)).unwrap()
+    }
+}
+
+/// A recorded output of the traced application that the replay did not reproduce.
+#[derive(Debug)]
+pub struct OutputMismatch {
+    /// Description of the resource the output was read from.
+    pub resource: String,
+    /// Number of bytes the application observed.
+    pub expected_size: usize,
+    /// Number of bytes the replay produced.
+    pub actual_size: usize,
+    /// Number of bytes, within the common size, that differ.
+    pub differing_bytes: usize,
+    /// Offset of the first byte that differs.
+    pub first_difference: usize,
+}
+
+impl OutputMismatch {
+    fn check(resource: String, expected: &[u8], actual: &[u8]) -> Result<(), Self> {
+        let mut differences = expected
+            .iter()
+            .zip(actual)
+            .enumerate()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(offset, _)| offset);
+        let first_difference = differences.next();
+        let differing_bytes = first_difference.map_or(0, |_| 1 + differences.count());
+        if differing_bytes == 0 && expected.len() == actual.len() {
+            return Ok(());
+        }
+        Err(Self {
+            resource,
+            expected_size: expected.len(),
+            actual_size: actual.len(),
+            differing_bytes,
+            first_difference: first_difference.unwrap_or(expected.len().min(actual.len())),
+        })
+    }
+}
+
+impl std::fmt::Display for OutputMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} of {} bytes differ, starting at offset {}",
+            self.resource, self.differing_bytes, self.expected_size, self.first_difference
+        )?;
+        if self.expected_size != self.actual_size {
+            write!(f, " (replay produced {} bytes)", self.actual_size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Streams the actions of a binary trace, keeping the data blobs they refer to.
+pub struct BinaryTrace<R> {
+    reader: trace::binary::Reader<R>,
+    blobs: HashMap<String, Vec<u8>>,
+}
+
+impl<R: Read> BinaryTrace<R> {
+    pub fn new(reader: R) -> Result<Self, trace::binary::BinaryTraceError> {
+        Ok(Self {
+            reader: trace::binary::Reader::new(reader)?,
+            blobs: HashMap::new(),
+        })
+    }
+
+    /// Reads frames up to the next action.
+    ///
+    /// Returns `Ok(None)` once everything written to the trace so far has been read.
+    pub fn next_action(
+        &mut self,
+    ) -> Result<Option<trace::Action<'static>>, trace::binary::BinaryTraceError> {
+        loop {
+            match self.reader.next_frame()? {
+                Some(trace::binary::Frame::Data { name, data }) => {
+                    self.blobs.insert(name, data);
+                }
+                Some(trace::binary::Frame::Action(action)) => return Ok(Some(action)),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Checks that the trace didn't end in the middle of a frame.
+    pub fn finish(&self) -> Result<(), trace::binary::BinaryTraceError> {
+        self.reader.finish()
+    }
+}
+
+impl<R> DataSource for BinaryTrace<R> {
+    fn read(&self, name: &str) -> Vec<u8> {
+        match self.blobs.get(name) {
+            Some(data) => data.clone(),
+            None => panic!("Data blob {name} is missing from the trace"),
+        }
+    }
+}
+
+/// Converts the RON trace in `src_dir` into a binary trace in `dst_dir`.
+pub fn convert_ron_to_binary(
+    src_dir: &Path,
+    dst_dir: &Path,
+) -> Result<(), trace::binary::BinaryTraceError> {
+    let file = fs::File::open(src_dir.join(trace::FILE_NAME))?;
+    let actions: Vec<trace::Action> =
+        ron::de::from_reader(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut writer = trace::binary::Writer::new(&dst_dir.join(trace::binary::FILE_NAME))?;
+    for mut action in actions {
+        for name in action.data_names_mut() {
+            let kind = Path::new(name.as_str())
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or("bin");
+            *name = writer.add_data(kind, &fs::read(src_dir.join(&*name))?)?;
+        }
+        writer.add_action(&action)?;
+    }
+    Ok(())
+}
+
+/// Converts the binary trace in `src_dir` into a RON trace in `dst_dir`.
+///
+/// Every data blob is written as a separate file next to the RON trace.
+pub fn convert_binary_to_ron(
+    src_dir: &Path,
+    dst_dir: &Path,
+) -> Result<(), trace::binary::BinaryTraceError> {
+    let file = io::BufReader::new(fs::File::open(src_dir.join(trace::binary::FILE_NAME))?);
+    let mut reader = trace::binary::Reader::new(file)?;
+    let mut out = io::BufWriter::new(fs::File::create(dst_dir.join(trace::FILE_NAME))?);
+    out.write_all(b"[\n")?;
+    while let Some(frame) = reader.next_frame()? {
+        match frame {
+            trace::binary::Frame::Data { name, data } => {
+                // Only ever write next to the trace, whatever the blob name says.
+                let Some(file_name) = Path::new(&name).file_name() else {
+                    return Err(trace::binary::BinaryTraceError::InvalidBlobName);
+                };
+                fs::write(dst_dir.join(file_name), data)?;
+            }
+            trace::binary::Frame::Action(action) => {
+                let string = ron::ser::to_string_pretty(&action, ron::ser::PrettyConfig::default())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                writeln!(out, "{string},")?;
+            }
+        }
+    }
+    reader.finish()?;
+    out.write_all(b"]")?;
+    Ok(())
+}
 
 pub trait GlobalPlay {
     fn encode_commands(
@@ -17,15 +191,15 @@ pub trait GlobalPlay {
         commands: Vec<trace::Command>,
         command_buffer_id_manager: &mut IdentityManager<wgc::id::markers::CommandBuffer>,
     ) -> wgc::id::CommandBufferId;
-    fn process(
+    fn process<D: DataSource + ?Sized>(
         &self,
         device: wgc::id::DeviceId,
         queue: wgc::id::QueueId,
         action: trace::Action,
-        dir: &Path,
+        data_source: &D,
         command_encoder_id_manager: &mut IdentityManager<wgc::id::markers::CommandEncoder>,
         command_buffer_id_manager: &mut IdentityManager<wgc::id::markers::CommandBuffer>,
-    );
+    ) -> Result<(), OutputMismatch>;
 }
 
 impl GlobalPlay for wgc::global::Global {
@@ -186,15 +360,15 @@ impl GlobalPlay for wgc::global::Global {
         cmd_buf
     }
 
-    fn process(
+    fn process<D: DataSource + ?Sized>(
         &self,
         device: wgc::id::DeviceId,
         queue: wgc::id::QueueId,
         action: trace::Action,
-        dir: &Path,
+        data_source: &D,
         command_encoder_id_manager: &mut IdentityManager<wgc::id::markers::CommandEncoder>,
         command_buffer_id_manager: &mut IdentityManager<wgc::id::markers::CommandBuffer>,
-    ) {
+    ) -> Result<(), OutputMismatch> {
         use wgc::device::trace::Action;
         log::debug!("action {action:?}");
         //TODO: find a way to force ID perishing without excessive `maintain()` calls.
@@ -301,7 +475,7 @@ impl GlobalPlay for wgc::global::Global {
             }
             Action::CreateShaderModule { id, desc, data } => {
                 log::debug!("Creating shader from {data}");
-                let code = fs::read_to_string(dir.join(&data)).unwrap();
+                let code = data_source.read_to_string(&data);
                 let source = if data.ends_with(".wgsl") {
                     wgc::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(code.clone()))
                 } else if data.ends_with(".ron") {
@@ -325,7 +499,7 @@ impl GlobalPlay for wgc::global::Global {
             } => {
                 let spirv = data.iter().find_map(|a| {
                     if a.ends_with(".spv") {
-                        let data = fs::read(dir.join(a)).unwrap();
+                        let data = data_source.read(a);
                         assert!(data.len() % 4 == 0);
 
                         Some(Cow::Owned(bytemuck::pod_collect_to_vec(&data)))
@@ -335,7 +509,7 @@ impl GlobalPlay for wgc::global::Global {
                 });
                 let dxil = data.iter().find_map(|a| {
                     if a.ends_with(".dxil") {
-                        let vec = std::fs::read(dir.join(a)).unwrap();
+                        let vec = data_source.read(a);
                         Some(Cow::Owned(vec))
                     } else {
                         None
@@ -343,7 +517,7 @@ impl GlobalPlay for wgc::global::Global {
                 });
                 let hlsl = data.iter().find_map(|a| {
                     if a.ends_with(".hlsl") {
-                        let code = fs::read_to_string(dir.join(a)).unwrap();
+                        let code = data_source.read_to_string(a);
                         Some(Cow::Owned(code))
                     } else {
                         None
@@ -351,7 +525,7 @@ impl GlobalPlay for wgc::global::Global {
                 });
                 let msl = data.iter().find_map(|a| {
                     if a.ends_with(".msl") {
-                        let code = fs::read_to_string(dir.join(a)).unwrap();
+                        let code = data_source.read_to_string(a);
                         Some(Cow::Owned(code))
                     } else {
                         None
@@ -359,7 +533,7 @@ impl GlobalPlay for wgc::global::Global {
                 });
                 let glsl = data.iter().find_map(|a| {
                     if a.ends_with(".glsl") {
-                        let code = fs::read_to_string(dir.join(a)).unwrap();
+                        let code = data_source.read_to_string(a);
                         Some(Cow::Owned(code))
                     } else {
                         None
@@ -367,7 +541,7 @@ impl GlobalPlay for wgc::global::Global {
                 });
                 let wgsl = data.iter().find_map(|a| {
                     if a.ends_with(".wgsl") {
-                        let code = fs::read_to_string(dir.join(a)).unwrap();
+                        let code = data_source.read_to_string(a);
                         Some(Cow::Owned(code))
                     } else {
                         None
@@ -456,7 +630,7 @@ impl GlobalPlay for wgc::global::Global {
                 range,
                 queued,
             } => {
-                let bin = std::fs::read(dir.join(data)).unwrap();
+                let bin = data_source.read(&data);
                 let size = (range.end - range.start) as usize;
                 if queued {
                     self.queue_write_buffer(queue, id, range.start, &bin)
@@ -472,7 +646,7 @@ impl GlobalPlay for wgc::global::Global {
                 layout,
                 size,
             } => {
-                let bin = std::fs::read(dir.join(data)).unwrap();
+                let bin = data_source.read(&data);
                 self.queue_write_texture(queue, &to, &bin, &layout, &size)
                     .unwrap();
             }
@@ -503,6 +677,42 @@ impl GlobalPlay for wgc::global::Global {
             Action::DestroyTlas(id) => {
                 self.tlas_drop(id);
             }
+            Action::ExpectBuffer { id, range, data } => {
+                let expected = data_source.read(&data);
+                let size = range.end - range.start;
+                self.buffer_map_async(
+                    id,
+                    range.start,
+                    Some(size),
+                    wgc::resource::BufferMapOperation {
+                        host: wgc::device::HostMap::Read,
+                        callback: None,
+                    },
+                )
+                .unwrap();
+                self.device_poll(device, wgt::PollType::wait()).unwrap();
+                let (ptr, size) = self
+                    .buffer_get_mapped_range(id, range.start, Some(size))
+                    .unwrap();
+                let actual = unsafe { std::slice::from_raw_parts(ptr.as_ptr(), size as usize) };
+                let result = OutputMismatch::check(
+                    format!("Buffer {id:?} range {range:?}"),
+                    &expected,
+                    actual,
+                );
+                self.buffer_unmap(id).unwrap();
+                return result;
+            }
+            Action::ExpectSurfaceTexture { id, data } => {
+                let expected = data_source.read(&data);
+                let actual = self.texture_read_back(id).unwrap();
+                return OutputMismatch::check(
+                    format!("Surface texture {id:?}"),
+                    &expected,
+                    &actual,
+                );
+            }
         }
+        Ok(())
     }
 }
@@ -109,14 +109,16 @@ impl Test<'_> {
         let mut command_buffer_id_manager = IdentityManager::new();
         println!("\t\t\tRunning...");
         for action in self.actions {
-            global.process(
-                device_id,
-                queue_id,
-                action,
-                dir,
-                &mut command_encoder_id_manager,
-                &mut command_buffer_id_manager,
-            );
+            global
+                .process(
+                    device_id,
+                    queue_id,
+                    action,
+                    dir,
+                    &mut command_encoder_id_manager,
+                    &mut command_buffer_id_manager,
+                )
+                .unwrap_or_else(|mismatch| panic!("{mismatch}"));
         }
         println!("\t\t\tMapping...");
         for expect in &self.expectations {
@@ -0,0 +1,171 @@
+use wgpu::util::DeviceExt;
+use wgpu_test::{gpu_test, GpuTestConfiguration, GpuTestInitializer, TestParameters};
+
+pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
+    vec.push(SUBALLOCATED_UNIFORM_BUFFERS);
+}
+
+const BUFFER_COUNT: u32 = 48;
+
+/// Make sure that sub-allocated buffers are initialized, written and bound at
+/// their own range of the shared buffer, including after other buffers of the
+/// same block have been freed.
+#[gpu_test]
+static SUBALLOCATED_UNIFORM_BUFFERS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .instance_flags(wgpu::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS)
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let usage = wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST;
+
+        // Leave holes in the blocks for the buffers below to fill.
+        let freed = (0..BUFFER_COUNT)
+            .map(|_| {
+                ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: None,
+                    size: 16,
+                    usage,
+                    mapped_at_creation: false,
+                })
+            })
+            .collect::<Vec<_>>();
+        let _kept = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage,
+            mapped_at_creation: false,
+        });
+        drop(freed);
+
+        let expected = |i: u32| match i % 3 {
+            0 | 1 => [i, i + 1, i + 2, i + 3],
+            _ => [0; 4],
+        };
+
+        let buffers = (0..BUFFER_COUNT)
+            .map(|i| match i % 3 {
+                // Written through a staging buffer when unmapped.
+                0 => ctx
+                    .device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::cast_slice(&expected(i)),
+                        usage,
+                    }),
+                // Written on the queue.
+                1 => {
+                    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: None,
+                        size: 16,
+                        usage,
+                        mapped_at_creation: false,
+                    });
+                    ctx.queue
+                        .write_buffer(&buffer, 0, bytemuck::cast_slice(&expected(i)));
+                    buffer
+                }
+                // Zero initialized on first use.
+                _ => ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: None,
+                    size: 16,
+                    usage,
+                    mapped_at_creation: false,
+                }),
+            })
+            .collect::<Vec<_>>();
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(
+                    "
+                    @group(0) @binding(0) var<uniform> input: vec4<u32>;
+                    @group(0) @binding(1) var<storage, read_write> output: vec4<u32>;
+
+                    @compute @workgroup_size(1)
+                    fn main() {
+                        output = input;
+                    }
+                    "
+                    .into(),
+                ),
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                cache: None,
+            });
+
+        let output_stride = u64::from(ctx.device.limits().min_storage_buffer_offset_alignment);
+        let output_size = output_stride * u64::from(BUFFER_COUNT);
+        let output = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_groups = buffers
+            .iter()
+            .enumerate()
+            .map(|(i, buffer)| {
+                ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &pipeline.get_bind_group_layout(0),
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                buffer: &output,
+                                offset: i as u64 * output_stride,
+                                size: wgpu::BufferSize::new(16),
+                            }),
+                        },
+                    ],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut encoder = ctx.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&pipeline);
+            for bind_group in &bind_groups {
+                pass.set_bind_group(0, bind_group, &[]);
+                pass.dispatch_workgroups(1, 1, 1);
+            }
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, output_size);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+
+        let data = readback.slice(..).get_mapped_range();
+        for i in 0..BUFFER_COUNT {
+            let start = (u64::from(i) * output_stride) as usize;
+            let values: &[u32] = bytemuck::cast_slice(&data[start..start + 16]);
+            assert_eq!(values, expected(i), "buffer {i}");
+        }
+    });
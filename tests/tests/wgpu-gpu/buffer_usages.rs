@@ -31,10 +31,15 @@ const NEEDS_MAPPABLE_PRIMARY_BUFFERS: &[Bu; 7] = &[
     Bu::MAP_WRITE.union(Bu::MAP_READ),
     Bu::MAP_READ.union(Bu::COPY_DST.union(Bu::STORAGE)),
     Bu::MAP_WRITE.union(Bu::COPY_SRC.union(Bu::STORAGE)),
-    // these two require acceleration_structures feature
-    Bu::all().intersection(Bu::BLAS_INPUT.union(Bu::TLAS_INPUT).complement()),
+    // these two require acceleration_structures feature, and sparse buffers can't be mapped
+    Bu::all().intersection(
+        Bu::BLAS_INPUT
+            .union(Bu::TLAS_INPUT)
+            .union(Bu::SPARSE)
+            .complement(),
+    ),
 ];
-const INVALID_BITS: Bu = Bu::from_bits_retain(0b1111111111111);
+const INVALID_BITS: Bu = Bu::from_bits_retain(0b11111111111111);
 const ALWAYS_FAIL: &[Bu; 2] = &[Bu::empty(), INVALID_BITS];
 
 fn try_create(ctx: TestingContext, usages: &[(bool, &[wgpu::BufferUsages])]) {
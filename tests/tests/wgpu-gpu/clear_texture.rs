@@ -403,8 +403,7 @@ static CLEAR_TEXTURE_COMPRESSED_BCN: GpuTestConfiguration = GpuTestConfiguration
             })
             // https://bugs.chromium.org/p/angleproject/issues/detail?id=7056
             .expect_fail(FailureCase::backend_adapter(wgpu::Backends::GL, "ANGLE"))
-            // compressed texture copy to buffer not yet implemented
-            .expect_fail(FailureCase::backend(wgpu::Backends::GL)),
+            .downlevel_flags(wgpu::DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES),
     )
     .run_async(|ctx| clear_texture_tests(ctx, TEXTURE_FORMATS_BC));
 
@@ -424,8 +423,7 @@ static CLEAR_TEXTURE_COMPRESSED_ASTC: GpuTestConfiguration = GpuTestConfiguratio
             })
             // https://bugs.chromium.org/p/angleproject/issues/detail?id=7056
             .expect_fail(FailureCase::backend_adapter(wgpu::Backends::GL, "ANGLE"))
-            // compressed texture copy to buffer not yet implemented
-            .expect_fail(FailureCase::backend(wgpu::Backends::GL)),
+            .downlevel_flags(wgpu::DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES),
     )
     .run_async(|ctx| clear_texture_tests(ctx, TEXTURE_FORMATS_ASTC));
 
@@ -436,7 +434,6 @@ static CLEAR_TEXTURE_COMPRESSED_ETC2: GpuTestConfiguration = GpuTestConfiguratio
             .features(wgpu::Features::CLEAR_TEXTURE | wgpu::Features::TEXTURE_COMPRESSION_ETC2)
             // https://bugs.chromium.org/p/angleproject/issues/detail?id=7056
             .expect_fail(FailureCase::backend_adapter(wgpu::Backends::GL, "ANGLE"))
-            // compressed texture copy to buffer not yet implemented
-            .expect_fail(FailureCase::backend(wgpu::Backends::GL)),
+            .downlevel_flags(wgpu::DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES),
     )
     .run_async(|ctx| clear_texture_tests(ctx, TEXTURE_FORMATS_ETC2));
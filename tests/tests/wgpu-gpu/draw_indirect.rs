@@ -27,6 +27,8 @@ pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
         INSTANCED_INDEXED_DRAW_OOB_INSTANCE_START,
         INSTANCED_INDEXED_DRAW_OOB_INSTANCE_COUNT,
         INDIRECT_BUFFER_OFFSETS,
+        MULTI_DRAW_INDIRECT_COUNT,
+        MULTI_DRAW_INDIRECT_COUNT_PARTIAL,
     ]);
 }
 
@@ -790,3 +792,211 @@ async fn indirect_buffer_offsets(ctx: TestingContext) {
         data[..half].iter().all(|b| *b == u8::MAX) && data[half..].iter().all(|b| *b == 0);
     assert!(succeeded);
 }
+
+#[gpu_test]
+static MULTI_DRAW_INDIRECT_COUNT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+            .features(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        // The count is clamped to `max_count` and the invalid draw is skipped.
+        let data = multi_draw_indirect_count(ctx, 100).await;
+        assert!(data.iter().all(|b| *b == u8::MAX));
+    });
+
+#[gpu_test]
+static MULTI_DRAW_INDIRECT_COUNT_PARTIAL: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+            .features(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        // Only the bottom left triangle is drawn.
+        let data = multi_draw_indirect_count(ctx, 1).await;
+        assert_eq!(data[250 * 256 + 5], u8::MAX);
+        assert_eq!(data[5 * 256 + 250], 0);
+    });
+
+/// Draws the bottom left and the top right halves of the target with
+/// `multi_draw_indirect_count`, with an out of bounds draw in between.
+async fn multi_draw_indirect_count(ctx: TestingContext, count: u32) -> Vec<u8> {
+    #[rustfmt::skip]
+    let vertex_buffer_content: [f32; 12] = [
+        // Bottom left triangle
+        -1.0, -1.0,
+        1.0, -1.0,
+        -1.0, 1.0,
+        // Top right triangle
+        1.0, -1.0,
+        1.0, 1.0,
+        -1.0, 1.0,
+    ];
+    let indirect_args = [
+        wgpu::util::DrawIndirectArgs {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance: 0,
+        },
+        wgpu::util::DrawIndirectArgs {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 6,
+            first_instance: 0,
+        },
+        wgpu::util::DrawIndirectArgs {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 3,
+            first_instance: 0,
+        },
+    ];
+
+    let vertex_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice::<f32, u8>(&vertex_buffer_content),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    let indirect_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice(&indirect_args),
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+    let count_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::bytes_of(&count),
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(
+                "
+                @vertex
+                fn vs_main(@location(0) position: vec2f) -> @builtin(position) vec4f {
+                    return vec4f(position, 0.0, 1.0);
+                }
+
+                @fragment
+                fn fs_main() -> @location(0) vec4f {
+                    return vec4f(1.0);
+                }
+                "
+                .into(),
+            ),
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 8,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &vertex_attr_array![0 => Float32x2],
+                }],
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::R8Unorm,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let out_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 256,
+            height: 256,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let out_texture_view = out_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * 256,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations::default(),
+                resolve_target: None,
+                view: &out_texture_view,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        rpass.set_pipeline(&pipeline);
+        rpass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        rpass.multi_draw_indirect_count(&indirect_buffer, 0, &count_buffer, 0, 3);
+    }
+
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &out_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: 256,
+            height: 256,
+            depth_or_array_layers: 1,
+        },
+    );
+
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+
+    slice.get_mapped_range().to_vec()
+}
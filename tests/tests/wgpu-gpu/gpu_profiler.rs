@@ -0,0 +1,86 @@
+use wgpu::{
+    util::{GpuProfiler, GpuProfilerSettings},
+    Features, InstanceFlags,
+};
+use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, GpuTestInitializer, TestParameters};
+
+pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
+    vec.push(GPU_PROFILER);
+}
+
+#[gpu_test]
+static GPU_PROFILER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .expect_fail(FailureCase::webgl2())
+            .features(Features::TIMESTAMP_QUERY | Features::TIMESTAMP_QUERY_INSIDE_ENCODERS)
+            .instance_flags(InstanceFlags::AUTOMATIC_TIMESTAMP_NORMALIZATION),
+    )
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1024,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut profiler = GpuProfiler::new(
+            &ctx.device,
+            &ctx.queue,
+            GpuProfilerSettings {
+                max_frames_in_flight: 2,
+                ..Default::default()
+            },
+        );
+
+        for _ in 0..3 {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            profiler.scope("frame", &mut encoder, |profiler, encoder| {
+                profiler.scope("clear", encoder, |_, encoder| {
+                    encoder.clear_buffer(&buffer, 0, None);
+                });
+                let timestamp_writes = profiler.compute_pass_timestamp_writes("pass");
+                encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: None,
+                    timestamp_writes,
+                });
+            });
+            profiler.resolve_queries(&mut encoder);
+            ctx.queue.submit([encoder.finish()]);
+            profiler.end_frame();
+        }
+
+        ctx.device.poll(wgpu::PollType::wait()).unwrap();
+
+        // The third frame was ended while the first two were in flight.
+        let frames = [
+            profiler.process_finished_frame().unwrap(),
+            profiler.process_finished_frame().unwrap(),
+        ];
+        assert_eq!(profiler.process_finished_frame(), None);
+
+        for scopes in &frames {
+            let [frame] = scopes.as_slice() else {
+                panic!("expected a single root scope, got {scopes:?}");
+            };
+            assert_eq!(frame.label, "frame");
+            assert!(frame.duration_ns() >= 0.0);
+
+            let labels: Vec<_> = frame
+                .nested_scopes
+                .iter()
+                .map(|scope| scope.label.as_ref())
+                .collect();
+            assert_eq!(labels, ["clear", "pass"]);
+            for scope in &frame.nested_scopes {
+                assert!(scope.duration_ns() >= 0.0);
+                assert!(scope.nested_scopes.is_empty());
+            }
+        }
+
+        let trace = wgpu::util::chrome_trace(frames.iter().map(Vec::as_slice));
+        assert!(trace.starts_with(r#"{"traceEvents":[{"name":"frame","ph":"X","pid":0,"tid":0"#));
+        assert_eq!(trace.matches(r#""ph":"X""#).count(), 6);
+    });
@@ -0,0 +1,201 @@
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt, DrawIndirectArgs, IndirectVertexBufferArgs},
+    vertex_attr_array,
+};
+use wgpu_test::{
+    gpu_test, GpuTestConfiguration, GpuTestInitializer, TestParameters, TestingContext,
+};
+
+pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
+    vec.push(EXECUTE_INDIRECT_COUNT);
+}
+
+const WIDTH: u32 = 4;
+
+/// Every record switches the vertex buffer to a single point, which writes its
+/// value to one texel of the target.
+#[gpu_test]
+static EXECUTE_INDIRECT_COUNT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::INDIRECT_COMMANDS))
+    .run_async(execute_indirect_count);
+
+async fn execute_indirect_count(ctx: TestingContext) {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 8,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &vertex_attr_array![0 => Float32, 1 => Uint32],
+                }],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::PointList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::R32Uint.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    // One point at the center of each texel, with the value it writes.
+    let points = (0..WIDTH)
+        .flat_map(|i| {
+            let x = (i as f32 + 0.5) / WIDTH as f32 * 2.0 - 1.0;
+            [x.to_bits(), i + 1]
+        })
+        .collect::<Vec<u32>>();
+    let data_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("data"),
+        contents: bytemuck::cast_slice(&points),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+
+    let layout = wgpu::IndirectCommandLayout {
+        vertex_buffers: 1,
+        index_buffer: false,
+        push_constant_stages: wgpu::ShaderStages::empty(),
+        push_constant_offset: 0,
+        push_constant_size: 0,
+        indexed: false,
+    };
+    let draw = DrawIndirectArgs {
+        vertex_count: 1,
+        instance_count: 1,
+        first_vertex: 0,
+        first_instance: 0,
+    };
+    let mut records = Vec::new();
+    // Texel 2 is skipped, because its vertex buffer is out of bounds, and
+    // texel 3 because the count excludes its record.
+    for offset in [0, 8, 64, 24] {
+        let vertex_buffer = IndirectVertexBufferArgs { offset, size: 8 };
+        records.extend_from_slice(vertex_buffer.as_bytes());
+        records.extend_from_slice(draw.as_bytes());
+    }
+    assert_eq!(records.len() as u64, layout.stride() * 4);
+    let indirect_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("records"),
+        contents: &records,
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+    let count_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("count"),
+        contents: bytemuck::bytes_of(&3u32),
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+
+    let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R32Uint,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.create_view(&wgpu::TextureViewDescriptor::default()),
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        rpass.set_pipeline(&pipeline);
+        rpass.execute_indirect_count(
+            &layout,
+            &data_buffer,
+            &indirect_buffer,
+            0,
+            &count_buffer,
+            0,
+            4,
+        );
+    }
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &target,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: None,
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let data = slice.get_mapped_range();
+    let texels: &[u32] = bytemuck::cast_slice(&data[..WIDTH as usize * 4]);
+    assert_eq!(texels, [1, 2, 0, 0]);
+}
+
+const SHADER_SRC: &str = "
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) @interpolate(flat) value: u32,
+}
+
+@vertex
+fn vs_main(@location(0) x: f32, @location(1) value: u32) -> VertexOutput {
+    return VertexOutput(vec4f(x, 0.0, 0.0, 1.0), value);
+}
+
+@fragment
+fn fs_main(@location(0) @interpolate(flat) value: u32) -> @location(0) u32 {
+    return value;
+}
+";
@@ -17,6 +17,7 @@ mod bind_groups;
 mod binding_array;
 mod buffer;
 mod buffer_copy;
+mod buffer_suballocation;
 mod buffer_usages;
 mod clear_texture;
 mod clip_distances;
@@ -31,7 +32,10 @@ mod encoder;
 mod external_image_copy;
 mod external_texture;
 mod float32_filterable;
+mod framebuffer_fetch;
+mod gpu_profiler;
 mod image_atomics;
+mod indirect_commands;
 mod instance;
 mod life_cycle;
 mod mem_leaks;
@@ -47,19 +51,24 @@ mod push_constants;
 mod query_set;
 mod queue_transfer;
 mod ray_tracing;
+mod render_bundle_inheritance;
 mod render_pass_ownership;
 mod render_target;
 mod resource_descriptor_accessor;
 mod resource_error;
+mod reusable_command_buffer;
 mod samplers;
 mod scissor_tests;
 mod shader;
+mod shader_bounds_report;
 mod shader_primitive_index;
 mod shader_view_format;
 mod subgroup_operations;
+mod texel_decode;
 mod texture_binding;
 mod texture_blit;
 mod texture_bounds;
+mod texture_buffer_copies;
 mod texture_view_creation;
 mod timestamp_normalization;
 mod timestamp_query;
@@ -67,6 +76,7 @@ mod transfer;
 mod transition_resources;
 mod vertex_formats;
 mod vertex_indices;
+mod video_decode;
 mod write_texture;
 mod zero_init_texture_after_discard;
 
@@ -78,6 +88,7 @@ fn all_tests() -> Vec<wgpu_test::GpuTestInitializer> {
     bind_groups::all_tests(&mut tests);
     binding_array::all_tests(&mut tests);
     buffer_copy::all_tests(&mut tests);
+    buffer_suballocation::all_tests(&mut tests);
     buffer_usages::all_tests(&mut tests);
     buffer::all_tests(&mut tests);
     clear_texture::all_tests(&mut tests);
@@ -91,7 +102,10 @@ fn all_tests() -> Vec<wgpu_test::GpuTestInitializer> {
     encoder::all_tests(&mut tests);
     external_texture::all_tests(&mut tests);
     float32_filterable::all_tests(&mut tests);
+    framebuffer_fetch::all_tests(&mut tests);
+    gpu_profiler::all_tests(&mut tests);
     image_atomics::all_tests(&mut tests);
+    indirect_commands::all_tests(&mut tests);
     instance::all_tests(&mut tests);
     life_cycle::all_tests(&mut tests);
     mem_leaks::all_tests(&mut tests);
@@ -117,19 +131,24 @@ fn all_tests() -> Vec<wgpu_test::GpuTestInitializer> {
     regression::issue_6317::all_tests(&mut tests);
     regression::issue_6467::all_tests(&mut tests);
     regression::issue_6827::all_tests(&mut tests);
+    render_bundle_inheritance::all_tests(&mut tests);
     render_pass_ownership::all_tests(&mut tests);
     render_target::all_tests(&mut tests);
     resource_descriptor_accessor::all_tests(&mut tests);
+    reusable_command_buffer::all_tests(&mut tests);
     resource_error::all_tests(&mut tests);
     samplers::all_tests(&mut tests);
     scissor_tests::all_tests(&mut tests);
     shader_primitive_index::all_tests(&mut tests);
     shader_view_format::all_tests(&mut tests);
     shader::all_tests(&mut tests);
+    shader_bounds_report::all_tests(&mut tests);
     subgroup_operations::all_tests(&mut tests);
+    texel_decode::all_tests(&mut tests);
     texture_binding::all_tests(&mut tests);
     texture_blit::all_tests(&mut tests);
     texture_bounds::all_tests(&mut tests);
+    texture_buffer_copies::all_tests(&mut tests);
     texture_view_creation::all_tests(&mut tests);
     timestamp_normalization::all_tests(&mut tests);
     timestamp_query::all_tests(&mut tests);
@@ -137,6 +156,7 @@ fn all_tests() -> Vec<wgpu_test::GpuTestInitializer> {
     transition_resources::all_tests(&mut tests);
     vertex_formats::all_tests(&mut tests);
     vertex_indices::all_tests(&mut tests);
+    video_decode::all_tests(&mut tests);
     write_texture::all_tests(&mut tests);
     zero_init_texture_after_discard::all_tests(&mut tests);
 
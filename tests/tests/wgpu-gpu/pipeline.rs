@@ -7,6 +7,10 @@ pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
         RENDER_PIPELINE_DEFAULT_LAYOUT_BAD_MODULE,
         RENDER_PIPELINE_DEFAULT_LAYOUT_BAD_BGL_INDEX,
         NO_TARGETLESS_RENDER,
+        COMPUTE_PIPELINE_ASYNC,
+        RENDER_PIPELINE_ASYNC,
+        RENDER_PIPELINE_ASYNC_ERROR,
+        RENDER_PIPELINES_SHARING_SHADERS,
     ]);
 }
 
@@ -234,3 +238,295 @@ static NO_TARGETLESS_RENDER: GpuTestConfiguration = GpuTestConfiguration::new()
             )),
         )
     });
+
+#[gpu_test]
+static COMPUTE_PIPELINE_ASYNC: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults())
+            .enable_noop(),
+    )
+    .run_async(|ctx| async move {
+        let module = ctx.device.create_shader_module(TRIVIAL_COMPUTE_SHADER_DESC);
+
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+                label: Some("compute pipeline"),
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let pipeline = pipeline.await.unwrap();
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        ctx.queue.submit([encoder.finish()]);
+    });
+
+#[gpu_test]
+static RENDER_PIPELINE_ASYNC: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().enable_noop())
+    .run_async(|ctx| async move {
+        let vs_module = ctx.device.create_shader_module(TRIVIAL_VERTEX_SHADER_DESC);
+        let fs_module = ctx
+            .device
+            .create_shader_module(TRIVIAL_FRAGMENT_SHADER_DESC);
+
+        // Request several pipelines at once, so that they are created concurrently.
+        let pipelines = (0..4)
+            .map(|_| {
+                ctx.device
+                    .create_render_pipeline_async(&wgpu::RenderPipelineDescriptor {
+                        label: Some("render pipeline"),
+                        layout: None,
+                        vertex: wgpu::VertexState {
+                            module: &vs_module,
+                            entry_point: Some("main"),
+                            compilation_options: Default::default(),
+                            buffers: &[],
+                        },
+                        primitive: Default::default(),
+                        depth_stencil: None,
+                        multisample: Default::default(),
+                        fragment: Some(wgpu::FragmentState {
+                            module: &fs_module,
+                            entry_point: Some("main"),
+                            compilation_options: Default::default(),
+                            targets: &[Some(wgpu::ColorTargetState {
+                                format: wgpu::TextureFormat::Rgba8Unorm,
+                                blend: None,
+                                write_mask: wgpu::ColorWrites::ALL,
+                            })],
+                        }),
+                        multiview: None,
+                        cache: None,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        for pipeline in pipelines {
+            pipeline.await.unwrap();
+        }
+    });
+
+// Errors from asynchronous pipeline creation are returned by the future, and
+// are not reported to the error scopes.
+#[gpu_test]
+static RENDER_PIPELINE_ASYNC_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().enable_noop())
+    .run_async(|ctx| async move {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let module = ctx.device.create_shader_module(TRIVIAL_VERTEX_SHADER_DESC);
+        let pipeline = ctx
+            .device
+            .create_render_pipeline_async(&wgpu::RenderPipelineDescriptor {
+                label: Some("render pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: Some("doesn't exist"),
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: Default::default(),
+                depth_stencil: None,
+                multisample: Default::default(),
+                fragment: None,
+                multiview: None,
+                cache: None,
+            });
+
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let error = pipeline.await.unwrap_err();
+        assert!(
+            matches!(error, wgpu::Error::Validation { .. }),
+            "unexpected error: {error}"
+        );
+
+        assert!(ctx.device.pop_error_scope().await.is_none());
+    });
+
+const SHARED_SHADER_SRC: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+    let uv = vec2f(f32(index & 1u), f32(index >> 1u)) * 2.0;
+    return vec4f(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return vec4f(0.5);
+}
+";
+
+// Pipelines created from the same shaders, which only differ in their fixed-function state,
+// may share their compiled shaders (e.g. as Vulkan shader objects). Each of them must still
+// draw with its own state, including after switching from another one.
+#[gpu_test]
+static RENDER_PIPELINES_SHARING_SHADERS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_async(|ctx| async move {
+        const WIDTH: u32 = 4;
+        const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHARED_SHADER_SRC.into()),
+            });
+        let create_pipeline = |target: wgpu::ColorTargetState, cull_mode| {
+            ctx.device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    vertex: wgpu::VertexState {
+                        module: &module,
+                        entry_point: Some("vs_main"),
+                        compilation_options: Default::default(),
+                        buffers: &[],
+                    },
+                    primitive: wgpu::PrimitiveState {
+                        cull_mode,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    fragment: Some(wgpu::FragmentState {
+                        module: &module,
+                        entry_point: Some("fs_main"),
+                        compilation_options: Default::default(),
+                        targets: &[Some(target)],
+                    }),
+                    multiview: None,
+                    cache: None,
+                })
+        };
+        let replace = create_pipeline(FORMAT.into(), None);
+        let add_red = create_pipeline(
+            wgpu::ColorTargetState {
+                format: FORMAT,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::One,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha: wgpu::BlendComponent::REPLACE,
+                }),
+                write_mask: wgpu::ColorWrites::RED | wgpu::ColorWrites::ALPHA,
+            },
+            None,
+        );
+        // The full-screen triangle is counter-clockwise, so front-facing.
+        let culled = create_pipeline(FORMAT.into(), Some(wgpu::Face::Front));
+
+        let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.create_view(&wgpu::TextureViewDescriptor::default()),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.25,
+                            g: 0.25,
+                            b: 0.25,
+                            a: 0.25,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            // One texel per draw, going back to the first pipeline after culling everything.
+            for (x, pipeline) in [&replace, &add_red, &culled, &replace]
+                .into_iter()
+                .enumerate()
+            {
+                rpass.set_viewport(x as f32, 0.0, 1.0, 1.0, 0.0, 1.0);
+                rpass.set_pipeline(pipeline);
+                rpass.draw(0..3, 0..1);
+            }
+        }
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &target,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        ctx.queue.submit([encoder.finish()]);
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let data = slice.get_mapped_range();
+        let expected: [[u8; 4]; WIDTH as usize] = [
+            [128, 128, 128, 128],
+            [192, 64, 64, 128],
+            [64, 64, 64, 64],
+            [128, 128, 128, 128],
+        ];
+        for (texel, expected) in data[..WIDTH as usize * 4].chunks(4).zip(expected) {
+            assert!(
+                texel.iter().zip(expected).all(|(&a, b)| a.abs_diff(b) <= 1),
+                "expected {expected:?}, got {texel:?}"
+            );
+        }
+    });
@@ -0,0 +1,267 @@
+//! Tests that [`wgpu::texel`] decodes compressed blocks the same way the GPU does.
+
+use nanorand::Rng;
+use wgpu::{AstcBlock, AstcChannel, TextureFormat};
+use wgpu_test::{
+    gpu_test, GpuTestConfiguration, GpuTestInitializer, TestParameters, TestingContext,
+};
+
+pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
+    vec.extend([TEXEL_DECODE_BC, TEXEL_DECODE_ETC2, TEXEL_DECODE_ASTC]);
+}
+
+/// Number of blocks along each side of the test textures.
+const BLOCKS: u32 = 16;
+
+const SHADER: &str = r#"
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var<storage, read_write> texels: array<vec4f>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    let size = textureDimensions(source);
+    if id.x < size.x && id.y < size.y {
+        texels[id.y * size.x + id.x] = textureLoad(source, id.xy, 0);
+    }
+}
+"#;
+
+/// Uploads random blocks of `format`, reads them back through `textureLoad`, and compares
+/// the result with [`TextureFormat::decode_texels`].
+async fn check_format(ctx: &TestingContext, format: TextureFormat, seed: u64) {
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_copy_size(None).unwrap();
+    let (width, height) = (block_width * BLOCKS, block_height * BLOCKS);
+    let bytes_per_row = block_size * BLOCKS;
+
+    let mut generator = nanorand::WyRand::new_seed(seed);
+    let mut data = vec![0; (bytes_per_row * BLOCKS) as usize];
+    generator.fill_bytes(&mut data);
+
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    ctx.queue.write_texture(
+        texture.as_image_copy(),
+        &data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(bytes_per_row),
+            rows_per_image: None,
+        },
+        size,
+    );
+
+    let buffer_size = u64::from(width * height) * 16;
+    let storage = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: buffer_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: buffer_size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            cache: None,
+        });
+    let view = texture.create_view(&Default::default());
+    let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: storage.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(width.div_ceil(8), height.div_ceil(8), 1);
+    }
+    encoder.copy_buffer_to_buffer(&storage, 0, &readback, 0, buffer_size);
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let gpu: Vec<[f32; 4]> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+
+    let cpu = format
+        .decode_texels(
+            wgpu::TextureAspect::All,
+            &data,
+            bytes_per_row,
+            width,
+            height,
+        )
+        .unwrap();
+
+    for (i, (gpu, cpu)) in gpu.iter().zip(&cpu).enumerate() {
+        let wgpu::texel::Texel::Float(cpu) = *cpu else {
+            panic!("{format:?} decoded to {cpu:?}");
+        };
+        // Drivers are lax about rejecting reserved ASTC encodings, such as HDR endpoints in LDR
+        // formats, so only compare blocks the spec gives a value.
+        if format.is_astc() && cpu == [1.0, 0.0, 1.0, 1.0] {
+            continue;
+        }
+        let matches = gpu.iter().zip(cpu).all(|(&gpu, cpu)| {
+            (gpu.is_nan() && cpu.is_nan()) || (gpu - cpu).abs() <= 0.02 * cpu.abs().max(1.0)
+        });
+        assert!(
+            matches,
+            "{format:?} texel ({}, {}) of block {:02x?}: GPU decoded {gpu:?}, CPU decoded {cpu:?}",
+            i as u32 % width,
+            i as u32 / width,
+            {
+                let block = (i as u32 / width / block_height * BLOCKS
+                    + i as u32 % width / block_width)
+                    * block_size;
+                &data[block as usize..(block + block_size) as usize]
+            },
+        );
+    }
+}
+
+async fn check_formats(ctx: &TestingContext, formats: &[TextureFormat]) {
+    for (seed, &format) in formats.iter().enumerate() {
+        if ctx.device.features().contains(format.required_features()) {
+            check_format(ctx, format, seed as u64).await;
+        }
+    }
+}
+
+#[gpu_test]
+static TEXEL_DECODE_BC: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::TEXTURE_COMPRESSION_BC)
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        check_formats(
+            &ctx,
+            &[
+                TextureFormat::Bc1RgbaUnorm,
+                TextureFormat::Bc1RgbaUnormSrgb,
+                TextureFormat::Bc2RgbaUnorm,
+                TextureFormat::Bc2RgbaUnormSrgb,
+                TextureFormat::Bc3RgbaUnorm,
+                TextureFormat::Bc3RgbaUnormSrgb,
+                TextureFormat::Bc4RUnorm,
+                TextureFormat::Bc4RSnorm,
+                TextureFormat::Bc5RgUnorm,
+                TextureFormat::Bc5RgSnorm,
+                TextureFormat::Bc6hRgbUfloat,
+                TextureFormat::Bc6hRgbFloat,
+                TextureFormat::Bc7RgbaUnorm,
+                TextureFormat::Bc7RgbaUnormSrgb,
+            ],
+        )
+        .await;
+    });
+
+#[gpu_test]
+static TEXEL_DECODE_ETC2: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::TEXTURE_COMPRESSION_ETC2)
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        check_formats(
+            &ctx,
+            &[
+                TextureFormat::Etc2Rgb8Unorm,
+                TextureFormat::Etc2Rgb8UnormSrgb,
+                TextureFormat::Etc2Rgb8A1Unorm,
+                TextureFormat::Etc2Rgb8A1UnormSrgb,
+                TextureFormat::Etc2Rgba8Unorm,
+                TextureFormat::Etc2Rgba8UnormSrgb,
+                TextureFormat::EacR11Unorm,
+                TextureFormat::EacR11Snorm,
+                TextureFormat::EacRg11Unorm,
+                TextureFormat::EacRg11Snorm,
+            ],
+        )
+        .await;
+    });
+
+#[gpu_test]
+static TEXEL_DECODE_ASTC: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::TEXTURE_COMPRESSION_ASTC)
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let blocks = [
+            AstcBlock::B4x4,
+            AstcBlock::B5x4,
+            AstcBlock::B5x5,
+            AstcBlock::B6x5,
+            AstcBlock::B6x6,
+            AstcBlock::B8x5,
+            AstcBlock::B8x6,
+            AstcBlock::B8x8,
+            AstcBlock::B10x5,
+            AstcBlock::B10x6,
+            AstcBlock::B10x8,
+            AstcBlock::B10x10,
+            AstcBlock::B12x10,
+            AstcBlock::B12x12,
+        ];
+        let formats: Vec<_> = [AstcChannel::Unorm, AstcChannel::UnormSrgb, AstcChannel::Hdr]
+            .into_iter()
+            .flat_map(|channel| {
+                blocks
+                    .iter()
+                    .map(move |&block| TextureFormat::Astc { block, channel })
+            })
+            .collect();
+        check_formats(&ctx, &formats).await;
+    });
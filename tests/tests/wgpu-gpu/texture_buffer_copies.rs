@@ -0,0 +1,391 @@
+//! Tests for copies between textures and buffers of the formats and dimensions that some
+//! backends need to emulate, like integer, depth and stencil formats, and cube maps, or
+//! don't support, like compressed formats.
+
+use nanorand::Rng;
+use wgpu::{TextureAspect, TextureDimension, TextureFormat};
+use wgpu_test::{
+    fail, gpu_test, GpuTestConfiguration, GpuTestInitializer, TestParameters, TestingContext,
+};
+
+pub fn all_tests(vec: &mut Vec<GpuTestInitializer>) {
+    vec.extend([
+        TEXTURE_BUFFER_COPIES_INTEGER,
+        TEXTURE_BUFFER_COPIES_DEPTH_STENCIL,
+        TEXTURE_BUFFER_COPIES_DEPTH32FLOAT_STENCIL8,
+        TEXTURE_BUFFER_COPIES_COMPRESSED,
+    ]);
+}
+
+/// Value of the bytes of the buffer that the copy must not touch.
+const UNTOUCHED: u8 = 0xAA;
+
+/// Shapes of texture the copies are tested with, as the dimension and the size.
+///
+/// Square textures with a multiple of 6 layers are cube maps on GL.
+const SHAPES: &[(TextureDimension, u32, u32, u32)] = &[
+    (TextureDimension::D2, 17, 5, 1),
+    (TextureDimension::D2, 9, 4, 3),
+    (TextureDimension::D2, 8, 8, 6),
+    (TextureDimension::D2, 8, 8, 12),
+    (TextureDimension::D3, 7, 3, 3),
+];
+
+fn create_texture(
+    ctx: &TestingContext,
+    format: TextureFormat,
+    dimension: TextureDimension,
+    size: wgpu::Extent3d,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(&format!("{format:?} {dimension:?} {size:?}")),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension,
+        format,
+        usage: usage | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    })
+}
+
+/// Copies `aspect` of `texture` into a buffer at an offset that isn't a multiple of 4 where
+/// possible, and checks that the bytes of each row are `expected` and the others are untouched.
+async fn check_copy_to_buffer(
+    ctx: &TestingContext,
+    texture: &wgpu::Texture,
+    aspect: TextureAspect,
+    expected: &[u8],
+) {
+    let format = texture.format();
+    let size = texture.size();
+    let texel_size = format.block_copy_size(Some(aspect)).unwrap();
+    let row_size = size.width * texel_size;
+    let layers = size.depth_or_array_layers;
+    let bytes_per_row = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let offset = if format.is_depth_stencil_format() {
+        4
+    } else {
+        texel_size
+    };
+    let buffer_size = (offset + bytes_per_row * size.height * layers + 4).next_multiple_of(4);
+
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: buffer_size.into(),
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: true,
+    });
+    buffer.slice(..).get_mapped_range_mut().fill(UNTOUCHED);
+    buffer.unmap();
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: offset.into(),
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(size.height),
+            },
+        },
+        size,
+    );
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let data = slice.get_mapped_range();
+
+    let mut expected_buffer = vec![UNTOUCHED; buffer_size as usize];
+    for (row, expected_row) in expected.chunks(row_size as usize).enumerate() {
+        let start = (offset + row as u32 * bytes_per_row) as usize;
+        expected_buffer[start..start + expected_row.len()].copy_from_slice(expected_row);
+    }
+    if let Some(index) = (0..data.len()).find(|&i| data[i] != expected_buffer[i]) {
+        panic!(
+            "{format:?} {aspect:?} of {:?} {size:?}: byte {index} of the buffer is {:#04x}, \
+             expected {:#04x}",
+            texture.dimension(),
+            data[index],
+            expected_buffer[index],
+        );
+    }
+}
+
+/// Writes random texels into `aspect` of a new texture, optionally copies it into another
+/// texture, and checks that copying it to a buffer gives back the same bytes.
+async fn check_round_trip(
+    ctx: &TestingContext,
+    format: TextureFormat,
+    aspect: TextureAspect,
+    through_texture: bool,
+    seed: u64,
+) {
+    for &(dimension, width, height, layers) in SHAPES {
+        if dimension == TextureDimension::D3 && format.is_depth_stencil_format() {
+            continue;
+        }
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: layers,
+        };
+        let texel_size = format.block_copy_size(Some(aspect)).unwrap();
+        let mut data = vec![0; (width * height * layers * texel_size) as usize];
+        nanorand::WyRand::new_seed(seed).fill_bytes(&mut data);
+
+        let texture = create_texture(ctx, format, dimension, size, wgpu::TextureUsages::empty());
+        ctx.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect,
+            },
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * texel_size),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+
+        let texture = if through_texture {
+            let copy = create_texture(ctx, format, dimension, size, wgpu::TextureUsages::empty());
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_texture_to_texture(texture.as_image_copy(), copy.as_image_copy(), size);
+            ctx.queue.submit([encoder.finish()]);
+            copy
+        } else {
+            texture
+        };
+
+        check_copy_to_buffer(ctx, &texture, aspect, &data).await;
+    }
+}
+
+#[gpu_test]
+static TEXTURE_BUFFER_COPIES_INTEGER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(wgpu_test::FailureCase::webgl2()))
+    .run_async(|ctx| async move {
+        let formats = [
+            TextureFormat::R8Uint,
+            TextureFormat::Rg8Sint,
+            TextureFormat::Rgba8Uint,
+            TextureFormat::R16Sint,
+            TextureFormat::Rg16Uint,
+            TextureFormat::Rgba16Sint,
+            TextureFormat::R32Uint,
+            TextureFormat::Rg32Sint,
+            TextureFormat::Rgba32Uint,
+            TextureFormat::Rgb10a2Uint,
+        ];
+        for (seed, format) in formats.into_iter().enumerate() {
+            check_round_trip(&ctx, format, TextureAspect::All, false, seed as u64).await;
+        }
+    });
+
+#[gpu_test]
+static TEXTURE_BUFFER_COPIES_DEPTH_STENCIL: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES),
+    )
+    .run_async(|ctx| async move {
+        for through_texture in [false, true] {
+            check_round_trip(
+                &ctx,
+                TextureFormat::Stencil8,
+                TextureAspect::All,
+                through_texture,
+                0,
+            )
+            .await;
+            check_round_trip(
+                &ctx,
+                TextureFormat::Depth16Unorm,
+                TextureAspect::All,
+                through_texture,
+                1,
+            )
+            .await;
+            check_round_trip(
+                &ctx,
+                TextureFormat::Depth24PlusStencil8,
+                TextureAspect::StencilOnly,
+                through_texture,
+                2,
+            )
+            .await;
+        }
+
+        // 32-bit float depth can't be written from buffers, so clear it with a render pass.
+        let size = wgpu::Extent3d {
+            width: 9,
+            height: 4,
+            depth_or_array_layers: 2,
+        };
+        let texture = create_texture(
+            &ctx,
+            TextureFormat::Depth32Float,
+            TextureDimension::D2,
+            size,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for layer in 0..size.depth_or_array_layers {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                base_array_layer: layer,
+                array_layer_count: Some(1),
+                ..Default::default()
+            });
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.25 * (layer + 1) as f32),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+        }
+        ctx.queue.submit([encoder.finish()]);
+        let expected: Vec<u8> = (0..size.depth_or_array_layers)
+            .flat_map(|layer| {
+                let texel = (0.25 * (layer + 1) as f32).to_le_bytes();
+                texel.repeat((size.width * size.height) as usize)
+            })
+            .collect();
+        check_copy_to_buffer(&ctx, &texture, TextureAspect::All, &expected).await;
+    });
+
+#[gpu_test]
+static TEXTURE_BUFFER_COPIES_DEPTH32FLOAT_STENCIL8: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(
+            TestParameters::default()
+                .features(wgpu::Features::DEPTH32FLOAT_STENCIL8)
+                .downlevel_flags(wgpu::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES),
+        )
+        .run_async(|ctx| async move {
+            check_round_trip(
+                &ctx,
+                TextureFormat::Depth32FloatStencil8,
+                TextureAspect::StencilOnly,
+                false,
+                0,
+            )
+            .await;
+        });
+
+/// Copies of compressed textures to buffers copy whole blocks, or fail validation where
+/// they aren't supported.
+#[gpu_test]
+static TEXTURE_BUFFER_COPIES_COMPRESSED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        let format = TextureFormat::Bc1RgbaUnorm;
+        let size = wgpu::Extent3d {
+            width: 8,
+            height: 8,
+            depth_or_array_layers: 1,
+        };
+        let block_size = format.block_copy_size(None).unwrap();
+        let row_size = size.width / 4 * block_size;
+        let mut data = vec![0; (row_size * size.height / 4) as usize];
+        nanorand::WyRand::new_seed(0).fill_bytes(&mut data);
+
+        let texture = create_texture(
+            &ctx,
+            format,
+            TextureDimension::D2,
+            size,
+            wgpu::TextureUsages::empty(),
+        );
+        ctx.queue.write_texture(
+            texture.as_image_copy(),
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(row_size),
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * size.height / 4).into(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let copy = |encoder: &mut wgpu::CommandEncoder| {
+            encoder.copy_texture_to_buffer(
+                texture.as_image_copy(),
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &buffer,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                        rows_per_image: None,
+                    },
+                },
+                size,
+            )
+        };
+        if !ctx
+            .adapter_downlevel_capabilities
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES)
+        {
+            fail(
+                &ctx.device,
+                || {
+                    copy(&mut encoder);
+                    encoder.finish()
+                },
+                Some("COMPRESSED_TEXTURE_TO_BUFFER_COPIES"),
+            );
+            return;
+        }
+        copy(&mut encoder);
+        ctx.queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let mapped = slice.get_mapped_range();
+        for (row, expected) in data.chunks(row_size as usize).enumerate() {
+            let start = row * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+            assert_eq!(
+                &mapped[start..start + expected.len()],
+                expected,
+                "block row {row}"
+            );
+        }
+    });
@@ -381,6 +381,7 @@ async fn vertex_index_common(ctx: TestingContext) {
                         depth_stencil: None,
                         sample_count: 1,
                         multiview: None,
+                        inherited_bind_group_layouts: &[],
                     },
                 )),
             };
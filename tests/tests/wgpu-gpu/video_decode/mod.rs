@@ -0,0 +1,502 @@
+//! Tests for decoding video pictures.
+//!
+//! The fixtures are hand-written streams of two pictures: an IDR picture made of PCM blocks,
+//! whose samples are stored uncompressed, followed by a P picture predicted from it. The blocks
+//! of the P picture are skipped, so copied from the IDR picture, except for its last row of
+//! blocks which are PCM blocks with new samples. Both pictures are read back and compared to the
+//! samples of [`luma`] and [`chroma`].
+//!
+//! - `idr_p.h264` is a 64x64 Constrained Baseline stream, of 16x16 macroblocks.
+//! - `idr_p_main10.h265` is a 160x160 Main 10 stream, of 32x32 coding tree blocks made of a
+//!   single coding unit.
+
+use wgpu::video::{h264, h265};
+use wgpu_test::{
+    gpu_test, GpuTestConfiguration, GpuTestInitializer, TestParameters, TestingContext,
+};
+
+pub fn all_tests(tests: &mut Vec<GpuTestInitializer>) {
+    tests.extend([DECODE_H264_NV12, DECODE_H265_P010]);
+}
+
+const H264_STREAM: &[u8] = include_bytes!("idr_p.h264");
+const H265_STREAM: &[u8] = include_bytes!("idr_p_main10.h265");
+
+/// Splits an Annex B byte stream into its NAL units, each starting with a three-byte
+/// start code.
+fn nal_units(stream: &[u8]) -> Vec<&[u8]> {
+    let starts = stream
+        .windows(3)
+        .enumerate()
+        .filter_map(|(i, window)| (window == [0, 0, 1]).then_some(i))
+        .collect::<Vec<_>>();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            // NAL units end with a one bit, so a zero byte before a start code belongs to it.
+            let end = starts.get(i + 1).map_or(stream.len(), |&next| {
+                if stream[next - 1] == 0 {
+                    next - 1
+                } else {
+                    next
+                }
+            });
+            &stream[start..end]
+        })
+        .collect()
+}
+
+/// The luma sample the stream codes at `x`, `y` of `picture` (0 for the samples of the
+/// IDR picture, 1 for the new samples of the P picture).
+fn luma(picture: u32, x: u32, y: u32, bit_depth: u32) -> u32 {
+    let max = (1 << bit_depth) - 1;
+    let value = match bit_depth {
+        8 => (x * 3 + y * 7) & max,
+        _ => (x * 13 + y * 7) & max,
+    };
+    if picture == 0 {
+        value
+    } else {
+        max - value
+    }
+}
+
+/// The Cb (`plane` 0) or Cr (`plane` 1) sample the stream codes at `x`, `y` of `picture`.
+fn chroma(picture: u32, plane: u32, x: u32, y: u32, bit_depth: u32) -> u32 {
+    let max = (1 << bit_depth) - 1;
+    let value = match (bit_depth, plane) {
+        (8, 0) => (x * 5 + y * 2 + 64) & max,
+        (8, _) => (x * 2 + y * 5 + 128) & max,
+        (_, 0) => (x * 11 + y * 5 + 256) & max,
+        (_, _) => (x * 5 + y * 11 + 512) & max,
+    };
+    if picture == 0 {
+        value
+    } else {
+        max - value
+    }
+}
+
+/// Creates a decoder for the pictures of a stream, and the two pictures to decode into.
+///
+/// Returns `None` if the device can't decode them, as devices only decode some codecs.
+fn create_decoder(
+    ctx: &TestingContext,
+    codec: wgpu::VideoCodec,
+    format: wgpu::TextureFormat,
+    size: u32,
+) -> Option<(wgpu::VideoDecoder, [wgpu::Texture; 2])> {
+    let max_coded_extent = wgpu::Extent3d {
+        width: size,
+        height: size,
+        depth_or_array_layers: 1,
+    };
+    let Some(caps) = ctx.device.video_decode_capabilities(codec, format) else {
+        log::info!("{codec:?} can't be decoded into {format:?}");
+        return None;
+    };
+    if size
+        < caps
+            .min_coded_extent
+            .width
+            .max(caps.min_coded_extent.height)
+        || size
+            > caps
+                .max_coded_extent
+                .width
+                .min(caps.max_coded_extent.height)
+        || caps.max_pictures < 2
+        || caps.max_reference_pictures < 1
+    {
+        log::info!("{codec:?} pictures of {size}x{size} can't be decoded: {caps:?}");
+        return None;
+    }
+
+    let decoder = ctx
+        .device
+        .create_video_decoder(&wgpu::VideoDecoderDescriptor {
+            label: None,
+            codec,
+            format,
+            max_coded_extent,
+            max_pictures: 2,
+            max_reference_pictures: 1,
+        });
+    let pictures = [(); 2].map(|()| {
+        decoder.create_picture(&wgpu::VideoPictureDescriptor {
+            label: None,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        })
+    });
+    Some((decoder, pictures))
+}
+
+/// Reads back the decoded `pictures`, and checks that the P picture kept the samples of the
+/// IDR picture besides its last row of `block_size` blocks.
+async fn check_pictures(
+    ctx: &TestingContext,
+    pictures: &[wgpu::Texture; 2],
+    bit_depth: u32,
+    block_size: u32,
+) {
+    let size = pictures[0].width();
+    let (luma_format, chroma_format) = match bit_depth {
+        8 => (wgpu::TextureFormat::R8Unorm, wgpu::TextureFormat::Rg8Unorm),
+        _ => (
+            wgpu::TextureFormat::R16Unorm,
+            wgpu::TextureFormat::Rg16Unorm,
+        ),
+    };
+
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(READ_PICTURE_SRC.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+    // The luma samples, followed by the interleaved chroma samples.
+    let sample_count = (size * size + size * size / 2) as u64;
+    let storage_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: sample_count * 4,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: sample_count * 4,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    for (index, picture) in pictures.iter().enumerate() {
+        let luma_view = picture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(luma_format),
+            aspect: wgpu::TextureAspect::Plane0,
+            ..Default::default()
+        });
+        let chroma_view = picture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(chroma_format),
+            aspect: wgpu::TextureAspect::Plane1,
+            ..Default::default()
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&luma_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&chroma_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: storage_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            cpass.set_pipeline(&pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch_workgroups(size.div_ceil(8), size.div_ceil(8), 1);
+        }
+        encoder.copy_buffer_to_buffer(&storage_buffer, 0, &readback_buffer, 0, None);
+        ctx.queue.submit([encoder.finish()]);
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let samples: Vec<u32> = bytemuck::cast_slice::<u8, f32>(&slice.get_mapped_range())
+            .iter()
+            .map(|&value| match bit_depth {
+                8 => (value * 255.0).round() as u32,
+                // P010 samples are stored in the high bits of 16-bit values.
+                _ => (value * 65535.0).round() as u32 >> 6,
+            })
+            .collect();
+        readback_buffer.unmap();
+
+        // Whether the samples of the block row at `y` are the new ones of the P picture.
+        let is_new = |y: u32| index == 1 && y >= size - block_size;
+        for y in 0..size {
+            for x in 0..size {
+                let expected = luma(is_new(y).into(), x, y, bit_depth);
+                let actual = samples[(y * size + x) as usize];
+                assert_eq!(actual, expected, "luma of picture {index} at {x}, {y}");
+            }
+        }
+        let chroma_samples = &samples[(size * size) as usize..];
+        for y in 0..size / 2 {
+            for x in 0..size / 2 {
+                for plane in 0..2 {
+                    let expected = chroma(is_new(y * 2).into(), plane, x, y, bit_depth);
+                    let actual = chroma_samples[((y * size / 2 + x) * 2 + plane) as usize];
+                    assert_eq!(
+                        actual, expected,
+                        "chroma {plane} of picture {index} at {x}, {y}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+const READ_PICTURE_SRC: &str = "
+@group(0) @binding(0) var luma: texture_2d<f32>;
+@group(0) @binding(1) var chroma: texture_2d<f32>;
+@group(0) @binding(2) var<storage, read_write> samples: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    let size = textureDimensions(luma);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+    samples[id.y * size.x + id.x] = textureLoad(luma, id.xy, 0).r;
+    if id.x % 2u == 0u && id.y % 2u == 0u {
+        let chroma_index = size.x * size.y + ((id.y / 2u) * (size.x / 2u) + id.x / 2u) * 2u;
+        let cb_cr = textureLoad(chroma, id.xy / 2u, 0).rg;
+        samples[chroma_index] = cb_cr.x;
+        samples[chroma_index + 1u] = cb_cr.y;
+    }
+}
+";
+
+/// Decodes an H.264 IDR picture, and a P picture predicted from it, into NV12 pictures.
+#[gpu_test]
+static DECODE_H264_NV12: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::VIDEO_DECODE | wgpu::Features::TEXTURE_FORMAT_NV12),
+    )
+    .run_async(|ctx| async move {
+        let nal_units = nal_units(H264_STREAM);
+        // The sequence and picture parameter sets are given below as parsed.
+        let [_sps, _pps, idr, p] = nal_units[..] else {
+            panic!("expected 4 NAL units, got {}", nal_units.len());
+        };
+        let sps = h264::SequenceParameterSet {
+            profile_idc: 66,
+            constraint_set0_flag: true,
+            constraint_set1_flag: true,
+            level_idc: 30,
+            chroma_format_idc: 1,
+            pic_order_cnt_type: 2,
+            max_num_ref_frames: 1,
+            pic_width_in_mbs_minus1: 3,
+            pic_height_in_map_units_minus1: 3,
+            frame_mbs_only_flag: true,
+            direct_8x8_inference_flag: true,
+            ..Default::default()
+        };
+        let pps = h264::PictureParameterSet {
+            deblocking_filter_control_present_flag: true,
+            ..Default::default()
+        };
+
+        let Some((decoder, pictures)) =
+            create_decoder(&ctx, wgpu::VideoCodec::H264, wgpu::TextureFormat::NV12, 64)
+        else {
+            return;
+        };
+        let idr_reference = wgpu::VideoReferenceInfo::H264(h264::ReferenceInfo::default());
+        ctx.queue.decode_video(
+            &decoder,
+            &wgpu::VideoDecodeDescriptor {
+                bitstream: idr,
+                target: &pictures[0],
+                target_reference: Some(idr_reference),
+                references: &[],
+                picture: wgpu::VideoPictureInfo::H264(h264::PictureInfo {
+                    sps: &sps,
+                    pps: &pps,
+                    idr_pic_flag: true,
+                    is_intra: true,
+                    is_reference: true,
+                    frame_num: 0,
+                    idr_pic_id: 0,
+                    pic_order_cnt: [0, 0],
+                    slice_offsets: &[0],
+                }),
+            },
+        );
+        ctx.queue.decode_video(
+            &decoder,
+            &wgpu::VideoDecodeDescriptor {
+                bitstream: p,
+                target: &pictures[1],
+                target_reference: Some(wgpu::VideoReferenceInfo::H264(h264::ReferenceInfo {
+                    frame_num: 1,
+                    pic_order_cnt: [2, 2],
+                    ..Default::default()
+                })),
+                references: &[wgpu::VideoReference {
+                    picture: &pictures[0],
+                    info: idr_reference,
+                }],
+                picture: wgpu::VideoPictureInfo::H264(h264::PictureInfo {
+                    sps: &sps,
+                    pps: &pps,
+                    idr_pic_flag: false,
+                    is_intra: false,
+                    is_reference: true,
+                    frame_num: 1,
+                    idr_pic_id: 0,
+                    pic_order_cnt: [2, 2],
+                    slice_offsets: &[0],
+                }),
+            },
+        );
+
+        check_pictures(&ctx, &pictures, 8, 16).await;
+    });
+
+/// Decodes an H.265 Main 10 IDR picture, and a P picture predicted from it, into
+/// P010 pictures.
+#[gpu_test]
+static DECODE_H265_P010: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(
+        wgpu::Features::VIDEO_DECODE
+            | wgpu::Features::TEXTURE_FORMAT_P010
+            | wgpu::Features::TEXTURE_FORMAT_16BIT_NORM,
+    ))
+    .run_async(|ctx| async move {
+        let nal_units = nal_units(H265_STREAM);
+        // The parameter sets are given below as parsed.
+        let [_vps, _sps, _pps, idr, p] = nal_units[..] else {
+            panic!("expected 5 NAL units, got {}", nal_units.len());
+        };
+        let profile_tier_level = h265::ProfileTierLevel {
+            general_profile_idc: 2,
+            general_progressive_source_flag: true,
+            general_frame_only_constraint_flag: true,
+            general_level_idc: 90,
+            ..Default::default()
+        };
+        let dec_pic_buf_mgr = h265::DecPicBufMgr {
+            max_dec_pic_buffering_minus1: [1, 0, 0, 0, 0, 0, 0],
+            ..Default::default()
+        };
+        let vps = h265::VideoParameterSet {
+            vps_temporal_id_nesting_flag: true,
+            profile_tier_level: profile_tier_level.clone(),
+            vps_sub_layer_ordering_info_present_flag: true,
+            dec_pic_buf_mgr: dec_pic_buf_mgr.clone(),
+            ..Default::default()
+        };
+        let sps = h265::SequenceParameterSet {
+            sps_temporal_id_nesting_flag: true,
+            profile_tier_level,
+            chroma_format_idc: 1,
+            pic_width_in_luma_samples: 160,
+            pic_height_in_luma_samples: 160,
+            bit_depth_luma_minus8: 2,
+            bit_depth_chroma_minus8: 2,
+            sps_sub_layer_ordering_info_present_flag: true,
+            dec_pic_buf_mgr,
+            log2_min_luma_coding_block_size_minus3: 2,
+            log2_diff_max_min_luma_transform_block_size: 3,
+            pcm_enabled_flag: true,
+            pcm_sample_bit_depth_luma_minus1: 9,
+            pcm_sample_bit_depth_chroma_minus1: 9,
+            log2_min_pcm_luma_coding_block_size_minus3: 2,
+            pcm_loop_filter_disabled_flag: true,
+            short_term_ref_pic_sets: vec![h265::ShortTermRefPicSet {
+                num_negative_pics: 1,
+                used_by_curr_pic_s0_flag: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let pps = h265::PictureParameterSet {
+            deblocking_filter_control_present_flag: true,
+            pps_deblocking_filter_disabled_flag: true,
+            ..Default::default()
+        };
+
+        let Some((decoder, pictures)) =
+            create_decoder(&ctx, wgpu::VideoCodec::H265, wgpu::TextureFormat::P010, 160)
+        else {
+            return;
+        };
+        let idr_reference = wgpu::VideoReferenceInfo::H265(h265::ReferenceInfo::default());
+        ctx.queue.decode_video(
+            &decoder,
+            &wgpu::VideoDecodeDescriptor {
+                bitstream: idr,
+                target: &pictures[0],
+                target_reference: Some(idr_reference),
+                references: &[],
+                picture: wgpu::VideoPictureInfo::H265(h265::PictureInfo {
+                    vps: &vps,
+                    sps: &sps,
+                    pps: &pps,
+                    irap_pic_flag: true,
+                    idr_pic_flag: true,
+                    is_reference: true,
+                    short_term_ref_pic_set_sps_flag: false,
+                    num_bits_for_st_ref_pic_set_in_slice: 0,
+                    num_delta_pocs_of_ref_rps_idx: 0,
+                    pic_order_cnt_val: 0,
+                    ref_pic_set_st_curr_before: &[],
+                    ref_pic_set_st_curr_after: &[],
+                    ref_pic_set_lt_curr: &[],
+                    slice_segment_offsets: &[0],
+                }),
+            },
+        );
+        ctx.queue.decode_video(
+            &decoder,
+            &wgpu::VideoDecodeDescriptor {
+                bitstream: p,
+                target: &pictures[1],
+                target_reference: Some(wgpu::VideoReferenceInfo::H265(h265::ReferenceInfo {
+                    used_for_long_term_reference: false,
+                    pic_order_cnt_val: 1,
+                })),
+                references: &[wgpu::VideoReference {
+                    picture: &pictures[0],
+                    info: idr_reference,
+                }],
+                picture: wgpu::VideoPictureInfo::H265(h265::PictureInfo {
+                    vps: &vps,
+                    sps: &sps,
+                    pps: &pps,
+                    irap_pic_flag: false,
+                    idr_pic_flag: false,
+                    is_reference: true,
+                    // The slice segment header selects the only set of the SPS.
+                    short_term_ref_pic_set_sps_flag: true,
+                    num_bits_for_st_ref_pic_set_in_slice: 0,
+                    num_delta_pocs_of_ref_rps_idx: 0,
+                    pic_order_cnt_val: 1,
+                    ref_pic_set_st_curr_before: &[0],
+                    ref_pic_set_st_curr_after: &[],
+                    ref_pic_set_lt_curr: &[],
+                    slice_segment_offsets: &[0],
+                }),
+            },
+        );
+
+        check_pictures(&ctx, &pictures, 10, 32).await;
+    });
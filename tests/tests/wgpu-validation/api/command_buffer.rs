@@ -0,0 +1,166 @@
+//! Tests of [`wgpu::CommandBuffer`] and related.
+
+fn reusable_device() -> (wgpu::Device, wgpu::Queue) {
+    wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::REUSABLE_COMMAND_BUFFERS,
+        ..Default::default()
+    })
+}
+
+fn clear_buffer_commands(
+    device: &wgpu::Device,
+    buffer: &wgpu::Buffer,
+) -> wgpu::ReusableCommandBuffer {
+    let mut encoder =
+        device.create_reusable_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.clear_buffer(buffer, 0, None);
+    encoder.finish()
+}
+
+/// Ensures that a reusable command buffer can be submitted several times,
+/// alone or along with other command buffers.
+#[test]
+fn reusable_command_buffer_resubmission() {
+    let (device, queue) = reusable_device();
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 1024,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let commands = clear_buffer_commands(&device, &buffer);
+
+    queue.submit([commands.to_command_buffer()]);
+    queue.submit([commands.to_command_buffer()]);
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.clear_buffer(&buffer, 0, None);
+    queue.submit([
+        commands.to_command_buffer(),
+        encoder.finish(),
+        commands.to_command_buffer(),
+    ]);
+
+    device.poll(wgpu::PollType::Wait).unwrap();
+}
+
+/// Ensures that the resources used by a reusable command buffer are checked
+/// again when it is submitted.
+#[test]
+#[should_panic = "Buffer with '' label has been destroyed"]
+fn reusable_command_buffer_destroyed_buffer() {
+    let (device, queue) = reusable_device();
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 1024,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let commands = clear_buffer_commands(&device, &buffer);
+    queue.submit([commands.to_command_buffer()]);
+
+    buffer.destroy();
+
+    queue.submit([commands.to_command_buffer()]);
+}
+
+/// Ensures that reusable command encoders require
+/// [`wgpu::Features::REUSABLE_COMMAND_BUFFERS`].
+#[test]
+#[should_panic = "are required but not enabled on the device"]
+fn reusable_command_encoder_missing_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let _encoder =
+        device.create_reusable_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+}
+
+/// Ensures that the transitions between the commands of a command buffer are
+/// reported with [`wgpu::InstanceFlags::REPORT_TRANSITIONS`].
+#[test]
+fn transition_report() {
+    let record = |flags| {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::NOOP,
+            flags,
+            backend_options: wgpu::BackendOptions {
+                noop: wgpu::NoopBackendOptions {
+                    enable: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .unwrap();
+        let (device, _queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("buffer"),
+            size: 1024,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("texture"),
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let copy_buffer = wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        };
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.clear_buffer(&buffer, 0, None);
+        encoder.copy_buffer_to_texture(copy_buffer, texture.as_image_copy(), texture.size());
+        encoder.copy_texture_to_buffer(texture.as_image_copy(), copy_buffer, texture.size());
+        encoder.finish().transition_report()
+    };
+
+    assert_eq!(record(wgpu::InstanceFlags::empty()), []);
+
+    let buffer = |from, to, command_index| wgpu::TransitionReport {
+        label: "buffer".to_owned(),
+        resource: wgpu::TransitionedResource::Buffer { from, to },
+        command_index,
+    };
+    assert_eq!(
+        record(wgpu::InstanceFlags::REPORT_TRANSITIONS),
+        [
+            buffer(wgpu::BufferUses::COPY_DST, wgpu::BufferUses::COPY_SRC, 1),
+            buffer(wgpu::BufferUses::COPY_SRC, wgpu::BufferUses::COPY_DST, 2),
+            wgpu::TransitionReport {
+                label: "texture".to_owned(),
+                resource: wgpu::TransitionedResource::Texture {
+                    selector: wgpu::TextureSelector {
+                        mips: 0..1,
+                        layers: 0..1,
+                    },
+                    from: wgpu::TextureUses::COPY_DST,
+                    to: wgpu::TextureUses::COPY_SRC,
+                },
+                command_index: 2,
+            },
+        ]
+    );
+}
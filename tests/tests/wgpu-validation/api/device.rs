@@ -42,3 +42,56 @@ fn recursive_uncaptured_error() {
 
     assert_eq!(errors_seen.load(Relaxed), 2);
 }
+
+/// Polling a device only finishes the pipelines created asynchronously on that device,
+/// even though the devices share the threads creating them.
+#[test]
+fn async_pipelines_finish_on_their_own_device() {
+    use core::future::Future as _;
+    use core::pin::pin;
+    use core::task;
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+    let (other_device, _other_queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl("@compute @workgroup_size(1) fn main() {}".into()),
+    });
+    let mut pipeline = pin!(device.create_compute_pipeline_async(
+        &wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        }
+    ));
+    let ctx = &mut task::Context::from_waker(task::Waker::noop());
+
+    other_device.poll(wgpu::PollType::wait()).unwrap();
+    assert!(pipeline.as_mut().poll(ctx).is_pending());
+
+    device.poll(wgpu::PollType::wait()).unwrap();
+    let task::Poll::Ready(result) = pipeline.as_mut().poll(ctx) else {
+        panic!("the pipeline wasn't finished by polling its device");
+    };
+    result.unwrap();
+}
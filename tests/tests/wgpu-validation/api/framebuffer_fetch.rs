@@ -0,0 +1,381 @@
+//! Tests of render pipelines reading color attachments with framebuffer fetch.
+
+use wgpu_test::{fail, valid};
+
+const SHADER_SRC: &str = "
+@vertex
+fn vs_main() -> @builtin(position) vec4f {
+    return vec4f(0.0);
+}
+
+@fragment
+fn fs_main(@color(1) dst: vec4f) -> @location(0) vec4f {
+    return dst;
+}
+";
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    targets: &[Option<wgpu::ColorTargetState>],
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            targets,
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn framebuffer_fetch_requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    fail(
+        &device,
+        || {
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            })
+        },
+        Some("FRAMEBUFFER_FETCH"),
+    );
+}
+
+#[test]
+fn framebuffer_fetch_color_targets() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::FRAMEBUFFER_FETCH,
+        ..Default::default()
+    });
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+    });
+    let target = |format| {
+        Some(wgpu::ColorTargetState {
+            format,
+            blend: None,
+            write_mask: wgpu::ColorWrites::ALL,
+        })
+    };
+
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            &module,
+            &[
+                target(wgpu::TextureFormat::Rgba8Unorm),
+                target(wgpu::TextureFormat::Rgba16Float),
+            ],
+        )
+    });
+
+    // Nothing to fetch from at location 1.
+    fail(
+        &device,
+        || create_pipeline(&device, &module, &[target(wgpu::TextureFormat::Rgba8Unorm)]),
+        Some("reads color attachment 1 with framebuffer fetch"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                &module,
+                &[target(wgpu::TextureFormat::Rgba8Unorm), None],
+            )
+        },
+        Some("reads color attachment 1 with framebuffer fetch"),
+    );
+
+    // The attachment at location 1 holds integers, but the shader reads floats.
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                &module,
+                &[
+                    target(wgpu::TextureFormat::Rgba8Unorm),
+                    target(wgpu::TextureFormat::Rgba8Uint),
+                ],
+            )
+        },
+        Some("Color state [1] is invalid"),
+    );
+}
+
+const DEPTH_STENCIL_SHADER_SRC: &str = "
+@vertex
+fn vs_main() -> @builtin(position) vec4f {
+    return vec4f(0.0);
+}
+
+@fragment
+fn fs_depth(@builtin(last_frag_depth) depth: f32) -> @location(0) vec4f {
+    return vec4f(depth);
+}
+
+@fragment
+fn fs_stencil(@builtin(last_frag_stencil) stencil: u32) -> @location(0) vec4f {
+    return vec4f(f32(stencil));
+}
+";
+
+fn create_depth_stencil_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    entry_point: &str,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(entry_point),
+            compilation_options: Default::default(),
+            targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn framebuffer_fetch_depth_stencil_requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::FRAMEBUFFER_FETCH,
+        ..Default::default()
+    });
+    fail(
+        &device,
+        || {
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(DEPTH_STENCIL_SHADER_SRC.into()),
+            })
+        },
+        Some("FRAMEBUFFER_FETCH_DEPTH_STENCIL"),
+    );
+}
+
+#[test]
+fn framebuffer_fetch_depth_stencil_formats() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+        ..Default::default()
+    });
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(DEPTH_STENCIL_SHADER_SRC.into()),
+    });
+
+    valid(&device, || {
+        create_depth_stencil_pipeline(
+            &device,
+            &module,
+            "fs_depth",
+            wgpu::TextureFormat::Depth32Float,
+        );
+        create_depth_stencil_pipeline(
+            &device,
+            &module,
+            "fs_stencil",
+            wgpu::TextureFormat::Depth24PlusStencil8,
+        )
+    });
+
+    fail(
+        &device,
+        || {
+            create_depth_stencil_pipeline(
+                &device,
+                &module,
+                "fs_depth",
+                wgpu::TextureFormat::Stencil8,
+            )
+        },
+        Some("reads the DepthOnly aspect with framebuffer fetch"),
+    );
+    fail(
+        &device,
+        || {
+            create_depth_stencil_pipeline(
+                &device,
+                &module,
+                "fs_stencil",
+                wgpu::TextureFormat::Depth32Float,
+            )
+        },
+        Some("reads the StencilOnly aspect with framebuffer fetch"),
+    );
+}
+
+/// Records a render pass with `color_formats` and `depth_stencil_format`
+/// attachments, that draws with `pipeline`.
+fn draw_with_attachments(
+    device: &wgpu::Device,
+    pipeline: &wgpu::RenderPipeline,
+    color_formats: &[wgpu::TextureFormat],
+    depth_stencil_format: Option<wgpu::TextureFormat>,
+) -> wgpu::CommandBuffer {
+    let view = |format, usage| {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    };
+    let color_views = color_formats
+        .iter()
+        .map(|&format| view(format, wgpu::TextureUsages::RENDER_ATTACHMENT))
+        .collect::<Vec<_>>();
+    let depth_stencil_view =
+        depth_stencil_format.map(|format| view(format, wgpu::TextureUsages::RENDER_ATTACHMENT));
+
+    let color_attachments = color_views
+        .iter()
+        .map(|view| {
+            Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations::default(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &color_attachments,
+            depth_stencil_attachment: depth_stencil_view.as_ref().map(|view| {
+                let format = depth_stencil_format.unwrap();
+                wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: format.has_depth_aspect().then(wgpu::Operations::default),
+                    stencil_ops: format.has_stencil_aspect().then(wgpu::Operations::default),
+                }
+            }),
+            ..Default::default()
+        });
+        pass.set_pipeline(pipeline);
+        pass.draw(0..3, 0..1);
+    }
+    encoder.finish()
+}
+
+#[test]
+fn framebuffer_fetch_render_pass_attachments() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::FRAMEBUFFER_FETCH
+            | wgpu::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+        ..Default::default()
+    });
+    let color_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+    });
+    let color_pipeline = create_pipeline(
+        &device,
+        &color_module,
+        &[
+            Some(wgpu::TextureFormat::Rgba8Unorm.into()),
+            Some(wgpu::TextureFormat::Rgba8Unorm.into()),
+        ],
+    );
+    let depth_stencil_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(DEPTH_STENCIL_SHADER_SRC.into()),
+    });
+    let stencil_pipeline = create_depth_stencil_pipeline(
+        &device,
+        &depth_stencil_module,
+        "fs_stencil",
+        wgpu::TextureFormat::Depth24PlusStencil8,
+    );
+
+    valid(&device, || {
+        draw_with_attachments(
+            &device,
+            &color_pipeline,
+            &[wgpu::TextureFormat::Rgba8Unorm; 2],
+            None,
+        );
+        draw_with_attachments(
+            &device,
+            &stencil_pipeline,
+            &[wgpu::TextureFormat::Rgba8Unorm],
+            Some(wgpu::TextureFormat::Depth24PlusStencil8),
+        )
+    });
+
+    // The pass has no color attachment at the fetched location 1.
+    fail(
+        &device,
+        || {
+            draw_with_attachments(
+                &device,
+                &color_pipeline,
+                &[wgpu::TextureFormat::Rgba8Unorm],
+                None,
+            )
+        },
+        Some("reads color attachment 1 with framebuffer fetch, but the RenderPass has no color attachment there"),
+    );
+    // The pass's depth attachment has no stencil to fetch.
+    fail(
+        &device,
+        || {
+            draw_with_attachments(
+                &device,
+                &stencil_pipeline,
+                &[wgpu::TextureFormat::Rgba8Unorm],
+                Some(wgpu::TextureFormat::Depth32Float),
+            )
+        },
+        Some("reads the StencilOnly aspect with framebuffer fetch, but the RenderPass has no depth-stencil attachment with that aspect"),
+    );
+}
@@ -0,0 +1,295 @@
+//! Tests of [`wgpu::RenderPass::execute_indirect`].
+
+use wgpu_test::{fail, valid};
+
+const SHADER_SRC: &str = "
+@vertex
+fn vs_main(@location(0) position: vec4f) -> @builtin(position) vec4f {
+    return position;
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return vec4f(1.0);
+}
+";
+
+/// A layout whose records switch vertex buffer slot 0 and draw.
+const VERTEX_BUFFER_LAYOUT: wgpu::IndirectCommandLayout = wgpu::IndirectCommandLayout {
+    vertex_buffers: 1,
+    index_buffer: false,
+    push_constant_stages: wgpu::ShaderStages::NONE,
+    push_constant_offset: 0,
+    push_constant_size: 0,
+    indexed: false,
+};
+
+struct Context {
+    device: wgpu::Device,
+    _queue: wgpu::Queue,
+    pipeline: wgpu::RenderPipeline,
+    view: wgpu::TextureView,
+    data_buffer: wgpu::Buffer,
+    indirect_buffer: wgpu::Buffer,
+}
+
+impl Context {
+    fn new(required_features: wgpu::Features) -> Self {
+        let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+            required_features,
+            ..Default::default()
+        });
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 16,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4],
+                }],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+        let view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&Default::default());
+        let data_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1024,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDEX,
+            mapped_at_creation: false,
+        });
+        let indirect_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: VERTEX_BUFFER_LAYOUT.stride() * 4,
+            usage: wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+        Self {
+            device,
+            _queue: queue,
+            pipeline,
+            view,
+            data_buffer,
+            indirect_buffer,
+        }
+    }
+
+    fn encode(&self, f: impl FnOnce(&mut wgpu::RenderPass<'_>)) -> wgpu::CommandBuffer {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            f(&mut pass);
+        }
+        encoder.finish()
+    }
+}
+
+#[test]
+fn execute_indirect_requires_feature() {
+    let ctx = Context::new(wgpu::Features::empty());
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(
+                    &VERTEX_BUFFER_LAYOUT,
+                    &ctx.data_buffer,
+                    &ctx.indirect_buffer,
+                    0,
+                    4,
+                )
+            })
+        },
+        Some("INDIRECT_COMMANDS"),
+    );
+}
+
+/// Ensures that the vertex buffers switched by the records don't need to be
+/// bound beforehand, and have to be bound again afterwards.
+#[test]
+fn execute_indirect_switched_vertex_buffers() {
+    let ctx = Context::new(wgpu::Features::INDIRECT_COMMANDS);
+
+    valid(&ctx.device, || {
+        ctx.encode(|pass| {
+            pass.execute_indirect(
+                &VERTEX_BUFFER_LAYOUT,
+                &ctx.data_buffer,
+                &ctx.indirect_buffer,
+                0,
+                4,
+            );
+            pass.set_vertex_buffer(0, ctx.data_buffer.slice(..));
+            pass.draw(0..3, 0..1);
+        })
+    });
+
+    // The layout doesn't switch slot 0, so it has to be bound.
+    let layout = wgpu::IndirectCommandLayout {
+        vertex_buffers: 0,
+        ..VERTEX_BUFFER_LAYOUT
+    };
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(&layout, &ctx.data_buffer, &ctx.indirect_buffer, 0, 1)
+            })
+        },
+        Some("requires vertex buffer 0 to be set"),
+    );
+
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(
+                    &VERTEX_BUFFER_LAYOUT,
+                    &ctx.data_buffer,
+                    &ctx.indirect_buffer,
+                    0,
+                    4,
+                );
+                pass.draw(0..3, 0..1);
+            })
+        },
+        Some("requires vertex buffer 0 to be set"),
+    );
+}
+
+#[test]
+fn execute_indirect_invalid_layout() {
+    let ctx = Context::new(wgpu::Features::INDIRECT_COMMANDS);
+
+    let layout = wgpu::IndirectCommandLayout {
+        index_buffer: true,
+        ..VERTEX_BUFFER_LAYOUT
+    };
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(&layout, &ctx.data_buffer, &ctx.indirect_buffer, 0, 1)
+            })
+        },
+        Some("switches the index buffer but its draws aren't indexed"),
+    );
+
+    let layout = wgpu::IndirectCommandLayout {
+        vertex_buffers: 1 << 31,
+        ..VERTEX_BUFFER_LAYOUT
+    };
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(&layout, &ctx.data_buffer, &ctx.indirect_buffer, 0, 1)
+            })
+        },
+        Some("switches vertex buffer slot 31"),
+    );
+}
+
+#[test]
+fn execute_indirect_buffer_overrun() {
+    let ctx = Context::new(wgpu::Features::INDIRECT_COMMANDS);
+
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect(
+                    &VERTEX_BUFFER_LAYOUT,
+                    &ctx.data_buffer,
+                    &ctx.indirect_buffer,
+                    VERTEX_BUFFER_LAYOUT.stride(),
+                    4,
+                )
+            })
+        },
+        Some("overruns indirect buffer"),
+    );
+
+    let count_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::INDIRECT,
+        mapped_at_creation: false,
+    });
+    valid(&ctx.device, || {
+        ctx.encode(|pass| {
+            pass.execute_indirect_count(
+                &VERTEX_BUFFER_LAYOUT,
+                &ctx.data_buffer,
+                &ctx.indirect_buffer,
+                0,
+                &count_buffer,
+                0,
+                4,
+            )
+        })
+    });
+    fail(
+        &ctx.device,
+        || {
+            ctx.encode(|pass| {
+                pass.execute_indirect_count(
+                    &VERTEX_BUFFER_LAYOUT,
+                    &ctx.data_buffer,
+                    &ctx.indirect_buffer,
+                    0,
+                    &count_buffer,
+                    4,
+                    4,
+                )
+            })
+        },
+        Some("overruns indirect buffer of size 4"),
+    );
+}
@@ -1,7 +1,14 @@
 mod binding_arrays;
 mod buffer;
 mod buffer_slice;
+mod command_buffer;
 mod device;
 mod external_texture;
+mod framebuffer_fetch;
+mod indirect_commands;
 mod instance;
+mod queue;
+mod render_bundle;
+mod sparse;
 mod texture;
+mod video;
@@ -0,0 +1,157 @@
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const COMPUTE_QUEUE: QueueDescriptor<'static> = QueueDescriptor {
+    label: None,
+    queue_type: QueueType::Compute,
+};
+
+const TRANSFER_QUEUE: QueueDescriptor<'static> = QueueDescriptor {
+    label: None,
+    queue_type: QueueType::Transfer,
+};
+
+fn multi_queue_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_limits: Limits {
+            max_compute_queues: 1,
+            max_transfer_queues: 1,
+            ..Limits::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// Ensures no more queues than the limits allow can be alive at the same time.
+#[test]
+fn create_queue_within_limits() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let error = device.create_queue(&COMPUTE_QUEUE).unwrap_err();
+    assert!(error.to_string().contains("exceed the limit of 0"));
+
+    let (device, _queue) = multi_queue_device();
+    let compute = device.create_queue(&COMPUTE_QUEUE).unwrap();
+    let _transfer = device.create_queue(&TRANSFER_QUEUE).unwrap();
+    let error = device.create_queue(&COMPUTE_QUEUE).unwrap_err();
+    assert!(error.to_string().contains("exceed the limit of 1"));
+
+    // Dropping a queue frees its slot again.
+    drop(compute);
+    let _compute = device.create_queue(&COMPUTE_QUEUE).unwrap();
+}
+
+/// Ensures command buffers can only be submitted to queues of the type they were encoded for.
+#[test]
+fn submit_to_queue_of_encoded_type() {
+    let (device, queue) = multi_queue_device();
+    let compute = device.create_queue(&COMPUTE_QUEUE).unwrap();
+    let transfer = device.create_queue(&TRANSFER_QUEUE).unwrap();
+
+    let encoder = compute.create_command_encoder(&CommandEncoderDescriptor::default());
+    valid(&device, || compute.submit([encoder.finish()]));
+
+    let encoder = transfer.create_command_encoder(&CommandEncoderDescriptor::default());
+    fail(
+        &device,
+        || compute.submit([encoder.finish()]),
+        Some("encoded for Transfer queues can not be submitted to a Compute queue"),
+    );
+
+    let encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    fail(
+        &device,
+        || transfer.submit([encoder.finish()]),
+        Some("encoded for Graphics queues can not be submitted to a Transfer queue"),
+    );
+
+    let encoder = compute.create_command_encoder(&CommandEncoderDescriptor::default());
+    fail(
+        &device,
+        || queue.submit([encoder.finish()]),
+        Some("encoded for Compute queues can not be submitted to a Graphics queue"),
+    );
+}
+
+/// Ensures passes can only be encoded for queues that can run them.
+#[test]
+fn passes_on_queue_types() {
+    let (device, _queue) = multi_queue_device();
+    let compute = device.create_queue(&COMPUTE_QUEUE).unwrap();
+    let transfer = device.create_queue(&TRANSFER_QUEUE).unwrap();
+
+    let texture = device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&TextureViewDescriptor::default());
+
+    let mut encoder = compute.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &view,
+            depth_slice: None,
+            resolve_target: None,
+            ops: Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    fail(
+        &device,
+        || encoder.finish(),
+        Some("Render passes can not be encoded for Compute queues"),
+    );
+
+    let mut encoder = compute.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.begin_compute_pass(&ComputePassDescriptor::default());
+    valid(&device, || encoder.finish());
+
+    let mut encoder = transfer.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.begin_compute_pass(&ComputePassDescriptor::default());
+    fail(
+        &device,
+        || encoder.finish(),
+        Some("Compute passes can not be encoded for Transfer queues"),
+    );
+}
+
+/// Ensures writes to a secondary queue are visible to its own submissions.
+#[test]
+fn write_buffer_on_secondary_queue() {
+    let (device, _queue) = multi_queue_device();
+    let transfer = device.create_queue(&TRANSFER_QUEUE).unwrap();
+
+    let source = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let destination = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    transfer.write_buffer(&source, 0, &[7; 16]);
+    let mut encoder = transfer.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.copy_buffer_to_buffer(&source, 0, &destination, 0, 16);
+    valid(&device, || transfer.submit([encoder.finish()]));
+
+    destination.map_async(MapMode::Read, .., Result::unwrap);
+    device.poll(PollType::Wait).unwrap();
+    assert_eq!(*destination.get_mapped_range(..), [7; 16]);
+}
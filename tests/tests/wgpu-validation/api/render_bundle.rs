@@ -0,0 +1,249 @@
+//! Tests of [`wgpu::RenderBundleEncoderDescriptor::inherited_bind_group_layouts`].
+
+use wgpu_test::{fail, valid};
+
+const SHADER_SRC: &str = "
+@group(0) @binding(0) var<uniform> color: vec4f;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+    return vec4f(f32(index), 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return color;
+}
+";
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+struct Context {
+    device: wgpu::Device,
+    _queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    view: wgpu::TextureView,
+}
+
+impl Context {
+    fn new() -> Self {
+        Self::with_features(wgpu::Features::RENDER_BUNDLE_INHERITANCE)
+    }
+
+    fn with_features(required_features: wgpu::Features) -> Self {
+        let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+            required_features,
+            ..Default::default()
+        });
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline = create_pipeline(&device, &layout);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+        let view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&Default::default());
+        Self {
+            device,
+            _queue: queue,
+            layout,
+            pipeline,
+            bind_group,
+            view,
+        }
+    }
+
+    /// Records a bundle that inherits bind group 0 and draws with `pipeline`.
+    fn create_bundle(&self, pipeline: &wgpu::RenderPipeline) -> wgpu::RenderBundle {
+        let mut encoder =
+            self.device
+                .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                    color_formats: &[Some(FORMAT)],
+                    sample_count: 1,
+                    inherited_bind_group_layouts: &[Some(&self.layout)],
+                    ..Default::default()
+                });
+        encoder.set_pipeline(pipeline);
+        encoder.draw(0..3, 0..1);
+        encoder.finish(&wgpu::RenderBundleDescriptor::default())
+    }
+
+    fn encode(&self, f: impl FnOnce(&mut wgpu::RenderPass<'_>)) -> wgpu::CommandBuffer {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            f(&mut pass);
+        }
+        encoder.finish()
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            targets: &[Some(FORMAT.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Ensures that bundles recorded on different threads can share the bind
+/// group set in the pass, and that it stays set after they're executed.
+#[test]
+fn inherited_bind_group() {
+    let ctx = Context::new();
+
+    let bundles = std::thread::scope(|s| {
+        let handles = [
+            s.spawn(|| ctx.create_bundle(&ctx.pipeline)),
+            s.spawn(|| ctx.create_bundle(&ctx.pipeline)),
+        ];
+        handles.map(|handle| handle.join().unwrap())
+    });
+
+    valid(&ctx.device, || {
+        ctx.encode(|pass| {
+            pass.set_bind_group(0, &ctx.bind_group, &[]);
+            pass.execute_bundles(&bundles);
+            pass.execute_bundles(&bundles[..1]);
+            pass.set_pipeline(&ctx.pipeline);
+            pass.draw(0..3, 0..1);
+        })
+    });
+
+    fail(
+        &ctx.device,
+        || ctx.encode(|pass| pass.execute_bundles(&bundles)),
+        Some("inherits the bind group at index 0, but none is set in the render pass"),
+    );
+}
+
+#[test]
+#[should_panic = "RENDER_BUNDLE_INHERITANCE"]
+fn inherited_bind_group_requires_feature() {
+    let ctx = Context::with_features(wgpu::Features::empty());
+    ctx.create_bundle(&ctx.pipeline);
+}
+
+#[test]
+#[should_panic = "Bind group index 0 is inherited from the render pass"]
+fn inherited_bind_group_set_in_bundle() {
+    let ctx = Context::new();
+
+    let mut encoder =
+        ctx.device
+            .create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                color_formats: &[Some(FORMAT)],
+                sample_count: 1,
+                inherited_bind_group_layouts: &[Some(&ctx.layout)],
+                ..Default::default()
+            });
+    encoder.set_pipeline(&ctx.pipeline);
+    encoder.set_bind_group(0, &ctx.bind_group, &[]);
+    encoder.draw(0..3, 0..1);
+    encoder.finish(&wgpu::RenderBundleDescriptor::default());
+}
+
+#[test]
+#[should_panic = "but the bundle inherits"]
+fn inherited_bind_group_incompatible_layout() {
+    let ctx = Context::new();
+
+    // Same entries, but with a dynamic offset.
+    let other_layout = ctx
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+    let other_pipeline = create_pipeline(&ctx.device, &other_layout);
+
+    ctx.create_bundle(&other_pipeline);
+}
@@ -0,0 +1,280 @@
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SPARSE_BUFFER: BufferDescriptor<'static> = BufferDescriptor {
+    label: None,
+    size: 2 * SPARSE_PAGE_SIZE,
+    usage: BufferUsages::SPARSE
+        .union(BufferUsages::COPY_SRC)
+        .union(BufferUsages::COPY_DST),
+    mapped_at_creation: false,
+};
+
+/// Pages of `Rgba8Unorm` are 128x128 texels, so levels 0 and 1 are made of pages and the mip
+/// tail starts at level 2.
+const SPARSE_TEXTURE: TextureDescriptor<'static> = TextureDescriptor {
+    label: None,
+    size: Extent3d {
+        width: 256,
+        height: 256,
+        depth_or_array_layers: 2,
+    },
+    mip_level_count: 5,
+    sample_count: 1,
+    dimension: TextureDimension::D2,
+    format: TextureFormat::Rgba8Unorm,
+    usage: TextureUsages::SPARSE.union(TextureUsages::TEXTURE_BINDING),
+    view_formats: &[],
+};
+
+fn sparse_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_features: Features::SPARSE_RESOURCES,
+        ..Default::default()
+    })
+}
+
+/// Ensures sparse resources can only be created with the feature and supported descriptors.
+#[test]
+fn create_sparse_resources() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let _ = fail(
+        &device,
+        || device.create_buffer(&SPARSE_BUFFER),
+        Some("FeaturesWGPU(SPARSE_RESOURCES)"),
+    );
+    let _ = fail(
+        &device,
+        || device.create_texture(&SPARSE_TEXTURE),
+        Some("Texture usage SPARSE can't be used due to missing features"),
+    );
+
+    let (device, _queue) = sparse_device();
+    assert_eq!(SPARSE_TEXTURE.sparse_mip_tail_start(), 2);
+    let _ = valid(&device, || device.create_buffer(&SPARSE_BUFFER));
+    let _ = valid(&device, || device.create_texture(&SPARSE_TEXTURE));
+    let _ = fail(
+        &device,
+        || {
+            device.create_buffer(&BufferDescriptor {
+                usage: BufferUsages::SPARSE | BufferUsages::MAP_READ,
+                ..SPARSE_BUFFER
+            })
+        },
+        Some("Sparse buffers can't have `MAP` usages"),
+    );
+    let _ = fail(
+        &device,
+        || {
+            device.create_buffer(&BufferDescriptor {
+                mapped_at_creation: true,
+                ..SPARSE_BUFFER
+            })
+        },
+        Some("Sparse buffers can't be mapped at creation"),
+    );
+    let _ = fail(
+        &device,
+        || {
+            device.create_texture(&TextureDescriptor {
+                dimension: TextureDimension::D3,
+                ..SPARSE_TEXTURE
+            })
+        },
+        Some("Sparse textures must be single-sampled 2D textures"),
+    );
+    let _ = fail(
+        &device,
+        || {
+            device.create_texture(&TextureDescriptor {
+                format: TextureFormat::Depth32Float,
+                ..SPARSE_TEXTURE
+            })
+        },
+        Some("Sparse textures must be single-sampled 2D textures"),
+    );
+}
+
+/// Ensures binds are validated against the pages of the resources.
+#[test]
+fn bind_sparse_validation() {
+    let (device, queue) = sparse_device();
+    let buffer = device.create_buffer(&SPARSE_BUFFER);
+    let texture = device.create_texture(&SPARSE_TEXTURE);
+
+    let buffer_bind = SparseBufferBind {
+        buffer: &buffer,
+        offset: 0,
+        size: SPARSE_PAGE_SIZE,
+        resident: true,
+    };
+    let texture_bind = SparseTextureBind {
+        texture: &texture,
+        mip_level: 0,
+        origin: Origin3d { x: 128, y: 0, z: 1 },
+        size: Extent3d {
+            width: 128,
+            height: 256,
+            depth_or_array_layers: 1,
+        },
+        resident: true,
+    };
+    valid(&device, || {
+        queue.bind_sparse(&[buffer_bind], &[texture_bind])
+    });
+
+    // The whole mip tail of a layer is bound at once.
+    valid(&device, || {
+        queue.bind_sparse(
+            &[],
+            &[SparseTextureBind {
+                mip_level: 3,
+                origin: Origin3d::ZERO,
+                size: Extent3d {
+                    width: 32,
+                    height: 32,
+                    depth_or_array_layers: 2,
+                },
+                ..texture_bind
+            }],
+        )
+    });
+
+    let plain_buffer = device.create_buffer(&BufferDescriptor {
+        usage: BufferUsages::COPY_DST,
+        ..SPARSE_BUFFER
+    });
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[SparseBufferBind {
+                    buffer: &plain_buffer,
+                    ..buffer_bind
+                }],
+                &[],
+            )
+        },
+        Some("is not sparse"),
+    );
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[SparseBufferBind {
+                    offset: 256,
+                    ..buffer_bind
+                }],
+                &[],
+            )
+        },
+        Some("is not aligned to pages of 65536 bytes"),
+    );
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[SparseBufferBind {
+                    offset: SPARSE_PAGE_SIZE,
+                    size: 2 * SPARSE_PAGE_SIZE,
+                    ..buffer_bind
+                }],
+                &[],
+            )
+        },
+        Some("is out of bounds"),
+    );
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[],
+                &[SparseTextureBind {
+                    origin: Origin3d { x: 64, y: 0, z: 0 },
+                    ..texture_bind
+                }],
+            )
+        },
+        Some("is not aligned to pages of 128x128 texels"),
+    );
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[],
+                &[SparseTextureBind {
+                    origin: Origin3d { x: 0, y: 0, z: 2 },
+                    ..texture_bind
+                }],
+            )
+        },
+        Some("out of bounds"),
+    );
+    fail(
+        &device,
+        || {
+            queue.bind_sparse(
+                &[],
+                &[SparseTextureBind {
+                    mip_level: 2,
+                    origin: Origin3d::ZERO,
+                    size: Extent3d {
+                        width: 32,
+                        height: 32,
+                        depth_or_array_layers: 1,
+                    },
+                    ..texture_bind
+                }],
+            )
+        },
+        Some("is in the mip tail starting at level 2"),
+    );
+}
+
+/// Ensures pages read zero when they become resident, and keep their contents while they stay so.
+#[test]
+fn bound_pages_are_zeroed() {
+    let (device, queue) = sparse_device();
+    let buffer = device.create_buffer(&SPARSE_BUFFER);
+    let destination = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let bind = |resident| {
+        valid(&device, || {
+            queue.bind_sparse(
+                &[SparseBufferBind {
+                    buffer: &buffer,
+                    offset: SPARSE_PAGE_SIZE,
+                    size: SPARSE_PAGE_SIZE,
+                    resident,
+                }],
+                &[],
+            )
+        })
+    };
+    let read_back = || {
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&buffer, SPARSE_PAGE_SIZE, &destination, 0, 16);
+        valid(&device, || queue.submit([encoder.finish()]));
+        destination.map_async(MapMode::Read, .., Result::unwrap);
+        device.poll(PollType::Wait).unwrap();
+        let contents = destination.get_mapped_range(..).to_vec();
+        destination.unmap();
+        contents
+    };
+
+    bind(true);
+    queue.write_buffer(&buffer, SPARSE_PAGE_SIZE, &[7; 16]);
+    assert_eq!(read_back(), [7; 16]);
+
+    bind(true);
+    assert_eq!(read_back(), [7; 16]);
+
+    bind(false);
+    bind(true);
+    assert_eq!(read_back(), [0; 16]);
+}
@@ -298,3 +298,160 @@ fn planar_texture_bad_size() {
         );
     }
 }
+
+fn transient_texture_descriptor(
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 4,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    }
+}
+
+/// Ensures that transient textures require [`wgpu::Features::TRANSIENT_ATTACHMENTS`]
+/// and can't have any usage besides `RENDER_ATTACHMENT`.
+#[test]
+fn transient_texture_creation() {
+    let transient = wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT;
+
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    fail(
+        &device,
+        || {
+            device.create_texture(&transient_texture_descriptor(
+                wgpu::TextureFormat::Rgba8Unorm,
+                transient,
+            ))
+        },
+        Some("Texture usage TRANSIENT can't be used due to missing features"),
+    );
+
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TRANSIENT_ATTACHMENTS,
+        ..Default::default()
+    });
+    valid(&device, || {
+        device.create_texture(&transient_texture_descriptor(
+            wgpu::TextureFormat::Rgba8Unorm,
+            transient,
+        ))
+    });
+    valid(&device, || {
+        device.create_texture(&transient_texture_descriptor(
+            wgpu::TextureFormat::Depth24PlusStencil8,
+            transient,
+        ))
+    });
+    for usage in [
+        transient | wgpu::TextureUsages::TEXTURE_BINDING,
+        transient | wgpu::TextureUsages::COPY_SRC,
+        transient | wgpu::TextureUsages::COPY_DST,
+    ] {
+        fail(
+            &device,
+            || {
+                device.create_texture(&transient_texture_descriptor(
+                    wgpu::TextureFormat::Rgba8Unorm,
+                    usage,
+                ))
+            },
+            Some("Transient textures must only have the RENDER_ATTACHMENT usage"),
+        );
+    }
+}
+
+/// Ensures that render passes must clear and discard transient attachments.
+#[test]
+fn transient_attachment_ops() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TRANSIENT_ATTACHMENTS,
+        ..Default::default()
+    });
+    let color = device
+        .create_texture(&transient_texture_descriptor(
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT,
+        ))
+        .create_view(&Default::default());
+    let depth = device
+        .create_texture(&transient_texture_descriptor(
+            wgpu::TextureFormat::Depth32Float,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT,
+        ))
+        .create_view(&Default::default());
+    let resolve = device
+        .create_texture(&wgpu::TextureDescriptor {
+            sample_count: 1,
+            ..transient_texture_descriptor(
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::RENDER_ATTACHMENT,
+            )
+        })
+        .create_view(&Default::default());
+
+    let render_pass = |color_ops: wgpu::Operations<wgpu::Color>, depth_ops| {
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &color,
+                depth_slice: None,
+                resolve_target: Some(&resolve),
+                ops: color_ops,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth,
+                depth_ops: Some(depth_ops),
+                stencil_ops: None,
+            }),
+            ..Default::default()
+        });
+        encoder.finish()
+    };
+    let cleared = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        store: wgpu::StoreOp::Discard,
+    };
+    let depth_cleared = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(1.0),
+        store: wgpu::StoreOp::Discard,
+    };
+
+    valid(&device, || render_pass(cleared, depth_cleared));
+    fail(
+        &device,
+        || {
+            render_pass(
+                wgpu::Operations {
+                    store: wgpu::StoreOp::Store,
+                    ..cleared
+                },
+                depth_cleared,
+            )
+        },
+        Some("must be cleared with `LoadOp::Clear` and discarded with `StoreOp::Discard`"),
+    );
+    fail(
+        &device,
+        || {
+            render_pass(
+                cleared,
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    ..depth_cleared
+                },
+            )
+        },
+        Some("must be cleared with `LoadOp::Clear` and discarded with `StoreOp::Discard`"),
+    );
+}
@@ -0,0 +1,224 @@
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const DECODER_DESCRIPTOR: VideoDecoderDescriptor<'static> = VideoDecoderDescriptor {
+    label: None,
+    codec: VideoCodec::H264,
+    format: TextureFormat::NV12,
+    max_coded_extent: Extent3d {
+        width: 64,
+        height: 64,
+        depth_or_array_layers: 1,
+    },
+    max_pictures: 2,
+    max_reference_pictures: 1,
+};
+
+const PICTURE_DESCRIPTOR: VideoPictureDescriptor<'static> = VideoPictureDescriptor {
+    label: None,
+    usage: TextureUsages::TEXTURE_BINDING,
+};
+
+fn video_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_features: Features::VIDEO_DECODE | Features::TEXTURE_FORMAT_NV12,
+        ..Default::default()
+    })
+}
+
+/// Ensures a [`VideoDecoder`] can only be created within the capabilities of the device.
+#[test]
+fn create_video_decoder() {
+    let (device, _queue) = video_device();
+
+    let caps = device
+        .video_decode_capabilities(VideoCodec::H264, TextureFormat::NV12)
+        .expect("the noop backend decodes H.264 into NV12");
+    assert!(caps.max_pictures >= DECODER_DESCRIPTOR.max_pictures);
+
+    let _ = valid(&device, || device.create_video_decoder(&DECODER_DESCRIPTOR));
+    let _ = fail(
+        &device,
+        || {
+            device.create_video_decoder(&VideoDecoderDescriptor {
+                format: TextureFormat::Rgba8Unorm,
+                ..DECODER_DESCRIPTOR
+            })
+        },
+        Some("Video pictures must be NV12 or P010"),
+    );
+    let _ = fail(
+        &device,
+        || {
+            device.create_video_decoder(&VideoDecoderDescriptor {
+                max_coded_extent: Extent3d {
+                    width: caps.max_coded_extent.width + 16,
+                    ..DECODER_DESCRIPTOR.max_coded_extent
+                },
+                ..DECODER_DESCRIPTOR
+            })
+        },
+        Some("is not within"),
+    );
+    let _ = fail(
+        &device,
+        || {
+            device.create_video_decoder(&VideoDecoderDescriptor {
+                max_pictures: 0,
+                ..DECODER_DESCRIPTOR
+            })
+        },
+        Some("at least one picture"),
+    );
+
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::TEXTURE_FORMAT_NV12,
+        ..Default::default()
+    });
+    assert_eq!(
+        device.video_decode_capabilities(VideoCodec::H264, TextureFormat::NV12),
+        None
+    );
+    let _ = fail(
+        &device,
+        || device.create_video_decoder(&DECODER_DESCRIPTOR),
+        Some("VIDEO_DECODE"),
+    );
+}
+
+/// Ensures a decoder creates at most `max_pictures` pictures at once, with sampling and
+/// copy usages only.
+#[test]
+fn create_video_picture() {
+    let (device, _queue) = video_device();
+    let decoder = device.create_video_decoder(&DECODER_DESCRIPTOR);
+
+    let first = valid(&device, || decoder.create_picture(&PICTURE_DESCRIPTOR));
+    assert_eq!(first.format(), TextureFormat::NV12);
+    assert_eq!(first.size(), DECODER_DESCRIPTOR.max_coded_extent);
+
+    let _ = fail(
+        &device,
+        || {
+            decoder.create_picture(&VideoPictureDescriptor {
+                label: None,
+                usage: TextureUsages::RENDER_ATTACHMENT,
+            })
+        },
+        Some("can only be used with"),
+    );
+
+    let second = valid(&device, || decoder.create_picture(&PICTURE_DESCRIPTOR));
+    let _ = fail(
+        &device,
+        || decoder.create_picture(&PICTURE_DESCRIPTOR),
+        Some("already has its maximum of 2 pictures"),
+    );
+
+    // The slot of a dropped picture is given to the next one.
+    drop(second);
+    let _ = valid(&device, || decoder.create_picture(&PICTURE_DESCRIPTOR));
+}
+
+/// Ensures decodes only use distinct pictures of their decoder.
+#[test]
+fn decode_video() {
+    let (device, queue) = video_device();
+    let decoder = device.create_video_decoder(&DECODER_DESCRIPTOR);
+    let first = decoder.create_picture(&PICTURE_DESCRIPTOR);
+    let second = decoder.create_picture(&PICTURE_DESCRIPTOR);
+
+    let other_decoder = device.create_video_decoder(&DECODER_DESCRIPTOR);
+    let other = other_decoder.create_picture(&PICTURE_DESCRIPTOR);
+
+    let sps = video::h264::SequenceParameterSet {
+        profile_idc: 100,
+        chroma_format_idc: 1,
+        pic_width_in_mbs_minus1: 3,
+        pic_height_in_map_units_minus1: 3,
+        frame_mbs_only_flag: true,
+        ..Default::default()
+    };
+    let pps = video::h264::PictureParameterSet::default();
+    let picture = VideoPictureInfo::H264(video::h264::PictureInfo {
+        sps: &sps,
+        pps: &pps,
+        idr_pic_flag: false,
+        is_intra: false,
+        is_reference: true,
+        frame_num: 1,
+        idr_pic_id: 0,
+        pic_order_cnt: [2, 2],
+        slice_offsets: &[0],
+    });
+    let bitstream = [0, 0, 1, 0x41, 0x9a];
+    let info = VideoReferenceInfo::H264(Default::default());
+
+    let references = [VideoReference {
+        picture: &first,
+        info,
+    }];
+    let desc = VideoDecodeDescriptor {
+        bitstream: &bitstream,
+        target: &second,
+        target_reference: Some(info),
+        references: &references,
+        picture,
+    };
+
+    valid(&device, || queue.decode_video(&decoder, &desc));
+    queue.submit([]);
+
+    fail(
+        &device,
+        || {
+            queue.decode_video(
+                &decoder,
+                &VideoDecodeDescriptor {
+                    bitstream: &[],
+                    ..desc
+                },
+            )
+        },
+        Some("bitstream of the picture is empty"),
+    );
+    fail(
+        &device,
+        || {
+            queue.decode_video(
+                &decoder,
+                &VideoDecodeDescriptor {
+                    target: &other,
+                    ..desc
+                },
+            )
+        },
+        Some("is not a picture of"),
+    );
+    fail(
+        &device,
+        || {
+            queue.decode_video(
+                &decoder,
+                &VideoDecodeDescriptor {
+                    target: &first,
+                    ..desc
+                },
+            )
+        },
+        Some("is used more than once"),
+    );
+    fail(
+        &device,
+        || {
+            queue.decode_video(
+                &decoder,
+                &VideoDecodeDescriptor {
+                    target_reference: Some(VideoReferenceInfo::H265(Default::default())),
+                    ..desc
+                },
+            )
+        },
+        Some("decodes H264, but a H265"),
+    );
+}
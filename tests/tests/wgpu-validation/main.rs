@@ -2,3 +2,5 @@
 
 mod api;
 mod noop;
+mod recorder;
+mod util;
@@ -1,6 +1,6 @@
 //! Tests of [`wgpu::Backend::Noop`].
 
-use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
 use std::sync::Arc;
 
 #[test]
@@ -19,7 +19,10 @@ fn device_is_available_when_requested() {
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: wgpu::Backends::NOOP,
         backend_options: wgpu::BackendOptions {
-            noop: wgpu::NoopBackendOptions { enable: true },
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
             ..Default::default()
         },
         ..Default::default()
@@ -29,6 +32,53 @@ fn device_is_available_when_requested() {
         .expect("noop backend adapter absent when it should be");
 }
 
+/// The EGL platform only applies to the OpenGL backend, whether or not it can create its
+/// instance with it.
+#[test]
+fn gl_platform_only_applies_to_gl() {
+    for platform in [
+        wgpu::GlPlatform::Automatic,
+        wgpu::GlPlatform::Surfaceless,
+        wgpu::GlPlatform::Device,
+    ] {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::NOOP | wgpu::Backends::GL,
+            backend_options: wgpu::BackendOptions {
+                gl: wgpu::GlBackendOptions {
+                    platform,
+                    ..Default::default()
+                },
+                noop: wgpu::NoopBackendOptions {
+                    enable: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let adapters = instance.enumerate_adapters(wgpu::Backends::all());
+        let backends: Vec<_> = adapters
+            .iter()
+            .map(|adapter| adapter.get_info().backend)
+            .collect();
+        assert!(
+            backends
+                .iter()
+                .all(|&backend| matches!(backend, wgpu::Backend::Noop | wgpu::Backend::Gl)),
+            "{platform:?}: {backends:?}"
+        );
+        assert_eq!(
+            backends
+                .iter()
+                .filter(|&&backend| backend == wgpu::Backend::Noop)
+                .count(),
+            1,
+            "{platform:?}: {backends:?}"
+        );
+    }
+}
+
 #[test]
 fn device_and_buffers() {
     let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
@@ -56,3 +106,71 @@ fn device_and_buffers() {
     device.poll(wgpu::PollType::Wait).unwrap();
     assert!(done2.load(Relaxed));
 }
+
+#[test]
+fn memory_budget() {
+    const BUDGET: u64 = 16 << 20;
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                memory_budget: Some(BUDGET),
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+    let report = device.get_memory_budget().unwrap();
+    assert_eq!(report.heaps.len(), 1);
+    assert_eq!(report.heaps[0].budget, BUDGET);
+    let initial_usage = report.heaps[0].usage;
+    assert!(initial_usage < BUDGET / 2);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls2 = calls.clone();
+    device.set_memory_budget_callback(50, move |report| {
+        assert!(report.heaps[0].exceeds(50));
+        calls2.fetch_add(1, Relaxed);
+    });
+
+    let create_buffer = || {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: BUDGET / 2,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        })
+    };
+
+    device.poll(wgpu::PollType::Poll).unwrap();
+    assert_eq!(calls.load(Relaxed), 0);
+
+    // Reaching the threshold calls the callback once.
+    let buffer = create_buffer();
+    assert_eq!(
+        device.get_memory_budget().unwrap().heaps[0].usage,
+        initial_usage + BUDGET / 2
+    );
+    device.poll(wgpu::PollType::Poll).unwrap();
+    device.poll(wgpu::PollType::Poll).unwrap();
+    assert_eq!(calls.load(Relaxed), 1);
+
+    // Dropping below the threshold and reaching it again calls it again.
+    drop(buffer);
+    device.poll(wgpu::PollType::Poll).unwrap();
+    assert_eq!(
+        device.get_memory_budget().unwrap().heaps[0].usage,
+        initial_usage
+    );
+    let _buffer = create_buffer();
+    device.poll(wgpu::PollType::Poll).unwrap();
+    assert_eq!(calls.load(Relaxed), 2);
+}
@@ -0,0 +1,157 @@
+//! Tests of [`wgpu_hal::recorder`], over the noop backend.
+
+use wgpu_hal::{api::Noop, recorder, Instance as _};
+
+fn recorded_instance() -> (wgpu::Instance, recorder::CallLog) {
+    let desc = wgpu_hal::InstanceDescriptor {
+        name: "recorder",
+        flags: wgpu::InstanceFlags::default(),
+        memory_budget_thresholds: Default::default(),
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    };
+    let hal_instance = unsafe { recorder::Instance::<Noop>::init(&desc) }.unwrap();
+    let log = hal_instance.call_log().clone();
+    let instance = unsafe { wgpu::Instance::from_hal::<recorder::Recorder<Noop>>(hal_instance) };
+    (instance, log)
+}
+
+#[test]
+fn records_encoder_calls() {
+    let (instance, log) = recorded_instance();
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("cleared"),
+        size: 16,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.clear_buffer(&buffer, 4, Some(8));
+    queue.submit([encoder.finish()]);
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    let calls = log.take();
+    let buffer_id = calls
+        .iter()
+        .find(|call| call.method == "create_buffer" && call.args[0].1.contains("\"cleared\""))
+        .and_then(|call| call.created)
+        .expect("buffer creation was not recorded");
+    assert_eq!(buffer_id.kind, recorder::ObjectKind::Buffer);
+
+    let clear_index = calls
+        .iter()
+        .position(|call| call.method == "clear_buffer" && call.args[0].1 == buffer_id.to_string())
+        .expect("buffer clear was not recorded");
+    let clear = &calls[clear_index];
+    assert_eq!(clear.object.kind, recorder::ObjectKind::CommandEncoder);
+    assert_eq!(clear.args[1], ("range", "4..12".to_string()));
+
+    assert!(
+        calls[clear_index..]
+            .iter()
+            .any(|call| call.object.kind == recorder::ObjectKind::Queue && call.method == "submit"),
+        "no submission was recorded after the clear: {calls:#?}"
+    );
+}
+
+/// Buffers move from the main queue to a compute queue, and back, with
+/// ownership transfers ordered by fence waits.
+#[test]
+fn records_queue_ownership_transfers() {
+    let (instance, log) = recorded_instance();
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_limits: wgpu::Limits {
+            max_compute_queues: 1,
+            ..wgpu::Limits::default()
+        },
+        ..Default::default()
+    }))
+    .unwrap();
+    let compute_queue = device
+        .create_queue(&wgpu::QueueDescriptor {
+            label: None,
+            queue_type: wgpu::QueueType::Compute,
+        })
+        .unwrap();
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("moved"),
+        size: 1 << 16,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    queue.write_buffer(&buffer, 0, &[1; 16]);
+
+    let mut encoder =
+        compute_queue.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.clear_buffer(&buffer, 0, Some(16));
+    compute_queue.submit([encoder.finish()]);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.clear_buffer(&buffer, 16, Some(16));
+    queue.submit([encoder.finish()]);
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    let calls = log.take();
+    let buffer_id = calls
+        .iter()
+        .find(|call| call.method == "create_buffer" && call.args[0].1.contains("\"moved\""))
+        .and_then(|call| call.created)
+        .expect("buffer creation was not recorded")
+        .to_string();
+    let compute_queue_id = calls
+        .iter()
+        .find(|call| call.method == "create_queue")
+        .and_then(|call| call.created)
+        .expect("queue creation was not recorded");
+    let transfer = |method: &str, queue_type: &str| {
+        calls
+            .iter()
+            .position(|call| {
+                call.method == method
+                    && call.args[0].1 == queue_type
+                    && call.args[1].1.contains(&buffer_id)
+            })
+            .unwrap_or_else(|| panic!("no {method} to {queue_type} was recorded: {calls:#?}"))
+    };
+    let next = |from: usize, method: &str, on_compute_queue: bool| {
+        from + calls[from..]
+            .iter()
+            .position(|call| {
+                call.method == method
+                    && call.object.kind == wgpu_hal::recorder::ObjectKind::Queue
+                    && (call.object == compute_queue_id) == on_compute_queue
+            })
+            .unwrap_or_else(|| panic!("no {method} was recorded after call {from}: {calls:#?}"))
+    };
+
+    // The main queue releases the buffer to the compute queue, which waits for
+    // that before acquiring it, and releases it back as its submission ends.
+    let release_to_compute = transfer("release_resources", "Compute");
+    let release_submit = next(release_to_compute, "submit", false);
+    let compute_wait = next(release_submit, "wait_for_fence", true);
+    let compute_submit = next(compute_wait, "submit", true);
+    assert!(transfer("acquire_resources", "Graphics") < compute_submit);
+    assert!(transfer("release_resources", "Graphics") < compute_submit);
+
+    // The main queue acquires it again when it next uses it. The compute
+    // submission is done by then on the noop backend, so there is no need to
+    // wait for it.
+    let acquire_from_compute = transfer("acquire_resources", "Compute");
+    assert!(acquire_from_compute > compute_submit);
+    next(acquire_from_compute, "submit", false);
+}
@@ -0,0 +1,284 @@
+//! Tests of [`wgpu::util::RenderGraph`].
+
+use std::cell::RefCell;
+
+use wgpu::util::{RenderGraph, RenderGraphError, RenderGraphResources, RenderGraphTexture};
+
+fn texture_descriptor(label: &str) -> wgpu::TextureDescriptor<'_> {
+    wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    }
+}
+
+/// Clears `texture` in a render pass.
+fn clear(
+    resources: &RenderGraphResources<'_>,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: RenderGraphTexture,
+) {
+    let view = resources.texture(texture).create_view(&Default::default());
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &view,
+            depth_slice: None,
+            resolve_target: None,
+            ops: wgpu::Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+}
+
+/// Ensures that passes whose results aren't used are culled, that the others run
+/// in the order they were added, and that transient textures whose lifetimes
+/// don't overlap share a texture.
+#[test]
+fn render_graph_compile_and_execute() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let output = device.create_texture(&texture_descriptor("output"));
+
+    let executed = RefCell::new(Vec::new());
+    let mut graph = RenderGraph::new();
+    let output = graph.import_texture("output", &output);
+    let scene = graph.create_texture(&texture_descriptor("scene"));
+    let blurred = graph.create_texture(&texture_descriptor("blurred"));
+    let tonemapped = graph.create_texture(&texture_descriptor("tonemapped"));
+    let debug = graph.create_texture(&texture_descriptor("debug"));
+
+    let mut add_pass = |label: &'static str, reads: &[RenderGraphTexture], write| {
+        let mut pass = graph.add_pass(label).write(write);
+        for &texture in reads {
+            pass = pass.read(texture);
+        }
+        let executed = &executed;
+        pass.execute(move |resources, encoder| {
+            executed.borrow_mut().push(label);
+            clear(resources, encoder, write);
+        })
+    };
+    let draw = add_pass("scene", &[], scene);
+    let draw_debug = add_pass("debug", &[], debug);
+    let blur = add_pass("blur", &[scene], blurred);
+    let tonemap = add_pass("tonemap", &[blurred], tonemapped);
+    let present = add_pass("present", &[tonemapped], output);
+
+    let compiled = graph.compile().unwrap();
+    assert_eq!(compiled.pass_order(), [draw, blur, tonemap, present]);
+    assert_eq!(compiled.culled_passes(), [draw_debug]);
+    assert_eq!(compiled.pass_label(draw_debug), "debug");
+
+    assert_eq!(compiled.texture_slot_count(), 2);
+    assert_eq!(compiled.texture_slot(output), None);
+    assert_eq!(compiled.texture_slot(debug), None);
+    assert_eq!(compiled.texture_slot(scene), Some(0));
+    assert_eq!(compiled.texture_slot(blurred), Some(1));
+    assert_eq!(compiled.texture_slot(tonemapped), Some(0));
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    compiled.execute(&device, &mut encoder);
+    queue.submit([encoder.finish()]);
+    assert_eq!(*executed.borrow(), ["scene", "blur", "tonemap", "present"]);
+}
+
+/// Ensures that a pass reads the version of a resource written by the last pass
+/// added before it, rather than waiting for every pass that writes it.
+#[test]
+fn render_graph_versions() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let output = device.create_texture(&texture_descriptor("output"));
+    let history = device.create_texture(&texture_descriptor("history"));
+
+    let mut graph = RenderGraph::new();
+    let output = graph.import_texture("output", &output);
+    let history = graph.import_texture("history", &history);
+    let color = graph.create_texture(&texture_descriptor("color"));
+    let bloom = graph.create_texture(&texture_descriptor("bloom"));
+
+    let draw = graph.add_pass("draw").write(color).execute(|_, _| {});
+    let bloom_pass = graph
+        .add_pass("bloom")
+        .read(color)
+        .write(bloom)
+        .execute(|_, _| {});
+    // Without versions, "bloom" would run after this pass, which runs after "bloom".
+    let composite = graph
+        .add_pass("composite")
+        .read(bloom)
+        .write(color)
+        .execute(|_, _| {});
+    // Reads the previous frame, so it runs before the pass that updates it.
+    let resolve = graph
+        .add_pass("resolve")
+        .read(color)
+        .read(history)
+        .write(output)
+        .execute(|_, _| {});
+    let update = graph
+        .add_pass("update history")
+        .read(color)
+        .write(history)
+        .execute(|_, _| {});
+    // Nothing reads the version this pass writes.
+    let overlay = graph.add_pass("overlay").write(color).execute(|_, _| {});
+
+    let compiled = graph.compile().unwrap();
+    assert_eq!(
+        compiled.pass_order(),
+        [draw, bloom_pass, composite, resolve, update]
+    );
+    assert_eq!(compiled.culled_passes(), [overlay]);
+}
+
+/// Ensures that transient textures that only differ in their usages and view
+/// formats share a texture created with all of them.
+#[test]
+fn render_graph_compatible_textures() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TRANSIENT_ATTACHMENTS,
+        ..Default::default()
+    });
+    let output = device.create_texture(&texture_descriptor("output"));
+
+    let mut graph = RenderGraph::new();
+    let output = graph.import_texture("output", &output);
+    let first = graph.create_texture(&wgpu::TextureDescriptor {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        ..texture_descriptor("first")
+    });
+    let second = graph.create_texture(&wgpu::TextureDescriptor {
+        view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
+        ..texture_descriptor("second")
+    });
+    let transient = graph.create_texture(&wgpu::TextureDescriptor {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT,
+        ..texture_descriptor("transient")
+    });
+    let larger = graph.create_texture(&wgpu::TextureDescriptor {
+        size: wgpu::Extent3d {
+            width: 8,
+            height: 8,
+            depth_or_array_layers: 1,
+        },
+        ..texture_descriptor("larger")
+    });
+
+    let third = graph.create_texture(&texture_descriptor("third"));
+
+    // Each pass reads the texture the previous one writes, so only the textures two
+    // passes apart can share a texture.
+    let mut previous = None;
+    for texture in [first, transient, second, third, larger] {
+        let mut pass = graph.add_pass("pass").write(texture);
+        if let Some(previous) = previous {
+            pass = pass.read(previous);
+        }
+        pass.execute(move |resources, _| {
+            if texture == third {
+                assert_eq!(
+                    resources.texture(second).usage(),
+                    wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_SRC
+                );
+            }
+        });
+        previous = Some(texture);
+    }
+    graph
+        .add_pass("output")
+        .read(larger)
+        .write(output)
+        .execute(|_, _| {});
+
+    let compiled = graph.compile().unwrap();
+    assert_eq!(compiled.texture_slot(first), Some(0));
+    assert_eq!(compiled.texture_slot(transient), Some(1));
+    assert_eq!(compiled.texture_slot(second), Some(0));
+    // The slot of `transient` is free again, but it's only created for transient attachments.
+    assert_eq!(compiled.texture_slot(third), Some(2));
+    assert_eq!(compiled.texture_slot(larger), Some(3));
+    assert_eq!(compiled.texture_slot_count(), 4);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    compiled.execute(&device, &mut encoder);
+    queue.submit([encoder.finish()]);
+}
+
+#[test]
+fn render_graph_errors() {
+    // The pass that writes the texture is only added after the pass that reads it.
+    let mut graph = RenderGraph::new();
+    let first = graph.create_texture(&texture_descriptor("first"));
+    let second = graph.create_texture(&texture_descriptor("second"));
+    graph
+        .add_pass("a")
+        .read(first)
+        .write(second)
+        .keep()
+        .execute(|_, _| {});
+    graph
+        .add_pass("b")
+        .read(second)
+        .write(first)
+        .execute(|_, _| {});
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        RenderGraphError::UnwrittenTexture {
+            pass: "a".to_owned(),
+            texture: "first".to_owned(),
+        }
+    );
+
+    let mut graph = RenderGraph::new();
+    let texture = graph.create_texture(&texture_descriptor("texture"));
+    graph
+        .add_pass("pass")
+        .read(texture)
+        .keep()
+        .execute(|_, _| {});
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        RenderGraphError::UnwrittenTexture {
+            pass: "pass".to_owned(),
+            texture: "texture".to_owned(),
+        }
+    );
+}
+
+#[test]
+#[should_panic = "Render graph pass \"pass\" uses \"other\" without declaring it"]
+fn render_graph_undeclared_resource() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let mut graph = RenderGraph::new();
+    let texture = graph.create_texture(&texture_descriptor("texture"));
+    let other = graph.create_texture(&texture_descriptor("other"));
+    graph
+        .add_pass("other pass")
+        .write(other)
+        .keep()
+        .execute(|_, _| {});
+    graph
+        .add_pass("pass")
+        .write(texture)
+        .keep()
+        .execute(move |resources, _| {
+            resources.texture(other);
+        });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    graph.compile().unwrap().execute(&device, &mut encoder);
+}
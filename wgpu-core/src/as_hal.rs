@@ -222,6 +222,9 @@ unsafe impl<Fence> Send for FenceGuard<Fence> where Fence: Send {}
 unsafe impl<Fence> Sync for FenceGuard<Fence> where Fence: Sync {}
 
 impl Global {
+    /// Returns `None` if the buffer shares its raw buffer with other buffers,
+    /// see [`wgt::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS`].
+    ///
     /// # Safety
     ///
     /// - The raw buffer handle must not be manually destroyed
@@ -235,6 +238,12 @@ impl Global {
 
         let buffer = hub.buffers.get(id).get().ok()?;
 
+        let snatch_guard = buffer.device.snatchable_lock.read();
+        if buffer.is_sub_allocated(&snatch_guard) {
+            return None;
+        }
+        drop(snatch_guard);
+
         SnatchableResourceGuard::new(buffer)
     }
 
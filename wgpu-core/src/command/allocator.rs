@@ -12,41 +12,54 @@ use crate::lock::{rank, Mutex};
 /// [`wgpu_hal::CommandEncoder`]: hal::CommandEncoder
 /// [ce]: hal::CommandEncoder
 /// [cb]: hal::Api::CommandBuffer
+/// Encoders are kept in a separate pool for each [`wgt::QueueType`], as the
+/// command buffers of an encoder can only be submitted to queues of the type
+/// it was created for.
 pub(crate) struct CommandAllocator {
-    free_encoders: Mutex<Vec<Box<dyn hal::DynCommandEncoder>>>,
+    free_encoders: Mutex<[Vec<Box<dyn hal::DynCommandEncoder>>; 3]>,
 }
 
 impl CommandAllocator {
     pub(crate) fn new() -> Self {
         Self {
-            free_encoders: Mutex::new(rank::COMMAND_ALLOCATOR_FREE_ENCODERS, Vec::new()),
+            free_encoders: Mutex::new(rank::COMMAND_ALLOCATOR_FREE_ENCODERS, Default::default()),
         }
     }
 
     /// Return a fresh [`wgpu_hal::CommandEncoder`] in the "closed" state.
     ///
-    /// If we have free encoders in the pool, take one of those. Otherwise,
-    /// create a new one on `device`.
+    /// If we have free encoders for queues of `queue_type` in the pool, take
+    /// one of those. Otherwise, create a new one on `device`, for `queue`,
+    /// which must be of `queue_type`.
     ///
     /// [`wgpu_hal::CommandEncoder`]: hal::CommandEncoder
     pub(crate) fn acquire_encoder(
         &self,
         device: &dyn hal::DynDevice,
         queue: &dyn hal::DynQueue,
+        queue_type: wgt::QueueType,
     ) -> Result<Box<dyn hal::DynCommandEncoder>, hal::DeviceError> {
         let mut free_encoders = self.free_encoders.lock();
-        match free_encoders.pop() {
+        match free_encoders[queue_type as usize].pop() {
             Some(encoder) => Ok(encoder),
             None => unsafe {
-                let hal_desc = hal::CommandEncoderDescriptor { label: None, queue };
+                let hal_desc = hal::CommandEncoderDescriptor {
+                    label: None,
+                    queue,
+                    reusable: false,
+                };
                 device.create_command_encoder(&hal_desc)
             },
         }
     }
 
-    /// Add `encoder` back to the free pool.
-    pub(crate) fn release_encoder(&self, encoder: Box<dyn hal::DynCommandEncoder>) {
+    /// Add `encoder`, created for queues of `queue_type`, back to the free pool.
+    pub(crate) fn release_encoder(
+        &self,
+        queue_type: wgt::QueueType,
+        encoder: Box<dyn hal::DynCommandEncoder>,
+    ) {
         let mut free_encoders = self.free_encoders.lock();
-        free_encoders.push(encoder);
+        free_encoders[queue_type as usize].push(encoder);
     }
 }
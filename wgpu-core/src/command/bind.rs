@@ -321,14 +321,28 @@ impl Binder {
             payloads: Default::default(),
         }
     }
-    pub(super) fn reset(&mut self) {
+    /// Unset the pipeline layout and all bind groups, except the ones at the
+    /// indices for which `keep` returns true.
+    ///
+    /// This is used after executing a render bundle, which leaves the bind
+    /// groups it inherited from the pass bound.
+    pub(super) fn reset_except(&mut self, keep: impl Fn(usize) -> bool) {
         self.pipeline_layout = None;
         self.manager = compat::BoundBindGroupLayouts::new();
-        for payload in self.payloads.iter_mut() {
-            payload.reset();
+        for (index, payload) in self.payloads.iter_mut().enumerate() {
+            match payload.group {
+                Some(ref group) if keep(index) => {
+                    self.manager.assign(index, group.layout.clone());
+                }
+                _ => payload.reset(),
+            }
         }
     }
 
+    pub(super) fn payload(&self, index: usize) -> &EntryPayload {
+        &self.payloads[index]
+    }
+
     pub(super) fn change_pipeline_layout<'a>(
         &'a mut self,
         new: &Arc<PipelineLayout>,
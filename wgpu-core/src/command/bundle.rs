@@ -25,9 +25,19 @@ was set in the `RenderPass` before executing the bundle. We call this property
 'isolation', in that a render bundle is somewhat isolated from the passes that
 use it.
 
+The exception are the bind groups listed in
+[`RenderBundleEncoderDescriptor::inherited_bind_group_layouts`]: the bundle
+can't set bind groups at these indices, and instead uses the ones the pass has
+set there when the bundle is executed. Their layouts are fixed when the bundle
+is created, so the bundle's pipelines can be validated against them while
+finishing it, and `ExecuteBundle` only needs to check that the pass's bind
+groups have those layouts.
+
 Render passes are also isolated from the effects of bundles. After executing a
 render bundle, a render pass's pipeline, bind groups, and vertex and index
 buffers are are unset, so the bundle cannot affect later draw calls in the pass.
+Only the bind groups the bundle inherited stay set, so that several bundles
+executed in a row can share them.
 
 A render pass is not fully isolated from a bundle's effects on push constant
 values. Draw calls following a bundle's execution will see whatever values the
@@ -69,7 +79,9 @@ verbatim without any state tracking.
 When the bundle is used in an actual render pass, `RenderBundle::execute` is
 called. It goes through the commands and issues them into the native command
 buffer. Thanks to isolation, it doesn't track any bind group invalidations or
-index format changes.
+index format changes. The only bind groups it has to re-set on its own are the
+inherited ones, whenever the bundle switches to a pipeline with a different
+layout.
 
 [Gdcrbe]: crate::global::Global::device_create_render_bundle_encoder
 [Grbef]: crate::global::Global::render_bundle_encoder_finish
@@ -97,14 +109,14 @@ use wgpu_hal::ShouldBeNonZeroExt;
 use wgt::error::{ErrorType, WebGpuError};
 
 use crate::{
-    binding_model::{BindError, BindGroup, PipelineLayout},
+    binding_model::{BindError, BindGroup, BindGroupLayout, PipelineLayout},
     command::{
         BasePass, BindGroupStateChange, ColorAttachmentError, DrawError, MapPassErr,
         PassErrorScope, RenderCommandError, StateChange,
     },
     device::{
-        AttachmentData, Device, DeviceError, MissingDownlevelFlags, RenderPassContext,
-        SHADER_STAGE_COUNT,
+        AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
+        RenderPassContext, SHADER_STAGE_COUNT,
     },
     hub::Hub,
     id,
@@ -112,7 +124,7 @@ use crate::{
     pipeline::{PipelineFlags, RenderPipeline, VertexStep},
     resource::{
         Buffer, DestroyedResourceError, Fallible, InvalidResourceError, Labeled, ParentDevice,
-        RawResourceAccess, TrackingData,
+        RawResourceAccess, ResourceErrorIdent, TrackingData,
     },
     resource_log,
     snatch::SnatchGuard,
@@ -121,6 +133,7 @@ use crate::{
 };
 
 use super::{
+    bind::Binder,
     pass,
     render_command::{ArcRenderCommand, RenderCommand},
     DrawCommandFamily, DrawKind,
@@ -153,6 +166,14 @@ pub struct RenderBundleEncoderDescriptor<'a> {
     /// If this render bundle will rendering to multiple array layers in the
     /// attachments at the same time.
     pub multiview: Option<NonZeroU32>,
+    /// The layouts of the bind groups this render bundle inherits from the
+    /// render pass it is executed in, indexed by bind group index.
+    ///
+    /// The bundle may not set bind groups at these indices itself. Instead, the
+    /// render pass must have a bind group with a matching layout set at each of
+    /// them when the bundle is executed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inherited_bind_group_layouts: Cow<'a, [Option<id::BindGroupLayoutId>]>,
 }
 
 #[derive(Debug)]
@@ -163,6 +184,7 @@ pub struct RenderBundleEncoder {
     pub(crate) context: RenderPassContext,
     pub(crate) is_depth_read_only: bool,
     pub(crate) is_stencil_read_only: bool,
+    pub(crate) inherited_bind_group_layouts: Vec<Option<id::BindGroupLayoutId>>,
 
     // Resource binding dedupe state.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -226,6 +248,7 @@ impl RenderBundleEncoder {
 
             is_depth_read_only,
             is_stencil_read_only,
+            inherited_bind_group_layouts: desc.inherited_bind_group_layouts.to_vec(),
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
         })
@@ -246,6 +269,7 @@ impl RenderBundleEncoder {
             },
             is_depth_read_only: false,
             is_stencil_read_only: false,
+            inherited_bind_group_layouts: Vec::new(),
 
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
@@ -281,6 +305,10 @@ impl RenderBundleEncoder {
 
         device.check_is_valid().map_pass_err(scope)?;
 
+        let inherited_bind_group_layouts =
+            resolve_inherited_bind_group_layouts(device, hub, &self.inherited_bind_group_layouts)
+                .map_pass_err(scope)?;
+
         let bind_group_guard = hub.bind_groups.read();
         let pipeline_guard = hub.render_pipelines.read();
         let buffer_guard = hub.buffers.read();
@@ -289,6 +317,7 @@ impl RenderBundleEncoder {
             trackers: RenderBundleScope::new(),
             pipeline: None,
             bind: (0..hal::MAX_BIND_GROUPS).map(|_| None).collect(),
+            inherited_bind_group_layouts,
             vertex: Default::default(),
             index: None,
             flat_dynamic_offsets: Vec::new(),
@@ -454,6 +483,7 @@ impl RenderBundleEncoder {
                 }
                 RenderCommand::DrawIndirect { .. }
                 | RenderCommand::MultiDrawIndirectCount { .. }
+                | RenderCommand::ExecuteIndirect { .. }
                 | RenderCommand::PushDebugGroup { color: _, len: _ }
                 | RenderCommand::InsertDebugMarker { color: _, len: _ }
                 | RenderCommand::PopDebugGroup => {
@@ -477,6 +507,7 @@ impl RenderBundleEncoder {
 
         let State {
             trackers,
+            inherited_bind_group_layouts,
             flat_dynamic_offsets,
             device,
             commands,
@@ -501,6 +532,7 @@ impl RenderBundleEncoder {
             },
             is_depth_read_only: self.is_depth_read_only,
             is_stencil_read_only: self.is_stencil_read_only,
+            inherited_bind_group_layouts,
             device: device.clone(),
             used: trackers,
             buffer_memory_init_actions,
@@ -532,6 +564,41 @@ impl RenderBundleEncoder {
     }
 }
 
+/// Look up the layouts of the bind groups a bundle inherits from the pass.
+fn resolve_inherited_bind_group_layouts(
+    device: &Arc<Device>,
+    hub: &Hub,
+    layout_ids: &[Option<id::BindGroupLayoutId>],
+) -> Result<ArrayVec<Option<Arc<BindGroupLayout>>, { hal::MAX_BIND_GROUPS }>, RenderBundleErrorInner>
+{
+    if layout_ids.iter().any(Option::is_some) {
+        device.require_features(wgt::Features::RENDER_BUNDLE_INHERITANCE)?;
+    }
+    let max_bind_groups = device.limits.max_bind_groups;
+    if layout_ids.len() > max_bind_groups as usize {
+        return Err(
+            RenderCommandError::BindGroupIndexOutOfRange(pass::BindGroupIndexOutOfRange {
+                index: layout_ids.len() as u32 - 1,
+                max: max_bind_groups,
+            })
+            .into(),
+        );
+    }
+
+    let bind_group_layout_guard = hub.bind_group_layouts.read();
+    layout_ids
+        .iter()
+        .map(|&layout_id| {
+            let Some(layout_id) = layout_id else {
+                return Ok(None);
+            };
+            let layout = bind_group_layout_guard.get(layout_id).get()?;
+            layout.same_device(device)?;
+            Ok(Some(layout))
+        })
+        .collect()
+}
+
 fn set_bind_group(
     state: &mut State,
     bind_group_guard: &crate::storage::Storage<Fallible<BindGroup>>,
@@ -562,6 +629,10 @@ fn set_bind_group(
         );
     }
 
+    if let Some(Some(_)) = state.inherited_bind_group_layouts.get(index as usize) {
+        return Err(RenderBundleErrorInner::SetInheritedBindGroup { index });
+    }
+
     // Identify the next `num_dynamic_offsets` entries from `dynamic_offsets`.
     let offsets_range = state.next_dynamic_offset..state.next_dynamic_offset + num_dynamic_offsets;
     state.next_dynamic_offset = offsets_range.end;
@@ -597,7 +668,8 @@ fn set_pipeline(
     pipeline.same_device(&state.device)?;
 
     context
-        .check_compatible(&pipeline.pass_context, pipeline.as_ref())
+        .check_framebuffer_fetches(&pipeline)
+        .and_then(|()| context.check_compatible(&pipeline.pass_context, pipeline.as_ref()))
         .map_err(RenderCommandError::IncompatiblePipelineTargets)?;
 
     if pipeline.flags.contains(PipelineFlags::WRITES_DEPTH) && is_depth_read_only {
@@ -607,6 +679,26 @@ fn set_pipeline(
         return Err(RenderCommandError::IncompatibleStencilAccess(pipeline.error_ident()).into());
     }
 
+    for (index, (inherited, layout)) in state
+        .inherited_bind_group_layouts
+        .iter()
+        .zip(&pipeline.layout.bind_group_layouts)
+        .enumerate()
+    {
+        if let Some(inherited) = inherited {
+            if !inherited.is_equal(layout) {
+                return Err(
+                    RenderBundleErrorInner::IncompatibleInheritedBindGroupLayout {
+                        index: index as u32,
+                        pipeline: pipeline.error_ident(),
+                        expected: layout.error_ident(),
+                        inherited: inherited.error_ident(),
+                    },
+                );
+            }
+        }
+    }
+
     let pipeline_state = PipelineState::new(&pipeline);
 
     state
@@ -948,6 +1040,10 @@ pub struct RenderBundle {
     base: BasePass<ArcRenderCommand, Infallible>,
     pub(super) is_depth_read_only: bool,
     pub(super) is_stencil_read_only: bool,
+    /// The layouts of the bind groups this bundle inherits from the pass,
+    /// indexed by bind group index.
+    pub(super) inherited_bind_group_layouts:
+        ArrayVec<Option<Arc<BindGroupLayout>>, { hal::MAX_BIND_GROUPS }>,
     pub(crate) device: Arc<Device>,
     pub(crate) used: RenderBundleScope,
     pub(super) buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
@@ -980,9 +1076,14 @@ impl RenderBundle {
     /// Note that the function isn't expected to fail, generally.
     /// All the validation has already been done by this point.
     /// The only failure condition is if some of the used buffers are destroyed.
+    ///
+    /// `binder` must hold compatible bind groups at every index in
+    /// [`Self::inherited_bind_group_layouts`]. They are re-set whenever the
+    /// bundle switches to a pipeline with a different layout.
     pub(super) unsafe fn execute(
         &self,
         raw: &mut dyn hal::DynCommandEncoder,
+        binder: &Binder,
         indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
         indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
         snatch_guard: &SnatchGuard,
@@ -1021,8 +1122,35 @@ impl RenderBundle {
                 }
                 Cmd::SetPipeline(pipeline) => {
                     unsafe { raw.set_render_pipeline(pipeline.raw()) };
+                    if let Some(ref bounds_reporter) = self.device.bounds_reporter {
+                        bounds_reporter.bind(raw, pipeline.layout.raw());
+                    }
 
+                    let layout_changed = !pipeline_layout
+                        .as_ref()
+                        .is_some_and(|layout| layout.is_equal(&pipeline.layout));
                     pipeline_layout = Some(pipeline.layout.clone());
+
+                    if layout_changed {
+                        let inherited_indices = self
+                            .inherited_bind_group_layouts
+                            .iter()
+                            .take(pipeline.layout.bind_group_layouts.len())
+                            .enumerate()
+                            .filter_map(|(index, layout)| layout.as_ref().map(|_| index));
+                        for index in inherited_indices {
+                            let payload = binder.payload(index);
+                            let bind_group = payload.group.as_ref().unwrap();
+                            unsafe {
+                                raw.set_bind_group(
+                                    pipeline.layout.raw(),
+                                    index as u32,
+                                    Some(bind_group.try_raw(snatch_guard)?),
+                                    &payload.dynamic_offsets,
+                                )
+                            };
+                        }
+                    }
                 }
                 Cmd::SetIndexBuffer {
                     buffer,
@@ -1030,10 +1158,9 @@ impl RenderBundle {
                     offset,
                     size,
                 } => {
-                    let buffer = buffer.try_raw(snatch_guard)?;
-                    // SAFETY: The binding size was checked against the buffer size
+                    // The binding size was checked against the buffer size
                     // in `set_index_buffer` and again in `IndexState::flush`.
-                    let bb = hal::BufferBinding::new_unchecked(buffer, *offset, *size);
+                    let bb = buffer.raw_binding(*offset, *size, snatch_guard)?;
                     unsafe { raw.set_index_buffer(bb, *index_format) };
                 }
                 Cmd::SetVertexBuffer {
@@ -1042,10 +1169,9 @@ impl RenderBundle {
                     offset,
                     size,
                 } => {
-                    let buffer = buffer.try_raw(snatch_guard)?;
-                    // SAFETY: The binding size was checked against the buffer size
+                    // The binding size was checked against the buffer size
                     // in `set_vertex_buffer` and again in `VertexState::flush`.
-                    let bb = hal::BufferBinding::new_unchecked(buffer, *offset, *size);
+                    let bb = buffer.raw_binding(*offset, *size, snatch_guard)?;
                     unsafe { raw.set_vertex_buffer(*slot, bb) };
                 }
                 Cmd::SetPushConstant {
@@ -1165,6 +1291,9 @@ impl RenderBundle {
                 Cmd::DrawIndirect { .. } | Cmd::MultiDrawIndirectCount { .. } => {
                     return Err(ExecutionError::Unimplemented("multi-draw-indirect"))
                 }
+                Cmd::ExecuteIndirect { .. } => {
+                    return Err(ExecutionError::Unimplemented("execute-indirect"))
+                }
                 Cmd::PushDebugGroup { .. } | Cmd::InsertDebugMarker { .. } | Cmd::PopDebugGroup => {
                     return Err(ExecutionError::Unimplemented("debug-markers"))
                 }
@@ -1396,6 +1525,10 @@ struct State {
     /// The bind group set at each index, if any.
     bind: ArrayVec<Option<BindState>, { hal::MAX_BIND_GROUPS }>,
 
+    /// The layouts of the bind groups inherited from the pass. Becomes
+    /// [`RenderBundle::inherited_bind_group_layouts`].
+    inherited_bind_group_layouts: ArrayVec<Option<Arc<BindGroupLayout>>, { hal::MAX_BIND_GROUPS }>,
+
     /// The state of each vertex buffer slot.
     vertex: [Option<VertexState>; hal::MAX_VERTEX_BUFFERS],
 
@@ -1600,9 +1733,22 @@ pub(super) enum RenderBundleErrorInner {
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
     #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
     Bind(#[from] BindError),
     #[error(transparent)]
     InvalidResource(#[from] InvalidResourceError),
+    #[error(
+        "Bind group index {index} is inherited from the render pass and can't be set in the bundle"
+    )]
+    SetInheritedBindGroup { index: u32 },
+    #[error("{pipeline} expects {expected} at bind group index {index}, but the bundle inherits {inherited} from the render pass there")]
+    IncompatibleInheritedBindGroupLayout {
+        index: u32,
+        pipeline: ResourceErrorIdent,
+        expected: ResourceErrorIdent,
+        inherited: ResourceErrorIdent,
+    },
 }
 
 impl<T> From<T> for RenderBundleErrorInner
@@ -1631,8 +1777,13 @@ impl WebGpuError for RenderBundleError {
             RenderBundleErrorInner::RenderCommand(e) => e,
             RenderBundleErrorInner::Draw(e) => e,
             RenderBundleErrorInner::MissingDownlevelFlags(e) => e,
+            RenderBundleErrorInner::MissingFeatures(e) => e,
             RenderBundleErrorInner::Bind(e) => e,
             RenderBundleErrorInner::InvalidResource(e) => e,
+            RenderBundleErrorInner::SetInheritedBindGroup { .. }
+            | RenderBundleErrorInner::IncompatibleInheritedBindGroupLayout { .. } => {
+                return ErrorType::Validation
+            }
         };
         e.webgpu_error_type()
     }
@@ -37,6 +37,13 @@ pub enum ClearError {
     MissingFeatures(#[from] MissingFeatures),
     #[error("{0} can not be cleared")]
     NoValidTextureClearMode(ResourceErrorIdent),
+    #[error(
+        "{texture} can only be cleared by a render pass, which {queue_type:?} queues can not run"
+    )]
+    RenderPassClearOnQueue {
+        texture: ResourceErrorIdent,
+        queue_type: wgt::QueueType,
+    },
     #[error("Buffer clear size {0:?} is not a multiple of `COPY_BUFFER_ALIGNMENT`")]
     UnalignedFillSize(BufferAddress),
     #[error("Buffer offset {0:?} is not a multiple of `COPY_BUFFER_ALIGNMENT`")]
@@ -91,6 +98,7 @@ impl WebGpuError for ClearError {
             Self::EncoderState(e) => e,
             Self::InvalidResource(e) => e,
             Self::NoValidTextureClearMode(..)
+            | Self::RenderPassClearOnQueue { .. }
             | Self::UnalignedFillSize(..)
             | Self::UnalignedBufferOffset(..)
             | Self::OffsetPlusSizeExceeds64BitBounds { .. }
@@ -183,7 +191,10 @@ impl Global {
             let cmd_buf_raw = cmd_buf_data.encoder.open()?;
             unsafe {
                 cmd_buf_raw.transition_buffers(dst_barrier.as_slice());
-                cmd_buf_raw.clear_buffer(dst_raw, offset..end_offset);
+                cmd_buf_raw.clear_buffer(
+                    dst_raw,
+                    dst_buffer.raw_offset + offset..dst_buffer.raw_offset + end_offset,
+                );
             }
 
             Ok(())
@@ -259,6 +270,7 @@ impl Global {
 
             let device = &cmd_enc.device;
             device.check_is_valid()?;
+            let queue_type = cmd_buf_data.encoder.queue_type;
             let (encoder, tracker) = cmd_buf_data.open_encoder_and_tracker()?;
 
             let snatch_guard = device.snatchable_lock.read();
@@ -269,6 +281,7 @@ impl Global {
                     layer_range: subresource_layer_range,
                 },
                 encoder,
+                queue_type,
                 &mut tracker.textures,
                 &device.alignments,
                 device.zero_buffer.as_ref(),
@@ -281,10 +294,31 @@ impl Global {
     }
 }
 
+/// Check that `texture` can be cleared by commands submitted to queues of
+/// `queue_type`, which can not run the render passes some textures are
+/// cleared with unless they are graphics queues.
+pub(crate) fn check_clear_queue_type(
+    texture: &Texture,
+    queue_type: wgt::QueueType,
+) -> Result<(), ClearError> {
+    let needs_render_pass = matches!(
+        *texture.clear_mode.read(),
+        TextureClearMode::RenderPass { .. } | TextureClearMode::Surface { .. }
+    );
+    if needs_render_pass && queue_type != wgt::QueueType::Graphics {
+        return Err(ClearError::RenderPassClearOnQueue {
+            texture: texture.error_ident(),
+            queue_type,
+        });
+    }
+    Ok(())
+}
+
 pub(crate) fn clear_texture<T: TextureTrackerSetSingle>(
     dst_texture: &Arc<Texture>,
     range: TextureInitRange,
     encoder: &mut dyn hal::DynCommandEncoder,
+    queue_type: wgt::QueueType,
     texture_tracker: &mut T,
     alignments: &hal::Alignments,
     zero_buffer: &dyn hal::DynBuffer,
@@ -292,6 +326,7 @@ pub(crate) fn clear_texture<T: TextureTrackerSetSingle>(
     instance_flags: wgt::InstanceFlags,
 ) -> Result<(), ClearError> {
     let dst_raw = dst_texture.try_raw(snatch_guard)?;
+    check_clear_queue_type(dst_texture, queue_type)?;
 
     // Issue the right barrier.
     let clear_usage = match *dst_texture.clear_mode.read() {
@@ -190,6 +190,8 @@ pub enum ComputePassErrorInner {
     // This one is unreachable, but required for generic pass support
     #[error(transparent)]
     InvalidValuesOffset(#[from] pass::InvalidValuesOffset),
+    #[error("Compute passes can not be encoded for {0:?} queues")]
+    UnsupportedQueueType(wgt::QueueType),
 }
 
 /// Error encountered when performing a compute pass, stored for later reporting
@@ -247,6 +249,7 @@ impl WebGpuError for ComputePassError {
             | ComputePassErrorInner::PushConstantOffsetAlignment
             | ComputePassErrorInner::PushConstantSizeAlignment
             | ComputePassErrorInner::PushConstantOutOfMemory
+            | ComputePassErrorInner::UnsupportedQueueType(_)
             | ComputePassErrorInner::PassEnded => return ErrorType::Validation,
         };
         e.webgpu_error_type()
@@ -356,6 +359,13 @@ impl Global {
                         None,
                     );
                 }
+                if cmd_enc.queue_type == wgt::QueueType::Transfer {
+                    let err = ComputePassErrorInner::UnsupportedQueueType(cmd_enc.queue_type);
+                    return (
+                        ComputePass::new_invalid(cmd_enc, &label, err.map_pass_err(scope)),
+                        None,
+                    );
+                }
 
                 match desc
                     .timestamp_writes
@@ -537,6 +547,8 @@ impl Global {
 
             let snatch_guard = device.snatchable_lock.read();
 
+            let intermediate_trackers = cmd_buf_data.trackers.new_intermediate();
+
             let mut state = State {
                 pipeline: None,
 
@@ -563,7 +575,7 @@ impl Global {
 
                 push_constants: Vec::new(),
 
-                intermediate_trackers: Tracker::new(),
+                intermediate_trackers,
             };
 
             let indices = &state.general.device.tracker_indices;
@@ -752,7 +764,7 @@ impl Global {
                         pending_discard_init_fixups,
                         ..
                     },
-                intermediate_trackers,
+                mut intermediate_trackers,
                 ..
             } = state;
 
@@ -781,6 +793,8 @@ impl Global {
                 &intermediate_trackers,
                 &snatch_guard,
             );
+            // The barriers inside the pass come after the ones inserted before it.
+            tracker.append_transition_log(&mut intermediate_trackers);
             // Close the command encoder, and swap it with the previous.
             encoder.close_and_swap().map_pass_err(pass_scope)?;
 
@@ -810,6 +824,9 @@ fn set_pipeline(
             .general
             .raw_encoder
             .set_compute_pipeline(pipeline.raw());
+        if let Some(ref bounds_reporter) = state.general.device.bounds_reporter {
+            bounds_reporter.bind(state.general.raw_encoder, pipeline.layout.raw());
+        }
     }
 
     // Rebind resources
@@ -991,6 +1008,9 @@ fn dispatch_indirect(
                     .general
                     .raw_encoder
                     .set_compute_pipeline(pipeline.raw());
+                if let Some(ref bounds_reporter) = state.general.device.bounds_reporter {
+                    bounds_reporter.bind(state.general.raw_encoder, pipeline.layout.raw());
+                }
             }
 
             if !state.push_constants.is_empty() {
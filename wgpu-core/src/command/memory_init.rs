@@ -15,7 +15,10 @@ use crate::{
     FastHashMap,
 };
 
-use super::{clear::clear_texture, BakedCommands, ClearError};
+use super::{
+    clear::{check_clear_queue_type, clear_texture},
+    BakedCommands, ClearError,
+};
 
 /// Surface that was discarded by `StoreOp::Discard` of a preceding renderpass.
 /// Any read access to this surface needs to be preceded by a texture initialization.
@@ -28,7 +31,7 @@ pub(crate) struct TextureSurfaceDiscard {
 
 pub(crate) type SurfacesInDiscardState = Vec<TextureSurfaceDiscard>;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub(crate) struct CommandBufferTextureMemoryActions {
     /// The tracker actions that we need to be executed before the command
     /// buffer is executed.
@@ -145,6 +148,7 @@ pub(crate) fn fixup_discarded_surfaces<InitIter: Iterator<Item = TextureSurfaceD
                 layer_range: init.layer..(init.layer + 1),
             },
             encoder,
+            wgt::QueueType::Graphics,
             texture_tracker,
             &device.alignments,
             device.zero_buffer.as_ref(),
@@ -247,7 +251,10 @@ impl BakedCommands {
                 );
 
                 unsafe {
-                    self.encoder.raw.clear_buffer(raw_buf, range.clone());
+                    self.encoder.raw.clear_buffer(
+                        raw_buf,
+                        buffer.raw_offset + range.start..buffer.raw_offset + range.end,
+                    );
                 }
             }
         }
@@ -263,13 +270,26 @@ impl BakedCommands {
         device_tracker: &mut DeviceTracker,
         device: &Device,
         snatch_guard: &SnatchGuard<'_>,
-    ) -> Result<(), DestroyedResourceError> {
+    ) -> Result<(), ClearError> {
         profiling::scope!("initialize_texture_memory");
 
         let mut ranges: Vec<TextureInitRange> = Vec::new();
         for texture_use in self.texture_memory_actions.drain_init_actions() {
             let mut initialization_status = texture_use.texture.initialization_status.write();
             let use_range = texture_use.range;
+
+            // Check that the texture can be cleared before its initialization
+            // status is updated.
+            if matches!(texture_use.kind, MemoryInitKind::NeedsInitializedMemory)
+                && initialization_status
+                    .mips
+                    .iter()
+                    .skip(use_range.mip_range.start as usize)
+                    .take((use_range.mip_range.end - use_range.mip_range.start) as usize)
+                    .any(|mip_tracker| mip_tracker.check(use_range.layer_range.clone()).is_some())
+            {
+                check_clear_queue_type(&texture_use.texture, self.encoder.queue_type)?;
+            }
             let affected_mip_trackers = initialization_status
                 .mips
                 .iter_mut()
@@ -301,6 +321,7 @@ impl BakedCommands {
                     &texture_use.texture,
                     range,
                     self.encoder.raw.as_mut(),
+                    self.encoder.queue_type,
                     &mut device_tracker.textures,
                     &device.alignments,
                     device.zero_buffer.as_ref(),
@@ -312,7 +333,7 @@ impl BakedCommands {
                 // and now, this is out of our control so we have to handle
                 // it gracefully.
                 if let Err(ClearError::DestroyedResource(e)) = clear_result {
-                    return Err(e);
+                    return Err(e.into());
                 }
 
                 // Other errors are unexpected.
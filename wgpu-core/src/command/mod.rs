@@ -25,6 +25,7 @@ pub use self::{
     render::*, render_command::RenderCommand, transfer::*,
 };
 pub(crate) use allocator::CommandAllocator;
+pub(crate) use ray_tracing::validate_acceleration_structure_actions;
 
 pub(crate) use timestamp_writes::ArcPassTimestampWrites;
 pub use timestamp_writes::PassTimestampWrites;
@@ -33,7 +34,7 @@ use self::memory_init::CommandBufferTextureMemoryActions;
 
 use crate::binding_model::BindingError;
 use crate::command::transition_resources::TransitionResourcesError;
-use crate::device::queue::TempResource;
+use crate::device::queue::{Queue, TempResource};
 use crate::device::{Device, DeviceError, MissingFeatures};
 use crate::lock::{rank, Mutex};
 use crate::snatch::SnatchGuard;
@@ -126,7 +127,7 @@ impl CommandEncoderStatus {
     /// returns `Ok(())`.
     ///
     /// [ves]: https://www.w3.org/TR/webgpu/#abstract-opdef-validate-the-encoder-state
-    fn record_with<
+    pub(crate) fn record_with<
         F: FnOnce(&mut CommandBufferMutable) -> Result<(), E>,
         E: Clone + Into<CommandEncoderError>,
     >(
@@ -342,6 +343,10 @@ impl<'a> RecordingGuard<'a> {
         mut self,
         f: F,
     ) {
+        let command_index = self.command_count;
+        self.command_count += 1;
+        self.trackers.set_command_index(command_index);
+
         match f(&mut self) {
             Ok(()) => self.mark_successful(),
             Err(err) => {
@@ -397,6 +402,9 @@ pub(crate) struct CommandEncoder {
 
     pub(crate) label: String,
 
+    /// The type of the queues the command buffer can be submitted to.
+    pub(crate) queue_type: wgt::QueueType,
+
     /// The mutable state of this command encoder.
     pub(crate) data: Mutex<CommandEncoderStatus>,
 }
@@ -461,6 +469,18 @@ pub(crate) struct InnerCommandEncoder {
     pub(crate) is_open: bool,
 
     pub(crate) label: String,
+
+    /// True if `raw` was created for a reusable [`CommandBuffer`].
+    ///
+    /// The command buffers of such encoders may be submitted more than once.
+    /// They are not recycled in the [`CommandAllocator`], which only holds
+    /// encoders for command buffers submitted once.
+    ///
+    /// [`CommandAllocator`]: crate::command::CommandAllocator
+    pub(crate) reusable: bool,
+
+    /// The type of the queues the command buffers of `raw` can be submitted to.
+    pub(crate) queue_type: wgt::QueueType,
 }
 
 impl InnerCommandEncoder {
@@ -610,7 +630,11 @@ impl Drop for InnerCommandEncoder {
         }
         // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
         let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
-        self.device.command_allocator.release_encoder(raw);
+        if !self.reusable {
+            self.device
+                .command_allocator
+                .release_encoder(self.queue_type, raw);
+        }
     }
 }
 
@@ -647,13 +671,17 @@ pub struct CommandBufferMutable {
 
     pub(crate) pending_query_resets: QueryResetMap,
 
-    as_actions: Vec<AsAction>,
+    pub(crate) as_actions: Vec<AsAction>,
     temp_resources: Vec<TempResource>,
 
     indirect_draw_validation_resources: crate::indirect_validation::DrawResources,
 
     debug_scope_depth: u32,
 
+    /// The number of commands recorded so far, used to index the transitions
+    /// reported with [`wgt::InstanceFlags::REPORT_TRANSITIONS`].
+    command_count: u32,
+
     #[cfg(feature = "trace")]
     pub(crate) commands: Option<Vec<TraceCommand>>,
 }
@@ -680,18 +708,99 @@ impl CommandBufferMutable {
     }
 }
 
+/// The commands of a reusable [`CommandBuffer`], shared by all of its
+/// submissions.
+///
+/// Unlike [`BakedCommands`], these are never consumed. Each submission
+/// encodes the resource transitions and memory initialization it needs with
+/// an encoder of its own (see [`ReusableCommands::bake`]), and holds onto
+/// these until it is done executing.
+pub(crate) struct ReusableCommands {
+    pub(crate) encoder: InnerCommandEncoder,
+    pub(crate) trackers: Tracker,
+    buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
+    texture_memory_actions: CommandBufferTextureMemoryActions,
+    pub(crate) as_actions: Vec<AsAction>,
+    /// We only need to keep these resources alive.
+    _temp_resources: Vec<TempResource>,
+    _indirect_draw_validation_resources: crate::indirect_validation::DrawResources,
+    #[cfg(feature = "trace")]
+    pub(crate) commands: Option<Vec<TraceCommand>>,
+}
+
+impl ReusableCommands {
+    fn new(inner: CommandBufferMutable) -> Self {
+        Self {
+            encoder: inner.encoder,
+            trackers: inner.trackers,
+            buffer_memory_init_actions: inner.buffer_memory_init_actions,
+            texture_memory_actions: inner.texture_memory_actions,
+            as_actions: inner.as_actions,
+            _temp_resources: inner.temp_resources,
+            _indirect_draw_validation_resources: inner.indirect_draw_validation_resources,
+            #[cfg(feature = "trace")]
+            commands: inner.commands,
+        }
+    }
+
+    /// Prepare a submission of these commands.
+    ///
+    /// The returned [`BakedCommands`] have a new encoder from the device's
+    /// [`CommandAllocator`], with no commands and no tracked resources, but
+    /// with the memory initialization these commands need.
+    pub(crate) fn bake(&self, queue: &Queue) -> Result<BakedCommands, DeviceError> {
+        let device = &self.encoder.device;
+        let raw = device
+            .command_allocator
+            .acquire_encoder(device.raw(), queue.raw(), queue.queue_type())
+            .map_err(|e| device.handle_hal_error(e))?;
+
+        Ok(BakedCommands {
+            encoder: InnerCommandEncoder {
+                raw: ManuallyDrop::new(raw),
+                list: Vec::new(),
+                device: device.clone(),
+                is_open: false,
+                label: self.encoder.label.clone(),
+                reusable: false,
+                queue_type: queue.queue_type(),
+            },
+            trackers: Tracker::new(),
+            temp_resources: Vec::new(),
+            indirect_draw_validation_resources: crate::indirect_validation::DrawResources::new(
+                device.clone(),
+            ),
+            buffer_memory_init_actions: self.buffer_memory_init_actions.clone(),
+            texture_memory_actions: self.texture_memory_actions.clone(),
+        })
+    }
+}
+
 /// A buffer of commands to be submitted to the GPU for execution.
 ///
 /// Once a command buffer is submitted to the queue, its contents are taken
 /// to construct a [`BakedCommands`], whose contents eventually become the
 /// property of the submission queue.
+///
+/// Reusable command buffers are the exception: their contents are moved to
+/// [`ReusableCommands`] when they are finished, and shared by each of their
+/// submissions.
 pub struct CommandBuffer {
     pub(crate) device: Arc<Device>,
     /// The `label` from the descriptor used to create the resource.
     label: String,
 
     /// The mutable state of this command buffer.
+    ///
+    /// This is [`CommandEncoderStatus::Consumed`] if `reusable` is set.
     pub(crate) data: Mutex<CommandEncoderStatus>,
+
+    /// The commands of a command buffer that can be submitted more than once.
+    pub(crate) reusable: Option<Arc<ReusableCommands>>,
+
+    /// The transitions inserted while the commands were recorded, if
+    /// [`wgt::InstanceFlags::REPORT_TRANSITIONS`] is set.
+    pub(crate) transition_report: Vec<wgt::TransitionReport>,
 }
 
 impl Drop for CommandBuffer {
@@ -705,10 +814,21 @@ impl CommandEncoder {
         encoder: Box<dyn hal::DynCommandEncoder>,
         device: &Arc<Device>,
         label: &Label,
+        reusable: bool,
+        queue_type: wgt::QueueType,
     ) -> Self {
+        let mut trackers = Tracker::new();
+        if device
+            .instance_flags
+            .contains(wgt::InstanceFlags::REPORT_TRANSITIONS)
+        {
+            trackers.enable_transition_log();
+        }
+
         CommandEncoder {
             device: device.clone(),
             label: label.to_string(),
+            queue_type,
             data: Mutex::new(
                 rank::COMMAND_BUFFER_DATA,
                 CommandEncoderStatus::Recording(CommandBufferMutable {
@@ -718,8 +838,10 @@ impl CommandEncoder {
                         device: device.clone(),
                         is_open: false,
                         label: label.to_string(),
+                        reusable,
+                        queue_type,
                     },
-                    trackers: Tracker::new(),
+                    trackers,
                     buffer_memory_init_actions: Default::default(),
                     texture_memory_actions: Default::default(),
                     pending_query_resets: QueryResetMap::new(),
@@ -728,6 +850,7 @@ impl CommandEncoder {
                     indirect_draw_validation_resources:
                         crate::indirect_validation::DrawResources::new(device.clone()),
                     debug_scope_depth: 0,
+                    command_count: 0,
                     #[cfg(feature = "trace")]
                     commands: if device.trace.lock().is_some() {
                         Some(Vec::new())
@@ -747,10 +870,49 @@ impl CommandEncoder {
         CommandEncoder {
             device: device.clone(),
             label: label.to_string(),
+            queue_type: wgt::QueueType::Graphics,
             data: Mutex::new(rank::COMMAND_BUFFER_DATA, CommandEncoderStatus::Error(err)),
         }
     }
 
+    /// Finishes recording, and returns the command buffer along with the error
+    /// that should be reported for it, if any.
+    pub(crate) fn finish(&self, label: &Label) -> (CommandBuffer, Option<CommandEncoderError>) {
+        let mut data = self.data.lock().finish();
+
+        // Errors related to destroyed resources are not reported until the
+        // command buffer is submitted.
+        let error = match data {
+            CommandEncoderStatus::Error(ref e) if !e.is_destroyed_error() => Some(e.clone()),
+            _ => None,
+        };
+
+        let transition_report = match data {
+            CommandEncoderStatus::Finished(ref mut inner) => {
+                inner.trackers.take_transition_report()
+            }
+            _ => Vec::new(),
+        };
+
+        let (data, reusable) = match data {
+            CommandEncoderStatus::Finished(inner) if inner.encoder.reusable => (
+                CommandEncoderStatus::Consumed,
+                Some(Arc::new(ReusableCommands::new(inner))),
+            ),
+            data => (data, None),
+        };
+
+        let cmd_buf = CommandBuffer {
+            device: self.device.clone(),
+            label: label.to_string(),
+            data: Mutex::new(rank::COMMAND_BUFFER_DATA, data),
+            reusable,
+            transition_report,
+        };
+
+        (cmd_buf, error)
+    }
+
     pub(crate) fn insert_barriers_from_tracker(
         raw: &mut dyn hal::DynCommandEncoder,
         base: &mut Tracker,
@@ -1168,27 +1330,28 @@ impl Global {
         let hub = &self.hub;
 
         let cmd_enc = hub.command_encoders.get(encoder_id);
-
-        let data = cmd_enc.data.lock().finish();
-
-        // Errors related to destroyed resources are not reported until the
-        // command buffer is submitted.
-        let error = match data {
-            CommandEncoderStatus::Error(ref e) if !e.is_destroyed_error() => Some(e.clone()),
-            _ => None,
-        };
-
-        let cmd_buf = CommandBuffer {
-            device: cmd_enc.device.clone(),
-            label: desc.label.to_string(),
-            data: Mutex::new(rank::COMMAND_BUFFER_DATA, data),
-        };
+        let (cmd_buf, error) = cmd_enc.finish(&desc.label);
 
         let cmd_buf_id = hub.command_buffers.prepare(id_in).assign(Arc::new(cmd_buf));
 
         (cmd_buf_id, error)
     }
 
+    /// Returns the transitions inserted into the given command buffer while its
+    /// commands were recorded.
+    ///
+    /// This is only populated if [`wgt::InstanceFlags::REPORT_TRANSITIONS`] is
+    /// set. The transitions inserted when the command buffer is submitted, to
+    /// bring its resources from their state at the time of submission, are not
+    /// included.
+    pub fn command_buffer_transition_report(
+        &self,
+        command_buffer_id: id::CommandBufferId,
+    ) -> Vec<wgt::TransitionReport> {
+        let cmd_buf = self.hub.command_buffers.get(command_buffer_id);
+        cmd_buf.transition_report.clone()
+    }
+
     pub fn command_encoder_push_debug_group(
         &self,
         encoder_id: id::CommandEncoderId,
@@ -1474,6 +1637,7 @@ pub enum DrawKind {
     DrawIndirect,
     MultiDrawIndirect,
     MultiDrawIndirectCount,
+    ExecuteIndirect,
 }
 
 /// The type of draw command(indexed or not, or mesh shader)
@@ -210,6 +210,11 @@ impl Global {
             let device = &cmd_enc.device;
             device.check_is_valid()?;
             device.require_features(Features::EXPERIMENTAL_RAY_QUERY)?;
+            if cmd_enc.queue_type == wgt::QueueType::Transfer {
+                return Err(BuildAccelerationStructureError::UnsupportedQueueType(
+                    cmd_enc.queue_type,
+                ));
+            }
 
             let mut buf_storage = Vec::new();
             iter_blas(
@@ -500,80 +505,78 @@ impl Global {
     }
 }
 
-impl CommandBufferMutable {
-    pub(crate) fn validate_acceleration_structure_actions(
-        &self,
-        snatch_guard: &SnatchGuard,
-        command_index_guard: &mut RwLockWriteGuard<CommandIndices>,
-    ) -> Result<(), ValidateAsActionsError> {
-        profiling::scope!("CommandEncoder::[submission]::validate_as_actions");
-        for action in &self.as_actions {
-            match action {
-                AsAction::Build(build) => {
-                    let build_command_index = NonZeroU64::new(
-                        command_index_guard.next_acceleration_structure_build_command_index,
-                    )
-                    .unwrap();
-
-                    command_index_guard.next_acceleration_structure_build_command_index += 1;
-                    for blas in build.blas_s_built.iter() {
-                        let mut state_lock = blas.compacted_state.lock();
-                        *state_lock = match *state_lock {
-                            BlasCompactState::Compacted => {
-                                unreachable!("Should be validated out in build.")
-                            }
-                            // Reset the compacted state to idle. This means any prepares, before mapping their
-                            // internal buffer, will terminate.
-                            _ => BlasCompactState::Idle,
-                        };
-                        *blas.built_index.write() = Some(build_command_index);
-                    }
-
-                    for tlas_build in build.tlas_s_built.iter() {
-                        for blas in &tlas_build.dependencies {
-                            if blas.built_index.read().is_none() {
-                                return Err(ValidateAsActionsError::UsedUnbuiltBlas(
-                                    blas.error_ident(),
-                                    tlas_build.tlas.error_ident(),
-                                ));
-                            }
+pub(crate) fn validate_acceleration_structure_actions(
+    as_actions: &[AsAction],
+    snatch_guard: &SnatchGuard,
+    command_index_guard: &mut RwLockWriteGuard<CommandIndices>,
+) -> Result<(), ValidateAsActionsError> {
+    profiling::scope!("CommandEncoder::[submission]::validate_as_actions");
+    for action in as_actions {
+        match action {
+            AsAction::Build(build) => {
+                let build_command_index = NonZeroU64::new(
+                    command_index_guard.next_acceleration_structure_build_command_index,
+                )
+                .unwrap();
+
+                command_index_guard.next_acceleration_structure_build_command_index += 1;
+                for blas in build.blas_s_built.iter() {
+                    let mut state_lock = blas.compacted_state.lock();
+                    *state_lock = match *state_lock {
+                        BlasCompactState::Compacted => {
+                            unreachable!("Should be validated out in build.")
                         }
-                        *tlas_build.tlas.built_index.write() = Some(build_command_index);
-                        tlas_build
-                            .tlas
-                            .dependencies
-                            .write()
-                            .clone_from(&tlas_build.dependencies)
-                    }
+                        // Reset the compacted state to idle. This means any prepares, before mapping their
+                        // internal buffer, will terminate.
+                        _ => BlasCompactState::Idle,
+                    };
+                    *blas.built_index.write() = Some(build_command_index);
                 }
-                AsAction::UseTlas(tlas) => {
-                    let tlas_build_index = tlas.built_index.read();
-                    let dependencies = tlas.dependencies.read();
 
-                    if (*tlas_build_index).is_none() {
-                        return Err(ValidateAsActionsError::UsedUnbuiltTlas(tlas.error_ident()));
-                    }
-                    for blas in dependencies.deref() {
-                        let blas_build_index = *blas.built_index.read();
-                        if blas_build_index.is_none() {
+                for tlas_build in build.tlas_s_built.iter() {
+                    for blas in &tlas_build.dependencies {
+                        if blas.built_index.read().is_none() {
                             return Err(ValidateAsActionsError::UsedUnbuiltBlas(
-                                tlas.error_ident(),
-                                blas.error_ident(),
-                            ));
-                        }
-                        if blas_build_index.unwrap() > tlas_build_index.unwrap() {
-                            return Err(ValidateAsActionsError::BlasNewerThenTlas(
                                 blas.error_ident(),
-                                tlas.error_ident(),
+                                tlas_build.tlas.error_ident(),
                             ));
                         }
-                        blas.try_raw(snatch_guard)?;
                     }
+                    *tlas_build.tlas.built_index.write() = Some(build_command_index);
+                    tlas_build
+                        .tlas
+                        .dependencies
+                        .write()
+                        .clone_from(&tlas_build.dependencies)
+                }
+            }
+            AsAction::UseTlas(tlas) => {
+                let tlas_build_index = tlas.built_index.read();
+                let dependencies = tlas.dependencies.read();
+
+                if (*tlas_build_index).is_none() {
+                    return Err(ValidateAsActionsError::UsedUnbuiltTlas(tlas.error_ident()));
+                }
+                for blas in dependencies.deref() {
+                    let blas_build_index = *blas.built_index.read();
+                    if blas_build_index.is_none() {
+                        return Err(ValidateAsActionsError::UsedUnbuiltBlas(
+                            tlas.error_ident(),
+                            blas.error_ident(),
+                        ));
+                    }
+                    if blas_build_index.unwrap() > tlas_build_index.unwrap() {
+                        return Err(ValidateAsActionsError::BlasNewerThenTlas(
+                            blas.error_ident(),
+                            tlas.error_ident(),
+                        ));
+                    }
+                    blas.try_raw(snatch_guard)?;
                 }
             }
         }
-        Ok(())
     }
+    Ok(())
 }
 
 ///iterates over the blas iterator, and it's geometry, pushing the buffers into a storage vector (and also some validation).
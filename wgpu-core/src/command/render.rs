@@ -514,6 +514,19 @@ impl<'scope, 'snatch_guard, 'cmd_enc, 'raw_encoder>
     State<'scope, 'snatch_guard, 'cmd_enc, 'raw_encoder>
 {
     fn is_ready(&self, family: DrawCommandFamily) -> Result<(), DrawError> {
+        self.is_ready_with_switched(family, 0, false)
+    }
+
+    /// Like [`Self::is_ready`], but the vertex buffer slots in the
+    /// `switched_vertex_buffers` mask, and the index buffer if
+    /// `switches_index_buffer` is set, are bound by the draw itself and
+    /// don't need to be bound beforehand.
+    fn is_ready_with_switched(
+        &self,
+        family: DrawCommandFamily,
+        switched_vertex_buffers: u32,
+        switches_index_buffer: bool,
+    ) -> Result<(), DrawError> {
         if let Some(pipeline) = self.pipeline.as_ref() {
             self.general.binder.check_compatibility(pipeline.as_ref())?;
             self.general.binder.check_late_buffer_bindings()?;
@@ -527,7 +540,8 @@ impl<'scope, 'snatch_guard, 'cmd_enc, 'raw_encoder>
                 .vertex
                 .buffer_sizes
                 .iter()
-                .take_while(|v| v.is_some())
+                .enumerate()
+                .take_while(|&(slot, v)| v.is_some() || switched_vertex_buffers & (1 << slot) != 0)
                 .count() as u32;
             // Compare with the needed quantity
             if vertex_buffer_count < pipeline.vertex_steps.len() as u32 {
@@ -537,7 +551,7 @@ impl<'scope, 'snatch_guard, 'cmd_enc, 'raw_encoder>
                 });
             }
 
-            if family == DrawCommandFamily::DrawIndexed {
+            if family == DrawCommandFamily::DrawIndexed && !switches_index_buffer {
                 // Pipeline expects an index buffer
                 if let Some(pipeline_index_format) = pipeline.strip_index_format {
                     // We have a buffer bound
@@ -568,8 +582,15 @@ impl<'scope, 'snatch_guard, 'cmd_enc, 'raw_encoder>
     }
 
     /// Reset the `RenderBundle`-related states.
-    fn reset_bundle(&mut self) {
-        self.general.binder.reset();
+    ///
+    /// The bind groups `bundle` inherited from the pass stay set.
+    fn reset_bundle(&mut self, bundle: &super::RenderBundle) {
+        self.general.binder.reset_except(|index| {
+            matches!(
+                bundle.inherited_bind_group_layouts.get(index),
+                Some(Some(_))
+            )
+        });
         self.pipeline = None;
         self.index.reset();
         self.vertex = Default::default();
@@ -714,6 +735,10 @@ pub enum RenderPassErrorInner {
         src: wgt::TextureFormat,
         dst: wgt::TextureFormat,
     },
+    #[error("The {location} belongs to a transient texture and must be cleared with `LoadOp::Clear` and discarded with `StoreOp::Discard`")]
+    InvalidTransientAttachmentOps { location: AttachmentErrorLocation },
+    #[error("The {location} belongs to a transient texture, which can't be a resolve target")]
+    TransientResolveTarget { location: AttachmentErrorLocation },
     #[error("Unable to clear non-present/read-only depth")]
     InvalidDepthOps,
     #[error("Unable to clear non-present/read-only stencil")]
@@ -739,6 +764,16 @@ pub enum RenderPassErrorInner {
         end_count_offset: u64,
         count_buffer_size: u64,
     },
+    #[error("Indirect command layout switches the index buffer but its draws aren't indexed")]
+    IndirectCommandIndexBufferWithoutIndexedDraws,
+    #[error("Indirect command layout switches vertex buffer slot {slot}, which is not below the `max_vertex_buffers` limit of {max}")]
+    IndirectCommandVertexBufferOutOfRange { slot: u32, max: u32 },
+    #[error("Indirect commands use {size} bytes of storage, which exceeds the `max_storage_buffer_binding_size` limit of {max}")]
+    IndirectCommandsTooLarge { size: u64, max: u64 },
+    #[error("Indirect commands have a `max_count` of {count}, which exceeds the maximum of {max}")]
+    IndirectCommandCountTooLarge { count: u32, max: u32 },
+    #[error("The backend can't address the data buffer of indirect commands")]
+    IndirectCommandsUnaddressable,
     #[error(transparent)]
     ResourceUsageCompatibility(#[from] ResourceUsageCompatibilityError),
     #[error("Render bundle has incompatible targets, {0}")]
@@ -755,6 +790,20 @@ pub enum RenderPassErrorInner {
         bundle_depth: bool,
         bundle_stencil: bool,
     },
+    #[error(
+        "{bundle} inherits the bind group at index {index}, but none is set in the render pass"
+    )]
+    MissingInheritedBindGroup {
+        index: u32,
+        bundle: ResourceErrorIdent,
+    },
+    #[error("{bundle} inherits {expected_bgl} at index {index}, which is not the layout of the {assigned_bg} set in the render pass")]
+    IncompatibleInheritedBindGroup {
+        index: u32,
+        bundle: ResourceErrorIdent,
+        expected_bgl: ResourceErrorIdent,
+        assigned_bg: ResourceErrorIdent,
+    },
     #[error(transparent)]
     RenderCommand(#[from] RenderCommandError),
     #[error(transparent)]
@@ -785,6 +834,8 @@ pub enum RenderPassErrorInner {
     InvalidResource(#[from] InvalidResourceError),
     #[error(transparent)]
     TimestampWrites(#[from] TimestampWritesError),
+    #[error("Render passes can not be encoded for {0:?} queues")]
+    UnsupportedQueueType(wgt::QueueType),
 }
 
 impl From<MissingBufferUsageError> for RenderPassErrorInner {
@@ -864,20 +915,30 @@ impl WebGpuError for RenderPassError {
             | RenderPassErrorInner::AttachmentSampleCountMismatch { .. }
             | RenderPassErrorInner::InvalidResolveSampleCounts { .. }
             | RenderPassErrorInner::MismatchedResolveTextureFormat { .. }
+            | RenderPassErrorInner::InvalidTransientAttachmentOps { .. }
+            | RenderPassErrorInner::TransientResolveTarget { .. }
             | RenderPassErrorInner::InvalidDepthOps
             | RenderPassErrorInner::InvalidStencilOps
             | RenderPassErrorInner::UnalignedIndirectBufferOffset(..)
             | RenderPassErrorInner::IndirectBufferOverrun { .. }
             | RenderPassErrorInner::IndirectCountBufferOverrun { .. }
+            | RenderPassErrorInner::IndirectCommandIndexBufferWithoutIndexedDraws
+            | RenderPassErrorInner::IndirectCommandVertexBufferOutOfRange { .. }
+            | RenderPassErrorInner::IndirectCommandsTooLarge { .. }
+            | RenderPassErrorInner::IndirectCommandCountTooLarge { .. }
             | RenderPassErrorInner::ResourceUsageCompatibility(..)
             | RenderPassErrorInner::IncompatibleBundleReadOnlyDepthStencil { .. }
+            | RenderPassErrorInner::MissingInheritedBindGroup { .. }
+            | RenderPassErrorInner::IncompatibleInheritedBindGroup { .. }
             | RenderPassErrorInner::PushConstantOffsetAlignment
             | RenderPassErrorInner::PushConstantSizeAlignment
             | RenderPassErrorInner::PushConstantOutOfMemory
             | RenderPassErrorInner::MultiViewMismatch
             | RenderPassErrorInner::MultiViewDimensionMismatch
             | RenderPassErrorInner::MissingOcclusionQuerySet
+            | RenderPassErrorInner::UnsupportedQueueType(_)
             | RenderPassErrorInner::PassEnded => return ErrorType::Validation,
+            RenderPassErrorInner::IndirectCommandsUnaddressable => return ErrorType::Internal,
         };
         e.webgpu_error_type()
     }
@@ -915,6 +976,21 @@ struct RenderPassInfo {
 }
 
 impl RenderPassInfo {
+    /// Transient textures have no backing memory to load from or store to.
+    fn check_transient_ops<V>(
+        load_op: LoadOp<V>,
+        store_op: StoreOp,
+        view: &TextureView,
+        location: AttachmentErrorLocation,
+    ) -> Result<(), RenderPassErrorInner> {
+        if view.parent.desc.usage.contains(TextureUsages::TRANSIENT)
+            && (matches!(load_op, LoadOp::Load) || store_op != StoreOp::Discard)
+        {
+            return Err(RenderPassErrorInner::InvalidTransientAttachmentOps { location });
+        }
+        Ok(())
+    }
+
     fn add_pass_texture_init_actions<V>(
         load_op: LoadOp<V>,
         store_op: StoreOp,
@@ -1058,6 +1134,23 @@ impl RenderPassInfo {
 
             let ds_aspects = view.desc.aspects();
 
+            if ds_aspects.contains(hal::FormatAspects::DEPTH) {
+                Self::check_transient_ops(
+                    at.depth.load_op(),
+                    at.depth.store_op(),
+                    view,
+                    AttachmentErrorLocation::Depth,
+                )?;
+            }
+            if ds_aspects.contains(hal::FormatAspects::STENCIL) {
+                Self::check_transient_ops(
+                    at.stencil.load_op(),
+                    at.stencil.store_op(),
+                    view,
+                    AttachmentErrorLocation::Depth,
+                )?;
+            }
+
             if !ds_aspects.contains(hal::FormatAspects::STENCIL)
                 || (at.stencil.load_op().eq_variant(at.depth.load_op())
                     && at.stencil.store_op() == at.depth.store_op())
@@ -1268,6 +1361,16 @@ impl RenderPassInfo {
 
             check_attachment_overlap(&mut attachment_set, color_view, at.depth_slice)?;
 
+            Self::check_transient_ops(
+                at.load_op,
+                at.store_op,
+                color_view,
+                AttachmentErrorLocation::Color {
+                    index,
+                    resolve: false,
+                },
+            )?;
+
             Self::add_pass_texture_init_actions(
                 at.load_op,
                 at.store_op,
@@ -1290,6 +1393,17 @@ impl RenderPassInfo {
                     resolve: true,
                 };
 
+                if resolve_view
+                    .parent
+                    .desc
+                    .usage
+                    .contains(TextureUsages::TRANSIENT)
+                {
+                    return Err(RenderPassErrorInner::TransientResolveTarget {
+                        location: resolve_location,
+                    });
+                }
+
                 let render_extent = resolve_view.render_extent.map_err(|reason| {
                     RenderPassErrorInner::TextureViewIsNotRenderable {
                         location: resolve_location,
@@ -1687,6 +1801,13 @@ impl Global {
                     depth_stencil_attachment: None,
                     occlusion_query_set: None,
                 };
+                if cmd_enc.queue_type != wgt::QueueType::Graphics {
+                    let err = RenderPassErrorInner::UnsupportedQueueType(cmd_enc.queue_type);
+                    return (
+                        RenderPass::new_invalid(cmd_enc, &desc.label, err.map_pass_err(scope)),
+                        None,
+                    );
+                }
                 match fill_arc_desc(hub, desc, &mut arc_desc, &cmd_enc.device) {
                     Ok(()) => (RenderPass::new(cmd_enc, arc_desc), None),
                     Err(err) => (
@@ -1847,6 +1968,7 @@ impl Global {
 
             let mut indirect_draw_validation_batcher =
                 crate::indirect_validation::DrawBatcher::new();
+            let mut indirect_commands_batcher = crate::indirect_validation::CommandsBatcher::new();
 
             let (scope, pending_discard_init_fixups) = {
                 let encoder = &mut cmd_buf_data.encoder;
@@ -2110,6 +2232,8 @@ impl Global {
                             };
                             multi_draw_indirect_count(
                                 &mut state,
+                                indirect_draw_validation_resources,
+                                &mut indirect_draw_validation_batcher,
                                 &cmd_enc,
                                 buffer,
                                 offset,
@@ -2120,6 +2244,38 @@ impl Global {
                             )
                             .map_pass_err(scope)?;
                         }
+                        ArcRenderCommand::ExecuteIndirect {
+                            layout,
+                            data_buffer,
+                            buffer,
+                            offset,
+                            count_buffer,
+                            count_buffer_offset,
+                            max_count,
+                        } => {
+                            let scope = PassErrorScope::Draw {
+                                kind: DrawKind::ExecuteIndirect,
+                                family: if layout.indexed {
+                                    DrawCommandFamily::DrawIndexed
+                                } else {
+                                    DrawCommandFamily::Draw
+                                },
+                            };
+                            execute_indirect(
+                                &mut state,
+                                indirect_draw_validation_resources,
+                                &mut indirect_commands_batcher,
+                                &cmd_enc,
+                                layout,
+                                data_buffer,
+                                buffer,
+                                offset,
+                                count_buffer,
+                                count_buffer_offset,
+                                max_count,
+                            )
+                            .map_pass_err(scope)?;
+                        }
                         ArcRenderCommand::PushDebugGroup { color: _, len } => {
                             pass::push_debug_group(&mut state.general, &base.string_data, len);
                         }
@@ -2282,6 +2438,18 @@ impl Global {
                         )
                         .map_pass_err(pass_scope)?;
                 }
+
+                if let Some(ref indirect_commands) = device.indirect_commands {
+                    indirect_commands
+                        .inject_translation_pass(
+                            device,
+                            &cmd_buf_data.indirect_draw_validation_resources,
+                            &mut cmd_buf_data.temp_resources,
+                            transit,
+                            indirect_commands_batcher,
+                        )
+                        .map_pass_err(pass_scope)?;
+                }
             }
 
             encoder.close_and_swap().map_pass_err(pass_scope)?;
@@ -2312,7 +2480,13 @@ fn set_pipeline(
     state
         .info
         .context
-        .check_compatible(&pipeline.pass_context, pipeline.as_ref())
+        .check_framebuffer_fetches(&pipeline)
+        .and_then(|()| {
+            state
+                .info
+                .context
+                .check_compatible(&pipeline.pass_context, pipeline.as_ref())
+        })
         .map_err(RenderCommandError::IncompatiblePipelineTargets)?;
 
     state.pipeline_flags = pipeline.flags;
@@ -2333,6 +2507,9 @@ fn set_pipeline(
             .general
             .raw_encoder
             .set_render_pipeline(pipeline.raw());
+        if let Some(ref bounds_reporter) = state.general.device.bounds_reporter {
+            bounds_reporter.bind(state.general.raw_encoder, pipeline.layout.raw());
+        }
     }
 
     if pipeline.flags.contains(PipelineFlags::STENCIL_REFERENCE) {
@@ -2867,6 +3044,8 @@ fn multi_draw_indirect(
 
 fn multi_draw_indirect_count(
     state: &mut State,
+    indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
+    indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
     cmd_enc: &Arc<CommandEncoder>,
     indirect_buffer: Arc<crate::resource::Buffer>,
     offset: u64,
@@ -2897,23 +3076,11 @@ fn multi_draw_indirect_count(
     indirect_buffer.same_device_as(cmd_enc.as_ref())?;
     count_buffer.same_device_as(cmd_enc.as_ref())?;
 
-    state
-        .general
-        .scope
-        .buffers
-        .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
-
     indirect_buffer.check_usage(BufferUsages::INDIRECT)?;
-    let indirect_raw = indirect_buffer.try_raw(state.general.snatch_guard)?;
-
-    state
-        .general
-        .scope
-        .buffers
-        .merge_single(&count_buffer, wgt::BufferUses::INDIRECT)?;
+    indirect_buffer.check_destroyed(state.general.snatch_guard)?;
 
     count_buffer.check_usage(BufferUsages::INDIRECT)?;
-    let count_raw = count_buffer.try_raw(state.general.snatch_guard)?;
+    count_buffer.check_destroyed(state.general.snatch_guard)?;
 
     if offset % 4 != 0 {
         return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(offset));
@@ -2953,35 +3120,454 @@ fn multi_draw_indirect_count(
         ),
     );
 
-    match family {
-        DrawCommandFamily::Draw => unsafe {
-            state.general.raw_encoder.draw_indirect_count(
-                indirect_raw,
-                offset,
-                count_raw,
-                count_buffer_offset,
-                max_count,
-            );
-        },
-        DrawCommandFamily::DrawIndexed => unsafe {
-            state.general.raw_encoder.draw_indexed_indirect_count(
-                indirect_raw,
-                offset,
-                count_raw,
+    fn draw(
+        raw_encoder: &mut dyn hal::DynCommandEncoder,
+        family: DrawCommandFamily,
+        indirect_buffer: &dyn hal::DynBuffer,
+        offset: u64,
+        count_buffer: &dyn hal::DynBuffer,
+        count_buffer_offset: u64,
+        max_count: u32,
+    ) {
+        match family {
+            DrawCommandFamily::Draw => unsafe {
+                raw_encoder.draw_indirect_count(
+                    indirect_buffer,
+                    offset,
+                    count_buffer,
+                    count_buffer_offset,
+                    max_count,
+                );
+            },
+            DrawCommandFamily::DrawIndexed => unsafe {
+                raw_encoder.draw_indexed_indirect_count(
+                    indirect_buffer,
+                    offset,
+                    count_buffer,
+                    count_buffer_offset,
+                    max_count,
+                );
+            },
+            DrawCommandFamily::DrawMeshTasks => unsafe {
+                raw_encoder.draw_mesh_tasks_indirect_count(
+                    indirect_buffer,
+                    offset,
+                    count_buffer,
+                    count_buffer_offset,
+                    max_count,
+                );
+            },
+        }
+    }
+
+    // The validation shader only knows about the arguments of draws.
+    if state.general.device.indirect_validation.is_some()
+        && family != DrawCommandFamily::DrawMeshTasks
+    {
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&indirect_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&count_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
+
+        if max_count == 0 {
+            return Ok(());
+        }
+
+        /// A part of the multi-draw whose validated arguments are contiguous.
+        struct DrawData {
+            buffer_index: usize,
+            offset: u64,
+            first_draw: u32,
+            count: u32,
+        }
+
+        let vertex_or_index_limit = if family == DrawCommandFamily::DrawIndexed {
+            state.index.limit
+        } else {
+            state.vertex.limits.vertex_limit
+        };
+        let instance_limit = state.vertex.limits.instance_limit;
+        let device = state.general.device;
+
+        // Validate all the arguments first, so that the parts stay contiguous.
+        let mut parts = Vec::new();
+        for i in 0..max_count {
+            let (buffer_index, dst_offset) = indirect_draw_validation_batcher.add(
+                indirect_draw_validation_resources,
+                device,
+                &indirect_buffer,
+                offset + stride * i as u64,
+                family,
+                vertex_or_index_limit,
+                instance_limit,
+            )?;
+            match parts.last_mut() {
+                Some(DrawData {
+                    buffer_index: current_buffer_index,
+                    count,
+                    ..
+                }) if *current_buffer_index == buffer_index => *count += 1,
+                _ => parts.push(DrawData {
+                    buffer_index,
+                    offset: dst_offset,
+                    first_draw: i,
+                    count: 1,
+                }),
+            }
+        }
+
+        for part in parts {
+            let (count_buffer_index, count_offset) = indirect_draw_validation_batcher.add_count(
+                indirect_draw_validation_resources,
+                device,
+                &count_buffer,
                 count_buffer_offset,
-                max_count,
+                part.first_draw,
+                part.count,
+            )?;
+            draw(
+                state.general.raw_encoder,
+                family,
+                indirect_draw_validation_resources.get_dst_buffer(part.buffer_index),
+                part.offset,
+                indirect_draw_validation_resources.get_dst_buffer(count_buffer_index),
+                count_offset,
+                part.count,
             );
+        }
+    } else {
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&count_buffer, wgt::BufferUses::INDIRECT)?;
+
+        draw(
+            state.general.raw_encoder,
+            family,
+            indirect_buffer.try_raw(state.general.snatch_guard)?,
+            offset,
+            count_buffer.try_raw(state.general.snatch_guard)?,
+            count_buffer_offset,
+            max_count,
+        );
+    }
+
+    Ok(())
+}
+
+fn execute_indirect(
+    state: &mut State,
+    indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
+    indirect_commands_batcher: &mut crate::indirect_validation::CommandsBatcher,
+    cmd_enc: &Arc<CommandEncoder>,
+    layout: wgt::IndirectCommandLayout,
+    data_buffer: Arc<crate::resource::Buffer>,
+    indirect_buffer: Arc<crate::resource::Buffer>,
+    offset: u64,
+    count_buffer: Option<Arc<crate::resource::Buffer>>,
+    count_buffer_offset: u64,
+    max_count: u32,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!(
+        "RenderPass::execute_indirect {layout:?} {} {} {offset} {:?} {count_buffer_offset:?} {max_count:?}",
+        data_buffer.error_ident(),
+        indirect_buffer.error_ident(),
+        count_buffer.as_ref().map(|buffer| buffer.error_ident())
+    );
+
+    let device = state.general.device;
+    device.require_features(wgt::Features::INDIRECT_COMMANDS)?;
+    if layout.push_constant_size != 0 {
+        device.require_features(wgt::Features::PUSH_CONSTANTS)?;
+    }
+
+    if layout.index_buffer && !layout.indexed {
+        return Err(RenderPassErrorInner::IndirectCommandIndexBufferWithoutIndexedDraws);
+    }
+    let max_vertex_buffers = device.limits.max_vertex_buffers;
+    if let Some(slot) =
+        (max_vertex_buffers..u32::BITS).find(|&slot| layout.vertex_buffers & (1 << slot) != 0)
+    {
+        return Err(
+            RenderPassErrorInner::IndirectCommandVertexBufferOutOfRange {
+                slot,
+                max: max_vertex_buffers,
+            },
+        );
+    }
+    if layout.push_constant_offset % wgt::PUSH_CONSTANT_ALIGNMENT != 0 {
+        return Err(RenderPassErrorInner::PushConstantOffsetAlignment);
+    }
+    if layout.push_constant_size % wgt::PUSH_CONSTANT_ALIGNMENT != 0 {
+        return Err(RenderPassErrorInner::PushConstantSizeAlignment);
+    }
+    // Each record is translated by one invocation of a single dispatch.
+    const MAX_COUNT: u32 = 65535 * 64;
+    if max_count > MAX_COUNT {
+        return Err(RenderPassErrorInner::IndirectCommandCountTooLarge {
+            count: max_count,
+            max: MAX_COUNT,
+        });
+    }
+
+    state.is_ready_with_switched(
+        if layout.indexed {
+            DrawCommandFamily::DrawIndexed
+        } else {
+            DrawCommandFamily::Draw
         },
-        DrawCommandFamily::DrawMeshTasks => unsafe {
-            state.general.raw_encoder.draw_mesh_tasks_indirect_count(
-                indirect_raw,
-                offset,
-                count_raw,
-                count_buffer_offset,
-                max_count,
-            );
+        layout.vertex_buffers,
+        layout.index_buffer,
+    )?;
+    let pipeline = state.pipeline.as_ref().unwrap();
+
+    if layout.push_constant_size != 0 {
+        pipeline
+            .layout
+            .validate_push_constant_ranges(
+                layout.push_constant_stages,
+                layout.push_constant_offset,
+                layout.push_constant_offset + layout.push_constant_size,
+            )
+            .map_err(RenderCommandError::from)?;
+    }
+
+    data_buffer.same_device_as(cmd_enc.as_ref())?;
+    indirect_buffer.same_device_as(cmd_enc.as_ref())?;
+    if let Some(ref count_buffer) = count_buffer {
+        count_buffer.same_device_as(cmd_enc.as_ref())?;
+    }
+
+    let mut data_uses = wgt::BufferUses::empty();
+    if layout.vertex_buffers != 0 {
+        data_buffer.check_usage(BufferUsages::VERTEX)?;
+        data_uses |= wgt::BufferUses::VERTEX;
+    }
+    if layout.index_buffer {
+        data_buffer.check_usage(BufferUsages::INDEX)?;
+        data_uses |= wgt::BufferUses::INDEX;
+    }
+    data_buffer.check_destroyed(state.general.snatch_guard)?;
+
+    indirect_buffer.check_usage(BufferUsages::INDIRECT)?;
+    indirect_buffer.check_destroyed(state.general.snatch_guard)?;
+
+    if let Some(ref count_buffer) = count_buffer {
+        count_buffer.check_usage(BufferUsages::INDIRECT)?;
+        count_buffer.check_destroyed(state.general.snatch_guard)?;
+    }
+
+    if offset % 4 != 0 {
+        return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(offset));
+    }
+
+    let end_offset = offset + layout.stride() * max_count as u64;
+    if end_offset > indirect_buffer.size {
+        return Err(RenderPassErrorInner::IndirectBufferOverrun {
+            count: max_count,
+            offset,
+            end_offset,
+            buffer_size: indirect_buffer.size,
+        });
+    }
+    state.general.buffer_memory_init_actions.extend(
+        indirect_buffer.initialization_status.read().create_action(
+            &indirect_buffer,
+            offset..end_offset,
+            MemoryInitKind::NeedsInitializedMemory,
+        ),
+    );
+
+    if let Some(ref count_buffer) = count_buffer {
+        let begin_count_offset = count_buffer_offset;
+        let end_count_offset = count_buffer_offset + 4;
+        if end_count_offset > count_buffer.size {
+            return Err(RenderPassErrorInner::IndirectCountBufferOverrun {
+                begin_count_offset,
+                end_count_offset,
+                count_buffer_size: count_buffer.size,
+            });
+        }
+        state.general.buffer_memory_init_actions.extend(
+            count_buffer.initialization_status.read().create_action(
+                count_buffer,
+                count_buffer_offset..end_count_offset,
+                MemoryInitKind::NeedsInitializedMemory,
+            ),
+        );
+    }
+
+    // The records may reference any part of the data buffer.
+    if !data_uses.is_empty() {
+        state.general.buffer_memory_init_actions.extend(
+            data_buffer.initialization_status.read().create_action(
+                &data_buffer,
+                0..data_buffer.size,
+                MemoryInitKind::NeedsInitializedMemory,
+            ),
+        );
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&data_buffer, data_uses)?;
+    }
+    state
+        .general
+        .scope
+        .buffers
+        .merge_single(&indirect_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
+    if let Some(ref count_buffer) = count_buffer {
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(count_buffer, wgt::BufferUses::INDIRECT)?;
+    }
+
+    let commands = device.indirect_commands.as_ref().unwrap();
+
+    let src_size = layout.stride() * max_count as u64;
+    let dst_size = commands.native_stride(&layout) * max_count as u64;
+    let (_, src_binding_size) = crate::indirect_validation::Commands::src_binding_range(
+        device,
+        &indirect_buffer,
+        offset,
+        src_size,
+    );
+    let max_binding_size = device.adapter.limits().max_storage_buffer_binding_size as u64;
+    for size in [src_binding_size, dst_size] {
+        if size > max_binding_size {
+            return Err(RenderPassErrorInner::IndirectCommandsTooLarge {
+                size,
+                max: max_binding_size,
+            });
+        }
+    }
+
+    if max_count == 0 {
+        return Ok(());
+    }
+
+    // The records validate the buffers they switch themselves, the limits of
+    // the other buffers are passed along.
+    let vertex_limits = VertexLimits::new(
+        state
+            .vertex
+            .buffer_sizes
+            .iter()
+            .enumerate()
+            .map(|(slot, &size)| {
+                if layout.vertex_buffers & (1 << slot) != 0 {
+                    Some(BufferAddress::MAX)
+                } else {
+                    size
+                }
+            }),
+        &pipeline.vertex_steps,
+    );
+    let index_limit = if layout.index_buffer {
+        u64::MAX
+    } else {
+        state.index.limit
+    };
+    let clamp = |limit: u64| u32::try_from(limit).unwrap_or(u32::MAX);
+
+    let data_raw = data_buffer.try_raw(state.general.snatch_guard)?;
+    let data_address = unsafe { device.raw().get_buffer_device_address(data_raw) }
+        .ok_or(RenderPassErrorInner::IndirectCommandsUnaddressable)?
+        + data_buffer.raw_offset;
+
+    let mut slots = [crate::indirect_validation::VertexSlot::default(); hal::MAX_VERTEX_BUFFERS];
+    for (slot, step) in slots.iter_mut().zip(pipeline.vertex_steps.iter()) {
+        *slot = crate::indirect_validation::VertexSlot {
+            stride: step.stride as u32,
+            last_stride: step.last_stride as u32,
+            mode: match step.mode {
+                VertexStepMode::Vertex => 1,
+                VertexStepMode::Instance => 2,
+            },
+            padding: 0,
+        };
+    }
+
+    let params = crate::indirect_validation::CommandsParams {
+        src_stride: (layout.stride() / 4) as u32,
+        dst_stride: (commands.native_stride(&layout) / 4) as u32,
+        push_constant_count: layout.push_constant_size / 4,
+        vertex_buffers: layout.vertex_buffers,
+        index_buffer: layout.index_buffer as u32,
+        indexed: layout.indexed as u32,
+        data_address_low: data_address as u32,
+        data_address_high: (data_address >> 32) as u32,
+        data_size: clamp(data_buffer.size),
+        strip_index_format: match pipeline.strip_index_format {
+            None => 0,
+            Some(IndexFormat::Uint16) => 1,
+            Some(IndexFormat::Uint32) => 2,
         },
+        vertex_limit: clamp(vertex_limits.vertex_limit),
+        instance_limit: clamp(vertex_limits.instance_limit),
+        index_limit: clamp(index_limit),
+        slots,
+        ..Default::default()
+    };
+
+    let buffer_index = commands.add(
+        device,
+        indirect_draw_validation_resources,
+        indirect_commands_batcher,
+        &indirect_buffer,
+        indirect_buffer.try_raw(state.general.snatch_guard)?,
+        offset,
+        max_count,
+        params,
+    )?;
+
+    let count_buffer = match count_buffer {
+        Some(ref count_buffer) => Some((
+            count_buffer.try_raw(state.general.snatch_guard)?,
+            count_buffer_offset,
+        )),
+        None => None,
+    };
+    unsafe {
+        state.general.raw_encoder.execute_indirect(
+            &layout,
+            pipeline.layout.raw(),
+            indirect_draw_validation_resources.get_commands_buffer(buffer_index),
+            0,
+            max_count,
+            count_buffer,
+        );
+    }
+
+    // The switched buffers stay bound to ranges we don't know the size of.
+    for slot in 0..hal::MAX_VERTEX_BUFFERS {
+        if layout.vertex_buffers & (1 << slot) != 0 {
+            state.vertex.buffer_sizes[slot] = None;
+        }
+    }
+    if layout.vertex_buffers != 0 {
+        let pipeline = state.pipeline.as_ref().unwrap();
+        state.vertex.update_limits(&pipeline.vertex_steps);
     }
+    if layout.index_buffer {
+        state.index.reset();
+    }
+
     Ok(())
 }
 
@@ -2994,7 +3580,7 @@ fn execute_bundle(
 ) -> Result<(), RenderPassErrorInner> {
     api_log!("RenderPass::execute_bundle {}", bundle.error_ident());
 
-    let bundle = state.general.tracker.bundles.insert_single(bundle);
+    let bundle = state.general.tracker.bundles.insert_single(bundle).clone();
 
     bundle.same_device_as(cmd_enc.as_ref())?;
 
@@ -3038,9 +3624,40 @@ fn execute_bundle(
         );
     }
 
+    if bundle
+        .inherited_bind_group_layouts
+        .iter()
+        .any(Option::is_some)
+    {
+        state
+            .general
+            .device
+            .require_features(wgt::Features::RENDER_BUNDLE_INHERITANCE)?;
+    }
+    for (index, layout) in bundle.inherited_bind_group_layouts.iter().enumerate() {
+        let Some(layout) = layout else {
+            continue;
+        };
+        let Some(ref group) = state.general.binder.payload(index).group else {
+            return Err(RenderPassErrorInner::MissingInheritedBindGroup {
+                index: index as u32,
+                bundle: bundle.error_ident(),
+            });
+        };
+        if !group.layout.is_equal(layout) {
+            return Err(RenderPassErrorInner::IncompatibleInheritedBindGroup {
+                index: index as u32,
+                bundle: bundle.error_ident(),
+                expected_bgl: layout.error_ident(),
+                assigned_bg: group.error_ident(),
+            });
+        }
+    }
+
     unsafe {
         bundle.execute(
             state.general.raw_encoder,
+            &state.general.binder,
             indirect_draw_validation_resources,
             indirect_draw_validation_batcher,
             state.general.snatch_guard,
@@ -3059,7 +3676,7 @@ fn execute_bundle(
     unsafe {
         state.general.scope.merge_render_bundle(&bundle.used)?;
     };
-    state.reset_bundle();
+    state.reset_bundle(&bundle);
     Ok(())
 }
 
@@ -3611,6 +4228,84 @@ impl Global {
         Ok(())
     }
 
+    pub fn render_pass_execute_indirect(
+        &self,
+        pass: &mut RenderPass,
+        layout: wgt::IndirectCommandLayout,
+        data_buffer_id: id::BufferId,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        max_count: u32,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::ExecuteIndirect,
+            family: if layout.indexed {
+                DrawCommandFamily::DrawIndexed
+            } else {
+                DrawCommandFamily::Draw
+            },
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::ExecuteIndirect {
+            layout,
+            data_buffer: pass_try!(
+                base,
+                scope,
+                self.resolve_render_pass_buffer_id(data_buffer_id)
+            ),
+            buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+            offset,
+            count_buffer: None,
+            count_buffer_offset: 0,
+            max_count,
+        });
+
+        Ok(())
+    }
+
+    pub fn render_pass_execute_indirect_count(
+        &self,
+        pass: &mut RenderPass,
+        layout: wgt::IndirectCommandLayout,
+        data_buffer_id: id::BufferId,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::ExecuteIndirect,
+            family: if layout.indexed {
+                DrawCommandFamily::DrawIndexed
+            } else {
+                DrawCommandFamily::Draw
+            },
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::ExecuteIndirect {
+            layout,
+            data_buffer: pass_try!(
+                base,
+                scope,
+                self.resolve_render_pass_buffer_id(data_buffer_id)
+            ),
+            buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+            offset,
+            count_buffer: Some(pass_try!(
+                base,
+                scope,
+                self.resolve_render_pass_buffer_id(count_buffer_id)
+            )),
+            count_buffer_offset,
+            max_count,
+        });
+
+        Ok(())
+    }
+
     pub fn render_pass_multi_draw_mesh_tasks_indirect_count(
         &self,
         pass: &mut RenderPass,
@@ -101,6 +101,15 @@ pub enum RenderCommand {
         max_count: u32,
         family: DrawCommandFamily,
     },
+    ExecuteIndirect {
+        layout: wgt::IndirectCommandLayout,
+        data_buffer_id: id::BufferId,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: Option<id::BufferId>,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -384,6 +393,43 @@ impl RenderCommand {
                             }
                         }
 
+                        RenderCommand::ExecuteIndirect {
+                            layout,
+                            data_buffer_id,
+                            buffer_id,
+                            offset,
+                            count_buffer_id,
+                            count_buffer_offset,
+                            max_count,
+                        } => {
+                            let scope = PassErrorScope::Draw {
+                                kind: DrawKind::ExecuteIndirect,
+                                family: if layout.indexed {
+                                    DrawCommandFamily::DrawIndexed
+                                } else {
+                                    DrawCommandFamily::Draw
+                                },
+                            };
+                            let get_buffer = |buffer_id| {
+                                buffers_guard
+                                    .get(buffer_id)
+                                    .get()
+                                    .map_err(|e| RenderPassError {
+                                        scope,
+                                        inner: e.into(),
+                                    })
+                            };
+                            ArcRenderCommand::ExecuteIndirect {
+                                layout,
+                                data_buffer: get_buffer(data_buffer_id)?,
+                                buffer: get_buffer(buffer_id)?,
+                                offset,
+                                count_buffer: count_buffer_id.map(get_buffer).transpose()?,
+                                count_buffer_offset,
+                                max_count,
+                            }
+                        }
+
                         RenderCommand::BeginOcclusionQuery { query_index } => {
                             ArcRenderCommand::BeginOcclusionQuery { query_index }
                         }
@@ -511,6 +557,15 @@ pub enum ArcRenderCommand {
         max_count: u32,
         family: DrawCommandFamily,
     },
+    ExecuteIndirect {
+        layout: wgt::IndirectCommandLayout,
+        data_buffer: Arc<Buffer>,
+        buffer: Arc<Buffer>,
+        offset: BufferAddress,
+        count_buffer: Option<Arc<Buffer>>,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         #[cfg_attr(not(any(feature = "serde", feature = "replay")), allow(dead_code))]
         color: u32,
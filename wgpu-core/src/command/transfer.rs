@@ -22,7 +22,7 @@ use crate::{
         TextureInitTrackerAction,
     },
     resource::{
-        MissingBufferUsageError, MissingTextureUsageError, ParentDevice, RawResourceAccess,
+        Buffer, MissingBufferUsageError, MissingTextureUsageError, ParentDevice, RawResourceAccess,
         Texture, TextureErrorDimension,
     },
     snatch::SnatchGuard,
@@ -575,11 +575,11 @@ pub(crate) fn validate_copy_within_same_texture<T>(
     })
 }
 
-fn handle_texture_init(
+fn handle_texture_init<T>(
     init_kind: MemoryInitKind,
     cmd_buf_data: &mut CommandBufferMutable,
     device: &Device,
-    copy_texture: &TexelCopyTextureInfo,
+    copy_texture: &wgt::TexelCopyTextureInfo<T>,
     copy_size: &Extent3d,
     texture: &Arc<Texture>,
     snatch_guard: &SnatchGuard<'_>,
@@ -601,6 +601,7 @@ fn handle_texture_init(
 
     // In rare cases we may need to insert an init operation immediately onto the command buffer.
     if !immediate_inits.is_empty() {
+        let queue_type = cmd_buf_data.encoder.queue_type;
         let cmd_buf_raw = cmd_buf_data.encoder.open()?;
         for init in immediate_inits {
             clear_texture(
@@ -610,6 +611,7 @@ fn handle_texture_init(
                     layer_range: init.layer..(init.layer + 1),
                 },
                 cmd_buf_raw,
+                queue_type,
                 &mut cmd_buf_data.trackers.textures,
                 &device.alignments,
                 device.zero_buffer.as_ref(),
@@ -626,10 +628,10 @@ fn handle_texture_init(
 ///
 /// Ensure the source texture of a transfer is in the right initialization
 /// state, and record the state for after the transfer operation.
-fn handle_src_texture_init(
+fn handle_src_texture_init<T>(
     cmd_buf_data: &mut CommandBufferMutable,
     device: &Device,
-    source: &TexelCopyTextureInfo,
+    source: &wgt::TexelCopyTextureInfo<T>,
     copy_size: &Extent3d,
     texture: &Arc<Texture>,
     snatch_guard: &SnatchGuard<'_>,
@@ -834,8 +836,8 @@ impl Global {
             );
 
             let region = hal::BufferCopy {
-                src_offset: source_offset,
-                dst_offset: destination_offset,
+                src_offset: src_buffer.raw_offset + source_offset,
+                dst_offset: dst_buffer.raw_offset + destination_offset,
                 size: wgt::BufferSize::new(size).unwrap(),
             };
             let cmd_buf_raw = cmd_buf_data.encoder.open()?;
@@ -992,7 +994,8 @@ impl Global {
                     let mut texture_base = dst_base.clone();
                     texture_base.array_layer += rel_array_layer;
                     let mut buffer_layout = source.layout;
-                    buffer_layout.offset += rel_array_layer as u64 * bytes_per_array_layer;
+                    buffer_layout.offset +=
+                        src_buffer.raw_offset + rel_array_layer as u64 * bytes_per_array_layer;
                     hal::BufferTextureCopy {
                         buffer_layout,
                         texture_base,
@@ -1043,144 +1046,21 @@ impl Global {
                 });
             }
 
-            let src_texture = hub.textures.get(source.texture).get()?;
-            let dst_buffer = hub.buffers.get(destination.buffer).get()?;
-
-            src_texture.same_device_as(cmd_enc.as_ref())?;
-            dst_buffer.same_device_as(cmd_enc.as_ref())?;
-
-            let (hal_copy_size, array_layer_count) = validate_texture_copy_range(
-                source,
-                &src_texture.desc,
-                CopySide::Source,
-                copy_size,
-            )?;
-
-            let (src_range, src_base) = extract_texture_selector(source, copy_size, &src_texture)?;
-
-            let snatch_guard = device.snatchable_lock.read();
-
-            let src_raw = src_texture.try_raw(&snatch_guard)?;
-            src_texture
-                .check_usage(TextureUsages::COPY_SRC)
-                .map_err(TransferError::MissingTextureUsage)?;
-
-            if source.mip_level >= src_texture.desc.mip_level_count {
-                return Err(TransferError::InvalidMipLevel {
-                    requested: source.mip_level,
-                    count: src_texture.desc.mip_level_count,
-                }
-                .into());
-            }
-
-            if !conv::is_valid_copy_src_texture_format(src_texture.desc.format, source.aspect) {
-                return Err(TransferError::CopyFromForbiddenTextureFormat {
-                    format: src_texture.desc.format,
-                    aspect: source.aspect,
-                }
-                .into());
-            }
-
-            validate_texture_buffer_copy(
-                source,
-                src_base.aspect,
-                &src_texture.desc,
-                destination.layout.offset,
-                true, // alignment required for buffer offset
-            )?;
-
-            let (required_buffer_bytes_in_copy, bytes_per_array_layer) =
-                validate_linear_texture_data(
-                    &destination.layout,
-                    src_texture.desc.format,
-                    source.aspect,
-                    dst_buffer.size,
-                    CopySide::Destination,
-                    copy_size,
-                    true,
-                )?;
-
-            if src_texture.desc.format.is_depth_stencil_format() {
-                device
-                    .require_downlevel_flags(wgt::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES)
-                    .map_err(TransferError::from)?;
-            }
-
-            let dst_raw = dst_buffer.try_raw(&snatch_guard)?;
-            dst_buffer
-                .check_usage(BufferUsages::COPY_DST)
-                .map_err(TransferError::MissingBufferUsage)?;
-
-            if copy_size.width == 0 || copy_size.height == 0 || copy_size.depth_or_array_layers == 0
-            {
-                log::trace!("Ignoring copy_texture_to_buffer of size 0");
-                return Ok(());
-            }
-
-            // Handle texture init *before* dealing with barrier transitions so we
-            // have an easier time inserting "immediate-inits" that may be required
-            // by prior discards in rare cases.
-            handle_src_texture_init(
-                cmd_buf_data,
-                device,
-                source,
-                copy_size,
-                &src_texture,
-                &snatch_guard,
-            )?;
-
-            let src_pending = cmd_buf_data.trackers.textures.set_single(
-                &src_texture,
-                src_range,
-                wgt::TextureUses::COPY_SRC,
-            );
-            let src_barrier = src_pending
-                .map(|pending| pending.into_hal(src_raw))
-                .collect::<Vec<_>>();
-
-            let dst_pending = cmd_buf_data
-                .trackers
-                .buffers
-                .set_single(&dst_buffer, wgt::BufferUses::COPY_DST);
-
-            let dst_barrier =
-                dst_pending.map(|pending| pending.into_hal(&dst_buffer, &snatch_guard));
-
-            cmd_buf_data.buffer_memory_init_actions.extend(
-                dst_buffer.initialization_status.read().create_action(
-                    &dst_buffer,
-                    destination.layout.offset
-                        ..(destination.layout.offset + required_buffer_bytes_in_copy),
-                    MemoryInitKind::ImplicitlyInitialized,
-                ),
-            );
+            let source = wgt::TexelCopyTextureInfo {
+                texture: hub.textures.get(source.texture).get()?,
+                mip_level: source.mip_level,
+                origin: source.origin,
+                aspect: source.aspect,
+            };
+            let destination = wgt::TexelCopyBufferInfo {
+                buffer: hub.buffers.get(destination.buffer).get()?,
+                layout: destination.layout,
+            };
 
-            let regions = (0..array_layer_count)
-                .map(|rel_array_layer| {
-                    let mut texture_base = src_base.clone();
-                    texture_base.array_layer += rel_array_layer;
-                    let mut buffer_layout = destination.layout;
-                    buffer_layout.offset += rel_array_layer as u64 * bytes_per_array_layer;
-                    hal::BufferTextureCopy {
-                        buffer_layout,
-                        texture_base,
-                        size: hal_copy_size,
-                    }
-                })
-                .collect::<Vec<_>>();
-            let cmd_buf_raw = cmd_buf_data.encoder.open()?;
-            unsafe {
-                cmd_buf_raw.transition_buffers(dst_barrier.as_slice());
-                cmd_buf_raw.transition_textures(&src_barrier);
-                cmd_buf_raw.copy_texture_to_buffer(
-                    src_raw,
-                    wgt::TextureUses::COPY_SRC,
-                    dst_raw,
-                    &regions,
-                );
-            }
+            source.texture.same_device_as(cmd_enc.as_ref())?;
+            destination.buffer.same_device_as(cmd_enc.as_ref())?;
 
-            Ok(())
+            copy_texture_to_buffer(cmd_buf_data, device, &source, &destination, copy_size)
         })
     }
 
@@ -1379,3 +1259,143 @@ impl Global {
         })
     }
 }
+
+/// Records a copy from `source` to `destination`, once their ids have been resolved.
+///
+/// This is also used by `wgpu-core` itself to read textures back.
+pub(crate) fn copy_texture_to_buffer(
+    cmd_buf_data: &mut CommandBufferMutable,
+    device: &Arc<Device>,
+    source: &wgt::TexelCopyTextureInfo<Arc<Texture>>,
+    destination: &wgt::TexelCopyBufferInfo<Arc<Buffer>>,
+    copy_size: &Extent3d,
+) -> Result<(), CommandEncoderError> {
+    let src_texture = &source.texture;
+    let dst_buffer = &destination.buffer;
+
+    let (hal_copy_size, array_layer_count) =
+        validate_texture_copy_range(source, &src_texture.desc, CopySide::Source, copy_size)?;
+
+    let (src_range, src_base) = extract_texture_selector(source, copy_size, src_texture)?;
+
+    let snatch_guard = device.snatchable_lock.read();
+
+    let src_raw = src_texture.try_raw(&snatch_guard)?;
+    src_texture
+        .check_usage(TextureUsages::COPY_SRC)
+        .map_err(TransferError::MissingTextureUsage)?;
+
+    if source.mip_level >= src_texture.desc.mip_level_count {
+        return Err(TransferError::InvalidMipLevel {
+            requested: source.mip_level,
+            count: src_texture.desc.mip_level_count,
+        }
+        .into());
+    }
+
+    if !conv::is_valid_copy_src_texture_format(src_texture.desc.format, source.aspect) {
+        return Err(TransferError::CopyFromForbiddenTextureFormat {
+            format: src_texture.desc.format,
+            aspect: source.aspect,
+        }
+        .into());
+    }
+
+    validate_texture_buffer_copy(
+        source,
+        src_base.aspect,
+        &src_texture.desc,
+        destination.layout.offset,
+        true, // alignment required for buffer offset
+    )?;
+
+    let (required_buffer_bytes_in_copy, bytes_per_array_layer) = validate_linear_texture_data(
+        &destination.layout,
+        src_texture.desc.format,
+        source.aspect,
+        dst_buffer.size,
+        CopySide::Destination,
+        copy_size,
+        true,
+    )?;
+
+    if src_texture.desc.format.is_depth_stencil_format() {
+        device
+            .require_downlevel_flags(wgt::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES)
+            .map_err(TransferError::from)?;
+    }
+    if src_texture.desc.format.is_compressed() {
+        device
+            .require_downlevel_flags(wgt::DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES)
+            .map_err(TransferError::from)?;
+    }
+
+    let dst_raw = dst_buffer.try_raw(&snatch_guard)?;
+    dst_buffer
+        .check_usage(BufferUsages::COPY_DST)
+        .map_err(TransferError::MissingBufferUsage)?;
+
+    if copy_size.width == 0 || copy_size.height == 0 || copy_size.depth_or_array_layers == 0 {
+        log::trace!("Ignoring copy_texture_to_buffer of size 0");
+        return Ok(());
+    }
+
+    // Handle texture init *before* dealing with barrier transitions so we
+    // have an easier time inserting "immediate-inits" that may be required
+    // by prior discards in rare cases.
+    handle_src_texture_init(
+        cmd_buf_data,
+        device,
+        source,
+        copy_size,
+        src_texture,
+        &snatch_guard,
+    )?;
+
+    let src_pending = cmd_buf_data.trackers.textures.set_single(
+        src_texture,
+        src_range,
+        wgt::TextureUses::COPY_SRC,
+    );
+    let src_barrier = src_pending
+        .map(|pending| pending.into_hal(src_raw))
+        .collect::<Vec<_>>();
+
+    let dst_pending = cmd_buf_data
+        .trackers
+        .buffers
+        .set_single(dst_buffer, wgt::BufferUses::COPY_DST);
+
+    let dst_barrier = dst_pending.map(|pending| pending.into_hal(dst_buffer, &snatch_guard));
+
+    cmd_buf_data.buffer_memory_init_actions.extend(
+        dst_buffer.initialization_status.read().create_action(
+            dst_buffer,
+            destination.layout.offset..(destination.layout.offset + required_buffer_bytes_in_copy),
+            MemoryInitKind::ImplicitlyInitialized,
+        ),
+    );
+
+    let regions = (0..array_layer_count)
+        .map(|rel_array_layer| {
+            let mut texture_base = src_base.clone();
+            texture_base.array_layer += rel_array_layer;
+            let mut buffer_layout = destination.layout;
+            buffer_layout.offset +=
+                dst_buffer.raw_offset + rel_array_layer as u64 * bytes_per_array_layer;
+            hal::BufferTextureCopy {
+                buffer_layout,
+                texture_base,
+                size: hal_copy_size,
+            }
+        })
+        .collect::<Vec<_>>();
+    let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+    unsafe {
+        cmd_buf_raw.transition_buffers(dst_barrier.as_slice());
+        cmd_buf_raw.transition_textures(&src_barrier);
+        cmd_buf_raw.copy_texture_to_buffer(src_raw, wgt::TextureUses::COPY_SRC, dst_raw, &regions);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,502 @@
+//! Reporting of out-of-bounds accesses made by shaders.
+//!
+//! With [`wgt::InstanceFlags::REPORT_SHADER_OUT_OF_BOUNDS`], shader modules are
+//! registered with the [`BoundsReporter`], and the back ends generate their
+//! code under [`naga::proc::BoundsCheckPolicy::Report`], so that their
+//! out-of-bounds accesses are recorded in a storage buffer owned by the
+//! device, the bounds report.
+//!
+//! The bounds report is bound at the bind group index following the last one
+//! available to the application, [`BoundsReporter::group`]: all pipeline
+//! layouts include its bind group layout, and it is bound along with every
+//! pipeline.
+//!
+//! At the end of each submission, the bounds report is copied to a readback
+//! buffer and cleared. Once the submission is done, the readback buffer is
+//! read and the first access it records becomes a
+//! [`ShaderOutOfBoundsError`], to be raised when the device is next polled
+//! or its queue next submitted to.
+
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::String,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, mem::ManuallyDrop};
+
+use arrayvec::ArrayVec;
+use thiserror::Error;
+use wgt::error::{ErrorType, WebGpuError};
+
+use crate::{
+    device::DeviceError,
+    hal_label,
+    lock::{rank, Mutex},
+    SubmissionIndex,
+};
+
+const REPORT_SIZE: wgt::BufferAddress = naga::proc::BOUNDS_REPORT_SIZE as wgt::BufferAddress;
+
+/// An out-of-bounds access made by a shader.
+///
+/// These are only reported with [`wgt::InstanceFlags::REPORT_SHADER_OUT_OF_BOUNDS`].
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub struct ShaderOutOfBoundsError {
+    /// The label of the shader module.
+    pub shader: String,
+    /// The name of the entry point being run, if its shader module is still alive.
+    pub entry_point: Option<String>,
+    /// The location of the access in the source of the shader module.
+    pub location: Option<naga::SourceLocation>,
+    /// The offending index. Negative indices show up as large values.
+    pub index: u32,
+    /// The number of elements of the indexed value.
+    pub length: u32,
+    /// The number of out-of-bounds accesses made by the submission, including this one.
+    pub count: u32,
+}
+
+impl fmt::Display for ShaderOutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Index {} is out of bounds of a value of length {}",
+            self.index, self.length
+        )?;
+        if let Some(ref entry_point) = self.entry_point {
+            write!(
+                f,
+                " in entry point `{entry_point}` of shader module {:?}",
+                self.shader
+            )?;
+        }
+        if let Some(location) = self.location {
+            write!(
+                f,
+                " at line {}, column {}",
+                location.line_number, location.line_position
+            )?;
+        }
+        if self.count > 1 {
+            write!(
+                f,
+                " ({} more out-of-bounds accesses were made by the same submission)",
+                self.count - 1
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl WebGpuError for ShaderOutOfBoundsError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        ErrorType::Validation
+    }
+}
+
+/// What the errors about a reported shader module need to know about it.
+///
+/// This is owned by the shader module, and only weakly referenced by the
+/// [`BoundsReporter`].
+#[derive(Debug)]
+pub(crate) struct ReportedShader {
+    label: String,
+    source: String,
+    entry_points: Vec<String>,
+}
+
+/// The bounds report of a device and its readback buffers.
+#[derive(Debug)]
+pub(crate) struct BoundsReporter {
+    /// The bind group index of the bounds report.
+    pub(crate) group: u32,
+    /// The stages whose accesses are reported.
+    stages: wgt::ShaderStages,
+    buffer: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    bind_group_layout: ManuallyDrop<Box<dyn hal::DynBindGroupLayout>>,
+    /// Fills the bind group indices between the application's and the report's.
+    empty_bind_group_layout: ManuallyDrop<Box<dyn hal::DynBindGroupLayout>>,
+    bind_group: ManuallyDrop<Box<dyn hal::DynBindGroup>>,
+    state: Mutex<BoundsReporterState>,
+}
+
+#[derive(Debug, Default)]
+struct BoundsReporterState {
+    /// The reported shader modules, by the shader id of their first entry point.
+    shaders: Vec<(u32, Weak<ReportedShader>)>,
+    next_shader_id: u32,
+    free_readbacks: Vec<Box<dyn hal::DynBuffer>>,
+    pending_readbacks: Vec<(SubmissionIndex, Box<dyn hal::DynBuffer>)>,
+    errors: Vec<ShaderOutOfBoundsError>,
+}
+
+impl BoundsReporter {
+    pub(crate) fn new(
+        device: &dyn hal::DynDevice,
+        group: u32,
+        stages: wgt::ShaderStages,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<Self, DeviceError> {
+        let buffer = unsafe {
+            device.create_buffer(&hal::BufferDescriptor {
+                label: hal_label(Some("(wgpu internal) shader bounds report"), instance_flags),
+                size: REPORT_SIZE,
+                usage: wgt::BufferUses::STORAGE_READ_WRITE
+                    | wgt::BufferUses::COPY_SRC
+                    | wgt::BufferUses::COPY_DST,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        }
+        .map_err(DeviceError::from_hal)?;
+
+        let bind_group_layout = unsafe {
+            device.create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                label: None,
+                flags: hal::BindGroupLayoutFlags::empty(),
+                entries: &[wgt::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: stages,
+                    ty: wgt::BindingType::Buffer {
+                        ty: wgt::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: wgt::BufferSize::new(REPORT_SIZE),
+                    },
+                    count: None,
+                }],
+            })
+        }
+        .map_err(DeviceError::from_hal)?;
+        let empty_bind_group_layout = unsafe {
+            device.create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                label: None,
+                flags: hal::BindGroupLayoutFlags::empty(),
+                entries: &[],
+            })
+        }
+        .map_err(DeviceError::from_hal)?;
+
+        let bind_group = unsafe {
+            device.create_bind_group(&hal::BindGroupDescriptor {
+                label: None,
+                layout: bind_group_layout.as_ref(),
+                entries: &[hal::BindGroupEntry {
+                    binding: 0,
+                    resource_index: 0,
+                    count: 1,
+                }],
+                // SAFETY: We just created the buffer with this size.
+                buffers: &[hal::BufferBinding::new_unchecked(
+                    buffer.as_ref(),
+                    0,
+                    wgt::BufferSize::new(REPORT_SIZE),
+                )],
+                samplers: &[],
+                textures: &[],
+                acceleration_structures: &[],
+                external_textures: &[],
+            })
+        }
+        .map_err(DeviceError::from_hal)?;
+
+        Ok(Self {
+            group,
+            stages,
+            buffer: ManuallyDrop::new(buffer),
+            bind_group_layout: ManuallyDrop::new(bind_group_layout),
+            empty_bind_group_layout: ManuallyDrop::new(empty_bind_group_layout),
+            bind_group: ManuallyDrop::new(bind_group),
+            state: Mutex::new(rank::DEVICE_BOUNDS_REPORTER, Default::default()),
+        })
+    }
+
+    /// Encode the initial clear of the bounds report.
+    pub(crate) fn initialize(&self, encoder: &mut dyn hal::DynCommandEncoder) {
+        let buffer = self.buffer.as_ref();
+        unsafe {
+            encoder.transition_buffers(&[hal::BufferBarrier {
+                buffer,
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::empty(),
+                    to: wgt::BufferUses::COPY_DST,
+                },
+            }]);
+            encoder.clear_buffer(buffer, 0..REPORT_SIZE);
+            encoder.transition_buffers(&[hal::BufferBarrier {
+                buffer,
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::COPY_DST,
+                    to: wgt::BufferUses::STORAGE_READ_WRITE,
+                },
+            }]);
+        }
+    }
+
+    /// Register `module`, so that its out-of-bounds accesses are reported.
+    ///
+    /// The returned [`ReportedShader`] must be kept alive as long as the
+    /// module, and the [`hal::ShaderBoundsReport`] passed to the back end.
+    pub(crate) fn register(
+        &self,
+        module: &naga::Module,
+        label: &str,
+        source: &str,
+    ) -> (Arc<ReportedShader>, hal::ShaderBoundsReport) {
+        let shader = Arc::new(ReportedShader {
+            label: label.to_owned(),
+            source: source.to_owned(),
+            entry_points: module
+                .entry_points
+                .iter()
+                .map(|ep| ep.name.clone())
+                .collect(),
+        });
+
+        let mut state = self.state.lock();
+        let first_shader_id = state.next_shader_id;
+        state.next_shader_id += module.entry_points.len() as u32;
+        state
+            .shaders
+            .retain(|(_, shader)| shader.strong_count() != 0);
+        state
+            .shaders
+            .push((first_shader_id, Arc::downgrade(&shader)));
+
+        let bounds_report = hal::ShaderBoundsReport {
+            binding: naga::ResourceBinding {
+                group: self.group,
+                binding: 0,
+            },
+            first_shader_id,
+            stages: self.stages,
+        };
+        (shader, bounds_report)
+    }
+
+    /// Add the bind group layouts needed by reported shaders to those of a
+    /// pipeline layout.
+    pub(crate) fn extend_bind_group_layouts<'a>(
+        &'a self,
+        bind_group_layouts: &mut ArrayVec<
+            &'a dyn hal::DynBindGroupLayout,
+            { hal::MAX_BIND_GROUPS },
+        >,
+    ) {
+        while bind_group_layouts.len() < self.group as usize {
+            bind_group_layouts.push(self.empty_bind_group_layout.as_ref());
+        }
+        bind_group_layouts.push(self.bind_group_layout.as_ref());
+    }
+
+    /// Bind the bounds report for pipelines of `layout`.
+    pub(crate) fn bind(
+        &self,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        layout: &dyn hal::DynPipelineLayout,
+    ) {
+        unsafe {
+            encoder.set_bind_group(layout, self.group, Some(self.bind_group.as_ref()), &[]);
+        }
+    }
+
+    /// Copy the bounds report to a readback buffer and clear it, at the end of
+    /// the submission `submit_index`.
+    pub(crate) fn encode_readback(
+        &self,
+        device: &dyn hal::DynDevice,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        submit_index: SubmissionIndex,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<(), DeviceError> {
+        let mut state = self.state.lock();
+        let readback = match state.free_readbacks.pop() {
+            Some(readback) => readback,
+            None => unsafe {
+                device.create_buffer(&hal::BufferDescriptor {
+                    label: hal_label(
+                        Some("(wgpu internal) shader bounds report readback"),
+                        instance_flags,
+                    ),
+                    size: REPORT_SIZE,
+                    usage: wgt::BufferUses::MAP_READ | wgt::BufferUses::COPY_DST,
+                    memory_flags: hal::MemoryFlags::empty(),
+                })
+            }
+            .map_err(DeviceError::from_hal)?,
+        };
+
+        let buffer = self.buffer.as_ref();
+        unsafe {
+            encoder.transition_buffers(&[
+                hal::BufferBarrier {
+                    buffer,
+                    usage: hal::StateTransition {
+                        from: wgt::BufferUses::STORAGE_READ_WRITE,
+                        to: wgt::BufferUses::COPY_SRC,
+                    },
+                },
+                hal::BufferBarrier {
+                    buffer: readback.as_ref(),
+                    usage: hal::StateTransition {
+                        from: wgt::BufferUses::MAP_READ,
+                        to: wgt::BufferUses::COPY_DST,
+                    },
+                },
+            ]);
+            encoder.copy_buffer_to_buffer(
+                buffer,
+                readback.as_ref(),
+                &[hal::BufferCopy {
+                    src_offset: 0,
+                    dst_offset: 0,
+                    size: wgt::BufferSize::new(REPORT_SIZE).unwrap(),
+                }],
+            );
+            encoder.transition_buffers(&[
+                hal::BufferBarrier {
+                    buffer,
+                    usage: hal::StateTransition {
+                        from: wgt::BufferUses::COPY_SRC,
+                        to: wgt::BufferUses::COPY_DST,
+                    },
+                },
+                hal::BufferBarrier {
+                    buffer: readback.as_ref(),
+                    usage: hal::StateTransition {
+                        from: wgt::BufferUses::COPY_DST,
+                        to: wgt::BufferUses::MAP_READ,
+                    },
+                },
+            ]);
+            encoder.clear_buffer(buffer, 0..REPORT_SIZE);
+            encoder.transition_buffers(&[hal::BufferBarrier {
+                buffer,
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::COPY_DST,
+                    to: wgt::BufferUses::STORAGE_READ_WRITE,
+                },
+            }]);
+        }
+
+        state.pending_readbacks.push((submit_index, readback));
+        Ok(())
+    }
+
+    /// Read the bounds reports of the submissions up to `last_done`.
+    pub(crate) fn read_back(&self, device: &dyn hal::DynDevice, last_done: SubmissionIndex) {
+        let mut state = self.state.lock();
+        let state = &mut *state;
+        let mut index = 0;
+        while index < state.pending_readbacks.len() {
+            if state.pending_readbacks[index].0 > last_done {
+                index += 1;
+                continue;
+            }
+            let (_, readback) = state.pending_readbacks.swap_remove(index);
+            match unsafe { read_buffer(device, readback.as_ref()) } {
+                Ok(words) => {
+                    let (count, records) = naga::proc::read_bounds_report(&words);
+                    if let Some(record) = records.first() {
+                        state
+                            .errors
+                            .push(Self::error(&state.shaders, record, count));
+                    }
+                }
+                Err(err) => log::error!("Failed to read a shader bounds report: {err}"),
+            }
+            state.free_readbacks.push(readback);
+        }
+    }
+
+    fn error(
+        shaders: &[(u32, Weak<ReportedShader>)],
+        record: &naga::proc::BoundsReportRecord,
+        count: u32,
+    ) -> ShaderOutOfBoundsError {
+        let shader = shaders
+            .iter()
+            .rev()
+            .find(|&&(first_id, _)| first_id <= record.shader_id)
+            .and_then(|(first_id, shader)| Some((record.shader_id - first_id, shader.upgrade()?)));
+        let (shader, entry_point, location) = match shader {
+            Some((entry_point, shader)) => (
+                shader.label.clone(),
+                shader.entry_points.get(entry_point as usize).cloned(),
+                record
+                    .span
+                    .to_range()
+                    .filter(|range| range.start <= shader.source.len())
+                    .map(|_| record.span.location(&shader.source)),
+            ),
+            None => (String::new(), None, None),
+        };
+        ShaderOutOfBoundsError {
+            shader,
+            entry_point,
+            location,
+            index: record.index,
+            length: record.length,
+            count,
+        }
+    }
+
+    /// Take the errors found in the bounds reports read so far.
+    pub(crate) fn take_errors(&self) -> Vec<ShaderOutOfBoundsError> {
+        core::mem::take(&mut self.state.lock().errors)
+    }
+
+    pub(crate) fn dispose(self, device: &dyn hal::DynDevice) {
+        let BoundsReporter {
+            buffer,
+            bind_group_layout,
+            empty_bind_group_layout,
+            bind_group,
+            state,
+            ..
+        } = self;
+        let state = state.into_inner();
+        unsafe {
+            device.destroy_bind_group(ManuallyDrop::into_inner(bind_group));
+            device.destroy_bind_group_layout(ManuallyDrop::into_inner(bind_group_layout));
+            device.destroy_bind_group_layout(ManuallyDrop::into_inner(empty_bind_group_layout));
+            device.destroy_buffer(ManuallyDrop::into_inner(buffer));
+            for readback in state.free_readbacks {
+                device.destroy_buffer(readback);
+            }
+            for (_, readback) in state.pending_readbacks {
+                device.destroy_buffer(readback);
+            }
+        }
+    }
+}
+
+/// Read the contents of a bounds report readback buffer.
+///
+/// # Safety
+///
+/// The GPU must be done writing to `buffer`.
+unsafe fn read_buffer(
+    device: &dyn hal::DynDevice,
+    buffer: &dyn hal::DynBuffer,
+) -> Result<Vec<u32>, hal::DeviceError> {
+    let mapping = unsafe { device.map_buffer(buffer, 0..REPORT_SIZE) }?;
+    if !mapping.is_coherent {
+        #[allow(clippy::single_range_in_vec_init)]
+        unsafe {
+            device.invalidate_mapped_ranges(buffer, &[0..REPORT_SIZE]);
+        }
+    }
+    let mut words = alloc::vec![0u32; REPORT_SIZE as usize / 4];
+    // SAFETY: The mapping covers `REPORT_SIZE` bytes, and `words` too.
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            mapping.ptr.as_ptr(),
+            words.as_mut_ptr().cast::<u8>(),
+            REPORT_SIZE as usize,
+        );
+        device.unmap_buffer(buffer);
+    }
+    Ok(words)
+}
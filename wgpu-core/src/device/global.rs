@@ -1,7 +1,7 @@
 use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::{ptr::NonNull, sync::atomic::Ordering};
 
-#[cfg(feature = "trace")]
+#[cfg(any(feature = "trace", feature = "replay"))]
 use crate::device::trace;
 use crate::{
     api_log,
@@ -9,9 +9,12 @@ use crate::{
         self, BindGroupEntry, BindingResource, BufferBinding, ResolvedBindGroupDescriptor,
         ResolvedBindGroupEntry, ResolvedBindingResource, ResolvedBufferBinding,
     },
-    command::{self, CommandEncoder},
+    command::{self, CommandEncoder, CommandEncoderError},
     conv,
-    device::{bgl, life::WaitIdleError, DeviceError, DeviceLostClosure},
+    device::{
+        bgl, life::WaitIdleError, pipeline_workers::CompletedPipeline, queue::CreateQueueError,
+        DeviceError, DeviceLostClosure, MemoryBudgetCallback, MemoryBudgetWatch,
+    },
     global::Global,
     id::{self, AdapterId, DeviceId, QueueId, SurfaceId},
     instance::{self, Adapter, Surface},
@@ -430,6 +433,23 @@ impl Global {
         (id, err)
     }
 
+    /// Copies the first mip level and array layer of a texture to the CPU, with tightly
+    /// packed rows.
+    ///
+    /// This waits for all previously submitted work and is only meant for checking
+    /// the outputs of replayed traces.
+    #[cfg(any(feature = "trace", feature = "replay"))]
+    pub fn texture_read_back(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<Vec<u8>, trace::TextureReadBackError> {
+        profiling::scope!("Texture::read_back");
+        api_log!("Texture::read_back {texture_id:?}");
+
+        let texture = self.hub.textures.get(texture_id).get()?;
+        texture.device.read_back_texture(&texture)
+    }
+
     pub fn texture_destroy(&self, texture_id: id::TextureId) {
         profiling::scope!("Texture::destroy");
         api_log!("Texture::destroy {texture_id:?}");
@@ -1163,7 +1183,8 @@ impl Global {
         let device = self.hub.devices.get(device_id);
 
         let error = 'error: {
-            let cmd_enc = match device.create_command_encoder(&desc.label) {
+            let queue = device.get_queue().unwrap();
+            let cmd_enc = match device.create_command_encoder(&queue, &desc.label, false) {
                 Ok(cmd_enc) => cmd_enc,
                 Err(e) => break 'error e,
             };
@@ -1181,6 +1202,63 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Create a command encoder whose command buffer can be submitted more than once.
+    ///
+    /// Requires [`wgt::Features::REUSABLE_COMMAND_BUFFERS`].
+    pub fn device_create_reusable_command_encoder(
+        &self,
+        device_id: DeviceId,
+        desc: &wgt::CommandEncoderDescriptor<Label>,
+        id_in: Option<id::CommandEncoderId>,
+    ) -> (id::CommandEncoderId, Option<CommandEncoderError>) {
+        profiling::scope!("Device::create_reusable_command_encoder");
+
+        let hub = &self.hub;
+        let fid = hub.command_encoders.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+
+        let error: CommandEncoderError = 'error: {
+            if let Err(e) = device.require_features(wgt::Features::REUSABLE_COMMAND_BUFFERS) {
+                break 'error e.into();
+            }
+
+            let queue = device.get_queue().unwrap();
+            let cmd_enc = match device.create_command_encoder(&queue, &desc.label, true) {
+                Ok(cmd_enc) => cmd_enc,
+                Err(e) => break 'error e.into(),
+            };
+
+            let id = fid.assign(cmd_enc);
+            api_log!("Device::create_reusable_command_encoder -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign(Arc::new(CommandEncoder::new_invalid(
+            &device,
+            &desc.label,
+            error.clone(),
+        )));
+        (id, Some(error))
+    }
+
+    /// Create a queue besides the one `device_id` was created with.
+    pub fn device_create_queue(
+        &self,
+        device_id: DeviceId,
+        desc: &wgt::QueueDescriptor<Label>,
+        id_in: Option<QueueId>,
+    ) -> Result<QueueId, CreateQueueError> {
+        profiling::scope!("Device::create_queue");
+
+        let device = self.hub.devices.get(device_id);
+        let queue = device.create_queue(desc)?;
+
+        let id = self.hub.queues.prepare(id_in).assign(queue);
+        api_log!("Device::create_queue -> {id:?}");
+        Ok(id)
+    }
+
     pub fn command_encoder_drop(&self, command_encoder_id: id::CommandEncoderId) {
         profiling::scope!("CommandEncoder::drop");
         api_log!("CommandEncoder::drop {command_encoder_id:?}");
@@ -1234,6 +1312,7 @@ impl Global {
                         &bundle_encoder.context,
                         bundle_encoder.is_depth_read_only,
                         bundle_encoder.is_stencil_read_only,
+                        &bundle_encoder.inherited_bind_group_layouts,
                     ),
                     base: bundle_encoder.to_base_pass(),
                 });
@@ -1373,6 +1452,67 @@ impl Global {
         self.device_create_general_render_pipeline(desc.clone().into(), device, fid)
     }
 
+    /// Create a render pipeline without blocking the calling thread.
+    ///
+    /// The pipeline is created on a background thread. Once it is ready,
+    /// `callback` is invoked by [`Self::device_poll`] of `device_id` or
+    /// [`Self::poll_all_devices`] with the ID of the pipeline and the error, if
+    /// creation failed. Errors are only reported to `callback`.
+    ///
+    /// Polling with a wait also waits for the pending pipelines of the polled
+    /// devices.
+    pub fn device_create_render_pipeline_async(
+        &self,
+        device_id: DeviceId,
+        desc: pipeline::RenderPipelineDescriptor<'static>,
+        id_in: Option<id::RenderPipelineId>,
+        callback: pipeline::CreateRenderPipelineCallback,
+    ) {
+        profiling::scope!("Device::create_render_pipeline_async");
+
+        let fid = self.hub.render_pipelines.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        #[cfg(feature = "trace")]
+        if let Some(ref mut trace) = *device.trace.lock() {
+            trace.add(trace::Action::CreateRenderPipeline {
+                id: fid.id(),
+                desc: desc.clone(),
+            });
+        }
+
+        let id = fid.detach();
+        let label = desc.label.to_string();
+        let desc = pipeline::GeneralRenderPipelineDescriptor::from(desc);
+        let resolved = device
+            .check_is_valid()
+            .map_err(Into::into)
+            .and_then(|()| self.resolve_general_render_pipeline_descriptor(&desc));
+        match resolved {
+            Ok(desc) => self.pipeline_workers.submit(
+                device_id,
+                Box::new(move || {
+                    let result = device.create_render_pipeline(desc);
+                    CompletedPipeline::Render {
+                        id,
+                        result,
+                        label,
+                        callback,
+                    }
+                }),
+            ),
+            Err(error) => self.pipeline_workers.push_completed(
+                device_id,
+                CompletedPipeline::Render {
+                    id,
+                    result: Err(error),
+                    label,
+                    callback,
+                },
+            ),
+        }
+    }
+
     fn device_create_general_render_pipeline(
         &self,
         desc: pipeline::GeneralRenderPipelineDescriptor,
@@ -1384,164 +1524,148 @@ impl Global {
     ) {
         profiling::scope!("Device::create_general_render_pipeline");
 
-        let hub = &self.hub;
-
         let error = 'error: {
             if let Err(e) = device.check_is_valid() {
                 break 'error e.into();
             }
 
-            let layout = desc
-                .layout
-                .map(|layout| hub.pipeline_layouts.get(layout).get())
-                .transpose();
-            let layout = match layout {
-                Ok(layout) => layout,
-                Err(e) => break 'error e.into(),
+            let desc = match self.resolve_general_render_pipeline_descriptor(&desc) {
+                Ok(desc) => desc,
+                Err(e) => break 'error e,
             };
 
-            let cache = desc
-                .cache
-                .map(|cache| hub.pipeline_caches.get(cache).get())
-                .transpose();
-            let cache = match cache {
-                Ok(cache) => cache,
-                Err(e) => break 'error e.into(),
+            let pipeline = match device.create_render_pipeline(desc) {
+                Ok(pair) => pair,
+                Err(e) => break 'error e,
             };
 
-            let vertex = match desc.vertex {
-                RenderPipelineVertexProcessor::Vertex(ref vertex) => {
+            let id = fid.assign(Fallible::Valid(pipeline));
+            api_log!("Device::create_render_pipeline -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+
+        (id, Some(error))
+    }
+
+    /// Look up the resources referenced by a render pipeline descriptor.
+    fn resolve_general_render_pipeline_descriptor<'a>(
+        &self,
+        desc: &pipeline::GeneralRenderPipelineDescriptor<'a>,
+    ) -> Result<ResolvedGeneralRenderPipelineDescriptor<'a>, pipeline::CreateRenderPipelineError>
+    {
+        let hub = &self.hub;
+
+        let layout = desc
+            .layout
+            .map(|layout| hub.pipeline_layouts.get(layout).get())
+            .transpose()?;
+
+        let cache = desc
+            .cache
+            .map(|cache| hub.pipeline_caches.get(cache).get())
+            .transpose()?;
+
+        let vertex = match desc.vertex {
+            RenderPipelineVertexProcessor::Vertex(ref vertex) => {
+                let module = hub
+                    .shader_modules
+                    .get(vertex.stage.module)
+                    .get()
+                    .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
+                        stage: wgt::ShaderStages::VERTEX,
+                        error: e.into(),
+                    })?;
+                let stage = ResolvedProgrammableStageDescriptor {
+                    module,
+                    entry_point: vertex.stage.entry_point.clone(),
+                    constants: vertex.stage.constants.clone(),
+                    zero_initialize_workgroup_memory: vertex.stage.zero_initialize_workgroup_memory,
+                };
+                RenderPipelineVertexProcessor::Vertex(ResolvedVertexState {
+                    stage,
+                    buffers: vertex.buffers.clone(),
+                })
+            }
+            RenderPipelineVertexProcessor::Mesh(ref task, ref mesh) => {
+                let task_module = if let Some(task) = task {
                     let module = hub
                         .shader_modules
-                        .get(vertex.stage.module)
+                        .get(task.stage.module)
                         .get()
                         .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
                             stage: wgt::ShaderStages::VERTEX,
                             error: e.into(),
-                        });
-                    let module = match module {
-                        Ok(module) => module,
-                        Err(e) => break 'error e,
-                    };
-                    let stage = ResolvedProgrammableStageDescriptor {
+                        })?;
+                    let state = ResolvedProgrammableStageDescriptor {
                         module,
-                        entry_point: vertex.stage.entry_point.clone(),
-                        constants: vertex.stage.constants.clone(),
-                        zero_initialize_workgroup_memory: vertex
-                            .stage
-                            .zero_initialize_workgroup_memory,
-                    };
-                    RenderPipelineVertexProcessor::Vertex(ResolvedVertexState {
-                        stage,
-                        buffers: vertex.buffers.clone(),
-                    })
-                }
-                RenderPipelineVertexProcessor::Mesh(ref task, ref mesh) => {
-                    let task_module = if let Some(task) = task {
-                        let module = hub
-                            .shader_modules
-                            .get(task.stage.module)
-                            .get()
-                            .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
-                                stage: wgt::ShaderStages::VERTEX,
-                                error: e.into(),
-                            });
-                        let module = match module {
-                            Ok(module) => module,
-                            Err(e) => break 'error e,
-                        };
-                        let state = ResolvedProgrammableStageDescriptor {
-                            module,
-                            entry_point: task.stage.entry_point.clone(),
-                            constants: task.stage.constants.clone(),
-                            zero_initialize_workgroup_memory: task
-                                .stage
-                                .zero_initialize_workgroup_memory,
-                        };
-                        Some(ResolvedTaskState { stage: state })
-                    } else {
-                        None
-                    };
-                    let mesh_module =
-                        hub.shader_modules
-                            .get(mesh.stage.module)
-                            .get()
-                            .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
-                                stage: wgt::ShaderStages::MESH,
-                                error: e.into(),
-                            });
-                    let mesh_module = match mesh_module {
-                        Ok(module) => module,
-                        Err(e) => break 'error e,
-                    };
-                    let mesh_stage = ResolvedProgrammableStageDescriptor {
-                        module: mesh_module,
-                        entry_point: mesh.stage.entry_point.clone(),
-                        constants: mesh.stage.constants.clone(),
-                        zero_initialize_workgroup_memory: mesh
+                        entry_point: task.stage.entry_point.clone(),
+                        constants: task.stage.constants.clone(),
+                        zero_initialize_workgroup_memory: task
                             .stage
                             .zero_initialize_workgroup_memory,
                     };
-                    RenderPipelineVertexProcessor::Mesh(
-                        task_module,
-                        ResolvedMeshState { stage: mesh_stage },
-                    )
-                }
-            };
-
-            let fragment = if let Some(ref state) = desc.fragment {
-                let module = hub
+                    Some(ResolvedTaskState { stage: state })
+                } else {
+                    None
+                };
+                let mesh_module = hub
                     .shader_modules
-                    .get(state.stage.module)
+                    .get(mesh.stage.module)
                     .get()
                     .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
-                        stage: wgt::ShaderStages::FRAGMENT,
+                        stage: wgt::ShaderStages::MESH,
                         error: e.into(),
-                    });
-                let module = match module {
-                    Ok(module) => module,
-                    Err(e) => break 'error e,
+                    })?;
+                let mesh_stage = ResolvedProgrammableStageDescriptor {
+                    module: mesh_module,
+                    entry_point: mesh.stage.entry_point.clone(),
+                    constants: mesh.stage.constants.clone(),
+                    zero_initialize_workgroup_memory: mesh.stage.zero_initialize_workgroup_memory,
                 };
-                let stage = ResolvedProgrammableStageDescriptor {
-                    module,
-                    entry_point: state.stage.entry_point.clone(),
-                    constants: state.stage.constants.clone(),
-                    zero_initialize_workgroup_memory: state.stage.zero_initialize_workgroup_memory,
-                };
-                Some(ResolvedFragmentState {
-                    stage,
-                    targets: state.targets.clone(),
-                })
-            } else {
-                None
-            };
-
-            let desc = ResolvedGeneralRenderPipelineDescriptor {
-                label: desc.label.clone(),
-                layout,
-                vertex,
-                primitive: desc.primitive,
-                depth_stencil: desc.depth_stencil.clone(),
-                multisample: desc.multisample,
-                fragment,
-                multiview: desc.multiview,
-                cache,
-            };
+                RenderPipelineVertexProcessor::Mesh(
+                    task_module,
+                    ResolvedMeshState { stage: mesh_stage },
+                )
+            }
+        };
 
-            let pipeline = match device.create_render_pipeline(desc) {
-                Ok(pair) => pair,
-                Err(e) => break 'error e,
+        let fragment = if let Some(ref state) = desc.fragment {
+            let module = hub
+                .shader_modules
+                .get(state.stage.module)
+                .get()
+                .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
+                    stage: wgt::ShaderStages::FRAGMENT,
+                    error: e.into(),
+                })?;
+            let stage = ResolvedProgrammableStageDescriptor {
+                module,
+                entry_point: state.stage.entry_point.clone(),
+                constants: state.stage.constants.clone(),
+                zero_initialize_workgroup_memory: state.stage.zero_initialize_workgroup_memory,
             };
-
-            let id = fid.assign(Fallible::Valid(pipeline));
-            api_log!("Device::create_render_pipeline -> {id:?}");
-
-            return (id, None);
+            Some(ResolvedFragmentState {
+                stage,
+                targets: state.targets.clone(),
+            })
+        } else {
+            None
         };
 
-        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
-
-        (id, Some(error))
+        Ok(ResolvedGeneralRenderPipelineDescriptor {
+            label: desc.label.clone(),
+            layout,
+            vertex,
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment,
+            multiview: desc.multiview,
+            cache,
+        })
     }
 
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
@@ -1623,41 +1747,9 @@ impl Global {
                 break 'error e.into();
             }
 
-            let layout = desc
-                .layout
-                .map(|layout| hub.pipeline_layouts.get(layout).get())
-                .transpose();
-            let layout = match layout {
-                Ok(layout) => layout,
-                Err(e) => break 'error e.into(),
-            };
-
-            let cache = desc
-                .cache
-                .map(|cache| hub.pipeline_caches.get(cache).get())
-                .transpose();
-            let cache = match cache {
-                Ok(cache) => cache,
-                Err(e) => break 'error e.into(),
-            };
-
-            let module = hub.shader_modules.get(desc.stage.module).get();
-            let module = match module {
-                Ok(module) => module,
-                Err(e) => break 'error e.into(),
-            };
-            let stage = ResolvedProgrammableStageDescriptor {
-                module,
-                entry_point: desc.stage.entry_point.clone(),
-                constants: desc.stage.constants.clone(),
-                zero_initialize_workgroup_memory: desc.stage.zero_initialize_workgroup_memory,
-            };
-
-            let desc = ResolvedComputePipelineDescriptor {
-                label: desc.label.clone(),
-                layout,
-                stage,
-                cache,
+            let desc = match self.resolve_compute_pipeline_descriptor(desc) {
+                Ok(desc) => desc,
+                Err(e) => break 'error e,
             };
 
             let pipeline = match device.create_compute_pipeline(desc) {
@@ -1676,6 +1768,94 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Create a compute pipeline without blocking the calling thread.
+    ///
+    /// See [`Self::device_create_render_pipeline_async`] for how the result is
+    /// reported.
+    pub fn device_create_compute_pipeline_async(
+        &self,
+        device_id: DeviceId,
+        desc: pipeline::ComputePipelineDescriptor<'static>,
+        id_in: Option<id::ComputePipelineId>,
+        callback: pipeline::CreateComputePipelineCallback,
+    ) {
+        profiling::scope!("Device::create_compute_pipeline_async");
+
+        let fid = self.hub.compute_pipelines.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        #[cfg(feature = "trace")]
+        if let Some(ref mut trace) = *device.trace.lock() {
+            trace.add(trace::Action::CreateComputePipeline {
+                id: fid.id(),
+                desc: desc.clone(),
+            });
+        }
+
+        let id = fid.detach();
+        let label = desc.label.to_string();
+        let resolved = device
+            .check_is_valid()
+            .map_err(Into::into)
+            .and_then(|()| self.resolve_compute_pipeline_descriptor(&desc));
+        match resolved {
+            Ok(desc) => self.pipeline_workers.submit(
+                device_id,
+                Box::new(move || {
+                    let result = device.create_compute_pipeline(desc);
+                    CompletedPipeline::Compute {
+                        id,
+                        result,
+                        label,
+                        callback,
+                    }
+                }),
+            ),
+            Err(error) => self.pipeline_workers.push_completed(
+                device_id,
+                CompletedPipeline::Compute {
+                    id,
+                    result: Err(error),
+                    label,
+                    callback,
+                },
+            ),
+        }
+    }
+
+    /// Look up the resources referenced by a compute pipeline descriptor.
+    fn resolve_compute_pipeline_descriptor<'a>(
+        &self,
+        desc: &pipeline::ComputePipelineDescriptor<'a>,
+    ) -> Result<ResolvedComputePipelineDescriptor<'a>, pipeline::CreateComputePipelineError> {
+        let hub = &self.hub;
+
+        let layout = desc
+            .layout
+            .map(|layout| hub.pipeline_layouts.get(layout).get())
+            .transpose()?;
+
+        let cache = desc
+            .cache
+            .map(|cache| hub.pipeline_caches.get(cache).get())
+            .transpose()?;
+
+        let module = hub.shader_modules.get(desc.stage.module).get()?;
+        let stage = ResolvedProgrammableStageDescriptor {
+            module,
+            entry_point: desc.stage.entry_point.clone(),
+            constants: desc.stage.constants.clone(),
+            zero_initialize_workgroup_memory: desc.stage.zero_initialize_workgroup_memory,
+        };
+
+        Ok(ResolvedComputePipelineDescriptor {
+            label: desc.label.clone(),
+            layout,
+            stage,
+            cache,
+        })
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn compute_pipeline_get_bind_group_layout(
@@ -1918,6 +2098,16 @@ impl Global {
 
                 #[cfg(feature = "trace")]
                 if let Some(ref mut trace) = *device.trace.lock() {
+                    // Recorded outputs include presented frames, which are copied out of the
+                    // surface textures.
+                    if trace.records_outputs()
+                        && !config.usage.contains(wgt::TextureUsages::COPY_SRC)
+                    {
+                        log::warn!(
+                            "Surface {surface_id:?} is not configured with `COPY_SRC`, \
+                            its presented frames won't be recorded in the trace"
+                        );
+                    }
                     trace.add(trace::Action::ConfigureSurface(surface_id, config.clone()));
                 }
 
@@ -2076,14 +2266,55 @@ impl Global {
         api_log!("Device::poll {poll_type:?}");
 
         let device = self.hub.devices.get(device_id);
+        let wait = poll_type.is_wait();
 
         let (closures, result) = Self::poll_single_device(&device, poll_type);
 
         closures.fire();
+        self.finish_async_pipelines(Some(device_id), wait);
 
         result
     }
 
+    /// Register the pipelines of `device_id`, or of all devices if it is `None`,
+    /// created in the background since the last poll, and invoke their callbacks.
+    ///
+    /// If `wait` is true, first wait for the pending pipelines of those devices.
+    fn finish_async_pipelines(&self, device_id: Option<DeviceId>, wait: bool) {
+        for completed in self.pipeline_workers.take_completed(device_id, wait) {
+            match completed {
+                CompletedPipeline::Render {
+                    id,
+                    result,
+                    label,
+                    callback,
+                } => {
+                    let (pipeline, error) = match result {
+                        Ok(pipeline) => (Fallible::Valid(pipeline), None),
+                        Err(e) => (Fallible::Invalid(Arc::new(label)), Some(e)),
+                    };
+                    self.hub.render_pipelines.assign(id, pipeline);
+                    api_log!("Device::create_render_pipeline_async -> {id:?}");
+                    callback(id, error);
+                }
+                CompletedPipeline::Compute {
+                    id,
+                    result,
+                    label,
+                    callback,
+                } => {
+                    let (pipeline, error) = match result {
+                        Ok(pipeline) => (Fallible::Valid(pipeline), None),
+                        Err(e) => (Fallible::Invalid(Arc::new(label)), Some(e)),
+                    };
+                    self.hub.compute_pipelines.assign(id, pipeline);
+                    api_log!("Device::create_compute_pipeline_async -> {id:?}");
+                    callback(id, error);
+                }
+            }
+        }
+    }
+
     fn poll_single_device(
         device: &crate::device::Device,
         poll_type: wgt::PollType<crate::SubmissionIndex>,
@@ -2151,6 +2382,7 @@ impl Global {
         let all_queue_empty = self.poll_all_devices_of_api(force_wait, &mut closures)?;
 
         closures.fire();
+        self.finish_async_pipelines(None, force_wait);
 
         Ok(all_queue_empty)
     }
@@ -2237,6 +2469,31 @@ impl Global {
             .replace(device_lost_closure);
     }
 
+    /// Call `callback` when the usage of a memory heap of the device reaches
+    /// `threshold` percent of its budget, replacing any previous callback.
+    ///
+    /// The budget is checked when the device is polled or work is submitted to
+    /// its queue. The callback is called once each time a heap reaches the
+    /// threshold, and not again until it drops below it. It is never called if
+    /// the backend can't report the memory budget.
+    pub fn device_set_memory_budget_callback(
+        &self,
+        device_id: DeviceId,
+        threshold: u8,
+        callback: MemoryBudgetCallback,
+    ) {
+        let device = self.hub.devices.get(device_id);
+
+        device
+            .memory_budget_watch
+            .lock()
+            .replace(MemoryBudgetWatch {
+                threshold,
+                callback,
+                exceeded: Vec::new(),
+            });
+    }
+
     pub fn device_destroy(&self, device_id: DeviceId) {
         api_log!("Device::destroy {device_id:?}");
 
@@ -2265,7 +2522,7 @@ impl Global {
         let device = self.hub.devices.get(device_id);
         wgt::InternalCounters {
             hal: device.get_hal_counters(),
-            core: wgt::CoreCounters {},
+            core: device.get_core_counters(),
         }
     }
 
@@ -2277,6 +2534,26 @@ impl Global {
         device.generate_allocator_report()
     }
 
+    pub fn device_get_memory_budget(&self, device_id: DeviceId) -> Option<wgt::MemoryBudgetReport> {
+        let device = self.hub.devices.get(device_id);
+        device.get_memory_budget()
+    }
+
+    /// Take the out-of-bounds shader accesses reported since the last call.
+    ///
+    /// Always empty unless the device was created with
+    /// [`wgt::InstanceFlags::REPORT_SHADER_OUT_OF_BOUNDS`].
+    pub fn device_take_shader_out_of_bounds_errors(
+        &self,
+        device_id: DeviceId,
+    ) -> Vec<super::bounds_report::ShaderOutOfBoundsError> {
+        let device = self.hub.devices.get(device_id);
+        device
+            .bounds_reporter
+            .as_ref()
+            .map_or_else(Vec::new, |reporter| reporter.take_errors())
+    }
+
     pub fn queue_drop(&self, queue_id: QueueId) {
         profiling::scope!("Queue::drop");
         api_log!("Queue::drop {queue_id:?}");
@@ -5,7 +5,7 @@ use thiserror::Error;
 
 use crate::{
     device::{
-        queue::{EncoderInFlight, SubmittedWorkDoneClosure, TempResource},
+        queue::{EncoderInFlight, QueueSlot, SubmittedWorkDoneClosure, TempResource},
         DeviceError,
     },
     ray_tracing::BlasCompactReadyPendingClosure,
@@ -25,10 +25,14 @@ use crate::{
 struct ActiveSubmission {
     /// The index of the submission we track.
     ///
-    /// When `Device::fence`'s value is greater than or equal to this, our queue
-    /// submission has completed.
+    /// When the value of the fence of `queue` is greater than or equal to
+    /// this, our queue submission has completed.
     index: SubmissionIndex,
 
+    /// The queue the submission was made to, `None` for the queue the device
+    /// was created with, whose fence is `Device::fence`.
+    queue: Option<QueueSlot>,
+
     /// Buffers to be mapped once this submission has completed.
     mapped: Vec<Arc<Buffer>>,
 
@@ -65,7 +69,7 @@ impl ActiveSubmission {
             // in the pending buffer list. If it came from a user command encoder,
             // it is in the tracker.
 
-            if encoder.trackers.buffers.contains(buffer) {
+            if encoder.used_resources().buffers.contains(buffer) {
                 return true;
             }
 
@@ -90,7 +94,7 @@ impl ActiveSubmission {
             // in the pending buffer list. If it came from a user command encoder,
             // it is in the tracker.
 
-            if encoder.trackers.textures.contains(texture) {
+            if encoder.used_resources().textures.contains(texture) {
                 return true;
             }
 
@@ -110,7 +114,7 @@ impl ActiveSubmission {
     /// This only uses constant-time operations.
     pub fn contains_blas(&self, blas: &Blas) -> bool {
         for encoder in &self.encoders {
-            if encoder.trackers.blas_s.contains(blas) {
+            if encoder.used_resources().blas_s.contains(blas) {
                 return true;
             }
 
@@ -121,6 +125,34 @@ impl ActiveSubmission {
 
         false
     }
+
+    /// Returns true if this submission contains any of the buffers, textures,
+    /// BLASes or TLASes used by `encoder`.
+    fn shares_resources_with(&self, encoder: &EncoderInFlight) -> bool {
+        let used = encoder.used_resources();
+        used.buffers
+            .used_resources()
+            .chain(encoder.pending_buffers.values())
+            .any(|buffer| self.contains_buffer(buffer))
+            || used
+                .textures
+                .used_resources()
+                .chain(encoder.pending_textures.values())
+                .any(|texture| self.contains_texture(texture))
+            || used
+                .blas_s
+                .used_resources()
+                .chain(encoder.pending_blas_s.values())
+                .any(|blas| self.contains_blas(blas))
+            || used.tlas_s.into_iter().any(|tlas| {
+                self.encoders.iter().any(|e| {
+                    e.used_resources()
+                        .tlas_s
+                        .into_iter()
+                        .any(|other| Arc::ptr_eq(tlas, other))
+                })
+            })
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -206,9 +238,15 @@ impl LifetimeTracker {
     }
 
     /// Start tracking resources associated with a new queue submission.
-    pub fn track_submission(&mut self, index: SubmissionIndex, encoders: Vec<EncoderInFlight>) {
+    pub fn track_submission(
+        &mut self,
+        index: SubmissionIndex,
+        queue: Option<QueueSlot>,
+        encoders: Vec<EncoderInFlight>,
+    ) {
         self.active.push(ActiveSubmission {
             index,
+            queue,
             mapped: Vec::new(),
             compact_read_back: Vec::new(),
             encoders,
@@ -277,6 +315,108 @@ impl LifetimeTracker {
         })
     }
 
+    /// Returns the most recent submission in flight on each queue other than
+    /// `queue` that uses any of the resources used by `encoders`.
+    ///
+    /// A submission of `encoders` to `queue` must wait for these.
+    pub fn cross_queue_dependencies(
+        &self,
+        queue: Option<QueueSlot>,
+        encoders: &[EncoderInFlight],
+    ) -> SmallVec<[(Option<QueueSlot>, SubmissionIndex); 2]> {
+        let mut dependencies = SmallVec::<[(Option<QueueSlot>, SubmissionIndex); 2]>::new();
+        for submission in self.active.iter().rev() {
+            if submission.queue == queue
+                || dependencies
+                    .iter()
+                    .any(|&(other, _)| other == submission.queue)
+            {
+                continue;
+            }
+            if encoders
+                .iter()
+                .any(|encoder| submission.shares_resources_with(encoder))
+            {
+                dependencies.push((submission.queue, submission.index));
+            }
+        }
+        dependencies
+    }
+
+    /// Returns the most recent submission in flight on each queue other than
+    /// `queue` that uses any of `buffers` or `textures`.
+    ///
+    /// Memory can only be bound to these resources on `queue` once these have
+    /// completed.
+    pub fn resource_dependencies(
+        &self,
+        queue: Option<QueueSlot>,
+        buffers: &[&Buffer],
+        textures: &[&Texture],
+    ) -> SmallVec<[(Option<QueueSlot>, SubmissionIndex); 2]> {
+        let mut dependencies = SmallVec::<[(Option<QueueSlot>, SubmissionIndex); 2]>::new();
+        for submission in self.active.iter().rev() {
+            if submission.queue == queue
+                || dependencies
+                    .iter()
+                    .any(|&(other, _)| other == submission.queue)
+            {
+                continue;
+            }
+            if buffers
+                .iter()
+                .any(|buffer| submission.contains_buffer(buffer))
+                || textures
+                    .iter()
+                    .any(|texture| submission.contains_texture(texture))
+            {
+                dependencies.push((submission.queue, submission.index));
+            }
+        }
+        dependencies
+    }
+
+    /// Returns the most recent submission in flight on each queue whose index
+    /// is at most `submission_index`.
+    ///
+    /// Once the fence of each queue has reached the value given for it, all
+    /// submissions up to `submission_index` have completed.
+    pub fn wait_targets(
+        &self,
+        submission_index: SubmissionIndex,
+    ) -> SmallVec<[(Option<QueueSlot>, SubmissionIndex); 2]> {
+        let mut targets = SmallVec::<[(Option<QueueSlot>, SubmissionIndex); 2]>::new();
+        for submission in self
+            .active
+            .iter()
+            .take_while(|a| a.index <= submission_index)
+        {
+            match targets
+                .iter_mut()
+                .find(|&&mut (queue, _)| queue == submission.queue)
+            {
+                Some(target) => target.1 = submission.index,
+                None => targets.push((submission.queue, submission.index)),
+            }
+        }
+        targets
+    }
+
+    /// Returns the index of the oldest submission in flight that has not
+    /// completed yet, if any.
+    ///
+    /// `fence_value` returns the value of the fence of a queue, or `None` if
+    /// the queue is gone, which implies its submissions have completed.
+    pub fn first_incomplete_submission(
+        &self,
+        fence_value: impl Fn(Option<QueueSlot>) -> Option<hal::FenceValue>,
+    ) -> Option<SubmissionIndex> {
+        self.active
+            .iter()
+            .find(|a| fence_value(a.queue).is_some_and(|value| value < a.index))
+            .map(|a| a.index)
+    }
+
     /// Sort out the consequences of completed submissions.
     ///
     /// Assume that all submissions up through `last_done` have completed.
@@ -1,8 +1,9 @@
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::{fmt, num::NonZeroU32};
 
 use crate::{
     binding_model,
+    pipeline::{PipelineFlags, RenderPipeline},
     ray_tracing::BlasCompactReadyPendingClosure,
     resource::{
         Buffer, BufferAccessError, BufferAccessResult, BufferMapOperation, Labeled,
@@ -21,13 +22,18 @@ use wgt::{
 };
 
 pub(crate) mod bgl;
+pub mod bounds_report;
 pub mod global;
 mod life;
+pub(crate) mod pipeline_workers;
 pub mod queue;
 pub mod ray_tracing;
 pub mod resource;
+mod sparse;
+pub(crate) mod suballocation;
 #[cfg(any(feature = "trace", feature = "replay"))]
 pub mod trace;
+mod video;
 pub use {life::WaitIdleError, resource::Device};
 
 pub const SHADER_STAGE_COUNT: usize = hal::MAX_CONCURRENT_SHADER_STAGES;
@@ -101,6 +107,16 @@ pub enum RenderPassCompatibilityError {
         actual: Option<NonZeroU32>,
         res: ResourceErrorIdent,
     },
+    #[error("The {res} reads color attachment {location} with framebuffer fetch, but the RenderPass has no color attachment there")]
+    MissingFetchedColorAttachment {
+        location: u32,
+        res: ResourceErrorIdent,
+    },
+    #[error("The {res} reads the {aspect:?} aspect with framebuffer fetch, but the RenderPass has no depth-stencil attachment with that aspect")]
+    MissingFetchedDepthStencilAspect {
+        aspect: wgt::TextureAspect,
+        res: ResourceErrorIdent,
+    },
 }
 
 impl WebGpuError for RenderPassCompatibilityError {
@@ -157,6 +173,55 @@ impl RenderPassContext {
         }
         Ok(())
     }
+
+    /// Checks that the render pass has every attachment `pipeline` reads with
+    /// framebuffer fetch.
+    ///
+    /// This is implied by [`Self::check_compatible`], but reports which
+    /// fetched attachment is missing.
+    pub(crate) fn check_framebuffer_fetches(
+        &self,
+        pipeline: &RenderPipeline,
+    ) -> Result<(), RenderPassCompatibilityError> {
+        for location in 0..hal::MAX_COLOR_ATTACHMENTS as u32 {
+            if pipeline.fetched_colors & (1 << location) != 0
+                && !matches!(
+                    self.attachments.colors.get(location as usize),
+                    Some(Some(_))
+                )
+            {
+                return Err(
+                    RenderPassCompatibilityError::MissingFetchedColorAttachment {
+                        location,
+                        res: pipeline.error_ident(),
+                    },
+                );
+            }
+        }
+        let depth_stencil = self.attachments.depth_stencil;
+        for (flag, aspect, present) in [
+            (
+                PipelineFlags::READS_DEPTH,
+                wgt::TextureAspect::DepthOnly,
+                depth_stencil.is_some_and(|format| format.has_depth_aspect()),
+            ),
+            (
+                PipelineFlags::READS_STENCIL,
+                wgt::TextureAspect::StencilOnly,
+                depth_stencil.is_some_and(|format| format.has_stencil_aspect()),
+            ),
+        ] {
+            if pipeline.flags.contains(flag) && !present {
+                return Err(
+                    RenderPassCompatibilityError::MissingFetchedDepthStencilAspect {
+                        aspect,
+                        res: pipeline.error_ident(),
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 pub type BufferMapPendingClosure = (BufferMapOperation, BufferAccessResult);
@@ -167,6 +232,7 @@ pub struct UserClosures {
     pub blas_compact_ready: Vec<BlasCompactReadyPendingClosure>,
     pub submissions: SmallVec<[queue::SubmittedWorkDoneClosure; 1]>,
     pub device_lost_invocations: SmallVec<[DeviceLostInvocation; 1]>,
+    pub memory_budget_invocations: SmallVec<[MemoryBudgetInvocation; 1]>,
 }
 
 impl UserClosures {
@@ -176,6 +242,8 @@ impl UserClosures {
         self.submissions.extend(other.submissions);
         self.device_lost_invocations
             .extend(other.device_lost_invocations);
+        self.memory_budget_invocations
+            .extend(other.memory_budget_invocations);
     }
 
     fn fire(self) {
@@ -200,6 +268,9 @@ impl UserClosures {
         for invocation in self.device_lost_invocations {
             (invocation.closure)(invocation.reason, invocation.message);
         }
+        for invocation in self.memory_budget_invocations {
+            (invocation.callback)(invocation.report);
+        }
     }
 }
 
@@ -214,6 +285,47 @@ pub struct DeviceLostInvocation {
     message: String,
 }
 
+#[cfg(send_sync)]
+pub type MemoryBudgetCallback = Arc<dyn Fn(wgt::MemoryBudgetReport) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type MemoryBudgetCallback = Arc<dyn Fn(wgt::MemoryBudgetReport) + 'static>;
+
+pub struct MemoryBudgetInvocation {
+    callback: MemoryBudgetCallback,
+    report: wgt::MemoryBudgetReport,
+}
+
+/// A [`MemoryBudgetCallback`] waiting for the usage of a memory heap to reach
+/// a percentage of its budget.
+pub(crate) struct MemoryBudgetWatch {
+    /// The percentage of the budget at which `callback` is called.
+    pub(crate) threshold: u8,
+    pub(crate) callback: MemoryBudgetCallback,
+    /// Whether each heap was at or above the threshold when last checked.
+    ///
+    /// The callback is only called when a heap reaches the threshold, not for
+    /// as long as it stays above it.
+    pub(crate) exceeded: Vec<bool>,
+}
+
+impl MemoryBudgetWatch {
+    /// Check `report` against the threshold, returning an invocation of the
+    /// callback if a heap has reached it since the last check.
+    fn check(&mut self, report: wgt::MemoryBudgetReport) -> Option<MemoryBudgetInvocation> {
+        self.exceeded.resize(report.heaps.len(), false);
+        let mut reached = false;
+        for (heap, exceeded) in report.heaps.iter().zip(self.exceeded.iter_mut()) {
+            let now_exceeded = heap.exceeds(self.threshold);
+            reached |= now_exceeded && !*exceeded;
+            *exceeded = now_exceeded;
+        }
+        reached.then(|| MemoryBudgetInvocation {
+            callback: Arc::clone(&self.callback),
+            report,
+        })
+    }
+}
+
 pub(crate) fn map_buffer(
     buffer: &Buffer,
     offset: BufferAddress,
@@ -510,6 +622,18 @@ pub fn create_validator(
         Caps::TEXTURE_EXTERNAL,
         features.intersects(wgt::Features::EXTERNAL_TEXTURE),
     );
+    caps.set(
+        Caps::FRAMEBUFFER_FETCH,
+        features.contains(wgt::Features::FRAMEBUFFER_FETCH),
+    );
+    caps.set(
+        Caps::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+        features.contains(wgt::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL),
+    );
+    caps.set(
+        Caps::SPARSE_RESIDENCY,
+        features.contains(wgt::Features::SPARSE_RESOURCES),
+    );
 
     naga::valid::Validator::new(flags, caps)
 }
@@ -0,0 +1,209 @@
+//! Background creation of pipelines requested with the `*_async` entry points.
+//!
+//! Pipelines requested with [`Global::device_create_render_pipeline_async`] and
+//! [`Global::device_create_compute_pipeline_async`] are created by a small pool
+//! of worker threads, so that shader translation and backend compilation don't
+//! block the calling thread. The pool is shared by all devices, but like
+//! buffer mapping callbacks, the callbacks of finished pipelines are invoked
+//! the next time their own device is polled.
+//!
+//! On targets without threads, the pipeline is created right away, but its
+//! callback is still deferred to the next poll.
+//!
+//! [`Global::device_create_render_pipeline_async`]: crate::global::Global::device_create_render_pipeline_async
+//! [`Global::device_create_compute_pipeline_async`]: crate::global::Global::device_create_compute_pipeline_async
+
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+// This lock is never held while calling into the rest of `wgpu-core`, so it
+// doesn't take part in lock ranking.
+use parking_lot::{Condvar, Mutex};
+
+use crate::{
+    id,
+    pipeline::{
+        ComputePipeline, CreateComputePipelineCallback, CreateComputePipelineError,
+        CreateRenderPipelineCallback, CreateRenderPipelineError, RenderPipeline,
+    },
+    FastHashMap,
+};
+
+/// The maximum number of worker threads creating pipelines.
+#[cfg(worker_threads)]
+const MAX_WORKER_THREADS: usize = 4;
+
+/// The outcome of an asynchronous pipeline creation, waiting to be registered
+/// and reported to the user.
+pub(crate) enum CompletedPipeline {
+    Render {
+        /// The ID reserved for the pipeline.
+        id: id::RenderPipelineId,
+        result: Result<Arc<RenderPipeline>, CreateRenderPipelineError>,
+        /// The label to use for the invalid pipeline if creation failed.
+        label: String,
+        callback: CreateRenderPipelineCallback,
+    },
+    Compute {
+        /// The ID reserved for the pipeline.
+        id: id::ComputePipelineId,
+        result: Result<Arc<ComputePipeline>, CreateComputePipelineError>,
+        /// The label to use for the invalid pipeline if creation failed.
+        label: String,
+        callback: CreateComputePipelineCallback,
+    },
+}
+
+#[cfg(send_sync)]
+pub(crate) type PipelineJob = Box<dyn FnOnce() -> CompletedPipeline + Send + 'static>;
+#[cfg(not(send_sync))]
+pub(crate) type PipelineJob = Box<dyn FnOnce() -> CompletedPipeline + 'static>;
+
+#[derive(Default)]
+struct State {
+    /// Jobs that no worker has picked up yet, with the device they are for.
+    #[cfg(worker_threads)]
+    jobs: alloc::collections::VecDeque<(id::DeviceId, PipelineJob)>,
+    /// Finished jobs, waiting for the next poll of their device.
+    completed: Vec<(id::DeviceId, CompletedPipeline)>,
+    /// The number of jobs that are queued or running, for each device that has any.
+    pending: FastHashMap<id::DeviceId, usize>,
+    /// The number of live worker threads.
+    #[cfg(worker_threads)]
+    threads: usize,
+    /// Set when the pool is dropped, telling workers to exit once idle.
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    /// Signalled when a job is queued, or on shutdown.
+    job_queued: Condvar,
+    /// Signalled when a job has finished.
+    job_finished: Condvar,
+}
+
+/// The pool of threads creating pipelines, and the pipelines they finished.
+#[derive(Default)]
+pub(crate) struct PipelineWorkers {
+    shared: Arc<Shared>,
+}
+
+impl PipelineWorkers {
+    /// Run `job` for `device_id` in the background. Its result is returned by a
+    /// later call to [`Self::take_completed`].
+    pub(crate) fn submit(&self, device_id: id::DeviceId, job: PipelineJob) {
+        #[cfg(worker_threads)]
+        {
+            let mut state = self.shared.state.lock();
+            state.jobs.push_back((device_id, job));
+            *state.pending.entry(device_id).or_default() += 1;
+            let pending = state.pending.values().sum::<usize>();
+            if state.threads < pending && state.threads < max_worker_threads() {
+                let shared = Arc::clone(&self.shared);
+                let spawned = std::thread::Builder::new()
+                    .name("wgpu pipeline worker".into())
+                    .spawn(move || work(&shared));
+                match spawned {
+                    Ok(_) => state.threads += 1,
+                    Err(e) => log::warn!("Failed to spawn a pipeline worker thread: {e}"),
+                }
+            }
+            if state.threads != 0 {
+                self.shared.job_queued.notify_one();
+                return;
+            }
+            // Without any worker to pick it up, run the job here.
+            let (_, job) = state.jobs.pop_back().unwrap();
+            drop(state);
+            let completed = job();
+            let mut state = self.shared.state.lock();
+            state.finish(device_id, completed);
+            self.shared.job_finished.notify_all();
+        }
+        #[cfg(not(worker_threads))]
+        self.push_completed(device_id, job());
+    }
+
+    /// Report an already finished pipeline creation on the next poll of
+    /// `device_id`.
+    pub(crate) fn push_completed(&self, device_id: id::DeviceId, completed: CompletedPipeline) {
+        self.shared
+            .state
+            .lock()
+            .completed
+            .push((device_id, completed));
+    }
+
+    /// Take the finished pipeline creations of `device_id`, or of all devices
+    /// if it is `None`.
+    ///
+    /// If `wait` is true, first block until the jobs submitted for those
+    /// devices have finished.
+    pub(crate) fn take_completed(
+        &self,
+        device_id: Option<id::DeviceId>,
+        wait: bool,
+    ) -> Vec<CompletedPipeline> {
+        let mut state = self.shared.state.lock();
+        if wait {
+            while match device_id {
+                Some(device_id) => state.pending.contains_key(&device_id),
+                None => !state.pending.is_empty(),
+            } {
+                self.shared.job_finished.wait(&mut state);
+            }
+        }
+        let (taken, kept) = core::mem::take(&mut state.completed)
+            .into_iter()
+            .partition(|&(id, _)| device_id.is_none_or(|device_id| id == device_id));
+        state.completed = kept;
+        taken.into_iter().map(|(_, completed)| completed).collect()
+    }
+}
+
+impl State {
+    /// Record that a job submitted for `device_id` finished.
+    #[cfg(worker_threads)]
+    fn finish(&mut self, device_id: id::DeviceId, completed: CompletedPipeline) {
+        let pending = self.pending.get_mut(&device_id).unwrap();
+        *pending -= 1;
+        if *pending == 0 {
+            self.pending.remove(&device_id);
+        }
+        self.completed.push((device_id, completed));
+    }
+}
+
+impl Drop for PipelineWorkers {
+    fn drop(&mut self) {
+        // Workers finish the queued jobs before exiting; their results are
+        // dropped along with `Shared`.
+        self.shared.state.lock().shutdown = true;
+        self.shared.job_queued.notify_all();
+    }
+}
+
+#[cfg(worker_threads)]
+fn max_worker_threads() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, core::num::NonZeroUsize::get)
+        .min(MAX_WORKER_THREADS)
+}
+
+#[cfg(worker_threads)]
+fn work(shared: &Shared) {
+    let mut state = shared.state.lock();
+    loop {
+        if let Some((device_id, job)) = state.jobs.pop_front() {
+            let completed = parking_lot::MutexGuard::unlocked(&mut state, job);
+            state.finish(device_id, completed);
+            shared.job_finished.notify_all();
+        } else if state.shutdown {
+            break;
+        } else {
+            shared.job_queued.wait(&mut state);
+        }
+    }
+    state.threads -= 1;
+}
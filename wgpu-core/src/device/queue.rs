@@ -4,6 +4,7 @@ use core::{
     mem::{self, ManuallyDrop},
     num::NonZeroU64,
     ptr::NonNull,
+    slice,
     sync::atomic::Ordering,
 };
 use smallvec::SmallVec;
@@ -19,9 +20,10 @@ use crate::device::trace::Action;
 use crate::{
     api_log,
     command::{
-        extract_texture_selector, validate_linear_texture_data, validate_texture_buffer_copy,
-        validate_texture_copy_range, ClearError, CommandAllocator, CommandBuffer, CommandEncoder,
-        CommandEncoderError, CopySide, TexelCopyTextureInfo, TransferError,
+        extract_texture_selector, validate_acceleration_structure_actions,
+        validate_linear_texture_data, validate_texture_buffer_copy, validate_texture_copy_range,
+        ClearError, CommandAllocator, CommandBuffer, CommandEncoder, CommandEncoderError, CopySide,
+        ReusableCommands, TexelCopyTextureInfo, TransferError,
     },
     conv,
     device::{DeviceError, WaitIdleError},
@@ -31,7 +33,7 @@ use crate::{
     id::{self, BlasId, QueueId},
     init_tracker::{has_copy_partial_init_tracker_coverage, TextureInitRange},
     lock::{rank, Mutex, MutexGuard, RwLock, RwLockWriteGuard},
-    ray_tracing::{BlasCompactReadyPendingClosure, CompactBlasError},
+    ray_tracing::{AsAction, BlasCompactReadyPendingClosure, CompactBlasError},
     resource::{
         Blas, BlasCompactState, Buffer, BufferAccessError, BufferMapState, DestroyedBuffer,
         DestroyedResourceError, DestroyedTexture, Fallible, FlushedStagingBuffer,
@@ -46,10 +48,42 @@ use crate::{
 };
 use crate::{device::resource::CommandIndices, resource::RawResourceAccess};
 
+/// Identifies a queue of a device other than the one it was created with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct QueueSlot {
+    pub(crate) queue_type: wgt::QueueType,
+    /// The index of the queue among the queues of `queue_type`.
+    pub(crate) index: u32,
+}
+
+/// A queue of a device.
+///
+/// All queues of a device share the states of its [`DeviceTracker`]: a
+/// submission waits for those in flight on other queues that use the same
+/// resources, so each resource is only ever in one state. The queue the device
+/// was created with makes the writes requested on all queues, and releases the
+/// resources that belong to the queues of one type at a time to the others, as
+/// described in the `track::ownership` module.
+///
+/// [`DeviceTracker`]: crate::track::DeviceTracker
 pub struct Queue {
     raw: Box<dyn hal::DynQueue>,
     pub(crate) pending_writes: Mutex<PendingWrites>,
+    /// The submissions in flight on all queues of the device, if this is the
+    /// queue it was created with. See [`Queue::lock_life`].
     life_tracker: Mutex<LifetimeTracker>,
+    /// The slot of this queue, if it is not the queue the device was created with.
+    ///
+    /// The submissions of such a queue signal the fence of its slot in
+    /// [`Device::queue_fences`] rather than [`Device::fence`].
+    pub(crate) slot: Option<QueueSlot>,
+    /// The queue the device was created with, if this is another one.
+    ///
+    /// It owns the [`LifetimeTracker`] of the submissions of all queues, and
+    /// makes the writes requested on this queue.
+    pub(crate) main: Option<Arc<Queue>>,
+    /// The index of the last successful submission made to this queue.
+    last_submission_index: hal::AtomicFenceValue,
     // The device needs to be dropped last (`Device.zero_buffer` might be referenced by the encoder in pending writes).
     pub(crate) device: Arc<Device>,
 }
@@ -62,7 +96,7 @@ impl Queue {
     ) -> Result<Self, DeviceError> {
         let pending_encoder = device
             .command_allocator
-            .acquire_encoder(device.raw(), raw.as_ref())
+            .acquire_encoder(device.raw(), raw.as_ref(), wgt::QueueType::Graphics)
             .map_err(DeviceError::from_hal);
 
         let pending_encoder = match pending_encoder {
@@ -99,22 +133,71 @@ impl Queue {
                     },
                 }]);
         }
+        if let Some(ref bounds_reporter) = device.bounds_reporter {
+            bounds_reporter.initialize(pending_writes.command_encoder.as_mut());
+        }
+
+        Ok(Queue {
+            raw,
+            device,
+            pending_writes: Mutex::new(rank::QUEUE_PENDING_WRITES, pending_writes),
+            life_tracker: Mutex::new(rank::QUEUE_LIFE_TRACKER, LifetimeTracker::new()),
+            slot: None,
+            main: None,
+            last_submission_index: hal::AtomicFenceValue::new(0),
+        })
+    }
+
+    /// Create a queue of the device of `main` other than the one it was created with.
+    ///
+    /// The fence of `slot` must have been inserted in [`Device::queue_fences`].
+    pub(crate) fn new_secondary(
+        main: Arc<Queue>,
+        raw: Box<dyn hal::DynQueue>,
+        slot: QueueSlot,
+    ) -> Result<Self, DeviceError> {
+        let device = main.device.clone();
+        let pending_encoder = device
+            .command_allocator
+            .acquire_encoder(device.raw(), raw.as_ref(), slot.queue_type)
+            .map_err(DeviceError::from_hal)?;
+
+        // Writes are made by the main queue, so the pending writes of this
+        // queue are never activated.
+        let pending_writes = PendingWrites::new(pending_encoder, device.instance_flags);
 
         Ok(Queue {
             raw,
             device,
             pending_writes: Mutex::new(rank::QUEUE_PENDING_WRITES, pending_writes),
             life_tracker: Mutex::new(rank::QUEUE_LIFE_TRACKER, LifetimeTracker::new()),
+            slot: Some(slot),
+            main: Some(main),
+            last_submission_index: hal::AtomicFenceValue::new(0),
         })
     }
 
+    /// The type of the queue.
+    pub(crate) fn queue_type(&self) -> wgt::QueueType {
+        self.slot
+            .map_or(wgt::QueueType::Graphics, |slot| slot.queue_type)
+    }
+
+    /// The index of the last successful submission made to this queue.
+    pub(crate) fn last_submission_index(&self) -> SubmissionIndex {
+        self.last_submission_index.load(Ordering::Acquire)
+    }
+
     pub(crate) fn raw(&self) -> &dyn hal::DynQueue {
         self.raw.as_ref()
     }
 
     #[track_caller]
     pub(crate) fn lock_life<'a>(&'a self) -> MutexGuard<'a, LifetimeTracker> {
-        self.life_tracker.lock()
+        match self.main {
+            Some(ref main) => main.lock_life(),
+            None => self.life_tracker.lock(),
+        }
     }
 
     pub(crate) fn maintain(
@@ -162,8 +245,14 @@ impl Drop for Queue {
             .device
             .last_successful_submission_index
             .load(Ordering::Acquire);
+        let last_submission_index = self.last_submission_index();
 
-        let fence = self.device.fence.read();
+        let main_fence = self.device.fence.read();
+        let mut queue_fences = self.device.queue_fences.lock();
+        let fence = match self.slot {
+            Some(slot) => queue_fences[&slot].as_ref(),
+            None => main_fence.as_ref(),
+        };
 
         // Try waiting on the last submission using the following sequence of timeouts
         let timeouts_in_ms = [100, 200, 400, 800, 1600, 3200];
@@ -180,8 +269,8 @@ impl Drop for Queue {
 
             let wait_res = unsafe {
                 self.device.raw().wait(
-                    fence.as_ref(),
-                    last_successful_submission_index,
+                    fence,
+                    last_submission_index,
                     #[cfg(not(target_arch = "wasm32"))]
                     timeout_ms,
                     #[cfg(target_arch = "wasm32")]
@@ -231,7 +320,16 @@ impl Drop for Queue {
                 },
             }
         }
-        drop(fence);
+
+        if let Some(slot) = self.slot {
+            // The submissions of this queue are done, so `Device::maintain`
+            // can consider those in flight on a queue without a fence done.
+            let fence = queue_fences.remove(&slot).unwrap();
+            unsafe { self.device.raw().destroy_fence(fence) };
+            return;
+        }
+        drop(queue_fences);
+        drop(main_fence);
 
         let snatch_guard = self.device.snatchable_lock.read();
         let (submission_closures, mapping_closures, blas_compact_ready_closures, queue_empty) =
@@ -245,6 +343,7 @@ impl Drop for Queue {
             blas_compact_ready: blas_compact_ready_closures,
             submissions: submission_closures,
             device_lost_invocations: SmallVec::new(),
+            memory_budget_invocations: SmallVec::new(),
         };
 
         closures.fire();
@@ -292,6 +391,40 @@ pub(crate) struct EncoderInFlight {
     pub(crate) pending_textures: FastHashMap<TrackerIndex, Arc<Texture>>,
     /// These are the BLASes that have been tracked by `PendingWrites`.
     pub(crate) pending_blas_s: FastHashMap<TrackerIndex, Arc<Blas>>,
+
+    /// The commands of the reusable command buffer submitted, if any.
+    ///
+    /// Its raw command buffers are submitted between the first raw command
+    /// buffer of `inner`, holding the transitions into the states they expect,
+    /// and the rest, and the resources they use are in its trackers rather
+    /// than in `trackers`.
+    pub(crate) reusable: Option<Arc<ReusableCommands>>,
+}
+
+impl EncoderInFlight {
+    /// The trackers of the resources used by the submitted commands.
+    pub(crate) fn used_resources(&self) -> &Tracker {
+        match self.reusable {
+            Some(ref reusable) => &reusable.trackers,
+            None => &self.trackers,
+        }
+    }
+
+    /// The raw command buffers to submit, in order.
+    fn raw_command_buffers(&self) -> impl Iterator<Item = &dyn hal::DynCommandBuffer> {
+        let (head, tail) = match self.reusable {
+            Some(_) => self.inner.list.split_at(1),
+            None => (self.inner.list.as_slice(), &[][..]),
+        };
+        let reusable = self
+            .reusable
+            .iter()
+            .flat_map(|reusable| reusable.encoder.list.iter());
+        head.iter()
+            .chain(reusable)
+            .chain(tail)
+            .map(|raw| raw.as_ref())
+    }
 }
 
 /// A private command encoder for writes made directly on the device
@@ -397,7 +530,7 @@ impl PendingWrites {
             self.is_recording = false;
 
             let new_encoder = command_allocator
-                .acquire_encoder(device.raw(), queue.raw())
+                .acquire_encoder(device.raw(), queue.raw(), queue.queue_type())
                 .map_err(|e| device.handle_hal_error(e))?;
 
             let encoder = EncoderInFlight {
@@ -407,6 +540,8 @@ impl PendingWrites {
                     device: device.clone(),
                     is_open: false,
                     label: "(wgpu internal) PendingWrites command encoder".into(),
+                    reusable: false,
+                    queue_type: queue.queue_type(),
                 },
                 trackers: Tracker::new(),
                 temp_resources: mem::take(&mut self.temp_resources),
@@ -416,6 +551,7 @@ impl PendingWrites {
                 pending_buffers,
                 pending_textures,
                 pending_blas_s,
+                reusable: None,
             };
             Ok(Some(encoder))
         } else {
@@ -452,6 +588,27 @@ impl Drop for PendingWrites {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateQueueError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Creating a {queue_type:?} queue would exceed the limit of {limit} such queues of the device")]
+    TooManyQueues {
+        queue_type: wgt::QueueType,
+        limit: u32,
+    },
+}
+
+impl WebGpuError for CreateQueueError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        match self {
+            Self::Device(e) => e.webgpu_error_type(),
+            Self::TooManyQueues { .. } => ErrorType::Validation,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum QueueWriteError {
@@ -497,6 +654,17 @@ pub enum QueueSubmitError {
     CommandEncoder(#[from] CommandEncoderError),
     #[error(transparent)]
     ValidateAsActionsError(#[from] crate::ray_tracing::ValidateAsActionsError),
+    #[error(transparent)]
+    MemoryInitFailure(#[from] ClearError),
+    #[error(
+        "Command buffers encoded for {encoded:?} queues can not be submitted to a {queue:?} queue"
+    )]
+    QueueTypeMismatch {
+        encoded: wgt::QueueType,
+        queue: wgt::QueueType,
+    },
+    #[error("Surface textures can not be used by submissions to {0:?} queues")]
+    SurfaceTextureOnQueue(wgt::QueueType),
 }
 
 impl WebGpuError for QueueSubmitError {
@@ -507,9 +675,107 @@ impl WebGpuError for QueueSubmitError {
             Self::CommandEncoder(e) => e,
             Self::ValidateAsActionsError(e) => e,
             Self::InvalidResource(e) => e,
-            Self::DestroyedResource(_) | Self::BufferStillMapped(_) => {
-                return ErrorType::Validation
-            }
+            Self::MemoryInitFailure(e) => e,
+            Self::DestroyedResource(_)
+            | Self::BufferStillMapped(_)
+            | Self::QueueTypeMismatch { .. }
+            | Self::SurfaceTextureOnQueue(_) => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum QueueBindSparseError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error(transparent)]
+    DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    Submit(#[from] QueueSubmitError),
+    #[error("{0} is not sparse")]
+    NotSparse(ResourceErrorIdent),
+    #[error(
+        "Range {start}..{end} of {buffer} is not aligned to pages of {} bytes",
+        wgt::SPARSE_PAGE_SIZE
+    )]
+    UnalignedBufferRange {
+        buffer: ResourceErrorIdent,
+        start: wgt::BufferAddress,
+        end: wgt::BufferAddress,
+    },
+    #[error("Range {start}..{end} is out of bounds of {buffer} of size {size}")]
+    BufferRangeOutOfBounds {
+        buffer: ResourceErrorIdent,
+        start: wgt::BufferAddress,
+        end: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
+    #[error("Mip level {mip_level} is out of bounds of {texture} with {count} mip levels")]
+    MipLevelOutOfBounds {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        count: u32,
+    },
+    #[error("Array layers {start}..{end} are out of bounds of {texture} with {count} layers")]
+    ArrayLayersOutOfBounds {
+        texture: ResourceErrorIdent,
+        start: u32,
+        end: u32,
+        count: u32,
+    },
+    #[error(
+        "Region at {origin:?} of size {size:?} is out of bounds of mip level {mip_level} \
+        of {texture} of size {mip_size:?}"
+    )]
+    TextureRegionOutOfBounds {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        mip_size: wgt::Extent3d,
+    },
+    #[error(
+        "Region at {origin:?} of size {size:?} of mip level {mip_level} of {texture} \
+        is not aligned to pages of {page_width}x{page_height} texels"
+    )]
+    UnalignedTextureRegion {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        page_width: u32,
+        page_height: u32,
+    },
+    #[error(
+        "Mip level {mip_level} of {texture} is in the mip tail starting at level {tail_start}, \
+        which is bound for whole array layers"
+    )]
+    PartialMipTail {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        tail_start: u32,
+    },
+}
+
+impl WebGpuError for QueueBindSparseError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::DestroyedResource(e) => e,
+            Self::Submit(e) => e,
+            Self::NotSparse(_)
+            | Self::UnalignedBufferRange { .. }
+            | Self::BufferRangeOutOfBounds { .. }
+            | Self::MipLevelOutOfBounds { .. }
+            | Self::ArrayLayersOutOfBounds { .. }
+            | Self::TextureRegionOutOfBounds { .. }
+            | Self::UnalignedTextureRegion { .. }
+            | Self::PartialMipTail { .. } => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -527,6 +793,11 @@ impl Queue {
         profiling::scope!("Queue::write_buffer");
         api_log!("Queue::write_buffer");
 
+        // Writes are made by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.write_buffer(buffer, buffer_offset, data);
+        }
+
         self.device.check_is_valid()?;
 
         let buffer = buffer.get()?;
@@ -591,6 +862,11 @@ impl Queue {
     ) -> Result<(), QueueWriteError> {
         profiling::scope!("Queue::write_staging_buffer");
 
+        // Writes are made by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.write_staging_buffer(buffer, buffer_offset, staging_buffer);
+        }
+
         self.device.check_is_valid()?;
 
         let buffer = buffer.get()?;
@@ -668,8 +944,11 @@ impl Queue {
     ) -> Result<(), QueueWriteError> {
         self.device.check_is_valid()?;
 
+        // The buffer may have to come back from the queues of another type.
+        let mut returned = track::ReturnedResources::default();
         let transition = {
             let mut trackers = self.device.trackers.lock();
+            trackers.acquire_buffer_for_graphics(&buffer, &mut returned);
             trackers
                 .buffers
                 .set_single(&buffer, wgt::BufferUses::COPY_DST)
@@ -683,7 +962,7 @@ impl Queue {
 
         let region = hal::BufferCopy {
             src_offset: 0,
-            dst_offset: buffer_offset,
+            dst_offset: buffer.raw_offset + buffer_offset,
             size: staging_buffer.size,
         };
         let barriers = iter::once(hal::BufferBarrier {
@@ -697,6 +976,7 @@ impl Queue {
         .collect::<Vec<_>>();
         let encoder = pending_writes.activate();
         unsafe {
+            returned.acquire(encoder, snatch_guard);
             encoder.transition_buffers(&barriers);
             encoder.copy_buffer_to_buffer(staging_buffer.raw(), dst_raw, &[region]);
         }
@@ -725,6 +1005,11 @@ impl Queue {
         profiling::scope!("Queue::write_texture");
         api_log!("Queue::write_texture");
 
+        // Writes are made by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.write_texture(destination, data, data_layout, size);
+        }
+
         self.device.check_is_valid()?;
 
         let dst = destination.texture.get()?;
@@ -793,6 +1078,16 @@ impl Queue {
         let mut pending_writes = self.pending_writes.lock();
         let encoder = pending_writes.activate();
 
+        // The texture may have to come back from the queues of another type.
+        {
+            let mut returned = track::ReturnedResources::default();
+            self.device
+                .trackers
+                .lock()
+                .acquire_texture_for_graphics(&dst, &mut returned);
+            unsafe { returned.acquire(encoder, &snatch_guard) };
+        }
+
         // If the copy does not fully cover the layers, we need to initialize to
         // zero *first* as we don't keep track of partial texture layer inits.
         //
@@ -822,6 +1117,7 @@ impl Queue {
                             layer_range,
                         },
                         encoder,
+                        wgt::QueueType::Graphics,
                         &mut trackers.textures,
                         &self.device.alignments,
                         self.device.zero_buffer.as_ref(),
@@ -953,6 +1249,11 @@ impl Queue {
     ) -> Result<(), QueueWriteError> {
         profiling::scope!("Queue::copy_external_image_to_texture");
 
+        // Writes are made by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.copy_external_image_to_texture(source, destination, size);
+        }
+
         self.device.check_is_valid()?;
 
         if size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0 {
@@ -1046,6 +1347,16 @@ impl Queue {
         let mut pending_writes = self.pending_writes.lock();
         let encoder = pending_writes.activate();
 
+        // The texture may have to come back from the queues of another type.
+        {
+            let mut returned = track::ReturnedResources::default();
+            self.device
+                .trackers
+                .lock()
+                .acquire_texture_for_graphics(&dst, &mut returned);
+            unsafe { returned.acquire(encoder, &self.device.snatchable_lock.read()) };
+        }
+
         // If the copy does not fully cover the layers, we need to initialize to
         // zero *first* as we don't keep track of partial texture layer inits.
         //
@@ -1075,6 +1386,7 @@ impl Queue {
                             layer_range,
                         },
                         encoder,
+                        wgt::QueueType::Graphics,
                         &mut trackers.textures,
                         &self.device.alignments,
                         self.device.zero_buffer.as_ref(),
@@ -1148,7 +1460,18 @@ impl Queue {
         profiling::scope!("Queue::submit");
         api_log!("Queue::submit");
 
+        // The writes requested on this queue were made by the main queue, and
+        // must be submitted before the commands that follow them.
+        if let Some(ref main) = self.main {
+            let is_recording = main.pending_writes.lock().is_recording;
+            if is_recording {
+                main.submit(&[])?;
+            }
+        }
+
         let submit_index;
+        // The submission of the main queue releasing resources to this one.
+        let mut main_submission = None;
 
         let res = 'error: {
             let snatch_guard = self.device.snatchable_lock.read();
@@ -1168,6 +1491,14 @@ impl Queue {
 
             let mut used_surface_textures = track::TextureUsageScope::default();
 
+            // The pending writes of the main queue are locked until it
+            // released the resources this queue takes, so that no write comes
+            // in between.
+            let mut main_pending_writes = self.main.as_ref().map(|main| main.pending_writes.lock());
+            let mut taken = track::TakenResources::default();
+            let mut returned = track::ReturnedResources::default();
+            let mut moved = track::OwnershipTransfer::default();
+
             // Use a hashmap here to deduplicate the surface textures that are used in the command buffers.
             // This avoids vulkan deadlocking from the same surface texture being submitted multiple times.
             let mut submit_surface_textures_owned = FastHashMap::default();
@@ -1190,46 +1521,96 @@ impl Queue {
                         // it, so make sure to set_size on it.
                         used_surface_textures.set_size(self.device.tracker_indices.textures.size());
 
-                        // Note that we are required to invalidate all command buffers in both the success and failure paths.
-                        // This is why we `continue` and don't early return via `?`.
-                        #[allow(unused_mut)]
-                        let mut cmd_buf_data = command_buffer.take_finished();
-
-                        #[cfg(feature = "trace")]
-                        if let Some(ref mut trace) = *self.device.trace.lock() {
-                            if let Ok(ref mut cmd_buf_data) = cmd_buf_data {
-                                trace.add(Action::Submit(
-                                    submit_index,
-                                    cmd_buf_data.commands.take().unwrap(),
-                                ));
+                        let (mut baked, reusable) = if let Some(ref reusable) =
+                            command_buffer.reusable
+                        {
+                            #[cfg(feature = "trace")]
+                            if let Some(ref mut trace) = *self.device.trace.lock() {
+                                if let Some(ref commands) = reusable.commands {
+                                    trace.add(Action::Submit(submit_index, commands.clone()));
+                                }
                             }
-                        }
 
-                        if first_error.is_some() {
-                            continue;
-                        }
+                            if first_error.is_some() {
+                                continue;
+                            }
 
-                        let mut baked = match cmd_buf_data {
-                            Ok(cmd_buf_data) => {
-                                let res = validate_command_buffer(
-                                    command_buffer,
-                                    self,
-                                    &cmd_buf_data,
-                                    &snatch_guard,
-                                    &mut submit_surface_textures_owned,
-                                    &mut used_surface_textures,
-                                    &mut command_index_guard,
-                                );
-                                if let Err(err) = res {
-                                    first_error.get_or_insert(err);
-                                    continue;
+                            if let Err(err) = self.validate_queue_type(reusable.encoder.queue_type)
+                            {
+                                first_error.get_or_insert(err);
+                                continue;
+                            }
+
+                            // The commands were validated when the command buffer was
+                            // finished, but the resources they use may have changed since.
+                            let res = validate_command_buffer(
+                                command_buffer,
+                                self,
+                                &reusable.trackers,
+                                &reusable.as_actions,
+                                &snatch_guard,
+                                &mut submit_surface_textures_owned,
+                                &mut used_surface_textures,
+                                &mut command_index_guard,
+                            );
+                            if let Err(err) = res {
+                                first_error.get_or_insert(err);
+                                continue;
+                            }
+                            match reusable.bake(self) {
+                                Ok(baked) => (baked, Some(reusable.clone())),
+                                Err(e) => break 'error Err(e.into()),
+                            }
+                        } else {
+                            // Note that we are required to invalidate all command buffers in both the success and failure paths.
+                            // This is why we `continue` and don't early return via `?`.
+                            #[allow(unused_mut)]
+                            let mut cmd_buf_data = command_buffer.take_finished();
+
+                            // Command buffers recorded by `wgpu-core` itself have no
+                            // commands to trace.
+                            #[cfg(feature = "trace")]
+                            if let Some(ref mut trace) = *self.device.trace.lock() {
+                                if let Ok(ref mut cmd_buf_data) = cmd_buf_data {
+                                    if let Some(commands) = cmd_buf_data.commands.take() {
+                                        trace.add(Action::Submit(submit_index, commands));
+                                    }
                                 }
-                                cmd_buf_data.into_baked_commands()
                             }
-                            Err(err) => {
-                                first_error.get_or_insert(err.into());
+
+                            if first_error.is_some() {
                                 continue;
                             }
+
+                            match cmd_buf_data {
+                                Ok(cmd_buf_data) => {
+                                    if let Err(err) =
+                                        self.validate_queue_type(cmd_buf_data.encoder.queue_type)
+                                    {
+                                        first_error.get_or_insert(err);
+                                        continue;
+                                    }
+                                    let res = validate_command_buffer(
+                                        command_buffer,
+                                        self,
+                                        &cmd_buf_data.trackers,
+                                        &cmd_buf_data.as_actions,
+                                        &snatch_guard,
+                                        &mut submit_surface_textures_owned,
+                                        &mut used_surface_textures,
+                                        &mut command_index_guard,
+                                    );
+                                    if let Err(err) = res {
+                                        first_error.get_or_insert(err);
+                                        continue;
+                                    }
+                                    (cmd_buf_data.into_baked_commands(), None)
+                                }
+                                Err(err) => {
+                                    first_error.get_or_insert(err.into());
+                                    continue;
+                                }
+                            }
                         };
 
                         // execute resource transitions
@@ -1242,6 +1623,33 @@ impl Queue {
 
                         //Note: locking the trackers has to be done after the storages
                         let mut trackers = self.device.trackers.lock();
+
+                        // Take ownership of the resources before anything uses them.
+                        let tracker = reusable
+                            .as_ref()
+                            .map_or(&baked.trackers, |reusable| &reusable.trackers);
+                        if self.main.is_some() {
+                            let mut moving = track::OwnershipTransfer::default();
+                            trackers.take_from_graphics(
+                                tracker,
+                                &mut taken,
+                                &mut returned,
+                                &mut moving,
+                            );
+                            unsafe {
+                                moving.acquire(
+                                    baked.encoder.raw.as_mut(),
+                                    wgt::QueueType::Graphics,
+                                    &snatch_guard,
+                                )
+                            };
+                            moved.append(&mut moving);
+                        } else {
+                            let mut returned = track::ReturnedResources::default();
+                            trackers.acquire_for_graphics(tracker, &mut returned);
+                            unsafe { returned.acquire(baked.encoder.raw.as_mut(), &snatch_guard) };
+                        }
+
                         if let Err(e) = baked.initialize_buffer_memory(&mut trackers, &snatch_guard)
                         {
                             break 'error Err(e.into());
@@ -1251,7 +1659,10 @@ impl Queue {
                             &self.device,
                             &snatch_guard,
                         ) {
-                            break 'error Err(e.into());
+                            break 'error Err(match e {
+                                ClearError::DestroyedResource(e) => e.into(),
+                                e => e.into(),
+                            });
                         }
 
                         //Note: stateless trackers are not merged:
@@ -1259,7 +1670,9 @@ impl Queue {
                         CommandEncoder::insert_barriers_from_device_tracker(
                             baked.encoder.raw.as_mut(),
                             &mut trackers,
-                            &baked.trackers,
+                            reusable
+                                .as_ref()
+                                .map_or(&baked.trackers, |reusable| &reusable.trackers),
                             &snatch_guard,
                         );
 
@@ -1303,12 +1716,130 @@ impl Queue {
                             pending_buffers: FastHashMap::default(),
                             pending_textures: FastHashMap::default(),
                             pending_blas_s: FastHashMap::default(),
+                            reusable,
                         });
                     }
 
                     if let Some(first_error) = first_error {
                         break 'error Err(first_error);
                     }
+
+                    // Read the out-of-bounds accesses of the submission back
+                    // once it is done.
+                    if let (Some(bounds_reporter), Some(last)) = (
+                        self.device.bounds_reporter.as_ref(),
+                        active_executions.last_mut(),
+                    ) {
+                        if let Err(e) = last.inner.open_pass(hal_label(
+                            Some("(wgpu internal) Shader bounds report"),
+                            self.device.instance_flags,
+                        )) {
+                            break 'error Err(e.into());
+                        }
+                        if let Err(e) = bounds_reporter.encode_readback(
+                            self.device.raw(),
+                            last.inner.raw.as_mut(),
+                            submit_index,
+                            self.device.instance_flags,
+                        ) {
+                            break 'error Err(e.into());
+                        }
+                        if let Err(e) = last.inner.close() {
+                            break 'error Err(e.into());
+                        }
+                    }
+
+                    // Give the resources this queue took back to the graphics queues.
+                    if let (false, Some(last)) = (taken.is_empty(), active_executions.last_mut()) {
+                        if let Err(e) = last.inner.open_pass(hal_label(
+                            Some("(wgpu internal) Release"),
+                            self.device.instance_flags,
+                        )) {
+                            break 'error Err(e.into());
+                        }
+                        let released = self
+                            .device
+                            .trackers
+                            .lock()
+                            .release_to_graphics(&taken, self.queue_type());
+                        unsafe {
+                            released.release(
+                                last.inner.raw.as_mut(),
+                                wgt::QueueType::Graphics,
+                                &snatch_guard,
+                            )
+                        };
+                        if let Err(e) = last.inner.close() {
+                            break 'error Err(e.into());
+                        }
+                    }
+                }
+            }
+
+            // Release the resources this queue takes from the graphics queues
+            // on the main queue, along with the writes requested since it was
+            // last submitted to.
+            if let (Some(main), Some(mut main_pending_writes)) =
+                (self.main.as_ref(), main_pending_writes.take())
+            {
+                if !moved.is_empty() {
+                    let encoder = main_pending_writes.activate();
+                    unsafe {
+                        returned.acquire(encoder, &snatch_guard);
+                        moved.release(encoder, self.queue_type(), &snatch_guard);
+                    }
+                }
+                let execution = match main_pending_writes.pre_submit(
+                    &self.device.command_allocator,
+                    &self.device,
+                    main,
+                ) {
+                    Ok(execution) => execution,
+                    Err(e) => break 'error Err(e.into()),
+                };
+                if let Some(execution) = execution {
+                    // The queues that released the resources to the graphics
+                    // queues must be done with them.
+                    let (buffers, textures) = moved.resources();
+                    let mut dependencies = self
+                        .lock_life()
+                        .resource_dependencies(None, &buffers, &textures);
+                    dependencies.extend(
+                        self.lock_life()
+                            .cross_queue_dependencies(None, slice::from_ref(&execution)),
+                    );
+                    let queue_fences = self.device.queue_fences.lock();
+                    if let Err(e) = wait_for_dependencies(
+                        &self.device,
+                        main.raw(),
+                        fence.as_ref(),
+                        &queue_fences,
+                        dependencies,
+                    ) {
+                        break 'error Err(e.into());
+                    }
+                    drop(queue_fences);
+
+                    command_index_guard.active_submission_index += 1;
+                    let release_index = command_index_guard.active_submission_index;
+                    let hal_command_buffers = execution.raw_command_buffers().collect::<Vec<_>>();
+                    if let Err(e) = unsafe {
+                        main.raw().submit(
+                            &hal_command_buffers,
+                            &[],
+                            (fence.as_mut(), release_index),
+                        )
+                    }
+                    .map_err(|e| self.device.handle_hal_error(e))
+                    {
+                        break 'error Err(e.into());
+                    }
+                    self.device
+                        .last_successful_submission_index
+                        .fetch_max(release_index, Ordering::SeqCst);
+                    main.last_submission_index
+                        .fetch_max(release_index, Ordering::SeqCst);
+                    main_submission = Some((release_index, execution));
                 }
             }
 
@@ -1363,9 +1894,37 @@ impl Queue {
                 Ok(None) => {}
                 Err(e) => break 'error Err(e.into()),
             }
+
+            if self.slot.is_some() && !submit_surface_textures_owned.is_empty() {
+                break 'error Err(QueueSubmitError::SurfaceTextureOnQueue(self.queue_type()));
+            }
+
+            // Wait for the submissions in flight on other queues that use the
+            // same resources.
+            let mut dependencies = self
+                .lock_life()
+                .cross_queue_dependencies(self.slot, &active_executions);
+            // The submission of the main queue releasing resources to this one
+            // comes after all its others.
+            if let Some(&(release_index, _)) = main_submission.as_ref() {
+                dependencies.retain(|&mut (slot, _)| slot.is_some());
+                dependencies.push((None, release_index));
+            }
+
+            let mut queue_fences = self.device.queue_fences.lock();
+            if let Err(e) = wait_for_dependencies(
+                &self.device,
+                self.raw(),
+                fence.as_ref(),
+                &queue_fences,
+                dependencies,
+            ) {
+                break 'error Err(e.into());
+            }
+
             let hal_command_buffers = active_executions
                 .iter()
-                .flat_map(|e| e.inner.list.iter().map(|b| b.as_ref()))
+                .flat_map(|e| e.raw_command_buffers())
                 .collect::<Vec<_>>();
 
             {
@@ -1382,17 +1941,22 @@ impl Queue {
                     submit_surface_textures.push(raw);
                 }
 
+                let signal_fence = match self.slot {
+                    Some(slot) => queue_fences.get_mut(&slot).unwrap().as_mut(),
+                    None => fence.as_mut(),
+                };
                 if let Err(e) = unsafe {
                     self.raw().submit(
                         &hal_command_buffers,
                         &submit_surface_textures,
-                        (fence.as_mut(), submit_index),
+                        (signal_fence, submit_index),
                     )
                 }
                 .map_err(|e| self.device.handle_hal_error(e))
                 {
                     break 'error Err(e.into());
                 }
+                drop(queue_fences);
 
                 drop(command_index_guard);
 
@@ -1400,13 +1964,20 @@ impl Queue {
                 self.device
                     .last_successful_submission_index
                     .fetch_max(submit_index, Ordering::SeqCst);
+                self.last_submission_index
+                    .fetch_max(submit_index, Ordering::SeqCst);
             }
 
             profiling::scope!("cleanup");
 
             // this will register the new submission to the life time tracker
-            self.lock_life()
-                .track_submission(submit_index, active_executions);
+            {
+                let mut life_tracker = self.lock_life();
+                life_tracker.track_submission(submit_index, self.slot, active_executions);
+                if let Some((release_index, execution)) = main_submission.take() {
+                    life_tracker.track_submission(release_index, None, vec![execution]);
+                }
+            }
             drop(pending_writes);
 
             // This will schedule destruction of all resources that are no longer needed
@@ -1432,6 +2003,13 @@ impl Queue {
             Ok(closures)
         };
 
+        // The main queue may have released resources to this one before the
+        // submission failed.
+        if let Some((release_index, execution)) = main_submission {
+            self.lock_life()
+                .track_submission(release_index, None, vec![execution]);
+        }
+
         let callbacks = match res {
             Ok(ok) => ok,
             Err(e) => return Err((submit_index, e)),
@@ -1447,6 +2025,18 @@ impl Queue {
         Ok(submit_index)
     }
 
+    /// Check that command buffers encoded for queues of `queue_type` can be
+    /// submitted to this queue.
+    fn validate_queue_type(&self, queue_type: wgt::QueueType) -> Result<(), QueueSubmitError> {
+        if queue_type != self.queue_type() {
+            return Err(QueueSubmitError::QueueTypeMismatch {
+                encoded: queue_type,
+                queue: self.queue_type(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn get_timestamp_period(&self) -> f32 {
         unsafe { self.raw().get_timestamp_period() }
     }
@@ -1465,6 +2055,11 @@ impl Queue {
         profiling::scope!("Queue::compact_blas");
         api_log!("Queue::compact_blas");
 
+        // Writes are made by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.compact_blas(blas);
+        }
+
         self.device.check_is_valid()?;
         self.same_device_as(blas.as_ref())?;
 
@@ -1542,6 +2137,39 @@ impl Queue {
 }
 
 impl Global {
+    /// Create a command encoder whose command buffer can be submitted to
+    /// queues of the type of `queue_id`.
+    pub fn queue_create_command_encoder(
+        &self,
+        queue_id: QueueId,
+        desc: &wgt::CommandEncoderDescriptor<crate::Label>,
+        id_in: Option<id::CommandEncoderId>,
+    ) -> (id::CommandEncoderId, Option<DeviceError>) {
+        profiling::scope!("Queue::create_command_encoder");
+
+        let hub = &self.hub;
+        let fid = hub.command_encoders.prepare(id_in);
+
+        let queue = hub.queues.get(queue_id);
+        let device = &queue.device;
+
+        let error = match device.create_command_encoder(&queue, &desc.label, false) {
+            Ok(cmd_enc) => {
+                let id = fid.assign(cmd_enc);
+                api_log!("Queue::create_command_encoder -> {id:?}");
+                return (id, None);
+            }
+            Err(e) => e,
+        };
+
+        let id = fid.assign(Arc::new(CommandEncoder::new_invalid(
+            device,
+            &desc.label,
+            error.clone().into(),
+        )));
+        (id, Some(error))
+    }
+
     pub fn queue_write_buffer(
         &self,
         queue_id: QueueId,
@@ -1671,6 +2299,22 @@ impl Global {
         queue.submit(&command_buffers)
     }
 
+    /// Take the out-of-bounds shader accesses reported by the device of this
+    /// queue since the last call.
+    ///
+    /// See [`Global::device_take_shader_out_of_bounds_errors`].
+    pub fn queue_take_shader_out_of_bounds_errors(
+        &self,
+        queue_id: QueueId,
+    ) -> Vec<super::bounds_report::ShaderOutOfBoundsError> {
+        let queue = self.hub.queues.get(queue_id);
+        queue
+            .device
+            .bounds_reporter
+            .as_ref()
+            .map_or_else(Vec::new, |reporter| reporter.take_errors())
+    }
+
     pub fn queue_get_timestamp_period(&self, queue_id: QueueId) -> f32 {
         let queue = self.hub.queues.get(queue_id);
 
@@ -1747,7 +2391,8 @@ impl Global {
 fn validate_command_buffer(
     command_buffer: &CommandBuffer,
     queue: &Queue,
-    cmd_buf_data: &crate::command::CommandBufferMutable,
+    trackers: &Tracker,
+    as_actions: &[AsAction],
     snatch_guard: &SnatchGuard,
     submit_surface_textures_owned: &mut FastHashMap<*const Texture, Arc<Texture>>,
     used_surface_textures: &mut track::TextureUsageScope,
@@ -1760,7 +2405,7 @@ fn validate_command_buffer(
 
         {
             profiling::scope!("buffers");
-            for buffer in cmd_buf_data.trackers.buffers.used_resources() {
+            for buffer in trackers.buffers.used_resources() {
                 buffer.check_destroyed(snatch_guard)?;
 
                 match *buffer.map_state.lock() {
@@ -1771,7 +2416,7 @@ fn validate_command_buffer(
         }
         {
             profiling::scope!("textures");
-            for texture in cmd_buf_data.trackers.textures.used_resources() {
+            for texture in trackers.textures.used_resources() {
                 let should_extend = match texture.try_inner(snatch_guard)? {
                     TextureInner::Native { .. } => false,
                     TextureInner::Surface { .. } => {
@@ -1792,10 +2437,34 @@ fn validate_command_buffer(
         }
 
         if let Err(e) =
-            cmd_buf_data.validate_acceleration_structure_actions(snatch_guard, command_index_guard)
+            validate_acceleration_structure_actions(as_actions, snatch_guard, command_index_guard)
         {
             return Err(e.into());
         }
     }
     Ok(())
 }
+
+/// Make the next submission to `raw` wait for each of `dependencies`, given as
+/// the slot of the queue it was made to and its index.
+fn wait_for_dependencies(
+    device: &Device,
+    raw: &dyn hal::DynQueue,
+    main_fence: &dyn hal::DynFence,
+    queue_fences: &FastHashMap<QueueSlot, Box<dyn hal::DynFence>>,
+    dependencies: impl IntoIterator<Item = (Option<QueueSlot>, SubmissionIndex)>,
+) -> Result<(), DeviceError> {
+    for (slot, index) in dependencies {
+        let dependency_fence = match slot {
+            Some(slot) => match queue_fences.get(&slot) {
+                Some(fence) => fence.as_ref(),
+                // The queue is gone, so its submissions have completed.
+                None => continue,
+            },
+            None => main_fence,
+        };
+        unsafe { raw.wait_for_fence(dependency_fence, index) }
+            .map_err(|e| device.handle_hal_error(e))?;
+    }
+    Ok(())
+}
@@ -27,9 +27,9 @@ use crate::{
     binding_model::{self, BindGroup, BindGroupLayout, BindGroupLayoutEntryError},
     command, conv,
     device::{
-        bgl, create_validator, life::WaitIdleError, map_buffer, AttachmentData,
-        DeviceLostInvocation, HostMap, MissingDownlevelFlags, MissingFeatures, RenderPassContext,
-        CLEANUP_WAIT_MS,
+        bgl, bounds_report::BoundsReporter, create_validator, life::WaitIdleError, map_buffer,
+        suballocation::BufferSubAllocator, AttachmentData, DeviceLostInvocation, HostMap,
+        MissingDownlevelFlags, MissingFeatures, RenderPassContext, CLEANUP_WAIT_MS,
     },
     hal_label,
     init_tracker::{
@@ -41,7 +41,7 @@ use crate::{
     pipeline,
     pool::ResourcePool,
     resource::{
-        self, Buffer, ExternalTexture, Fallible, Labeled, ParentDevice, QuerySet,
+        self, Buffer, ExternalTexture, Fallible, Labeled, ParentDevice, QuerySet, RawBuffer,
         RawResourceAccess, Sampler, StagingBuffer, Texture, TextureView,
         TextureViewNotRenderableReason, Tlas, TrackingData,
     },
@@ -51,11 +51,12 @@ use crate::{
     track::{BindGroupStates, DeviceTracker, TrackerIndexAllocators, UsageScope, UsageScopePool},
     validation::{self, validate_color_attachment_bytes_per_sample},
     weak_vec::WeakVec,
-    FastHashMap, LabelHelpers, OnceCellOrLock,
+    FastHashMap, FastHashSet, LabelHelpers, OnceCellOrLock,
 };
 
 use super::{
-    queue::Queue, DeviceDescriptor, DeviceError, DeviceLostClosure, UserClosures,
+    queue::{CreateQueueError, Queue, QueueSlot},
+    DeviceDescriptor, DeviceError, DeviceLostClosure, MemoryBudgetWatch, UserClosures,
     ENTRYPOINT_FAILURE_ERROR, ZERO_BUFFER_SIZE,
 };
 
@@ -226,6 +227,13 @@ pub struct Device {
     // NOTE: if both are needed, the `snatchable_lock` must be consistently acquired before the
     // `fence` lock to avoid deadlocks.
     pub(crate) fence: RwLock<ManuallyDrop<Box<dyn hal::DynFence>>>,
+    /// The fences signaled by the submissions of the queues other than the
+    /// one the device was created with, by slot.
+    ///
+    /// Like `fence`, these are signaled with the index of each submission.
+    /// They are inserted when such a queue is created, and removed once its
+    /// submissions have completed when it is dropped.
+    pub(crate) queue_fences: Mutex<FastHashMap<QueueSlot, Box<dyn hal::DynFence>>>,
     pub(crate) snatchable_lock: SnatchLock,
 
     /// Is this device valid? Valid is closely associated with "lose the device",
@@ -246,6 +254,10 @@ pub struct Device {
     /// has been destroyed and its queues are empty.
     pub(crate) device_lost_closure: Mutex<Option<DeviceLostClosure>>,
 
+    /// Callback to be called when the memory usage reaches a percentage of the
+    /// budget. Checked by `maintain`, only if set.
+    pub(crate) memory_budget_watch: Mutex<Option<MemoryBudgetWatch>>,
+
     /// Stores the state of buffers and textures.
     pub(crate) trackers: Mutex<DeviceTracker>,
     pub(crate) tracker_indices: TrackerIndexAllocators,
@@ -259,6 +271,9 @@ pub struct Device {
     pub(crate) deferred_destroy: Mutex<Vec<DeferredDestroy>>,
     pub(crate) usage_scopes: UsageScopePool,
     pub(crate) indirect_validation: Option<crate::indirect_validation::IndirectValidation>,
+    /// Translates the command streams of `execute_indirect`, if
+    /// [`wgt::Features::INDIRECT_COMMANDS`] is enabled.
+    pub(crate) indirect_commands: Option<crate::indirect_validation::Commands>,
     // Optional so that we can late-initialize this after the queue is created.
     pub(crate) timestamp_normalizer:
         OnceCellOrLock<crate::timestamp_normalization::TimestampNormalizer>,
@@ -267,6 +282,13 @@ pub struct Device {
     /// binding point will be rendered correctly. Intended to be used as the
     /// [`hal::ExternalTextureBinding::params`] field.
     pub(crate) default_external_texture_params_buffer: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    /// Packs small buffers into shared HAL buffers, if enabled with
+    /// [`wgt::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS`].
+    pub(crate) small_buffers: Option<BufferSubAllocator>,
+    /// Reports out-of-bounds accesses made by shaders, if enabled with
+    /// [`wgt::InstanceFlags::REPORT_SHADER_OUT_OF_BOUNDS`].
+    pub(crate) bounds_reporter: Option<BoundsReporter>,
+    pub(crate) counters: wgt::CoreCounters,
     // needs to be dropped last
     #[cfg(feature = "trace")]
     pub(crate) trace: Mutex<Option<trace::Trace>>,
@@ -303,9 +325,15 @@ impl Drop for Device {
         if let Some(indirect_validation) = self.indirect_validation.take() {
             indirect_validation.dispose(self.raw.as_ref());
         }
+        if let Some(indirect_commands) = self.indirect_commands.take() {
+            indirect_commands.dispose(self.raw.as_ref());
+        }
         if let Some(timestamp_normalizer) = self.timestamp_normalizer.take() {
             timestamp_normalizer.dispose(self.raw.as_ref());
         }
+        if let Some(bounds_reporter) = self.bounds_reporter.take() {
+            bounds_reporter.dispose(self.raw.as_ref());
+        }
         unsafe {
             self.raw.destroy_buffer(zero_buffer);
             self.raw
@@ -354,9 +382,13 @@ impl Device {
             }
         };
         #[cfg(feature = "trace")]
-        let trace_dir_name: Option<&std::path::PathBuf> = match &desc.trace {
+        let trace_dir_name: Option<(&std::path::PathBuf, bool, bool)> = match &desc.trace {
             wgt::Trace::Off => None,
-            wgt::Trace::Directory(d) => Some(d),
+            wgt::Trace::Directory(d) => Some((d, false, false)),
+            wgt::Trace::Binary(d) => Some((d, true, false)),
+            wgt::Trace::Options(options) => {
+                Some((&options.path, options.binary, options.record_outputs))
+            }
             // The enum is non_exhaustive, so we must have a fallback arm (that should be
             // unreachable in practice).
             t => {
@@ -422,6 +454,64 @@ impl Device {
             None
         };
 
+        let indirect_commands = if desc
+            .required_features
+            .contains(wgt::Features::INDIRECT_COMMANDS)
+        {
+            Some(crate::indirect_validation::Commands::new(
+                raw_device.as_ref(),
+                &desc.required_features,
+                adapter.backend(),
+            )?)
+        } else {
+            None
+        };
+
+        let bounds_reporter =
+            if instance_flags.contains(wgt::InstanceFlags::REPORT_SHADER_OUT_OF_BOUNDS) {
+                // The bounds report takes a bind group and a storage buffer binding
+                // in each stage beyond the device limits.
+                let adapter_limits = &adapter.raw.capabilities.limits;
+                let mut stages = wgt::ShaderStages::empty();
+                stages.set(
+                    wgt::ShaderStages::COMPUTE,
+                    downlevel
+                        .flags
+                        .contains(wgt::DownlevelFlags::COMPUTE_SHADERS),
+                );
+                stages.set(
+                    wgt::ShaderStages::FRAGMENT,
+                    downlevel
+                        .flags
+                        .contains(wgt::DownlevelFlags::FRAGMENT_WRITABLE_STORAGE),
+                );
+                stages.set(
+                    wgt::ShaderStages::VERTEX,
+                    desc.required_features
+                        .contains(wgt::Features::VERTEX_WRITABLE_STORAGE),
+                );
+                if desc.required_limits.max_bind_groups >= adapter_limits.max_bind_groups
+                    || desc.required_limits.max_storage_buffers_per_shader_stage
+                        >= adapter_limits.max_storage_buffers_per_shader_stage
+                    || stages.is_empty()
+                {
+                    log::warn!(
+                        "Shader out-of-bounds accesses can't be reported: the device limits leave \
+                    no room for the bounds report, or no shader stage can write to storage buffers"
+                    );
+                    None
+                } else {
+                    Some(BoundsReporter::new(
+                        raw_device.as_ref(),
+                        desc.required_limits.max_bind_groups,
+                        stages,
+                        instance_flags,
+                    )?)
+                }
+            } else {
+                None
+            };
+
         Ok(Self {
             raw: raw_device,
             adapter: adapter.clone(),
@@ -442,29 +532,39 @@ impl Device {
             ),
             last_successful_submission_index: AtomicU64::new(0),
             fence: RwLock::new(rank::DEVICE_FENCE, ManuallyDrop::new(fence)),
+            queue_fences: Mutex::new(rank::DEVICE_QUEUE_FENCES, FastHashMap::default()),
             snatchable_lock: unsafe { SnatchLock::new(rank::DEVICE_SNATCHABLE_LOCK) },
             valid: AtomicBool::new(true),
             device_lost_closure: Mutex::new(rank::DEVICE_LOST_CLOSURE, None),
+            memory_budget_watch: Mutex::new(rank::DEVICE_MEMORY_BUDGET_WATCH, None),
             trackers: Mutex::new(rank::DEVICE_TRACKERS, DeviceTracker::new()),
             tracker_indices: TrackerIndexAllocators::new(),
             bgl_pool: ResourcePool::new(),
             #[cfg(feature = "trace")]
             trace: Mutex::new(
                 rank::DEVICE_TRACE,
-                trace_dir_name.and_then(|path| match trace::Trace::new(path.clone()) {
-                    Ok(mut trace) => {
-                        trace.add(trace::Action::Init {
-                            desc: wgt::DeviceDescriptor {
-                                trace: wgt::Trace::Off,
-                                ..desc.clone()
-                            },
-                            backend: adapter.backend(),
-                        });
-                        Some(trace)
-                    }
-                    Err(e) => {
-                        log::error!("Unable to start a trace in '{path:?}': {e}");
-                        None
+                trace_dir_name.and_then(|(path, binary, record_outputs)| {
+                    let trace = if binary {
+                        trace::Trace::new_binary(path.clone(), record_outputs)
+                            .map_err(|e| e.to_string())
+                    } else {
+                        trace::Trace::new(path.clone(), record_outputs).map_err(|e| e.to_string())
+                    };
+                    match trace {
+                        Ok(mut trace) => {
+                            trace.add(trace::Action::Init {
+                                desc: wgt::DeviceDescriptor {
+                                    trace: wgt::Trace::Off,
+                                    ..desc.clone()
+                                },
+                                backend: adapter.backend(),
+                            });
+                            Some(trace)
+                        }
+                        Err(e) => {
+                            log::error!("Unable to start a trace in '{path:?}': {e}");
+                            None
+                        }
                     }
                 }),
             ),
@@ -474,9 +574,15 @@ impl Device {
             downlevel,
             instance_flags,
             deferred_destroy: Mutex::new(rank::DEVICE_DEFERRED_DESTROY, Vec::new()),
+            small_buffers: (instance_flags.contains(wgt::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS)
+                && adapter.backend() != wgt::Backend::Dx12)
+                .then(BufferSubAllocator::new),
+            bounds_reporter,
+            counters: wgt::CoreCounters::default(),
             usage_scopes: Mutex::new(rank::DEVICE_USAGE_SCOPES, Default::default()),
             timestamp_normalizer: OnceCellOrLock::new(),
             indirect_validation,
+            indirect_commands,
         })
     }
 
@@ -683,6 +789,77 @@ impl Device {
         assert!(self.queue.set(Arc::downgrade(queue)).is_ok());
     }
 
+    /// Wait for the submissions up to `submission_index` to complete on all
+    /// queues, or for `timeout_ms` to pass.
+    ///
+    /// `fence` must be [`Device::fence`], locked by the caller. Returns
+    /// `Ok(false)` on timeout.
+    pub(crate) unsafe fn wait_for_submissions(
+        &self,
+        fence: &dyn hal::DynFence,
+        submission_index: crate::SubmissionIndex,
+        timeout_ms: u32,
+    ) -> Result<bool, hal::DeviceError> {
+        let Some(queue) = self.get_queue() else {
+            return unsafe { self.raw().wait(fence, submission_index, timeout_ms) };
+        };
+        let targets = queue.lock_life().wait_targets(submission_index);
+        let queue_fences = self.queue_fences.lock();
+        for (slot, index) in targets {
+            let fence = match slot {
+                Some(slot) => match queue_fences.get(&slot) {
+                    Some(fence) => fence.as_ref(),
+                    // The queue is gone, and its submissions have completed.
+                    None => continue,
+                },
+                None => fence,
+            };
+            if !unsafe { self.raw().wait(fence, index, timeout_ms) }? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the index of the last submission that has completed, along
+    /// with all submissions before it, on all queues.
+    ///
+    /// `fence` must be [`Device::fence`], locked by the caller.
+    unsafe fn finished_submission_index(
+        &self,
+        fence: &dyn hal::DynFence,
+    ) -> Result<crate::SubmissionIndex, hal::DeviceError> {
+        let main_value = unsafe { self.raw().get_fence_value(fence) }?;
+        let Some(queue) = self.get_queue() else {
+            return Ok(main_value);
+        };
+        let queue_values = self
+            .queue_fences
+            .lock()
+            .iter()
+            .map(|(&slot, fence)| {
+                Ok((slot, unsafe { self.raw().get_fence_value(fence.as_ref()) }?))
+            })
+            .collect::<Result<SmallVec<[_; 4]>, hal::DeviceError>>()?;
+
+        let first_incomplete = queue
+            .lock_life()
+            .first_incomplete_submission(|slot| match slot {
+                Some(slot) => queue_values
+                    .iter()
+                    .find(|&&(other, _)| other == slot)
+                    .map(|&(_, value)| value),
+                None => Some(main_value),
+            });
+        Ok(match first_incomplete {
+            Some(index) => index - 1,
+            None => main_value.max(
+                self.last_successful_submission_index
+                    .load(Ordering::Acquire),
+            ),
+        })
+    }
+
     /// Check the current status of the GPU and process any submissions that have
     /// finished.
     ///
@@ -740,8 +917,7 @@ impl Device {
             log::trace!("Device::maintain: waiting for submission index {target_submission_index}");
 
             let wait_result = unsafe {
-                self.raw()
-                    .wait(fence.as_ref(), target_submission_index, CLEANUP_WAIT_MS)
+                self.wait_for_submissions(fence.as_ref(), target_submission_index, CLEANUP_WAIT_MS)
             };
 
             // This error match is only about `DeviceErrors`. At this stage we do not care if
@@ -754,7 +930,7 @@ impl Device {
 
         // Get the currently finished submission index. This may be higher than the requested
         // wait, or it may be less than the requested wait if the wait failed.
-        let fence_value_result = unsafe { self.raw().get_fence_value(fence.as_ref()) };
+        let fence_value_result = unsafe { self.finished_submission_index(fence.as_ref()) };
         let current_finished_submission = match fence_value_result {
             Ok(fence_value) => fence_value,
             Err(e) => {
@@ -778,6 +954,10 @@ impl Device {
             ) = queue_result
         };
 
+        if let Some(ref bounds_reporter) = self.bounds_reporter {
+            bounds_reporter.read_back(self.raw(), current_finished_submission);
+        }
+
         // Based on the queue empty status, and the current finished submission index, determine the result of the poll.
         let result = if queue_empty {
             if let Some(wait_submission_index) = wait_submission_index {
@@ -836,6 +1016,14 @@ impl Device {
             self.release_gpu_resources();
         }
 
+        if let Some(watch) = self.memory_budget_watch.lock().as_mut() {
+            if let Some(report) = self.raw().get_memory_budget() {
+                user_closures
+                    .memory_budget_invocations
+                    .extend(watch.check(report));
+            }
+        }
+
         (user_closures, result)
     }
 
@@ -874,6 +1062,20 @@ impl Device {
             return Err(resource::CreateBufferError::InvalidUsage(desc.usage));
         }
 
+        let sparse = desc.usage.contains(wgt::BufferUsages::SPARSE);
+        if sparse {
+            self.require_features(wgt::Features::SPARSE_RESOURCES)?;
+            if desc
+                .usage
+                .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+            {
+                return Err(resource::CreateBufferError::SparseUsageMismatch(desc.usage));
+            }
+            if desc.mapped_at_creation {
+                return Err(resource::CreateBufferError::SparseMappedAtCreation);
+            }
+        }
+
         if !self
             .features
             .contains(wgt::Features::MAPPABLE_PRIMARY_BUFFERS)
@@ -930,16 +1132,47 @@ impl Device {
         } else {
             actual_size
         };
+        // Memory is bound to sparse buffers a whole page at a time.
+        let aligned_size = if sparse {
+            aligned_size.next_multiple_of(wgt::SPARSE_PAGE_SIZE)
+        } else {
+            aligned_size
+        };
 
-        let hal_desc = hal::BufferDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
-            size: aligned_size,
-            usage,
-            memory_flags: hal::MemoryFlags::empty(),
+        let sub_allocation = match self.small_buffers {
+            Some(ref small_buffers) => {
+                small_buffers.allocate(self, desc.usage, usage, aligned_size)?
+            }
+            None => None,
         };
-        let buffer = unsafe { self.raw().create_buffer(&hal_desc) }
-            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+        let raw = match sub_allocation {
+            Some(sub_allocation) => RawBuffer::SubAllocated(sub_allocation),
+            None => {
+                let hal_desc = hal::BufferDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    size: aligned_size,
+                    usage,
+                    // Sparse buffers can be bound to on all queues.
+                    memory_flags: if sparse {
+                        hal::MemoryFlags::SPARSE
+                    } else {
+                        hal::MemoryFlags::QUEUE_EXCLUSIVE
+                    },
+                };
+                let buffer = unsafe { self.raw().create_buffer(&hal_desc) }
+                    .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+                RawBuffer::Dedicated(buffer)
+            }
+        };
+        let raw_offset = match raw {
+            RawBuffer::Dedicated(_) => 0,
+            RawBuffer::SubAllocated(ref sub_allocation) => sub_allocation.offset(),
+        };
+        // Sub-allocated buffers share their HAL buffer with others.
+        let queue_exclusive = !sparse && matches!(raw, RawBuffer::Dedicated(_));
 
+        // Sub-allocated buffers have neither of these bind groups, as they
+        // would bind the whole block.
         let timestamp_normalization_bind_group = Snatchable::new(unsafe {
             // SAFETY: The size passed here must not overflow the buffer.
             self.timestamp_normalizer
@@ -947,18 +1180,19 @@ impl Device {
                 .unwrap()
                 .create_normalization_bind_group(
                     self,
-                    &*buffer,
+                    raw.raw(),
                     desc.label.as_deref(),
-                    wgt::BufferSize::new(hal_desc.size).unwrap(),
+                    wgt::BufferSize::new(aligned_size).unwrap(),
                     desc.usage,
                 )
         }?);
 
         let indirect_validation_bind_groups =
-            self.create_indirect_validation_bind_groups(buffer.as_ref(), desc.size, desc.usage)?;
+            self.create_indirect_validation_bind_groups(raw.raw(), desc.size, desc.usage)?;
 
         let buffer = Buffer {
-            raw: Snatchable::new(buffer),
+            raw: Snatchable::new(raw),
+            raw_offset,
             device: self.clone(),
             usage: desc.usage,
             size: desc.size,
@@ -972,10 +1206,18 @@ impl Device {
             bind_groups: Mutex::new(rank::BUFFER_BIND_GROUPS, WeakVec::new()),
             timestamp_normalization_bind_group,
             indirect_validation_bind_groups,
+            resident_pages: Mutex::new(rank::BUFFER_RESIDENT_PAGES, FastHashSet::default()),
+            queue_exclusive,
         };
 
         let buffer = Arc::new(buffer);
 
+        if sparse {
+            // Pages are zeroed when they become resident, and the others read
+            // as zero, so there is nothing to initialize.
+            buffer.initialization_status.write().drain(0..aligned_size);
+        }
+
         let buffer_use = if !desc.mapped_at_creation {
             wgt::BufferUses::empty()
         } else if desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
@@ -1093,7 +1335,8 @@ impl Device {
         unsafe { self.raw().add_raw_buffer(&*hal_buffer) };
 
         let buffer = Buffer {
-            raw: Snatchable::new(hal_buffer),
+            raw: Snatchable::new(RawBuffer::Dedicated(hal_buffer)),
+            raw_offset: 0,
             device: self.clone(),
             usage: desc.usage,
             size: desc.size,
@@ -1107,6 +1350,8 @@ impl Device {
             bind_groups: Mutex::new(rank::BUFFER_BIND_GROUPS, WeakVec::new()),
             timestamp_normalization_bind_group,
             indirect_validation_bind_groups,
+            resident_pages: Mutex::new(rank::BUFFER_RESIDENT_PAGES, FastHashSet::default()),
+            queue_exclusive: false,
         };
 
         let buffer = Arc::new(buffer);
@@ -1327,7 +1572,40 @@ impl Device {
             });
         }
 
-        let missing_allowed_usages = desc.usage - format_features.allowed_usages;
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT) {
+            self.require_features(wgt::Features::TRANSIENT_ATTACHMENTS)
+                .map_err(CreateTextureError::TransientMissingFeatures)?;
+
+            // Transient textures never have their contents stored, so they
+            // can't be used for anything but render pass attachments.
+            if desc.usage != wgt::TextureUsages::TRANSIENT | wgt::TextureUsages::RENDER_ATTACHMENT {
+                return Err(CreateTextureError::InvalidTransientUsage(desc.usage));
+            }
+        }
+
+        let sparse = desc.usage.contains(wgt::TextureUsages::SPARSE);
+        if sparse {
+            self.require_features(wgt::Features::SPARSE_RESOURCES)
+                .map_err(CreateTextureError::SparseMissingFeatures)?;
+
+            if desc.dimension != wgt::TextureDimension::D2
+                || desc.sample_count != 1
+                || desc.format.sparse_page_dimensions().is_none()
+            {
+                return Err(CreateTextureError::InvalidSparseTexture(
+                    desc.dimension,
+                    desc.format,
+                    desc.sample_count,
+                ));
+            }
+        }
+
+        // `TRANSIENT` and `SPARSE` only change how the texture is backed and were
+        // validated above.
+        let missing_allowed_usages = desc.usage
+            - wgt::TextureUsages::TRANSIENT
+            - wgt::TextureUsages::SPARSE
+            - format_features.allowed_usages;
         if !missing_allowed_usages.is_empty() {
             // detect downlevel incompatibilities
             let wgpu_allowed_usages = desc
@@ -1366,14 +1644,22 @@ impl Device {
             dimension: desc.dimension,
             format: desc.format,
             usage: hal_usage,
-            memory_flags: hal::MemoryFlags::empty(),
+            // Sparse textures can be bound to on all queues.
+            memory_flags: if sparse {
+                hal::MemoryFlags::SPARSE
+            } else {
+                hal::MemoryFlags::QUEUE_EXCLUSIVE
+            },
             view_formats: hal_view_formats,
         };
 
         let raw_texture = unsafe { self.raw().create_texture(&hal_desc) }
             .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
 
-        let clear_mode = if hal_usage
+        let clear_mode = if desc.usage.contains(wgt::TextureUsages::TRANSIENT) {
+            // Transient textures are always cleared by the render passes using them.
+            resource::TextureClearMode::None
+        } else if hal_usage
             .intersects(wgt::TextureUses::DEPTH_STENCIL_WRITE | wgt::TextureUses::COLOR_TARGET)
             && desc.dimension == wgt::TextureDimension::D2
         {
@@ -1434,15 +1720,18 @@ impl Device {
             resource::TextureClearMode::BufferCopy
         };
 
-        let texture = Texture::new(
+        let mut texture = Texture::new(
             self,
             resource::TextureInner::Native { raw: raw_texture },
             hal_usage,
             desc,
             format_features,
             clear_mode,
-            true,
+            // Pages of sparse textures are zeroed when they become resident,
+            // and the others read as zero.
+            !sparse,
         );
+        texture.queue_exclusive = !sparse;
 
         let texture = Arc::new(texture);
 
@@ -2080,17 +2369,28 @@ impl Device {
         .validate(&module)
         .map_err(|inner| {
             pipeline::CreateShaderModuleError::Validation(naga::error::ShaderError {
-                source,
+                source: source.clone(),
                 label: desc.label.as_ref().map(|l| l.to_string()),
                 inner: Box::new(inner),
             })
         })?;
 
         let interface = validation::Interface::new(&module, &info, self.limits.clone());
+
+        let (reported_shader, bounds_report) = match self.bounds_reporter {
+            Some(ref bounds_reporter) => {
+                let label = desc.label.as_deref().unwrap_or_default();
+                let (shader, bounds_report) = bounds_reporter.register(&module, label, &source);
+                (Some(shader), Some(bounds_report))
+            }
+            None => (None, None),
+        };
+
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
             module,
             info,
             debug_source,
+            bounds_report,
         });
         let hal_desc = hal::ShaderModuleDescriptor {
             label: desc.label.to_hal(self.instance_flags),
@@ -2116,6 +2416,7 @@ impl Device {
             device: self.clone(),
             interface: Some(interface),
             label: desc.label.to_string(),
+            _bounds_report: reported_shader,
         };
 
         let module = Arc::new(module);
@@ -2205,31 +2506,88 @@ impl Device {
             device: self.clone(),
             interface: None,
             label: descriptor.label.to_string(),
+            _bounds_report: None,
         };
 
         Ok(Arc::new(module))
     }
 
+    /// Create a command encoder.
+    ///
+    /// If `reusable` is true, the command buffer it finishes can be submitted
+    /// more than once. The caller is responsible for checking that
+    /// [`wgt::Features::REUSABLE_COMMAND_BUFFERS`] is enabled.
+    ///
+    /// The command buffer it finishes can only be submitted to queues of the
+    /// type of `queue`.
     pub(crate) fn create_command_encoder(
         self: &Arc<Self>,
+        queue: &Queue,
         label: &crate::Label,
+        reusable: bool,
     ) -> Result<Arc<command::CommandEncoder>, DeviceError> {
         self.check_is_valid()?;
 
-        let queue = self.get_queue().unwrap();
-
-        let encoder = self
-            .command_allocator
-            .acquire_encoder(self.raw(), queue.raw())
-            .map_err(|e| self.handle_hal_error(e))?;
+        let encoder = if reusable {
+            // Reusable encoders are not recycled, see `InnerCommandEncoder::reusable`.
+            unsafe {
+                self.raw()
+                    .create_command_encoder(&hal::CommandEncoderDescriptor {
+                        label: None,
+                        queue: queue.raw(),
+                        reusable: true,
+                    })
+            }
+        } else {
+            self.command_allocator
+                .acquire_encoder(self.raw(), queue.raw(), queue.queue_type())
+        }
+        .map_err(|e| self.handle_hal_error(e))?;
 
-        let cmd_enc = command::CommandEncoder::new(encoder, self, label);
+        let cmd_enc =
+            command::CommandEncoder::new(encoder, self, label, reusable, queue.queue_type());
 
         let cmd_enc = Arc::new(cmd_enc);
 
         Ok(cmd_enc)
     }
 
+    /// Create a queue besides the one the device was created with.
+    pub(crate) fn create_queue(
+        self: &Arc<Self>,
+        desc: &wgt::QueueDescriptor<crate::Label>,
+    ) -> Result<Arc<Queue>, CreateQueueError> {
+        self.check_is_valid()?;
+
+        let queue_type = desc.queue_type;
+        let limit = queue_type.max_count(&self.limits);
+        let main = self.get_queue().unwrap();
+
+        let (slot, raw) = {
+            let mut queue_fences = self.queue_fences.lock();
+            let slot = (0..limit)
+                .map(|index| QueueSlot { queue_type, index })
+                .find(|slot| !queue_fences.contains_key(slot))
+                .ok_or(CreateQueueError::TooManyQueues { queue_type, limit })?;
+
+            let raw = unsafe { self.raw().create_queue(queue_type, slot.index) }
+                .map_err(|e| self.handle_hal_error(e))?;
+            let fence =
+                unsafe { self.raw().create_fence() }.map_err(|e| self.handle_hal_error(e))?;
+            queue_fences.insert(slot, fence);
+            (slot, raw)
+        };
+
+        match Queue::new_secondary(main, raw, slot) {
+            Ok(queue) => Ok(Arc::new(queue)),
+            Err(e) => {
+                let fence = self.queue_fences.lock().remove(&slot).unwrap();
+                unsafe { self.raw().destroy_fence(fence) };
+                Err(e.into())
+            }
+        }
+    }
+
     /// Generate information about late-validated buffer bindings for pipelines.
     //TODO: should this be combined with `get_introspection_bind_group_layouts` in some way?
     fn make_late_sized_buffer_groups(
@@ -3402,11 +3760,14 @@ impl Device {
             .cloned()
             .collect::<ArrayVec<_, { hal::MAX_BIND_GROUPS }>>();
 
-        let raw_bind_group_layouts = desc
+        let mut raw_bind_group_layouts = desc
             .bind_group_layouts
             .iter()
             .map(|bgl| bgl.raw())
             .collect::<ArrayVec<_, { hal::MAX_BIND_GROUPS }>>();
+        if let Some(ref bounds_reporter) = self.bounds_reporter {
+            bounds_reporter.extend_bind_group_layouts(&mut raw_bind_group_layouts);
+        }
 
         let additional_flags = if self.indirect_validation.is_some() {
             hal::PipelineLayoutFlags::INDIRECT_BUILTIN_UPDATE
@@ -3668,6 +4029,7 @@ impl Device {
         let mut total_attributes;
         let mut shader_expects_dual_source_blending = false;
         let mut pipeline_expects_dual_source_blending = false;
+        let mut framebuffer_fetches = validation::FramebufferFetches::default();
         if let pipeline::RenderPipelineVertexProcessor::Vertex(ref vertex) = desc.vertex {
             vertex_steps = Vec::with_capacity(vertex.buffers.len());
             vertex_buffers = Vec::with_capacity(vertex.buffers.len());
@@ -4174,6 +4536,12 @@ impl Device {
                             stage,
                             error,
                         })?;
+                    framebuffer_fetches = interface
+                        .fragment_framebuffer_fetches(&fragment_entry_point_name)
+                        .map_err(|error| pipeline::CreateRenderPipelineError::Stage {
+                            stage,
+                            error,
+                        })?;
                 }
 
                 Some(hal::ProgrammableStage {
@@ -4199,6 +4567,46 @@ impl Device {
             );
         }
 
+        let mut fetched_colors = 0u8;
+        for &(location, ty) in framebuffer_fetches.colors.iter() {
+            fetched_colors |= 1 << location;
+            let Some(Some(state)) = color_targets.get(location as usize) else {
+                return Err(
+                    pipeline::CreateRenderPipelineError::FramebufferFetchMissingColorTarget {
+                        location,
+                    },
+                );
+            };
+            validation::check_texture_format(state.format, &ty).map_err(|pipeline| {
+                pipeline::CreateRenderPipelineError::ColorState(
+                    location as u8,
+                    pipeline::ColorStateError::IncompatibleFormat {
+                        pipeline,
+                        shader: ty,
+                    },
+                )
+            })?;
+        }
+        let depth_stencil_format = desc.depth_stencil.as_ref().map(|ds| ds.format);
+        if framebuffer_fetches.depth
+            && !depth_stencil_format.is_some_and(|format| format.has_depth_aspect())
+        {
+            return Err(
+                pipeline::CreateRenderPipelineError::FramebufferFetchMissingDepthStencilAspect {
+                    aspect: wgt::TextureAspect::DepthOnly,
+                },
+            );
+        }
+        if framebuffer_fetches.stencil
+            && !depth_stencil_format.is_some_and(|format| format.has_stencil_aspect())
+        {
+            return Err(
+                pipeline::CreateRenderPipelineError::FramebufferFetchMissingDepthStencilAspect {
+                    aspect: wgt::TextureAspect::StencilOnly,
+                },
+            );
+        }
+
         if validated_stages.contains(wgt::ShaderStages::FRAGMENT) {
             for (i, output) in io.iter() {
                 match color_targets.get(*i as usize) {
@@ -4353,6 +4761,14 @@ impl Device {
                 flags |= pipeline::PipelineFlags::WRITES_STENCIL;
             }
         }
+        flags.set(
+            pipeline::PipelineFlags::READS_DEPTH,
+            framebuffer_fetches.depth,
+        );
+        flags.set(
+            pipeline::PipelineFlags::READS_STENCIL,
+            framebuffer_fetches.stencil,
+        );
         let shader_modules = {
             let mut shader_modules = ArrayVec::new();
             match desc.vertex {
@@ -4377,6 +4793,7 @@ impl Device {
             pass_context,
             _shader_modules: shader_modules,
             flags,
+            fetched_colors,
             strip_index_format: desc.primitive.strip_index_format,
             vertex_steps,
             late_sized_buffer_groups,
@@ -4467,7 +4884,7 @@ impl Device {
         format_features
     }
 
-    fn describe_format_features(
+    pub(crate) fn describe_format_features(
         &self,
         format: TextureFormat,
     ) -> Result<wgt::TextureFormatFeatures, MissingFeatures> {
@@ -4496,10 +4913,10 @@ impl Device {
         submission_index: crate::SubmissionIndex,
     ) -> Result<(), DeviceError> {
         let fence = self.fence.read();
-        let last_done_index = unsafe { self.raw().get_fence_value(fence.as_ref()) }
+        let last_done_index = unsafe { self.finished_submission_index(fence.as_ref()) }
             .map_err(|e| self.handle_hal_error(e))?;
         if last_done_index < submission_index {
-            unsafe { self.raw().wait(fence.as_ref(), submission_index, !0) }
+            unsafe { self.wait_for_submissions(fence.as_ref(), submission_index, !0) }
                 .map_err(|e| self.handle_hal_error(e))?;
             drop(fence);
             if let Some(queue) = self.get_queue() {
@@ -4608,6 +5025,10 @@ impl Device {
         UsageScope::new_pooled(&self.usage_scopes, &self.tracker_indices)
     }
 
+    pub fn get_core_counters(&self) -> wgt::CoreCounters {
+        self.counters.clone()
+    }
+
     pub fn get_hal_counters(&self) -> wgt::HalCounters {
         self.raw().get_internal_counters()
     }
@@ -4615,6 +5036,10 @@ impl Device {
     pub fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport> {
         self.raw().generate_allocator_report()
     }
+
+    pub fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        self.raw().get_memory_budget()
+    }
 }
 
 crate::impl_resource_type!(Device);
@@ -0,0 +1,450 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    api_log,
+    device::{
+        queue::{Queue, QueueBindSparseError},
+        ZERO_BUFFER_SIZE,
+    },
+    get_lowest_common_denom,
+    global::Global,
+    id::{BufferId, QueueId, TextureId},
+    resource::{Buffer, Labeled, ParentDevice, RawResourceAccess, Texture},
+};
+use wgt::{math::align_to, TextureSelector, SPARSE_PAGE_SIZE};
+
+/// Checks `bind` and returns the indices of the pages it binds.
+fn validate_buffer_bind(
+    bind: &wgt::SparseBufferBind<Arc<Buffer>>,
+) -> Result<Range<u64>, QueueBindSparseError> {
+    let buffer = &bind.buffer;
+    if !buffer.usage.contains(wgt::BufferUsages::SPARSE) {
+        return Err(QueueBindSparseError::NotSparse(buffer.error_ident()));
+    }
+
+    let start = bind.offset;
+    let end = start.saturating_add(bind.size);
+    if end > buffer.size {
+        return Err(QueueBindSparseError::BufferRangeOutOfBounds {
+            buffer: buffer.error_ident(),
+            start,
+            end,
+            size: buffer.size,
+        });
+    }
+    // The last page of the buffer may be bound by a range that ends with it.
+    if start % SPARSE_PAGE_SIZE != 0 || (end % SPARSE_PAGE_SIZE != 0 && end != buffer.size) {
+        return Err(QueueBindSparseError::UnalignedBufferRange {
+            buffer: buffer.error_ident(),
+            start,
+            end,
+        });
+    }
+
+    Ok(start / SPARSE_PAGE_SIZE..end.div_ceil(SPARSE_PAGE_SIZE))
+}
+
+/// Checks `bind` and returns the pages it binds, keyed like
+/// [`Texture::resident_pages`].
+fn validate_texture_bind(
+    bind: &wgt::SparseTextureBind<Arc<Texture>>,
+) -> Result<Vec<[u32; 4]>, QueueBindSparseError> {
+    let texture = &bind.texture;
+    let desc = &texture.desc;
+    if !desc.usage.contains(wgt::TextureUsages::SPARSE) {
+        return Err(QueueBindSparseError::NotSparse(texture.error_ident()));
+    }
+
+    if bind.mip_level >= desc.mip_level_count {
+        return Err(QueueBindSparseError::MipLevelOutOfBounds {
+            texture: texture.error_ident(),
+            mip_level: bind.mip_level,
+            count: desc.mip_level_count,
+        });
+    }
+
+    let layer_count = desc.array_layer_count();
+    let layers = bind.origin.z
+        ..bind
+            .origin
+            .z
+            .saturating_add(bind.size.depth_or_array_layers);
+    if layers.end > layer_count {
+        return Err(QueueBindSparseError::ArrayLayersOutOfBounds {
+            texture: texture.error_ident(),
+            start: layers.start,
+            end: layers.end,
+            count: layer_count,
+        });
+    }
+
+    let mip_size = desc.mip_level_size(bind.mip_level).unwrap();
+    let tail_start = desc.sparse_mip_tail_start();
+    if bind.mip_level >= tail_start {
+        if bind.origin.x != 0
+            || bind.origin.y != 0
+            || bind.size.width != mip_size.width
+            || bind.size.height != mip_size.height
+        {
+            return Err(QueueBindSparseError::PartialMipTail {
+                texture: texture.error_ident(),
+                mip_level: bind.mip_level,
+                tail_start,
+            });
+        }
+        return Ok(layers.map(|layer| [tail_start, layer, 0, 0]).collect());
+    }
+
+    let x = bind.origin.x..bind.origin.x.saturating_add(bind.size.width);
+    let y = bind.origin.y..bind.origin.y.saturating_add(bind.size.height);
+    if x.end > mip_size.width || y.end > mip_size.height {
+        return Err(QueueBindSparseError::TextureRegionOutOfBounds {
+            texture: texture.error_ident(),
+            mip_level: bind.mip_level,
+            origin: bind.origin,
+            size: bind.size,
+            mip_size,
+        });
+    }
+
+    // The last pages of a row or column may be bound by a region that ends with them.
+    let (page_width, page_height) = desc.format.sparse_page_dimensions().unwrap();
+    let is_aligned = |range: &Range<u32>, page: u32, edge: u32| {
+        range.start % page == 0 && (range.end % page == 0 || range.end == edge)
+    };
+    if !is_aligned(&x, page_width, mip_size.width) || !is_aligned(&y, page_height, mip_size.height)
+    {
+        return Err(QueueBindSparseError::UnalignedTextureRegion {
+            texture: texture.error_ident(),
+            mip_level: bind.mip_level,
+            origin: bind.origin,
+            size: bind.size,
+            page_width,
+            page_height,
+        });
+    }
+
+    let columns = x.start / page_width..x.end.div_ceil(page_width);
+    let rows = y.start / page_height..y.end.div_ceil(page_height);
+    let mut pages = Vec::new();
+    for layer in layers {
+        for row in rows.clone() {
+            for column in columns.clone() {
+                pages.push([bind.mip_level, layer, column, row]);
+            }
+        }
+    }
+    Ok(pages)
+}
+
+/// Returns the copies from the zero buffer that zero `pages` of `texture`.
+fn zero_page_copies(
+    texture: &Texture,
+    alignments: &hal::Alignments,
+    pages: &[[u32; 4]],
+) -> Vec<hal::BufferTextureCopy> {
+    let desc = &texture.desc;
+    let (page_width, page_height) = desc.format.sparse_page_dimensions().unwrap();
+    let tail_start = desc.sparse_mip_tail_start();
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_size = desc.format.block_copy_size(None).unwrap();
+    let bytes_per_row_alignment =
+        get_lowest_common_denom(alignments.buffer_copy_pitch.get() as u32, block_size);
+
+    let mut copies = Vec::new();
+    let mut push_copies = |mip_level, array_layer, origin: wgt::Origin3d, width, height| {
+        // Round to multiple of block size
+        let width = align_to(width, block_width);
+        let height = align_to(height, block_height);
+        let bytes_per_row = align_to(width / block_width * block_size, bytes_per_row_alignment);
+        let max_rows_per_copy = ZERO_BUFFER_SIZE as u32 / bytes_per_row;
+        let max_rows_per_copy = max_rows_per_copy / block_height * block_height;
+
+        let mut row = 0;
+        while row < height {
+            let num_rows = (height - row).min(max_rows_per_copy);
+            copies.push(hal::BufferTextureCopy {
+                buffer_layout: wgt::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+                texture_base: hal::TextureCopyBase {
+                    mip_level,
+                    array_layer,
+                    origin: wgt::Origin3d {
+                        y: origin.y + row,
+                        ..origin
+                    },
+                    aspect: hal::FormatAspects::COLOR,
+                },
+                size: hal::CopyExtent {
+                    width,
+                    height: num_rows,
+                    depth: 1,
+                },
+            });
+            row += num_rows;
+        }
+    };
+
+    for &[mip_level, array_layer, column, row] in pages {
+        if mip_level >= tail_start {
+            for mip_level in tail_start..desc.mip_level_count {
+                let mip_size = desc.mip_level_size(mip_level).unwrap();
+                push_copies(
+                    mip_level,
+                    array_layer,
+                    wgt::Origin3d::ZERO,
+                    mip_size.width,
+                    mip_size.height,
+                );
+            }
+        } else {
+            let mip_size = desc.mip_level_size(mip_level).unwrap();
+            let origin = wgt::Origin3d {
+                x: column * page_width,
+                y: row * page_height,
+                z: 0,
+            };
+            push_copies(
+                mip_level,
+                array_layer,
+                origin,
+                page_width.min(mip_size.width - origin.x),
+                page_height.min(mip_size.height - origin.y),
+            );
+        }
+    }
+    copies
+}
+
+impl Queue {
+    fn bind_sparse(
+        &self,
+        buffer_binds: &[wgt::SparseBufferBind<Arc<Buffer>>],
+        texture_binds: &[wgt::SparseTextureBind<Arc<Texture>>],
+    ) -> Result<(), QueueBindSparseError> {
+        profiling::scope!("Queue::bind_sparse");
+
+        // Memory is bound by the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.bind_sparse(buffer_binds, texture_binds);
+        }
+
+        let device = &self.device;
+        device.check_is_valid()?;
+
+        let mut buffer_pages = Vec::with_capacity(buffer_binds.len());
+        for bind in buffer_binds {
+            bind.buffer.same_device_as(self)?;
+            buffer_pages.push(validate_buffer_bind(bind)?);
+        }
+        let mut texture_pages = Vec::with_capacity(texture_binds.len());
+        for bind in texture_binds {
+            bind.texture.same_device_as(self)?;
+            texture_pages.push(validate_texture_bind(bind)?);
+        }
+
+        // The writes requested before the binds must be made before them.
+        let is_recording = self.pending_writes.lock().is_recording;
+        if is_recording {
+            self.submit(&[]).map_err(|(_, e)| e)?;
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+        let raw_buffers = buffer_binds
+            .iter()
+            .map(|bind| bind.buffer.try_raw(&snatch_guard))
+            .collect::<Result<Vec<_>, _>>()?;
+        let raw_textures = texture_binds
+            .iter()
+            .map(|bind| bind.texture.try_raw(&snatch_guard))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Fence lock must be acquired after the snatch lock everywhere to avoid deadlocks.
+        // Holding it for writing keeps submissions from being made until the binds are, so
+        // that the next one comes after them.
+        let _fence = device.fence.write();
+
+        // Other queues must be done with the resources before their memory changes.
+        let buffers = buffer_binds
+            .iter()
+            .map(|bind| &*bind.buffer)
+            .collect::<Vec<_>>();
+        let textures = texture_binds
+            .iter()
+            .map(|bind| &*bind.texture)
+            .collect::<Vec<_>>();
+        let dependencies = self
+            .lock_life()
+            .resource_dependencies(self.slot, &buffers, &textures);
+        if !dependencies.is_empty() {
+            let queue_fences = device.queue_fences.lock();
+            for (slot, index) in dependencies {
+                // Only the queues other than the main one have a slot.
+                let dependency_fence = queue_fences[&slot.unwrap()].as_ref();
+                unsafe { self.raw().wait_for_fence(dependency_fence, index) }
+                    .map_err(|e| device.handle_hal_error(e))?;
+            }
+        }
+
+        let hal_buffer_binds = buffer_binds
+            .iter()
+            .zip(&raw_buffers)
+            .zip(&buffer_pages)
+            .map(|((bind, &buffer), pages)| hal::SparseBufferBind {
+                buffer,
+                range: pages.start * SPARSE_PAGE_SIZE..pages.end * SPARSE_PAGE_SIZE,
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        let hal_texture_binds = texture_binds
+            .iter()
+            .zip(&raw_textures)
+            .map(|(bind, &texture)| hal::SparseTextureBind {
+                texture,
+                mip_level: bind.mip_level,
+                origin: bind.origin,
+                size: bind.size,
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            self.raw()
+                .bind_sparse_memory(&hal_buffer_binds, &hal_texture_binds)
+        }
+        .map_err(|e| device.handle_hal_error(e))?;
+
+        // Pages that became resident are zeroed by the next submission, which
+        // comes after the binds.
+        let mut pending_writes = self.pending_writes.lock();
+        let mut trackers = device.trackers.lock();
+
+        for ((bind, raw), pages) in buffer_binds.iter().zip(raw_buffers).zip(buffer_pages) {
+            let buffer = &bind.buffer;
+            let mut resident_pages = buffer.resident_pages.lock();
+            if !bind.resident {
+                for page in pages {
+                    resident_pages.remove(&page);
+                }
+                continue;
+            }
+
+            let mut ranges = Vec::<Range<u64>>::new();
+            for page in pages.filter(|&page| resident_pages.insert(page)) {
+                match ranges.last_mut() {
+                    Some(range) if range.end == page => range.end += 1,
+                    _ => ranges.push(page..page + 1),
+                }
+            }
+            drop(resident_pages);
+            if ranges.is_empty() {
+                continue;
+            }
+
+            let barriers = trackers
+                .buffers
+                .set_single(buffer, wgt::BufferUses::COPY_DST)
+                .map(|pending| pending.into_hal(buffer, &snatch_guard))
+                .into_iter()
+                .collect::<Vec<_>>();
+            let encoder = pending_writes.activate();
+            unsafe {
+                encoder.transition_buffers(&barriers);
+                for range in ranges {
+                    encoder.clear_buffer(
+                        raw,
+                        range.start * SPARSE_PAGE_SIZE..range.end * SPARSE_PAGE_SIZE,
+                    );
+                }
+            }
+            pending_writes.insert_buffer(buffer);
+        }
+
+        for ((bind, raw), pages) in texture_binds.iter().zip(raw_textures).zip(texture_pages) {
+            let texture = &bind.texture;
+            let mut resident_pages = texture.resident_pages.lock();
+            if !bind.resident {
+                for page in pages {
+                    resident_pages.remove(&page);
+                }
+                continue;
+            }
+
+            let pages = pages
+                .into_iter()
+                .filter(|&page| resident_pages.insert(page))
+                .collect::<Vec<_>>();
+            drop(resident_pages);
+            if pages.is_empty() {
+                continue;
+            }
+
+            let tail_start = texture.desc.sparse_mip_tail_start();
+            let selector = TextureSelector {
+                mips: if bind.mip_level >= tail_start {
+                    tail_start..texture.desc.mip_level_count
+                } else {
+                    bind.mip_level..bind.mip_level + 1
+                },
+                layers: bind.origin.z..bind.origin.z + bind.size.depth_or_array_layers,
+            };
+            let barriers = trackers
+                .textures
+                .set_single(texture, selector, wgt::TextureUses::COPY_DST)
+                .map(|pending| pending.into_hal(raw))
+                .collect::<Vec<_>>();
+            let copies = zero_page_copies(texture, &device.alignments, &pages);
+            let encoder = pending_writes.activate();
+            unsafe {
+                encoder.transition_textures(&barriers);
+                encoder.copy_buffer_to_texture(device.zero_buffer.as_ref(), raw, &copies);
+            }
+            pending_writes.insert_texture(texture);
+        }
+
+        Ok(())
+    }
+}
+
+impl Global {
+    pub fn queue_bind_sparse(
+        &self,
+        queue_id: QueueId,
+        buffer_binds: &[wgt::SparseBufferBind<BufferId>],
+        texture_binds: &[wgt::SparseTextureBind<TextureId>],
+    ) -> Result<(), QueueBindSparseError> {
+        api_log!("Queue::bind_sparse");
+
+        let hub = &self.hub;
+        let queue = hub.queues.get(queue_id);
+
+        let buffer_binds = buffer_binds
+            .iter()
+            .map(|bind| {
+                Ok(wgt::SparseBufferBind {
+                    buffer: hub.buffers.get(bind.buffer).get()?,
+                    offset: bind.offset,
+                    size: bind.size,
+                    resident: bind.resident,
+                })
+            })
+            .collect::<Result<Vec<_>, QueueBindSparseError>>()?;
+        let texture_binds = texture_binds
+            .iter()
+            .map(|bind| {
+                Ok(wgt::SparseTextureBind {
+                    texture: hub.textures.get(bind.texture).get()?,
+                    mip_level: bind.mip_level,
+                    origin: bind.origin,
+                    size: bind.size,
+                    resident: bind.resident,
+                })
+            })
+            .collect::<Result<Vec<_>, QueueBindSparseError>>()?;
+
+        queue.bind_sparse(&buffer_binds, &texture_binds)
+    }
+}
@@ -0,0 +1,328 @@
+//! Packing of small buffers into shared HAL buffers.
+//!
+//! With [`wgt::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS`], buffers of at most
+//! [`MAX_SUBALLOCATION_SIZE`] bytes whose usages are all in
+//! [`SUBALLOCATABLE_USAGES`] don't get a HAL buffer of their own. Instead, they
+//! are placed in a range of a larger HAL buffer, a [`BufferBlock`], shared with
+//! other buffers that have the same usages. A block is destroyed along with the
+//! last buffer placed in it.
+//!
+//! Within `wgpu-core`, a sub-allocated buffer behaves like any other buffer: it
+//! has its own state in the trackers and its own initialization tracker, both
+//! covering its range only. Whenever it is passed to `wgpu-hal`, its offset in
+//! the block, [`Buffer::raw_offset`], must be added to the offsets of the
+//! operation.
+//!
+//! Barriers on a sub-allocated buffer apply to the whole block. On backends
+//! where buffer barriers only synchronize accesses, this may add
+//! synchronization between the buffers of a block, but never removes any. DX12
+//! barriers also change the state of the whole resource, so the sub-allocator
+//! is not used there.
+//!
+//! [`Buffer::raw_offset`]: crate::resource::Buffer::raw_offset
+
+use alloc::{
+    boxed::Box,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{mem::ManuallyDrop, ops::Range};
+
+use crate::{
+    device::{Device, DeviceError},
+    hal_label,
+    lock::{rank, Mutex},
+    resource_log, FastHashMap,
+};
+
+/// The usages a buffer may have to be sub-allocated.
+///
+/// Other usages let `wgpu-core` or `wgpu-hal` access the HAL buffer as a whole,
+/// for mapping, indirect validation, timestamp normalization or acceleration
+/// structure builds, and copies from the buffer could have a source and
+/// destination in the same block.
+pub(crate) const SUBALLOCATABLE_USAGES: wgt::BufferUsages = wgt::BufferUsages::VERTEX
+    .union(wgt::BufferUsages::INDEX)
+    .union(wgt::BufferUsages::UNIFORM)
+    .union(wgt::BufferUsages::COPY_DST);
+
+/// The size in bytes of the largest buffer that is sub-allocated.
+pub(crate) const MAX_SUBALLOCATION_SIZE: wgt::BufferAddress = 16 << 10;
+
+/// The alignment in bytes of all sub-allocated buffers in their block.
+///
+/// This is the largest texel block size, so that copies between a texture and
+/// a sub-allocated buffer keep the alignment the backends require.
+const MIN_ALIGNMENT: wgt::BufferAddress = 16;
+
+/// The size in bytes of the HAL buffers holding sub-allocated buffers.
+const BLOCK_SIZE: wgt::BufferAddress = 256 << 10;
+
+/// The sub-allocator of small buffers of a [`Device`].
+pub(crate) struct BufferSubAllocator {
+    /// The blocks that may have free space, by the usages of their buffers.
+    ///
+    /// Blocks are owned by the buffers placed in them, so that they are
+    /// destroyed along with their last buffer.
+    blocks: Mutex<FastHashMap<wgt::BufferUsages, Vec<Weak<BufferBlock>>>>,
+}
+
+impl BufferSubAllocator {
+    pub(crate) fn new() -> Self {
+        Self {
+            blocks: Mutex::new(rank::DEVICE_BUFFER_BLOCKS, FastHashMap::default()),
+        }
+    }
+
+    /// Place a buffer of `size` bytes with `usage` in a block.
+    ///
+    /// Returns `None` if the buffer can't be sub-allocated. `hal_usage` is the
+    /// usage a dedicated HAL buffer would be created with.
+    pub(crate) fn allocate(
+        &self,
+        device: &Arc<Device>,
+        usage: wgt::BufferUsages,
+        hal_usage: wgt::BufferUses,
+        size: wgt::BufferAddress,
+    ) -> Result<Option<SubAllocation>, DeviceError> {
+        if size > MAX_SUBALLOCATION_SIZE || !SUBALLOCATABLE_USAGES.contains(usage) {
+            return Ok(None);
+        }
+
+        // Uniform bindings are placed at an offset aligned to
+        // `min_uniform_buffer_offset_alignment` in the buffer, which must also
+        // be aligned in the block.
+        let alignment = if usage.contains(wgt::BufferUsages::UNIFORM) {
+            u64::from(device.limits.min_uniform_buffer_offset_alignment).max(MIN_ALIGNMENT)
+        } else {
+            MIN_ALIGNMENT
+        };
+        // All allocations in a block have a size that is a multiple of the
+        // alignment, which keeps all free ranges aligned.
+        let size = size.next_multiple_of(alignment);
+
+        let mut blocks = self.blocks.lock();
+        let usage_blocks = blocks.entry(usage).or_default();
+
+        usage_blocks.retain(|block| block.strong_count() != 0);
+        for block in usage_blocks.iter() {
+            let Some(block) = block.upgrade() else {
+                continue;
+            };
+            let range = block.free_ranges.lock().allocate(size);
+            if let Some(range) = range {
+                return Ok(Some(SubAllocation::new(block, range)));
+            }
+        }
+
+        let hal_desc = hal::BufferDescriptor {
+            label: hal_label(
+                Some("(wgpu internal) sub-allocated buffers"),
+                device.instance_flags,
+            ),
+            size: BLOCK_SIZE,
+            usage: hal_usage,
+            memory_flags: hal::MemoryFlags::empty(),
+        };
+        let raw = match unsafe { device.raw().create_buffer(&hal_desc) } {
+            Ok(raw) => raw,
+            Err(e) => {
+                // Handling the error may lose the device, which takes other locks.
+                drop(blocks);
+                return Err(device.handle_hal_error_with_nonfatal_oom(e));
+            }
+        };
+
+        let block = Arc::new(BufferBlock {
+            raw: ManuallyDrop::new(raw),
+            device: device.clone(),
+            free_ranges: Mutex::new(rank::BUFFER_BLOCK_FREE_RANGES, FreeRanges::new(BLOCK_SIZE)),
+        });
+        device.counters.buffer_blocks.add(1);
+        device.counters.buffer_block_memory.add(BLOCK_SIZE as isize);
+
+        usage_blocks.push(Arc::downgrade(&block));
+        let range = block.free_ranges.lock().allocate(size).unwrap();
+        Ok(Some(SubAllocation::new(block, range)))
+    }
+}
+
+/// A HAL buffer holding sub-allocated buffers.
+#[derive(Debug)]
+pub(crate) struct BufferBlock {
+    raw: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    device: Arc<Device>,
+    free_ranges: Mutex<FreeRanges>,
+}
+
+impl Drop for BufferBlock {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw buffer block");
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe {
+            self.device.raw().destroy_buffer(raw);
+        }
+        self.device.counters.buffer_blocks.sub(1);
+        self.device
+            .counters
+            .buffer_block_memory
+            .sub(BLOCK_SIZE as isize);
+    }
+}
+
+/// The range of a [`BufferBlock`] holding a buffer.
+///
+/// The range is returned to the block when this is dropped, which must only
+/// happen once the GPU is done with the buffer.
+#[derive(Debug)]
+pub(crate) struct SubAllocation {
+    block: Arc<BufferBlock>,
+    range: Range<wgt::BufferAddress>,
+}
+
+impl SubAllocation {
+    fn new(block: Arc<BufferBlock>, range: Range<wgt::BufferAddress>) -> Self {
+        let counters = &block.device.counters;
+        counters.suballocated_buffers.add(1);
+        counters
+            .suballocated_buffer_memory
+            .add((range.end - range.start) as isize);
+        Self { block, range }
+    }
+
+    /// The HAL buffer of the block.
+    pub(crate) fn raw(&self) -> &dyn hal::DynBuffer {
+        self.block.raw.as_ref()
+    }
+
+    /// The offset of the range in the block.
+    pub(crate) fn offset(&self) -> wgt::BufferAddress {
+        self.range.start
+    }
+}
+
+impl Drop for SubAllocation {
+    fn drop(&mut self) {
+        let counters = &self.block.device.counters;
+        counters.suballocated_buffers.sub(1);
+        counters
+            .suballocated_buffer_memory
+            .sub((self.range.end - self.range.start) as isize);
+        self.block.free_ranges.lock().free(self.range.clone());
+    }
+}
+
+/// Returns the offset and size in its block of a binding of a sub-allocated
+/// buffer, placed at `raw_offset` and of `buffer_size` bytes.
+///
+/// An implicit size would extend the binding to the end of the block, so it is
+/// resolved against the buffer. The binding must be contained within the buffer.
+pub(crate) fn binding_in_block(
+    raw_offset: wgt::BufferAddress,
+    buffer_size: wgt::BufferAddress,
+    offset: wgt::BufferAddress,
+    size: Option<wgt::BufferSize>,
+) -> (wgt::BufferAddress, Option<wgt::BufferSize>) {
+    let size = size.or_else(|| wgt::BufferSize::new(buffer_size - offset));
+    (raw_offset + offset, size)
+}
+
+/// The free ranges of a [`BufferBlock`].
+#[derive(Debug)]
+struct FreeRanges {
+    /// Non-empty, non-adjacent ranges, sorted by offset.
+    ranges: Vec<Range<wgt::BufferAddress>>,
+}
+
+impl FreeRanges {
+    fn new(size: wgt::BufferAddress) -> Self {
+        Self {
+            ranges: alloc::vec![0..size],
+        }
+    }
+
+    /// Take `size` bytes from the first free range large enough.
+    fn allocate(&mut self, size: wgt::BufferAddress) -> Option<Range<wgt::BufferAddress>> {
+        let index = self
+            .ranges
+            .iter()
+            .position(|range| range.end - range.start >= size)?;
+        let free = &mut self.ranges[index];
+        let start = free.start;
+        free.start += size;
+        if free.is_empty() {
+            self.ranges.remove(index);
+        }
+        Some(start..start + size)
+    }
+
+    /// Return `range` to the free ranges, merging it with its neighbors.
+    fn free(&mut self, range: Range<wgt::BufferAddress>) {
+        let index = self.ranges.partition_point(|free| free.end <= range.start);
+        let merge_previous = index > 0 && self.ranges[index - 1].end == range.start;
+        let merge_next = index < self.ranges.len() && self.ranges[index].start == range.end;
+        match (merge_previous, merge_next) {
+            (true, true) => {
+                self.ranges[index - 1].end = self.ranges[index].end;
+                self.ranges.remove(index);
+            }
+            (true, false) => self.ranges[index - 1].end = range.end,
+            (false, true) => self.ranges[index].start = range.start,
+            (false, false) => self.ranges.insert(index, range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{binding_in_block, FreeRanges};
+
+    #[test]
+    fn allocate_first_fit() {
+        let mut free = FreeRanges::new(16);
+        assert_eq!(free.allocate(4), Some(0..4));
+        assert_eq!(free.allocate(8), Some(4..12));
+        assert_eq!(free.allocate(8), None);
+        assert_eq!(free.allocate(4), Some(12..16));
+        assert!(free.ranges.is_empty());
+        assert_eq!(free.allocate(4), None);
+    }
+
+    #[test]
+    fn free_merges_neighbors() {
+        let mut free = FreeRanges::new(16);
+        let a = free.allocate(4).unwrap();
+        let b = free.allocate(4).unwrap();
+        let c = free.allocate(4).unwrap();
+
+        free.free(a);
+        assert_eq!(free.ranges, [0..4, 12..16]);
+        free.free(c);
+        assert_eq!(free.ranges, [0..4, 8..16]);
+        free.free(b);
+        #[allow(clippy::single_range_in_vec_init)]
+        let whole = [0..16];
+        assert_eq!(free.ranges, whole);
+    }
+
+    #[test]
+    fn freed_range_is_reused() {
+        let mut free = FreeRanges::new(16);
+        let a = free.allocate(8).unwrap();
+        let _b = free.allocate(8).unwrap();
+        free.free(a);
+        assert_eq!(free.allocate(4), Some(0..4));
+        assert_eq!(free.allocate(4), Some(4..8));
+        assert_eq!(free.allocate(4), None);
+    }
+
+    #[test]
+    fn implicit_binding_size_is_clamped_to_buffer() {
+        let size = wgt::BufferSize::new;
+        // A buffer at the start of its block, where an implicit size used to be kept.
+        assert_eq!(binding_in_block(0, 64, 16, None), (16, size(48)));
+        assert_eq!(binding_in_block(256, 64, 0, None), (256, size(64)));
+        assert_eq!(binding_in_block(256, 64, 16, size(32)), (272, size(32)));
+    }
+}
@@ -0,0 +1,426 @@
+//! Compact binary encoding of traces.
+//!
+//! A binary trace is a single file made of a header followed by a sequence of frames:
+//!
+//! - The header is [`MAGIC`] followed by the format [`VERSION`] as a little-endian `u32`.
+//! - Every frame starts with a one-byte [`FrameKind`] tag and the length of its payload
+//!   as a little-endian `u32`, followed by the payload itself.
+//!
+//! A [`FrameKind::Data`] frame holds a data blob: its name, as a length-prefixed UTF-8
+//! string, followed by its contents. Actions refer to blobs by name, exactly like RON
+//! traces refer to the files next to them, and a blob is always written before the
+//! first action that uses it. The writer deduplicates blobs by content, so uploading
+//! the same data many times only stores it once.
+//!
+//! A [`FrameKind::Action`] frame holds a single [`Action`] encoded with `bincode`.
+//!
+//! Frames are self-delimiting, so a trace can be appended to while the application
+//! runs and read back one frame at a time.
+
+use alloc::{string::String, vec::Vec};
+use std::io;
+#[cfg(feature = "replay")]
+use std::io::Read;
+
+use thiserror::Error;
+
+use super::Action;
+
+/// Name of the trace file inside the trace directory.
+pub const FILE_NAME: &str = "trace.bin";
+
+/// Bytes every binary trace starts with.
+pub const MAGIC: [u8; 8] = *b"WGPUTRCE";
+
+/// Version of the binary format written by this version of `wgpu-core`.
+pub const VERSION: u32 = 1;
+
+const FRAME_HEADER_SIZE: usize = 1 + size_of::<u32>();
+
+/// The kind of payload stored in a frame.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameKind {
+    /// A named data blob.
+    Data = 0,
+    /// A `bincode`-encoded [`Action`].
+    Action = 1,
+}
+
+impl FrameKind {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Data),
+            1 => Some(Self::Action),
+            _ => None,
+        }
+    }
+}
+
+/// A single decoded frame of a binary trace.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum Frame {
+    Data { name: String, data: Vec<u8> },
+    Action(Action<'static>),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum BinaryTraceError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Not a binary trace")]
+    InvalidMagic,
+    #[error("Unsupported binary trace version {0}, expected {VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("Unknown frame kind {0}")]
+    UnknownFrameKind(u8),
+    #[error("Frame is truncated")]
+    TruncatedFrame,
+    #[error("Frame of {0} bytes is too large")]
+    FrameTooLarge(usize),
+    #[error("Data blob name is not valid UTF-8")]
+    InvalidBlobName,
+    #[error("Failed to encode action: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+    #[error("Failed to decode action: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
+}
+
+fn bincode_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+}
+
+#[cfg(feature = "trace")]
+pub use writer::Writer;
+
+#[cfg(feature = "trace")]
+mod writer {
+    use alloc::{format, string::String, vec::Vec};
+    use core::hash::BuildHasher as _;
+    use std::{
+        fs::File,
+        io::{Read as _, Seek as _, SeekFrom, Write as _},
+        path::Path,
+    };
+
+    use super::{BinaryTraceError, FrameKind, FRAME_HEADER_SIZE, MAGIC, VERSION};
+    use crate::{device::trace::Action, FastHashMap};
+
+    #[derive(Debug)]
+    struct StoredBlob {
+        name: String,
+        /// Offset of the blob contents in the trace file.
+        offset: u64,
+        size: usize,
+    }
+
+    /// Streams actions and data blobs into a binary trace file.
+    #[derive(Debug)]
+    pub struct Writer {
+        file: File,
+        /// Separate handle used to read back stored blobs when deduplicating.
+        reader: File,
+        /// Size of everything written so far.
+        offset: u64,
+        /// Blobs written so far, keyed by a hash of their contents.
+        blobs: FastHashMap<u64, Vec<StoredBlob>>,
+        next_blob_id: usize,
+    }
+
+    impl Writer {
+        /// Creates the trace file at `path` and writes the header.
+        pub fn new(path: &Path) -> Result<Self, BinaryTraceError> {
+            let mut file = File::create(path)?;
+            let reader = File::open(path)?;
+            let mut header = Vec::with_capacity(MAGIC.len() + size_of::<u32>());
+            header.extend_from_slice(&MAGIC);
+            header.extend_from_slice(&VERSION.to_le_bytes());
+            file.write_all(&header)?;
+            Ok(Self {
+                file,
+                reader,
+                offset: header.len() as u64,
+                blobs: FastHashMap::default(),
+                next_blob_id: 0,
+            })
+        }
+
+        /// Stores a data blob and returns the name actions should use to refer to it.
+        ///
+        /// If a blob with the same contents was stored before, its name is returned
+        /// and nothing is written.
+        pub fn add_data(&mut self, kind: &str, data: &[u8]) -> Result<String, BinaryTraceError> {
+            let hash = self.blobs.hasher().hash_one(data);
+            if let Some(candidates) = self.blobs.get(&hash) {
+                for blob in candidates {
+                    if blob.size == data.len()
+                        && Self::stored_blob_eq(&mut self.reader, blob.offset, data)?
+                    {
+                        return Ok(blob.name.clone());
+                    }
+                }
+            }
+
+            self.next_blob_id += 1;
+            let name = format!("data{}.{}", self.next_blob_id, kind);
+            let mut payload = Vec::with_capacity(size_of::<u32>() + name.len() + data.len());
+            payload.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            payload.extend_from_slice(name.as_bytes());
+            let data_offset = self.offset + (FRAME_HEADER_SIZE + payload.len()) as u64;
+            payload.extend_from_slice(data);
+            self.write_frame(FrameKind::Data, &payload)?;
+
+            self.blobs.entry(hash).or_default().push(StoredBlob {
+                name: name.clone(),
+                offset: data_offset,
+                size: data.len(),
+            });
+            Ok(name)
+        }
+
+        /// Appends an action to the trace.
+        pub fn add_action(&mut self, action: &Action) -> Result<(), BinaryTraceError> {
+            let payload = bincode::serde::encode_to_vec(action, super::bincode_config())?;
+            self.write_frame(FrameKind::Action, &payload)
+        }
+
+        fn write_frame(&mut self, kind: FrameKind, payload: &[u8]) -> Result<(), BinaryTraceError> {
+            let size = u32::try_from(payload.len())
+                .map_err(|_| BinaryTraceError::FrameTooLarge(payload.len()))?;
+            // Write the whole frame at once, so that a concurrent reader never observes
+            // a frame header without at least the start of its payload.
+            let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+            frame.push(kind as u8);
+            frame.extend_from_slice(&size.to_le_bytes());
+            frame.extend_from_slice(payload);
+            self.file.write_all(&frame)?;
+            self.offset += frame.len() as u64;
+            Ok(())
+        }
+
+        fn stored_blob_eq(
+            reader: &mut File,
+            offset: u64,
+            data: &[u8],
+        ) -> Result<bool, BinaryTraceError> {
+            let mut stored = alloc::vec![0; data.len()];
+            reader.seek(SeekFrom::Start(offset))?;
+            reader.read_exact(&mut stored)?;
+            Ok(stored == data)
+        }
+    }
+}
+
+/// Reads frames from a binary trace.
+#[cfg(feature = "replay")]
+#[derive(Debug)]
+pub struct Reader<R> {
+    inner: R,
+    /// Bytes of the next frame that have been read so far.
+    pending: Vec<u8>,
+}
+
+#[cfg(feature = "replay")]
+impl<R: Read> Reader<R> {
+    /// Checks the header of the trace and returns a reader positioned on the first frame.
+    pub fn new(mut inner: R) -> Result<Self, BinaryTraceError> {
+        let mut magic = [0; MAGIC.len()];
+        inner.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(BinaryTraceError::InvalidMagic);
+        }
+        let version = read_u32(&mut inner)?;
+        if version != VERSION {
+            return Err(BinaryTraceError::UnsupportedVersion(version));
+        }
+        Ok(Self {
+            inner,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Reads the next frame.
+    ///
+    /// Returns `Ok(None)` when the end of the trace is reached before a whole frame
+    /// could be read. The bytes of a partially written frame are kept, so if the trace
+    /// is still being written, calling this again later picks up the frames that have
+    /// been appended since. Once the trace is complete, [`Self::finish`] checks that it
+    /// didn't end in the middle of a frame.
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, BinaryTraceError> {
+        if !self.fill(FRAME_HEADER_SIZE)? {
+            return Ok(None);
+        }
+        let header = &self.pending[..FRAME_HEADER_SIZE];
+        let kind =
+            FrameKind::from_u8(header[0]).ok_or(BinaryTraceError::UnknownFrameKind(header[0]))?;
+        let size = u32::from_le_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if !self.fill(FRAME_HEADER_SIZE + size)? {
+            return Ok(None);
+        }
+        let rest = self.pending.split_off(FRAME_HEADER_SIZE + size);
+        let frame = core::mem::replace(&mut self.pending, rest);
+        let payload = &frame[FRAME_HEADER_SIZE..];
+
+        Ok(Some(match kind {
+            FrameKind::Data => {
+                let mut payload = payload;
+                let name_size =
+                    read_u32(&mut payload).map_err(|_| BinaryTraceError::TruncatedFrame)? as usize;
+                if payload.len() < name_size {
+                    return Err(BinaryTraceError::TruncatedFrame);
+                }
+                let (name, data) = payload.split_at(name_size);
+                let name =
+                    core::str::from_utf8(name).map_err(|_| BinaryTraceError::InvalidBlobName)?;
+                Frame::Data {
+                    name: name.into(),
+                    data: data.to_vec(),
+                }
+            }
+            FrameKind::Action => {
+                let (action, _) = bincode::serde::decode_from_slice(payload, bincode_config())?;
+                Frame::Action(action)
+            }
+        }))
+    }
+
+    /// Checks that the trace ended on a frame boundary.
+    ///
+    /// Call this once [`Self::next_frame`] returned `Ok(None)` for a trace that is no
+    /// longer being written.
+    pub fn finish(&self) -> Result<(), BinaryTraceError> {
+        if self.pending.is_empty() {
+            Ok(())
+        } else {
+            Err(BinaryTraceError::TruncatedFrame)
+        }
+    }
+
+    /// Reads until `len` bytes of the next frame are pending.
+    ///
+    /// Returns `false` if the end of the trace is reached first.
+    fn fill(&mut self, len: usize) -> io::Result<bool> {
+        if let Some(missing) = len.checked_sub(self.pending.len()) {
+            (&mut self.inner)
+                .take(missing as u64)
+                .read_to_end(&mut self.pending)?;
+        }
+        Ok(self.pending.len() >= len)
+    }
+}
+
+#[cfg(feature = "replay")]
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; size_of::<u32>()];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+#[cfg(all(test, feature = "trace", feature = "replay"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_with_deduplication() {
+        let path = std::env::temp_dir().join(alloc::format!(
+            "wgpu-core-binary-trace-{}.bin",
+            std::process::id()
+        ));
+        let mut writer = Writer::new(&path).unwrap();
+        let first = writer.add_data("bin", &[1, 2, 3, 4]).unwrap();
+        let other = writer.add_data("bin", &[5, 6]).unwrap();
+        let repeated = writer.add_data("bin", &[1, 2, 3, 4]).unwrap();
+        assert_eq!(first, repeated);
+        assert_ne!(first, other);
+        writer
+            .add_action(&Action::DestroyBuffer(crate::id::Id::zip(3, 1)))
+            .unwrap();
+        drop(writer);
+
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let mut reader = Reader::new(bytes.as_slice()).unwrap();
+        let mut blobs = Vec::new();
+        let mut actions = Vec::new();
+        while let Some(frame) = reader.next_frame().unwrap() {
+            match frame {
+                Frame::Data { name, data } => blobs.push((name, data)),
+                Frame::Action(action) => actions.push(action),
+            }
+        }
+        assert_eq!(
+            blobs,
+            [(first, alloc::vec![1, 2, 3, 4]), (other, alloc::vec![5, 6])]
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::DestroyBuffer(id)] if *id == crate::id::Id::zip(3, 1)
+        ));
+
+        // A frame that has only been partially written is reported, not skipped.
+        let mut reader = Reader::new(&bytes[..bytes.len() - 1]).unwrap();
+        while reader.next_frame().unwrap().is_some() {}
+        assert!(matches!(
+            reader.finish(),
+            Err(BinaryTraceError::TruncatedFrame)
+        ));
+    }
+
+    #[test]
+    fn read_while_written() {
+        use std::io::Write as _;
+
+        let path = std::env::temp_dir().join(alloc::format!(
+            "wgpu-core-live-binary-trace-{}.bin",
+            std::process::id()
+        ));
+        let mut writer = Writer::new(&path).unwrap();
+        writer.add_data("bin", &[1, 2, 3, 4]).unwrap();
+        writer
+            .add_action(&Action::DestroyBuffer(crate::id::Id::zip(3, 1)))
+            .unwrap();
+        writer.add_data("bin", &[5, 6]).unwrap();
+        drop(writer);
+        let bytes = std::fs::read(&path).unwrap();
+
+        // Append the trace a few bytes at a time, reading whatever frames are complete.
+        let header_size = MAGIC.len() + size_of::<u32>();
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&bytes[..header_size]).unwrap();
+        let mut reader = Reader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut frames = Vec::new();
+        for chunk in bytes[header_size..].chunks(3) {
+            file.write_all(chunk).unwrap();
+            while let Some(frame) = reader.next_frame().unwrap() {
+                frames.push(frame);
+            }
+        }
+        reader.finish().unwrap();
+        drop(file);
+        let _ = std::fs::remove_file(&path);
+
+        assert!(matches!(
+            frames.as_slice(),
+            [
+                Frame::Data { data: first, .. },
+                Frame::Action(Action::DestroyBuffer(_)),
+                Frame::Data { data: second, .. },
+            ] if first == &[1, 2, 3, 4] && second == &[5, 6]
+        ));
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            Reader::new(&b"[\n]"[..]),
+            Err(BinaryTraceError::Io(_))
+        ));
+        assert!(matches!(
+            Reader::new(&b"not a wgpu trace"[..]),
+            Err(BinaryTraceError::InvalidMagic)
+        ));
+    }
+}
@@ -1,4 +1,7 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    string::{String, ToString as _},
+    vec::Vec,
+};
 use core::{convert::Infallible, ops::Range};
 
 #[cfg(feature = "trace")]
@@ -6,18 +9,35 @@ use {alloc::borrow::Cow, std::io::Write as _};
 
 use crate::id;
 
+#[cfg(any(feature = "trace", feature = "replay"))]
+pub mod binary;
+#[cfg(any(feature = "trace", feature = "replay"))]
+mod readback;
+
+#[cfg(any(feature = "trace", feature = "replay"))]
+pub use readback::TextureReadBackError;
+
 //TODO: consider a readable Id that doesn't include the backend
 
 type FileName = String;
 
 pub const FILE_NAME: &str = "trace.ron";
 
+/// Environment variable that makes traces record the outputs the application observed.
+///
+/// When set to anything but `0`, traces also contain [`Action::ExpectBuffer`] and
+/// [`Action::ExpectSurfaceTexture`], which `player` uses to verify its replay, as if
+/// [`wgt::TraceOptions::record_outputs`] was set. Presented textures are only read back
+/// from surfaces configured with [`wgt::TextureUsages::COPY_SRC`].
+pub const OUTPUTS_ENV_VAR: &str = "WGPU_TRACE_OUTPUTS";
+
 #[cfg(feature = "trace")]
 pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
     label: crate::Label<'a>,
     context: &'a super::RenderPassContext,
     depth_read_only: bool,
     stencil_read_only: bool,
+    inherited_bind_group_layouts: &'a [Option<id::BindGroupLayoutId>],
 ) -> crate::command::RenderBundleEncoderDescriptor<'a> {
     crate::command::RenderBundleEncoderDescriptor {
         label,
@@ -31,6 +51,7 @@ pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
         }),
         sample_count: context.sample_count,
         multiview: context.multiview,
+        inherited_bind_group_layouts: Cow::Borrowed(inherited_bind_group_layouts),
     }
 }
 
@@ -157,9 +178,25 @@ pub enum Action<'a> {
         desc: crate::resource::TlasDescriptor<'a>,
     },
     DestroyTlas(id::TlasId),
+    /// The application read `data` from `range` of a buffer it had mapped for reading.
+    ///
+    /// Only recorded when the trace records outputs, see [`wgt::TraceOptions::record_outputs`].
+    ExpectBuffer {
+        id: id::BufferId,
+        range: Range<wgt::BufferAddress>,
+        data: FileName,
+    },
+    /// The surface texture `id` was presented with the given contents.
+    ///
+    /// The data holds the first mip level and array layer, with tightly packed rows.
+    /// Only recorded when the trace records outputs, see [`wgt::TraceOptions::record_outputs`].
+    ExpectSurfaceTexture {
+        id: id::TextureId,
+        data: FileName,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Command {
     CopyBufferToBuffer {
@@ -224,51 +261,164 @@ pub enum Command {
     },
 }
 
+impl Action<'_> {
+    /// Returns the names of the data blobs this action refers to.
+    pub fn data_names_mut(&mut self) -> impl Iterator<Item = &mut FileName> {
+        match self {
+            Action::CreateShaderModule { data, .. }
+            | Action::WriteBuffer { data, .. }
+            | Action::WriteTexture { data, .. }
+            | Action::ExpectBuffer { data, .. }
+            | Action::ExpectSurfaceTexture { data, .. } => core::slice::from_mut(data),
+            Action::CreateShaderModulePassthrough { data, .. } => data.as_mut_slice(),
+            _ => &mut [],
+        }
+        .iter_mut()
+    }
+}
+
+#[cfg(feature = "trace")]
+#[derive(Debug)]
+enum TraceWriter {
+    Ron {
+        path: std::path::PathBuf,
+        file: std::fs::File,
+        config: ron::ser::PrettyConfig,
+        binary_id: usize,
+    },
+    Binary(binary::Writer),
+}
+
 #[cfg(feature = "trace")]
 #[derive(Debug)]
 pub struct Trace {
-    path: std::path::PathBuf,
-    file: std::fs::File,
-    config: ron::ser::PrettyConfig,
-    binary_id: usize,
+    writer: TraceWriter,
+    record_outputs: bool,
+    /// The texture most recently acquired from each surface.
+    surface_textures: crate::FastHashMap<id::SurfaceId, id::TextureId>,
+    /// Set once writing to the trace failed. Nothing is written after that, so the
+    /// trace never refers to data that is missing from it.
+    failed: bool,
 }
 
 #[cfg(feature = "trace")]
 impl Trace {
-    pub fn new(path: std::path::PathBuf) -> Result<Self, std::io::Error> {
+    /// Starts a RON trace in the directory `path`.
+    ///
+    /// Outputs are recorded if `record_outputs` is set, or if [`OUTPUTS_ENV_VAR`] is.
+    pub fn new(path: std::path::PathBuf, record_outputs: bool) -> Result<Self, std::io::Error> {
         log::info!("Tracing into '{path:?}'");
         let mut file = std::fs::File::create(path.join(FILE_NAME))?;
         file.write_all(b"[\n")?;
         Ok(Self {
-            path,
-            file,
-            config: ron::ser::PrettyConfig::default(),
-            binary_id: 0,
+            writer: TraceWriter::Ron {
+                path,
+                file,
+                config: ron::ser::PrettyConfig::default(),
+                binary_id: 0,
+            },
+            record_outputs: record_outputs || Self::record_outputs_from_env(),
+            surface_textures: Default::default(),
+            failed: false,
         })
     }
 
+    /// Starts a binary trace in the directory `path`.
+    ///
+    /// Outputs are recorded if `record_outputs` is set, or if [`OUTPUTS_ENV_VAR`] is.
+    pub fn new_binary(
+        path: std::path::PathBuf,
+        record_outputs: bool,
+    ) -> Result<Self, binary::BinaryTraceError> {
+        log::info!("Tracing into '{path:?}' using the binary format");
+        Ok(Self {
+            writer: TraceWriter::Binary(binary::Writer::new(&path.join(binary::FILE_NAME))?),
+            record_outputs: record_outputs || Self::record_outputs_from_env(),
+            surface_textures: Default::default(),
+            failed: false,
+        })
+    }
+
+    fn record_outputs_from_env() -> bool {
+        std::env::var_os(OUTPUTS_ENV_VAR).is_some_and(|value| value != "0")
+    }
+
+    /// Whether buffer readbacks and presented surface textures should be recorded.
+    pub(crate) fn records_outputs(&self) -> bool {
+        self.record_outputs
+    }
+
+    /// Returns the id of the texture most recently acquired from `surface_id`.
+    pub(crate) fn surface_texture(&self, surface_id: id::SurfaceId) -> Option<id::TextureId> {
+        self.surface_textures.get(&surface_id).copied()
+    }
+
+    /// Stores a data blob and returns the name actions should use to refer to it.
+    ///
+    /// If the data can't be written, the trace stops, and the returned name is never
+    /// written to it.
     pub fn make_binary(&mut self, kind: &str, data: &[u8]) -> String {
-        self.binary_id += 1;
-        let name = std::format!("data{}.{}", self.binary_id, kind);
-        let _ = std::fs::write(self.path.join(&name), data);
-        name
+        if self.failed {
+            return String::new();
+        }
+        let result = match self.writer {
+            TraceWriter::Ron {
+                ref path,
+                ref mut binary_id,
+                ..
+            } => {
+                *binary_id += 1;
+                let name = std::format!("data{binary_id}.{kind}");
+                std::fs::write(path.join(&name), data)
+                    .map(|()| name)
+                    .map_err(|e| e.to_string())
+            }
+            TraceWriter::Binary(ref mut writer) => {
+                writer.add_data(kind, data).map_err(|e| e.to_string())
+            }
+        };
+        result.unwrap_or_else(|e| {
+            self.fail(&std::format!("Unable to write trace data: {e}"));
+            String::new()
+        })
     }
 
     pub(crate) fn add(&mut self, action: Action) {
-        match ron::ser::to_string_pretty(&action, self.config.clone()) {
-            Ok(string) => {
-                let _ = writeln!(self.file, "{string},");
-            }
-            Err(e) => {
-                log::warn!("RON serialization failure: {e:?}");
+        if self.failed {
+            return;
+        }
+        if let Action::GetSurfaceTexture { id, parent_id } = action {
+            self.surface_textures.insert(parent_id, id);
+        }
+        let result = match self.writer {
+            TraceWriter::Ron {
+                ref mut file,
+                ref config,
+                ..
+            } => match ron::ser::to_string_pretty(&action, config.clone()) {
+                Ok(string) => writeln!(file, "{string},").map_err(|e| e.to_string()),
+                Err(e) => Err(std::format!("RON serialization failure: {e:?}")),
+            },
+            TraceWriter::Binary(ref mut writer) => {
+                writer.add_action(&action).map_err(|e| e.to_string())
             }
+        };
+        if let Err(e) = result {
+            self.fail(&std::format!("Unable to write trace action: {e}"));
         }
     }
+
+    fn fail(&mut self, message: &str) {
+        log::error!("{message}; nothing more will be traced");
+        self.failed = true;
+    }
 }
 
 #[cfg(feature = "trace")]
 impl Drop for Trace {
     fn drop(&mut self) {
-        let _ = self.file.write_all(b"]");
+        if let TraceWriter::Ron { ref mut file, .. } = self.writer {
+            let _ = file.write_all(b"]");
+        }
     }
 }
@@ -0,0 +1,152 @@
+//! Synchronous texture readback, used to record and verify presented frames.
+
+use alloc::{borrow::Cow, sync::Arc, vec::Vec};
+
+use thiserror::Error;
+
+use crate::{
+    command::{copy_texture_to_buffer, CommandEncoderError},
+    device::{queue::QueueSubmitError, Device, DeviceError, WaitIdleError},
+    resource::{
+        self, CreateBufferError, DestroyedResourceError, InvalidResourceError,
+        MissingTextureUsageError, ParentDevice, RawResourceAccess, Texture,
+    },
+};
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum TextureReadBackError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error(transparent)]
+    MissingTextureUsage(#[from] MissingTextureUsageError),
+    #[error("Reading back multisampled textures or textures of format {0:?} is not supported")]
+    Unsupported(wgt::TextureFormat),
+    #[error("The queue of the device has been dropped")]
+    QueueDropped,
+    #[error(transparent)]
+    CreateBuffer(#[from] CreateBufferError),
+    #[error(transparent)]
+    Encoder(#[from] CommandEncoderError),
+    #[error(transparent)]
+    Submit(#[from] QueueSubmitError),
+    #[error(transparent)]
+    Wait(#[from] WaitIdleError),
+}
+
+impl Device {
+    /// Copies the first mip level and array layer of `texture` to the CPU.
+    ///
+    /// The copy is submitted to the queue like any other command buffer, and this
+    /// waits for it. This is only meant for tracing and replaying. The returned rows
+    /// are tightly packed.
+    pub(crate) fn read_back_texture(
+        self: &Arc<Self>,
+        texture: &Arc<Texture>,
+    ) -> Result<Vec<u8>, TextureReadBackError> {
+        self.check_is_valid()?;
+        texture.same_device(self)?;
+        texture.check_usage(wgt::TextureUsages::COPY_SRC)?;
+
+        let format = texture.desc.format;
+        let block_size = match format.block_copy_size(None) {
+            Some(size) if format.block_dimensions() == (1, 1) && texture.desc.sample_count == 1 => {
+                size
+            }
+            _ => return Err(TextureReadBackError::Unsupported(format)),
+        };
+        let queue = self.get_queue().ok_or(TextureReadBackError::QueueDropped)?;
+
+        let width = texture.desc.size.width;
+        let height = texture.desc.size.height;
+        let row_size = width * block_size;
+        let padded_row_size = row_size.next_multiple_of(wgt::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer_size = u64::from(padded_row_size) * u64::from(height);
+
+        let label = Some(Cow::Borrowed("(wgpu internal) trace readback"));
+        let buffer = self.create_buffer(&resource::BufferDescriptor {
+            label: label.clone(),
+            size: buffer_size,
+            usage: wgt::BufferUsages::MAP_READ | wgt::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })?;
+
+        let cmd_enc = self.create_command_encoder(&queue, &label, false)?;
+        cmd_enc
+            .data
+            .lock()
+            .record_with(|cmd_buf_data| {
+                // Only the commands of the application are traced.
+                #[cfg(feature = "trace")]
+                {
+                    cmd_buf_data.commands = None;
+                }
+                copy_texture_to_buffer(
+                    cmd_buf_data,
+                    self,
+                    &wgt::TexelCopyTextureInfo {
+                        texture: texture.clone(),
+                        mip_level: 0,
+                        origin: wgt::Origin3d::ZERO,
+                        aspect: wgt::TextureAspect::All,
+                    },
+                    &wgt::TexelCopyBufferInfo {
+                        buffer: buffer.clone(),
+                        layout: wgt::TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(padded_row_size),
+                            rows_per_image: None,
+                        },
+                    },
+                    &wgt::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                )
+            })
+            .map_err(CommandEncoderError::from)?;
+        let (cmd_buf, error) = cmd_enc.finish(&label);
+        if let Some(error) = error {
+            return Err(error.into());
+        }
+
+        let submit_index = queue.submit(&[Arc::new(cmd_buf)]).map_err(|(_, e)| e)?;
+        let (closures, result) = {
+            let snatch_guard = self.snatchable_lock.read();
+            let fence = self.fence.read();
+            self.maintain(
+                fence,
+                wgt::PollType::WaitForSubmissionIndex(submit_index),
+                snatch_guard,
+            )
+        };
+        closures.fire();
+        result?;
+
+        // The buffer is never seen by the application, so it is mapped directly
+        // rather than with `map_async`.
+        let snatch_guard = self.snatchable_lock.read();
+        let raw_buffer = buffer.try_raw(&snatch_guard)?;
+        let raw_device = self.raw();
+        let range = buffer.raw_offset..buffer.raw_offset + buffer_size;
+        let mapping = unsafe { raw_device.map_buffer(raw_buffer, range.clone()) }
+            .map_err(|e| self.handle_hal_error(e))?;
+        if !mapping.is_coherent {
+            unsafe { raw_device.invalidate_mapped_ranges(raw_buffer, &[range]) };
+        }
+        let padded =
+            unsafe { core::slice::from_raw_parts(mapping.ptr.as_ptr(), buffer_size as usize) };
+        let data = padded
+            .chunks_exact(padded_row_size as usize)
+            .flat_map(|row| &row[..row_size as usize])
+            .copied()
+            .collect();
+        unsafe { raw_device.unmap_buffer(raw_buffer) };
+        Ok(data)
+    }
+}
@@ -0,0 +1,473 @@
+use alloc::{string::ToString as _, sync::Arc, vec::Vec};
+use core::mem::ManuallyDrop;
+
+use crate::{
+    api_log, conv,
+    device::{queue::Queue, Device},
+    global::Global,
+    id::{DeviceId, QueueId, TextureId, VideoDecoderId},
+    lock::{rank, Mutex},
+    resource::{Fallible, Labeled, ParentDevice, RawResourceAccess, Texture, TextureClearMode},
+    video::{
+        CreateVideoDecoderError, CreateVideoPictureError, DecodeVideoError, VideoDecoder,
+        VideoDecoderDescriptor, VideoPictureDescriptor,
+    },
+    LabelHelpers,
+};
+use wgt::Features;
+
+/// The usages a picture can have besides decoding.
+const PICTURE_USAGES: wgt::TextureUsages =
+    wgt::TextureUsages::TEXTURE_BINDING.union(wgt::TextureUsages::COPY_SRC);
+
+impl Device {
+    pub(crate) fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        if !self.features.contains(Features::VIDEO_DECODE) {
+            return None;
+        }
+        self.raw().video_decode_capabilities(codec, format)
+    }
+
+    fn create_video_decoder(
+        self: &Arc<Self>,
+        desc: &VideoDecoderDescriptor,
+    ) -> Result<Arc<VideoDecoder>, CreateVideoDecoderError> {
+        self.check_is_valid()?;
+        self.require_features(Features::VIDEO_DECODE)?;
+
+        if !matches!(
+            desc.format,
+            wgt::TextureFormat::NV12 | wgt::TextureFormat::P010
+        ) {
+            return Err(CreateVideoDecoderError::InvalidFormat(desc.format));
+        }
+        self.require_features(desc.format.required_features())?;
+
+        let caps = self
+            .video_decode_capabilities(desc.codec, desc.format)
+            .ok_or(CreateVideoDecoderError::UnsupportedCodec {
+                codec: desc.codec,
+                format: desc.format,
+            })?;
+
+        let extent = desc.max_coded_extent;
+        if extent.width < caps.min_coded_extent.width
+            || extent.height < caps.min_coded_extent.height
+            || extent.width > caps.max_coded_extent.width
+            || extent.height > caps.max_coded_extent.height
+            || extent.depth_or_array_layers != 1
+        {
+            return Err(CreateVideoDecoderError::InvalidCodedExtent {
+                requested: extent,
+                min: caps.min_coded_extent,
+                max: caps.max_coded_extent,
+            });
+        }
+        if desc.max_pictures == 0 {
+            return Err(CreateVideoDecoderError::ZeroPictures);
+        }
+        if desc.max_pictures > caps.max_pictures {
+            return Err(CreateVideoDecoderError::TooManyPictures {
+                requested: desc.max_pictures,
+                max: caps.max_pictures,
+            });
+        }
+        if desc.max_reference_pictures > caps.max_reference_pictures {
+            return Err(CreateVideoDecoderError::TooManyReferencePictures {
+                requested: desc.max_reference_pictures,
+                max: caps.max_reference_pictures,
+            });
+        }
+
+        let hal_desc = hal::VideoDecoderDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            codec: desc.codec,
+            format: desc.format,
+            max_coded_extent: desc.max_coded_extent,
+            max_pictures: desc.max_pictures,
+            max_reference_pictures: desc.max_reference_pictures,
+        };
+        let raw = unsafe { self.raw().create_video_decoder(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        Ok(Arc::new(VideoDecoder {
+            raw: ManuallyDrop::new(raw),
+            device: self.clone(),
+            desc: desc.map_label(|_| ()),
+            label: desc.label.to_string(),
+            pictures: Mutex::new(rank::VIDEO_DECODER_PICTURES, Vec::new()),
+        }))
+    }
+
+    fn create_video_picture(
+        self: &Arc<Self>,
+        decoder: &Arc<VideoDecoder>,
+        desc: &VideoPictureDescriptor,
+    ) -> Result<Arc<Texture>, CreateVideoPictureError> {
+        self.check_is_valid()?;
+        decoder.same_device(self)?;
+
+        if !PICTURE_USAGES.contains(desc.usage) {
+            return Err(CreateVideoPictureError::InvalidUsage(desc.usage));
+        }
+
+        let texture_desc = decoder.picture_texture_descriptor(desc);
+        let format_features = self.describe_format_features(texture_desc.format)?;
+        let hal_usage = conv::map_texture_usage(
+            texture_desc.usage,
+            texture_desc.format.into(),
+            format_features.flags,
+        );
+
+        let mut pictures = decoder.pictures.lock();
+        let slot = pictures
+            .iter()
+            .position(|picture| picture.strong_count() == 0)
+            .unwrap_or(pictures.len());
+        if slot as u32 >= decoder.desc.max_pictures {
+            return Err(CreateVideoPictureError::TooManyPictures {
+                decoder: decoder.error_ident(),
+                max: decoder.desc.max_pictures,
+            });
+        }
+
+        let hal_desc = hal::TextureDescriptor {
+            label: texture_desc.label.to_hal(self.instance_flags),
+            size: texture_desc.size,
+            mip_level_count: texture_desc.mip_level_count,
+            sample_count: texture_desc.sample_count,
+            dimension: texture_desc.dimension,
+            format: texture_desc.format,
+            usage: hal_usage,
+            memory_flags: hal::MemoryFlags::empty(),
+            view_formats: Vec::new(),
+        };
+        let raw = unsafe { self.raw().create_video_picture(decoder.raw(), &hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        // Pictures are only written by decodes, so there is nothing to initialize.
+        let texture = Arc::new(Texture::new(
+            self,
+            crate::resource::TextureInner::Native { raw },
+            hal_usage,
+            &texture_desc,
+            format_features,
+            TextureClearMode::None,
+            false,
+        ));
+
+        if slot == pictures.len() {
+            pictures.push(Arc::downgrade(&texture));
+        } else {
+            pictures[slot] = Arc::downgrade(&texture);
+        }
+        drop(pictures);
+
+        self.trackers
+            .lock()
+            .textures
+            .insert_single(&texture, wgt::TextureUses::UNINITIALIZED);
+
+        Ok(texture)
+    }
+}
+
+impl Queue {
+    fn decode_video(
+        &self,
+        decoder: &Arc<VideoDecoder>,
+        desc: &wgt::VideoDecodeDescriptor<Arc<Texture>>,
+    ) -> Result<(), DecodeVideoError> {
+        profiling::scope!("Queue::decode_video");
+
+        // Pictures are decoded for the main queue, see `Queue::main`.
+        if let Some(ref main) = self.main {
+            return main.decode_video(decoder, desc);
+        }
+
+        let device = &self.device;
+        device.check_is_valid()?;
+        decoder.same_device_as(self)?;
+
+        if desc.bitstream.is_empty() {
+            return Err(DecodeVideoError::EmptyBitstream);
+        }
+
+        let codec = decoder.desc.codec;
+        let codecs = core::iter::once(desc.picture.codec())
+            .chain(desc.target_reference.iter().map(|info| info.codec()))
+            .chain(
+                desc.references
+                    .iter()
+                    .map(|reference| reference.info.codec()),
+            );
+        for found in codecs {
+            if found != codec {
+                return Err(DecodeVideoError::CodecMismatch {
+                    decoder: decoder.error_ident(),
+                    expected: codec,
+                    found,
+                });
+            }
+        }
+
+        let extent = desc.picture.coded_extent();
+        let max = decoder.desc.max_coded_extent;
+        if extent.width > max.width || extent.height > max.height {
+            return Err(DecodeVideoError::CodedExtentTooLarge {
+                decoder: decoder.error_ident(),
+                extent,
+                max,
+            });
+        }
+
+        if desc.references.len() > decoder.desc.max_reference_pictures as usize {
+            return Err(DecodeVideoError::TooManyReferences {
+                decoder: decoder.error_ident(),
+                count: desc.references.len(),
+                max: decoder.desc.max_reference_pictures,
+            });
+        }
+
+        let count = desc.references.len();
+        let check_index = |index: usize| {
+            if index < count {
+                Ok(())
+            } else {
+                Err(DecodeVideoError::InvalidReferenceIndex { index, count })
+            }
+        };
+        match desc.picture {
+            wgt::VideoPictureInfo::H264(_) => {}
+            wgt::VideoPictureInfo::H265(ref picture) => {
+                for set in [
+                    picture.ref_pic_set_st_curr_before,
+                    picture.ref_pic_set_st_curr_after,
+                    picture.ref_pic_set_lt_curr,
+                ] {
+                    if set.len() > 8 {
+                        return Err(DecodeVideoError::ReferencePictureSetTooLong(set.len()));
+                    }
+                    for &index in set {
+                        check_index(usize::from(index))?;
+                    }
+                }
+            }
+            wgt::VideoPictureInfo::Av1(ref picture) => {
+                for index in picture.reference_name_indices.into_iter().flatten() {
+                    check_index(usize::from(index))?;
+                }
+            }
+        }
+
+        let textures = core::iter::once(&desc.target)
+            .chain(desc.references.iter().map(|reference| &reference.picture))
+            .collect::<Vec<_>>();
+        let mut slots = Vec::with_capacity(textures.len());
+        for texture in textures.iter() {
+            texture.same_device_as(self)?;
+            let slot = decoder
+                .slot_of(texture)
+                .ok_or_else(|| DecodeVideoError::NotAPicture {
+                    texture: texture.error_ident(),
+                    decoder: decoder.error_ident(),
+                })?;
+            if slots.contains(&slot) {
+                return Err(DecodeVideoError::DuplicatePicture(texture.error_ident()));
+            }
+            slots.push(slot);
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+        let raw_textures = textures
+            .iter()
+            .map(|texture| texture.try_raw(&snatch_guard))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Fence lock must be acquired after the snatch lock everywhere to avoid deadlocks.
+        // Holding it for writing keeps submissions from being made until the decode is, so
+        // that the next one waits for it.
+        let fence = device.fence.write();
+        let last_submission = self.last_submission_index();
+
+        let mut pending_writes = self.pending_writes.lock();
+        let mut trackers = device.trackers.lock();
+
+        let pictures = textures
+            .iter()
+            .zip(raw_textures)
+            .zip(slots)
+            .map(|((texture, raw), slot)| {
+                let usage = trackers
+                    .textures
+                    .set_single(
+                        texture,
+                        texture.full_range.clone(),
+                        wgt::TextureUses::RESOURCE,
+                    )
+                    .next()
+                    .map_or(
+                        hal::StateTransition {
+                            from: wgt::TextureUses::RESOURCE,
+                            to: wgt::TextureUses::RESOURCE,
+                        },
+                        |transition| transition.usage,
+                    );
+                (raw, slot, usage)
+            })
+            .collect::<Vec<_>>();
+        drop(trackers);
+
+        let hal_picture =
+            |&(texture, slot, ref usage): &(_, _, hal::StateTransition<_>)| hal::VideoPicture {
+                texture,
+                slot,
+                usage: usage.clone(),
+            };
+        let references = pictures[1..]
+            .iter()
+            .zip(desc.references)
+            .map(|(picture, reference)| hal::VideoReference {
+                picture: hal_picture(picture),
+                info: reference.info,
+            })
+            .collect::<Vec<_>>();
+        let hal_desc = hal::VideoDecodeDescriptor {
+            bitstream: desc.bitstream,
+            target: hal_picture(&pictures[0]),
+            target_reference: desc.target_reference,
+            references: &references,
+            picture: desc.picture,
+        };
+
+        unsafe {
+            self.raw()
+                .decode_video(decoder.raw(), &hal_desc, (fence.as_ref(), last_submission))
+        }
+        .map_err(|e| device.handle_hal_error(e))?;
+
+        // The pictures are kept alive until the next submission, which waits for the decode,
+        // is done with them.
+        pending_writes.activate();
+        for texture in textures {
+            pending_writes.insert_texture(texture);
+        }
+
+        Ok(())
+    }
+}
+
+impl Global {
+    pub fn device_video_decode_capabilities(
+        &self,
+        device_id: DeviceId,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        let device = self.hub.devices.get(device_id);
+        device.video_decode_capabilities(codec, format)
+    }
+
+    pub fn device_create_video_decoder(
+        &self,
+        device_id: DeviceId,
+        desc: &VideoDecoderDescriptor,
+        id_in: Option<VideoDecoderId>,
+    ) -> (VideoDecoderId, Option<CreateVideoDecoderError>) {
+        profiling::scope!("Device::create_video_decoder");
+
+        let fid = self.hub.video_decoders.prepare(id_in);
+
+        let error = 'error: {
+            let device = self.hub.devices.get(device_id);
+
+            let decoder = match device.create_video_decoder(desc) {
+                Ok(decoder) => decoder,
+                Err(e) => break 'error e,
+            };
+
+            let id = fid.assign(Fallible::Valid(decoder));
+            api_log!("Device::create_video_decoder -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(error.to_string())));
+        (id, Some(error))
+    }
+
+    pub fn video_decoder_drop(&self, video_decoder_id: VideoDecoderId) {
+        profiling::scope!("VideoDecoder::drop");
+        api_log!("VideoDecoder::drop {video_decoder_id:?}");
+
+        let _decoder = self.hub.video_decoders.remove(video_decoder_id);
+    }
+
+    pub fn video_decoder_create_picture(
+        &self,
+        video_decoder_id: VideoDecoderId,
+        desc: &VideoPictureDescriptor,
+        id_in: Option<TextureId>,
+    ) -> (TextureId, Option<CreateVideoPictureError>) {
+        profiling::scope!("VideoDecoder::create_picture");
+
+        let fid = self.hub.textures.prepare(id_in);
+
+        let error = 'error: {
+            let decoder = match self.hub.video_decoders.get(video_decoder_id).get() {
+                Ok(decoder) => decoder,
+                Err(e) => break 'error e.into(),
+            };
+
+            let texture = match decoder.device.create_video_picture(&decoder, desc) {
+                Ok(texture) => texture,
+                Err(e) => break 'error e,
+            };
+
+            let id = fid.assign(Fallible::Valid(texture));
+            api_log!("VideoDecoder::create_picture -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(error.to_string())));
+        (id, Some(error))
+    }
+
+    pub fn queue_decode_video(
+        &self,
+        queue_id: QueueId,
+        video_decoder_id: VideoDecoderId,
+        desc: &wgt::VideoDecodeDescriptor<TextureId>,
+    ) -> Result<(), DecodeVideoError> {
+        api_log!("Queue::decode_video {video_decoder_id:?}");
+
+        let hub = &self.hub;
+        let queue = hub.queues.get(queue_id);
+        let decoder = hub.video_decoders.get(video_decoder_id).get()?;
+
+        let references = desc
+            .references
+            .iter()
+            .map(|reference| {
+                Ok(wgt::VideoReference {
+                    picture: hub.textures.get(reference.picture).get()?,
+                    info: reference.info,
+                })
+            })
+            .collect::<Result<Vec<_>, DecodeVideoError>>()?;
+        let desc = wgt::VideoDecodeDescriptor {
+            bitstream: desc.bitstream,
+            target: hub.textures.get(desc.target).get()?,
+            target_reference: desc.target_reference,
+            references: &references,
+            picture: desc.picture,
+        };
+
+        queue.decode_video(&decoder, &desc)
+    }
+}
@@ -2,6 +2,7 @@ use alloc::{borrow::ToOwned as _, sync::Arc};
 use core::fmt;
 
 use crate::{
+    device::pipeline_workers::PipelineWorkers,
     hub::{Hub, HubReport},
     instance::{Instance, Surface},
     registry::{Registry, RegistryReport},
@@ -26,6 +27,7 @@ impl GlobalReport {
 pub struct Global {
     pub(crate) surfaces: Registry<Arc<Surface>>,
     pub(crate) hub: Hub,
+    pub(crate) pipeline_workers: PipelineWorkers,
     // the instance must be dropped last
     pub instance: Instance,
 }
@@ -37,6 +39,7 @@ impl Global {
             instance: Instance::new(name, instance_desc),
             surfaces: Registry::new(),
             hub: Hub::new(),
+            pipeline_workers: PipelineWorkers::default(),
         }
     }
 
@@ -50,6 +53,7 @@ impl Global {
             instance: Instance::from_hal_instance::<A>(name.to_owned(), hal_instance),
             surfaces: Registry::new(),
             hub: Hub::new(),
+            pipeline_workers: PipelineWorkers::default(),
         }
     }
 
@@ -69,6 +73,7 @@ impl Global {
             instance,
             surfaces: Registry::new(),
             hub: Hub::new(),
+            pipeline_workers: PipelineWorkers::default(),
         }
     }
 
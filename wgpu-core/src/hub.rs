@@ -114,6 +114,7 @@ use crate::{
         Blas, Buffer, ExternalTexture, Fallible, QuerySet, Sampler, StagingBuffer, Texture,
         TextureView, Tlas,
     },
+    video::VideoDecoder,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -189,6 +190,7 @@ pub struct Hub {
     pub(crate) samplers: Registry<Fallible<Sampler>>,
     pub(crate) blas_s: Registry<Fallible<Blas>>,
     pub(crate) tlas_s: Registry<Fallible<Tlas>>,
+    pub(crate) video_decoders: Registry<Fallible<VideoDecoder>>,
 }
 
 impl Hub {
@@ -216,6 +218,7 @@ impl Hub {
             samplers: Registry::new(),
             blas_s: Registry::new(),
             tlas_s: Registry::new(),
+            video_decoders: Registry::new(),
         }
     }
 
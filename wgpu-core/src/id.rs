@@ -279,6 +279,7 @@ ids! {
     pub type QuerySetId QuerySet;
     pub type BlasId Blas;
     pub type TlasId Tlas;
+    pub type VideoDecoderId VideoDecoder;
 }
 
 #[test]
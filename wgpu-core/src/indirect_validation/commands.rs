@@ -0,0 +1,497 @@
+use super::{
+    draw::{create_bind_group_layout, create_validation_module, create_validation_pipeline},
+    utils::{BufferBarrierScratch, BufferBarriers},
+    CreateIndirectValidationPipelineError, DrawResources,
+};
+use crate::{
+    device::{queue::TempResource, Device, DeviceError},
+    resource::{Buffer, StagingBuffer},
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{mem::size_of, num::NonZeroU64};
+
+/// Byte size of a `D3D12_VERTEX_BUFFER_VIEW` or `D3D12_INDEX_BUFFER_VIEW`.
+const BUFFER_VIEW_SIZE: u64 = 16;
+
+/// Holds all device-level resources that are needed to translate the command
+/// streams of `execute_indirect` into native records.
+///
+/// Records are read in the [`wgt::IndirectCommandLayout`] format, validated, and
+/// written in the format expected by [`hal::CommandEncoder::execute_indirect`].
+/// The draw of an invalid record is replaced with one drawing nothing.
+#[derive(Debug)]
+pub(crate) struct Commands {
+    module: Box<dyn hal::DynShaderModule>,
+    params_bind_group_layout: Box<dyn hal::DynBindGroupLayout>,
+    src_bind_group_layout: Box<dyn hal::DynBindGroupLayout>,
+    dst_bind_group_layout: Box<dyn hal::DynBindGroupLayout>,
+    pipeline_layout: Box<dyn hal::DynPipelineLayout>,
+    pipeline: Box<dyn hal::DynComputePipeline>,
+
+    /// Byte size of the special constants preceding the draw arguments of a
+    /// native record.
+    special_constants_size: u64,
+}
+
+impl Commands {
+    pub(crate) fn new(
+        device: &dyn hal::DynDevice,
+        required_features: &wgt::Features,
+        backend: wgt::Backend,
+    ) -> Result<Self, DeviceError> {
+        match Self::create(device, required_features, backend) {
+            Ok(commands) => Ok(commands),
+            Err(e) => {
+                log::error!("indirect-commands error: {e:?}");
+                Err(DeviceError::Lost)
+            }
+        }
+    }
+
+    fn create(
+        device: &dyn hal::DynDevice,
+        required_features: &wgt::Features,
+        backend: wgt::Backend,
+    ) -> Result<Self, CreateIndirectValidationPipelineError> {
+        let module = create_validation_module(device, include_str!("./translate_commands.wgsl"))?;
+
+        let params_bind_group_layout = create_bind_group_layout(
+            device,
+            true,
+            false,
+            wgt::BufferSize::new(size_of::<Params>() as u64).unwrap(),
+        )?;
+        let src_bind_group_layout =
+            create_bind_group_layout(device, true, false, wgt::BufferSize::new(4).unwrap())?;
+        let dst_bind_group_layout =
+            create_bind_group_layout(device, false, false, wgt::BufferSize::new(4).unwrap())?;
+
+        let pipeline_layout_desc = hal::PipelineLayoutDescriptor {
+            label: None,
+            flags: hal::PipelineLayoutFlags::empty(),
+            bind_group_layouts: &[
+                params_bind_group_layout.as_ref(),
+                src_bind_group_layout.as_ref(),
+                dst_bind_group_layout.as_ref(),
+            ],
+            push_constant_ranges: &[],
+        };
+        let pipeline_layout = unsafe {
+            device
+                .create_pipeline_layout(&pipeline_layout_desc)
+                .map_err(DeviceError::from_hal)?
+        };
+
+        let supports_indirect_first_instance =
+            required_features.contains(wgt::Features::INDIRECT_FIRST_INSTANCE);
+        let write_d3d12_special_constants = backend == wgt::Backend::Dx12;
+        let pipeline = create_validation_pipeline(
+            device,
+            module.as_ref(),
+            pipeline_layout.as_ref(),
+            supports_indirect_first_instance,
+            write_d3d12_special_constants,
+        )?;
+
+        Ok(Self {
+            module,
+            params_bind_group_layout,
+            src_bind_group_layout,
+            dst_bind_group_layout,
+            pipeline_layout,
+            pipeline,
+
+            special_constants_size: if write_d3d12_special_constants {
+                3 * size_of::<u32>() as u64
+            } else {
+                0
+            },
+        })
+    }
+
+    /// Returns the byte size of a native record for `layout`.
+    pub(crate) fn native_stride(&self, layout: &wgt::IndirectCommandLayout) -> u64 {
+        let draw_size = if layout.indexed {
+            size_of::<wgt::DrawIndexedIndirectArgs>()
+        } else {
+            size_of::<wgt::DrawIndirectArgs>()
+        };
+        layout.push_constant_size as u64
+            + layout.vertex_buffers.count_ones() as u64 * BUFFER_VIEW_SIZE
+            + layout.index_buffer as u64 * BUFFER_VIEW_SIZE
+            + self.special_constants_size
+            + draw_size as u64
+    }
+
+    /// Returns the offset and size of the binding of `src_buffer` that
+    /// contains `size` bytes of records at `offset`.
+    ///
+    /// Both are relative to the HAL buffer.
+    pub(crate) fn src_binding_range(
+        device: &Device,
+        src_buffer: &Buffer,
+        offset: u64,
+        size: u64,
+    ) -> (u64, u64) {
+        let alignment = device.adapter.limits().min_storage_buffer_offset_alignment as u64;
+        let start = src_buffer.raw_offset + offset;
+        let binding_start = start - start % alignment;
+        (binding_start, start + size - binding_start)
+    }
+
+    /// Adds the translation of `max_count` records of `src_buffer` at `offset`.
+    ///
+    /// `src_raw` is the HAL buffer of `src_buffer`.
+    ///
+    /// Returns the index of the buffer that will hold the native records in
+    /// `resources`, see [`DrawResources::get_commands_buffer`].
+    pub(crate) fn add(
+        &self,
+        device: &Device,
+        resources: &mut DrawResources,
+        batcher: &mut CommandsBatcher,
+        src_buffer: &Buffer,
+        src_raw: &dyn hal::DynBuffer,
+        offset: u64,
+        max_count: u32,
+        mut params: Params,
+    ) -> Result<usize, DeviceError> {
+        let raw_device = device.raw();
+
+        let src_size = u64::from(params.src_stride) * 4 * max_count as u64;
+        let (binding_start, binding_size) =
+            Self::src_binding_range(device, src_buffer, offset, src_size);
+        params.src_offset = ((src_buffer.raw_offset + offset - binding_start) / 4) as u32;
+        params.record_count = max_count;
+
+        let dst_size = u64::from(params.dst_stride) * 4 * max_count as u64;
+
+        let create_buffer = |size: u64, usage: wgt::BufferUses| unsafe {
+            raw_device.create_buffer(&hal::BufferDescriptor {
+                label: None,
+                size,
+                usage,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        };
+        let params_buffer = create_buffer(
+            size_of::<Params>() as u64,
+            wgt::BufferUses::COPY_DST | wgt::BufferUses::STORAGE_READ_ONLY,
+        )
+        .map_err(DeviceError::from_hal)?;
+        let dst_buffer = match create_buffer(
+            dst_size,
+            wgt::BufferUses::INDIRECT | wgt::BufferUses::STORAGE_READ_WRITE,
+        ) {
+            Ok(dst_buffer) => dst_buffer,
+            Err(e) => {
+                unsafe { raw_device.destroy_buffer(params_buffer) };
+                return Err(DeviceError::from_hal(e));
+            }
+        };
+
+        let create_bind_group =
+            |layout: &dyn hal::DynBindGroupLayout,
+             binding: hal::BufferBinding<'_, dyn hal::DynBuffer>| unsafe {
+                raw_device.create_bind_group(&hal::BindGroupDescriptor {
+                    label: None,
+                    layout,
+                    entries: &[hal::BindGroupEntry {
+                        binding: 0,
+                        resource_index: 0,
+                        count: 1,
+                    }],
+                    buffers: &[binding],
+                    samplers: &[],
+                    textures: &[],
+                    acceleration_structures: &[],
+                    external_textures: &[],
+                })
+            };
+        // SAFETY: We just created the buffers with these sizes, and the
+        // records were checked to be within `src_buffer`.
+        let bind_groups = [
+            create_bind_group(
+                self.params_bind_group_layout.as_ref(),
+                hal::BufferBinding::new_unchecked(
+                    params_buffer.as_ref(),
+                    0,
+                    NonZeroU64::new(size_of::<Params>() as u64),
+                ),
+            ),
+            create_bind_group(
+                self.src_bind_group_layout.as_ref(),
+                hal::BufferBinding::new_unchecked(
+                    src_raw,
+                    binding_start,
+                    NonZeroU64::new(binding_size),
+                ),
+            ),
+            create_bind_group(
+                self.dst_bind_group_layout.as_ref(),
+                hal::BufferBinding::new_unchecked(
+                    dst_buffer.as_ref(),
+                    0,
+                    NonZeroU64::new(dst_size),
+                ),
+            ),
+        ];
+        let entry = match bind_groups {
+            [Ok(params_bind_group), Ok(src_bind_group), Ok(dst_bind_group)] => CommandsEntry {
+                params_buffer,
+                dst_buffer,
+                bind_groups: [params_bind_group, src_bind_group, dst_bind_group],
+            },
+            bind_groups => {
+                let mut error = None;
+                for bind_group in bind_groups {
+                    match bind_group {
+                        Ok(bind_group) => unsafe { raw_device.destroy_bind_group(bind_group) },
+                        Err(e) => error = Some(e),
+                    }
+                }
+                unsafe {
+                    raw_device.destroy_buffer(params_buffer);
+                    raw_device.destroy_buffer(dst_buffer);
+                }
+                return Err(DeviceError::from_hal(error.unwrap()));
+            }
+        };
+
+        let entry_index = resources.push_commands_entry(entry);
+        batcher.translations.push(Translation {
+            entry_index,
+            params,
+        });
+        Ok(entry_index)
+    }
+
+    /// Injects a compute pass that will translate all command streams executed in the current render pass.
+    pub(crate) fn inject_translation_pass(
+        &self,
+        device: &Arc<Device>,
+        resources: &DrawResources,
+        temp_resources: &mut Vec<TempResource>,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        batcher: CommandsBatcher,
+    ) -> Result<(), DeviceError> {
+        let translations = batcher.translations;
+
+        if translations.is_empty() {
+            return Ok(());
+        }
+
+        let params_size = size_of::<Params>();
+        let mut staging_buffer = StagingBuffer::new(
+            device,
+            NonZeroU64::new((translations.len() * params_size) as u64).unwrap(),
+        )?;
+        let data: Vec<u8> = translations
+            .iter()
+            .flat_map(|translation| bytemuck::bytes_of(&translation.params).iter().copied())
+            .collect();
+        staging_buffer.write(&data);
+        let staging_buffer = staging_buffer.flush();
+
+        let buffer_barrier_scratch = &mut BufferBarrierScratch::new();
+
+        BufferBarriers::new(buffer_barrier_scratch)
+            .extend(core::iter::once(hal::BufferBarrier {
+                buffer: staging_buffer.raw(),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::MAP_WRITE,
+                    to: wgt::BufferUses::COPY_SRC,
+                },
+            }))
+            .extend(translations.iter().map(|translation| hal::BufferBarrier {
+                buffer: resources.get_commands_params_buffer(translation.entry_index),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::STORAGE_READ_ONLY,
+                    to: wgt::BufferUses::COPY_DST,
+                },
+            }))
+            .encode(encoder);
+
+        for (i, translation) in translations.iter().enumerate() {
+            unsafe {
+                encoder.copy_buffer_to_buffer(
+                    staging_buffer.raw(),
+                    resources.get_commands_params_buffer(translation.entry_index),
+                    &[hal::BufferCopy {
+                        src_offset: (i * params_size) as u64,
+                        dst_offset: 0,
+                        size: NonZeroU64::new(params_size as u64).unwrap(),
+                    }],
+                );
+            }
+        }
+
+        BufferBarriers::new(buffer_barrier_scratch)
+            .extend(translations.iter().map(|translation| hal::BufferBarrier {
+                buffer: resources.get_commands_params_buffer(translation.entry_index),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::COPY_DST,
+                    to: wgt::BufferUses::STORAGE_READ_ONLY,
+                },
+            }))
+            .extend(translations.iter().map(|translation| hal::BufferBarrier {
+                buffer: resources.get_commands_buffer(translation.entry_index),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::INDIRECT,
+                    to: wgt::BufferUses::STORAGE_READ_WRITE,
+                },
+            }))
+            .encode(encoder);
+
+        temp_resources.push(TempResource::StagingBuffer(staging_buffer));
+
+        let desc = hal::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        };
+        unsafe {
+            encoder.begin_compute_pass(&desc);
+            encoder.set_compute_pipeline(self.pipeline.as_ref());
+        }
+
+        for translation in translations.iter() {
+            let bind_groups = resources.get_commands_bind_groups(translation.entry_index);
+            for (index, bind_group) in bind_groups.iter().enumerate() {
+                unsafe {
+                    encoder.set_bind_group(
+                        self.pipeline_layout.as_ref(),
+                        index as u32,
+                        Some(bind_group.as_ref()),
+                        &[],
+                    );
+                }
+            }
+            unsafe {
+                encoder.dispatch([translation.params.record_count.div_ceil(64), 1, 1]);
+            }
+        }
+
+        unsafe {
+            encoder.end_compute_pass();
+        }
+
+        BufferBarriers::new(buffer_barrier_scratch)
+            .extend(translations.iter().map(|translation| hal::BufferBarrier {
+                buffer: resources.get_commands_buffer(translation.entry_index),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::STORAGE_READ_WRITE,
+                    to: wgt::BufferUses::INDIRECT,
+                },
+            }))
+            .encode(encoder);
+
+        Ok(())
+    }
+
+    pub(crate) fn dispose(self, device: &dyn hal::DynDevice) {
+        let Commands {
+            module,
+            params_bind_group_layout,
+            src_bind_group_layout,
+            dst_bind_group_layout,
+            pipeline_layout,
+            pipeline,
+            special_constants_size: _,
+        } = self;
+
+        unsafe {
+            device.destroy_compute_pipeline(pipeline);
+            device.destroy_pipeline_layout(pipeline_layout);
+            device.destroy_bind_group_layout(params_bind_group_layout);
+            device.destroy_bind_group_layout(src_bind_group_layout);
+            device.destroy_bind_group_layout(dst_bind_group_layout);
+            device.destroy_shader_module(module);
+        }
+    }
+}
+
+/// A vertex buffer slot used by the pipeline.
+///
+/// This must match the `VertexSlot` struct used by the shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct VertexSlot {
+    pub(crate) stride: u32,
+    pub(crate) last_stride: u32,
+    /// 0 if the pipeline doesn't use the slot, 1 if it steps per vertex and 2
+    /// if it steps per instance.
+    pub(crate) mode: u32,
+    pub(crate) padding: u32,
+}
+
+/// Describes the translation of a command stream.
+///
+/// This must match the `Params` struct used by the shader. Limits of
+/// `u32::MAX` are unbounded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct Params {
+    /// Filled in by [`Commands::add`].
+    pub(crate) src_offset: u32,
+    /// Filled in by [`Commands::add`].
+    pub(crate) record_count: u32,
+    pub(crate) src_stride: u32,
+    pub(crate) dst_stride: u32,
+    pub(crate) push_constant_count: u32,
+    pub(crate) vertex_buffers: u32,
+    pub(crate) index_buffer: u32,
+    pub(crate) indexed: u32,
+    pub(crate) data_address_low: u32,
+    pub(crate) data_address_high: u32,
+    pub(crate) data_size: u32,
+    /// 0 if the pipeline accepts any index format, 1 for `Uint16` and 2 for `Uint32`.
+    pub(crate) strip_index_format: u32,
+    pub(crate) vertex_limit: u32,
+    pub(crate) instance_limit: u32,
+    pub(crate) index_limit: u32,
+    pub(crate) padding: u32,
+    pub(crate) slots: [VertexSlot; hal::MAX_VERTEX_BUFFERS],
+}
+
+/// The buffers and bind groups used to translate a single command stream.
+#[derive(Debug)]
+pub(super) struct CommandsEntry {
+    pub(super) params_buffer: Box<dyn hal::DynBuffer>,
+    pub(super) dst_buffer: Box<dyn hal::DynBuffer>,
+    pub(super) bind_groups: [Box<dyn hal::DynBindGroup>; 3],
+}
+
+impl CommandsEntry {
+    pub(super) fn dispose(self, device: &dyn hal::DynDevice) {
+        let Self {
+            params_buffer,
+            dst_buffer,
+            bind_groups,
+        } = self;
+        unsafe {
+            for bind_group in bind_groups {
+                device.destroy_bind_group(bind_group);
+            }
+            device.destroy_buffer(params_buffer);
+            device.destroy_buffer(dst_buffer);
+        }
+    }
+}
+
+struct Translation {
+    entry_index: usize,
+    params: Params,
+}
+
+/// Accumulates all command streams to translate in a render pass.
+pub(crate) struct CommandsBatcher {
+    translations: Vec<Translation>,
+}
+
+impl CommandsBatcher {
+    pub(crate) fn new() -> Self {
+        Self {
+            translations: Vec::new(),
+        }
+    }
+}
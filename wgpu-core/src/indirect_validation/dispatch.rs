@@ -112,6 +112,7 @@ impl Dispatch {
             module: alloc::borrow::Cow::Owned(module),
             info,
             debug_source: None,
+            bounds_report: None,
         });
         let hal_desc = hal::ShaderModuleDescriptor {
             label: None,
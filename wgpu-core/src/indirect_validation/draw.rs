@@ -1,4 +1,5 @@
 use super::{
+    commands::CommandsEntry,
     utils::{BufferBarrierScratch, BufferBarriers, UniqueIndexExt as _, UniqueIndexScratch},
     CreateIndirectValidationPipelineError,
 };
@@ -64,7 +65,7 @@ impl Draw {
         required_features: &wgt::Features,
         backend: wgt::Backend,
     ) -> Result<Self, CreateIndirectValidationPipelineError> {
-        let module = create_validation_module(device)?;
+        let module = create_validation_module(device, include_str!("./validate_draw.wgsl"))?;
 
         let metadata_bind_group_layout =
             create_bind_group_layout(device, true, false, BUFFER_SIZE)?;
@@ -465,11 +466,10 @@ impl Draw {
     }
 }
 
-fn create_validation_module(
+pub(super) fn create_validation_module(
     device: &dyn hal::DynDevice,
+    src: &str,
 ) -> Result<Box<dyn hal::DynShaderModule>, CreateIndirectValidationPipelineError> {
-    let src = include_str!("./validate_draw.wgsl");
-
     #[cfg(feature = "wgsl")]
     let module = naga::front::wgsl::parse_str(src).map_err(|inner| {
         CreateShaderModuleError::Parsing(naga::error::ShaderError {
@@ -499,6 +499,7 @@ fn create_validation_module(
         module: alloc::borrow::Cow::Owned(module),
         info,
         debug_source: None,
+        bounds_report: None,
     });
     let hal_desc = hal::ShaderModuleDescriptor {
         label: None,
@@ -519,7 +520,7 @@ fn create_validation_module(
     Ok(module)
 }
 
-fn create_validation_pipeline(
+pub(super) fn create_validation_pipeline(
     device: &dyn hal::DynDevice,
     module: &dyn hal::DynShaderModule,
     pipeline_layout: &dyn hal::DynPipelineLayout,
@@ -563,7 +564,7 @@ fn create_validation_pipeline(
     Ok(pipeline)
 }
 
-fn create_bind_group_layout(
+pub(super) fn create_bind_group_layout(
     device: &dyn hal::DynDevice,
     read_only: bool,
     has_dynamic_offset: bool,
@@ -708,6 +709,9 @@ pub(crate) struct DrawResources {
     device: Arc<Device>,
     dst_entries: Vec<BufferPoolEntry>,
     metadata_entries: Vec<BufferPoolEntry>,
+    /// The resources used to translate the command streams of
+    /// `execute_indirect`, see [`super::Commands`].
+    commands_entries: Vec<CommandsEntry>,
 }
 
 impl Drop for DrawResources {
@@ -717,6 +721,9 @@ impl Drop for DrawResources {
             indirect_draw_validation.release_dst_entries(self.dst_entries.drain(..));
             indirect_draw_validation.release_metadata_entries(self.metadata_entries.drain(..));
         }
+        for entry in self.commands_entries.drain(..) {
+            entry.dispose(self.device.raw());
+        }
     }
 }
 
@@ -726,9 +733,29 @@ impl DrawResources {
             device,
             dst_entries: Vec::new(),
             metadata_entries: Vec::new(),
+            commands_entries: Vec::new(),
         }
     }
 
+    /// Returns the buffer holding the native records of a command stream
+    /// added with [`super::Commands::add`].
+    pub(crate) fn get_commands_buffer(&self, index: usize) -> &dyn hal::DynBuffer {
+        self.commands_entries[index].dst_buffer.as_ref()
+    }
+
+    pub(super) fn get_commands_params_buffer(&self, index: usize) -> &dyn hal::DynBuffer {
+        self.commands_entries[index].params_buffer.as_ref()
+    }
+
+    pub(super) fn get_commands_bind_groups(&self, index: usize) -> &[Box<dyn hal::DynBindGroup>] {
+        &self.commands_entries[index].bind_groups
+    }
+
+    pub(super) fn push_commands_entry(&mut self, entry: CommandsEntry) -> usize {
+        self.commands_entries.push(entry);
+        self.commands_entries.len() - 1
+    }
+
     pub(crate) fn get_dst_buffer(&self, index: usize) -> &dyn hal::DynBuffer {
         self.dst_entries.get(index).unwrap().buffer.as_ref()
     }
@@ -869,6 +896,24 @@ impl MetadataEntry {
             instance_limit,
         }
     }
+
+    /// An entry clamping the count of an indirect multi-draw to the
+    /// `draw_count` draws starting at `first_draw`.
+    fn new_count(src_offset: u64, dst_offset: u64, first_draw: u32, draw_count: u32) -> Self {
+        let src_offset = src_offset as u32 / 4;
+        let dst_offset = dst_offset as u32 / 4;
+
+        // `src_offset` needs at most 30 bits,
+        // pack the fact that this is a count in bit 30 of `src_offset`
+        let src_offset = src_offset | (1 << 30);
+
+        Self {
+            src_offset,
+            dst_offset,
+            vertex_or_index_limit: first_draw,
+            instance_limit: draw_count,
+        }
+    }
 }
 
 struct DrawIndirectValidationBatch {
@@ -938,8 +983,6 @@ impl DrawBatcher {
         let limits = device.adapter.limits();
         let (src_dynamic_offset, src_offset) = calculate_src_offsets(buffer_size, &limits, offset);
 
-        let src_buffer_tracker_index = src_buffer.tracker_index();
-
         let entry = MetadataEntry::new(
             family == crate::command::DrawCommandFamily::DrawIndexed,
             src_offset,
@@ -947,9 +990,52 @@ impl DrawBatcher {
             vertex_or_index_limit,
             instance_limit,
         );
+        self.push_entry(src_buffer, src_dynamic_offset, dst_resource_index, entry);
 
+        Ok((dst_resource_index, dst_offset))
+    }
+
+    /// Add the count of an indirect multi-draw to be validated.
+    ///
+    /// The count is read from `count_buffer` at `count_buffer_offset`. The
+    /// count written to the destination is the number of draws it covers among
+    /// the `draw_count` draws starting at draw `first_draw`, so that a
+    /// multi-draw split across several destination buffers can use one count
+    /// per part.
+    ///
+    /// Returns the index of the count buffer in `indirect_draw_validation_resources`
+    /// and the offset of the count.
+    pub(crate) fn add_count(
+        &mut self,
+        indirect_draw_validation_resources: &mut DrawResources,
+        device: &Device,
+        count_buffer: &Arc<crate::resource::Buffer>,
+        count_buffer_offset: u64,
+        first_draw: u32,
+        draw_count: u32,
+    ) -> Result<(usize, u64), DeviceError> {
+        let (dst_resource_index, dst_offset) = indirect_draw_validation_resources
+            .get_dst_subrange(size_of::<u32>() as u64, &mut self.current_dst_entry)?;
+
+        let limits = device.adapter.limits();
+        let (src_dynamic_offset, src_offset) =
+            calculate_src_offsets(count_buffer.size, &limits, count_buffer_offset);
+
+        let entry = MetadataEntry::new_count(src_offset, dst_offset, first_draw, draw_count);
+        self.push_entry(count_buffer, src_dynamic_offset, dst_resource_index, entry);
+
+        Ok((dst_resource_index, dst_offset))
+    }
+
+    fn push_entry(
+        &mut self,
+        src_buffer: &Arc<crate::resource::Buffer>,
+        src_dynamic_offset: u64,
+        dst_resource_index: usize,
+        entry: MetadataEntry,
+    ) {
         match self.batches.entry((
-            src_buffer_tracker_index,
+            src_buffer.tracker_index(),
             src_dynamic_offset,
             dst_resource_index,
         )) {
@@ -971,7 +1057,5 @@ impl DrawBatcher {
                 });
             }
         }
-
-        Ok((dst_resource_index, dst_offset))
     }
 }
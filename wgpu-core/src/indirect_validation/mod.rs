@@ -5,10 +5,12 @@ use crate::{
 use alloc::boxed::Box;
 use thiserror::Error;
 
+mod commands;
 mod dispatch;
 mod draw;
 mod utils;
 
+pub(crate) use commands::{Commands, CommandsBatcher, Params as CommandsParams, VertexSlot};
 pub(crate) use dispatch::Dispatch;
 pub(crate) use draw::{Draw, DrawBatcher, DrawResources};
 
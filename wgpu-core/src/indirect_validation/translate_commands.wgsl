@@ -0,0 +1,172 @@
+override supports_indirect_first_instance: bool;
+override write_d3d12_special_constants: bool;
+
+// A vertex buffer slot used by the pipeline.
+struct VertexSlot {
+    stride: u32,
+    last_stride: u32,
+    // 0 if the pipeline doesn't use the slot, 1 if it steps per vertex and
+    // 2 if it steps per instance
+    mode: u32,
+    padding: u32,
+}
+
+// Describes a stream of `IndirectCommandLayout` records to translate into
+// native records. Limits of `0xFFFFFFFF` are unbounded.
+struct Params {
+    // offset in u32s of the first record in `src`
+    src_offset: u32,
+    record_count: u32,
+    src_stride: u32,
+    dst_stride: u32,
+    push_constant_count: u32,
+    vertex_buffers: u32,
+    index_buffer: u32,
+    indexed: u32,
+    data_address_low: u32,
+    data_address_high: u32,
+    data_size: u32,
+    // 0 if the pipeline accepts any index format, 1 for uint16 and 2 for uint32
+    strip_index_format: u32,
+    // limits of the vertex buffers and index buffer not switched by the records
+    vertex_limit: u32,
+    instance_limit: u32,
+    index_limit: u32,
+    padding: u32,
+    slots: array<VertexSlot, 16>,
+}
+
+@group(0) @binding(0)
+var<storage, read> params: Params;
+@group(1) @binding(0)
+var<storage, read> src: array<u32>;
+@group(2) @binding(0)
+var<storage, read_write> dst: array<u32>;
+
+const UNBOUNDED: u32 = 0xFFFFFFFFu;
+
+// Whether the elements `first..first + count` fit within `limit`.
+fn fits(first: u32, count: u32, limit: u32) -> bool {
+    return limit == UNBOUNDED || (count <= limit && first <= limit - count);
+}
+
+// Resolves the size of a range of the data buffer, or `UNBOUNDED` if the
+// range is out of bounds.
+fn range_size(offset: u32, size: u32) -> u32 {
+    if offset > params.data_size {
+        return UNBOUNDED;
+    }
+    let available = params.data_size - offset;
+    if size == 0u {
+        return available;
+    }
+    return select(UNBOUNDED, size, size <= available);
+}
+
+// Writes the GPU address of `offset` in the data buffer at `dst[index]`.
+fn write_address(index: u32, offset: u32) {
+    let low = params.data_address_low + offset;
+    let carry = select(0u, 1u, low < params.data_address_low);
+    dst[index] = low;
+    dst[index + 1u] = params.data_address_high + carry;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_invocation_id: vec3u) {
+    let record = global_invocation_id.x;
+    if record >= params.record_count { return; }
+
+    var s = params.src_offset + record * params.src_stride;
+    var d = record * params.dst_stride;
+    var failed = false;
+
+    for (var i = 0u; i < params.push_constant_count; i++) {
+        dst[d + i] = src[s + i];
+    }
+    s += params.push_constant_count;
+    d += params.push_constant_count;
+
+    var vertex_limit = params.vertex_limit;
+    var instance_limit = params.instance_limit;
+    for (var slot = 0u; slot < 16u; slot++) {
+        if ((params.vertex_buffers >> slot) & 1u) == 0u { continue; }
+
+        let offset = src[s];
+        var size = range_size(offset, src[s + 1u]);
+        s += 2u;
+        if size == UNBOUNDED || offset % 4u != 0u {
+            failed = true;
+            size = 0u;
+        }
+
+        // D3D12_VERTEX_BUFFER_VIEW
+        write_address(d, offset);
+        dst[d + 2u] = size;
+        dst[d + 3u] = params.slots[slot].stride;
+        d += 4u;
+
+        let step = params.slots[slot];
+        if step.mode == 0u { continue; }
+        var limit = UNBOUNDED;
+        if size < step.last_stride {
+            limit = 0u;
+        } else if step.stride != 0u {
+            limit = (size - step.last_stride) / step.stride + 1u;
+        }
+        if step.mode == 1u {
+            vertex_limit = min(vertex_limit, limit);
+        } else {
+            instance_limit = min(instance_limit, limit);
+        }
+    }
+
+    var index_limit = params.index_limit;
+    if params.index_buffer != 0u {
+        let offset = src[s];
+        var size = range_size(offset, src[s + 1u]);
+        let format = src[s + 2u];
+        s += 3u;
+        let index_size = select(2u, 4u, format == 1u);
+        failed |= format > 1u;
+        failed |= params.strip_index_format != 0u && params.strip_index_format != format + 1u;
+        if size == UNBOUNDED || offset % index_size != 0u {
+            failed = true;
+            size = 0u;
+        }
+        index_limit = size / index_size;
+
+        // D3D12_INDEX_BUFFER_VIEW, with DXGI_FORMAT_R16_UINT or DXGI_FORMAT_R32_UINT
+        write_address(d, offset);
+        dst[d + 2u] = size;
+        dst[d + 3u] = select(57u, 42u, format == 1u);
+        d += 4u;
+    }
+
+    let is_indexed = params.indexed != 0u;
+    let count = src[s + 0u];
+    let instance_count = src[s + 1u];
+    let first = src[s + 2u];
+    let first_instance = src[s + 3u + u32(is_indexed)];
+
+    if is_indexed {
+        failed |= !fits(first, count, index_limit);
+    } else {
+        failed |= !fits(first, count, vertex_limit);
+    }
+    failed |= !fits(first_instance, instance_count, instance_limit);
+    if !supports_indirect_first_instance {
+        failed |= first_instance != 0u;
+    }
+
+    if write_d3d12_special_constants {
+        dst[d + 0u] = select(src[s + 2u + u32(is_indexed)], 0u, failed);
+        dst[d + 1u] = select(first_instance, 0u, failed);
+        dst[d + 2u] = 0u;
+        d += 3u;
+    }
+
+    let arg_count = select(4u, 5u, is_indexed);
+    for (var i = 0u; i < arg_count; i++) {
+        dst[d + i] = select(src[s + i], 0u, failed);
+    }
+}
@@ -1,8 +1,13 @@
 override supports_indirect_first_instance: bool;
 override write_d3d12_special_constants: bool;
 
+// An entry either validates the arguments of a draw, or clamps the count of an
+// indirect multi-draw (if bit 30 of `src_offset` is set). For a count,
+// `vertex_or_index_limit` is the index of the first draw it applies to and
+// `instance_limit` is the number of draws it applies to.
 struct MetadataEntry {
     // bits 0..30 are an offset into `src`
+    // bit 30 signifies that we are clamping a draw count
     // bit 31 signifies that we are validating an indexed draw
     src_offset: u32,
     // bits 0..30 are an offset into `dst`
@@ -37,10 +42,19 @@ fn main(@builtin(global_invocation_id) global_invocation_id: vec3u) {
     let metadata = metadata[metadata_range.start + global_invocation_id.x];
     var failed = false;
 
+    let is_count = is_bit_set(metadata.src_offset, 30);
     let is_indexed = is_bit_set(metadata.src_offset, 31);
     let src_base_offset = ((metadata.src_offset << 2) >> 2);
     let dst_base_offset = ((metadata.dst_offset << 2) >> 2);
 
+    if is_count {
+        let first_draw = metadata.vertex_or_index_limit;
+        let draw_count = metadata.instance_limit;
+        let count = src[src_base_offset];
+        dst[dst_base_offset] = min(max(count, first_draw) - first_draw, draw_count);
+        return;
+    }
+
     let first_vertex_or_index = src[src_base_offset + 2];
     let vertex_or_index_count = src[src_base_offset + 0];
 
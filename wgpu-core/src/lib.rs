@@ -93,6 +93,7 @@ mod snatch;
 pub mod storage;
 mod timestamp_normalization;
 mod track;
+pub mod video;
 mod weak_vec;
 // This is public for users who pre-compile shaders while still wanting to
 // preserve all run-time checks that `wgpu-core` does.
@@ -123,20 +123,29 @@ define_lock_ranks! {
     rank QUEUE_LIFE_TRACKER "Queue::life_tracker" followed by {
         COMMAND_ALLOCATOR_FREE_ENCODERS,
         DEVICE_TRACE,
+        BUFFER_BLOCK_FREE_RANGES,
     }
     rank COMMAND_ALLOCATOR_FREE_ENCODERS "CommandAllocator::free_encoders" followed by {
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
     }
 
     rank BUFFER_BIND_GROUPS "Buffer::bind_groups" followed by { }
+    rank BUFFER_BLOCK_FREE_RANGES "BufferBlock::free_ranges" followed by { }
     rank BUFFER_INITIALIZATION_STATUS "Buffer::initialization_status" followed by { }
+    rank BUFFER_RESIDENT_PAGES "Buffer::resident_pages" followed by { }
+    rank DEVICE_BUFFER_BLOCKS "BufferSubAllocator::blocks" followed by {
+        BUFFER_BLOCK_FREE_RANGES,
+    }
+    rank DEVICE_BOUNDS_REPORTER "BoundsReporter::state" followed by { }
     rank DEVICE_COMMAND_INDICES "Device::command_indices" followed by {}
     rank DEVICE_DEFERRED_DESTROY "Device::deferred_destroy" followed by {}
     rank DEVICE_FENCE "Device::fence" followed by { }
+    rank DEVICE_QUEUE_FENCES "Device::queue_fences" followed by { }
     #[allow(dead_code)]
     rank DEVICE_TRACE "Device::trace" followed by { }
     rank DEVICE_TRACKERS "Device::trackers" followed by { }
     rank DEVICE_LOST_CLOSURE "Device::device_lost_closure" followed by { }
+    rank DEVICE_MEMORY_BUDGET_WATCH "Device::memory_budget_watch" followed by { }
     rank DEVICE_USAGE_SCOPES "Device::usage_scopes" followed by { }
     rank IDENTITY_MANAGER_VALUES "IdentityManager::values" followed by { }
     rank REGISTRY_STORAGE "Registry::storage" followed by { }
@@ -146,12 +155,14 @@ define_lock_ranks! {
     rank TEXTURE_BIND_GROUPS "Texture::bind_groups" followed by { }
     rank TEXTURE_INITIALIZATION_STATUS "Texture::initialization_status" followed by { }
     rank TEXTURE_CLEAR_MODE "Texture::clear_mode" followed by { }
+    rank TEXTURE_RESIDENT_PAGES "Texture::resident_pages" followed by { }
     rank TEXTURE_VIEWS "Texture::views" followed by { }
     rank BLAS_BUILT_INDEX "Blas::built_index" followed by { }
     rank BLAS_COMPACTION_STATE "Blas::compaction_size" followed by { }
     rank TLAS_BUILT_INDEX "Tlas::built_index" followed by { }
     rank TLAS_DEPENDENCIES "Tlas::dependencies" followed by { }
     rank BUFFER_POOL "BufferPool::buffers" followed by { }
+    rank VIDEO_DECODER_PICTURES "VideoDecoder::pictures" followed by { }
 
     #[cfg(test)]
     rank PAWN "pawn" followed by { ROOK, BISHOP }
@@ -17,7 +17,7 @@ use crate::{
     binding_model::{CreateBindGroupLayoutError, CreatePipelineLayoutError, PipelineLayout},
     command::ColorAttachmentError,
     device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures, RenderPassContext},
-    id::{PipelineCacheId, PipelineLayoutId, ShaderModuleId},
+    id::{ComputePipelineId, PipelineCacheId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
     resource::{InvalidResourceError, Labeled, TrackingData},
     resource_log, validation, Label,
 };
@@ -64,6 +64,8 @@ pub struct ShaderModule {
     pub(crate) interface: Option<validation::Interface>,
     /// The `label` from the descriptor used to create the resource.
     pub(crate) label: String,
+    /// Kept alive for the errors about the out-of-bounds accesses of this module.
+    pub(crate) _bounds_report: Option<Arc<crate::device::bounds_report::ReportedShader>>,
 }
 
 impl Drop for ShaderModule {
@@ -268,6 +270,20 @@ impl WebGpuError for CreateComputePipelineError {
     }
 }
 
+/// Invoked when a pipeline requested with
+/// [`Global::device_create_compute_pipeline_async`] has been created.
+///
+/// The pipeline ID is always registered; it refers to an invalid pipeline if
+/// the error is `Some`.
+///
+/// [`Global::device_create_compute_pipeline_async`]: crate::global::Global::device_create_compute_pipeline_async
+#[cfg(send_sync)]
+pub type CreateComputePipelineCallback =
+    Box<dyn FnOnce(ComputePipelineId, Option<CreateComputePipelineError>) + Send + 'static>;
+#[cfg(not(send_sync))]
+pub type CreateComputePipelineCallback =
+    Box<dyn FnOnce(ComputePipelineId, Option<CreateComputePipelineError>) + 'static>;
+
 #[derive(Debug)]
 pub struct ComputePipeline {
     pub(crate) raw: ManuallyDrop<Box<dyn hal::DynComputePipeline>>,
@@ -687,6 +703,10 @@ pub enum CreateRenderPipelineError {
     PipelineExpectsShaderToUseDualSourceBlending,
     #[error("Shader entry point expects the pipeline to make use of dual-source blending.")]
     ShaderExpectsPipelineToUseDualSourceBlending,
+    #[error("Shader entry point reads color attachment {location} with framebuffer fetch, but the pipeline has no color target there")]
+    FramebufferFetchMissingColorTarget { location: u32 },
+    #[error("Shader entry point reads the {aspect:?} aspect with framebuffer fetch, but the pipeline's depth-stencil format has no such aspect")]
+    FramebufferFetchMissingDepthStencilAspect { aspect: wgt::TextureAspect },
     #[error("{}", concat!(
         "At least one color attachment or depth-stencil attachment was expected, ",
         "but no render target for the pipeline was specified."
@@ -725,6 +745,8 @@ impl WebGpuError for CreateRenderPipelineError {
             | Self::BlendFactorOnUnsupportedTarget { .. }
             | Self::PipelineExpectsShaderToUseDualSourceBlending
             | Self::ShaderExpectsPipelineToUseDualSourceBlending
+            | Self::FramebufferFetchMissingColorTarget { .. }
+            | Self::FramebufferFetchMissingDepthStencilAspect { .. }
             | Self::NoTargetSpecified
             | Self::PipelineConstants { .. }
             | Self::VertexAttributeStrideTooLarge { .. } => return ErrorType::Validation,
@@ -733,6 +755,20 @@ impl WebGpuError for CreateRenderPipelineError {
     }
 }
 
+/// Invoked when a pipeline requested with
+/// [`Global::device_create_render_pipeline_async`] has been created.
+///
+/// The pipeline ID is always registered; it refers to an invalid pipeline if
+/// the error is `Some`.
+///
+/// [`Global::device_create_render_pipeline_async`]: crate::global::Global::device_create_render_pipeline_async
+#[cfg(send_sync)]
+pub type CreateRenderPipelineCallback =
+    Box<dyn FnOnce(RenderPipelineId, Option<CreateRenderPipelineError>) + Send + 'static>;
+#[cfg(not(send_sync))]
+pub type CreateRenderPipelineCallback =
+    Box<dyn FnOnce(RenderPipelineId, Option<CreateRenderPipelineError>) + 'static>;
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -741,6 +777,10 @@ bitflags::bitflags! {
         const STENCIL_REFERENCE = 1 << 1;
         const WRITES_DEPTH = 1 << 2;
         const WRITES_STENCIL = 1 << 3;
+        /// The fragment shader reads the depth attachment with framebuffer fetch.
+        const READS_DEPTH = 1 << 4;
+        /// The fragment shader reads the stencil attachment with framebuffer fetch.
+        const READS_STENCIL = 1 << 5;
     }
 }
 
@@ -775,6 +815,9 @@ pub struct RenderPipeline {
     pub(crate) _shader_modules: ArrayVec<Arc<ShaderModule>, { hal::MAX_CONCURRENT_SHADER_STAGES }>,
     pub(crate) pass_context: RenderPassContext,
     pub(crate) flags: PipelineFlags,
+    /// Bit `n` is set if the fragment shader reads color attachment `n` with
+    /// framebuffer fetch.
+    pub(crate) fetched_colors: u8,
     pub(crate) strip_index_format: Option<wgt::IndexFormat>,
     pub(crate) vertex_steps: Vec<VertexStep>,
     pub(crate) late_sized_buffer_groups: ArrayVec<LateSizedBufferGroup, { hal::MAX_BIND_GROUPS }>,
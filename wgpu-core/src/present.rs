@@ -319,6 +319,45 @@ impl Surface {
         }
     }
 
+    /// Records the contents of the texture about to be presented, if the trace records
+    /// outputs.
+    #[cfg(feature = "trace")]
+    fn trace_presented_texture(&self, surface_id: id::SurfaceId) {
+        let (device, texture) = match *self.presentation.lock() {
+            Some(Presentation {
+                ref device,
+                acquired_texture: Some(ref texture),
+                ..
+            }) => (device.clone(), texture.clone()),
+            _ => return,
+        };
+        // Surfaces not configured with `COPY_SRC` can't be read back, which
+        // `Global::surface_configure` warns about.
+        if !texture.desc.usage.contains(wgt::TextureUsages::COPY_SRC) {
+            return;
+        }
+        let texture_id = match *device.trace.lock() {
+            Some(ref trace) if trace.records_outputs() => trace.surface_texture(surface_id),
+            _ => None,
+        };
+        let Some(texture_id) = texture_id else {
+            return;
+        };
+
+        match device.read_back_texture(&texture) {
+            Ok(data) => {
+                if let Some(ref mut trace) = *device.trace.lock() {
+                    let data = trace.make_binary("bin", &data);
+                    trace.add(Action::ExpectSurfaceTexture {
+                        id: texture_id,
+                        data,
+                    });
+                }
+            }
+            Err(e) => log::warn!("Unable to record the presented surface texture: {e}"),
+        }
+    }
+
     pub fn discard(&self) -> Result<(), SurfaceError> {
         profiling::scope!("Surface::discard");
 
@@ -386,6 +425,9 @@ impl Global {
     pub fn surface_present(&self, surface_id: id::SurfaceId) -> Result<Status, SurfaceError> {
         let surface = self.surfaces.get(surface_id);
 
+        #[cfg(feature = "trace")]
+        surface.trace_presented_texture(surface_id);
+
         #[cfg(feature = "trace")]
         if let Some(present) = surface.presentation.lock().as_ref() {
             if let Some(ref mut trace) = *present.device.trace.lock() {
@@ -179,6 +179,9 @@ pub enum BuildAccelerationStructureError {
         "Tlas {0:?} dependent {1:?} is missing AccelerationStructureFlags::ALLOW_RAY_HIT_VERTEX_RETURN"
     )]
     TlasDependentMissingVertexReturn(ResourceErrorIdent, ResourceErrorIdent),
+
+    #[error("Acceleration structures can not be built on {0:?} queues")]
+    UnsupportedQueueType(wgt::QueueType),
 }
 
 impl WebGpuError for BuildAccelerationStructureError {
@@ -210,7 +213,8 @@ impl WebGpuError for BuildAccelerationStructureError {
             | Self::TlasInstanceCountExceeded(..)
             | Self::TransformMissing(..)
             | Self::UseTransformMissing(..)
-            | Self::TlasDependentMissingVertexReturn(..) => return ErrorType::Validation,
+            | Self::TlasDependentMissingVertexReturn(..)
+            | Self::UnsupportedQueueType(..) => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -68,6 +68,13 @@ impl<T: StorageItem> FutureId<'_, T> {
         data.insert(self.id, value);
         self.id
     }
+
+    /// Keep this ID reserved without registering a resource yet.
+    ///
+    /// The resource must later be registered with [`Registry::assign`].
+    pub fn detach(self) -> Id<T::Marker> {
+        self.id
+    }
 }
 
 impl<T: StorageItem> Registry<T> {
@@ -84,6 +91,12 @@ impl<T: StorageItem> Registry<T> {
         }
     }
 
+    /// Register a resource for an ID previously reserved with [`FutureId::detach`].
+    pub(crate) fn assign(&self, id: Id<T::Marker>, value: T) -> Id<T::Marker> {
+        self.storage.write().insert(id, value);
+        id
+    }
+
     #[track_caller]
     pub(crate) fn read<'a>(&'a self) -> RwLockReadGuard<'a, Storage<T>> {
         self.storage.read()
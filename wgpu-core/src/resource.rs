@@ -19,8 +19,11 @@ use crate::device::trace;
 use crate::{
     binding_model::{BindGroup, BindingError},
     device::{
-        queue, resource::DeferredDestroy, BufferMapPendingClosure, Device, DeviceError,
-        DeviceMismatch, HostMap, MissingDownlevelFlags, MissingFeatures,
+        queue,
+        resource::DeferredDestroy,
+        suballocation::{self, SubAllocation},
+        BufferMapPendingClosure, Device, DeviceError, DeviceMismatch, HostMap,
+        MissingDownlevelFlags, MissingFeatures,
     },
     hal_label,
     init_tracker::{BufferInitTracker, TextureInitTracker},
@@ -31,7 +34,7 @@ use crate::{
     timestamp_normalization::TimestampNormalizationBindGroup,
     track::{SharedTrackerIndexAllocator, TrackerIndex},
     weak_vec::WeakVec,
-    Label, LabelHelpers, SubmissionIndex,
+    FastHashSet, Label, LabelHelpers, SubmissionIndex,
 };
 
 /// Information about the wgpu-core resource.
@@ -424,9 +427,45 @@ pub(crate) struct BufferPendingMapping {
 
 pub type BufferDescriptor<'a> = wgt::BufferDescriptor<Label<'a>>;
 
+/// The HAL buffer backing a [`Buffer`].
+#[derive(Debug)]
+pub(crate) enum RawBuffer {
+    /// A HAL buffer created for this buffer only.
+    Dedicated(Box<dyn hal::DynBuffer>),
+    /// A range of a HAL buffer shared with other buffers, see
+    /// [`crate::device::suballocation`].
+    SubAllocated(SubAllocation),
+}
+
+impl RawBuffer {
+    pub(crate) fn raw(&self) -> &dyn hal::DynBuffer {
+        match self {
+            Self::Dedicated(raw) => raw.as_ref(),
+            Self::SubAllocated(allocation) => allocation.raw(),
+        }
+    }
+
+    /// Destroy the HAL buffer, or return the range to its block.
+    ///
+    /// # Safety
+    ///
+    /// The GPU must be done with the buffer.
+    pub(crate) unsafe fn destroy(self, device: &dyn hal::DynDevice) {
+        match self {
+            Self::Dedicated(raw) => unsafe { device.destroy_buffer(raw) },
+            Self::SubAllocated(allocation) => drop(allocation),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Buffer {
-    pub(crate) raw: Snatchable<Box<dyn hal::DynBuffer>>,
+    pub(crate) raw: Snatchable<RawBuffer>,
+    /// The offset of the buffer in its HAL buffer.
+    ///
+    /// This is zero unless the buffer is sub-allocated, and must be added to
+    /// all offsets into the buffer passed to `wgpu-hal`.
+    pub(crate) raw_offset: wgt::BufferAddress,
     pub(crate) device: Arc<Device>,
     pub(crate) usage: wgt::BufferUsages,
     pub(crate) size: wgt::BufferAddress,
@@ -438,6 +477,11 @@ pub struct Buffer {
     pub(crate) bind_groups: Mutex<WeakVec<BindGroup>>,
     pub(crate) timestamp_normalization_bind_group: Snatchable<TimestampNormalizationBindGroup>,
     pub(crate) indirect_validation_bind_groups: Snatchable<crate::indirect_validation::BindGroups>,
+    /// The indices of the pages of a sparse buffer that have memory bound.
+    pub(crate) resident_pages: Mutex<FastHashSet<u64>>,
+    /// Whether the buffer was created with [`hal::MemoryFlags::QUEUE_EXCLUSIVE`],
+    /// and moves between the queues of different types with ownership transfers.
+    pub(crate) queue_exclusive: bool,
 }
 
 impl Drop for Buffer {
@@ -453,7 +497,7 @@ impl Drop for Buffer {
         if let Some(raw) = self.raw.take() {
             resource_log!("Destroy raw {}", self.error_ident());
             unsafe {
-                self.device.raw().destroy_buffer(raw);
+                raw.destroy(self.device.raw());
             }
         }
     }
@@ -463,7 +507,7 @@ impl RawResourceAccess for Buffer {
     type DynResource = dyn hal::DynBuffer;
 
     fn raw<'a>(&'a self, guard: &'a SnatchGuard) -> Option<&'a Self::DynResource> {
-        self.raw.get(guard).map(|b| b.as_ref())
+        self.raw.get(guard).map(RawBuffer::raw)
     }
 }
 
@@ -561,16 +605,50 @@ impl Buffer {
         binding_size: Option<wgt::BufferSize>,
         snatch_guard: &'a SnatchGuard,
     ) -> Result<(hal::BufferBinding<'a, dyn hal::DynBuffer>, u64), BindingError> {
-        let buf_raw = self.try_raw(snatch_guard)?;
         let resolved_size = self.resolve_binding_size(offset, binding_size)?;
-        // SAFETY: The offset and size passed to hal::BufferBinding::new_unchecked must
-        // define a binding contained within the buffer.
         Ok((
-            hal::BufferBinding::new_unchecked(buf_raw, offset, binding_size),
+            self.raw_binding(offset, binding_size, snatch_guard)?,
             resolved_size,
         ))
     }
 
+    /// Create a new [`hal::BufferBinding`] for the buffer with `offset` and
+    /// `binding_size`, without validating them.
+    ///
+    /// The returned binding is relative to the HAL buffer, so it accounts for
+    /// [`Buffer::raw_offset`]. An implicit size is kept implicit, unless the
+    /// buffer is sub-allocated, where it would extend past the buffer.
+    ///
+    /// The caller must have checked that the binding is contained within the
+    /// buffer, e.g. with [`Buffer::resolve_binding_size`].
+    pub(crate) fn raw_binding<'a>(
+        &'a self,
+        offset: wgt::BufferAddress,
+        binding_size: Option<wgt::BufferSize>,
+        snatch_guard: &'a SnatchGuard,
+    ) -> Result<hal::BufferBinding<'a, dyn hal::DynBuffer>, DestroyedResourceError> {
+        let buf_raw = self.try_raw(snatch_guard)?;
+        let (offset, binding_size) = if self.is_sub_allocated(snatch_guard) {
+            suballocation::binding_in_block(self.raw_offset, self.size, offset, binding_size)
+        } else {
+            (offset, binding_size)
+        };
+        // SAFETY: The offset and size passed to hal::BufferBinding::new_unchecked must
+        // define a binding contained within the buffer, which is contained within
+        // the HAL buffer at `raw_offset`.
+        Ok(hal::BufferBinding::new_unchecked(
+            buf_raw,
+            offset,
+            binding_size,
+        ))
+    }
+
+    /// Returns `true` if the buffer is placed in a HAL buffer shared with other
+    /// buffers.
+    pub(crate) fn is_sub_allocated(&self, guard: &SnatchGuard) -> bool {
+        matches!(self.raw.get(guard), Some(RawBuffer::SubAllocated(_)))
+    }
+
     /// Returns the mapping callback in case of error so that the callback can be fired outside
     /// of the locks that are held in this function.
     pub(crate) fn map_async(
@@ -772,7 +850,7 @@ impl Buffer {
                 if let Some(queue) = device.get_queue() {
                     let region = wgt::BufferSize::new(self.size).map(|size| hal::BufferCopy {
                         src_offset: 0,
-                        dst_offset: 0,
+                        dst_offset: self.raw_offset,
                         size,
                     });
                     let transition_src = hal::BufferBarrier {
@@ -816,6 +894,22 @@ impl Buffer {
                 range,
                 host,
             } => {
+                #[cfg(feature = "trace")]
+                if host == HostMap::Read {
+                    if let Some(ref mut trace) = *device.trace.lock() {
+                        if trace.records_outputs() {
+                            let size = range.end - range.start;
+                            let data = trace.make_binary("bin", unsafe {
+                                core::slice::from_raw_parts(mapping.ptr.as_ptr(), size as usize)
+                            });
+                            trace.add(trace::Action::ExpectBuffer {
+                                id: buffer_id,
+                                range: range.clone(),
+                                data,
+                            });
+                        }
+                    }
+                }
                 #[allow(clippy::collapsible_if)]
                 if host == HostMap::Write {
                     #[cfg(feature = "trace")]
@@ -908,6 +1002,10 @@ pub enum CreateBufferError {
     InvalidUsage(wgt::BufferUsages),
     #[error("`MAP` usage can only be combined with the opposite `COPY`, requested {0:?}")]
     UsageMismatch(wgt::BufferUsages),
+    #[error("Sparse buffers can't have `MAP` usages, requested {0:?}")]
+    SparseUsageMismatch(wgt::BufferUsages),
+    #[error("Sparse buffers can't be mapped at creation")]
+    SparseMappedAtCreation,
     #[error("Buffer size {requested} is greater than the maximum buffer size ({maximum})")]
     MaxBufferSize { requested: u64, maximum: u64 },
     #[error(transparent)]
@@ -936,6 +1034,8 @@ impl WebGpuError for CreateBufferError {
             Self::UnalignedSize
             | Self::InvalidUsage(_)
             | Self::UsageMismatch(_)
+            | Self::SparseUsageMismatch(_)
+            | Self::SparseMappedAtCreation
             | Self::MaxBufferSize { .. } => return ErrorType::Validation,
         };
         e.webgpu_error_type()
@@ -945,7 +1045,7 @@ impl WebGpuError for CreateBufferError {
 /// A buffer that has been marked as destroyed and is staged for actual deletion soon.
 #[derive(Debug)]
 pub struct DestroyedBuffer {
-    raw: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    raw: ManuallyDrop<RawBuffer>,
     device: Arc<Device>,
     label: String,
     bind_groups: WeakVec<BindGroup>,
@@ -979,7 +1079,7 @@ impl Drop for DestroyedBuffer {
         // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
         let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
         unsafe {
-            hal::DynDevice::destroy_buffer(self.device.raw(), raw);
+            raw.destroy(self.device.raw());
         }
     }
 }
@@ -1194,6 +1294,15 @@ pub struct Texture {
     pub(crate) clear_mode: RwLock<TextureClearMode>,
     pub(crate) views: Mutex<WeakVec<TextureView>>,
     pub(crate) bind_groups: Mutex<WeakVec<BindGroup>>,
+    /// The pages of a sparse texture that have memory bound, by mip level,
+    /// array layer, column and row.
+    ///
+    /// The mip tail of an array layer is a single page at the first level of
+    /// the tail.
+    pub(crate) resident_pages: Mutex<FastHashSet<[u32; 4]>>,
+    /// Whether the texture was created with [`hal::MemoryFlags::QUEUE_EXCLUSIVE`],
+    /// and moves between the queues of different types with ownership transfers.
+    pub(crate) queue_exclusive: bool,
 }
 
 impl Texture {
@@ -1229,6 +1338,8 @@ impl Texture {
             clear_mode: RwLock::new(rank::TEXTURE_CLEAR_MODE, clear_mode),
             views: Mutex::new(rank::TEXTURE_VIEWS, WeakVec::new()),
             bind_groups: Mutex::new(rank::TEXTURE_BIND_GROUPS, WeakVec::new()),
+            resident_pages: Mutex::new(rank::TEXTURE_RESIDENT_PAGES, FastHashSet::default()),
+            queue_exclusive: false,
         }
     }
 
@@ -1538,6 +1649,19 @@ pub enum CreateTextureError {
     MultisampledNotRenderAttachment,
     #[error("Texture format {0:?} can't be used due to missing features")]
     MissingFeatures(wgt::TextureFormat, #[source] MissingFeatures),
+    #[error("Texture usage TRANSIENT can't be used due to missing features")]
+    TransientMissingFeatures(#[source] MissingFeatures),
+    #[error(
+        "Transient textures must only have the RENDER_ATTACHMENT usage, but {0:?} was requested"
+    )]
+    InvalidTransientUsage(wgt::TextureUsages),
+    #[error("Texture usage SPARSE can't be used due to missing features")]
+    SparseMissingFeatures(#[source] MissingFeatures),
+    #[error(
+        "Sparse textures must be single-sampled 2D textures of a color format, \
+        but a {0:?} texture of {1:?} with {2} samples was requested"
+    )]
+    InvalidSparseTexture(wgt::TextureDimension, wgt::TextureFormat, u32),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
@@ -1561,9 +1685,13 @@ impl WebGpuError for CreateTextureError {
             Self::CreateTextureView(e) => e,
             Self::InvalidDimension(e) => e,
             Self::MissingFeatures(_, e) => e,
+            Self::TransientMissingFeatures(e) => e,
+            Self::SparseMissingFeatures(e) => e,
             Self::MissingDownlevelFlags(e) => e,
 
             Self::InvalidUsage(_)
+            | Self::InvalidTransientUsage(_)
+            | Self::InvalidSparseTexture(..)
             | Self::InvalidDepthDimension(_, _)
             | Self::InvalidCompressedDimension(_, _)
             | Self::InvalidMipLevelCount { .. }
@@ -168,6 +168,7 @@ impl TimestampNormalizer {
                 module: alloc::borrow::Cow::Owned(module),
                 info,
                 debug_source: None,
+                bounds_report: None,
             });
             let hal_desc = hal::ShaderModuleDescriptor {
                 label: None,
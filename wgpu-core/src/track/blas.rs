@@ -56,4 +56,9 @@ impl BlasTracker {
     pub fn contains(&self, blas: &Blas) -> bool {
         self.metadata.contains(blas.tracker_index().as_usize())
     }
+
+    /// Returns a list of all BLASes tracked.
+    pub fn used_resources(&self) -> impl Iterator<Item = &Arc<Blas>> + '_ {
+        self.metadata.owned_resources()
+    }
 }
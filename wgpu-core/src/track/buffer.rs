@@ -12,9 +12,9 @@ use alloc::{
 use hal::BufferBarrier;
 use wgt::{strict_assert, strict_assert_eq, BufferUses};
 
-use super::{PendingTransition, TrackerIndex};
+use super::{PendingTransition, TrackerIndex, TransitionLog};
 use crate::{
-    resource::{Buffer, Trackable},
+    resource::{Buffer, Labeled, Trackable},
     snatch::SnatchGuard,
     track::{
         invalid_resource_state, skip_barrier, ResourceMetadata, ResourceMetadataProvider,
@@ -271,6 +271,8 @@ pub(crate) struct BufferTracker {
     metadata: ResourceMetadata<Arc<Buffer>>,
 
     temp: Vec<PendingTransition<BufferUses>>,
+
+    pub(super) log: Option<TransitionLog>,
 }
 
 impl BufferTracker {
@@ -282,6 +284,25 @@ impl BufferTracker {
             metadata: ResourceMetadata::new(),
 
             temp: Vec::new(),
+
+            log: None,
+        }
+    }
+
+    /// Logs the pending transitions, if the log is enabled.
+    fn log_transitions(&mut self) {
+        let Some(log) = &mut self.log else {
+            return;
+        };
+        for pending in &self.temp {
+            let buf = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
+            log.push(
+                buf.label(),
+                wgt::TransitionedResource::Buffer {
+                    from: pending.usage.from,
+                    to: pending.usage.to,
+                },
+            );
         }
     }
 
@@ -324,6 +345,7 @@ impl BufferTracker {
         &'b mut self,
         snatch_guard: &'a SnatchGuard<'a>,
     ) -> impl Iterator<Item = BufferBarrier<'a, dyn hal::DynBuffer>> {
+        self.log_transitions();
         let buffer_barriers = self.temp.drain(..).map(|pending| {
             let buf = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
             pending.into_hal(buf, snatch_guard)
@@ -360,6 +382,7 @@ impl BufferTracker {
 
         strict_assert!(self.temp.len() <= 1);
 
+        self.log_transitions();
         self.temp.pop()
     }
 
@@ -536,6 +559,11 @@ impl BufferTracker {
 /// Stores all buffer state within a device.
 pub(crate) struct DeviceBufferTracker {
     current_states: Vec<BufferUses>,
+    /// The type of the queues that released each buffer to the graphics
+    /// queues, if those haven't acquired it since.
+    ///
+    /// See [`DeviceTracker`](super::DeviceTracker).
+    released: Vec<Option<wgt::QueueType>>,
     metadata: ResourceMetadata<Weak<Buffer>>,
     temp: Vec<PendingTransition<BufferUses>>,
 }
@@ -544,6 +572,7 @@ impl DeviceBufferTracker {
     pub fn new() -> Self {
         Self {
             current_states: Vec::new(),
+            released: Vec::new(),
             metadata: ResourceMetadata::new(),
             temp: Vec::new(),
         }
@@ -558,6 +587,7 @@ impl DeviceBufferTracker {
     fn allow_index(&mut self, index: usize) {
         if index >= self.current_states.len() {
             self.current_states.resize(index + 1, BufferUses::empty());
+            self.released.resize(index + 1, None);
             self.metadata.set_size(index + 1);
         }
     }
@@ -577,6 +607,7 @@ impl DeviceBufferTracker {
 
         self.tracker_assert_in_bounds(index);
 
+        self.released[index] = None;
         unsafe {
             insert(
                 None,
@@ -622,6 +653,29 @@ impl DeviceBufferTracker {
         self.temp.pop()
     }
 
+    /// Returns the state of `buffer`, along with the type of the queues that
+    /// released it to the graphics queues if those haven't acquired it yet.
+    ///
+    /// The graphics queues are then considered to own the buffer.
+    pub fn take_release(&mut self, buffer: &Arc<Buffer>) -> (BufferUses, Option<wgt::QueueType>) {
+        let index = buffer.tracker_index().as_usize();
+
+        self.tracker_assert_in_bounds(index);
+
+        (self.current_states[index], self.released[index].take())
+    }
+
+    /// Records that the queues of `queue_type` released `buffer` to the
+    /// graphics queues, and returns its state.
+    pub fn set_released(&mut self, buffer: &Arc<Buffer>, queue_type: wgt::QueueType) -> BufferUses {
+        let index = buffer.tracker_index().as_usize();
+
+        self.tracker_assert_in_bounds(index);
+
+        self.released[index] = Some(queue_type);
+        self.current_states[index]
+    }
+
     /// Sets the given state for all buffers in the given tracker.
     ///
     /// If a transition is needed to get the buffers into the needed state,
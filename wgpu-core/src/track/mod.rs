@@ -98,6 +98,7 @@ Device <- CommandBuffer = insert(device.start, device.end, buffer.start, buffer.
 mod blas;
 mod buffer;
 mod metadata;
+mod ownership;
 mod range;
 mod stateless;
 mod texture;
@@ -111,7 +112,7 @@ use crate::{
     track::blas::BlasTracker,
 };
 
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{borrow::ToOwned, sync::Arc, vec::Vec};
 use core::{fmt, mem, ops};
 
 use thiserror::Error;
@@ -120,6 +121,7 @@ pub(crate) use buffer::{
     BufferBindGroupState, BufferTracker, BufferUsageScope, DeviceBufferTracker,
 };
 use metadata::{ResourceMetadata, ResourceMetadataProvider};
+pub(crate) use ownership::{OwnershipTransfer, ReturnedResources, TakenResources};
 pub(crate) use stateless::StatelessTracker;
 pub(crate) use texture::{
     DeviceTextureTracker, TextureTracker, TextureTrackerSetSingle, TextureUsageScope,
@@ -267,6 +269,25 @@ pub(crate) struct PendingTransition<S: ResourceUses> {
 
 pub(crate) type PendingTransitionList = Vec<PendingTransition<wgt::TextureUses>>;
 
+/// The transitions produced by a [`BufferTracker`] or a [`TextureTracker`], kept when
+/// [`wgt::InstanceFlags::REPORT_TRANSITIONS`] is set.
+#[derive(Debug, Default)]
+pub(crate) struct TransitionLog {
+    /// The index of the command being recorded, see [`Tracker::set_command_index`].
+    command_index: u32,
+    reports: Vec<wgt::TransitionReport>,
+}
+
+impl TransitionLog {
+    fn push(&mut self, label: &str, resource: wgt::TransitionedResource) {
+        self.reports.push(wgt::TransitionReport {
+            label: label.to_owned(),
+            resource,
+            command_index: self.command_index,
+        });
+    }
+}
+
 impl PendingTransition<wgt::BufferUses> {
     /// Produce the hal barrier corresponding to the transition.
     pub fn into_hal<'a>(
@@ -598,6 +619,11 @@ impl<'a> UsageScope<'a> {
 }
 
 /// A tracker used by Device.
+///
+/// Its states are shared by all queues of the device: the submissions to
+/// different queues using the same resources wait for each other, so each
+/// resource is only ever in one state. Resources that belong to the queues of
+/// one type at a time move between them as described in [`ownership`].
 pub(crate) struct DeviceTracker {
     pub buffers: DeviceBufferTracker,
     pub textures: DeviceTextureTracker,
@@ -642,6 +668,58 @@ impl Tracker {
         }
     }
 
+    /// Creates an empty tracker for the barriers inside a pass, which logs its
+    /// transitions with the current command index if this tracker logs them.
+    pub fn new_intermediate(&self) -> Self {
+        let mut tracker = Self::new();
+        if let Some(log) = &self.buffers.log {
+            tracker.enable_transition_log();
+            tracker.set_command_index(log.command_index);
+        }
+        tracker
+    }
+
+    /// Starts logging the buffer and texture transitions produced by this tracker.
+    pub fn enable_transition_log(&mut self) {
+        self.buffers.log = Some(TransitionLog::default());
+        self.textures.log = Some(TransitionLog::default());
+    }
+
+    /// Sets the command index that transitions are logged with from now on.
+    pub fn set_command_index(&mut self, index: u32) {
+        for log in [&mut self.buffers.log, &mut self.textures.log]
+            .into_iter()
+            .flatten()
+        {
+            log.command_index = index;
+        }
+    }
+
+    /// Moves the transitions logged by `other` to the log of this tracker.
+    pub fn append_transition_log(&mut self, other: &mut Self) {
+        for (log, other) in [
+            (&mut self.buffers.log, &mut other.buffers.log),
+            (&mut self.textures.log, &mut other.textures.log),
+        ] {
+            if let (Some(log), Some(other)) = (log, other) {
+                log.reports.append(&mut other.reports);
+            }
+        }
+    }
+
+    /// Takes the logged transitions, ordered by the index of the command that caused them.
+    pub fn take_transition_report(&mut self) -> Vec<wgt::TransitionReport> {
+        let mut reports = Vec::new();
+        for log in [&mut self.buffers.log, &mut self.textures.log]
+            .into_iter()
+            .flatten()
+        {
+            reports.append(&mut log.reports);
+        }
+        reports.sort_by_key(|report| report.command_index);
+        reports
+    }
+
     /// Iterates through all resources in the given bind group and adopts
     /// the state given for those resources in the UsageScope. It also
     /// removes all touched resources from the usage scope.
@@ -0,0 +1,301 @@
+//! Queue ownership of resources created with [`hal::MemoryFlags::QUEUE_EXCLUSIVE`].
+//!
+//! Such resources belong to the graphics queues between submissions. A
+//! submission to a queue of another type takes the ones it uses from the
+//! graphics queues, which release them to it in a submission of their own
+//! first, and releases them back to the graphics queues as it ends. The
+//! graphics queues acquire them again the next time they use them.
+//!
+//! This way, only the queue a device was created with ever has to submit
+//! commands on behalf of another queue, which it already does for the writes
+//! requested on all queues.
+
+use alloc::{sync::Arc, vec::Vec};
+
+use wgt::{BufferUses, TextureSelector, TextureUses};
+
+use crate::{
+    resource::{Buffer, RawResourceAccess, Texture, Trackable},
+    snatch::SnatchGuard,
+    track::{DeviceTracker, Tracker, TrackerIndex},
+    FastHashMap,
+};
+
+/// Resources moving between the queues of two types, along with the states
+/// they are in.
+#[derive(Default)]
+pub(crate) struct OwnershipTransfer {
+    buffers: Vec<(Arc<Buffer>, BufferUses)>,
+    textures: Vec<(Arc<Texture>, TextureSelector, TextureUses)>,
+}
+
+impl OwnershipTransfer {
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty() && self.textures.is_empty()
+    }
+
+    fn push_texture(&mut self, texture: &Arc<Texture>, states: &[(TextureSelector, TextureUses)]) {
+        self.textures.extend(
+            states
+                .iter()
+                .map(|(selector, state)| (texture.clone(), selector.clone(), *state)),
+        );
+    }
+
+    /// The buffers and textures moving.
+    pub fn resources(&self) -> (Vec<&Buffer>, Vec<&Texture>) {
+        (
+            self.buffers.iter().map(|(buffer, _)| &**buffer).collect(),
+            self.textures
+                .iter()
+                .map(|(texture, _, _)| &**texture)
+                .collect(),
+        )
+    }
+
+    /// Moves the resources of `other` to `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.buffers.append(&mut other.buffers);
+        self.textures.append(&mut other.textures);
+    }
+
+    /// Records the release of the resources from the queues of `encoder` to
+    /// the queues of `queue_type`.
+    ///
+    /// # Safety
+    ///
+    /// `encoder` must be recording, and the resources must belong to its queues.
+    pub unsafe fn release(
+        &self,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        queue_type: wgt::QueueType,
+        snatch_guard: &SnatchGuard,
+    ) {
+        let buffers = self.buffer_barriers(snatch_guard);
+        let textures = self.texture_barriers(snatch_guard);
+        unsafe { encoder.release_resources(queue_type, &buffers, &textures) };
+    }
+
+    /// Records the acquisition of the resources by the queues of `encoder`
+    /// from the queues of `queue_type`.
+    ///
+    /// # Safety
+    ///
+    /// `encoder` must be recording, and the queues of `queue_type` must have
+    /// released the resources to its queues.
+    pub unsafe fn acquire(
+        &self,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        queue_type: wgt::QueueType,
+        snatch_guard: &SnatchGuard,
+    ) {
+        let buffers = self.buffer_barriers(snatch_guard);
+        let textures = self.texture_barriers(snatch_guard);
+        unsafe { encoder.acquire_resources(queue_type, &buffers, &textures) };
+    }
+
+    /// The barriers of the buffers that haven't been destroyed, which keep
+    /// their states.
+    fn buffer_barriers<'a>(
+        &'a self,
+        snatch_guard: &'a SnatchGuard,
+    ) -> Vec<hal::BufferBarrier<'a, dyn hal::DynBuffer>> {
+        self.buffers
+            .iter()
+            .filter_map(|&(ref buffer, state)| {
+                Some(hal::BufferBarrier {
+                    buffer: buffer.raw(snatch_guard)?,
+                    usage: hal::StateTransition {
+                        from: state,
+                        to: state,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// The barriers of the textures that haven't been destroyed, which keep
+    /// their states.
+    fn texture_barriers<'a>(
+        &'a self,
+        snatch_guard: &'a SnatchGuard,
+    ) -> Vec<hal::TextureBarrier<'a, dyn hal::DynTexture>> {
+        self.textures
+            .iter()
+            .filter_map(|&(ref texture, ref selector, state)| {
+                Some(hal::TextureBarrier {
+                    texture: texture.raw(snatch_guard)?,
+                    range: wgt::ImageSubresourceRange {
+                        aspect: wgt::TextureAspect::All,
+                        base_mip_level: selector.mips.start,
+                        mip_level_count: Some(selector.mips.end - selector.mips.start),
+                        base_array_layer: selector.layers.start,
+                        array_layer_count: Some(selector.layers.end - selector.layers.start),
+                    },
+                    usage: hal::StateTransition {
+                        from: state,
+                        to: state,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// The resources the graphics queues acquire from the queues of each other
+/// type.
+#[derive(Default)]
+pub(crate) struct ReturnedResources(Vec<(wgt::QueueType, OwnershipTransfer)>);
+
+impl ReturnedResources {
+    fn released_by(&mut self, queue_type: wgt::QueueType) -> &mut OwnershipTransfer {
+        let position = match self.0.iter().position(|&(other, _)| other == queue_type) {
+            Some(position) => position,
+            None => {
+                self.0.push((queue_type, OwnershipTransfer::default()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[position].1
+    }
+
+    /// Records the acquisition of the resources by the graphics queues.
+    ///
+    /// # Safety
+    ///
+    /// `encoder` must be recording for a graphics queue.
+    pub unsafe fn acquire(
+        &self,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        snatch_guard: &SnatchGuard,
+    ) {
+        for (queue_type, transfer) in self.0.iter() {
+            unsafe { transfer.acquire(encoder, *queue_type, snatch_guard) };
+        }
+    }
+}
+
+/// The resources a submission to a queue of a type other than graphics took
+/// from the graphics queues.
+#[derive(Default)]
+pub(crate) struct TakenResources {
+    buffers: FastHashMap<TrackerIndex, Arc<Buffer>>,
+    textures: FastHashMap<TrackerIndex, Arc<Texture>>,
+}
+
+impl TakenResources {
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty() && self.textures.is_empty()
+    }
+}
+
+impl DeviceTracker {
+    /// Makes the graphics queues own the resources used by `tracker`, adding
+    /// those the queues of other types released to them to `returned`.
+    pub fn acquire_for_graphics(&mut self, tracker: &Tracker, returned: &mut ReturnedResources) {
+        for buffer in tracker.buffers.used_resources() {
+            self.acquire_buffer_for_graphics(buffer, returned);
+        }
+        for texture in tracker.textures.used_resources() {
+            self.acquire_texture_for_graphics(texture, returned);
+        }
+    }
+
+    /// Makes the graphics queues own `buffer`, adding it to `returned` if the
+    /// queues of another type released it to them.
+    pub fn acquire_buffer_for_graphics(
+        &mut self,
+        buffer: &Arc<Buffer>,
+        returned: &mut ReturnedResources,
+    ) {
+        if !buffer.queue_exclusive {
+            return;
+        }
+        if let (state, Some(queue_type)) = self.buffers.take_release(buffer) {
+            returned
+                .released_by(queue_type)
+                .buffers
+                .push((buffer.clone(), state));
+        }
+    }
+
+    /// Makes the graphics queues own `texture`, adding it to `returned` if the
+    /// queues of another type released it to them.
+    pub fn acquire_texture_for_graphics(
+        &mut self,
+        texture: &Arc<Texture>,
+        returned: &mut ReturnedResources,
+    ) {
+        if !texture.queue_exclusive {
+            return;
+        }
+        if let (states, Some(queue_type)) = self.textures.take_release(texture) {
+            returned
+                .released_by(queue_type)
+                .push_texture(texture, &states);
+        }
+    }
+
+    /// Moves the resources used by `tracker` that are not in `taken` yet from
+    /// the graphics queues to the queues of another type, and adds them to
+    /// `taken` and `moved`.
+    ///
+    /// The graphics queues first acquire those the queues of other types
+    /// released to them, which are added to `returned`.
+    pub fn take_from_graphics(
+        &mut self,
+        tracker: &Tracker,
+        taken: &mut TakenResources,
+        returned: &mut ReturnedResources,
+        moved: &mut OwnershipTransfer,
+    ) {
+        for buffer in tracker.buffers.used_resources() {
+            if !buffer.queue_exclusive || taken.buffers.contains_key(&buffer.tracker_index()) {
+                continue;
+            }
+            let (state, released) = self.buffers.take_release(buffer);
+            if let Some(queue_type) = released {
+                returned
+                    .released_by(queue_type)
+                    .buffers
+                    .push((buffer.clone(), state));
+            }
+            moved.buffers.push((buffer.clone(), state));
+            taken.buffers.insert(buffer.tracker_index(), buffer.clone());
+        }
+        for texture in tracker.textures.used_resources() {
+            if !texture.queue_exclusive || taken.textures.contains_key(&texture.tracker_index()) {
+                continue;
+            }
+            let (states, released) = self.textures.take_release(texture);
+            if let Some(queue_type) = released {
+                returned
+                    .released_by(queue_type)
+                    .push_texture(texture, &states);
+            }
+            moved.push_texture(texture, &states);
+            taken
+                .textures
+                .insert(texture.tracker_index(), texture.clone());
+        }
+    }
+
+    /// Releases the resources in `taken` from the queues of `queue_type` back
+    /// to the graphics queues.
+    pub fn release_to_graphics(
+        &mut self,
+        taken: &TakenResources,
+        queue_type: wgt::QueueType,
+    ) -> OwnershipTransfer {
+        let mut transfer = OwnershipTransfer::default();
+        for buffer in taken.buffers.values() {
+            let state = self.buffers.set_released(buffer, queue_type);
+            transfer.buffers.push((buffer.clone(), state));
+        }
+        for texture in taken.textures.values() {
+            let states = self.textures.set_released(texture, queue_type);
+            transfer.push_texture(texture, &states);
+        }
+        transfer
+    }
+}
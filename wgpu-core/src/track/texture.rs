@@ -18,9 +18,9 @@
 //!   is known to be in some undefined state. Any transition away from UNINITIALIZED
 //!   will treat the contents as junk.
 
-use super::{range::RangedStates, PendingTransition, PendingTransitionList};
+use super::{range::RangedStates, PendingTransition, PendingTransitionList, TransitionLog};
 use crate::{
-    resource::{RawResourceAccess, Texture, TextureInner, TextureView, Trackable},
+    resource::{Labeled, RawResourceAccess, Texture, TextureInner, TextureView, Trackable},
     snatch::SnatchGuard,
     track::{
         invalid_resource_state, skip_barrier, ResourceMetadata, ResourceMetadataProvider,
@@ -419,6 +419,8 @@ pub(crate) struct TextureTracker {
     metadata: ResourceMetadata<Arc<Texture>>,
 
     temp: Vec<PendingTransition<TextureUses>>,
+
+    pub(super) log: Option<TransitionLog>,
 }
 
 impl TextureTracker {
@@ -430,6 +432,26 @@ impl TextureTracker {
             metadata: ResourceMetadata::new(),
 
             temp: Vec::new(),
+
+            log: None,
+        }
+    }
+
+    /// Logs the pending transitions, if the log is enabled.
+    fn log_transitions(&mut self) {
+        let Some(log) = &mut self.log else {
+            return;
+        };
+        for pending in &self.temp {
+            let tex = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
+            log.push(
+                tex.label(),
+                wgt::TransitionedResource::Texture {
+                    selector: pending.selector.clone(),
+                    from: pending.usage.from,
+                    to: pending.usage.to,
+                },
+            );
         }
     }
 
@@ -471,6 +493,7 @@ impl TextureTracker {
         &'a mut self,
         snatch_guard: &'a SnatchGuard<'a>,
     ) -> (PendingTransitionList, Vec<Option<&'a TextureInner>>) {
+        self.log_transitions();
         let mut textures = Vec::new();
         let transitions = self
             .temp
@@ -519,6 +542,7 @@ impl TextureTracker {
             )
         }
 
+        self.log_transitions();
         self.temp.drain(..)
     }
 
@@ -669,6 +693,11 @@ impl TextureTrackerSetSingle for TextureTracker {
 /// Stores all texture state within a device.
 pub(crate) struct DeviceTextureTracker {
     current_state_set: TextureStateSet,
+    /// The type of the queues that released each texture to the graphics
+    /// queues, if those haven't acquired it since.
+    ///
+    /// See [`DeviceTracker`](super::DeviceTracker).
+    released: Vec<Option<wgt::QueueType>>,
     metadata: ResourceMetadata<Weak<Texture>>,
     temp: Vec<PendingTransition<TextureUses>>,
 }
@@ -677,6 +706,7 @@ impl DeviceTextureTracker {
     pub fn new() -> Self {
         Self {
             current_state_set: TextureStateSet::new(),
+            released: Vec::new(),
             metadata: ResourceMetadata::new(),
             temp: Vec::new(),
         }
@@ -691,6 +721,7 @@ impl DeviceTextureTracker {
     fn allow_index(&mut self, index: usize) {
         if index >= self.current_state_set.size() {
             self.current_state_set.set_size(index + 1);
+            self.released.resize(index + 1, None);
             self.metadata.set_size(index + 1);
         }
     }
@@ -710,6 +741,7 @@ impl DeviceTextureTracker {
 
         self.tracker_assert_in_bounds(index);
 
+        self.released[index] = None;
         unsafe {
             insert(
                 None,
@@ -768,6 +800,57 @@ impl DeviceTextureTracker {
         self.temp.drain(..)
     }
 
+    /// Returns the states of the initialized subresources of `texture`, along
+    /// with the type of the queues that released it to the graphics queues if
+    /// those haven't acquired it yet.
+    ///
+    /// The graphics queues are then considered to own the texture.
+    pub fn take_release(
+        &mut self,
+        texture: &Arc<Texture>,
+    ) -> (Vec<(TextureSelector, TextureUses)>, Option<wgt::QueueType>) {
+        let index = texture.tracker_index().as_usize();
+
+        self.tracker_assert_in_bounds(index);
+
+        (
+            self.initialized_states(texture, index),
+            self.released[index].take(),
+        )
+    }
+
+    /// Records that the queues of `queue_type` released `texture` to the
+    /// graphics queues, and returns the states of its initialized subresources.
+    pub fn set_released(
+        &mut self,
+        texture: &Arc<Texture>,
+        queue_type: wgt::QueueType,
+    ) -> Vec<(TextureSelector, TextureUses)> {
+        let index = texture.tracker_index().as_usize();
+
+        self.tracker_assert_in_bounds(index);
+
+        self.released[index] = Some(queue_type);
+        self.initialized_states(texture, index)
+    }
+
+    /// The states of the subresources of `texture` other than the
+    /// uninitialized ones, whose contents don't need to move between queues.
+    fn initialized_states(
+        &self,
+        texture: &Texture,
+        index: usize,
+    ) -> Vec<(TextureSelector, TextureUses)> {
+        let mut states: Vec<_> = match unsafe { self.current_state_set.get_unchecked(index) } {
+            SingleOrManyStates::Single(state) => {
+                iter::once((texture.full_range.clone(), state)).collect()
+            }
+            SingleOrManyStates::Many(complex) => complex.to_selector_state_iter().collect(),
+        };
+        states.retain(|&(_, state)| state != TextureUses::UNINITIALIZED);
+        states
+    }
+
     /// Sets the given state for all texture in the given tracker.
     ///
     /// If a transition is needed to get the texture into the needed state,
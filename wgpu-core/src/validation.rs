@@ -155,6 +155,7 @@ impl fmt::Display for InterfaceVar {
 enum Varying {
     Local { location: u32, iv: InterfaceVar },
     BuiltIn(naga::BuiltIn),
+    FramebufferFetch { location: u32, ty: NumericType },
 }
 
 #[allow(unused)]
@@ -964,6 +965,10 @@ impl Interface {
                 },
             },
             Some(&naga::Binding::BuiltIn(built_in)) => Varying::BuiltIn(built_in),
+            Some(&naga::Binding::FramebufferFetch { location }) => Varying::FramebufferFetch {
+                location,
+                ty: numeric_ty,
+            },
             None => {
                 log::error!("Missing binding for a varying");
                 return;
@@ -1313,7 +1318,8 @@ impl Interface {
                         }
                     }
                 }
-                Varying::BuiltIn(_) => {}
+                // Checked against the color targets by `fragment_framebuffer_fetches`.
+                Varying::BuiltIn(_) | Varying::FramebufferFetch { .. } => {}
             }
         }
 
@@ -1322,7 +1328,7 @@ impl Interface {
                 //TODO: count builtins towards the limit?
                 inter_stage_components += match *output {
                     Varying::Local { ref iv, .. } => iv.ty.dim.num_components(),
-                    Varying::BuiltIn(_) => 0,
+                    Varying::BuiltIn(_) | Varying::FramebufferFetch { .. } => 0,
                 };
 
                 if let Some(
@@ -1354,7 +1360,7 @@ impl Interface {
             .iter()
             .filter_map(|output| match *output {
                 Varying::Local { location, ref iv } => Some((location, iv.clone())),
-                Varying::BuiltIn(_) => None,
+                Varying::BuiltIn(_) | Varying::FramebufferFetch { .. } => None,
             })
             .collect();
         Ok(outputs)
@@ -1370,6 +1376,40 @@ impl Interface {
             .ok_or(StageError::MissingEntryPoint(pair.1))
             .map(|ep| ep.dual_source_blending)
     }
+
+    /// Returns the attachments the fragment entry point reads with
+    /// framebuffer fetch.
+    pub fn fragment_framebuffer_fetches(
+        &self,
+        entry_point_name: &str,
+    ) -> Result<FramebufferFetches, StageError> {
+        let pair = (naga::ShaderStage::Fragment, entry_point_name.to_string());
+        let entry_point = self
+            .entry_points
+            .get(&pair)
+            .ok_or(StageError::MissingEntryPoint(pair.1))?;
+        let mut fetches = FramebufferFetches::default();
+        for input in entry_point.inputs.iter() {
+            match *input {
+                Varying::FramebufferFetch { location, ty } => fetches.colors.push((location, ty)),
+                Varying::BuiltIn(naga::BuiltIn::LastFragDepth) => fetches.depth = true,
+                Varying::BuiltIn(naga::BuiltIn::LastFragStencil) => fetches.stencil = true,
+                _ => {}
+            }
+        }
+        Ok(fetches)
+    }
+}
+
+/// The attachments a fragment entry point reads with framebuffer fetch.
+#[derive(Clone, Debug, Default)]
+pub struct FramebufferFetches {
+    /// The color locations read, along with the type they are read as.
+    pub colors: Vec<(u32, NumericType)>,
+    /// Whether the depth aspect is read.
+    pub depth: bool,
+    /// Whether the stencil aspect is read.
+    pub stencil: bool,
 }
 
 // https://gpuweb.github.io/gpuweb/#abstract-opdef-calculating-color-attachment-bytes-per-sample
@@ -0,0 +1,230 @@
+//! Video decoding.
+//!
+//! A [`VideoDecoder`] owns the decoding session of a stream, and creates the textures it decodes
+//! into, called pictures. Each picture is given a slot in the decoded picture buffer of the
+//! decoder, that it keeps until it's dropped.
+//!
+//! Decodes are submitted to the queue right away, after the work already submitted, and the next
+//! submission waits for them.
+
+use alloc::{
+    boxed::Box,
+    string::String,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::mem::ManuallyDrop;
+
+use thiserror::Error;
+use wgt::error::{ErrorType, WebGpuError};
+
+use crate::{
+    device::{Device, DeviceError, MissingFeatures},
+    lock::Mutex,
+    resource::{
+        DestroyedResourceError, InvalidResourceError, Labeled, ResourceErrorIdent, Texture,
+        TextureDescriptor,
+    },
+    resource_log, Label,
+};
+
+pub type VideoDecoderDescriptor<'a> = wgt::VideoDecoderDescriptor<Label<'a>>;
+pub type VideoPictureDescriptor<'a> = wgt::VideoPictureDescriptor<Label<'a>>;
+
+#[derive(Debug)]
+pub struct VideoDecoder {
+    pub(crate) raw: ManuallyDrop<Box<dyn hal::DynVideoDecoder>>,
+    pub(crate) device: Arc<Device>,
+    pub(crate) desc: wgt::VideoDecoderDescriptor<()>,
+    /// The `label` from the descriptor used to create the resource.
+    pub(crate) label: String,
+    /// The pictures created by the decoder, indexed by their slot.
+    ///
+    /// The slot of a picture that was dropped is given to the next picture created.
+    pub(crate) pictures: Mutex<Vec<Weak<Texture>>>,
+}
+
+impl Drop for VideoDecoder {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw {}", self.error_ident());
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe {
+            self.device.raw().destroy_video_decoder(raw);
+        }
+    }
+}
+
+impl VideoDecoder {
+    pub(crate) fn raw(&self) -> &dyn hal::DynVideoDecoder {
+        self.raw.as_ref()
+    }
+
+    /// The slot of `texture` if it's a picture of this decoder.
+    pub(crate) fn slot_of(&self, texture: &Arc<Texture>) -> Option<u32> {
+        self.pictures
+            .lock()
+            .iter()
+            .position(|picture| core::ptr::eq(picture.as_ptr(), Arc::as_ptr(texture)))
+            .map(|slot| slot as u32)
+    }
+
+    /// The descriptor of the textures of the pictures.
+    pub(crate) fn picture_texture_descriptor<'a>(
+        &self,
+        desc: &VideoPictureDescriptor<'a>,
+    ) -> TextureDescriptor<'a> {
+        wgt::TextureDescriptor {
+            label: desc.label.clone(),
+            size: self.desc.max_coded_extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D2,
+            format: self.desc.format,
+            usage: desc.usage,
+            view_formats: Vec::new(),
+        }
+    }
+}
+
+crate::impl_resource_type!(VideoDecoder);
+crate::impl_labeled!(VideoDecoder);
+crate::impl_parent_device!(VideoDecoder);
+crate::impl_storage_item!(VideoDecoder);
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateVideoDecoderError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Video pictures must be NV12 or P010, but {0:?} was given")]
+    InvalidFormat(wgt::TextureFormat),
+    #[error("The device can't decode {codec:?} into {format:?} pictures")]
+    UnsupportedCodec {
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    },
+    #[error("Coded extent {requested:?} is not within {min:?} and {max:?}")]
+    InvalidCodedExtent {
+        requested: wgt::Extent3d,
+        min: wgt::Extent3d,
+        max: wgt::Extent3d,
+    },
+    #[error("A decoder must have at least one picture")]
+    ZeroPictures,
+    #[error(
+        "The device supports decoders with up to {max} pictures, but {requested} were requested"
+    )]
+    TooManyPictures { requested: u32, max: u32 },
+    #[error("The device supports up to {max} reference pictures, but {requested} were requested")]
+    TooManyReferencePictures { requested: u32, max: u32 },
+}
+
+impl WebGpuError for CreateVideoDecoderError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::InvalidFormat(_)
+            | Self::UnsupportedCodec { .. }
+            | Self::InvalidCodedExtent { .. }
+            | Self::ZeroPictures
+            | Self::TooManyPictures { .. }
+            | Self::TooManyReferencePictures { .. } => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateVideoPictureError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error("Video pictures can only be used with `TEXTURE_BINDING` and `COPY_SRC`, but {0:?} was given")]
+    InvalidUsage(wgt::TextureUsages),
+    #[error("{decoder} already has its maximum of {max} pictures")]
+    TooManyPictures {
+        decoder: ResourceErrorIdent,
+        max: u32,
+    },
+}
+
+impl WebGpuError for CreateVideoPictureError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::InvalidUsage(_) | Self::TooManyPictures { .. } => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum DecodeVideoError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error(transparent)]
+    DestroyedResource(#[from] DestroyedResourceError),
+    #[error("The bitstream of the picture is empty")]
+    EmptyBitstream,
+    #[error("{decoder} decodes {expected:?}, but a {found:?} picture or reference was given")]
+    CodecMismatch {
+        decoder: ResourceErrorIdent,
+        expected: wgt::VideoCodec,
+        found: wgt::VideoCodec,
+    },
+    #[error("Coded extent {extent:?} of the picture is larger than the {max:?} of {decoder}")]
+    CodedExtentTooLarge {
+        decoder: ResourceErrorIdent,
+        extent: wgt::Extent3d,
+        max: wgt::Extent3d,
+    },
+    #[error("{decoder} supports up to {max} reference pictures, but {count} were given")]
+    TooManyReferences {
+        decoder: ResourceErrorIdent,
+        count: usize,
+        max: u32,
+    },
+    #[error("{texture} is not a picture of {decoder}")]
+    NotAPicture {
+        texture: ResourceErrorIdent,
+        decoder: ResourceErrorIdent,
+    },
+    #[error("{0} is used more than once by the decode")]
+    DuplicatePicture(ResourceErrorIdent),
+    #[error("The picture refers to reference {index}, but only {count} references were given")]
+    InvalidReferenceIndex { index: usize, count: usize },
+    #[error("A reference picture set of the picture has {0} entries, but can have at most 8")]
+    ReferencePictureSetTooLong(usize),
+}
+
+impl WebGpuError for DecodeVideoError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::DestroyedResource(e) => e,
+            Self::EmptyBitstream
+            | Self::CodecMismatch { .. }
+            | Self::CodedExtentTooLarge { .. }
+            | Self::TooManyReferences { .. }
+            | Self::NotAPicture { .. }
+            | Self::DuplicatePicture(_)
+            | Self::InvalidReferenceIndex { .. }
+            | Self::ReferencePictureSetTooLong(_) => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
@@ -306,6 +306,7 @@ fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height
             .create_command_encoder(&hal::CommandEncoderDescriptor {
                 label: None,
                 queue: &od.queue,
+                reusable: false,
             })
             .unwrap()
     };
@@ -371,6 +371,7 @@ impl<A: hal::Api> Example<A> {
                 module: Cow::Owned(module),
                 info,
                 debug_source: None,
+                bounds_report: None,
             }
         };
         let shader_desc = hal::ShaderModuleDescriptor {
@@ -735,6 +736,7 @@ impl<A: hal::Api> Example<A> {
         let cmd_encoder_desc = hal::CommandEncoderDescriptor {
             label: None,
             queue: &queue,
+            reusable: false,
         };
         let mut cmd_encoder = unsafe { device.create_command_encoder(&cmd_encoder_desc).unwrap() };
 
@@ -1055,6 +1057,7 @@ impl<A: hal::Api> Example<A> {
             let hal_desc = hal::CommandEncoderDescriptor {
                 label: None,
                 queue: &self.queue,
+                reusable: false,
             };
             self.contexts.push(unsafe {
                 ExecutionContext {
@@ -55,6 +55,13 @@ pub mod db {
 /// offset at some intermediate point, internally, as i32.
 pub const MAX_I32_BINDING_SIZE: u32 = (1 << 31) - 1;
 
+/// Make `policies` report the out-of-bounds accesses of indices and buffers,
+/// for shaders with [`crate::NagaShader::bounds_report_options`].
+pub fn report_out_of_bounds_accesses(policies: &mut naga::proc::BoundsCheckPolicies) {
+    policies.index = naga::proc::BoundsCheckPolicy::Report;
+    policies.buffer = naga::proc::BoundsCheckPolicy::Report;
+}
+
 pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
     match stage {
         naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
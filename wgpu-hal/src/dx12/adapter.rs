@@ -364,7 +364,10 @@ impl super::Adapter {
             | wgt::Features::FLOAT32_FILTERABLE
             | wgt::Features::TEXTURE_ATOMIC
             | wgt::Features::EXPERIMENTAL_PASSTHROUGH_SHADERS
-            | wgt::Features::EXTERNAL_TEXTURE;
+            | wgt::Features::EXTERNAL_TEXTURE
+            | wgt::Features::REUSABLE_COMMAND_BUFFERS
+            | wgt::Features::INDIRECT_COMMANDS
+            | wgt::Features::RENDER_BUNDLE_INHERITANCE;
 
         //TODO: in order to expose this, we need to run a compute shader
         // that extract the necessary statistics out of the D3D12 result.
@@ -529,6 +532,13 @@ impl super::Adapter {
             atomic_int64_on_typed_resource_supported,
         );
 
+        // Tier 2 returns zero from unmapped tiles and gives each array layer
+        // its own packed mips.
+        features.set(
+            wgt::Features::SPARSE_RESOURCES,
+            options.TiledResourcesTier.0 >= Direct3D12::D3D12_TILED_RESOURCES_TIER_2.0,
+        );
+
         // TODO: Determine if IPresentationManager is supported
         let presentation_timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
 
@@ -672,6 +682,9 @@ impl super::Adapter {
                     } else {
                         0
                     },
+
+                    max_compute_queues: super::MAX_QUEUES_PER_TYPE,
+                    max_transfer_queues: super::MAX_QUEUES_PER_TYPE,
                 },
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(
@@ -709,7 +722,7 @@ impl crate::Adapter for super::Adapter {
             unsafe {
                 self.device
                     .CreateCommandQueue(&Direct3D12::D3D12_COMMAND_QUEUE_DESC {
-                        Type: Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
+                        Type: super::conv::map_queue_type(wgt::QueueType::Graphics),
                         Priority: Direct3D12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL.0,
                         Flags: Direct3D12::D3D12_COMMAND_QUEUE_FLAG_NONE,
                         NodeMask: 0,
@@ -735,6 +748,7 @@ impl crate::Adapter for super::Adapter {
             device,
             queue: super::Queue {
                 raw: queue,
+                queue_type: wgt::QueueType::Graphics,
                 temp_lists: Mutex::new(Vec::new()),
             },
         })
@@ -85,6 +85,152 @@ impl Drop for super::CommandEncoder {
 }
 
 impl super::CommandEncoder {
+    /// Adds the barrier transitioning `buffer` from `s0` to `s1` to the
+    /// temporary barriers.
+    fn push_buffer_transition(
+        &mut self,
+        buffer: &super::Buffer,
+        s0: Direct3D12::D3D12_RESOURCE_STATES,
+        s1: Direct3D12::D3D12_RESOURCE_STATES,
+    ) {
+        let raw = Direct3D12::D3D12_RESOURCE_BARRIER {
+            Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+            Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+            Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                Transition: mem::ManuallyDrop::new(Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
+                    pResource: unsafe { borrow_interface_temporarily(&buffer.resource) },
+                    Subresource: Direct3D12::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                    StateBefore: s0,
+                    StateAfter: s1,
+                }),
+            },
+        };
+        self.temp.barriers.push(raw);
+    }
+
+    /// Adds the barriers transitioning the subresources of `texture` in
+    /// `range` from `s0` to `s1` to the temporary barriers.
+    fn push_texture_transition(
+        &mut self,
+        texture: &super::Texture,
+        range: &wgt::ImageSubresourceRange,
+        s0: Direct3D12::D3D12_RESOURCE_STATES,
+        s1: Direct3D12::D3D12_RESOURCE_STATES,
+    ) {
+        let mut raw = Direct3D12::D3D12_RESOURCE_BARRIER {
+            Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+            Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+            Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                Transition: mem::ManuallyDrop::new(Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
+                    pResource: unsafe { borrow_interface_temporarily(&texture.resource) },
+                    Subresource: Direct3D12::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                    StateBefore: s0,
+                    StateAfter: s1,
+                }),
+            },
+        };
+
+        let tex_mip_level_count = texture.mip_level_count;
+        let tex_array_layer_count = texture.array_layer_count();
+
+        if range.is_full_resource(texture.format, tex_mip_level_count, tex_array_layer_count) {
+            // Only one barrier if it affects the whole image.
+            self.temp.barriers.push(raw);
+            return;
+        }
+
+        // Selected texture aspect is relevant if the texture format has both depth _and_ stencil aspects.
+        let planes = if texture.format.is_combined_depth_stencil_format() {
+            match range.aspect {
+                wgt::TextureAspect::All => 0..2,
+                wgt::TextureAspect::DepthOnly => 0..1,
+                wgt::TextureAspect::StencilOnly => 1..2,
+                _ => unreachable!(),
+            }
+        } else if let Some(planes) = texture.format.planes() {
+            match range.aspect {
+                wgt::TextureAspect::All => 0..planes,
+                wgt::TextureAspect::Plane0 => 0..1,
+                wgt::TextureAspect::Plane1 => 1..2,
+                wgt::TextureAspect::Plane2 => 2..3,
+                _ => unreachable!(),
+            }
+        } else {
+            match texture.format {
+                wgt::TextureFormat::Stencil8 => 1..2,
+                wgt::TextureFormat::Depth24Plus => 0..2, // TODO: investigate why tests fail if we set this to 0..1
+                _ => 0..1,
+            }
+        };
+
+        for mip_level in range.mip_range(tex_mip_level_count) {
+            for array_layer in range.layer_range(tex_array_layer_count) {
+                for plane in planes.clone() {
+                    unsafe { &mut *raw.Anonymous.Transition }.Subresource =
+                        texture.calc_subresource(mip_level, array_layer, plane);
+                    self.temp.barriers.push(raw.clone());
+                }
+            }
+        }
+    }
+
+    /// Records the temporary barriers, if there are any.
+    unsafe fn flush_barriers(&mut self) {
+        if !self.temp.barriers.is_empty() {
+            unsafe {
+                self.list
+                    .as_ref()
+                    .unwrap()
+                    .ResourceBarrier(&self.temp.barriers)
+            };
+        }
+    }
+
+    /// Records the transitions of `buffers` and `textures` from their state
+    /// to the common state, when `release` is true, or back.
+    ///
+    /// Resources move between queues of different types in the common state,
+    /// which the command lists of all types support.
+    unsafe fn transition_common_state<'a, T, U>(&mut self, release: bool, buffers: T, textures: U)
+    where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        let common = Direct3D12::D3D12_RESOURCE_STATE_COMMON;
+        self.temp.barriers.clear();
+
+        for barrier in buffers {
+            let state = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_state(barrier.usage.from),
+            );
+            if state != common {
+                let (s0, s1) = if release {
+                    (state, common)
+                } else {
+                    (common, state)
+                };
+                self.push_buffer_transition(barrier.buffer, s0, s1);
+            }
+        }
+        for barrier in textures {
+            let state = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_state(barrier.usage.from),
+            );
+            if state != common {
+                let (s0, s1) = if release {
+                    (state, common)
+                } else {
+                    (common, state)
+                };
+                self.push_texture_transition(barrier.texture, &barrier.range, s0, s1);
+            }
+        }
+
+        unsafe { self.flush_barriers() };
+    }
+
     unsafe fn begin_pass(&mut self, kind: super::PassKind, label: crate::Label) {
         let list = self.list.as_ref().unwrap();
         self.pass.kind = kind;
@@ -336,7 +482,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             unsafe {
                 self.device.CreateCommandList(
                     0,
-                    Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
+                    conv::map_queue_type(self.queue_type),
                     &self.allocator,
                     None,
                 )
@@ -381,26 +527,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.temp.barriers.clear();
 
         for barrier in barriers {
-            let s0 = conv::map_buffer_usage_to_state(barrier.usage.from);
-            let s1 = conv::map_buffer_usage_to_state(barrier.usage.to);
+            let s0 = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_state(barrier.usage.from),
+            );
+            let s1 = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_state(barrier.usage.to),
+            );
             if s0 != s1 {
-                let raw = Direct3D12::D3D12_RESOURCE_BARRIER {
-                    Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
-                    Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
-                    Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
-                        Transition: mem::ManuallyDrop::new(
-                            Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
-                                pResource: unsafe {
-                                    borrow_interface_temporarily(&barrier.buffer.resource)
-                                },
-                                Subresource: Direct3D12::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
-                                StateBefore: s0,
-                                StateAfter: s1,
-                            },
-                        ),
-                    },
-                };
-                self.temp.barriers.push(raw);
+                self.push_buffer_transition(barrier.buffer, s0, s1);
             } else if barrier.usage.from == wgt::BufferUses::STORAGE_READ_WRITE
                 || barrier.usage.from == wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY
             {
@@ -419,14 +555,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             }
         }
 
-        if !self.temp.barriers.is_empty() {
-            unsafe {
-                self.list
-                    .as_ref()
-                    .unwrap()
-                    .ResourceBarrier(&self.temp.barriers)
-            };
-        }
+        unsafe { self.flush_barriers() };
     }
 
     unsafe fn transition_textures<'a, T>(&mut self, barriers: T)
@@ -436,72 +565,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.temp.barriers.clear();
 
         for barrier in barriers {
-            let s0 = conv::map_texture_usage_to_state(barrier.usage.from);
-            let s1 = conv::map_texture_usage_to_state(barrier.usage.to);
+            let s0 = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_state(barrier.usage.from),
+            );
+            let s1 = conv::map_state_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_state(barrier.usage.to),
+            );
             if s0 != s1 {
-                let mut raw = Direct3D12::D3D12_RESOURCE_BARRIER {
-                    Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
-                    Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
-                    Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
-                        Transition: mem::ManuallyDrop::new(
-                            Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
-                                pResource: unsafe {
-                                    borrow_interface_temporarily(&barrier.texture.resource)
-                                },
-                                Subresource: Direct3D12::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
-                                StateBefore: s0,
-                                StateAfter: s1,
-                            },
-                        ),
-                    },
-                };
-
-                let tex_mip_level_count = barrier.texture.mip_level_count;
-                let tex_array_layer_count = barrier.texture.array_layer_count();
-
-                if barrier.range.is_full_resource(
-                    barrier.texture.format,
-                    tex_mip_level_count,
-                    tex_array_layer_count,
-                ) {
-                    // Only one barrier if it affects the whole image.
-                    self.temp.barriers.push(raw);
-                } else {
-                    // Selected texture aspect is relevant if the texture format has both depth _and_ stencil aspects.
-                    let planes = if barrier.texture.format.is_combined_depth_stencil_format() {
-                        match barrier.range.aspect {
-                            wgt::TextureAspect::All => 0..2,
-                            wgt::TextureAspect::DepthOnly => 0..1,
-                            wgt::TextureAspect::StencilOnly => 1..2,
-                            _ => unreachable!(),
-                        }
-                    } else if let Some(planes) = barrier.texture.format.planes() {
-                        match barrier.range.aspect {
-                            wgt::TextureAspect::All => 0..planes,
-                            wgt::TextureAspect::Plane0 => 0..1,
-                            wgt::TextureAspect::Plane1 => 1..2,
-                            wgt::TextureAspect::Plane2 => 2..3,
-                            _ => unreachable!(),
-                        }
-                    } else {
-                        match barrier.texture.format {
-                            wgt::TextureFormat::Stencil8 => 1..2,
-                            wgt::TextureFormat::Depth24Plus => 0..2, // TODO: investigate why tests fail if we set this to 0..1
-                            _ => 0..1,
-                        }
-                    };
-
-                    for mip_level in barrier.range.mip_range(tex_mip_level_count) {
-                        for array_layer in barrier.range.layer_range(tex_array_layer_count) {
-                            for plane in planes.clone() {
-                                unsafe { &mut *raw.Anonymous.Transition }.Subresource = barrier
-                                    .texture
-                                    .calc_subresource(mip_level, array_layer, plane);
-                                self.temp.barriers.push(raw.clone());
-                            }
-                        }
-                    }
-                }
+                self.push_texture_transition(barrier.texture, &barrier.range, s0, s1);
             } else if barrier.usage.from == wgt::TextureUses::STORAGE_READ_WRITE {
                 let raw = Direct3D12::D3D12_RESOURCE_BARRIER {
                     Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_UAV,
@@ -518,14 +591,31 @@ impl crate::CommandEncoder for super::CommandEncoder {
             }
         }
 
-        if !self.temp.barriers.is_empty() {
-            unsafe {
-                self.list
-                    .as_ref()
-                    .unwrap()
-                    .ResourceBarrier(&self.temp.barriers)
-            };
-        }
+        unsafe { self.flush_barriers() };
+    }
+
+    unsafe fn release_resources<'a, T, U>(
+        &mut self,
+        _queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        unsafe { self.transition_common_state(true, buffers, textures) };
+    }
+
+    unsafe fn acquire_resources<'a, T, U>(
+        &mut self,
+        _queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        unsafe { self.transition_common_state(false, buffers, textures) };
     }
 
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
@@ -1226,6 +1316,62 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         }
     }
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &super::PipelineLayout,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&super::Buffer, wgt::BufferAddress)>,
+    ) {
+        unsafe { self.prepare_vertex_buffers() };
+        self.update_root_elements();
+
+        let mut signatures = pipeline_layout.indirect_command_signatures.lock();
+        let cmd_signature = match signatures.entry(*layout) {
+            hashbrown::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hashbrown::hash_map::Entry::Vacant(entry) => {
+                let Ok(signature) = create_indirect_command_signature(
+                    &self.device,
+                    &pipeline_layout.shared,
+                    layout,
+                ) else {
+                    log::error!("Failed to create the command signature of {layout:?}");
+                    return;
+                };
+                entry.insert(signature)
+            }
+        };
+
+        let (count_buffer, count_offset) = match count_buffer {
+            Some((count_buffer, count_offset)) => (Some(&count_buffer.resource), count_offset),
+            None => (None, 0),
+        };
+        unsafe {
+            self.list.as_ref().unwrap().ExecuteIndirect(
+                &*cmd_signature,
+                max_count,
+                &buffer.resource,
+                offset,
+                count_buffer,
+                count_offset,
+            )
+        }
+        drop(signatures);
+
+        // The state switched by the records is left undefined, so it is
+        // applied again before the next draw.
+        self.pass.dirty_vertex_buffers |= layout.vertex_buffers;
+        if let Some(info) = pipeline_layout.shared.root_constant_info.as_ref() {
+            if layout.push_constant_size != 0 {
+                self.pass.dirty_root_elements |= 1 << info.root_index;
+            }
+        }
+        if let Some(sc) = pipeline_layout.shared.special_constants.as_ref() {
+            self.pass.root_elements[sc.root_index as usize] = super::RootElement::Empty;
+        }
+    }
     unsafe fn draw_mesh_tasks(
         &mut self,
         _group_count_x: u32,
@@ -1328,10 +1474,31 @@ impl crate::CommandEncoder for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
-        unsafe { self.prepare_draw(0, 0) };
+        if self
+            .pass
+            .layout
+            .special_constants
+            .as_ref()
+            .and_then(|sc| sc.indirect_cmd_signatures.as_ref())
+            .is_some()
+        {
+            unsafe { self.prepare_vertex_buffers() };
+            self.update_root_elements();
+        } else {
+            unsafe { self.prepare_draw(0, 0) };
+        }
+
+        let cmd_signature = &self
+            .pass
+            .layout
+            .special_constants
+            .as_ref()
+            .and_then(|sc| sc.indirect_cmd_signatures.as_ref())
+            .unwrap_or_else(|| &self.shared.cmd_signatures)
+            .draw;
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
-                &self.shared.cmd_signatures.draw,
+                cmd_signature,
                 max_count,
                 &buffer.resource,
                 offset,
@@ -1348,10 +1515,31 @@ impl crate::CommandEncoder for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
-        unsafe { self.prepare_draw(0, 0) };
+        if self
+            .pass
+            .layout
+            .special_constants
+            .as_ref()
+            .and_then(|sc| sc.indirect_cmd_signatures.as_ref())
+            .is_some()
+        {
+            unsafe { self.prepare_vertex_buffers() };
+            self.update_root_elements();
+        } else {
+            unsafe { self.prepare_draw(0, 0) };
+        }
+
+        let cmd_signature = &self
+            .pass
+            .layout
+            .special_constants
+            .as_ref()
+            .and_then(|sc| sc.indirect_cmd_signatures.as_ref())
+            .unwrap_or_else(|| &self.shared.cmd_signatures)
+            .draw_indexed;
         unsafe {
             self.list.as_ref().unwrap().ExecuteIndirect(
-                &self.shared.cmd_signatures.draw_indexed,
+                cmd_signature,
                 max_count,
                 &buffer.resource,
                 offset,
@@ -1669,3 +1857,83 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 }
+
+/// Creates the command signature matching the native records of `layout`.
+///
+/// See [`crate::CommandEncoder::execute_indirect`] for the layout of a record.
+fn create_indirect_command_signature(
+    device: &Direct3D12::ID3D12Device,
+    pipeline_layout: &super::PipelineLayoutShared,
+    layout: &wgt::IndirectCommandLayout,
+) -> Result<Direct3D12::ID3D12CommandSignature, crate::DeviceError> {
+    let mut arguments = Vec::new();
+    let mut byte_stride = 0;
+    if layout.push_constant_size != 0 {
+        let info = pipeline_layout.root_constant_info.as_ref().unwrap();
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_CONSTANT,
+            Anonymous: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0 {
+                Constant: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0_1 {
+                    RootParameterIndex: info.root_index,
+                    DestOffsetIn32BitValues: layout.push_constant_offset / 4,
+                    Num32BitValuesToSet: layout.push_constant_size / 4,
+                },
+            },
+        });
+        byte_stride += layout.push_constant_size as usize;
+    }
+    let mut vertex_buffers = layout.vertex_buffers;
+    while vertex_buffers != 0 {
+        let slot = vertex_buffers.trailing_zeros();
+        vertex_buffers ^= 1 << slot;
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_VERTEX_BUFFER_VIEW,
+            Anonymous: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0 {
+                VertexBuffer: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0_5 { Slot: slot },
+            },
+        });
+        byte_stride += size_of::<Direct3D12::D3D12_VERTEX_BUFFER_VIEW>();
+    }
+    if layout.index_buffer {
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_INDEX_BUFFER_VIEW,
+            ..Default::default()
+        });
+        byte_stride += size_of::<Direct3D12::D3D12_INDEX_BUFFER_VIEW>();
+    }
+    if let Some(sc) = pipeline_layout.special_constants.as_ref() {
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_CONSTANT,
+            Anonymous: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0 {
+                Constant: Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC_0_1 {
+                    RootParameterIndex: sc.root_index,
+                    DestOffsetIn32BitValues: 0,
+                    Num32BitValuesToSet: 3,
+                },
+            },
+        });
+        byte_stride += 3 * size_of::<u32>();
+    }
+    if layout.indexed {
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_DRAW_INDEXED,
+            ..Default::default()
+        });
+        byte_stride += size_of::<wgt::DrawIndexedIndirectArgs>();
+    } else {
+        arguments.push(Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_DRAW,
+            ..Default::default()
+        });
+        byte_stride += size_of::<wgt::DrawIndirectArgs>();
+    }
+
+    // The root signature is only needed when root arguments are written.
+    let root_signature =
+        if layout.push_constant_size != 0 || pipeline_layout.special_constants.is_some() {
+            pipeline_layout.signature.as_ref()
+        } else {
+            None
+        };
+    super::Device::create_command_signature(device, root_signature, byte_stride, &arguments, 0)
+}
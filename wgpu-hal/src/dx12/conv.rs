@@ -207,6 +207,43 @@ pub fn map_texture_usage_to_state(usage: wgt::TextureUses) -> Direct3D12::D3D12_
     state
 }
 
+pub fn map_queue_type(queue_type: wgt::QueueType) -> Direct3D12::D3D12_COMMAND_LIST_TYPE {
+    match queue_type {
+        wgt::QueueType::Graphics => Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
+        wgt::QueueType::Compute => Direct3D12::D3D12_COMMAND_LIST_TYPE_COMPUTE,
+        wgt::QueueType::Transfer => Direct3D12::D3D12_COMMAND_LIST_TYPE_COPY,
+    }
+}
+
+/// Restricts `state` to the states the command lists of queues of `queue_type`
+/// can transition resources to and from.
+///
+/// The usages of a resource on other queues are ordered by the fences the
+/// queues wait for, so the barriers of a queue only need the states of its
+/// own commands.
+pub fn map_state_to_queue(
+    queue_type: wgt::QueueType,
+    state: Direct3D12::D3D12_RESOURCE_STATES,
+) -> Direct3D12::D3D12_RESOURCE_STATES {
+    let supported = match queue_type {
+        wgt::QueueType::Graphics => return state,
+        wgt::QueueType::Compute => {
+            Direct3D12::D3D12_RESOURCE_STATE_VERTEX_AND_CONSTANT_BUFFER
+                | Direct3D12::D3D12_RESOURCE_STATE_UNORDERED_ACCESS
+                | Direct3D12::D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE
+                | Direct3D12::D3D12_RESOURCE_STATE_INDIRECT_ARGUMENT
+                | Direct3D12::D3D12_RESOURCE_STATE_COPY_DEST
+                | Direct3D12::D3D12_RESOURCE_STATE_COPY_SOURCE
+                | Direct3D12::D3D12_RESOURCE_STATE_RAYTRACING_ACCELERATION_STRUCTURE
+        }
+        wgt::QueueType::Transfer => {
+            Direct3D12::D3D12_RESOURCE_STATE_COPY_DEST
+                | Direct3D12::D3D12_RESOURCE_STATE_COPY_SOURCE
+        }
+    };
+    Direct3D12::D3D12_RESOURCE_STATES(state.0 & supported.0)
+}
+
 pub fn map_topology(
     topology: wgt::PrimitiveTopology,
 ) -> (
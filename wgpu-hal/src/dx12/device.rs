@@ -211,7 +211,7 @@ impl super::Device {
         })
     }
 
-    fn create_command_signature(
+    pub(super) fn create_command_signature(
         raw: &Direct3D12::ID3D12Device,
         root_signature: Option<&Direct3D12::ID3D12RootSignature>,
         byte_stride: usize,
@@ -269,17 +269,25 @@ impl super::Device {
     ) -> Result<super::CompiledShader, crate::PipelineError> {
         let stage_bit = auxil::map_naga_stage(naga_stage);
 
+        let bounds_report = match stage.module.source {
+            super::ShaderModuleSource::Naga(ref naga_shader) => {
+                naga_shader.bounds_report_options(naga_stage, stage.entry_point)
+            }
+            _ => None,
+        };
         let needs_temp_options = stage.zero_initialize_workgroup_memory
             != layout.naga_options.zero_initialize_workgroup_memory
             || stage.module.runtime_checks.bounds_checks != layout.naga_options.restrict_indexing
             || stage.module.runtime_checks.force_loop_bounding
-                != layout.naga_options.force_loop_bounding;
+                != layout.naga_options.force_loop_bounding
+            || bounds_report.is_some();
         let mut temp_options;
         let naga_options = if needs_temp_options {
             temp_options = layout.naga_options.clone();
             temp_options.zero_initialize_workgroup_memory = stage.zero_initialize_workgroup_memory;
             temp_options.restrict_indexing = stage.module.runtime_checks.bounds_checks;
             temp_options.force_loop_bounding = stage.module.runtime_checks.force_loop_bounding;
+            temp_options.bounds_report = bounds_report;
             &temp_options
         } else {
             &layout.naga_options
@@ -438,6 +446,7 @@ impl super::Device {
                 suballocation::AllocationType::Texture,
                 format.theoretical_memory_footprint(size),
             ),
+            sparse: None,
         }
     }
 
@@ -452,6 +461,7 @@ impl super::Device {
                 suballocation::AllocationType::Buffer,
                 size,
             ),
+            sparse: None,
         }
     }
 }
@@ -471,6 +481,10 @@ impl crate::Device for super::Device {
                 .next_multiple_of(Direct3D12::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT.into())
         }
 
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            return unsafe { self.create_sparse_buffer(&desc) };
+        }
+
         let (resource, allocation) =
             suballocation::DeviceAllocationContext::from(self).create_buffer(&desc)?;
 
@@ -480,6 +494,7 @@ impl crate::Device for super::Device {
             resource,
             size: desc.size,
             allocation,
+            sparse: None,
         })
     }
 
@@ -494,6 +509,13 @@ impl crate::Device for super::Device {
         self.counters.buffers.add(1);
     }
 
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &super::Buffer,
+    ) -> Option<wgt::BufferAddress> {
+        Some(unsafe { buffer.resource.GetGPUVirtualAddress() })
+    }
+
     unsafe fn map_buffer(
         &self,
         buffer: &super::Buffer,
@@ -546,6 +568,10 @@ impl crate::Device for super::Device {
             Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
         };
 
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            return unsafe { self.create_sparse_texture(desc, raw_desc) };
+        }
+
         let (resource, allocation) =
             suballocation::DeviceAllocationContext::from(self).create_texture(desc, raw_desc)?;
 
@@ -559,6 +585,7 @@ impl crate::Device for super::Device {
             mip_level_count: desc.mip_level_count,
             sample_count: desc.sample_count,
             allocation,
+            sparse: None,
         })
     }
 
@@ -761,9 +788,10 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::CommandEncoderDescriptor<super::Queue>,
     ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        let queue_type = desc.queue.queue_type;
         let allocator: Direct3D12::ID3D12CommandAllocator = unsafe {
             self.raw
-                .CreateCommandAllocator(Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT)
+                .CreateCommandAllocator(conv::map_queue_type(queue_type))
         }
         .into_device_result("Command allocator creation")?;
 
@@ -783,6 +811,7 @@ impl crate::Device for super::Device {
             null_rtv_handle: self.null_rtv_handle,
             list: None,
             free_lists: Vec::new(),
+            queue_type,
             pass: super::PassState::new(),
             temp: super::Temp::default(),
             end_of_pass_timer_query: None,
@@ -1445,7 +1474,10 @@ impl crate::Device for super::Device {
                 sampler_buffer_binding_map,
                 external_texture_binding_map,
                 force_loop_bounding: true,
+                // Set for each stage.
+                bounds_report: None,
             },
+            indirect_command_signatures: Mutex::new(Default::default()),
         })
     }
 
@@ -2426,6 +2458,32 @@ impl crate::Device for super::Device {
         );
     }
 
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        _index: u32,
+    ) -> Result<super::Queue, crate::DeviceError> {
+        let raw: Direct3D12::ID3D12CommandQueue = {
+            profiling::scope!("ID3D12Device::CreateCommandQueue");
+            unsafe {
+                self.raw
+                    .CreateCommandQueue(&Direct3D12::D3D12_COMMAND_QUEUE_DESC {
+                        Type: conv::map_queue_type(queue_type),
+                        Priority: Direct3D12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL.0,
+                        Flags: Direct3D12::D3D12_COMMAND_QUEUE_FLAG_NONE,
+                        NodeMask: 0,
+                    })
+            }
+            .into_device_result("Queue creation")?
+        };
+
+        Ok(super::Queue {
+            raw,
+            queue_type,
+            temp_lists: Mutex::new(Vec::new()),
+        })
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters {
         self.counters.as_ref().clone()
     }
@@ -2434,6 +2492,28 @@ impl crate::Device for super::Device {
         Some(self.mem_allocator.generate_report())
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        let mut segment_groups = vec![(Dxgi::DXGI_MEMORY_SEGMENT_GROUP_LOCAL, true)];
+        if matches!(
+            self.shared.private_caps.memory_architecture,
+            super::MemoryArchitecture::NonUnified
+        ) {
+            segment_groups.push((Dxgi::DXGI_MEMORY_SEGMENT_GROUP_NON_LOCAL, false));
+        }
+
+        let mut heaps = Vec::with_capacity(segment_groups.len());
+        for (group, device_local) in segment_groups {
+            let info = self.shared.adapter.query_video_memory_info(group).ok()?;
+            heaps.push(wgt::MemoryHeapBudget {
+                device_local,
+                budget: info.Budget,
+                usage: info.CurrentUsage,
+            });
+        }
+
+        Some(wgt::MemoryBudgetReport { heaps })
+    }
+
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8> {
         const MAX_U24: u32 = (1u32 << 24u32) - 1u32;
         let temp = Direct3D12::D3D12_RAYTRACING_INSTANCE_DESC {
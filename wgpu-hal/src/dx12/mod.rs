@@ -80,6 +80,7 @@ mod device;
 mod instance;
 mod sampler;
 mod shader_compilation;
+mod sparse;
 mod suballocation;
 mod types;
 mod view;
@@ -425,6 +426,7 @@ impl crate::Api for Api {
     type PipelineCache = PipelineCache;
 
     type AccelerationStructure = AccelerationStructure;
+    type VideoDecoder = VideoDecoder;
 }
 
 crate::impl_dyn_resource!(
@@ -448,12 +450,16 @@ crate::impl_dyn_resource!(
     ShaderModule,
     Surface,
     Texture,
-    TextureView
+    TextureView,
+    VideoDecoder
 );
 
 // Limited by D3D12's root signature size of 64. Each element takes 1 or 2 entries.
 const MAX_ROOT_ELEMENTS: usize = 64;
 const ZERO_BUFFER_SIZE: wgt::BufferAddress = 256 << 10;
+// D3D12 creates any number of compute and copy queues, but each of them is
+// scheduled on the few engines of the GPU.
+const MAX_QUEUES_PER_TYPE: u32 = 4;
 
 pub struct Instance {
     factory: DxgiFactory,
@@ -706,6 +712,8 @@ unsafe impl Sync for Device {}
 
 pub struct Queue {
     raw: Direct3D12::ID3D12CommandQueue,
+    /// The type of the queue, which is the type of the command lists it executes.
+    queue_type: wgt::QueueType,
     temp_lists: Mutex<Vec<Option<Direct3D12::ID3D12CommandList>>>,
 }
 
@@ -832,6 +840,9 @@ pub struct CommandEncoder {
     null_rtv_handle: descriptor::Handle,
     list: Option<Direct3D12::ID3D12GraphicsCommandList>,
     free_lists: Vec<Direct3D12::ID3D12GraphicsCommandList>,
+    /// The type of the queue the command lists are executed by, which
+    /// restricts the resource states their barriers can use.
+    queue_type: wgt::QueueType,
     pass: PassState,
     temp: Temp,
 
@@ -872,6 +883,7 @@ pub struct Buffer {
     // as the allocation size varies for assorted reasons.
     size: wgt::BufferAddress,
     allocation: suballocation::Allocation,
+    sparse: Option<Mutex<sparse::SparseBuffer>>,
 }
 
 unsafe impl Send for Buffer {}
@@ -902,6 +914,7 @@ pub struct Texture {
     mip_level_count: u32,
     sample_count: u32,
     allocation: suballocation::Allocation,
+    sparse: Option<Mutex<sparse::SparseTexture>>,
 }
 
 impl Texture {
@@ -1092,6 +1105,10 @@ pub struct PipelineLayout {
     // in the root signature. This is required for binding descriptor sets.
     bind_group_infos: ArrayVec<BindGroupInfo, { crate::MAX_BIND_GROUPS }>,
     naga_options: naga::back::hlsl::Options,
+    /// Command signatures created by `execute_indirect`, which depend on the
+    /// root signature when they write push constants or special constants.
+    indirect_command_signatures:
+        Mutex<HashMap<wgt::IndirectCommandLayout, Direct3D12::ID3D12CommandSignature>>,
 }
 
 impl crate::DynPipelineLayout for PipelineLayout {}
@@ -1189,6 +1206,11 @@ pub struct AccelerationStructure {
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
 
+#[derive(Debug)]
+pub struct VideoDecoder;
+
+impl crate::DynVideoDecoder for VideoDecoder {}
+
 impl SwapChain {
     unsafe fn release_resources(mut self) -> Dxgi::IDXGISwapChain3 {
         if let Some(mut waitable) = self.waitable.take() {
@@ -1464,6 +1486,7 @@ impl crate::Surface for Surface {
                 suballocation::AllocationType::Texture,
                 sc.format.theoretical_memory_footprint(sc.size),
             ),
+            sparse: None,
         };
         Ok(Some(crate::AcquiredSurfaceTexture {
             texture,
@@ -1508,6 +1531,16 @@ impl crate::Queue for Queue {
 
         Ok(())
     }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        // The queue waits on the GPU, before executing the command lists
+        // submitted after this.
+        unsafe { self.raw.Wait(&fence.raw, value) }.into_device_result("Wait fence")
+    }
     unsafe fn present(
         &self,
         surface: &Surface,
@@ -1537,6 +1570,14 @@ impl crate::Queue for Queue {
         let frequency = unsafe { self.raw.GetTimestampFrequency() }.expect("GetTimestampFrequency");
         (1_000_000_000.0 / frequency as f64) as f32
     }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_, Buffer>],
+        texture_binds: &[crate::SparseTextureBind<'_, Texture>],
+    ) -> Result<(), crate::DeviceError> {
+        unsafe { self.bind_sparse(buffer_binds, texture_binds) }
+    }
 }
 #[derive(Debug)]
 pub struct DxilPassthroughShader {
@@ -0,0 +1,346 @@
+//! Sparse buffers and textures.
+//!
+//! Sparse resources are reserved resources without memory. Tiles of memory are
+//! mapped to them on the queue with `UpdateTileMappings`, from heaps each
+//! resource creates for itself, so that the heap flags always suit the
+//! resource. Unmapped tiles return to the resource's free list, and heaps are
+//! released along with the resource.
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use parking_lot::Mutex;
+use windows::Win32::Graphics::Direct3D12;
+
+use super::{conv, suballocation};
+use crate::auxil::dxgi::{name::ObjectExt, result::HResult as _};
+use naga::FastHashMap;
+
+/// The number of tiles in each heap.
+const TILES_PER_HEAP: u32 = 64;
+
+/// Tiles of memory mapped to a sparse resource, keyed by `K`.
+#[derive(Debug)]
+pub(super) struct SparseMemory<K> {
+    device: Direct3D12::ID3D12Device,
+    heap_flags: Direct3D12::D3D12_HEAP_FLAGS,
+    heaps: Vec<Direct3D12::ID3D12Heap>,
+    /// Free tiles, as the index of their heap and their offset in it.
+    free_tiles: Vec<(usize, u32)>,
+    bound_tiles: FastHashMap<K, (usize, u32)>,
+}
+
+impl<K: Eq + Hash> SparseMemory<K> {
+    fn new(device: &Direct3D12::ID3D12Device, heap_flags: Direct3D12::D3D12_HEAP_FLAGS) -> Self {
+        Self {
+            device: device.clone(),
+            heap_flags,
+            heaps: Vec::new(),
+            free_tiles: Vec::new(),
+            bound_tiles: FastHashMap::default(),
+        }
+    }
+
+    /// Returns the heap and offset of a tile to map at `key`, or `None` if a
+    /// tile is already mapped there.
+    fn bind(
+        &mut self,
+        key: K,
+    ) -> Result<Option<(&Direct3D12::ID3D12Heap, u32)>, crate::DeviceError> {
+        if self.bound_tiles.contains_key(&key) {
+            return Ok(None);
+        }
+        if self.free_tiles.is_empty() {
+            let desc = Direct3D12::D3D12_HEAP_DESC {
+                SizeInBytes: u64::from(TILES_PER_HEAP) * wgt::SPARSE_PAGE_SIZE,
+                Properties: Direct3D12::D3D12_HEAP_PROPERTIES {
+                    Type: Direct3D12::D3D12_HEAP_TYPE_DEFAULT,
+                    CPUPageProperty: Direct3D12::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+                    MemoryPoolPreference: Direct3D12::D3D12_MEMORY_POOL_UNKNOWN,
+                    CreationNodeMask: 0,
+                    VisibleNodeMask: 0,
+                },
+                Alignment: wgt::SPARSE_PAGE_SIZE,
+                Flags: self.heap_flags,
+            };
+            let mut heap = None::<Direct3D12::ID3D12Heap>;
+            unsafe { self.device.CreateHeap(&desc, &mut heap) }
+                .into_device_result("Sparse heap creation")?;
+            let heap = heap.ok_or(crate::DeviceError::Unexpected)?;
+            let index = self.heaps.len();
+            self.heaps.push(heap);
+            self.free_tiles
+                .extend((0..TILES_PER_HEAP).rev().map(|tile| (index, tile)));
+        }
+        let tile = self.free_tiles.pop().unwrap();
+        self.bound_tiles.insert(key, tile);
+        Ok(Some((&self.heaps[tile.0], tile.1)))
+    }
+
+    /// Returns whether a tile was mapped at `key`, making it free for later binds.
+    fn unbind(&mut self, key: &K) -> bool {
+        match self.bound_tiles.remove(key) {
+            Some(tile) => {
+                self.free_tiles.push(tile);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The tiles of a sparse buffer, keyed by their index.
+pub(super) type SparseBuffer = SparseMemory<u64>;
+
+/// The tiles of a sparse texture.
+#[derive(Debug)]
+pub(super) struct SparseTexture {
+    /// Tiles keyed by mip level, array layer, and their column and row.
+    ///
+    /// The tiles of the packed mips of a layer are keyed by the first packed
+    /// mip level, and their index as the column.
+    memory: SparseMemory<[u32; 4]>,
+    page_extent: (u32, u32),
+    packed_mips: Direct3D12::D3D12_PACKED_MIP_INFO,
+}
+
+impl super::Device {
+    /// Creates a reserved buffer, whose tiles are mapped with
+    /// [`crate::Queue::bind_sparse_memory`].
+    pub(super) unsafe fn create_sparse_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let raw_desc = conv::map_buffer_descriptor(desc);
+        let mut resource = None::<Direct3D12::ID3D12Resource>;
+        unsafe {
+            self.raw.CreateReservedResource(
+                &raw_desc,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                None,
+                &mut resource,
+            )
+        }
+        .into_device_result("Reserved buffer creation")?;
+        let resource = resource.ok_or(crate::DeviceError::Unexpected)?;
+
+        if let Some(label) = desc.label {
+            resource.set_name(label)?;
+        }
+
+        self.counters.buffers.add(1);
+
+        Ok(super::Buffer {
+            resource,
+            size: desc.size,
+            allocation: suballocation::Allocation::none(suballocation::AllocationType::Buffer, 0),
+            sparse: Some(Mutex::new(SparseMemory::new(
+                &self.raw,
+                Direct3D12::D3D12_HEAP_FLAG_ALLOW_ONLY_BUFFERS,
+            ))),
+        })
+    }
+
+    /// Creates a reserved texture, whose tiles are mapped with
+    /// [`crate::Queue::bind_sparse_memory`].
+    pub(super) unsafe fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        raw_desc: Direct3D12::D3D12_RESOURCE_DESC,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let raw_desc = Direct3D12::D3D12_RESOURCE_DESC {
+            Layout: Direct3D12::D3D12_TEXTURE_LAYOUT_64KB_UNDEFINED_SWIZZLE,
+            ..raw_desc
+        };
+        let mut resource = None::<Direct3D12::ID3D12Resource>;
+        unsafe {
+            self.raw.CreateReservedResource(
+                &raw_desc,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                None,
+                &mut resource,
+            )
+        }
+        .into_device_result("Reserved texture creation")?;
+        let resource = resource.ok_or(crate::DeviceError::Unexpected)?;
+
+        let mut packed_mips = Direct3D12::D3D12_PACKED_MIP_INFO::default();
+        let mut tile_shape = Direct3D12::D3D12_TILE_SHAPE::default();
+        let mut subresource_count = 0;
+        unsafe {
+            self.raw.GetResourceTiling(
+                &resource,
+                None,
+                Some(&mut packed_mips),
+                Some(&mut tile_shape),
+                Some(&mut subresource_count),
+                0,
+                &mut Direct3D12::D3D12_SUBRESOURCE_TILING::default(),
+            )
+        };
+
+        // Tiles and packed mips must be where `wgt` says they are.
+        let page_extent = desc.format.sparse_page_dimensions();
+        if page_extent != Some((tile_shape.WidthInTexels, tile_shape.HeightInTexels))
+            || u32::from(packed_mips.NumStandardMips) != desc.sparse_mip_tail_start()
+        {
+            log::error!(
+                "Reserved texture tiling {tile_shape:?}, {packed_mips:?} doesn't fit pages"
+            );
+            return Err(crate::DeviceError::Unexpected);
+        }
+
+        if let Some(label) = desc.label {
+            resource.set_name(label)?;
+        }
+
+        let heap_flags = if raw_desc
+            .Flags
+            .contains(Direct3D12::D3D12_RESOURCE_FLAG_ALLOW_RENDER_TARGET)
+        {
+            Direct3D12::D3D12_HEAP_FLAG_ALLOW_ONLY_RT_DS_TEXTURES
+        } else {
+            Direct3D12::D3D12_HEAP_FLAG_ALLOW_ONLY_NON_RT_DS_TEXTURES
+        };
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: suballocation::Allocation::none(suballocation::AllocationType::Texture, 0),
+            sparse: Some(Mutex::new(SparseTexture {
+                memory: SparseMemory::new(&self.raw, heap_flags),
+                page_extent: page_extent.unwrap(),
+                packed_mips,
+            })),
+        })
+    }
+}
+
+impl super::Queue {
+    pub(super) unsafe fn bind_sparse(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_, super::Buffer>],
+        texture_binds: &[crate::SparseTextureBind<'_, super::Texture>],
+    ) -> Result<(), crate::DeviceError> {
+        for bind in buffer_binds {
+            let mut sparse = bind.buffer.sparse.as_ref().unwrap().lock();
+            for offset in bind.range.clone().step_by(wgt::SPARSE_PAGE_SIZE as usize) {
+                let key = offset / wgt::SPARSE_PAGE_SIZE;
+                let coordinate = Direct3D12::D3D12_TILED_RESOURCE_COORDINATE {
+                    X: key as u32,
+                    ..Default::default()
+                };
+                if bind.resident {
+                    if let Some(tile) = sparse.bind(key)? {
+                        unsafe { self.map_tile(&bind.buffer.resource, coordinate, Some(tile)) };
+                    }
+                } else if sparse.unbind(&key) {
+                    unsafe { self.map_tile(&bind.buffer.resource, coordinate, None) };
+                }
+            }
+        }
+
+        for bind in texture_binds {
+            let texture = bind.texture;
+            let mut sparse = texture.sparse.as_ref().unwrap().lock();
+            let sparse = &mut *sparse;
+            let layers = bind.origin.z..bind.origin.z + bind.size.depth_or_array_layers;
+            let standard_mips = u32::from(sparse.packed_mips.NumStandardMips);
+
+            let mut tiles = Vec::new();
+            if bind.mip_level >= standard_mips {
+                for layer in layers {
+                    let subresource = texture.calc_subresource(standard_mips, layer, 0);
+                    for index in 0..sparse.packed_mips.NumTilesForPackedMips {
+                        tiles.push((
+                            [standard_mips, layer, index, 0],
+                            Direct3D12::D3D12_TILED_RESOURCE_COORDINATE {
+                                X: index,
+                                Y: 0,
+                                Z: 0,
+                                Subresource: subresource,
+                            },
+                        ));
+                    }
+                }
+            } else {
+                let (page_width, page_height) = sparse.page_extent;
+                for layer in layers {
+                    let subresource = texture.calc_subresource(bind.mip_level, layer, 0);
+                    for y in (bind.origin.y..bind.origin.y + bind.size.height)
+                        .step_by(page_height as usize)
+                    {
+                        for x in (bind.origin.x..bind.origin.x + bind.size.width)
+                            .step_by(page_width as usize)
+                        {
+                            let (column, row) = (x / page_width, y / page_height);
+                            tiles.push((
+                                [bind.mip_level, layer, column, row],
+                                Direct3D12::D3D12_TILED_RESOURCE_COORDINATE {
+                                    X: column,
+                                    Y: row,
+                                    Z: 0,
+                                    Subresource: subresource,
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+
+            for (key, coordinate) in tiles {
+                if bind.resident {
+                    if let Some(tile) = sparse.memory.bind(key)? {
+                        unsafe { self.map_tile(&texture.resource, coordinate, Some(tile)) };
+                    }
+                } else if sparse.memory.unbind(&key) {
+                    unsafe { self.map_tile(&texture.resource, coordinate, None) };
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Maps the tile of `resource` at `coordinate` to the tile of a heap at an
+    /// offset, or to no memory.
+    unsafe fn map_tile(
+        &self,
+        resource: &Direct3D12::ID3D12Resource,
+        coordinate: Direct3D12::D3D12_TILED_RESOURCE_COORDINATE,
+        tile: Option<(&Direct3D12::ID3D12Heap, u32)>,
+    ) {
+        let region = Direct3D12::D3D12_TILE_REGION_SIZE {
+            NumTiles: 1,
+            UseBox: false.into(),
+            Width: 1,
+            Height: 1,
+            Depth: 1,
+        };
+        let (heap, flags, offset) = match tile {
+            Some((heap, offset)) => (Some(heap), Direct3D12::D3D12_TILE_RANGE_FLAG_NONE, offset),
+            None => (None, Direct3D12::D3D12_TILE_RANGE_FLAG_NULL, 0),
+        };
+        profiling::scope!("ID3D12CommandQueue::UpdateTileMappings");
+        unsafe {
+            self.raw.UpdateTileMappings(
+                resource,
+                1,
+                Some(&coordinate),
+                Some(&region),
+                heap,
+                1,
+                Some(&flags),
+                Some(&offset),
+                Some(&1),
+                Direct3D12::D3D12_TILE_MAPPING_FLAG_NONE,
+            )
+        };
+    }
+}
@@ -26,6 +26,19 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
     unsafe fn transition_buffers(&mut self, barriers: &[BufferBarrier<'_, dyn DynBuffer>]);
     unsafe fn transition_textures(&mut self, barriers: &[TextureBarrier<'_, dyn DynTexture>]);
 
+    unsafe fn release_resources(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: &[BufferBarrier<'_, dyn DynBuffer>],
+        textures: &[TextureBarrier<'_, dyn DynTexture>],
+    );
+    unsafe fn acquire_resources(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: &[BufferBarrier<'_, dyn DynBuffer>],
+        textures: &[TextureBarrier<'_, dyn DynTexture>],
+    );
+
     unsafe fn clear_buffer(&mut self, buffer: &dyn DynBuffer, range: MemoryRange);
 
     unsafe fn copy_buffer_to_buffer(
@@ -178,6 +191,15 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     );
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &dyn DynPipelineLayout,
+        buffer: &dyn DynBuffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&dyn DynBuffer, wgt::BufferAddress)>,
+    );
 
     unsafe fn begin_compute_pass(&mut self, desc: &ComputePassDescriptor<dyn DynQuerySet>);
     unsafe fn end_compute_pass(&mut self);
@@ -252,6 +274,42 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         unsafe { self.transition_textures(barriers) };
     }
 
+    unsafe fn release_resources(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: &[BufferBarrier<'_, dyn DynBuffer>],
+        textures: &[TextureBarrier<'_, dyn DynTexture>],
+    ) {
+        let buffers = buffers.iter().map(|barrier| BufferBarrier {
+            buffer: barrier.buffer.expect_downcast_ref(),
+            usage: barrier.usage.clone(),
+        });
+        let textures = textures.iter().map(|barrier| TextureBarrier {
+            texture: barrier.texture.expect_downcast_ref(),
+            usage: barrier.usage.clone(),
+            range: barrier.range,
+        });
+        unsafe { C::release_resources(self, queue_type, buffers, textures) };
+    }
+
+    unsafe fn acquire_resources(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: &[BufferBarrier<'_, dyn DynBuffer>],
+        textures: &[TextureBarrier<'_, dyn DynTexture>],
+    ) {
+        let buffers = buffers.iter().map(|barrier| BufferBarrier {
+            buffer: barrier.buffer.expect_downcast_ref(),
+            usage: barrier.usage.clone(),
+        });
+        let textures = textures.iter().map(|barrier| TextureBarrier {
+            texture: barrier.texture.expect_downcast_ref(),
+            usage: barrier.usage.clone(),
+            range: barrier.range,
+        });
+        unsafe { C::acquire_resources(self, queue_type, buffers, textures) };
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &dyn DynBuffer, range: MemoryRange) {
         let buffer = buffer.expect_downcast_ref();
         unsafe { C::clear_buffer(self, buffer, range) };
@@ -591,6 +649,32 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         };
     }
 
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &dyn DynPipelineLayout,
+        buffer: &dyn DynBuffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&dyn DynBuffer, wgt::BufferAddress)>,
+    ) {
+        let pipeline_layout = pipeline_layout.expect_downcast_ref();
+        let buffer = buffer.expect_downcast_ref();
+        let count_buffer = count_buffer
+            .map(|(count_buffer, count_offset)| (count_buffer.expect_downcast_ref(), count_offset));
+        unsafe {
+            C::execute_indirect(
+                self,
+                layout,
+                pipeline_layout,
+                buffer,
+                offset,
+                max_count,
+                count_buffer,
+            )
+        };
+    }
+
     unsafe fn begin_compute_pass(&mut self, desc: &ComputePassDescriptor<dyn DynQuerySet>) {
         let desc = ComputePassDescriptor {
             label: desc.label,
@@ -7,14 +7,14 @@ use crate::{
     GetAccelerationStructureBuildSizesDescriptor, Label, MemoryRange, PipelineCacheDescriptor,
     PipelineCacheError, PipelineError, PipelineLayoutDescriptor, RenderPipelineDescriptor,
     SamplerDescriptor, ShaderError, ShaderInput, ShaderModuleDescriptor, TextureDescriptor,
-    TextureViewDescriptor, TlasInstance,
+    TextureViewDescriptor, TlasInstance, VideoDecoderDescriptor,
 };
 
 use super::{
     DynAccelerationStructure, DynBindGroup, DynBindGroupLayout, DynBuffer, DynCommandEncoder,
     DynComputePipeline, DynFence, DynPipelineCache, DynPipelineLayout, DynQuerySet, DynQueue,
     DynRenderPipeline, DynResource, DynResourceExt as _, DynSampler, DynShaderModule, DynTexture,
-    DynTextureView,
+    DynTextureView, DynVideoDecoder,
 };
 
 pub trait DynDevice: DynResource {
@@ -25,6 +25,10 @@ pub trait DynDevice: DynResource {
 
     unsafe fn destroy_buffer(&self, buffer: Box<dyn DynBuffer>);
     unsafe fn add_raw_buffer(&self, buffer: &dyn DynBuffer);
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &dyn DynBuffer,
+    ) -> Option<wgt::BufferAddress>;
 
     unsafe fn map_buffer(
         &self,
@@ -161,8 +165,31 @@ pub trait DynDevice: DynResource {
     );
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8>;
 
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities>;
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &VideoDecoderDescriptor,
+    ) -> Result<Box<dyn DynVideoDecoder>, DeviceError>;
+    unsafe fn destroy_video_decoder(&self, decoder: Box<dyn DynVideoDecoder>);
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &dyn DynVideoDecoder,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError>;
+
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        index: u32,
+    ) -> Result<Box<dyn DynQueue>, DeviceError>;
+
     fn get_internal_counters(&self) -> wgt::HalCounters;
     fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport>;
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport>;
 
     fn check_if_oom(&self) -> Result<(), DeviceError>;
 }
@@ -182,6 +209,13 @@ impl<D: Device + DynResource> DynDevice for D {
         let buffer = buffer.expect_downcast_ref();
         unsafe { D::add_raw_buffer(self, buffer) };
     }
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &dyn DynBuffer,
+    ) -> Option<wgt::BufferAddress> {
+        let buffer = buffer.expect_downcast_ref();
+        unsafe { D::get_buffer_device_address(self, buffer) }
+    }
 
     unsafe fn map_buffer(
         &self,
@@ -266,6 +300,7 @@ impl<D: Device + DynResource> DynDevice for D {
         let desc = CommandEncoderDescriptor {
             label: desc.label,
             queue: desc.queue.expect_downcast_ref(),
+            reusable: desc.reusable,
         };
         unsafe { D::create_command_encoder(self, &desc) }
             .map(|b| -> Box<dyn DynCommandEncoder> { Box::new(b) })
@@ -544,6 +579,48 @@ impl<D: Device + DynResource> DynDevice for D {
         D::tlas_instance_to_bytes(self, instance)
     }
 
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        D::video_decode_capabilities(self, codec, format)
+    }
+
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &VideoDecoderDescriptor,
+    ) -> Result<Box<dyn DynVideoDecoder>, DeviceError> {
+        unsafe { D::create_video_decoder(self, desc) }
+            .map(|b| -> Box<dyn DynVideoDecoder> { Box::new(b) })
+    }
+
+    unsafe fn destroy_video_decoder(&self, decoder: Box<dyn DynVideoDecoder>) {
+        unsafe { D::destroy_video_decoder(self, decoder.unbox()) };
+    }
+
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &dyn DynVideoDecoder,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError> {
+        let decoder = decoder.expect_downcast_ref();
+        unsafe { D::create_video_picture(self, decoder, desc) }.map(|b| {
+            let boxed_texture: Box<<D::A as Api>::Texture> = Box::new(b);
+            let boxed_texture: Box<dyn DynTexture> = boxed_texture;
+            boxed_texture
+        })
+    }
+
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        index: u32,
+    ) -> Result<Box<dyn DynQueue>, DeviceError> {
+        unsafe { D::create_queue(self, queue_type, index) }
+            .map(|queue| -> Box<dyn DynQueue> { Box::new(queue) })
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters {
         D::get_internal_counters(self)
     }
@@ -552,6 +629,10 @@ impl<D: Device + DynResource> DynDevice for D {
         D::generate_allocator_report(self)
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        D::get_memory_budget(self)
+    }
+
     fn check_if_oom(&self) -> Result<(), DeviceError> {
         D::check_if_oom(self)
     }
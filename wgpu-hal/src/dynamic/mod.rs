@@ -24,7 +24,7 @@ use crate::{
     AccelerationStructureAABBs, AccelerationStructureEntries, AccelerationStructureInstances,
     AccelerationStructureTriangleIndices, AccelerationStructureTriangleTransform,
     AccelerationStructureTriangles, BufferBinding, ExternalTextureBinding, ProgrammableStage,
-    TextureBinding,
+    TextureBinding, VideoPicture,
 };
 
 /// Base trait for all resources, allows downcasting via [`Any`].
@@ -124,6 +124,7 @@ pub trait DynSurfaceTexture:
 }
 pub trait DynTexture: DynResource + fmt::Debug {}
 pub trait DynTextureView: DynResource + fmt::Debug {}
+pub trait DynVideoDecoder: DynResource + fmt::Debug {}
 
 impl<'a> BufferBinding<'a, dyn DynBuffer> {
     pub fn expect_downcast<B: DynBuffer>(self) -> BufferBinding<'a, B> {
@@ -154,6 +155,16 @@ impl<'a> ExternalTextureBinding<'a, dyn DynBuffer, dyn DynTextureView> {
     }
 }
 
+impl<'a> VideoPicture<'a, dyn DynTexture> {
+    fn expect_downcast<T: DynTexture>(&self) -> VideoPicture<'a, T> {
+        VideoPicture {
+            texture: self.texture.expect_downcast_ref(),
+            slot: self.slot,
+            usage: self.usage.clone(),
+        }
+    }
+}
+
 impl<'a> ProgrammableStage<'a, dyn DynShaderModule> {
     fn expect_downcast<T: DynShaderModule>(self) -> ProgrammableStage<'a, T> {
         ProgrammableStage {
@@ -1,8 +1,9 @@
 use alloc::{boxed::Box, vec::Vec};
 
 use crate::{
-    DeviceError, DynCommandBuffer, DynFence, DynResource, DynSurface, DynSurfaceTexture,
-    FenceValue, Queue, SurfaceError,
+    DeviceError, DynBuffer, DynCommandBuffer, DynFence, DynResource, DynSurface, DynSurfaceTexture,
+    DynTexture, DynVideoDecoder, FenceValue, Queue, SparseBufferBind, SparseTextureBind,
+    SurfaceError, VideoDecodeDescriptor, VideoReference,
 };
 
 use super::DynResourceExt as _;
@@ -20,6 +21,22 @@ pub trait DynQueue: DynResource {
         texture: Box<dyn DynSurfaceTexture>,
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
+    unsafe fn decode_video(
+        &self,
+        decoder: &dyn DynVideoDecoder,
+        desc: &VideoDecodeDescriptor<dyn DynTexture>,
+        wait_fence: (&dyn DynFence, FenceValue),
+    ) -> Result<(), DeviceError>;
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[SparseBufferBind<'_, dyn DynBuffer>],
+        texture_binds: &[SparseTextureBind<'_, dyn DynTexture>],
+    ) -> Result<(), DeviceError>;
 }
 
 impl<Q: Queue + DynResource> DynQueue for Q {
@@ -53,4 +70,65 @@ impl<Q: Queue + DynResource> DynQueue for Q {
     unsafe fn get_timestamp_period(&self) -> f32 {
         unsafe { Q::get_timestamp_period(self) }
     }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError> {
+        let fence = fence.expect_downcast_ref();
+        unsafe { Q::wait_for_fence(self, fence, value) }
+    }
+
+    unsafe fn decode_video(
+        &self,
+        decoder: &dyn DynVideoDecoder,
+        desc: &VideoDecodeDescriptor<dyn DynTexture>,
+        wait_fence: (&dyn DynFence, FenceValue),
+    ) -> Result<(), DeviceError> {
+        let decoder = decoder.expect_downcast_ref();
+        let references = desc
+            .references
+            .iter()
+            .map(|reference| VideoReference {
+                picture: reference.picture.expect_downcast(),
+                info: reference.info,
+            })
+            .collect::<Vec<_>>();
+        let desc = VideoDecodeDescriptor {
+            bitstream: desc.bitstream,
+            target: desc.target.expect_downcast(),
+            target_reference: desc.target_reference,
+            references: &references,
+            picture: desc.picture,
+        };
+        let wait_fence = (wait_fence.0.expect_downcast_ref(), wait_fence.1);
+        unsafe { Q::decode_video(self, decoder, &desc, wait_fence) }
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[SparseBufferBind<'_, dyn DynBuffer>],
+        texture_binds: &[SparseTextureBind<'_, dyn DynTexture>],
+    ) -> Result<(), DeviceError> {
+        let buffer_binds = buffer_binds
+            .iter()
+            .map(|bind| SparseBufferBind {
+                buffer: bind.buffer.expect_downcast_ref(),
+                range: bind.range.clone(),
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        let texture_binds = texture_binds
+            .iter()
+            .map(|bind| SparseTextureBind {
+                texture: bind.texture.expect_downcast_ref(),
+                mip_level: bind.mip_level,
+                origin: bind.origin,
+                size: bind.size,
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        unsafe { Q::bind_sparse_memory(self, &buffer_binds, &texture_binds) }
+    }
 }
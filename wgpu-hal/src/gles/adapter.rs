@@ -431,6 +431,12 @@ impl super::Adapter {
             wgt::DownlevelFlags::MULTISAMPLED_SHADING,
             supported((3, 2), (4, 0)) || extensions.contains("OES_sample_variables"),
         );
+        // Copies GL can't do itself are emulated with internal shaders, see `copy.rs`.
+        let shader_copies = supported((3, 1), (4, 3)) && !cfg!(any(webgl, Emscripten));
+        downlevel_flags.set(
+            wgt::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES,
+            shader_copies,
+        );
         let query_buffers = extensions.contains("GL_ARB_query_buffer_object")
             || extensions.contains("GL_AMD_query_buffer_object");
         if query_buffers {
@@ -441,7 +447,9 @@ impl super::Adapter {
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::CLEAR_TEXTURE
             | wgt::Features::PUSH_CONSTANTS
-            | wgt::Features::DEPTH32FLOAT_STENCIL8;
+            | wgt::Features::DEPTH32FLOAT_STENCIL8
+            | wgt::Features::REUSABLE_COMMAND_BUFFERS
+            | wgt::Features::RENDER_BUNDLE_INHERITANCE;
         features.set(
             wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO,
             extensions.contains("GL_EXT_texture_border_clamp")
@@ -465,6 +473,16 @@ impl super::Adapter {
             extensions.contains("GL_EXT_blend_func_extended")
                 || extensions.contains("GL_ARB_blend_func_extended"),
         );
+        features.set(
+            wgt::Features::FRAMEBUFFER_FETCH,
+            extensions.contains("GL_EXT_shader_framebuffer_fetch"),
+        );
+        // The extension only exists on GLES.
+        features.set(
+            wgt::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+            es_ver.is_some()
+                && extensions.contains("GL_ARM_shader_framebuffer_fetch_depth_stencil"),
+        );
         features.set(
             wgt::Features::CLIP_DISTANCES,
             full_ver.is_some() || extensions.contains("GL_EXT_clip_cull_distance"),
@@ -634,6 +652,7 @@ impl super::Adapter {
             super::PrivateCapabilities::INVALIDATE_FRAMEBUFFER,
             supported((3, 0), (4, 3)),
         );
+        private_caps.set(super::PrivateCapabilities::SHADER_COPIES, shader_copies);
         if let Some(full_ver) = full_ver {
             let supported =
                 full_ver >= (4, 2) && extensions.contains("GL_ARB_shader_draw_parameters");
@@ -811,6 +830,9 @@ impl super::Adapter {
             max_blas_geometry_count: 0,
             max_tlas_instance_count: 0,
             max_acceleration_structures_per_shader_stage: 0,
+
+            max_compute_queues: 0,
+            max_transfer_queues: 0,
         };
 
         let mut workarounds = super::Workarounds::empty();
@@ -1026,6 +1048,7 @@ impl crate::Adapter for super::Adapter {
                     .map_err(|_| crate::DeviceError::OutOfMemory)?,
                 shader_clear_program,
                 zero_buffer,
+                copy_programs: Default::default(),
                 temp_query_results: Mutex::new(Vec::new()),
                 draw_buffer_count: AtomicU8::new(1),
                 current_index_buffer: Mutex::new(None),
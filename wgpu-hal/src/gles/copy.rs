@@ -0,0 +1,674 @@
+//! Copies that GL can't do with its own functions, emulated with internal shaders.
+//!
+//! See the "Copies" section of the [module documentation](super) for when they are used.
+
+use alloc::{format, string::String, vec::Vec};
+
+use glow::HasContext;
+use naga::FastHashMap;
+
+use super::{conv::is_layered_target, lock, queue::get_2d_target, BindTarget};
+
+/// Kind of values a texture is sampled as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum SamplerKind {
+    Float,
+    Uint,
+    Sint,
+}
+
+/// How `copy_texture_to_buffer.comp` packs the texels of a format into bytes.
+#[derive(Clone, Copy, Debug)]
+struct TexelPacking {
+    kind: SamplerKind,
+    /// Number of bits of each component, 0 for the ones the format doesn't have.
+    component_bits: [u32; 4],
+    /// Scale converting float values to unsigned normalized integers, or 0 to copy their bits.
+    unorm_scale: f32,
+}
+
+impl TexelPacking {
+    /// Returns how to pack the texels of `aspect` of `format`, if `glReadPixels` can't read them.
+    ///
+    /// That is the case for depth and stencil aspects, which GLES can't read at all, and for
+    /// integer formats, for which GLES only guarantees reading four component formats.
+    fn for_copy(format: wgt::TextureFormat, aspect: crate::FormatAspects) -> Option<Self> {
+        use wgt::TextureFormat as Tf;
+
+        let packing = |kind, component_bits, unorm_scale| {
+            Some(Self {
+                kind,
+                component_bits,
+                unorm_scale,
+            })
+        };
+        if aspect == crate::FormatAspects::STENCIL {
+            return packing(SamplerKind::Uint, [8, 0, 0, 0], 0.0);
+        }
+        if aspect == crate::FormatAspects::DEPTH {
+            return match format {
+                Tf::Depth16Unorm => packing(SamplerKind::Float, [16, 0, 0, 0], 65535.0),
+                Tf::Depth32Float | Tf::Depth32FloatStencil8 => {
+                    packing(SamplerKind::Float, [32, 0, 0, 0], 0.0)
+                }
+                _ => None,
+            };
+        }
+
+        let kind = match format.sample_type(None, None)? {
+            wgt::TextureSampleType::Uint => SamplerKind::Uint,
+            wgt::TextureSampleType::Sint => SamplerKind::Sint,
+            _ => return None,
+        };
+        let component_bits = match format {
+            Tf::Rgb10a2Uint => [10, 10, 10, 2],
+            // Stored as `RG32UI`.
+            Tf::R64Uint => [32, 32, 0, 0],
+            _ => {
+                let components = u32::from(format.components());
+                let bits = format.block_copy_size(None)? * 8 / components;
+                let mut component_bits = [0; 4];
+                component_bits[..components as usize].fill(bits);
+                component_bits
+            }
+        };
+        packing(kind, component_bits, 0.0)
+    }
+
+    fn texel_size(&self) -> u32 {
+        self.component_bits.iter().sum::<u32>() / 8
+    }
+}
+
+struct TextureToBufferProgram {
+    program: glow::Program,
+    buffer_layout: Option<glow::UniformLocation>,
+    origin: Option<glow::UniformLocation>,
+    size: Option<glow::UniformLocation>,
+    component_bits: Option<glow::UniformLocation>,
+    unorm_scale: Option<glow::UniformLocation>,
+}
+
+struct StencilUploadProgram {
+    program: glow::Program,
+    bit: Option<glow::UniformLocation>,
+    origin: Option<glow::UniformLocation>,
+}
+
+/// Internal programs used to emulate copies, which are compiled the first time they are needed.
+///
+/// A program that failed to compile is kept as `None`, so that it isn't compiled again.
+#[derive(Default)]
+pub(super) struct CopyPrograms {
+    texture_to_buffer: FastHashMap<(SamplerKind, BindTarget), Option<TextureToBufferProgram>>,
+    stencil_upload: Option<Option<StencilUploadProgram>>,
+    /// Sampler fetching single texels without comparison, used for the source textures.
+    sampler: Option<glow::Sampler>,
+}
+
+impl CopyPrograms {
+    pub(super) unsafe fn destroy(&mut self, gl: &glow::Context) {
+        for program in self.texture_to_buffer.drain().filter_map(|(_, p)| p) {
+            unsafe { gl.delete_program(program.program) };
+        }
+        if let Some(Some(program)) = self.stencil_upload.take() {
+            unsafe { gl.delete_program(program.program) };
+        }
+        if let Some(sampler) = self.sampler.take() {
+            unsafe { gl.delete_sampler(sampler) };
+        }
+    }
+
+    unsafe fn texture_to_buffer(
+        &mut self,
+        gl: &glow::Context,
+        kind: SamplerKind,
+        target: BindTarget,
+    ) -> Option<&TextureToBufferProgram> {
+        self.texture_to_buffer
+            .entry((kind, target))
+            .or_insert_with(|| unsafe { create_texture_to_buffer_program(gl, kind, target) })
+            .as_ref()
+    }
+
+    unsafe fn stencil_upload(&mut self, gl: &glow::Context) -> Option<&StencilUploadProgram> {
+        self.stencil_upload
+            .get_or_insert_with(|| unsafe { create_stencil_upload_program(gl) })
+            .as_ref()
+    }
+
+    unsafe fn sampler(&mut self, gl: &glow::Context) -> Option<glow::Sampler> {
+        if self.sampler.is_none() {
+            let sampler = unsafe { gl.create_sampler() }.ok()?;
+            for (parameter, value) in [
+                (glow::TEXTURE_MIN_FILTER, glow::NEAREST_MIPMAP_NEAREST),
+                (glow::TEXTURE_MAG_FILTER, glow::NEAREST),
+                (glow::TEXTURE_COMPARE_MODE, glow::NONE),
+            ] {
+                unsafe { gl.sampler_parameter_i32(sampler, parameter, value as i32) };
+            }
+            self.sampler = Some(sampler);
+        }
+        self.sampler
+    }
+}
+
+unsafe fn link_program(gl: &glow::Context, shaders: &[(u32, &str)]) -> Option<glow::Program> {
+    let program = unsafe { gl.create_program() }.ok()?;
+    let mut compiled = Vec::new();
+    for &(shader_type, source) in shaders {
+        let shader = unsafe { gl.create_shader(shader_type) }.ok()?;
+        unsafe { gl.shader_source(shader, source) };
+        unsafe { gl.compile_shader(shader) };
+        if !unsafe { gl.get_shader_compile_status(shader) } {
+            let msg = unsafe { gl.get_shader_info_log(shader) };
+            log::error!("Copy shader compile error: {msg}");
+            unsafe { gl.delete_shader(shader) };
+            break;
+        }
+        unsafe { gl.attach_shader(program, shader) };
+        compiled.push(shader);
+    }
+
+    let linked = compiled.len() == shaders.len() && {
+        unsafe { gl.link_program(program) };
+        unsafe { gl.get_program_link_status(program) }
+    };
+    if compiled.len() == shaders.len() && !linked {
+        let msg = unsafe { gl.get_program_info_log(program) };
+        log::error!("Copy shader link error: {msg}");
+    }
+    for shader in compiled {
+        unsafe { gl.delete_shader(shader) };
+    }
+    if linked {
+        Some(program)
+    } else {
+        unsafe { gl.delete_program(program) };
+        None
+    }
+}
+
+unsafe fn create_texture_to_buffer_program(
+    gl: &glow::Context,
+    kind: SamplerKind,
+    target: BindTarget,
+) -> Option<TextureToBufferProgram> {
+    let version = gl.version();
+    let mut source = if !version.is_embedded {
+        String::from("#version 430 core\n")
+    } else if (version.major, version.minor) >= (3, 2) {
+        String::from("#version 320 es\n")
+    } else if target == glow::TEXTURE_CUBE_MAP_ARRAY {
+        String::from("#version 310 es\n#extension GL_EXT_texture_cube_map_array : require\n")
+    } else {
+        String::from("#version 310 es\n")
+    };
+    source.push_str("precision highp float;\nprecision highp int;\n");
+
+    let (prefix, texel) = match kind {
+        SamplerKind::Float => {
+            source.push_str("#define FLOAT\n");
+            ("", "vec4")
+        }
+        SamplerKind::Uint => ("u", "uvec4"),
+        SamplerKind::Sint => ("i", "ivec4"),
+    };
+    let (sampler, defines) = match target {
+        glow::TEXTURE_2D => ("sampler2D", ""),
+        glow::TEXTURE_2D_ARRAY => ("sampler2DArray", "#define ARRAYED\n"),
+        glow::TEXTURE_3D => ("sampler3D", "#define VOLUME\n"),
+        glow::TEXTURE_CUBE_MAP => ("samplerCube", "#define CUBE\n"),
+        glow::TEXTURE_CUBE_MAP_ARRAY => ("samplerCubeArray", "#define CUBE\n#define ARRAYED\n"),
+        _ => return None,
+    };
+    source.push_str(&format!(
+        "#define SAMPLER {prefix}{sampler}\n#define TEXEL {texel}\n{defines}"
+    ));
+    source.push_str(include_str!("./shaders/copy_texture_to_buffer.comp"));
+
+    let program = unsafe { link_program(gl, &[(glow::COMPUTE_SHADER, &source)]) }?;
+    let location = |name| unsafe { gl.get_uniform_location(program, name) };
+    Some(TextureToBufferProgram {
+        program,
+        buffer_layout: location("buffer_layout"),
+        origin: location("origin"),
+        size: location("size"),
+        component_bits: location("component_bits"),
+        unorm_scale: location("unorm_scale"),
+    })
+}
+
+unsafe fn create_stencil_upload_program(gl: &glow::Context) -> Option<StencilUploadProgram> {
+    let header = if gl.version().is_embedded {
+        "#version 300 es\nprecision highp float;\n"
+    } else {
+        "#version 330 core\n"
+    };
+    let vertex = format!("{header}{}", include_str!("./shaders/clear.vert"));
+    let fragment = format!("{header}{}", include_str!("./shaders/stencil_upload.frag"));
+    let program = unsafe {
+        link_program(
+            gl,
+            &[
+                (glow::VERTEX_SHADER, &vertex),
+                (glow::FRAGMENT_SHADER, &fragment),
+            ],
+        )
+    }?;
+    let location = |name| unsafe { gl.get_uniform_location(program, name) };
+    Some(StencilUploadProgram {
+        program,
+        bit: location("bit"),
+        origin: location("origin"),
+    })
+}
+
+impl super::Queue {
+    /// Copies a texture to a buffer with a compute shader, if it is a copy that `glReadPixels`
+    /// can't do and the device supports it.
+    ///
+    /// Returns `false` if the copy wasn't done.
+    pub(super) unsafe fn copy_texture_to_buffer_with_shader(
+        &self,
+        gl: &glow::Context,
+        src: glow::Texture,
+        src_target: BindTarget,
+        src_format: wgt::TextureFormat,
+        dst: &super::Buffer,
+        copy: &crate::BufferTextureCopy,
+    ) -> bool {
+        if !self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::SHADER_COPIES)
+        {
+            return false;
+        }
+        let Some(buffer) = dst.raw else {
+            return false;
+        };
+        let aspect = copy.texture_base.aspect;
+        let Some(packing) = TexelPacking::for_copy(src_format, aspect) else {
+            return false;
+        };
+        let mut programs = self.copy_programs.lock();
+        let Some(sampler) = (unsafe { programs.sampler(gl) }) else {
+            return false;
+        };
+        let Some(program) = (unsafe { programs.texture_to_buffer(gl, packing.kind, src_target) })
+        else {
+            return false;
+        };
+
+        let texel_size = packing.texel_size();
+        let row_size = copy.size.width * texel_size;
+        let bytes_per_row = copy.buffer_layout.bytes_per_row.unwrap_or(row_size);
+        let rows_per_image = copy
+            .buffer_layout
+            .rows_per_image
+            .unwrap_or(copy.size.height);
+        let bytes_per_image = bytes_per_row * rows_per_image;
+        let start = copy.buffer_layout.offset;
+        let end = start
+            + u64::from(bytes_per_image * (copy.size.depth - 1))
+            + u64::from(bytes_per_row * (copy.size.height - 1))
+            + u64::from(row_size);
+
+        // Only bind the range of the buffer that is copied to, which is more likely to fit in
+        // the maximum size of a storage block than the whole buffer.
+        let alignment = u64::from(self.shared.limits.min_storage_buffer_offset_alignment);
+        let range_start = start - start % alignment;
+        let range_end = end.next_multiple_of(4).min(dst.size);
+        unsafe {
+            gl.bind_buffer_range(
+                glow::SHADER_STORAGE_BUFFER,
+                0,
+                Some(buffer),
+                range_start as i32,
+                (range_end - range_start) as i32,
+            )
+        };
+
+        // Texture parameters are set again whenever the texture is bound for a pass.
+        let mip_level = copy.texture_base.mip_level as i32;
+        unsafe { gl.active_texture(glow::TEXTURE0) };
+        unsafe { gl.bind_texture(src_target, Some(src)) };
+        unsafe { gl.tex_parameter_i32(src_target, glow::TEXTURE_BASE_LEVEL, mip_level) };
+        unsafe { gl.tex_parameter_i32(src_target, glow::TEXTURE_MAX_LEVEL, mip_level) };
+        if src_format.is_combined_depth_stencil_format() {
+            let mode = if aspect == crate::FormatAspects::STENCIL {
+                glow::STENCIL_INDEX
+            } else {
+                glow::DEPTH_COMPONENT
+            };
+            unsafe {
+                gl.tex_parameter_i32(src_target, glow::DEPTH_STENCIL_TEXTURE_MODE, mode as i32)
+            };
+        }
+        unsafe { gl.bind_sampler(0, Some(sampler)) };
+
+        let origin_z = match src_target {
+            glow::TEXTURE_3D => copy.texture_base.origin.z,
+            glow::TEXTURE_2D => 0,
+            _ => copy.texture_base.array_layer,
+        };
+        let [bits_x, bits_y, bits_z, bits_w] = packing.component_bits;
+        unsafe {
+            gl.use_program(Some(program.program));
+            gl.uniform_3_u32(
+                program.buffer_layout.as_ref(),
+                (start - range_start) as u32,
+                bytes_per_row,
+                bytes_per_image,
+            );
+            gl.uniform_3_i32(
+                program.origin.as_ref(),
+                copy.texture_base.origin.x as i32,
+                copy.texture_base.origin.y as i32,
+                origin_z as i32,
+            );
+            gl.uniform_3_u32(
+                program.size.as_ref(),
+                copy.size.width,
+                copy.size.height,
+                copy.size.depth,
+            );
+            gl.uniform_4_u32(
+                program.component_bits.as_ref(),
+                bits_x,
+                bits_y,
+                bits_z,
+                bits_w,
+            );
+            gl.uniform_1_f32(program.unorm_scale.as_ref(), packing.unorm_scale);
+        }
+
+        // A row can start in the middle of a word, so it may touch one word more than it fills.
+        let words_per_row = (row_size + 3).div_ceil(4);
+        unsafe {
+            gl.dispatch_compute(
+                words_per_row.div_ceil(64),
+                copy.size.height,
+                copy.size.depth,
+            )
+        };
+        unsafe { gl.memory_barrier(glow::ALL_BARRIER_BITS) };
+        unsafe { gl.bind_sampler(0, None) };
+        true
+    }
+
+    /// Copies a buffer to the stencil aspect of a combined depth-stencil texture, which can't be
+    /// uploaded to with `glTexSubImage`, by drawing once for each bit of the stencil values.
+    ///
+    /// Returns `false` if the copy wasn't done.
+    pub(super) unsafe fn copy_buffer_to_stencil_with_shader(
+        &self,
+        gl: &glow::Context,
+        src: &super::Buffer,
+        dst: glow::Texture,
+        dst_target: BindTarget,
+        dst_format: wgt::TextureFormat,
+        copy: &crate::BufferTextureCopy,
+    ) -> bool {
+        if !self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::SHADER_COPIES)
+            || copy.texture_base.aspect != crate::FormatAspects::STENCIL
+            || !dst_format.is_combined_depth_stencil_format()
+        {
+            return false;
+        }
+        let mut programs = self.copy_programs.lock();
+        let Some(program) = (unsafe { programs.stencil_upload(gl) }) else {
+            return false;
+        };
+        let Ok(staging) = (unsafe { gl.create_texture() }) else {
+            return false;
+        };
+
+        let (width, height) = (copy.size.width as i32, copy.size.height as i32);
+        let bytes_per_row = copy.buffer_layout.bytes_per_row.unwrap_or(copy.size.width);
+        let rows_per_image = copy
+            .buffer_layout
+            .rows_per_image
+            .unwrap_or(copy.size.height);
+        let bytes_per_image = u64::from(bytes_per_row * rows_per_image);
+
+        unsafe { gl.active_texture(glow::TEXTURE0) };
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, Some(staging)) };
+        unsafe { gl.tex_storage_2d(glow::TEXTURE_2D, 1, glow::R8UI, width, height) };
+        // Integer textures are incomplete with linear filtering.
+        for parameter in [glow::TEXTURE_MIN_FILTER, glow::TEXTURE_MAG_FILTER] {
+            unsafe { gl.tex_parameter_i32(glow::TEXTURE_2D, parameter, glow::NEAREST as i32) };
+        }
+        unsafe { gl.bind_sampler(0, None) };
+        unsafe { gl.pixel_store_i32(glow::UNPACK_ROW_LENGTH, bytes_per_row as i32) };
+        unsafe { gl.pixel_store_i32(glow::UNPACK_IMAGE_HEIGHT, 0) };
+
+        unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.draw_fbo)) };
+        for i in 0..self.shared.limits.max_color_attachments {
+            let attachment = glow::COLOR_ATTACHMENT0 + i;
+            unsafe {
+                gl.framebuffer_texture_2d(
+                    glow::DRAW_FRAMEBUFFER,
+                    attachment,
+                    glow::TEXTURE_2D,
+                    None,
+                    0,
+                )
+            };
+        }
+        unsafe {
+            gl.use_program(Some(program.program));
+            gl.uniform_2_i32(
+                program.origin.as_ref(),
+                copy.texture_base.origin.x as i32,
+                copy.texture_base.origin.y as i32,
+            );
+            gl.viewport(
+                copy.texture_base.origin.x as i32,
+                copy.texture_base.origin.y as i32,
+                width,
+                height,
+            );
+            gl.scissor(
+                copy.texture_base.origin.x as i32,
+                copy.texture_base.origin.y as i32,
+                width,
+                height,
+            );
+            gl.enable(glow::SCISSOR_TEST);
+            gl.enable(glow::STENCIL_TEST);
+            gl.disable(glow::DEPTH_TEST);
+            gl.disable(glow::BLEND);
+            gl.disable(glow::CULL_FACE);
+            gl.disable(glow::RASTERIZER_DISCARD);
+            gl.color_mask(false, false, false, false);
+            gl.stencil_func(glow::ALWAYS, 0xff, 0xff);
+            gl.stencil_op(glow::KEEP, glow::KEEP, glow::REPLACE);
+        }
+
+        for z in 0..copy.size.depth {
+            let offset = copy.buffer_layout.offset + u64::from(z) * bytes_per_image;
+            let buffer_data;
+            let unpack_data = match src.raw {
+                Some(buffer) => {
+                    unsafe { gl.bind_buffer(glow::PIXEL_UNPACK_BUFFER, Some(buffer)) };
+                    glow::PixelUnpackData::BufferOffset(offset as u32)
+                }
+                None => {
+                    buffer_data = lock(src.data.as_ref().unwrap());
+                    glow::PixelUnpackData::Slice(Some(&buffer_data.as_slice()[offset as usize..]))
+                }
+            };
+            unsafe {
+                gl.tex_sub_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    0,
+                    0,
+                    width,
+                    height,
+                    glow::RED_INTEGER,
+                    glow::UNSIGNED_BYTE,
+                    unpack_data,
+                )
+            };
+            unsafe { gl.bind_buffer(glow::PIXEL_UNPACK_BUFFER, None) };
+
+            let layer = copy.texture_base.array_layer + z;
+            if is_layered_target(dst_target) {
+                unsafe {
+                    gl.framebuffer_texture_layer(
+                        glow::DRAW_FRAMEBUFFER,
+                        glow::DEPTH_STENCIL_ATTACHMENT,
+                        Some(dst),
+                        copy.texture_base.mip_level as i32,
+                        layer as i32,
+                    )
+                };
+            } else {
+                unsafe {
+                    gl.framebuffer_texture_2d(
+                        glow::DRAW_FRAMEBUFFER,
+                        glow::DEPTH_STENCIL_ATTACHMENT,
+                        get_2d_target(dst_target, layer),
+                        Some(dst),
+                        copy.texture_base.mip_level as i32,
+                    )
+                };
+            }
+
+            // Drawing only sets bits, so clear the copied region first.
+            unsafe { gl.stencil_mask(0xff) };
+            unsafe { gl.clear_buffer_i32_slice(glow::STENCIL, 0, &[0]) };
+            for bit in 0..8 {
+                unsafe { gl.stencil_mask(1 << bit) };
+                unsafe { gl.uniform_1_u32(program.bit.as_ref(), bit) };
+                unsafe { gl.draw_arrays(glow::TRIANGLES, 0, 3) };
+            }
+        }
+
+        // The rest of the state is reset at the start of every render pass.
+        unsafe {
+            gl.framebuffer_texture_2d(
+                glow::DRAW_FRAMEBUFFER,
+                glow::DEPTH_STENCIL_ATTACHMENT,
+                glow::TEXTURE_2D,
+                None,
+                0,
+            )
+        };
+        unsafe { gl.color_mask(true, true, true, true) };
+        unsafe { gl.stencil_mask(!0) };
+        unsafe { gl.disable(glow::STENCIL_TEST) };
+        unsafe { gl.disable(glow::SCISSOR_TEST) };
+        unsafe { gl.delete_texture(staging) };
+        true
+    }
+
+    /// Copies between depth or stencil textures, which `glCopyTexSubImage` can't do, by blitting
+    /// between framebuffers.
+    pub(super) unsafe fn copy_depth_stencil_texture(
+        &self,
+        gl: &glow::Context,
+        src: glow::Texture,
+        src_target: BindTarget,
+        dst: glow::Texture,
+        dst_target: BindTarget,
+        copy: &crate::TextureCopy,
+    ) {
+        let (attachment, mask) = match (
+            copy.src_base.aspect.contains(crate::FormatAspects::DEPTH),
+            copy.src_base.aspect.contains(crate::FormatAspects::STENCIL),
+        ) {
+            (true, true) => (
+                glow::DEPTH_STENCIL_ATTACHMENT,
+                glow::DEPTH_BUFFER_BIT | glow::STENCIL_BUFFER_BIT,
+            ),
+            (true, false) => (glow::DEPTH_ATTACHMENT, glow::DEPTH_BUFFER_BIT),
+            _ => (glow::STENCIL_ATTACHMENT, glow::STENCIL_BUFFER_BIT),
+        };
+        let attach = |fbo_target, texture, target, base: &crate::TextureCopyBase, z: u32| {
+            if is_layered_target(target) {
+                let layer = if target == glow::TEXTURE_3D {
+                    base.origin.z + z
+                } else {
+                    base.array_layer + z
+                };
+                unsafe {
+                    gl.framebuffer_texture_layer(
+                        fbo_target,
+                        attachment,
+                        texture,
+                        base.mip_level as i32,
+                        layer as i32,
+                    )
+                };
+            } else {
+                unsafe {
+                    gl.framebuffer_texture_2d(
+                        fbo_target,
+                        attachment,
+                        get_2d_target(target, base.array_layer + z),
+                        texture,
+                        base.mip_level as i32,
+                    )
+                };
+            }
+        };
+
+        unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.copy_fbo)) };
+        unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.draw_fbo)) };
+        for fbo_target in [glow::READ_FRAMEBUFFER, glow::DRAW_FRAMEBUFFER] {
+            unsafe {
+                gl.framebuffer_texture_2d(
+                    fbo_target,
+                    glow::COLOR_ATTACHMENT0,
+                    glow::TEXTURE_2D,
+                    None,
+                    0,
+                )
+            };
+        }
+        unsafe { gl.disable(glow::SCISSOR_TEST) };
+        let (src_x, src_y) = (copy.src_base.origin.x as i32, copy.src_base.origin.y as i32);
+        let (dst_x, dst_y) = (copy.dst_base.origin.x as i32, copy.dst_base.origin.y as i32);
+        let (width, height) = (copy.size.width as i32, copy.size.height as i32);
+        for z in 0..copy.size.depth {
+            attach(
+                glow::READ_FRAMEBUFFER,
+                Some(src),
+                src_target,
+                &copy.src_base,
+                z,
+            );
+            attach(
+                glow::DRAW_FRAMEBUFFER,
+                Some(dst),
+                dst_target,
+                &copy.dst_base,
+                z,
+            );
+            unsafe {
+                gl.blit_framebuffer(
+                    src_x,
+                    src_y,
+                    src_x + width,
+                    src_y + height,
+                    dst_x,
+                    dst_y,
+                    dst_x + width,
+                    dst_y + height,
+                    mask,
+                    glow::NEAREST,
+                )
+            };
+        }
+        for fbo_target in [glow::READ_FRAMEBUFFER, glow::DRAW_FRAMEBUFFER] {
+            unsafe { gl.framebuffer_texture_2d(fbo_target, attachment, glow::TEXTURE_2D, None, 0) };
+        }
+    }
+}
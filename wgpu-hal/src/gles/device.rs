@@ -27,12 +27,14 @@ struct CompilationContext<'a> {
 }
 
 impl CompilationContext<'_> {
+    #[allow(clippy::too_many_arguments)]
     fn consume_reflection(
         self,
         gl: &glow::Context,
         module: &naga::Module,
         ep_info: &naga::valid::FunctionInfo,
         reflection_info: naga::back::glsl::ReflectionInfo,
+        bounds_report: Option<naga::proc::BoundsReportOptions>,
         naga_stage: naga::ShaderStage,
         program: glow::Program,
     ) {
@@ -63,6 +65,12 @@ impl CompilationContext<'_> {
             self.name_binding_map.insert(name, (register, slot));
         }
 
+        if let (Some(name), Some(bounds_report)) = (reflection_info.bounds_report, bounds_report) {
+            let slot = self.layout.get_slot(&bounds_report.binding);
+            self.name_binding_map
+                .insert(name, (super::BindingRegister::StorageBuffers, slot));
+        }
+
         for (name, mapping) in reflection_info.texture_mapping {
             let var = &module.global_variables[mapping.texture];
             let register = match module.types[var.ty].inner {
@@ -248,22 +256,32 @@ impl super::Device {
         };
 
         // Other bounds check are either provided by glsl or not implemented yet.
-        let policies = naga::proc::BoundsCheckPolicies {
+        let mut policies = naga::proc::BoundsCheckPolicies {
             index: BoundsCheckPolicy::Unchecked,
             buffer: BoundsCheckPolicy::Unchecked,
             image_load: image_check,
             binding_array: BoundsCheckPolicy::Unchecked,
         };
 
+        let bounds_report = stage
+            .module
+            .source
+            .bounds_report_options(naga_stage, stage.entry_point);
+        if bounds_report.is_some() {
+            crate::auxil::report_out_of_bounds_accesses(&mut policies);
+        }
+
         let mut output = String::new();
         let needs_temp_options = stage.zero_initialize_workgroup_memory
-            != context.layout.naga_options.zero_initialize_workgroup_memory;
+            != context.layout.naga_options.zero_initialize_workgroup_memory
+            || bounds_report.is_some();
         let mut temp_options;
         let naga_options = if needs_temp_options {
             // We use a conditional here, as cloning the naga_options could be expensive
             // That is, we want to avoid doing that unless we cannot avoid it
             temp_options = context.layout.naga_options.clone();
             temp_options.zero_initialize_workgroup_memory = stage.zero_initialize_workgroup_memory;
+            temp_options.bounds_report = bounds_report;
             &temp_options
         } else {
             &context.layout.naga_options
@@ -293,6 +311,7 @@ impl super::Device {
             &module,
             info.get_entry_point(entry_point_index),
             reflection_info,
+            bounds_report,
             naga_stage,
             program,
         );
@@ -650,6 +669,14 @@ impl crate::Device for super::Device {
         self.counters.buffers.add(1);
     }
 
+    unsafe fn get_buffer_device_address(
+        &self,
+        _buffer: &super::Buffer,
+    ) -> Option<wgt::BufferAddress> {
+        // Indirect commands are not exposed, and don't need addresses.
+        None
+    }
+
     unsafe fn map_buffer(
         &self,
         buffer: &super::Buffer,
@@ -1226,6 +1253,8 @@ impl crate::Device for super::Device {
                 writer_flags,
                 binding_map,
                 zero_initialize_workgroup_memory: true,
+                // Set for each stage.
+                bounds_report: None,
             },
         })
     }
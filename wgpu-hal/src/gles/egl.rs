@@ -18,6 +18,8 @@ const EGL_PLATFORM_ANGLE_ANGLE: u32 = 0x3202;
 const EGL_PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE: u32 = 0x348F;
 const EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED: u32 = 0x3451;
 const EGL_PLATFORM_SURFACELESS_MESA: u32 = 0x31DD;
+const EGL_PLATFORM_DEVICE_EXT: u32 = 0x313F;
+const EGL_DRM_DEVICE_FILE_EXT: khronos_egl::Int = 0x3233;
 const EGL_GL_COLORSPACE_KHR: u32 = 0x309D;
 const EGL_GL_COLORSPACE_SRGB_KHR: u32 = 0x3089;
 
@@ -77,6 +79,17 @@ type EglDebugMessageControlFun = unsafe extern "system" fn(
     attrib_list: *const khronos_egl::Attrib,
 ) -> ffi::c_int;
 
+type EglDevice = *mut ffi::c_void;
+
+type EglQueryDevicesFun = unsafe extern "system" fn(
+    max_devices: khronos_egl::Int,
+    devices: *mut EglDevice,
+    num_devices: *mut khronos_egl::Int,
+) -> khronos_egl::Boolean;
+
+type EglQueryDeviceStringFun =
+    unsafe extern "system" fn(device: EglDevice, name: khronos_egl::Int) -> *const ffi::c_char;
+
 unsafe extern "system" fn egl_debug_proc(
     error: khronos_egl::Enum,
     command_raw: *const ffi::c_char,
@@ -192,6 +205,80 @@ fn test_wayland_display() -> Option<DisplayOwner> {
     })
 }
 
+/// Lists the devices of `EGL_EXT_device_enumeration`, with the DRM device file of each if
+/// `EGL_EXT_device_drm` reports one.
+fn query_devices(
+    egl: &EglInstance,
+) -> Result<Vec<(EglDevice, Option<String>)>, crate::InstanceError> {
+    let query_devices: EglQueryDevicesFun = {
+        let addr = egl.get_proc_address("eglQueryDevicesEXT").ok_or_else(|| {
+            crate::InstanceError::new(String::from("eglQueryDevicesEXT is not available"))
+        })?;
+        unsafe { core::mem::transmute(addr) }
+    };
+    let query_device_string: Option<EglQueryDeviceStringFun> = egl
+        .get_proc_address("eglQueryDeviceStringEXT")
+        .map(|addr| unsafe { core::mem::transmute(addr) });
+
+    let mut count = 0;
+    if unsafe { query_devices(0, ptr::null_mut(), &mut count) } == khronos_egl::FALSE {
+        return Err(crate::InstanceError::new(String::from(
+            "eglQueryDevicesEXT failed to count the EGL devices",
+        )));
+    }
+    let mut devices = vec![ptr::null_mut(); count as usize];
+    if unsafe { query_devices(count, devices.as_mut_ptr(), &mut count) } == khronos_egl::FALSE {
+        return Err(crate::InstanceError::new(String::from(
+            "eglQueryDevicesEXT failed to list the EGL devices",
+        )));
+    }
+    devices.truncate(count as usize);
+
+    Ok(devices
+        .into_iter()
+        .map(|device| {
+            let drm_file = query_device_string.and_then(|query_device_string| {
+                let file = unsafe { query_device_string(device, EGL_DRM_DEVICE_FILE_EXT) };
+                (!file.is_null()).then(|| {
+                    unsafe { ffi::CStr::from_ptr(file) }
+                        .to_string_lossy()
+                        .into_owned()
+                })
+            });
+            (device, drm_file)
+        })
+        .collect())
+}
+
+/// Checks that EGL supports `platform`, given its client extensions and whether it is EGL 1.5.
+fn check_platform(
+    platform: wgt::GlPlatform,
+    client_extensions: &str,
+    egl1_5: bool,
+) -> Result<(), crate::InstanceError> {
+    let required: &[&str] = match platform {
+        wgt::GlPlatform::Automatic => return Ok(()),
+        wgt::GlPlatform::Surfaceless => &["EGL_MESA_platform_surfaceless"],
+        wgt::GlPlatform::Device => &["EGL_EXT_platform_device", "EGL_EXT_device_enumeration"],
+    };
+    let missing: Vec<_> = required
+        .iter()
+        .filter(|&&extension| !client_extensions.split_whitespace().any(|e| e == extension))
+        .collect();
+    if !egl1_5 || !missing.is_empty() {
+        return Err(crate::InstanceError::new(format!(
+            "the {platform:?} EGL platform requires EGL 1.5 and {required:?}, \
+            but {}",
+            if egl1_5 {
+                format!("{missing:?} are missing")
+            } else {
+                String::from("EGL 1.5 is missing")
+            }
+        )));
+    }
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug)]
 enum SrgbFrameBufferKind {
     /// No support for SRGB surface
@@ -793,6 +880,8 @@ pub struct Instance {
     flags: wgt::InstanceFlags,
     options: wgt::GlBackendOptions,
     inner: Mutex<Inner>,
+    /// The displays of the devices after the first one, with [`wgt::GlPlatform::Device`].
+    other_devices: Vec<Mutex<Inner>>,
 }
 
 impl Instance {
@@ -869,28 +958,34 @@ impl crate::Instance for Instance {
             client_ext_str.split_whitespace().collect::<Vec<_>>()
         );
 
-        let wayland_library = if client_ext_str.contains("EGL_EXT_platform_wayland") {
+        #[cfg(not(Emscripten))]
+        let egl1_5 = egl.upcast::<khronos_egl::EGL1_5>();
+
+        #[cfg(Emscripten)]
+        let egl1_5: Option<&Arc<EglInstance>> = Some(&egl);
+
+        // Explicit platforms don't use a window system, so don't connect to one.
+        let platform = desc.backend_options.gl.platform;
+        check_platform(platform, &client_ext_str, egl1_5.is_some())?;
+        let automatic = platform == wgt::GlPlatform::Automatic;
+        let wayland_library = if automatic && client_ext_str.contains("EGL_EXT_platform_wayland") {
             test_wayland_display()
         } else {
             None
         };
-        let x11_display_library = if client_ext_str.contains("EGL_EXT_platform_x11") {
-            open_x_display()
-        } else {
-            None
-        };
-        let angle_x11_display_library = if client_ext_str.contains("EGL_ANGLE_platform_angle") {
+        let x11_display_library = if automatic && client_ext_str.contains("EGL_EXT_platform_x11") {
             open_x_display()
         } else {
             None
         };
+        let angle_x11_display_library =
+            if automatic && client_ext_str.contains("EGL_ANGLE_platform_angle") {
+                open_x_display()
+            } else {
+                None
+            };
 
-        #[cfg(not(Emscripten))]
-        let egl1_5 = egl.upcast::<khronos_egl::EGL1_5>();
-
-        #[cfg(Emscripten)]
-        let egl1_5: Option<&Arc<EglInstance>> = Some(&egl);
-
+        let mut other_displays = Vec::new();
         let (display, display_owner, wsi_kind) =
             if let (Some(library), Some(egl)) = (wayland_library, egl1_5) {
                 log::info!("Using Wayland platform");
@@ -934,6 +1029,53 @@ impl crate::Instance for Instance {
                 }
                 .unwrap();
                 (display, Some(Rc::new(display_owner)), WindowKind::AngleX11)
+            } else if platform == wgt::GlPlatform::Surfaceless {
+                // Checked by `check_platform`.
+                let egl = egl1_5.unwrap();
+                log::info!("Using surfaceless platform");
+                let display = unsafe {
+                    egl.get_platform_display(
+                        EGL_PLATFORM_SURFACELESS_MESA,
+                        khronos_egl::DEFAULT_DISPLAY,
+                        &[khronos_egl::ATTRIB_NONE],
+                    )
+                }
+                .map_err(|e| {
+                    crate::InstanceError::with_source(
+                        String::from("failed to get the surfaceless EGL display"),
+                        e,
+                    )
+                })?;
+                (display, None, WindowKind::Unknown)
+            } else if platform == wgt::GlPlatform::Device {
+                let egl1_5 = egl1_5.unwrap();
+                let mut displays = Vec::new();
+                let devices = query_devices(&egl)?;
+                for (index, (device, drm_file)) in devices.into_iter().enumerate() {
+                    log::info!(
+                        "Using device platform with EGL device {index} ({})",
+                        drm_file.as_deref().unwrap_or("no DRM device file")
+                    );
+                    let display = unsafe {
+                        egl1_5.get_platform_display(
+                            EGL_PLATFORM_DEVICE_EXT,
+                            device,
+                            &[khronos_egl::ATTRIB_NONE],
+                        )
+                    };
+                    match display {
+                        Ok(display) => displays.push(display),
+                        Err(e) => log::warn!("Failed to get the display of device {index}: {e}"),
+                    }
+                }
+                if displays.is_empty() {
+                    return Err(crate::InstanceError::new(String::from(
+                        "no EGL device has a display",
+                    )));
+                }
+                let display = displays.remove(0);
+                other_displays = displays;
+                (display, None, WindowKind::Unknown)
             } else if client_ext_str.contains("EGL_MESA_platform_surfaceless") {
                 log::warn!("No windowing system present. Using surfaceless platform");
                 #[allow(clippy::unnecessary_literal_unwrap)] // This is only a literal on Emscripten
@@ -976,12 +1118,27 @@ impl crate::Instance for Instance {
             unsafe { (function)(Some(egl_debug_proc), attributes.as_ptr()) };
         }
 
-        let inner = Inner::create(
-            desc.flags,
-            egl,
-            display,
-            desc.backend_options.gl.gles_minor_version,
-        )?;
+        // Devices that fail to initialize are skipped, as long as one of them succeeds.
+        let mut inners = Vec::with_capacity(1 + other_displays.len());
+        let mut last_error = None;
+        for display in core::iter::once(display).chain(other_displays) {
+            match Inner::create(
+                desc.flags,
+                Arc::clone(&egl),
+                display,
+                desc.backend_options.gl.gles_minor_version,
+            ) {
+                Ok(inner) => inners.push(Mutex::new(inner)),
+                Err(e) => {
+                    log::warn!("Failed to initialize EGL display: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        if inners.is_empty() {
+            return Err(last_error.unwrap());
+        }
+        let inner = inners.remove(0);
 
         Ok(Instance {
             wsi: WindowSystemInterface {
@@ -990,7 +1147,8 @@ impl crate::Instance for Instance {
             },
             flags: desc.flags,
             options: desc.backend_options.gl.clone(),
-            inner: Mutex::new(inner),
+            inner,
+            other_devices: inners,
         })
     }
 
@@ -1002,6 +1160,13 @@ impl crate::Instance for Instance {
     ) -> Result<Surface, crate::InstanceError> {
         use raw_window_handle::RawWindowHandle as Rwh;
 
+        if self.options.platform != wgt::GlPlatform::Automatic {
+            return Err(crate::InstanceError::new(format!(
+                "surfaces can't be created with the {:?} EGL platform, which has no window system",
+                self.options.platform
+            )));
+        }
+
         #[cfg_attr(any(target_os = "android", Emscripten), allow(unused_mut))]
         let mut inner = self.inner.lock();
 
@@ -1112,7 +1277,16 @@ impl crate::Instance for Instance {
         &self,
         _surface_hint: Option<&Surface>,
     ) -> Vec<crate::ExposedAdapter<super::Api>> {
-        let inner = self.inner.lock();
+        core::iter::once(&self.inner)
+            .chain(&self.other_devices)
+            .filter_map(|inner| unsafe { self.expose_adapter(&inner.lock()) })
+            .collect()
+    }
+}
+
+impl Instance {
+    /// Exposes the adapter of the display of `inner`.
+    unsafe fn expose_adapter(&self, inner: &Inner) -> Option<crate::ExposedAdapter<super::Api>> {
         inner.egl.make_current();
 
         let mut gl = unsafe {
@@ -1158,8 +1332,6 @@ impl crate::Instance for Instance {
                 self.options.clone(),
             )
         }
-        .into_iter()
-        .collect()
     }
 }
 
@@ -1584,3 +1756,45 @@ impl crate::Surface for Surface {
     }
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::check_platform;
+
+    #[test]
+    fn test_check_platform() {
+        const MESA: &str = "EGL_EXT_platform_base EGL_EXT_device_enumeration \
+            EGL_EXT_platform_device EGL_MESA_platform_surfaceless";
+
+        for platform in [
+            wgt::GlPlatform::Automatic,
+            wgt::GlPlatform::Surfaceless,
+            wgt::GlPlatform::Device,
+        ] {
+            check_platform(platform, MESA, true).unwrap();
+        }
+        // The automatic platform falls back to the default display.
+        check_platform(wgt::GlPlatform::Automatic, "", false).unwrap();
+
+        check_platform(wgt::GlPlatform::Surfaceless, MESA, false).unwrap_err();
+        check_platform(wgt::GlPlatform::Surfaceless, "EGL_EXT_platform_base", true).unwrap_err();
+        let error = check_platform(
+            wgt::GlPlatform::Device,
+            "EGL_EXT_platform_device EGL_MESA_platform_surfaceless",
+            true,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("[\"EGL_EXT_device_enumeration\"] are missing"));
+        // Extensions are matched by their whole name.
+        check_platform(
+            wgt::GlPlatform::Device,
+            "EGL_EXT_platform_device_x EGL_EXT_device_enumeration",
+            true,
+        )
+        .unwrap_err();
+    }
+}
@@ -77,6 +77,21 @@ we don't bother with that combination.
 - We bind instance buffers with offset emulation.
 - We _do not_ advertise support for `INDIRECT_FIRST_INSTANCE` and cpu-side pretend the `first_instance` is 0 on indirect calls.
 
+## Copies
+
+Copies are done with `glCopyTexSubImage`, `glTexSubImage` and `glReadPixels` where possible.
+GLES can't read depth or stencil values with `glReadPixels`, only guarantees reading integer
+formats with four components, and can't upload only the stencil aspect of a combined
+depth-stencil format. On GLES 3.1 and GL 4.3, we emulate these copies with internal shaders
+in `copy.rs`:
+  - texture to buffer copies of depth, stencil and integer formats use a compute shader that
+    fetches the texels and packs them into the buffer as a storage buffer
+  - buffer to texture copies into a stencil aspect upload the values into a temporary texture,
+    and draw once for each bit, discarding the fragments where it isn't set
+  - texture to texture copies of depth and stencil formats blit between framebuffers
+
+Copies of compressed textures into buffers are still not supported.
+
 */
 
 ///cbindgen:ignore
@@ -92,6 +107,7 @@ mod wgl;
 mod adapter;
 mod command;
 mod conv;
+mod copy;
 mod device;
 mod fence;
 mod queue;
@@ -160,6 +176,7 @@ impl crate::Api for Api {
     type QuerySet = QuerySet;
     type Fence = Fence;
     type AccelerationStructure = AccelerationStructure;
+    type VideoDecoder = VideoDecoder;
     type PipelineCache = PipelineCache;
 
     type BindGroupLayout = BindGroupLayout;
@@ -191,7 +208,8 @@ crate::impl_dyn_resource!(
     ShaderModule,
     Surface,
     Texture,
-    TextureView
+    TextureView,
+    VideoDecoder
 );
 
 bitflags::bitflags! {
@@ -232,6 +250,10 @@ bitflags::bitflags! {
         ///
         /// When this is true, instance offset emulation via vertex buffer rebinding and a shader uniform will be disabled.
         const FULLY_FEATURED_INSTANCING = 1 << 16;
+        /// Supports the internal shaders that emulate the copies GL can't do itself.
+        ///
+        /// They need compute shaders, storage buffers and `GL_DEPTH_STENCIL_TEXTURE_MODE`.
+        const SHADER_COPIES = 1 << 17;
     }
 }
 
@@ -326,6 +348,8 @@ pub struct Queue {
     /// Keep a reasonably large buffer filled with zeroes, so that we can implement `ClearBuffer` of
     /// zeroes by copying from it.
     zero_buffer: glow::Buffer,
+    /// Programs emulating the copies that GL can't do itself.
+    copy_programs: Mutex<copy::CopyPrograms>,
     temp_query_results: Mutex<Vec<u64>>,
     draw_buffer_count: AtomicU8,
     current_index_buffer: Mutex<Option<glow::Buffer>>,
@@ -337,6 +361,7 @@ impl Drop for Queue {
         unsafe { gl.delete_framebuffer(self.draw_fbo) };
         unsafe { gl.delete_framebuffer(self.copy_fbo) };
         unsafe { gl.delete_buffer(self.zero_buffer) };
+        unsafe { self.copy_programs.lock().destroy(gl) };
     }
 }
 
@@ -760,6 +785,11 @@ pub struct AccelerationStructure;
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
 
+#[derive(Debug)]
+pub struct VideoDecoder;
+
+impl crate::DynVideoDecoder for VideoDecoder {}
+
 #[derive(Debug)]
 pub struct PipelineCache;
 
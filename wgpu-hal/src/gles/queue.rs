@@ -25,7 +25,7 @@ fn to_debug_str(s: &str) -> &str {
     }
 }
 
-fn get_2d_target(target: u32, array_layer: u32) -> u32 {
+pub(super) fn get_2d_target(target: u32, array_layer: u32) -> u32 {
     const CUBEMAP_FACES: [u32; 6] = [
         glow::TEXTURE_CUBE_MAP_POSITIVE_X,
         glow::TEXTURE_CUBE_MAP_NEGATIVE_X,
@@ -675,6 +675,16 @@ impl super::Queue {
                 dst_target,
                 ref copy,
             } => {
+                if copy
+                    .src_base
+                    .aspect
+                    .intersects(crate::FormatAspects::DEPTH_STENCIL)
+                {
+                    unsafe {
+                        self.copy_depth_stencil_texture(gl, src, src_target, dst, dst_target, copy)
+                    };
+                    return;
+                }
                 //TODO: handle 3D copies
                 unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.copy_fbo)) };
                 if is_layered_target(src_target) {
@@ -738,6 +748,13 @@ impl super::Queue {
                 dst_format,
                 ref copy,
             } => {
+                if unsafe {
+                    self.copy_buffer_to_stencil_with_shader(
+                        gl, src, dst, dst_target, dst_format, copy,
+                    )
+                } {
+                    return;
+                }
                 let (block_width, block_height) = dst_format.block_dimensions();
                 let block_size = dst_format.block_copy_size(None).unwrap();
                 let format_desc = self.shared.describe_texture_format(dst_format);
@@ -876,17 +893,22 @@ impl super::Queue {
                 dst_target: _,
                 ref copy,
             } => {
-                let block_size = src_format.block_copy_size(None).unwrap();
-                if src_format.is_compressed() {
-                    log::error!("Not implemented yet: compressed texture copy to buffer");
+                if unsafe {
+                    self.copy_texture_to_buffer_with_shader(
+                        gl, src, src_target, src_format, dst, copy,
+                    )
+                } {
                     return;
                 }
-                if src_target == glow::TEXTURE_CUBE_MAP
-                    || src_target == glow::TEXTURE_CUBE_MAP_ARRAY
-                {
-                    log::error!("Not implemented yet: cubemap texture copy to buffer");
+                // GL can't read compressed textures back as blocks.
+                if src_format.is_compressed() {
+                    log::error!(
+                        "Copies from compressed textures to buffers are not supported \
+                        (`DownlevelFlags::COMPRESSED_TEXTURE_TO_BUFFER_COPIES`)"
+                    );
                     return;
                 }
+                let block_size = src_format.block_copy_size(None).unwrap();
                 let format_desc = self.shared.describe_texture_format(src_format);
                 let row_texels = copy
                     .buffer_layout
@@ -926,49 +948,30 @@ impl super::Queue {
                     };
                 };
 
-                match src_target {
-                    glow::TEXTURE_2D => {
+                let bytes_per_image = u64::from(block_size * row_texels * column_texels);
+                for z in 0..copy.size.depth {
+                    if is_layered_target(src_target) {
                         unsafe {
-                            gl.framebuffer_texture_2d(
+                            gl.framebuffer_texture_layer(
                                 glow::READ_FRAMEBUFFER,
                                 glow::COLOR_ATTACHMENT0,
-                                src_target,
                                 Some(src),
                                 copy.texture_base.mip_level as i32,
+                                (get_z_offset(src_target, &copy.texture_base) + z) as i32,
                             )
                         };
-                        read_pixels(copy.buffer_layout.offset);
-                    }
-                    glow::TEXTURE_2D_ARRAY => {
+                    } else {
                         unsafe {
-                            gl.framebuffer_texture_layer(
+                            gl.framebuffer_texture_2d(
                                 glow::READ_FRAMEBUFFER,
                                 glow::COLOR_ATTACHMENT0,
+                                get_2d_target(src_target, copy.texture_base.array_layer + z),
                                 Some(src),
                                 copy.texture_base.mip_level as i32,
-                                copy.texture_base.array_layer as i32,
                             )
                         };
-                        read_pixels(copy.buffer_layout.offset);
-                    }
-                    glow::TEXTURE_3D => {
-                        for z in copy.texture_base.origin.z..copy.size.depth {
-                            unsafe {
-                                gl.framebuffer_texture_layer(
-                                    glow::READ_FRAMEBUFFER,
-                                    glow::COLOR_ATTACHMENT0,
-                                    Some(src),
-                                    copy.texture_base.mip_level as i32,
-                                    z as i32,
-                                )
-                            };
-                            let offset = copy.buffer_layout.offset
-                                + (z * block_size * row_texels * column_texels) as u64;
-                            read_pixels(offset);
-                        }
                     }
-                    glow::TEXTURE_CUBE_MAP | glow::TEXTURE_CUBE_MAP_ARRAY => unimplemented!(),
-                    _ => unreachable!(),
+                    read_pixels(copy.buffer_layout.offset + u64::from(z) * bytes_per_image);
                 }
             }
             C::SetIndexBuffer(buffer) => {
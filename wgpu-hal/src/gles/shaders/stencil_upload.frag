@@ -0,0 +1,12 @@
+// Keeps the fragments whose stencil value has `bit` set, so that drawing once per bit with that
+// bit as the write mask uploads `source` into the stencil buffer.
+uniform highp usampler2D source;
+uniform highp uint bit;
+// Position of the first texel of the copy in the framebuffer.
+uniform ivec2 origin;
+void main() {
+  uint value = texelFetch(source, ivec2(gl_FragCoord.xy) - origin, 0).r;
+  if ((value & (1u << bit)) == 0u) {
+    discard;
+  }
+}
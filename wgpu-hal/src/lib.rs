@@ -277,6 +277,8 @@ pub mod api {
 }
 
 mod dynamic;
+#[cfg(feature = "recorder")]
+pub mod recorder;
 #[cfg(feature = "validation_canary")]
 mod validation_canary;
 
@@ -289,7 +291,7 @@ pub use dynamic::{
     DynBindGroupLayout, DynBuffer, DynCommandBuffer, DynCommandEncoder, DynComputePipeline,
     DynDevice, DynExposedAdapter, DynFence, DynInstance, DynOpenDevice, DynPipelineCache,
     DynPipelineLayout, DynQuerySet, DynQueue, DynRenderPipeline, DynResource, DynSampler,
-    DynShaderModule, DynSurface, DynSurfaceTexture, DynTexture, DynTextureView,
+    DynShaderModule, DynSurface, DynSurfaceTexture, DynTexture, DynTextureView, DynVideoDecoder,
 };
 
 #[allow(unused)]
@@ -537,6 +539,12 @@ pub trait Api: Clone + fmt::Debug + Sized + WasmNotSendSync + 'static {
     type PipelineCache: DynPipelineCache;
 
     type AccelerationStructure: DynAccelerationStructure + 'static;
+
+    /// A decoder of compressed video, created by [`Device::create_video_decoder`].
+    ///
+    /// Backends without [`wgt::Features::VIDEO_DECODE`] use a type that is
+    /// never constructed.
+    type VideoDecoder: DynVideoDecoder;
 }
 
 pub trait Instance: Sized + WasmNotSendSync {
@@ -775,6 +783,17 @@ pub trait Device: WasmNotSendSync {
     /// A hook for when a wgpu-core buffer is created from a raw wgpu-hal buffer.
     unsafe fn add_raw_buffer(&self, buffer: &<Self::A as Api>::Buffer);
 
+    /// Returns the GPU virtual address of the start of `buffer`, or `None` if
+    /// the backend can't address buffers.
+    ///
+    /// Backends that expose [`wgt::Features::INDIRECT_COMMANDS`] must return
+    /// an address, which the records passed to
+    /// [`CommandEncoder::execute_indirect`] need.
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &<Self::A as Api>::Buffer,
+    ) -> Option<wgt::BufferAddress>;
+
     /// Return a pointer to CPU memory mapping the contents of `buffer`.
     ///
     /// Buffer mappings are persistent: the buffer may remain mapped on the CPU
@@ -1036,12 +1055,103 @@ pub trait Device: WasmNotSendSync {
     );
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8>;
 
+    /// Query the sizes and picture counts that `codec` can be decoded with into
+    /// pictures of `format`.
+    ///
+    /// Returns `None` if the device can't decode `codec` into `format`.
+    #[allow(unused_variables)]
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        None
+    }
+
+    /// Create a decoder for the video streams described by `desc`.
+    ///
+    /// # Safety
+    ///
+    /// - The device must have been opened with [`wgt::Features::VIDEO_DECODE`].
+    /// - `desc` must be within the [`video_decode_capabilities`] of its codec
+    ///   and format.
+    ///
+    /// [`video_decode_capabilities`]: Device::video_decode_capabilities
+    #[allow(unused_variables)]
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &VideoDecoderDescriptor,
+    ) -> Result<<Self::A as Api>::VideoDecoder, DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
+
+    /// Destroy `decoder`, waiting for its decodes to finish.
+    ///
+    /// The pictures created by `decoder` stay valid textures.
+    #[allow(unused_variables)]
+    unsafe fn destroy_video_decoder(&self, decoder: <Self::A as Api>::VideoDecoder) {}
+
+    /// Create a picture `decoder` can decode into and predict from.
+    ///
+    /// The picture is a texture like one created by [`create_texture`], whose
+    /// size and format are those of the decoder, with a single mip level and
+    /// array layer, and that can also be used by [`Queue::decode_video`].
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must describe a 2D texture of the decoder's
+    ///   `max_coded_extent` and `format`, with one mip level and one sample.
+    /// - `desc.usage` must not include storage or attachment usages.
+    ///
+    /// [`create_texture`]: Device::create_texture
+    #[allow(unused_variables)]
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &<Self::A as Api>::VideoDecoder,
+        desc: &TextureDescriptor,
+    ) -> Result<<Self::A as Api>::Texture, DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
+
+    /// Create a queue of `queue_type`, besides the queue the device was
+    /// opened with.
+    ///
+    /// The queue shares the resources of the device, and its submissions
+    /// execute alongside the submissions of the other queues, in no particular
+    /// order unless they wait for each other with [`Queue::wait_for_fence`].
+    /// Command buffers submitted to the queue must be built by encoders created
+    /// with it.
+    ///
+    /// # Safety
+    ///
+    /// - `queue_type` must not be [`wgt::QueueType::Graphics`].
+    /// - `index` must be lower than the [`max_count`] of `queue_type` for the
+    ///   limits the device was opened with.
+    /// - There must be no other live queue of `queue_type` and `index`.
+    ///
+    /// [`max_count`]: wgt::QueueType::max_count
+    #[allow(unused_variables)]
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        index: u32,
+    ) -> Result<<Self::A as Api>::Queue, DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters;
 
     fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport> {
         None
     }
 
+    /// Query the current budget and usage of the device's memory heaps.
+    ///
+    /// Returns `None` if the backend or the driver can't report them.
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        None
+    }
+
     fn check_if_oom(&self) -> Result<(), DeviceError>;
 }
 
@@ -1128,6 +1238,83 @@ pub trait Queue: WasmNotSendSync {
         texture: <Self::A as Api>::SurfaceTexture,
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+
+    /// Make the next call to [`submit`] wait for `fence` to reach `value`
+    /// before executing its command buffers.
+    ///
+    /// This orders the next submission after work submitted to another queue
+    /// of the same device, and makes the results of that work visible to it.
+    ///
+    /// # Safety
+    ///
+    /// - `fence` must have been created by the device of this queue.
+    /// - A submission signaling `fence` with `value` or greater must have been
+    ///   made to another queue, before the next call to [`submit`].
+    ///
+    /// [`submit`]: Queue::submit
+    #[allow(unused_variables)]
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &<Self::A as Api>::Fence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
+
+    /// Decode a picture with `decoder`, as described by `desc`.
+    ///
+    /// Decoding starts once `wait_fence` reaches its value, and the next call
+    /// to [`submit`] waits for it to finish. The pictures of `desc` are left in
+    /// the usage given by `to` of their transitions.
+    ///
+    /// # Safety
+    ///
+    /// - `decoder` must have been created by the device of this queue.
+    /// - Every texture in `desc` must be a picture created by `decoder`, in the
+    ///   usage given by `from` of its transition, which must stay alive until
+    ///   the next submission completes.
+    /// - `desc.picture` and every reference must be of the decoder's codec.
+    /// - The slot of every picture must be different, and lower than the
+    ///   decoder's `max_pictures`.
+    ///
+    /// [`submit`]: Queue::submit
+    #[allow(unused_variables)]
+    unsafe fn decode_video(
+        &self,
+        decoder: &<Self::A as Api>::VideoDecoder,
+        desc: &VideoDecodeDescriptor<<Self::A as Api>::Texture>,
+        wait_fence: (&<Self::A as Api>::Fence, FenceValue),
+    ) -> Result<(), DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
+
+    /// Bind memory to the pages of sparse buffers and textures, or unbind it.
+    ///
+    /// Binding is ordered after the previous calls to [`submit`], and before
+    /// the next, and waits for the fences given to [`wait_for_fence`] like
+    /// [`submit`] does. The contents of pages that become resident are
+    /// undefined, and pages that lose their memory read as zero.
+    ///
+    /// # Safety
+    ///
+    /// - Every resource must have been created by the device of this queue,
+    ///   with [`MemoryFlags::SPARSE`], and must stay alive until the next
+    ///   submission completes.
+    /// - Every region must be within its resource, and aligned as described by
+    ///   [`SparseBufferBind`] and [`SparseTextureBind`].
+    /// - Work on other queues using the resources must have completed, or be
+    ///   waited for with [`wait_for_fence`].
+    ///
+    /// [`submit`]: Queue::submit
+    /// [`wait_for_fence`]: Queue::wait_for_fence
+    #[allow(unused_variables)]
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[SparseBufferBind<'_, <Self::A as Api>::Buffer>],
+        texture_binds: &[SparseTextureBind<'_, <Self::A as Api>::Texture>],
+    ) -> Result<(), DeviceError> {
+        Err(DeviceError::Unexpected)
+    }
 }
 
 /// Encoder and allocation pool for `CommandBuffer`s.
@@ -1250,6 +1437,66 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     where
         T: Iterator<Item = TextureBarrier<'a, <Self::A as Api>::Texture>>;
 
+    /// Release `buffers` and `textures` from the queues of the type this
+    /// encoder was created for, to the queues of `queue_type`.
+    ///
+    /// Resources created with [`MemoryFlags::QUEUE_EXCLUSIVE`] can only be
+    /// used by the queues of another type once a command buffer submitted to
+    /// a queue owning them released them, and a command buffer submitted to
+    /// the other queue, after it waited for the release with
+    /// [`Queue::wait_for_fence`], acquired them with [`acquire_resources`].
+    ///
+    /// The `from` and `to` of the usage of each barrier are both the state the
+    /// resource is in, which it keeps.
+    ///
+    /// Backends whose queues all share their resources don't need to do
+    /// anything.
+    ///
+    /// # Safety
+    ///
+    /// - The resources must have been created with
+    ///   [`MemoryFlags::QUEUE_EXCLUSIVE`], and belong to the queues of this
+    ///   encoder.
+    /// - `queue_type` must not be the type of the queue this encoder was
+    ///   created for.
+    ///
+    /// [`acquire_resources`]: CommandEncoder::acquire_resources
+    #[allow(unused_variables)]
+    unsafe fn release_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = BufferBarrier<'a, <Self::A as Api>::Buffer>>,
+        U: Iterator<Item = TextureBarrier<'a, <Self::A as Api>::Texture>>,
+    {
+    }
+
+    /// Acquire `buffers` and `textures`, which the queues of `queue_type`
+    /// released with [`release_resources`], for the queues of the type this
+    /// encoder was created for.
+    ///
+    /// The barriers must be the ones given to [`release_resources`].
+    ///
+    /// # Safety
+    ///
+    /// - The resources must have been released to the queues of this encoder
+    ///   by the queues of `queue_type`, and not acquired since.
+    ///
+    /// [`release_resources`]: CommandEncoder::release_resources
+    #[allow(unused_variables)]
+    unsafe fn acquire_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = BufferBarrier<'a, <Self::A as Api>::Buffer>>,
+        U: Iterator<Item = TextureBarrier<'a, <Self::A as Api>::Texture>>,
+    {
+    }
+
     // copy operations
 
     unsafe fn clear_buffer(&mut self, buffer: &<Self::A as Api>::Buffer, range: MemoryRange);
@@ -1505,6 +1752,36 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     );
+    /// Execute up to `max_count` records of a GPU-generated command stream.
+    ///
+    /// Each record in `buffer`, starting at `offset`, switches the state named
+    /// by `layout` and then draws. The records are in the backend's native
+    /// format, not the one described by [`wgt::IndirectCommandLayout`]:
+    ///
+    /// - On DX12, a record holds the push constant values, a
+    ///   `D3D12_VERTEX_BUFFER_VIEW` per switched vertex buffer, a
+    ///   `D3D12_INDEX_BUFFER_VIEW` if the index buffer is switched, the three
+    ///   special constants if `pipeline_layout` was created with
+    ///   [`PipelineLayoutFlags::FIRST_VERTEX_INSTANCE`], and the draw arguments.
+    ///
+    /// Only DX12 exposes [`wgt::Features::INDIRECT_COMMANDS`]. Metal indirect
+    /// command buffers and `VK_EXT_device_generated_commands` would need their
+    /// own record formats, and the other backends are never called.
+    ///
+    /// If `count_buffer` is given, the number of records executed is the
+    /// smaller of `max_count` and the `u32` in `count_buffer` at `count_offset`.
+    ///
+    /// The vertex buffers, index buffer and push constants switched by the
+    /// records must be set again before the next draw.
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &<Self::A as Api>::PipelineLayout,
+        buffer: &<Self::A as Api>::Buffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&<Self::A as Api>::Buffer, wgt::BufferAddress)>,
+    );
     unsafe fn draw_mesh_tasks(
         &mut self,
         group_count_x: u32,
@@ -1745,6 +2022,17 @@ bitflags!(
     pub struct MemoryFlags: u32 {
         const TRANSIENT = 1 << 0;
         const PREFER_COHERENT = 1 << 1;
+        /// The resource is created without memory, which is bound to it one
+        /// page at a time with [`Queue::bind_sparse_memory`].
+        const SPARSE = 1 << 2;
+        /// The resource belongs to the queues of one type at a time, and moves
+        /// to the queues of another type with
+        /// [`CommandEncoder::release_resources`] and
+        /// [`CommandEncoder::acquire_resources`].
+        ///
+        /// Other resources can be used by the queues of all types, which may
+        /// be slower.
+        const QUEUE_EXCLUSIVE = 1 << 3;
     }
 );
 
@@ -1921,6 +2209,22 @@ impl TextureDescriptor<'_> {
             wgt::TextureDimension::D2 => self.size.depth_or_array_layers,
         }
     }
+
+    /// Returns the first mip level of the mip tail of a sparse texture, as
+    /// given by [`wgt::TextureDescriptor::sparse_mip_tail_start`].
+    pub fn sparse_mip_tail_start(&self) -> u32 {
+        wgt::TextureDescriptor {
+            label: (),
+            size: self.size,
+            mip_level_count: self.mip_level_count,
+            sample_count: self.sample_count,
+            dimension: self.dimension,
+            format: self.format,
+            usage: wgt::TextureUsages::empty(),
+            view_formats: (),
+        }
+        .sparse_mip_tail_start()
+    }
 }
 
 /// TextureView descriptor.
@@ -2193,6 +2497,13 @@ pub struct BindGroupDescriptor<
 pub struct CommandEncoderDescriptor<'a, Q: DynQueue + ?Sized> {
     pub label: Label<'a>,
     pub queue: &'a Q,
+    /// If true, the command buffers built by the encoder may be submitted
+    /// more than once, including while a previous submission of them is still
+    /// executing.
+    ///
+    /// This is only supported when the adapter supports
+    /// [`wgt::Features::REUSABLE_COMMAND_BUFFERS`].
+    pub reusable: bool,
 }
 
 /// Naga shader module.
@@ -2204,6 +2515,36 @@ pub struct NagaShader {
     pub info: naga::valid::ModuleInfo,
     /// Source codes for debug
     pub debug_source: Option<DebugSource>,
+    /// Where to record the out-of-bounds accesses of the module, if they
+    /// should be reported.
+    pub bounds_report: Option<ShaderBoundsReport>,
+}
+
+impl NagaShader {
+    /// Return the options for reporting the out-of-bounds accesses of the
+    /// entry point `entry_point` of `stage`, if they should be reported.
+    ///
+    /// Back ends generating code with these options must apply
+    /// [`naga::proc::BoundsCheckPolicy::Report`] to indices and buffers.
+    pub fn bounds_report_options(
+        &self,
+        stage: naga::ShaderStage,
+        entry_point: &str,
+    ) -> Option<naga::proc::BoundsReportOptions> {
+        let bounds_report = self.bounds_report?;
+        if !bounds_report.stages.contains(auxil::map_naga_stage(stage)) {
+            return None;
+        }
+        let index = self
+            .module
+            .entry_points
+            .iter()
+            .position(|ep| ep.stage == stage && ep.name == entry_point)?;
+        Some(naga::proc::BoundsReportOptions {
+            binding: bounds_report.binding,
+            shader_id: bounds_report.first_shader_id + index as u32,
+        })
+    }
 }
 
 // Custom implementation avoids the need to generate Debug impl code
@@ -2214,6 +2555,22 @@ impl fmt::Debug for NagaShader {
     }
 }
 
+/// Where a [`NagaShader`] records its out-of-bounds accesses.
+///
+/// See [`naga::proc::BoundsReportOptions`].
+#[derive(Clone, Copy, Debug)]
+pub struct ShaderBoundsReport {
+    /// The binding of the bounds report storage buffer.
+    ///
+    /// This is part of the pipeline layouts of the shader's pipelines.
+    pub binding: naga::ResourceBinding,
+    /// The shader id recorded by the first entry point of the module. The
+    /// following entry points record the following ids.
+    pub first_shader_id: u32,
+    /// The stages whose out-of-bounds accesses are reported.
+    pub stages: wgt::ShaderStages,
+}
+
 /// Shader input.
 #[allow(clippy::large_enum_variant)]
 pub enum ShaderInput<'a> {
@@ -2411,6 +2768,71 @@ pub struct TextureBarrier<'a, T: DynTexture + ?Sized> {
     pub usage: StateTransition<wgt::TextureUses>,
 }
 
+pub type VideoDecoderDescriptor<'a> = wgt::VideoDecoderDescriptor<Label<'a>>;
+
+/// A picture of a [`VideoDecodeDescriptor`].
+#[derive(Debug, Clone)]
+pub struct VideoPicture<'a, T: DynTexture + ?Sized> {
+    pub texture: &'a T,
+    /// The slot of the picture in the decoded picture buffer of the decoder.
+    pub slot: u32,
+    /// The usage the picture is in before the decode, and the usage it's left in.
+    pub usage: StateTransition<wgt::TextureUses>,
+}
+
+/// A picture the picture being decoded is predicted from.
+#[derive(Debug, Clone)]
+pub struct VideoReference<'a, T: DynTexture + ?Sized> {
+    pub picture: VideoPicture<'a, T>,
+    /// What the picture was decoded as.
+    pub info: wgt::VideoReferenceInfo,
+}
+
+/// Describes a call to [`Queue::decode_video`].
+#[derive(Debug)]
+pub struct VideoDecodeDescriptor<'a, T: DynTexture + ?Sized> {
+    /// The compressed data of the picture.
+    pub bitstream: &'a [u8],
+    /// The picture to decode into.
+    pub target: VideoPicture<'a, T>,
+    /// What the target is decoded as, if later pictures can be predicted from it.
+    pub target_reference: Option<wgt::VideoReferenceInfo>,
+    /// The pictures the picture is predicted from.
+    pub references: &'a [VideoReference<'a, T>],
+    /// The headers of the picture.
+    pub picture: wgt::VideoPictureInfo<'a>,
+}
+
+/// Binds or unbinds the memory of pages of a buffer created with
+/// [`MemoryFlags::SPARSE`].
+#[derive(Debug, Clone)]
+pub struct SparseBufferBind<'a, B: DynBuffer + ?Sized> {
+    pub buffer: &'a B,
+    /// The pages to bind. Both ends are multiples of [`wgt::SPARSE_PAGE_SIZE`].
+    pub range: MemoryRange,
+    pub resident: bool,
+}
+
+/// Binds or unbinds the memory of pages of a texture created with
+/// [`MemoryFlags::SPARSE`].
+///
+/// If `mip_level` is in the mip tail given by
+/// [`wgt::TextureDescriptor::sparse_mip_tail_start`], the whole mip tail of
+/// every array layer of the region is bound.
+#[derive(Debug, Clone)]
+pub struct SparseTextureBind<'a, T: DynTexture + ?Sized> {
+    pub texture: &'a T,
+    pub mip_level: u32,
+    /// The first texel of the region, a multiple of the page dimensions, and
+    /// in `z` its first array layer.
+    pub origin: wgt::Origin3d,
+    /// The size of the region, a multiple of the page dimensions unless it
+    /// ends at the edge of the mip level, and in `depth_or_array_layers` its
+    /// number of array layers.
+    pub size: wgt::Extent3d,
+    pub resident: bool,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct BufferCopy {
     pub src_offset: wgt::BufferAddress,
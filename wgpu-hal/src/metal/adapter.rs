@@ -679,6 +679,12 @@ impl super::PrivateCapabilities {
             format_astc_hdr: family_check && device.supports_family(MTLGPUFamily::Apple6),
             // Apple3 and later supports compressed volume texture formats including ASTC Sliced 3D
             format_astc_3d: family_check && device.supports_family(MTLGPUFamily::Apple3),
+            // Memoryless textures live in tile memory, which only Apple GPUs have
+            supports_memoryless_storage: family_check
+                && device.supports_family(MTLGPUFamily::Apple2),
+            // Programmable blending (`[[color(n)]]` inputs) is only available on Apple GPUs
+            supports_framebuffer_fetch: family_check
+                && device.supports_family(MTLGPUFamily::Apple1),
             format_any8_unorm_srgb_all: Self::supports_any(device, ANY8_UNORM_SRGB_ALL),
             format_any8_unorm_srgb_no_write: !Self::supports_any(device, ANY8_UNORM_SRGB_ALL)
                 && !os_is_mac,
@@ -928,7 +934,8 @@ impl super::PrivateCapabilities {
             | F::DEPTH32FLOAT_STENCIL8
             | F::BGRA8UNORM_STORAGE
             | F::EXPERIMENTAL_PASSTHROUGH_SHADERS
-            | F::EXTERNAL_TEXTURE;
+            | F::EXTERNAL_TEXTURE
+            | F::RENDER_BUNDLE_INHERITANCE;
 
         features.set(F::FLOAT32_FILTERABLE, self.supports_float_filtering);
         features.set(
@@ -999,6 +1006,8 @@ impl super::PrivateCapabilities {
         features.set(F::ADDRESS_MODE_CLAMP_TO_ZERO, true);
 
         features.set(F::RG11B10UFLOAT_RENDERABLE, self.format_rg11b10_all);
+        features.set(F::TRANSIENT_ATTACHMENTS, self.supports_memoryless_storage);
+        features.set(F::FRAMEBUFFER_FETCH, self.supports_framebuffer_fetch);
 
         if self.supports_simd_scoped_operations {
             features.insert(F::SUBGROUP | F::SUBGROUP_BARRIER);
@@ -1095,6 +1104,9 @@ impl super::PrivateCapabilities {
                 // > [Acceleration structures] are opaque objects that can be bound directly using
                 // buffer binding points or via argument buffers
                 max_acceleration_structures_per_shader_stage: 0,
+
+                max_compute_queues: 0,
+                max_transfer_queues: 0,
             },
             alignments: crate::Alignments {
                 buffer_copy_offset: wgt::BufferSize::new(self.buffer_alignment).unwrap(),
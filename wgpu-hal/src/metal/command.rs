@@ -1109,6 +1109,17 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn execute_indirect(
+        &mut self,
+        _layout: &wgt::IndirectCommandLayout,
+        _pipeline_layout: &super::PipelineLayout,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _max_count: u32,
+        _count_buffer: Option<(&super::Buffer, wgt::BufferAddress)>,
+    ) {
+        unreachable!("`Features::INDIRECT_COMMANDS` is not exposed on Metal")
+    }
     unsafe fn draw_mesh_tasks(
         &mut self,
         _group_count_x: u32,
@@ -37,6 +37,11 @@ pub fn map_texture_usage(format: wgt::TextureFormat, usage: wgt::TextureUses) ->
         usage.intersects(Tu::STORAGE_ATOMIC),
     );
 
+    // Memoryless textures can't be read outside of the render pass writing them
+    if usage.contains(Tu::TRANSIENT) {
+        mtl_usage.remove(MTLTextureUsage::ShaderRead);
+    }
+
     mtl_usage
 }
 
@@ -149,6 +149,17 @@ impl super::Device {
         } else {
             naga::proc::BoundsCheckPolicy::Unchecked
         };
+        let mut bounds_check_policies = naga::proc::BoundsCheckPolicies {
+            index: bounds_check_policy,
+            buffer: bounds_check_policy,
+            image_load: bounds_check_policy,
+            // TODO: support bounds checks on binding arrays
+            binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
+        };
+        let bounds_report = naga_shader.bounds_report_options(naga_stage, stage.entry_point);
+        if bounds_report.is_some() {
+            crate::auxil::report_out_of_bounds_accesses(&mut bounds_check_policies);
+        }
 
         let options = naga::back::msl::Options {
             lang_version: match self.shared.private_caps.msl_version {
@@ -170,15 +181,10 @@ impl super::Device {
                 stage.entry_point.to_owned(),
                 ep_resources.clone(),
             )]),
-            bounds_check_policies: naga::proc::BoundsCheckPolicies {
-                index: bounds_check_policy,
-                buffer: bounds_check_policy,
-                image_load: bounds_check_policy,
-                // TODO: support bounds checks on binding arrays
-                binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
-            },
+            bounds_check_policies,
             zero_initialize_workgroup_memory: stage.zero_initialize_workgroup_memory,
             force_loop_bounding: stage.module.bounds_checks.force_loop_bounding,
+            bounds_report,
         };
 
         let pipeline_options = naga::back::msl::PipelineOptions {
@@ -387,6 +393,14 @@ impl crate::Device for super::Device {
         self.counters.buffers.add(1);
     }
 
+    unsafe fn get_buffer_device_address(
+        &self,
+        _buffer: &super::Buffer,
+    ) -> Option<wgt::BufferAddress> {
+        // Indirect commands are not exposed, and don't need addresses.
+        None
+    }
+
     unsafe fn map_buffer(
         &self,
         buffer: &super::Buffer,
@@ -440,7 +454,11 @@ impl crate::Device for super::Device {
             descriptor.set_mipmap_level_count(desc.mip_level_count as u64);
             descriptor.set_pixel_format(mtl_format);
             descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
-            descriptor.set_storage_mode(MTLStorageMode::Private);
+            if desc.usage.contains(wgt::TextureUses::TRANSIENT) {
+                descriptor.set_storage_mode(MTLStorageMode::Memoryless);
+            } else {
+                descriptor.set_storage_mode(MTLStorageMode::Private);
+            }
 
             let raw = self.shared.device.lock().new_texture(&descriptor);
             if raw.as_ptr().is_null() {
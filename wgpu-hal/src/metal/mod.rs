@@ -75,6 +75,7 @@ impl crate::Api for Api {
     type PipelineCache = PipelineCache;
 
     type AccelerationStructure = AccelerationStructure;
+    type VideoDecoder = VideoDecoder;
 }
 
 crate::impl_dyn_resource!(
@@ -99,7 +100,8 @@ crate::impl_dyn_resource!(
     Surface,
     SurfaceTexture,
     Texture,
-    TextureView
+    TextureView,
+    VideoDecoder
 );
 
 pub struct Instance {}
@@ -229,6 +231,8 @@ struct PrivateCapabilities {
     format_astc: bool,
     format_astc_hdr: bool,
     format_astc_3d: bool,
+    supports_memoryless_storage: bool,
+    supports_framebuffer_fetch: bool,
     format_any8_unorm_srgb_all: bool,
     format_any8_unorm_srgb_no_write: bool,
     format_any8_snorm_all: bool,
@@ -1022,3 +1026,8 @@ impl crate::DynPipelineCache for PipelineCache {}
 pub struct AccelerationStructure;
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
+
+#[derive(Debug)]
+pub struct VideoDecoder;
+
+impl crate::DynVideoDecoder for VideoDecoder {}
@@ -57,6 +57,11 @@ impl Buffer {
         Ok(Buffer { storage, size })
     }
 
+    /// Returns the size of the buffer in bytes.
+    pub(super) fn size(&self) -> u64 {
+        self.size as u64
+    }
+
     /// Returns a pointer to the memory owned by this buffer within the given `range`.
     ///
     /// This may be used to create any number of simultaneous pointers;
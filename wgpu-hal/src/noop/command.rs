@@ -211,6 +211,16 @@ impl crate::CommandEncoder for CommandBuffer {
         instance_count: u32,
     ) {
     }
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &Resource,
+        buffer: &Buffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&Buffer, wgt::BufferAddress)>,
+    ) {
+    }
     unsafe fn draw_mesh_tasks(
         &mut self,
         group_count_x: u32,
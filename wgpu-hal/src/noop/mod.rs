@@ -18,6 +18,18 @@ pub use command::CommandBuffer;
 #[derive(Clone, Debug)]
 pub struct Api;
 pub struct Context;
+pub struct Instance {
+    memory_budget: u64,
+}
+pub struct Adapter {
+    memory_budget: u64,
+}
+pub struct Device {
+    /// The simulated budget of the single memory heap.
+    memory_budget: u64,
+    /// The total size of the live buffers, in bytes.
+    memory_usage: AtomicU64,
+}
 #[derive(Debug)]
 pub struct Encoder;
 #[derive(Debug)]
@@ -33,10 +45,10 @@ type DeviceResult<T> = Result<T, crate::DeviceError>;
 impl crate::Api for Api {
     const VARIANT: wgt::Backend = wgt::Backend::Noop;
 
-    type Instance = Context;
+    type Instance = Instance;
     type Surface = Context;
-    type Adapter = Context;
-    type Device = Context;
+    type Adapter = Adapter;
+    type Device = Device;
 
     type Queue = Context;
     type CommandEncoder = CommandBuffer;
@@ -50,6 +62,7 @@ impl crate::Api for Api {
     type QuerySet = Resource;
     type Fence = Fence;
     type AccelerationStructure = Resource;
+    type VideoDecoder = Resource;
     type PipelineCache = Resource;
 
     type BindGroupLayout = Resource;
@@ -60,7 +73,16 @@ impl crate::Api for Api {
     type ComputePipeline = Resource;
 }
 
-crate::impl_dyn_resource!(Buffer, CommandBuffer, Context, Fence, Resource);
+crate::impl_dyn_resource!(
+    Adapter,
+    Buffer,
+    CommandBuffer,
+    Context,
+    Device,
+    Fence,
+    Instance,
+    Resource
+);
 
 impl crate::DynAccelerationStructure for Resource {}
 impl crate::DynBindGroup for Resource {}
@@ -78,6 +100,7 @@ impl crate::DynShaderModule for Resource {}
 impl crate::DynSurfaceTexture for Resource {}
 impl crate::DynTexture for Resource {}
 impl crate::DynTextureView for Resource {}
+impl crate::DynVideoDecoder for Resource {}
 
 impl core::borrow::Borrow<dyn crate::DynTexture> for Resource {
     fn borrow(&self) -> &dyn crate::DynTexture {
@@ -85,14 +108,21 @@ impl core::borrow::Borrow<dyn crate::DynTexture> for Resource {
     }
 }
 
-impl crate::Instance for Context {
+/// The memory budget reported when [`wgt::NoopBackendOptions::memory_budget`] is `None`.
+const DEFAULT_MEMORY_BUDGET: u64 = 1 << 30;
+
+impl crate::Instance for Instance {
     type A = Api;
 
     unsafe fn init(desc: &crate::InstanceDescriptor) -> Result<Self, crate::InstanceError> {
         let crate::InstanceDescriptor {
             backend_options:
                 wgt::BackendOptions {
-                    noop: wgt::NoopBackendOptions { enable },
+                    noop:
+                        wgt::NoopBackendOptions {
+                            enable,
+                            memory_budget,
+                        },
                     ..
                 },
             name: _,
@@ -100,7 +130,9 @@ impl crate::Instance for Context {
             memory_budget_thresholds: _,
         } = *desc;
         if enable {
-            Ok(Context)
+            Ok(Instance {
+                memory_budget: memory_budget.unwrap_or(DEFAULT_MEMORY_BUDGET),
+            })
         } else {
             Err(crate::InstanceError::new(String::from(
                 "noop backend disabled because NoopBackendOptions::enable is false",
@@ -119,7 +151,9 @@ impl crate::Instance for Context {
         _surface_hint: Option<&Context>,
     ) -> Vec<crate::ExposedAdapter<Api>> {
         vec![crate::ExposedAdapter {
-            adapter: Context,
+            adapter: Adapter {
+                memory_budget: self.memory_budget,
+            },
             info: adapter_info(),
             features: wgt::Features::all(),
             capabilities: CAPABILITIES,
@@ -201,6 +235,9 @@ pub const CAPABILITIES: crate::Capabilities = {
             max_blas_geometry_count: ALLOC_MAX_U32,
             max_tlas_instance_count: ALLOC_MAX_U32,
             max_acceleration_structures_per_shader_stage: ALLOC_MAX_U32,
+
+            max_compute_queues: ALLOC_MAX_U32,
+            max_transfer_queues: ALLOC_MAX_U32,
         },
         alignments: crate::Alignments {
             // All maximally permissive
@@ -223,13 +260,13 @@ impl crate::Surface for Context {
 
     unsafe fn configure(
         &self,
-        device: &Context,
+        device: &Device,
         config: &crate::SurfaceConfiguration,
     ) -> Result<(), crate::SurfaceError> {
         Ok(())
     }
 
-    unsafe fn unconfigure(&self, device: &Context) {}
+    unsafe fn unconfigure(&self, device: &Device) {}
 
     unsafe fn acquire_texture(
         &self,
@@ -241,7 +278,7 @@ impl crate::Surface for Context {
     unsafe fn discard_texture(&self, texture: Resource) {}
 }
 
-impl crate::Adapter for Context {
+impl crate::Adapter for Adapter {
     type A = Api;
 
     unsafe fn open(
@@ -251,7 +288,10 @@ impl crate::Adapter for Context {
         _memory_hints: &wgt::MemoryHints,
     ) -> DeviceResult<crate::OpenDevice<Api>> {
         Ok(crate::OpenDevice {
-            device: Context,
+            device: Device {
+                memory_budget: self.memory_budget,
+                memory_usage: AtomicU64::new(0),
+            },
             queue: Context,
         })
     }
@@ -302,17 +342,49 @@ impl crate::Queue for Context {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn wait_for_fence(&self, fence: &Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        // Submissions execute synchronously, so the awaited one already finished.
+        debug_assert!(fence.value.load(Ordering::Acquire) >= value);
+        Ok(())
+    }
+
+    unsafe fn decode_video(
+        &self,
+        decoder: &Resource,
+        desc: &crate::VideoDecodeDescriptor<Resource>,
+        wait_fence: (&Fence, crate::FenceValue),
+    ) -> DeviceResult<()> {
+        Ok(())
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_, Buffer>],
+        texture_binds: &[crate::SparseTextureBind<'_, Resource>],
+    ) -> DeviceResult<()> {
+        Ok(())
+    }
 }
 
-impl crate::Device for Context {
+impl crate::Device for Device {
     type A = Api;
 
     unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
-        Buffer::new(desc)
+        let buffer = Buffer::new(desc)?;
+        self.memory_usage
+            .fetch_add(buffer.size(), Ordering::Relaxed);
+        Ok(buffer)
     }
 
-    unsafe fn destroy_buffer(&self, buffer: Buffer) {}
+    unsafe fn destroy_buffer(&self, buffer: Buffer) {
+        self.memory_usage
+            .fetch_sub(buffer.size(), Ordering::Relaxed);
+    }
     unsafe fn add_raw_buffer(&self, _buffer: &Buffer) {}
+    unsafe fn get_buffer_device_address(&self, _buffer: &Buffer) -> Option<wgt::BufferAddress> {
+        Some(0)
+    }
 
     unsafe fn map_buffer(
         &self,
@@ -469,10 +541,65 @@ impl crate::Device for Context {
         vec![]
     }
 
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        match (codec, format) {
+            (_, wgt::TextureFormat::NV12)
+            | (wgt::VideoCodec::H265 | wgt::VideoCodec::Av1, wgt::TextureFormat::P010) => {
+                Some(wgt::VideoDecodeCapabilities {
+                    min_coded_extent: wgt::Extent3d {
+                        width: 16,
+                        height: 16,
+                        depth_or_array_layers: 1,
+                    },
+                    max_coded_extent: wgt::Extent3d {
+                        width: 8192,
+                        height: 8192,
+                        depth_or_array_layers: 1,
+                    },
+                    max_pictures: 32,
+                    max_reference_pictures: 16,
+                })
+            }
+            _ => None,
+        }
+    }
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &crate::VideoDecoderDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_video_decoder(&self, decoder: Resource) {}
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &Resource,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+
+    unsafe fn create_queue(&self, queue_type: wgt::QueueType, index: u32) -> DeviceResult<Context> {
+        Ok(Context)
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters {
         Default::default()
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        Some(wgt::MemoryBudgetReport {
+            heaps: vec![wgt::MemoryHeapBudget {
+                device_local: true,
+                budget: self.memory_budget,
+                usage: self.memory_usage.load(Ordering::Relaxed),
+            }],
+        })
+    }
+
     fn check_if_oom(&self) -> DeviceResult<()> {
         Ok(())
     }
@@ -0,0 +1,748 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, ops::Range};
+
+use crate::{
+    AccelerationStructureBarrier, Api, BufferBarrier, BufferBinding, BufferCopy, BufferTextureCopy,
+    BuildAccelerationStructureDescriptor, ComputePassDescriptor, DeviceError, Label, MemoryRange,
+    Rect, RenderPassDescriptor, TextureBarrier, TextureCopy,
+};
+
+use super::{args, CallLog, ObjectId, ObjectKind, Recorded, Recorder, Texture};
+
+/// A command encoder of the backend `A`, recording the calls made to it.
+pub struct CommandEncoder<A: Api> {
+    pub(super) id: ObjectId,
+    pub(super) inner: A::CommandEncoder,
+    pub(super) log: CallLog,
+}
+
+impl<A: Api> CommandEncoder<A> {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the command encoder of the wrapped backend.
+    pub fn inner(&self) -> &A::CommandEncoder {
+        &self.inner
+    }
+
+    fn record(&self, method: &'static str, args: Vec<(&'static str, String)>) {
+        self.log.record(self.id, method, args, None);
+    }
+}
+
+impl<A: Api> fmt::Debug for CommandEncoder<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl<A: Api> crate::CommandEncoder for CommandEncoder<A> {
+    type A = Recorder<A>;
+
+    unsafe fn begin_encoding(&mut self, label: Label) -> Result<(), DeviceError> {
+        self.record("begin_encoding", args!(label));
+        unsafe { self.inner.begin_encoding(label) }
+    }
+
+    unsafe fn discard_encoding(&mut self) {
+        self.record("discard_encoding", args!());
+        unsafe { self.inner.discard_encoding() }
+    }
+
+    unsafe fn end_encoding(&mut self) -> Result<Recorded<A::CommandBuffer>, DeviceError> {
+        let inner = unsafe { self.inner.end_encoding() };
+        self.log.record_creation(
+            self.id,
+            "end_encoding",
+            args!(),
+            ObjectKind::CommandBuffer,
+            inner,
+        )
+    }
+
+    unsafe fn reset_all<I>(&mut self, command_buffers: I)
+    where
+        I: Iterator<Item = Recorded<A::CommandBuffer>>,
+    {
+        let command_buffers = command_buffers.collect::<Vec<_>>();
+        self.record("reset_all", args!(command_buffers));
+        unsafe {
+            self.inner
+                .reset_all(command_buffers.into_iter().map(|cb| cb.inner))
+        }
+    }
+
+    unsafe fn transition_buffers<'a, T>(&mut self, barriers: T)
+    where
+        T: Iterator<Item = BufferBarrier<'a, Recorded<A::Buffer>>>,
+    {
+        let barriers = barriers.collect::<Vec<_>>();
+        self.record("transition_buffers", args!(barriers));
+        unsafe {
+            self.inner
+                .transition_buffers(barriers.iter().map(|barrier| BufferBarrier {
+                    buffer: &barrier.buffer.inner,
+                    usage: barrier.usage.clone(),
+                }))
+        }
+    }
+
+    unsafe fn transition_textures<'a, T>(&mut self, barriers: T)
+    where
+        T: Iterator<Item = TextureBarrier<'a, Texture<A>>>,
+    {
+        let barriers = barriers.collect::<Vec<_>>();
+        self.record("transition_textures", args!(barriers));
+        unsafe {
+            self.inner
+                .transition_textures(barriers.iter().map(|barrier| TextureBarrier {
+                    texture: barrier.texture.inner(),
+                    range: barrier.range,
+                    usage: barrier.usage.clone(),
+                }))
+        }
+    }
+
+    unsafe fn release_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = BufferBarrier<'a, Recorded<A::Buffer>>>,
+        U: Iterator<Item = TextureBarrier<'a, Texture<A>>>,
+    {
+        let buffers = buffers.collect::<Vec<_>>();
+        let textures = textures.collect::<Vec<_>>();
+        self.record("release_resources", args!(queue_type, buffers, textures));
+        unsafe {
+            self.inner.release_resources(
+                queue_type,
+                buffers.iter().map(|barrier| BufferBarrier {
+                    buffer: &barrier.buffer.inner,
+                    usage: barrier.usage.clone(),
+                }),
+                textures.iter().map(|barrier| TextureBarrier {
+                    texture: barrier.texture.inner(),
+                    range: barrier.range,
+                    usage: barrier.usage.clone(),
+                }),
+            )
+        }
+    }
+
+    unsafe fn acquire_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = BufferBarrier<'a, Recorded<A::Buffer>>>,
+        U: Iterator<Item = TextureBarrier<'a, Texture<A>>>,
+    {
+        let buffers = buffers.collect::<Vec<_>>();
+        let textures = textures.collect::<Vec<_>>();
+        self.record("acquire_resources", args!(queue_type, buffers, textures));
+        unsafe {
+            self.inner.acquire_resources(
+                queue_type,
+                buffers.iter().map(|barrier| BufferBarrier {
+                    buffer: &barrier.buffer.inner,
+                    usage: barrier.usage.clone(),
+                }),
+                textures.iter().map(|barrier| TextureBarrier {
+                    texture: barrier.texture.inner(),
+                    range: barrier.range,
+                    usage: barrier.usage.clone(),
+                }),
+            )
+        }
+    }
+
+    unsafe fn clear_buffer(&mut self, buffer: &Recorded<A::Buffer>, range: MemoryRange) {
+        self.record("clear_buffer", args!(buffer, range));
+        unsafe { self.inner.clear_buffer(&buffer.inner, range) }
+    }
+
+    unsafe fn copy_buffer_to_buffer<T>(
+        &mut self,
+        src: &Recorded<A::Buffer>,
+        dst: &Recorded<A::Buffer>,
+        regions: T,
+    ) where
+        T: Iterator<Item = BufferCopy>,
+    {
+        let regions = regions.collect::<Vec<_>>();
+        self.record("copy_buffer_to_buffer", args!(src, dst, regions));
+        unsafe {
+            self.inner
+                .copy_buffer_to_buffer(&src.inner, &dst.inner, regions.into_iter())
+        }
+    }
+
+    #[cfg(webgl)]
+    unsafe fn copy_external_image_to_texture<T>(
+        &mut self,
+        src: &wgt::CopyExternalImageSourceInfo,
+        dst: &Texture<A>,
+        dst_premultiplication: bool,
+        regions: T,
+    ) where
+        T: Iterator<Item = TextureCopy>,
+    {
+        let regions = regions.collect::<Vec<_>>();
+        self.record(
+            "copy_external_image_to_texture",
+            args!(dst, dst_premultiplication, regions),
+        );
+        unsafe {
+            self.inner.copy_external_image_to_texture(
+                src,
+                dst.inner(),
+                dst_premultiplication,
+                regions.into_iter(),
+            )
+        }
+    }
+
+    unsafe fn copy_texture_to_texture<T>(
+        &mut self,
+        src: &Texture<A>,
+        src_usage: wgt::TextureUses,
+        dst: &Texture<A>,
+        regions: T,
+    ) where
+        T: Iterator<Item = TextureCopy>,
+    {
+        let regions = regions.collect::<Vec<_>>();
+        self.record(
+            "copy_texture_to_texture",
+            args!(src, src_usage, dst, regions),
+        );
+        unsafe {
+            self.inner.copy_texture_to_texture(
+                src.inner(),
+                src_usage,
+                dst.inner(),
+                regions.into_iter(),
+            )
+        }
+    }
+
+    unsafe fn copy_buffer_to_texture<T>(
+        &mut self,
+        src: &Recorded<A::Buffer>,
+        dst: &Texture<A>,
+        regions: T,
+    ) where
+        T: Iterator<Item = BufferTextureCopy>,
+    {
+        let regions = regions.collect::<Vec<_>>();
+        self.record("copy_buffer_to_texture", args!(src, dst, regions));
+        unsafe {
+            self.inner
+                .copy_buffer_to_texture(&src.inner, dst.inner(), regions.into_iter())
+        }
+    }
+
+    unsafe fn copy_texture_to_buffer<T>(
+        &mut self,
+        src: &Texture<A>,
+        src_usage: wgt::TextureUses,
+        dst: &Recorded<A::Buffer>,
+        regions: T,
+    ) where
+        T: Iterator<Item = BufferTextureCopy>,
+    {
+        let regions = regions.collect::<Vec<_>>();
+        self.record(
+            "copy_texture_to_buffer",
+            args!(src, src_usage, dst, regions),
+        );
+        unsafe {
+            self.inner.copy_texture_to_buffer(
+                src.inner(),
+                src_usage,
+                &dst.inner,
+                regions.into_iter(),
+            )
+        }
+    }
+
+    unsafe fn copy_acceleration_structure_to_acceleration_structure(
+        &mut self,
+        src: &Recorded<A::AccelerationStructure>,
+        dst: &Recorded<A::AccelerationStructure>,
+        copy: wgt::AccelerationStructureCopy,
+    ) {
+        self.record(
+            "copy_acceleration_structure_to_acceleration_structure",
+            args!(src, dst, copy),
+        );
+        unsafe {
+            self.inner
+                .copy_acceleration_structure_to_acceleration_structure(&src.inner, &dst.inner, copy)
+        }
+    }
+
+    unsafe fn set_bind_group(
+        &mut self,
+        layout: &Recorded<A::PipelineLayout>,
+        index: u32,
+        group: &Recorded<A::BindGroup>,
+        dynamic_offsets: &[wgt::DynamicOffset],
+    ) {
+        self.record(
+            "set_bind_group",
+            args!(layout, index, group, dynamic_offsets),
+        );
+        unsafe {
+            self.inner
+                .set_bind_group(&layout.inner, index, &group.inner, dynamic_offsets)
+        }
+    }
+
+    unsafe fn set_push_constants(
+        &mut self,
+        layout: &Recorded<A::PipelineLayout>,
+        stages: wgt::ShaderStages,
+        offset_bytes: u32,
+        data: &[u32],
+    ) {
+        self.record(
+            "set_push_constants",
+            args!(layout, stages, offset_bytes, data),
+        );
+        unsafe {
+            self.inner
+                .set_push_constants(&layout.inner, stages, offset_bytes, data)
+        }
+    }
+
+    unsafe fn insert_debug_marker(&mut self, label: &str) {
+        self.record("insert_debug_marker", args!(label));
+        unsafe { self.inner.insert_debug_marker(label) }
+    }
+
+    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+        self.record("begin_debug_marker", args!(group_label));
+        unsafe { self.inner.begin_debug_marker(group_label) }
+    }
+
+    unsafe fn end_debug_marker(&mut self) {
+        self.record("end_debug_marker", args!());
+        unsafe { self.inner.end_debug_marker() }
+    }
+
+    unsafe fn begin_query(&mut self, set: &Recorded<A::QuerySet>, index: u32) {
+        self.record("begin_query", args!(set, index));
+        unsafe { self.inner.begin_query(&set.inner, index) }
+    }
+
+    unsafe fn end_query(&mut self, set: &Recorded<A::QuerySet>, index: u32) {
+        self.record("end_query", args!(set, index));
+        unsafe { self.inner.end_query(&set.inner, index) }
+    }
+
+    unsafe fn write_timestamp(&mut self, set: &Recorded<A::QuerySet>, index: u32) {
+        self.record("write_timestamp", args!(set, index));
+        unsafe { self.inner.write_timestamp(&set.inner, index) }
+    }
+
+    unsafe fn reset_queries(&mut self, set: &Recorded<A::QuerySet>, range: Range<u32>) {
+        self.record("reset_queries", args!(set, range));
+        unsafe { self.inner.reset_queries(&set.inner, range) }
+    }
+
+    unsafe fn copy_query_results(
+        &mut self,
+        set: &Recorded<A::QuerySet>,
+        range: Range<u32>,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        stride: wgt::BufferSize,
+    ) {
+        self.record(
+            "copy_query_results",
+            args!(set, range, buffer, offset, stride),
+        );
+        unsafe {
+            self.inner
+                .copy_query_results(&set.inner, range, &buffer.inner, offset, stride)
+        }
+    }
+
+    unsafe fn begin_render_pass(
+        &mut self,
+        desc: &RenderPassDescriptor<Recorded<A::QuerySet>, Recorded<A::TextureView>>,
+    ) -> Result<(), DeviceError> {
+        self.record("begin_render_pass", args!(desc));
+        let color_attachments = desc
+            .color_attachments
+            .iter()
+            .map(|attachment| attachment.as_ref().map(|at| at.to_inner()))
+            .collect::<Vec<_>>();
+        unsafe {
+            self.inner.begin_render_pass(&RenderPassDescriptor {
+                label: desc.label,
+                extent: desc.extent,
+                sample_count: desc.sample_count,
+                color_attachments: &color_attachments,
+                depth_stencil_attachment: desc
+                    .depth_stencil_attachment
+                    .as_ref()
+                    .map(|ds| ds.to_inner()),
+                multiview: desc.multiview,
+                timestamp_writes: desc.timestamp_writes.as_ref().map(|tw| tw.to_inner()),
+                occlusion_query_set: desc.occlusion_query_set.map(|set| &set.inner),
+            })
+        }
+    }
+
+    unsafe fn end_render_pass(&mut self) {
+        self.record("end_render_pass", args!());
+        unsafe { self.inner.end_render_pass() }
+    }
+
+    unsafe fn set_render_pipeline(&mut self, pipeline: &Recorded<A::RenderPipeline>) {
+        self.record("set_render_pipeline", args!(pipeline));
+        unsafe { self.inner.set_render_pipeline(&pipeline.inner) }
+    }
+
+    unsafe fn set_index_buffer<'a>(
+        &mut self,
+        binding: BufferBinding<'a, Recorded<A::Buffer>>,
+        format: wgt::IndexFormat,
+    ) {
+        self.record("set_index_buffer", args!(binding, format));
+        unsafe { self.inner.set_index_buffer(binding.to_inner(), format) }
+    }
+
+    unsafe fn set_vertex_buffer<'a>(
+        &mut self,
+        index: u32,
+        binding: BufferBinding<'a, Recorded<A::Buffer>>,
+    ) {
+        self.record("set_vertex_buffer", args!(index, binding));
+        unsafe { self.inner.set_vertex_buffer(index, binding.to_inner()) }
+    }
+
+    unsafe fn set_viewport(&mut self, rect: &Rect<f32>, depth_range: Range<f32>) {
+        self.record("set_viewport", args!(rect, depth_range));
+        unsafe { self.inner.set_viewport(rect, depth_range) }
+    }
+
+    unsafe fn set_scissor_rect(&mut self, rect: &Rect<u32>) {
+        self.record("set_scissor_rect", args!(rect));
+        unsafe { self.inner.set_scissor_rect(rect) }
+    }
+
+    unsafe fn set_stencil_reference(&mut self, value: u32) {
+        self.record("set_stencil_reference", args!(value));
+        unsafe { self.inner.set_stencil_reference(value) }
+    }
+
+    unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
+        self.record("set_blend_constants", args!(color));
+        unsafe { self.inner.set_blend_constants(color) }
+    }
+
+    unsafe fn draw(
+        &mut self,
+        first_vertex: u32,
+        vertex_count: u32,
+        first_instance: u32,
+        instance_count: u32,
+    ) {
+        self.record(
+            "draw",
+            args!(first_vertex, vertex_count, first_instance, instance_count),
+        );
+        unsafe {
+            self.inner
+                .draw(first_vertex, vertex_count, first_instance, instance_count)
+        }
+    }
+
+    unsafe fn draw_indexed(
+        &mut self,
+        first_index: u32,
+        index_count: u32,
+        base_vertex: i32,
+        first_instance: u32,
+        instance_count: u32,
+    ) {
+        self.record(
+            "draw_indexed",
+            args!(
+                first_index,
+                index_count,
+                base_vertex,
+                first_instance,
+                instance_count
+            ),
+        );
+        unsafe {
+            self.inner.draw_indexed(
+                first_index,
+                index_count,
+                base_vertex,
+                first_instance,
+                instance_count,
+            )
+        }
+    }
+
+    unsafe fn draw_indirect(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        self.record("draw_indirect", args!(buffer, offset, draw_count));
+        unsafe { self.inner.draw_indirect(&buffer.inner, offset, draw_count) }
+    }
+
+    unsafe fn draw_indexed_indirect(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        self.record("draw_indexed_indirect", args!(buffer, offset, draw_count));
+        unsafe {
+            self.inner
+                .draw_indexed_indirect(&buffer.inner, offset, draw_count)
+        }
+    }
+
+    unsafe fn draw_indirect_count(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        count_buffer: &Recorded<A::Buffer>,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        self.record(
+            "draw_indirect_count",
+            args!(buffer, offset, count_buffer, count_offset, max_count),
+        );
+        unsafe {
+            self.inner.draw_indirect_count(
+                &buffer.inner,
+                offset,
+                &count_buffer.inner,
+                count_offset,
+                max_count,
+            )
+        }
+    }
+
+    unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        count_buffer: &Recorded<A::Buffer>,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        self.record(
+            "draw_indexed_indirect_count",
+            args!(buffer, offset, count_buffer, count_offset, max_count),
+        );
+        unsafe {
+            self.inner.draw_indexed_indirect_count(
+                &buffer.inner,
+                offset,
+                &count_buffer.inner,
+                count_offset,
+                max_count,
+            )
+        }
+    }
+
+    unsafe fn execute_indirect(
+        &mut self,
+        layout: &wgt::IndirectCommandLayout,
+        pipeline_layout: &Recorded<A::PipelineLayout>,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&Recorded<A::Buffer>, wgt::BufferAddress)>,
+    ) {
+        self.record(
+            "execute_indirect",
+            args!(
+                layout,
+                pipeline_layout,
+                buffer,
+                offset,
+                max_count,
+                count_buffer
+            ),
+        );
+        unsafe {
+            self.inner.execute_indirect(
+                layout,
+                &pipeline_layout.inner,
+                &buffer.inner,
+                offset,
+                max_count,
+                count_buffer.map(|(buffer, offset)| (&buffer.inner, offset)),
+            )
+        }
+    }
+
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        self.record(
+            "draw_mesh_tasks",
+            args!(group_count_x, group_count_y, group_count_z),
+        );
+        unsafe {
+            self.inner
+                .draw_mesh_tasks(group_count_x, group_count_y, group_count_z)
+        }
+    }
+
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        self.record(
+            "draw_mesh_tasks_indirect",
+            args!(buffer, offset, draw_count),
+        );
+        unsafe {
+            self.inner
+                .draw_mesh_tasks_indirect(&buffer.inner, offset, draw_count)
+        }
+    }
+
+    unsafe fn draw_mesh_tasks_indirect_count(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+        count_buffer: &Recorded<A::Buffer>,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        self.record(
+            "draw_mesh_tasks_indirect_count",
+            args!(buffer, offset, count_buffer, count_offset, max_count),
+        );
+        unsafe {
+            self.inner.draw_mesh_tasks_indirect_count(
+                &buffer.inner,
+                offset,
+                &count_buffer.inner,
+                count_offset,
+                max_count,
+            )
+        }
+    }
+
+    unsafe fn begin_compute_pass(&mut self, desc: &ComputePassDescriptor<Recorded<A::QuerySet>>) {
+        self.record("begin_compute_pass", args!(desc));
+        unsafe {
+            self.inner.begin_compute_pass(&ComputePassDescriptor {
+                label: desc.label,
+                timestamp_writes: desc.timestamp_writes.as_ref().map(|tw| tw.to_inner()),
+            })
+        }
+    }
+
+    unsafe fn end_compute_pass(&mut self) {
+        self.record("end_compute_pass", args!());
+        unsafe { self.inner.end_compute_pass() }
+    }
+
+    unsafe fn set_compute_pipeline(&mut self, pipeline: &Recorded<A::ComputePipeline>) {
+        self.record("set_compute_pipeline", args!(pipeline));
+        unsafe { self.inner.set_compute_pipeline(&pipeline.inner) }
+    }
+
+    unsafe fn dispatch(&mut self, count: [u32; 3]) {
+        self.record("dispatch", args!(count));
+        unsafe { self.inner.dispatch(count) }
+    }
+
+    unsafe fn dispatch_indirect(
+        &mut self,
+        buffer: &Recorded<A::Buffer>,
+        offset: wgt::BufferAddress,
+    ) {
+        self.record("dispatch_indirect", args!(buffer, offset));
+        unsafe { self.inner.dispatch_indirect(&buffer.inner, offset) }
+    }
+
+    unsafe fn build_acceleration_structures<'a, T>(&mut self, descriptor_count: u32, descriptors: T)
+    where
+        Recorder<A>: 'a,
+        T: IntoIterator<
+            Item = BuildAccelerationStructureDescriptor<
+                'a,
+                Recorded<A::Buffer>,
+                Recorded<A::AccelerationStructure>,
+            >,
+        >,
+    {
+        let descriptors = descriptors.into_iter().collect::<Vec<_>>();
+        self.record(
+            "build_acceleration_structures",
+            args!(descriptor_count, descriptors),
+        );
+        // The entries are collected first so that the descriptors can reference them.
+        let descriptor_entries = descriptors
+            .iter()
+            .map(|d| d.entries.to_inner())
+            .collect::<Vec<_>>();
+        let descriptors = descriptors
+            .iter()
+            .zip(descriptor_entries.iter())
+            .map(|(d, entries)| BuildAccelerationStructureDescriptor {
+                entries,
+                mode: d.mode,
+                flags: d.flags,
+                source_acceleration_structure: d.source_acceleration_structure.map(|a| &a.inner),
+                destination_acceleration_structure: &d.destination_acceleration_structure.inner,
+                scratch_buffer: &d.scratch_buffer.inner,
+                scratch_buffer_offset: d.scratch_buffer_offset,
+            });
+        unsafe {
+            self.inner
+                .build_acceleration_structures(descriptor_count, descriptors)
+        }
+    }
+
+    unsafe fn place_acceleration_structure_barrier(
+        &mut self,
+        barrier: AccelerationStructureBarrier,
+    ) {
+        self.record("place_acceleration_structure_barrier", args!(barrier));
+        unsafe { self.inner.place_acceleration_structure_barrier(barrier) }
+    }
+
+    unsafe fn read_acceleration_structure_compact_size(
+        &mut self,
+        acceleration_structure: &Recorded<A::AccelerationStructure>,
+        buf: &Recorded<A::Buffer>,
+    ) {
+        self.record(
+            "read_acceleration_structure_compact_size",
+            args!(acceleration_structure, buf),
+        );
+        unsafe {
+            self.inner
+                .read_acceleration_structure_compact_size(&acceleration_structure.inner, &buf.inner)
+        }
+    }
+}
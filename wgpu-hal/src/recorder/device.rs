@@ -0,0 +1,663 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    AccelerationStructureBuildSizes, AccelerationStructureDescriptor, Api, BindGroupDescriptor,
+    BindGroupLayoutDescriptor, BufferDescriptor, BufferMapping, CommandEncoderDescriptor,
+    ComputePipelineDescriptor, DeviceError, FenceValue,
+    GetAccelerationStructureBuildSizesDescriptor, Label, MemoryRange, PipelineCacheDescriptor,
+    PipelineCacheError, PipelineError, PipelineLayoutDescriptor, RenderPipelineDescriptor,
+    SamplerDescriptor, ShaderError, ShaderInput, ShaderModuleDescriptor, TextureDescriptor,
+    TextureViewDescriptor, TlasInstance, VertexProcessor, VideoDecoderDescriptor,
+};
+
+use super::{
+    args, CallLog, CommandEncoder, ObjectId, ObjectKind, Queue, Recorded, Recorder, Texture,
+    TextureInner,
+};
+
+/// A device of the backend `A`, recording the calls made to it.
+pub struct Device<A: Api> {
+    pub(super) id: ObjectId,
+    pub(super) inner: A::Device,
+    pub(super) log: CallLog,
+}
+
+impl<A: Api> Device<A> {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the device of the wrapped backend.
+    pub fn inner(&self) -> &A::Device {
+        &self.inner
+    }
+
+    fn record(&self, method: &'static str, args: Vec<(&'static str, String)>) {
+        self.log.record(self.id, method, args, None);
+    }
+
+    fn record_creation<T, E>(
+        &self,
+        method: &'static str,
+        args: Vec<(&'static str, String)>,
+        kind: ObjectKind,
+        inner: Result<T, E>,
+    ) -> Result<Recorded<T>, E> {
+        self.log.record_creation(self.id, method, args, kind, inner)
+    }
+}
+
+impl<A: Api> fmt::Debug for Device<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+fn shader_input_kind(shader: &ShaderInput) -> &'static str {
+    match *shader {
+        ShaderInput::Naga(_) => "Naga",
+        ShaderInput::Msl { .. } => "Msl",
+        ShaderInput::SpirV(_) => "SpirV",
+        ShaderInput::Dxil { .. } => "Dxil",
+        ShaderInput::Hlsl { .. } => "Hlsl",
+        ShaderInput::Glsl { .. } => "Glsl",
+    }
+}
+
+impl<A: Api> crate::Device for Device<A> {
+    type A = Recorder<A>;
+
+    unsafe fn create_buffer(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<Recorded<A::Buffer>, DeviceError> {
+        let inner = unsafe { self.inner.create_buffer(desc) };
+        self.record_creation("create_buffer", args!(desc), ObjectKind::Buffer, inner)
+    }
+
+    unsafe fn destroy_buffer(&self, buffer: Recorded<A::Buffer>) {
+        self.record("destroy_buffer", args!(buffer));
+        unsafe { self.inner.destroy_buffer(buffer.inner) }
+    }
+
+    unsafe fn add_raw_buffer(&self, buffer: &Recorded<A::Buffer>) {
+        self.record("add_raw_buffer", args!(buffer));
+        unsafe { self.inner.add_raw_buffer(&buffer.inner) }
+    }
+
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &Recorded<A::Buffer>,
+    ) -> Option<wgt::BufferAddress> {
+        self.record("get_buffer_device_address", args!(buffer));
+        unsafe { self.inner.get_buffer_device_address(&buffer.inner) }
+    }
+
+    unsafe fn map_buffer(
+        &self,
+        buffer: &Recorded<A::Buffer>,
+        range: MemoryRange,
+    ) -> Result<BufferMapping, DeviceError> {
+        self.record("map_buffer", args!(buffer, range));
+        unsafe { self.inner.map_buffer(&buffer.inner, range) }
+    }
+
+    unsafe fn unmap_buffer(&self, buffer: &Recorded<A::Buffer>) {
+        self.record("unmap_buffer", args!(buffer));
+        unsafe { self.inner.unmap_buffer(&buffer.inner) }
+    }
+
+    unsafe fn flush_mapped_ranges<I>(&self, buffer: &Recorded<A::Buffer>, ranges: I)
+    where
+        I: Iterator<Item = MemoryRange>,
+    {
+        let ranges = ranges.collect::<Vec<_>>();
+        self.record("flush_mapped_ranges", args!(buffer, ranges));
+        unsafe {
+            self.inner
+                .flush_mapped_ranges(&buffer.inner, ranges.into_iter())
+        }
+    }
+
+    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Recorded<A::Buffer>, ranges: I)
+    where
+        I: Iterator<Item = MemoryRange>,
+    {
+        let ranges = ranges.collect::<Vec<_>>();
+        self.record("invalidate_mapped_ranges", args!(buffer, ranges));
+        unsafe {
+            self.inner
+                .invalidate_mapped_ranges(&buffer.inner, ranges.into_iter())
+        }
+    }
+
+    unsafe fn create_texture(&self, desc: &TextureDescriptor) -> Result<Texture<A>, DeviceError> {
+        let inner = unsafe { self.inner.create_texture(desc) };
+        let texture = inner.map(|inner| Texture {
+            id: self.log.next_id(ObjectKind::Texture),
+            inner: TextureInner::Texture(inner),
+        });
+        self.log.record(
+            self.id,
+            "create_texture",
+            args!(desc),
+            texture.as_ref().ok().map(Texture::id),
+        );
+        texture
+    }
+
+    unsafe fn destroy_texture(&self, texture: Texture<A>) {
+        self.record("destroy_texture", args!(texture));
+        unsafe { self.inner.destroy_texture(texture.into_texture()) }
+    }
+
+    unsafe fn add_raw_texture(&self, texture: &Texture<A>) {
+        self.record("add_raw_texture", args!(texture));
+        unsafe { self.inner.add_raw_texture(texture.inner()) }
+    }
+
+    unsafe fn create_texture_view(
+        &self,
+        texture: &Texture<A>,
+        desc: &TextureViewDescriptor,
+    ) -> Result<Recorded<A::TextureView>, DeviceError> {
+        let inner = unsafe { self.inner.create_texture_view(texture.inner(), desc) };
+        self.record_creation(
+            "create_texture_view",
+            args!(texture, desc),
+            ObjectKind::TextureView,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_texture_view(&self, view: Recorded<A::TextureView>) {
+        self.record("destroy_texture_view", args!(view));
+        unsafe { self.inner.destroy_texture_view(view.inner) }
+    }
+
+    unsafe fn create_sampler(
+        &self,
+        desc: &SamplerDescriptor,
+    ) -> Result<Recorded<A::Sampler>, DeviceError> {
+        let inner = unsafe { self.inner.create_sampler(desc) };
+        self.record_creation("create_sampler", args!(desc), ObjectKind::Sampler, inner)
+    }
+
+    unsafe fn destroy_sampler(&self, sampler: Recorded<A::Sampler>) {
+        self.record("destroy_sampler", args!(sampler));
+        unsafe { self.inner.destroy_sampler(sampler.inner) }
+    }
+
+    unsafe fn create_command_encoder(
+        &self,
+        desc: &CommandEncoderDescriptor<Queue<A>>,
+    ) -> Result<CommandEncoder<A>, DeviceError> {
+        let inner = unsafe {
+            self.inner
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: desc.label,
+                    queue: &desc.queue.inner,
+                    reusable: desc.reusable,
+                })
+        };
+        let encoder = inner.map(|inner| CommandEncoder {
+            id: self.log.next_id(ObjectKind::CommandEncoder),
+            inner,
+            log: self.log.clone(),
+        });
+        self.log.record(
+            self.id,
+            "create_command_encoder",
+            args!(desc),
+            encoder.as_ref().ok().map(CommandEncoder::id),
+        );
+        encoder
+    }
+
+    unsafe fn create_bind_group_layout(
+        &self,
+        desc: &BindGroupLayoutDescriptor,
+    ) -> Result<Recorded<A::BindGroupLayout>, DeviceError> {
+        let inner = unsafe { self.inner.create_bind_group_layout(desc) };
+        self.record_creation(
+            "create_bind_group_layout",
+            args!(desc),
+            ObjectKind::BindGroupLayout,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_bind_group_layout(&self, bg_layout: Recorded<A::BindGroupLayout>) {
+        self.record("destroy_bind_group_layout", args!(bg_layout));
+        unsafe { self.inner.destroy_bind_group_layout(bg_layout.inner) }
+    }
+
+    unsafe fn create_pipeline_layout(
+        &self,
+        desc: &PipelineLayoutDescriptor<Recorded<A::BindGroupLayout>>,
+    ) -> Result<Recorded<A::PipelineLayout>, DeviceError> {
+        let bind_group_layouts = desc
+            .bind_group_layouts
+            .iter()
+            .map(|bgl| &bgl.inner)
+            .collect::<Vec<_>>();
+        let inner = unsafe {
+            self.inner
+                .create_pipeline_layout(&PipelineLayoutDescriptor {
+                    label: desc.label,
+                    flags: desc.flags,
+                    bind_group_layouts: &bind_group_layouts,
+                    push_constant_ranges: desc.push_constant_ranges,
+                })
+        };
+        self.record_creation(
+            "create_pipeline_layout",
+            args!(desc),
+            ObjectKind::PipelineLayout,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_pipeline_layout(&self, pipeline_layout: Recorded<A::PipelineLayout>) {
+        self.record("destroy_pipeline_layout", args!(pipeline_layout));
+        unsafe { self.inner.destroy_pipeline_layout(pipeline_layout.inner) }
+    }
+
+    unsafe fn create_bind_group(
+        &self,
+        desc: &BindGroupDescriptor<
+            Recorded<A::BindGroupLayout>,
+            Recorded<A::Buffer>,
+            Recorded<A::Sampler>,
+            Recorded<A::TextureView>,
+            Recorded<A::AccelerationStructure>,
+        >,
+    ) -> Result<Recorded<A::BindGroup>, DeviceError> {
+        let buffers = desc
+            .buffers
+            .iter()
+            .map(|binding| binding.to_inner())
+            .collect::<Vec<_>>();
+        let samplers = desc
+            .samplers
+            .iter()
+            .map(|sampler| &sampler.inner)
+            .collect::<Vec<_>>();
+        let textures = desc
+            .textures
+            .iter()
+            .map(|binding| binding.to_inner())
+            .collect::<Vec<_>>();
+        let acceleration_structures = desc
+            .acceleration_structures
+            .iter()
+            .map(|tlas| &tlas.inner)
+            .collect::<Vec<_>>();
+        let external_textures = desc
+            .external_textures
+            .iter()
+            .map(|binding| binding.to_inner())
+            .collect::<Vec<_>>();
+        let inner = unsafe {
+            self.inner.create_bind_group(&BindGroupDescriptor {
+                label: desc.label,
+                layout: &desc.layout.inner,
+                buffers: &buffers,
+                samplers: &samplers,
+                textures: &textures,
+                entries: desc.entries,
+                acceleration_structures: &acceleration_structures,
+                external_textures: &external_textures,
+            })
+        };
+        self.record_creation(
+            "create_bind_group",
+            args!(desc),
+            ObjectKind::BindGroup,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_bind_group(&self, group: Recorded<A::BindGroup>) {
+        self.record("destroy_bind_group", args!(group));
+        unsafe { self.inner.destroy_bind_group(group.inner) }
+    }
+
+    unsafe fn create_shader_module(
+        &self,
+        desc: &ShaderModuleDescriptor,
+        shader: ShaderInput,
+    ) -> Result<Recorded<A::ShaderModule>, ShaderError> {
+        let args = args!(
+            label = desc.label,
+            runtime_checks = desc.runtime_checks,
+            shader = format_args!("{}", shader_input_kind(&shader)),
+        );
+        let inner = unsafe { self.inner.create_shader_module(desc, shader) };
+        self.record_creation(
+            "create_shader_module",
+            args,
+            ObjectKind::ShaderModule,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_shader_module(&self, module: Recorded<A::ShaderModule>) {
+        self.record("destroy_shader_module", args!(module));
+        unsafe { self.inner.destroy_shader_module(module.inner) }
+    }
+
+    unsafe fn create_render_pipeline(
+        &self,
+        desc: &RenderPipelineDescriptor<
+            Recorded<A::PipelineLayout>,
+            Recorded<A::ShaderModule>,
+            Recorded<A::PipelineCache>,
+        >,
+    ) -> Result<Recorded<A::RenderPipeline>, PipelineError> {
+        let vertex_processor = match desc.vertex_processor {
+            VertexProcessor::Standard {
+                vertex_buffers,
+                ref vertex_stage,
+            } => VertexProcessor::Standard {
+                vertex_buffers,
+                vertex_stage: vertex_stage.to_inner(),
+            },
+            VertexProcessor::Mesh {
+                ref task_stage,
+                ref mesh_stage,
+            } => VertexProcessor::Mesh {
+                task_stage: task_stage.as_ref().map(|stage| stage.to_inner()),
+                mesh_stage: mesh_stage.to_inner(),
+            },
+        };
+        let inner = unsafe {
+            self.inner
+                .create_render_pipeline(&RenderPipelineDescriptor {
+                    label: desc.label,
+                    layout: &desc.layout.inner,
+                    vertex_processor,
+                    primitive: desc.primitive,
+                    depth_stencil: desc.depth_stencil.clone(),
+                    multisample: desc.multisample,
+                    fragment_stage: desc.fragment_stage.as_ref().map(|stage| stage.to_inner()),
+                    color_targets: desc.color_targets,
+                    multiview: desc.multiview,
+                    cache: desc.cache.map(|cache| &cache.inner),
+                })
+        };
+        self.record_creation(
+            "create_render_pipeline",
+            args!(desc),
+            ObjectKind::RenderPipeline,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_render_pipeline(&self, pipeline: Recorded<A::RenderPipeline>) {
+        self.record("destroy_render_pipeline", args!(pipeline));
+        unsafe { self.inner.destroy_render_pipeline(pipeline.inner) }
+    }
+
+    unsafe fn create_compute_pipeline(
+        &self,
+        desc: &ComputePipelineDescriptor<
+            Recorded<A::PipelineLayout>,
+            Recorded<A::ShaderModule>,
+            Recorded<A::PipelineCache>,
+        >,
+    ) -> Result<Recorded<A::ComputePipeline>, PipelineError> {
+        let inner = unsafe {
+            self.inner
+                .create_compute_pipeline(&ComputePipelineDescriptor {
+                    label: desc.label,
+                    layout: &desc.layout.inner,
+                    stage: desc.stage.to_inner(),
+                    cache: desc.cache.map(|cache| &cache.inner),
+                })
+        };
+        self.record_creation(
+            "create_compute_pipeline",
+            args!(desc),
+            ObjectKind::ComputePipeline,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_compute_pipeline(&self, pipeline: Recorded<A::ComputePipeline>) {
+        self.record("destroy_compute_pipeline", args!(pipeline));
+        unsafe { self.inner.destroy_compute_pipeline(pipeline.inner) }
+    }
+
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> Result<Recorded<A::PipelineCache>, PipelineCacheError> {
+        let args = args!(label = desc.label, data_len = desc.data.map(<[u8]>::len),);
+        let inner = unsafe { self.inner.create_pipeline_cache(desc) };
+        self.record_creation(
+            "create_pipeline_cache",
+            args,
+            ObjectKind::PipelineCache,
+            inner,
+        )
+    }
+
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        self.record("pipeline_cache_validation_key", args!());
+        self.inner.pipeline_cache_validation_key()
+    }
+
+    unsafe fn destroy_pipeline_cache(&self, cache: Recorded<A::PipelineCache>) {
+        self.record("destroy_pipeline_cache", args!(cache));
+        unsafe { self.inner.destroy_pipeline_cache(cache.inner) }
+    }
+
+    unsafe fn create_query_set(
+        &self,
+        desc: &wgt::QuerySetDescriptor<Label>,
+    ) -> Result<Recorded<A::QuerySet>, DeviceError> {
+        let inner = unsafe { self.inner.create_query_set(desc) };
+        self.record_creation("create_query_set", args!(desc), ObjectKind::QuerySet, inner)
+    }
+
+    unsafe fn destroy_query_set(&self, set: Recorded<A::QuerySet>) {
+        self.record("destroy_query_set", args!(set));
+        unsafe { self.inner.destroy_query_set(set.inner) }
+    }
+
+    unsafe fn create_fence(&self) -> Result<Recorded<A::Fence>, DeviceError> {
+        let inner = unsafe { self.inner.create_fence() };
+        self.record_creation("create_fence", args!(), ObjectKind::Fence, inner)
+    }
+
+    unsafe fn destroy_fence(&self, fence: Recorded<A::Fence>) {
+        self.record("destroy_fence", args!(fence));
+        unsafe { self.inner.destroy_fence(fence.inner) }
+    }
+
+    unsafe fn get_fence_value(
+        &self,
+        fence: &Recorded<A::Fence>,
+    ) -> Result<FenceValue, DeviceError> {
+        self.record("get_fence_value", args!(fence));
+        unsafe { self.inner.get_fence_value(&fence.inner) }
+    }
+
+    unsafe fn wait(
+        &self,
+        fence: &Recorded<A::Fence>,
+        value: FenceValue,
+        timeout_ms: u32,
+    ) -> Result<bool, DeviceError> {
+        self.record("wait", args!(fence, value, timeout_ms));
+        unsafe { self.inner.wait(&fence.inner, value, timeout_ms) }
+    }
+
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        self.record("start_graphics_debugger_capture", args!());
+        unsafe { self.inner.start_graphics_debugger_capture() }
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        self.record("stop_graphics_debugger_capture", args!());
+        unsafe { self.inner.stop_graphics_debugger_capture() }
+    }
+
+    unsafe fn pipeline_cache_get_data(
+        &self,
+        cache: &Recorded<A::PipelineCache>,
+    ) -> Option<Vec<u8>> {
+        self.record("pipeline_cache_get_data", args!(cache));
+        unsafe { self.inner.pipeline_cache_get_data(&cache.inner) }
+    }
+
+    unsafe fn create_acceleration_structure(
+        &self,
+        desc: &AccelerationStructureDescriptor,
+    ) -> Result<Recorded<A::AccelerationStructure>, DeviceError> {
+        let inner = unsafe { self.inner.create_acceleration_structure(desc) };
+        self.record_creation(
+            "create_acceleration_structure",
+            args!(desc),
+            ObjectKind::AccelerationStructure,
+            inner,
+        )
+    }
+
+    unsafe fn get_acceleration_structure_build_sizes(
+        &self,
+        desc: &GetAccelerationStructureBuildSizesDescriptor<Recorded<A::Buffer>>,
+    ) -> AccelerationStructureBuildSizes {
+        self.record("get_acceleration_structure_build_sizes", args!(desc));
+        let entries = desc.entries.to_inner();
+        unsafe {
+            self.inner.get_acceleration_structure_build_sizes(
+                &GetAccelerationStructureBuildSizesDescriptor {
+                    entries: &entries,
+                    flags: desc.flags,
+                },
+            )
+        }
+    }
+
+    unsafe fn get_acceleration_structure_device_address(
+        &self,
+        acceleration_structure: &Recorded<A::AccelerationStructure>,
+    ) -> wgt::BufferAddress {
+        self.record(
+            "get_acceleration_structure_device_address",
+            args!(acceleration_structure),
+        );
+        unsafe {
+            self.inner
+                .get_acceleration_structure_device_address(&acceleration_structure.inner)
+        }
+    }
+
+    unsafe fn destroy_acceleration_structure(
+        &self,
+        acceleration_structure: Recorded<A::AccelerationStructure>,
+    ) {
+        self.record(
+            "destroy_acceleration_structure",
+            args!(acceleration_structure),
+        );
+        unsafe {
+            self.inner
+                .destroy_acceleration_structure(acceleration_structure.inner)
+        }
+    }
+
+    fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8> {
+        self.record("tlas_instance_to_bytes", args!(instance));
+        self.inner.tlas_instance_to_bytes(instance)
+    }
+
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        self.record("video_decode_capabilities", args!(codec, format));
+        self.inner.video_decode_capabilities(codec, format)
+    }
+
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &VideoDecoderDescriptor,
+    ) -> Result<Recorded<A::VideoDecoder>, DeviceError> {
+        let inner = unsafe { self.inner.create_video_decoder(desc) };
+        self.record_creation(
+            "create_video_decoder",
+            args!(desc),
+            ObjectKind::VideoDecoder,
+            inner,
+        )
+    }
+
+    unsafe fn destroy_video_decoder(&self, decoder: Recorded<A::VideoDecoder>) {
+        self.record("destroy_video_decoder", args!(decoder));
+        unsafe { self.inner.destroy_video_decoder(decoder.inner) }
+    }
+
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &Recorded<A::VideoDecoder>,
+        desc: &TextureDescriptor,
+    ) -> Result<Texture<A>, DeviceError> {
+        let inner = unsafe { self.inner.create_video_picture(&decoder.inner, desc) };
+        let texture = inner.map(|inner| Texture {
+            id: self.log.next_id(ObjectKind::Texture),
+            inner: TextureInner::Texture(inner),
+        });
+        self.log.record(
+            self.id,
+            "create_video_picture",
+            args!(decoder, desc),
+            texture.as_ref().ok().map(Texture::id),
+        );
+        texture
+    }
+
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        index: u32,
+    ) -> Result<Queue<A>, DeviceError> {
+        let inner = unsafe { self.inner.create_queue(queue_type, index) };
+        let queue = inner.map(|inner| Queue {
+            id: self.log.next_id(ObjectKind::Queue),
+            inner,
+            log: self.log.clone(),
+        });
+        self.log.record(
+            self.id,
+            "create_queue",
+            args!(queue_type, index),
+            queue.as_ref().ok().map(Queue::id),
+        );
+        queue
+    }
+
+    fn get_internal_counters(&self) -> wgt::HalCounters {
+        self.record("get_internal_counters", args!());
+        self.inner.get_internal_counters()
+    }
+
+    fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport> {
+        self.record("generate_allocator_report", args!());
+        self.inner.generate_allocator_report()
+    }
+
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        self.record("get_memory_budget", args!());
+        self.inner.get_memory_budget()
+    }
+
+    fn check_if_oom(&self) -> Result<(), DeviceError> {
+        self.record("check_if_oom", args!());
+        self.inner.check_if_oom()
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    AcquiredSurfaceTexture, Api, DeviceError, ExposedAdapter, InstanceError, OpenDevice,
+    SurfaceCapabilities, SurfaceConfiguration, SurfaceError, TextureFormatCapabilities,
+};
+
+use super::{CallLog, Device, ObjectKind, Queue, Recorded, Recorder, Texture, TextureInner};
+
+/// An instance of the backend `A`, whose devices record the calls made to them.
+pub struct Instance<A: Api> {
+    inner: A::Instance,
+    log: CallLog,
+}
+
+impl<A: Api> Instance<A> {
+    /// Wraps an instance of `A`, recording the calls made to its devices into `log`.
+    pub fn new(inner: A::Instance, log: CallLog) -> Self {
+        Self { inner, log }
+    }
+
+    /// Returns the log the calls made to the devices of this instance are recorded into.
+    pub fn call_log(&self) -> &CallLog {
+        &self.log
+    }
+
+    /// Returns the instance of the wrapped backend.
+    pub fn inner(&self) -> &A::Instance {
+        &self.inner
+    }
+}
+
+impl<A: Api> fmt::Debug for Instance<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instance").field("log", &self.log).finish()
+    }
+}
+
+impl<A: Api> crate::Instance for Instance<A> {
+    type A = Recorder<A>;
+
+    unsafe fn init(desc: &crate::InstanceDescriptor) -> Result<Self, InstanceError> {
+        let inner = unsafe { A::Instance::init(desc) }?;
+        Ok(Self::new(inner, CallLog::new()))
+    }
+
+    unsafe fn create_surface(
+        &self,
+        display_handle: raw_window_handle::RawDisplayHandle,
+        window_handle: raw_window_handle::RawWindowHandle,
+    ) -> Result<Surface<A>, InstanceError> {
+        let inner = unsafe { self.inner.create_surface(display_handle, window_handle) }?;
+        Ok(Surface {
+            inner,
+            log: self.log.clone(),
+        })
+    }
+
+    unsafe fn enumerate_adapters(
+        &self,
+        surface_hint: Option<&Surface<A>>,
+    ) -> Vec<ExposedAdapter<Recorder<A>>> {
+        unsafe {
+            self.inner
+                .enumerate_adapters(surface_hint.map(|surface| &surface.inner))
+        }
+        .into_iter()
+        .map(|exposed| ExposedAdapter {
+            adapter: Adapter {
+                inner: exposed.adapter,
+                log: self.log.clone(),
+            },
+            info: exposed.info,
+            features: exposed.features,
+            capabilities: exposed.capabilities,
+        })
+        .collect()
+    }
+}
+
+/// A surface of the backend `A`.
+///
+/// Calls made to surfaces aren't recorded, but the textures acquired from them are named.
+pub struct Surface<A: Api> {
+    pub(super) inner: A::Surface,
+    log: CallLog,
+}
+
+impl<A: Api> Surface<A> {
+    /// Returns the surface of the wrapped backend.
+    pub fn inner(&self) -> &A::Surface {
+        &self.inner
+    }
+}
+
+impl<A: Api> fmt::Debug for Surface<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Surface").finish_non_exhaustive()
+    }
+}
+
+impl<A: Api> crate::Surface for Surface<A> {
+    type A = Recorder<A>;
+
+    unsafe fn configure(
+        &self,
+        device: &Device<A>,
+        config: &SurfaceConfiguration,
+    ) -> Result<(), SurfaceError> {
+        unsafe { self.inner.configure(&device.inner, config) }
+    }
+
+    unsafe fn unconfigure(&self, device: &Device<A>) {
+        unsafe { self.inner.unconfigure(&device.inner) }
+    }
+
+    unsafe fn acquire_texture(
+        &self,
+        timeout: Option<core::time::Duration>,
+        fence: &Recorded<A::Fence>,
+    ) -> Result<Option<AcquiredSurfaceTexture<Recorder<A>>>, SurfaceError> {
+        let acquired = unsafe { self.inner.acquire_texture(timeout, &fence.inner) }?;
+        Ok(acquired.map(|acquired| AcquiredSurfaceTexture {
+            texture: Texture {
+                id: self.log.next_id(ObjectKind::SurfaceTexture),
+                inner: TextureInner::Surface(acquired.texture),
+            },
+            suboptimal: acquired.suboptimal,
+        }))
+    }
+
+    unsafe fn discard_texture(&self, texture: Texture<A>) {
+        unsafe { self.inner.discard_texture(texture.into_surface_texture()) }
+    }
+}
+
+/// An adapter of the backend `A`, whose devices record the calls made to them.
+pub struct Adapter<A: Api> {
+    inner: A::Adapter,
+    log: CallLog,
+}
+
+impl<A: Api> Adapter<A> {
+    /// Returns the adapter of the wrapped backend.
+    pub fn inner(&self) -> &A::Adapter {
+        &self.inner
+    }
+}
+
+impl<A: Api> fmt::Debug for Adapter<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Adapter").finish_non_exhaustive()
+    }
+}
+
+impl<A: Api> crate::Adapter for Adapter<A> {
+    type A = Recorder<A>;
+
+    unsafe fn open(
+        &self,
+        features: wgt::Features,
+        limits: &wgt::Limits,
+        memory_hints: &wgt::MemoryHints,
+    ) -> Result<OpenDevice<Recorder<A>>, DeviceError> {
+        let open = unsafe { self.inner.open(features, limits, memory_hints) }?;
+        Ok(OpenDevice {
+            device: Device {
+                id: self.log.next_id(ObjectKind::Device),
+                inner: open.device,
+                log: self.log.clone(),
+            },
+            queue: Queue {
+                id: self.log.next_id(ObjectKind::Queue),
+                inner: open.queue,
+                log: self.log.clone(),
+            },
+        })
+    }
+
+    unsafe fn texture_format_capabilities(
+        &self,
+        format: wgt::TextureFormat,
+    ) -> TextureFormatCapabilities {
+        unsafe { self.inner.texture_format_capabilities(format) }
+    }
+
+    unsafe fn surface_capabilities(&self, surface: &Surface<A>) -> Option<SurfaceCapabilities> {
+        unsafe { self.inner.surface_capabilities(&surface.inner) }
+    }
+
+    unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
+        unsafe { self.inner.get_presentation_timestamp() }
+    }
+}
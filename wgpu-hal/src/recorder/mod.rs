@@ -0,0 +1,609 @@
+//! A wrapping [`Api`] that records the calls made to another backend.
+//!
+//! [`Recorder<A>`] forwards everything to the backend `A`, and records every call made to its
+//! [`Device`], [`Queue`] and [`CommandEncoder`], along with the arguments, into a [`CallLog`].
+//! This shows the work `wgpu-core` hands to the backend, like the barriers it inserts, the
+//! clears that initialize resources or the passes it records to validate indirect draws,
+//! which API traces don't capture.
+//!
+//! Objects are named after their kind and the order in which they were created, like
+//! `Buffer#2`, so the calls recorded for a given workload are the same from one run to the next.
+//! Combined with the noop backend, this allows testing what `wgpu-core` records without a GPU:
+//!
+//! ```
+//! # use wgpu_hal::{api::Noop, recorder, Instance as _};
+//! let desc = wgpu_hal::InstanceDescriptor {
+//!     name: "recorded",
+//!     flags: Default::default(),
+//!     memory_budget_thresholds: Default::default(),
+//!     backend_options: wgpu_types::BackendOptions {
+//!         noop: wgpu_types::NoopBackendOptions {
+//!             enable: true,
+//!             ..Default::default()
+//!         },
+//!         ..Default::default()
+//!     },
+//! };
+//! let instance = unsafe { recorder::Instance::<Noop>::init(&desc) }.unwrap();
+//! let log = instance.call_log().clone();
+//! // Hand `instance` over to `wgpu-core`, then inspect `log.take()`.
+//! ```
+//!
+//! Calls are also logged at the trace level as they're recorded.
+//!
+//! [`Api`]: crate::Api
+
+mod command;
+mod device;
+mod instance;
+mod queue;
+
+pub use command::CommandEncoder;
+pub use device::Device;
+pub use instance::{Adapter, Instance, Surface};
+pub use queue::Queue;
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{any::Any, borrow::Borrow, fmt, marker::PhantomData};
+
+use parking_lot::Mutex;
+
+use crate::{
+    AccelerationStructureAABBs, AccelerationStructureEntries, AccelerationStructureInstances,
+    AccelerationStructureTriangleIndices, AccelerationStructureTriangleTransform,
+    AccelerationStructureTriangles, Api, Attachment, BufferBinding, ColorAttachment,
+    DepthStencilAttachment, DynResource, ExternalTextureBinding, PassTimestampWrites,
+    ProgrammableStage, SparseBufferBind, SparseTextureBind, TextureBinding, VideoPicture,
+};
+
+/// Formats the arguments of a call, named after the variables they're in unless given a name.
+macro_rules! args {
+    ($($name:ident $(= $value:expr)?),* $(,)?) => {
+        alloc::vec![$((stringify!($name), args!(@value $name $(, $value)?))),*]
+    };
+    (@value $name:ident) => {
+        alloc::format!("{:?}", $name)
+    };
+    (@value $name:ident, $value:expr) => {
+        alloc::format!("{:?}", $value)
+    };
+}
+use args;
+
+/// An [`Api`] that records the calls made to the backend `A`.
+///
+/// See the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct Recorder<A: Api>(PhantomData<A>);
+
+impl<A: Api> Api for Recorder<A> {
+    const VARIANT: wgt::Backend = A::VARIANT;
+
+    type Instance = Instance<A>;
+    type Surface = Surface<A>;
+    type Adapter = Adapter<A>;
+    type Device = Device<A>;
+
+    type Queue = Queue<A>;
+    type CommandEncoder = CommandEncoder<A>;
+    type CommandBuffer = Recorded<A::CommandBuffer>;
+
+    type Buffer = Recorded<A::Buffer>;
+    type Texture = Texture<A>;
+    type SurfaceTexture = Texture<A>;
+    type TextureView = Recorded<A::TextureView>;
+    type Sampler = Recorded<A::Sampler>;
+    type QuerySet = Recorded<A::QuerySet>;
+    type Fence = Recorded<A::Fence>;
+
+    type BindGroupLayout = Recorded<A::BindGroupLayout>;
+    type BindGroup = Recorded<A::BindGroup>;
+    type PipelineLayout = Recorded<A::PipelineLayout>;
+    type ShaderModule = Recorded<A::ShaderModule>;
+    type RenderPipeline = Recorded<A::RenderPipeline>;
+    type ComputePipeline = Recorded<A::ComputePipeline>;
+    type PipelineCache = Recorded<A::PipelineCache>;
+
+    type AccelerationStructure = Recorded<A::AccelerationStructure>;
+    type VideoDecoder = Recorded<A::VideoDecoder>;
+}
+
+/// The kind of an object, which it's named after in the log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObjectKind {
+    Device,
+    Queue,
+    CommandEncoder,
+    CommandBuffer,
+    Buffer,
+    Texture,
+    SurfaceTexture,
+    TextureView,
+    Sampler,
+    QuerySet,
+    Fence,
+    BindGroupLayout,
+    BindGroup,
+    PipelineLayout,
+    ShaderModule,
+    RenderPipeline,
+    ComputePipeline,
+    PipelineCache,
+    AccelerationStructure,
+    VideoDecoder,
+}
+
+impl ObjectKind {
+    const COUNT: usize = Self::VideoDecoder as usize + 1;
+}
+
+/// Identifies an object by its kind, and the number of objects of that kind created before it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId {
+    pub kind: ObjectKind,
+    pub index: u32,
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}#{}", self.kind, self.index)
+    }
+}
+
+impl fmt::Debug for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// A recorded call to a [`Device`], [`Queue`] or [`CommandEncoder`].
+///
+/// Displays as `CommandEncoder#0.clear_buffer(buffer: Buffer#1, range: 0..4)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Call {
+    /// The object the method was called on.
+    pub object: ObjectId,
+    pub method: &'static str,
+    /// The names of the arguments, and their values formatted with [`fmt::Debug`].
+    ///
+    /// Objects are formatted as their [`ObjectId`].
+    pub args: Vec<(&'static str, String)>,
+    /// The object returned by the call, if it created one.
+    pub created: Option<ObjectId>,
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}(", self.object, self.method)?;
+        for (i, (name, value)) in self.args.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name}: {value}")?;
+        }
+        f.write_str(")")?;
+        if let Some(created) = self.created {
+            write!(f, " -> {created}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct LogState {
+    calls: Vec<Call>,
+    counts: [u32; ObjectKind::COUNT],
+}
+
+/// The calls recorded by a [`Recorder`] instance, and the objects created through it.
+///
+/// Clones share the same log.
+#[derive(Clone, Default)]
+pub struct CallLog {
+    state: Arc<Mutex<LogState>>,
+}
+
+impl fmt::Debug for CallLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallLog")
+            .field("calls", &self.state.lock().calls.len())
+            .finish()
+    }
+}
+
+impl CallLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the calls recorded so far.
+    pub fn calls(&self) -> Vec<Call> {
+        self.state.lock().calls.clone()
+    }
+
+    /// Returns the calls recorded so far, and clears the log.
+    ///
+    /// Objects keep their names, and new objects are still numbered after the previous ones.
+    pub fn take(&self) -> Vec<Call> {
+        core::mem::take(&mut self.state.lock().calls)
+    }
+
+    fn next_id(&self, kind: ObjectKind) -> ObjectId {
+        let mut state = self.state.lock();
+        let count = &mut state.counts[kind as usize];
+        let id = ObjectId {
+            kind,
+            index: *count,
+        };
+        *count += 1;
+        id
+    }
+
+    fn record(
+        &self,
+        object: ObjectId,
+        method: &'static str,
+        args: Vec<(&'static str, String)>,
+        created: Option<ObjectId>,
+    ) {
+        let call = Call {
+            object,
+            method,
+            args,
+            created,
+        };
+        log::trace!("{call}");
+        self.state.lock().calls.push(call);
+    }
+
+    /// Names `inner` after `kind` if it was created, and records the call that created it.
+    fn record_creation<T, E>(
+        &self,
+        object: ObjectId,
+        method: &'static str,
+        args: Vec<(&'static str, String)>,
+        kind: ObjectKind,
+        inner: Result<T, E>,
+    ) -> Result<Recorded<T>, E> {
+        let created = inner.map(|inner| Recorded {
+            id: self.next_id(kind),
+            inner,
+        });
+        self.record(
+            object,
+            method,
+            args,
+            created.as_ref().ok().map(Recorded::id),
+        );
+        created
+    }
+}
+
+/// An object of the wrapped backend, along with its name in the log.
+pub struct Recorded<T> {
+    id: ObjectId,
+    inner: T,
+}
+
+impl<T> Recorded<T> {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the object of the wrapped backend.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> fmt::Debug for Recorded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl<T: DynResource> DynResource for Recorded<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Implements the `Dyn*` resource traits for [`Recorded`] resources.
+macro_rules! impl_dyn_recorded {
+    ($($trait:ident),*) => {
+        $(
+            impl<T: crate::$trait> crate::$trait for Recorded<T> {}
+        )*
+    };
+}
+
+impl_dyn_recorded!(
+    DynAccelerationStructure,
+    DynBindGroup,
+    DynBindGroupLayout,
+    DynBuffer,
+    DynCommandBuffer,
+    DynComputePipeline,
+    DynFence,
+    DynPipelineCache,
+    DynPipelineLayout,
+    DynQuerySet,
+    DynRenderPipeline,
+    DynSampler,
+    DynShaderModule,
+    DynTextureView,
+    DynVideoDecoder
+);
+
+/// A texture of the wrapped backend, which may be a surface texture.
+///
+/// This is both the texture and the surface texture type of [`Recorder`], as surface textures
+/// must be borrowable as textures.
+pub struct Texture<A: Api> {
+    id: ObjectId,
+    inner: TextureInner<A>,
+}
+
+enum TextureInner<A: Api> {
+    Texture(A::Texture),
+    Surface(A::SurfaceTexture),
+}
+
+impl<A: Api> Texture<A> {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the texture of the wrapped backend.
+    pub fn inner(&self) -> &A::Texture {
+        match self.inner {
+            TextureInner::Texture(ref texture) => texture,
+            TextureInner::Surface(ref texture) => texture.borrow(),
+        }
+    }
+
+    fn into_texture(self) -> A::Texture {
+        match self.inner {
+            TextureInner::Texture(texture) => texture,
+            TextureInner::Surface(_) => {
+                crate::hal_usage_error("a surface texture was used as a regular texture")
+            }
+        }
+    }
+
+    fn into_surface_texture(self) -> A::SurfaceTexture {
+        match self.inner {
+            TextureInner::Surface(texture) => texture,
+            TextureInner::Texture(_) => {
+                crate::hal_usage_error("a regular texture was used as a surface texture")
+            }
+        }
+    }
+
+    fn surface_texture(&self) -> &A::SurfaceTexture {
+        match self.inner {
+            TextureInner::Surface(ref texture) => texture,
+            TextureInner::Texture(_) => {
+                crate::hal_usage_error("a regular texture was used as a surface texture")
+            }
+        }
+    }
+}
+
+impl<A: Api> fmt::Debug for Texture<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl<A: Api> Borrow<dyn crate::DynTexture> for Texture<A> {
+    fn borrow(&self) -> &dyn crate::DynTexture {
+        self
+    }
+}
+
+impl<A: Api> DynResource for Texture<A> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<A: Api> crate::DynTexture for Texture<A> {}
+impl<A: Api> crate::DynSurfaceTexture for Texture<A> {}
+
+/// Implements [`DynResource`] for the wrappers of the backend's objects.
+macro_rules! impl_dyn_wrapper {
+    ($($type:ident),*) => {
+        $(
+            impl<A: Api> DynResource for $type<A> {
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+
+                fn as_any_mut(&mut self) -> &mut dyn Any {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_dyn_wrapper!(Instance, Surface, Adapter, Device, Queue, CommandEncoder);
+
+impl<'a, B: crate::DynBuffer> BufferBinding<'a, Recorded<B>> {
+    fn to_inner(&self) -> BufferBinding<'a, B> {
+        BufferBinding {
+            buffer: &self.buffer.inner,
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+}
+
+impl<'a, T: crate::DynTextureView> TextureBinding<'a, Recorded<T>> {
+    fn to_inner(&self) -> TextureBinding<'a, T> {
+        TextureBinding {
+            view: &self.view.inner,
+            usage: self.usage,
+        }
+    }
+}
+
+impl<'a, B: crate::DynBuffer, T: crate::DynTextureView>
+    ExternalTextureBinding<'a, Recorded<B>, Recorded<T>>
+{
+    fn to_inner(&self) -> ExternalTextureBinding<'a, B, T> {
+        ExternalTextureBinding {
+            planes: self.planes.each_ref().map(TextureBinding::to_inner),
+            params: self.params.to_inner(),
+        }
+    }
+}
+
+impl<'a, A: Api> VideoPicture<'a, Texture<A>> {
+    fn to_inner(&self) -> VideoPicture<'a, A::Texture> {
+        VideoPicture {
+            texture: self.texture.inner(),
+            slot: self.slot,
+            usage: self.usage.clone(),
+        }
+    }
+}
+
+impl<'a, B: crate::DynBuffer> SparseBufferBind<'a, Recorded<B>> {
+    fn to_inner(&self) -> SparseBufferBind<'a, B> {
+        SparseBufferBind {
+            buffer: &self.buffer.inner,
+            range: self.range.clone(),
+            resident: self.resident,
+        }
+    }
+}
+
+impl<'a, A: Api> SparseTextureBind<'a, Texture<A>> {
+    fn to_inner(&self) -> SparseTextureBind<'a, A::Texture> {
+        SparseTextureBind {
+            texture: self.texture.inner(),
+            mip_level: self.mip_level,
+            origin: self.origin,
+            size: self.size,
+            resident: self.resident,
+        }
+    }
+}
+
+impl<'a, M: crate::DynShaderModule> ProgrammableStage<'a, Recorded<M>> {
+    fn to_inner(&self) -> ProgrammableStage<'a, M> {
+        ProgrammableStage {
+            module: &self.module.inner,
+            entry_point: self.entry_point,
+            constants: self.constants,
+            zero_initialize_workgroup_memory: self.zero_initialize_workgroup_memory,
+        }
+    }
+}
+
+impl<'a, T: crate::DynTextureView> Attachment<'a, Recorded<T>> {
+    fn to_inner(&self) -> Attachment<'a, T> {
+        Attachment {
+            view: &self.view.inner,
+            usage: self.usage,
+        }
+    }
+}
+
+impl<'a, T: crate::DynTextureView> ColorAttachment<'a, Recorded<T>> {
+    fn to_inner(&self) -> ColorAttachment<'a, T> {
+        ColorAttachment {
+            target: self.target.to_inner(),
+            depth_slice: self.depth_slice,
+            resolve_target: self.resolve_target.as_ref().map(Attachment::to_inner),
+            ops: self.ops,
+            clear_value: self.clear_value,
+        }
+    }
+}
+
+impl<'a, T: crate::DynTextureView> DepthStencilAttachment<'a, Recorded<T>> {
+    fn to_inner(&self) -> DepthStencilAttachment<'a, T> {
+        DepthStencilAttachment {
+            target: self.target.to_inner(),
+            depth_ops: self.depth_ops,
+            stencil_ops: self.stencil_ops,
+            clear_value: self.clear_value,
+        }
+    }
+}
+
+impl<'a, Q: crate::DynQuerySet> PassTimestampWrites<'a, Recorded<Q>> {
+    fn to_inner(&self) -> PassTimestampWrites<'a, Q> {
+        PassTimestampWrites {
+            query_set: &self.query_set.inner,
+            beginning_of_pass_write_index: self.beginning_of_pass_write_index,
+            end_of_pass_write_index: self.end_of_pass_write_index,
+        }
+    }
+}
+
+impl<'a, B: crate::DynBuffer> AccelerationStructureEntries<'a, Recorded<B>> {
+    fn to_inner(&self) -> AccelerationStructureEntries<'a, B> {
+        match self {
+            AccelerationStructureEntries::Instances(instances) => {
+                AccelerationStructureEntries::Instances(AccelerationStructureInstances {
+                    buffer: instances.buffer.map(|b| &b.inner),
+                    offset: instances.offset,
+                    count: instances.count,
+                })
+            }
+            AccelerationStructureEntries::Triangles(triangles) => {
+                AccelerationStructureEntries::Triangles(
+                    triangles
+                        .iter()
+                        .map(|t| AccelerationStructureTriangles {
+                            vertex_buffer: t.vertex_buffer.map(|b| &b.inner),
+                            vertex_format: t.vertex_format,
+                            first_vertex: t.first_vertex,
+                            vertex_count: t.vertex_count,
+                            vertex_stride: t.vertex_stride,
+                            indices: t.indices.as_ref().map(|i| {
+                                AccelerationStructureTriangleIndices {
+                                    buffer: i.buffer.map(|b| &b.inner),
+                                    format: i.format,
+                                    offset: i.offset,
+                                    count: i.count,
+                                }
+                            }),
+                            transform: t.transform.as_ref().map(|t| {
+                                AccelerationStructureTriangleTransform {
+                                    buffer: &t.buffer.inner,
+                                    offset: t.offset,
+                                }
+                            }),
+                            flags: t.flags,
+                        })
+                        .collect(),
+                )
+            }
+            AccelerationStructureEntries::AABBs(entries) => AccelerationStructureEntries::AABBs(
+                entries
+                    .iter()
+                    .map(|e| AccelerationStructureAABBs {
+                        buffer: e.buffer.map(|b| &b.inner),
+                        offset: e.offset,
+                        count: e.count,
+                        stride: e.stride,
+                        flags: e.flags,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    Api, DeviceError, FenceValue, SparseBufferBind, SparseTextureBind, SurfaceError,
+    VideoDecodeDescriptor, VideoReference,
+};
+
+use super::{args, CallLog, ObjectId, Recorded, Recorder, Surface, Texture};
+
+/// A queue of the backend `A`, recording the calls made to it.
+pub struct Queue<A: Api> {
+    pub(super) id: ObjectId,
+    pub(super) inner: A::Queue,
+    pub(super) log: CallLog,
+}
+
+impl<A: Api> Queue<A> {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the queue of the wrapped backend.
+    pub fn inner(&self) -> &A::Queue {
+        &self.inner
+    }
+}
+
+impl<A: Api> fmt::Debug for Queue<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.id, f)
+    }
+}
+
+impl<A: Api> crate::Queue for Queue<A> {
+    type A = Recorder<A>;
+
+    unsafe fn submit(
+        &self,
+        command_buffers: &[&Recorded<A::CommandBuffer>],
+        surface_textures: &[&Texture<A>],
+        (signal_fence, signal_value): (&mut Recorded<A::Fence>, FenceValue),
+    ) -> Result<(), DeviceError> {
+        self.log.record(
+            self.id,
+            "submit",
+            args!(
+                command_buffers,
+                surface_textures,
+                signal_fence = (&*signal_fence, signal_value)
+            ),
+            None,
+        );
+        let command_buffers = command_buffers
+            .iter()
+            .map(|cb| &cb.inner)
+            .collect::<Vec<_>>();
+        let surface_textures = surface_textures
+            .iter()
+            .map(|texture| texture.surface_texture())
+            .collect::<Vec<_>>();
+        unsafe {
+            self.inner.submit(
+                &command_buffers,
+                &surface_textures,
+                (&mut signal_fence.inner, signal_value),
+            )
+        }
+    }
+
+    unsafe fn present(
+        &self,
+        surface: &Surface<A>,
+        texture: Texture<A>,
+    ) -> Result<(), SurfaceError> {
+        self.log.record(self.id, "present", args!(texture), None);
+        unsafe {
+            self.inner
+                .present(&surface.inner, texture.into_surface_texture())
+        }
+    }
+
+    unsafe fn get_timestamp_period(&self) -> f32 {
+        self.log
+            .record(self.id, "get_timestamp_period", args!(), None);
+        unsafe { self.inner.get_timestamp_period() }
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Recorded<A::Fence>,
+        value: FenceValue,
+    ) -> Result<(), DeviceError> {
+        self.log
+            .record(self.id, "wait_for_fence", args!(fence, value), None);
+        unsafe { self.inner.wait_for_fence(&fence.inner, value) }
+    }
+
+    unsafe fn decode_video(
+        &self,
+        decoder: &Recorded<A::VideoDecoder>,
+        desc: &VideoDecodeDescriptor<Texture<A>>,
+        (wait_fence, wait_value): (&Recorded<A::Fence>, FenceValue),
+    ) -> Result<(), DeviceError> {
+        // The bitstream and the headers are left out, only their size and codec are logged.
+        self.log.record(
+            self.id,
+            "decode_video",
+            args!(
+                decoder,
+                bitstream = desc.bitstream.len(),
+                target = desc.target,
+                target_reference = desc.target_reference,
+                references = desc.references,
+                codec = desc.picture.codec(),
+                wait_fence = (wait_fence, wait_value)
+            ),
+            None,
+        );
+        let references = desc
+            .references
+            .iter()
+            .map(|reference| VideoReference {
+                picture: reference.picture.to_inner(),
+                info: reference.info,
+            })
+            .collect::<Vec<_>>();
+        let desc = VideoDecodeDescriptor {
+            bitstream: desc.bitstream,
+            target: desc.target.to_inner(),
+            target_reference: desc.target_reference,
+            references: &references,
+            picture: desc.picture,
+        };
+        unsafe {
+            self.inner
+                .decode_video(&decoder.inner, &desc, (&wait_fence.inner, wait_value))
+        }
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[SparseBufferBind<'_, Recorded<A::Buffer>>],
+        texture_binds: &[SparseTextureBind<'_, Texture<A>>],
+    ) -> Result<(), DeviceError> {
+        self.log.record(
+            self.id,
+            "bind_sparse_memory",
+            args!(buffer_binds, texture_binds),
+            None,
+        );
+        let buffer_binds = buffer_binds
+            .iter()
+            .map(SparseBufferBind::to_inner)
+            .collect::<Vec<_>>();
+        let texture_binds = texture_binds
+            .iter()
+            .map(SparseTextureBind::to_inner)
+            .collect::<Vec<_>>();
+        unsafe { self.inner.bind_sparse_memory(&buffer_binds, &texture_binds) }
+    }
+}
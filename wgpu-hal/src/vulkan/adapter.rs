@@ -126,6 +126,13 @@ pub struct PhysicalDeviceFeatures {
     /// Features provided by `VK_KHR_shader_integer_dot_product`, promoted to Vulkan 1.3.
     shader_integer_dot_product:
         Option<vk::PhysicalDeviceShaderIntegerDotProductFeaturesKHR<'static>>,
+
+    /// Features provided by `VK_EXT_graphics_pipeline_library`.
+    graphics_pipeline_library:
+        Option<vk::PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT<'static>>,
+
+    /// Features provided by `VK_EXT_shader_object`.
+    shader_object: Option<vk::PhysicalDeviceShaderObjectFeaturesEXT<'static>>,
 }
 
 impl PhysicalDeviceFeatures {
@@ -195,6 +202,12 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.shader_integer_dot_product {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.graphics_pipeline_library {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.shader_object {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -320,7 +333,16 @@ impl PhysicalDeviceFeatures {
                 .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
                 .shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
                 .shader_int16(requested_features.contains(wgt::Features::SHADER_I16))
-                //.shader_resource_residency(requested_features.contains(wgt::Features::SHADER_RESOURCE_RESIDENCY))
+                .shader_resource_residency(
+                    requested_features.contains(wgt::Features::SPARSE_RESOURCES),
+                )
+                .sparse_binding(requested_features.contains(wgt::Features::SPARSE_RESOURCES))
+                .sparse_residency_buffer(
+                    requested_features.contains(wgt::Features::SPARSE_RESOURCES),
+                )
+                .sparse_residency_image2_d(
+                    requested_features.contains(wgt::Features::SPARSE_RESOURCES),
+                )
                 .geometry_shader(requested_features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX))
                 .depth_clamp(requested_features.contains(wgt::Features::DEPTH_CLIP_CONTROL))
                 .dual_src_blend(requested_features.contains(wgt::Features::DUAL_SOURCE_BLENDING)),
@@ -531,6 +553,24 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            graphics_pipeline_library: if enabled_extensions
+                .contains(&ext::graphics_pipeline_library::NAME)
+            {
+                Some(
+                    vk::PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT::default()
+                        .graphics_pipeline_library(private_caps.graphics_pipeline_library),
+                )
+            } else {
+                None
+            },
+            shader_object: if enabled_extensions.contains(&ext::shader_object::NAME) {
+                Some(
+                    vk::PhysicalDeviceShaderObjectFeaturesEXT::default()
+                        .shader_object(private_caps.shader_object),
+                )
+            } else {
+                None
+            },
         }
     }
 
@@ -562,7 +602,10 @@ impl PhysicalDeviceFeatures {
             | F::PIPELINE_CACHE
             | F::SHADER_EARLY_DEPTH_TEST
             | F::TEXTURE_ATOMIC
-            | F::EXPERIMENTAL_PASSTHROUGH_SHADERS;
+            | F::EXPERIMENTAL_PASSTHROUGH_SHADERS
+            | F::REUSABLE_COMMAND_BUFFERS
+            | F::RENDER_BUNDLE_INHERITANCE
+            | F::TRANSIENT_ATTACHMENTS;
 
         let mut dl_flags = Df::COMPUTE_SHADERS
             | Df::BASE_VERTEX
@@ -577,7 +620,8 @@ impl PhysicalDeviceFeatures {
             | Df::INDIRECT_EXECUTION
             | Df::VIEW_FORMATS
             | Df::UNRESTRICTED_EXTERNAL_TEXTURE_COPIES
-            | Df::NONBLOCKING_QUERY_RESOLVE;
+            | Df::NONBLOCKING_QUERY_RESOLVE
+            | Df::COMPRESSED_TEXTURE_TO_BUFFER_COPIES;
 
         dl_flags.set(
             Df::SURFACE_VIEW_FORMATS,
@@ -960,6 +1004,11 @@ pub struct PhysicalDeviceProperties {
     /// `VK_EXT_mesh_shader` extension.
     mesh_shader: Option<vk::PhysicalDeviceMeshShaderPropertiesEXT<'static>>,
 
+    /// Additional `vk::PhysicalDevice` properties from the
+    /// `VK_EXT_graphics_pipeline_library` extension.
+    graphics_pipeline_library:
+        Option<vk::PhysicalDeviceGraphicsPipelineLibraryPropertiesEXT<'static>>,
+
     /// The device API version.
     ///
     /// Which is the version of Vulkan supported for device-level functionality.
@@ -1085,6 +1134,12 @@ impl PhysicalDeviceProperties {
             }
         }
 
+        // Optional `VK_EXT_graphics_pipeline_library`, which requires `VK_KHR_pipeline_library`
+        if self.supports_extension(ext::graphics_pipeline_library::NAME) {
+            extensions.push(khr::pipeline_library::NAME);
+            extensions.push(ext::graphics_pipeline_library::NAME);
+        }
+
         // Optional `VK_KHR_swapchain_mutable_format`
         if self.supports_extension(khr::swapchain_mutable_format::NAME) {
             extensions.push(khr::swapchain_mutable_format::NAME);
@@ -1318,6 +1373,10 @@ impl PhysicalDeviceProperties {
             max_blas_geometry_count,
             max_tlas_instance_count,
             max_acceleration_structures_per_shader_stage,
+
+            // Set from the queue families of the adapter.
+            max_compute_queues: 0,
+            max_transfer_queues: 0,
         }
     }
 
@@ -1396,6 +1455,9 @@ impl super::InstanceShared {
 
                 let supports_mesh_shader = capabilities.supports_extension(ext::mesh_shader::NAME);
 
+                let supports_graphics_pipeline_library =
+                    capabilities.supports_extension(ext::graphics_pipeline_library::NAME);
+
                 let mut properties2 = vk::PhysicalDeviceProperties2KHR::default();
                 if supports_maintenance3 {
                     let next = capabilities
@@ -1453,6 +1515,13 @@ impl super::InstanceShared {
                     properties2 = properties2.push_next(next);
                 }
 
+                if supports_graphics_pipeline_library {
+                    let next = capabilities
+                        .graphics_pipeline_library
+                        .insert(vk::PhysicalDeviceGraphicsPipelineLibraryPropertiesEXT::default());
+                    properties2 = properties2.push_next(next);
+                }
+
                 unsafe {
                     get_device_properties.get_physical_device_properties2(phd, &mut properties2)
                 };
@@ -1621,6 +1690,20 @@ impl super::InstanceShared {
                 features2 = features2.push_next(next);
             }
 
+            if capabilities.supports_extension(ext::graphics_pipeline_library::NAME) {
+                let next = features
+                    .graphics_pipeline_library
+                    .insert(vk::PhysicalDeviceGraphicsPipelineLibraryFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::shader_object::NAME) {
+                let next = features
+                    .shader_object
+                    .insert(vk::PhysicalDeviceShaderObjectFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
             unsafe { get_device_properties.get_physical_device_features2(phd, &mut features2) };
             features2.features
         } else {
@@ -1680,7 +1763,7 @@ impl super::Instance {
             },
             backend: wgt::Backend::Vulkan,
         };
-        let (available_features, downlevel_flags) =
+        let (mut available_features, downlevel_flags) =
             phd_features.to_wgpu(&self.shared.raw, phd, &phd_capabilities);
         let mut workarounds = super::Workarounds::empty();
         {
@@ -1810,9 +1893,77 @@ impl super::Instance {
             shader_int8: phd_features
                 .shader_float16_int8
                 .is_some_and(|features| features.shader_int8 != 0),
+            graphics_pipeline_library: phd_features
+                .graphics_pipeline_library
+                .is_some_and(|features| features.graphics_pipeline_library != 0),
+            graphics_pipeline_library_fast_linking: phd_capabilities
+                .graphics_pipeline_library
+                .is_some_and(|properties| properties.graphics_pipeline_library_fast_linking != 0),
+            shader_object: super::shader_object::is_supported(
+                phd_capabilities.device_api_version,
+                phd_features.shader_object.as_ref(),
+            ),
+            video_decode: if phd_capabilities.device_api_version >= vk::API_VERSION_1_3 {
+                super::video::VideoDecodeFamily::find(&self.shared, phd, &phd_capabilities)
+            } else {
+                None
+            },
+            // Queues that support graphics or compute implicitly support transfers.
+            compute_queue_family: find_dedicated_queue_family(
+                &queue_families,
+                vk::QueueFlags::COMPUTE,
+                vk::QueueFlags::GRAPHICS,
+            ),
+            transfer_queue_family: find_dedicated_queue_family(
+                &queue_families,
+                vk::QueueFlags::TRANSFER,
+                vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE,
+            ),
         };
+        // Decodes synchronize with the main queue through timeline semaphores.
+        available_features.set(
+            wgt::Features::VIDEO_DECODE,
+            private_caps.video_decode.is_some() && private_caps.timeline_semaphores,
+        );
+        // Pages are bound on the main queue, and must have the shapes given by
+        // `wgt::TextureFormat::sparse_page_dimensions`, with mip tails starting
+        // at the first level smaller than a page.
+        let sparse_properties = phd_capabilities.properties.sparse_properties;
+        available_features.set(
+            wgt::Features::SPARSE_RESOURCES,
+            queue_flags.contains(vk::QueueFlags::SPARSE_BINDING)
+                && phd_features.core.sparse_binding != 0
+                && phd_features.core.sparse_residency_buffer != 0
+                && phd_features.core.sparse_residency_image2_d != 0
+                && phd_features.core.shader_resource_residency != 0
+                && sparse_properties.residency_standard2_d_block_shape != 0
+                && sparse_properties.residency_non_resident_strict != 0
+                && sparse_properties.residency_aligned_mip_size == 0,
+        );
+        // Attachments are read as input attachments, bound in a descriptor set
+        // after the bind groups.
+        let phd_limits = &phd_capabilities.properties.limits;
+        let input_attachments = phd_limits.max_per_stage_descriptor_input_attachments
+            >= super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS
+            && phd_limits.max_descriptor_set_input_attachments
+                >= super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS
+            && phd_limits.max_bound_descriptor_sets > crate::MAX_BIND_GROUPS as u32;
+        available_features.set(
+            wgt::Features::FRAMEBUFFER_FETCH | wgt::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+            input_attachments,
+        );
+        let mut limits = phd_capabilities.to_wgpu_limits();
+        // Queues wait for the submissions of each other with timeline semaphores.
+        if private_caps.timeline_semaphores {
+            limits.max_compute_queues = private_caps
+                .compute_queue_family
+                .map_or(0, |family| family.queue_count);
+            limits.max_transfer_queues = private_caps
+                .transfer_queue_family
+                .map_or(0, |family| family.queue_count);
+        }
         let capabilities = crate::Capabilities {
-            limits: phd_capabilities.to_wgpu_limits(),
+            limits,
             alignments: phd_capabilities.to_hal_alignments(private_caps.robust_buffer_access2),
             downlevel: wgt::DownlevelCapabilities {
                 flags: downlevel_flags,
@@ -1859,12 +2010,58 @@ impl super::Adapter {
         &self.instance
     }
 
+    /// The queue family pictures are decoded on, if the adapter supports
+    /// [`wgt::Features::VIDEO_DECODE`].
+    ///
+    /// Devices created with [`device_from_raw`] and that feature must have a queue of
+    /// this family.
+    ///
+    /// [`device_from_raw`]: Self::device_from_raw
+    pub fn video_decode_queue_family_index(&self) -> Option<u32> {
+        self.private_caps.video_decode.map(|family| family.index)
+    }
+
+    /// The queue family compute queues are opened from, if the adapter has
+    /// queues dedicated to compute work.
+    ///
+    /// Devices created with [`device_from_raw`] and a non-zero
+    /// [`wgt::Limits::max_compute_queues`] must have that many queues of this family.
+    ///
+    /// [`device_from_raw`]: Self::device_from_raw
+    pub fn compute_queue_family_index(&self) -> Option<u32> {
+        self.private_caps
+            .compute_queue_family
+            .map(|family| family.index)
+    }
+
+    /// The queue family transfer queues are opened from, if the adapter has
+    /// queues dedicated to transfers.
+    ///
+    /// Devices created with [`device_from_raw`] and a non-zero
+    /// [`wgt::Limits::max_transfer_queues`] must have that many queues of this family.
+    ///
+    /// [`device_from_raw`]: Self::device_from_raw
+    pub fn transfer_queue_family_index(&self) -> Option<u32> {
+        self.private_caps
+            .transfer_queue_family
+            .map(|family| family.index)
+    }
+
     pub fn required_device_extensions(&self, features: wgt::Features) -> Vec<&'static CStr> {
-        let (supported_extensions, unsupported_extensions) = self
-            .phd_capabilities
-            .get_required_extensions(features)
-            .iter()
-            .partition::<Vec<&CStr>, _>(|&&extension| {
+        let mut extensions = self.phd_capabilities.get_required_extensions(features);
+        // `VK_EXT_shader_object`, if supported
+        if self.private_caps.shader_object {
+            extensions.push(ext::shader_object::NAME);
+        }
+        // `VK_KHR_video_queue`, `VK_KHR_video_decode_queue` and the extensions of the codecs
+        if features.contains(wgt::Features::VIDEO_DECODE) {
+            if let Some(family) = self.private_caps.video_decode {
+                extensions.extend(family.extensions());
+            }
+        }
+
+        let (supported_extensions, unsupported_extensions) =
+            extensions.iter().partition::<Vec<&CStr>, _>(|&&extension| {
                 self.phd_capabilities.supports_extension(extension)
             });
 
@@ -1910,6 +2107,13 @@ impl super::Adapter {
     /// - `raw_device` must be created from this adapter.
     /// - `raw_device` must be created using `family_index`, `enabled_extensions` and `physical_device_features()`
     /// - `enabled_extensions` must be a superset of `required_device_extensions()`.
+    /// - If `features` contains [`wgt::Features::VIDEO_DECODE`], `raw_device` must have a
+    ///   queue of the family given by `video_decode_queue_family_index()`, whose first queue
+    ///   isn't used outside of wgpu-hal.
+    /// - `limits` must be within the limits of the adapter. `raw_device` must have
+    ///   `limits.max_compute_queues` queues of the family given by
+    ///   `compute_queue_family_index()`, and `limits.max_transfer_queues` queues of the family
+    ///   given by `transfer_queue_family_index()`, which aren't used outside of wgpu-hal.
     /// - If `drop_callback` is [`None`], wgpu-hal will take ownership of `raw_device`. If
     ///   `drop_callback` is [`Some`], `raw_device` must be valid until the callback is called.
     #[allow(clippy::too_many_arguments)]
@@ -1919,6 +2123,7 @@ impl super::Adapter {
         drop_callback: Option<crate::DropCallback>,
         enabled_extensions: &[&'static CStr],
         features: wgt::Features,
+        limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
         family_index: u32,
         queue_index: u32,
@@ -1986,6 +2191,14 @@ impl super::Adapter {
         } else {
             None
         };
+        let shader_object_fns = if enabled_extensions.contains(&ext::shader_object::NAME) {
+            Some(ext::shader_object::Device::new(
+                &self.instance.raw,
+                &raw_device,
+            ))
+        } else {
+            None
+        };
         let mesh_shading_fns = if enabled_extensions.contains(&ext::mesh_shader::NAME) {
             Some(ext::mesh_shader::Device::new(
                 &self.instance.raw,
@@ -2085,6 +2298,16 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::ClipDistance);
             }
 
+            if features.contains(wgt::Features::SPARSE_RESOURCES) {
+                capabilities.push(spv::Capability::SparseResidency);
+            }
+
+            if features.intersects(
+                wgt::Features::FRAMEBUFFER_FETCH | wgt::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+            ) {
+                capabilities.push(spv::Capability::InputAttachment);
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
@@ -2158,9 +2381,13 @@ impl super::Adapter {
                 force_loop_bounding: true,
                 use_storage_input_output_16: features.contains(wgt::Features::SHADER_F16)
                     && self.phd_features.supports_storage_input_output_16(),
+                // Set for fragment stages, after the bind groups of the layout.
+                input_attachment_descriptor_set: None,
                 // We need to build this separately for each invocation, so just default it out here
                 binding_map: BTreeMap::default(),
                 debug_info: None,
+                // Set for each stage.
+                bounds_report: None,
             }
         };
 
@@ -2182,6 +2409,51 @@ impl super::Adapter {
             0, 0, 0, 0,
         ];
 
+        let video = match self.private_caps.video_decode {
+            Some(family) if features.contains(wgt::Features::VIDEO_DECODE) => {
+                Some(unsafe { super::video::VideoQueue::new(&self.instance, &raw_device, family) }?)
+            }
+            _ => None,
+        };
+
+        let opened_queues = |family: Option<super::DedicatedQueueFamily>, count: u32| {
+            family
+                .filter(|_| count > 0)
+                .map(|family| super::DedicatedQueueFamily {
+                    index: family.index,
+                    queue_count: count.min(family.queue_count),
+                })
+        };
+        let compute_queues = opened_queues(
+            self.private_caps.compute_queue_family,
+            limits.max_compute_queues,
+        );
+        let transfer_queues = opened_queues(
+            self.private_caps.transfer_queue_family,
+            limits.max_transfer_queues,
+        );
+
+        let input_attachment_set_layout = if features.intersects(
+            wgt::Features::FRAMEBUFFER_FETCH | wgt::Features::FRAMEBUFFER_FETCH_DEPTH_STENCIL,
+        ) {
+            let bindings = (0..super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS)
+                .map(|binding| {
+                    vk::DescriptorSetLayoutBinding::default()
+                        .binding(binding)
+                        .descriptor_type(vk::DescriptorType::INPUT_ATTACHMENT)
+                        .descriptor_count(1)
+                        .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+                })
+                .collect::<Vec<_>>();
+            let vk_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+            Some(
+                unsafe { raw_device.create_descriptor_set_layout(&vk_info, None) }
+                    .map_err(super::map_host_device_oom_err)?,
+            )
+        } else {
+            None
+        };
+
         let drop_guard = crate::DropGuard::from_option(drop_callback);
 
         let shared = Arc::new(super::DeviceShared {
@@ -2199,6 +2471,7 @@ impl super::Adapter {
                 timeline_semaphore: timeline_semaphore_fn,
                 ray_tracing: ray_tracing_fns,
                 mesh_shading: mesh_shading_fns,
+                shader_object: shader_object_fns,
             },
             pipeline_cache_validation_key,
             vendor_id: self.phd_capabilities.properties.vendor_id,
@@ -2214,6 +2487,10 @@ impl super::Adapter {
 
             texture_identity_factory: super::ResourceIdentityFactory::new(),
             texture_view_identity_factory: super::ResourceIdentityFactory::new(),
+            video,
+            compute_queues,
+            transfer_queues,
+            input_attachment_set_layout,
         });
 
         let relay_semaphores = super::RelaySemaphores::new(&shared)?;
@@ -2223,8 +2500,10 @@ impl super::Adapter {
             swapchain_fn,
             device: Arc::clone(&shared),
             family_index,
+            queue_type: wgt::QueueType::Graphics,
             relay_semaphores: Mutex::new(relay_semaphores),
             signal_semaphores: Default::default(),
+            wait_semaphores: Default::default(),
         };
 
         let mem_allocator = {
@@ -2316,6 +2595,23 @@ impl super::Adapter {
             },
         );
 
+        let pipeline_libraries = if self.private_caps.graphics_pipeline_library
+            && enabled_extensions.contains(&ext::graphics_pipeline_library::NAME)
+        {
+            Some(super::pipeline_library::PipelineLibraries::new(&shared))
+        } else {
+            None
+        };
+        let shader_objects = if self.private_caps.shader_object {
+            shared
+                .extension_fns
+                .shader_object
+                .clone()
+                .map(|fns| super::shader_object::ShaderObjects::new(fns, shared.features))
+        } else {
+            None
+        };
+
         let device = super::Device {
             shared,
             mem_allocator: Mutex::new(mem_allocator),
@@ -2325,6 +2621,8 @@ impl super::Adapter {
             #[cfg(feature = "renderdoc")]
             render_doc: Default::default(),
             counters: Default::default(),
+            pipeline_libraries,
+            shader_objects,
         };
 
         Ok(crate::OpenDevice { device, queue })
@@ -2341,6 +2639,7 @@ impl super::Adapter {
     pub unsafe fn open_with_callback<'a>(
         &self,
         features: wgt::Features,
+        limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
         callback: Option<Box<super::CreateDeviceCallback<'a>>>,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
@@ -2352,6 +2651,37 @@ impl super::Adapter {
             .queue_family_index(family_index)
             .queue_priorities(&[1.0]);
         let mut family_infos = Vec::from([family_info]);
+        if features.contains(wgt::Features::VIDEO_DECODE) {
+            if let Some(family) = self.private_caps.video_decode {
+                family_infos.push(
+                    vk::DeviceQueueCreateInfo::default()
+                        .queue_family_index(family.index)
+                        .queue_priorities(&[1.0]),
+                );
+            }
+        }
+        let compute_queue_count = self.private_caps.compute_queue_family.map_or(0, |family| {
+            limits.max_compute_queues.min(family.queue_count)
+        });
+        let transfer_queue_count = self.private_caps.transfer_queue_family.map_or(0, |family| {
+            limits.max_transfer_queues.min(family.queue_count)
+        });
+        let priorities = vec![1.0; compute_queue_count.max(transfer_queue_count) as usize];
+        for (family, count) in [
+            (self.private_caps.compute_queue_family, compute_queue_count),
+            (
+                self.private_caps.transfer_queue_family,
+                transfer_queue_count,
+            ),
+        ] {
+            if let Some(family) = family.filter(|_| count > 0) {
+                family_infos.push(
+                    vk::DeviceQueueCreateInfo::default()
+                        .queue_family_index(family.index)
+                        .queue_priorities(&priorities[..count as usize]),
+                );
+            }
+        }
 
         let mut pre_info = vk::DeviceCreateInfo::default();
 
@@ -2403,6 +2733,7 @@ impl super::Adapter {
                 None,
                 &enabled_extensions,
                 features,
+                limits,
                 memory_hints,
                 family_info.queue_family_index,
                 0,
@@ -2417,10 +2748,10 @@ impl crate::Adapter for super::Adapter {
     unsafe fn open(
         &self,
         features: wgt::Features,
-        _limits: &wgt::Limits,
+        limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        unsafe { self.open_with_callback(features, memory_hints, None) }
+        unsafe { self.open_with_callback(features, limits, memory_hints, None) }
     }
 
     unsafe fn texture_format_capabilities(
@@ -2850,3 +3181,35 @@ fn is_intel_igpu_outdated_for_robustness2(
     }
     is_outdated
 }
+
+/// Finds a queue family other than the one of the main queue whose queues support `required`
+/// and none of `excluded`, nor video coding.
+///
+/// Families with a granularity for image copies are skipped, as copies of any size must be
+/// possible on every queue.
+fn find_dedicated_queue_family(
+    queue_families: &[vk::QueueFamilyProperties],
+    required: vk::QueueFlags,
+    excluded: vk::QueueFlags,
+) -> Option<super::DedicatedQueueFamily> {
+    let excluded = excluded | vk::QueueFlags::VIDEO_DECODE_KHR | vk::QueueFlags::VIDEO_ENCODE_KHR;
+    let unit_granularity = vk::Extent3D {
+        width: 1,
+        height: 1,
+        depth: 1,
+    };
+    queue_families
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|&(_, family)| {
+            family.queue_flags.contains(required)
+                && !family.queue_flags.intersects(excluded)
+                && family.queue_count > 0
+                && family.min_image_transfer_granularity == unit_granularity
+        })
+        .map(|(index, family)| super::DedicatedQueueFamily {
+            index: index as u32,
+            queue_count: family.queue_count,
+        })
+}
@@ -90,20 +90,27 @@ impl super::CommandEncoder {
                     texture,
                     texture_identity: _,
                     format,
+                    aspect,
                     mip_level,
-                    depth_slice,
+                    first_layer,
+                    layer_count,
                 } = *e.key();
 
+                let view_type = if layer_count == 1 {
+                    vk::ImageViewType::TYPE_2D
+                } else {
+                    vk::ImageViewType::TYPE_2D_ARRAY
+                };
                 let vk_info = vk::ImageViewCreateInfo::default()
                     .image(texture)
-                    .view_type(vk::ImageViewType::TYPE_2D)
+                    .view_type(view_type)
                     .format(format)
                     .subresource_range(vk::ImageSubresourceRange {
-                        aspect_mask: vk::ImageAspectFlags::COLOR,
+                        aspect_mask: aspect,
                         base_mip_level: mip_level,
                         level_count: 1,
-                        base_array_layer: depth_slice,
-                        layer_count: 1,
+                        base_array_layer: first_layer,
+                        layer_count,
                     });
                 let raw = unsafe { self.device.raw.create_image_view(&vk_info, None) }
                     .map_err(super::map_host_device_oom_and_ioca_err)?;
@@ -114,6 +121,283 @@ impl super::CommandEncoder {
             }
         })
     }
+
+    /// Allocates a descriptor set of input attachments, from a new pool if the
+    /// last one is exhausted.
+    fn allocate_input_attachment_set(
+        &mut self,
+        layout: vk::DescriptorSetLayout,
+    ) -> Result<vk::DescriptorSet, crate::DeviceError> {
+        const SETS_PER_POOL: u32 = 16;
+
+        let layouts = [layout];
+        if let Some(&pool) = self.input_attachment_pools.last() {
+            let vk_info = vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(pool)
+                .set_layouts(&layouts);
+            match unsafe { self.device.raw.allocate_descriptor_sets(&vk_info) } {
+                Ok(sets) => return Ok(sets[0]),
+                Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY | vk::Result::ERROR_FRAGMENTED_POOL) => {}
+                Err(err) => return Err(super::map_host_device_oom_err(err)),
+            }
+        }
+
+        let pool_sizes = [vk::DescriptorPoolSize {
+            ty: vk::DescriptorType::INPUT_ATTACHMENT,
+            descriptor_count: SETS_PER_POOL * super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS,
+        }];
+        let vk_info = vk::DescriptorPoolCreateInfo::default()
+            .max_sets(SETS_PER_POOL)
+            .pool_sizes(&pool_sizes);
+        let pool = unsafe { self.device.raw.create_descriptor_pool(&vk_info, None) }
+            .map_err(super::map_host_device_oom_err)?;
+        self.input_attachment_pools.push(pool);
+
+        let vk_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(pool)
+            .set_layouts(&layouts);
+        let sets = unsafe { self.device.raw.allocate_descriptor_sets(&vk_info) }
+            .map_err(super::map_host_device_oom_err)?;
+        Ok(sets[0])
+    }
+
+    /// Binds the attachments of the current render pass as input attachments,
+    /// for `pipeline` to read with framebuffer fetch.
+    unsafe fn bind_input_attachments(&mut self, pipeline: &super::RenderPipeline) {
+        let Some(ref mut attachments) = self.rpass_input_attachments else {
+            return;
+        };
+        attachments.read = pipeline.input_attachments.is_some();
+        let Some((layout, index)) = pipeline.input_attachments else {
+            return;
+        };
+
+        let set = match attachments.set {
+            Some(set) => set,
+            None => {
+                let set_layout = self.device.input_attachment_set_layout.unwrap();
+                let set = match self.allocate_input_attachment_set(set_layout) {
+                    Ok(set) => set,
+                    Err(err) => {
+                        log::error!("Failed to allocate input attachments: {err}");
+                        return;
+                    }
+                };
+                let attachments = self.rpass_input_attachments.as_mut().unwrap();
+                unsafe { attachments.write(&self.device.raw, set) };
+                attachments.set = Some(set);
+                set
+            }
+        };
+
+        unsafe {
+            self.device.raw.cmd_bind_descriptor_sets(
+                self.active,
+                vk::PipelineBindPoint::GRAPHICS,
+                layout,
+                index,
+                &[set],
+                &[],
+            )
+        };
+    }
+
+    /// Makes the attachment writes of earlier draws visible to the next draw,
+    /// if it reads the attachments with framebuffer fetch.
+    unsafe fn framebuffer_fetch_barrier(&mut self) {
+        if !self
+            .rpass_input_attachments
+            .as_ref()
+            .is_some_and(|attachments| attachments.read)
+        {
+            return;
+        }
+        let barrier = vk::MemoryBarrier::default()
+            .src_access_mask(super::FRAMEBUFFER_FETCH_SRC_ACCESS)
+            .dst_access_mask(vk::AccessFlags::INPUT_ATTACHMENT_READ);
+        let mut dependency_flags = vk::DependencyFlags::BY_REGION;
+        if self.rpass_input_attachments.as_ref().unwrap().multiview {
+            dependency_flags |= vk::DependencyFlags::VIEW_LOCAL;
+        }
+        unsafe {
+            self.device.raw.cmd_pipeline_barrier(
+                self.active,
+                super::FRAMEBUFFER_FETCH_SRC_STAGES,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                dependency_flags,
+                &[barrier],
+                &[],
+                &[],
+            )
+        };
+    }
+
+    /// Records the half of the ownership transfer of `buffers` and `textures`
+    /// from the family of the queues of `src_queue_type` to the family of the
+    /// queues of `dst_queue_type` that happens on the queue of this encoder.
+    ///
+    /// The release only makes the writes of the releasing queue available, and
+    /// the acquire makes them visible to the stages of the acquiring queue that
+    /// use the resources in their state. The semaphore the acquiring queue
+    /// waits for orders the two.
+    unsafe fn transfer_ownership<'a, T, U>(
+        &mut self,
+        src_queue_type: wgt::QueueType,
+        dst_queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        let release = src_queue_type == self.queue_type;
+        let src_family = self.device.queue_family_index(src_queue_type);
+        let dst_family = self.device.queue_family_index(dst_queue_type);
+        if src_family == dst_family {
+            // Queues of the same family share their resources.
+            return;
+        }
+        let mut stages = vk::PipelineStageFlags::empty();
+
+        let buffer_barriers = &mut self.temp.buffer_barriers;
+        buffer_barriers.clear();
+        for bar in buffers {
+            let (stage, access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_barrier(bar.usage.from),
+            );
+            stages |= stage;
+            let barrier = vk::BufferMemoryBarrier::default()
+                .buffer(bar.buffer.raw)
+                .size(vk::WHOLE_SIZE)
+                .src_queue_family_index(src_family)
+                .dst_queue_family_index(dst_family);
+            buffer_barriers.push(if release {
+                barrier.src_access_mask(access)
+            } else {
+                barrier.dst_access_mask(access)
+            });
+        }
+
+        let image_barriers = &mut self.temp.image_barriers;
+        image_barriers.clear();
+        for bar in textures {
+            let range = conv::map_subresource_range_combined_aspect(
+                &bar.range,
+                bar.texture.format,
+                &self.device.private_caps,
+            );
+            let (stage, access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_barrier(bar.usage.from),
+            );
+            stages |= stage;
+            // The layout stays the same, so that neither queue transitions it.
+            let layout = conv::derive_image_layout(bar.usage.from, bar.texture.format);
+            let barrier = vk::ImageMemoryBarrier::default()
+                .image(bar.texture.raw)
+                .subresource_range(range)
+                .old_layout(layout)
+                .new_layout(layout)
+                .src_queue_family_index(src_family)
+                .dst_queue_family_index(dst_family);
+            image_barriers.push(if release {
+                barrier.src_access_mask(access)
+            } else {
+                barrier.dst_access_mask(access)
+            });
+        }
+
+        if buffer_barriers.is_empty() && image_barriers.is_empty() {
+            return;
+        }
+        if stages.is_empty() {
+            stages = vk::PipelineStageFlags::ALL_COMMANDS;
+        }
+        let (src_stages, dst_stages) = if release {
+            (stages, vk::PipelineStageFlags::BOTTOM_OF_PIPE)
+        } else {
+            (vk::PipelineStageFlags::TOP_OF_PIPE, stages)
+        };
+        unsafe {
+            self.device.raw.cmd_pipeline_barrier(
+                self.active,
+                src_stages,
+                dst_stages,
+                vk::DependencyFlags::empty(),
+                &[],
+                buffer_barriers,
+                image_barriers,
+            )
+        };
+    }
+
+    /// Sets the viewport, along with the viewport count if shader objects are used.
+    unsafe fn set_viewports(&mut self, viewports: &[vk::Viewport]) {
+        if self.device.private_caps.shader_object {
+            unsafe {
+                self.device
+                    .raw
+                    .cmd_set_viewport_with_count(self.active, viewports)
+            };
+        } else {
+            unsafe { self.device.raw.cmd_set_viewport(self.active, 0, viewports) };
+        }
+    }
+
+    /// Sets the scissor rectangle, along with the scissor count if shader objects are used.
+    unsafe fn set_scissors(&mut self, scissors: &[vk::Rect2D]) {
+        if self.device.private_caps.shader_object {
+            unsafe {
+                self.device
+                    .raw
+                    .cmd_set_scissor_with_count(self.active, scissors)
+            };
+        } else {
+            unsafe { self.device.raw.cmd_set_scissor(self.active, 0, scissors) };
+        }
+    }
+}
+
+impl super::RenderPassInputAttachments {
+    /// Writes the attachments to `set`, at the bindings naga reads them from.
+    unsafe fn write(&self, device: &ash::Device, set: vk::DescriptorSet) {
+        let depth_stencil = [
+            (naga::back::spv::DEPTH_INPUT_ATTACHMENT_INDEX, self.depth),
+            (
+                naga::back::spv::STENCIL_INPUT_ATTACHMENT_INDEX,
+                self.stencil,
+            ),
+        ];
+        let image_infos = self
+            .colors
+            .iter()
+            .enumerate()
+            .map(|(binding, &view)| (binding as u32, view, vk::ImageLayout::GENERAL))
+            .chain(
+                depth_stencil
+                    .into_iter()
+                    .map(|(binding, view)| (binding, view, self.depth_stencil_layout)),
+            )
+            .filter_map(|(binding, view, layout)| {
+                let info = vk::DescriptorImageInfo::default()
+                    .image_view(view?)
+                    .image_layout(layout);
+                Some((binding, [info]))
+            })
+            .collect::<ArrayVec<_, { super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS as usize }>>();
+        let writes = image_infos
+            .iter()
+            .map(|(binding, info)| {
+                vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(*binding)
+                    .descriptor_type(vk::DescriptorType::INPUT_ATTACHMENT)
+                    .image_info(info)
+            })
+            .collect::<ArrayVec<_, { super::FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS as usize }>>();
+        unsafe { device.update_descriptor_sets(&writes, &[]) };
+    }
 }
 
 impl crate::CommandEncoder for super::CommandEncoder {
@@ -141,8 +425,12 @@ impl crate::CommandEncoder for super::CommandEncoder {
         // Reset this in case the last renderpass was never ended.
         self.rpass_debug_marker_active = false;
 
-        let vk_info = vk::CommandBufferBeginInfo::default()
-            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        let usage = if self.reusable {
+            vk::CommandBufferUsageFlags::SIMULTANEOUS_USE
+        } else {
+            vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT
+        };
+        let vk_info = vk::CommandBufferBeginInfo::default().flags(usage);
         unsafe { self.device.raw.begin_command_buffer(raw, &vk_info) }
             .map_err(super::map_host_device_oom_err)?;
         self.active = raw;
@@ -184,6 +472,14 @@ impl crate::CommandEncoder for super::CommandEncoder {
         for (_, framebuffer) in self.framebuffers.drain() {
             unsafe { self.device.raw.destroy_framebuffer(framebuffer, None) };
         }
+        // Free the descriptor sets of input attachments
+        for &pool in self.input_attachment_pools.iter() {
+            let _ = unsafe {
+                self.device
+                    .raw
+                    .reset_descriptor_pool(pool, vk::DescriptorPoolResetFlags::empty())
+            };
+        }
         let _ = unsafe {
             self.device
                 .raw
@@ -202,9 +498,15 @@ impl crate::CommandEncoder for super::CommandEncoder {
         vk_barriers.clear();
 
         for bar in barriers {
-            let (src_stage, src_access) = conv::map_buffer_usage_to_barrier(bar.usage.from);
+            let (src_stage, src_access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_barrier(bar.usage.from),
+            );
             src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_buffer_usage_to_barrier(bar.usage.to);
+            let (dst_stage, dst_access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_buffer_usage_to_barrier(bar.usage.to),
+            );
             dst_stages |= dst_stage;
 
             vk_barriers.push(
@@ -246,10 +548,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 bar.texture.format,
                 &self.device.private_caps,
             );
-            let (src_stage, src_access) = conv::map_texture_usage_to_barrier(bar.usage.from);
+            let (src_stage, src_access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_barrier(bar.usage.from),
+            );
             let src_layout = conv::derive_image_layout(bar.usage.from, bar.texture.format);
             src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_texture_usage_to_barrier(bar.usage.to);
+            let (dst_stage, dst_access) = conv::map_barrier_to_queue(
+                self.queue_type,
+                conv::map_texture_usage_to_barrier(bar.usage.to),
+            );
             let dst_layout = conv::derive_image_layout(bar.usage.to, bar.texture.format);
             dst_stages |= dst_stage;
 
@@ -279,6 +587,30 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn release_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        unsafe { self.transfer_ownership(self.queue_type, queue_type, buffers, textures) };
+    }
+
+    unsafe fn acquire_resources<'a, T, U>(
+        &mut self,
+        queue_type: wgt::QueueType,
+        buffers: T,
+        textures: U,
+    ) where
+        T: Iterator<Item = crate::BufferBarrier<'a, super::Buffer>>,
+        U: Iterator<Item = crate::TextureBarrier<'a, super::Texture>>,
+    {
+        unsafe { self.transfer_ownership(queue_type, self.queue_type, buffers, textures) };
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         let range_size = range.end - range.start;
         if self.device.workarounds.contains(
@@ -749,13 +1081,19 @@ impl crate::CommandEncoder for super::CommandEncoder {
         &mut self,
         barrier: crate::AccelerationStructureBarrier,
     ) {
-        let (src_stage, src_access) = conv::map_acceleration_structure_usage_to_barrier(
-            barrier.usage.from,
-            self.device.features,
+        let (src_stage, src_access) = conv::map_barrier_to_queue(
+            self.queue_type,
+            conv::map_acceleration_structure_usage_to_barrier(
+                barrier.usage.from,
+                self.device.features,
+            ),
         );
-        let (dst_stage, dst_access) = conv::map_acceleration_structure_usage_to_barrier(
-            barrier.usage.to,
-            self.device.features,
+        let (dst_stage, dst_access) = conv::map_barrier_to_queue(
+            self.queue_type,
+            conv::map_acceleration_structure_usage_to_barrier(
+                barrier.usage.to,
+                self.device.features,
+            ),
         );
 
         unsafe {
@@ -792,6 +1130,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
             attachment_identities: ArrayVec::default(),
             extent: desc.extent,
         };
+        let mut input_attachments =
+            self.device
+                .input_attachment_set_layout
+                .map(|_| super::RenderPassInputAttachments {
+                    colors: ArrayVec::new(),
+                    depth: None,
+                    stencil: None,
+                    depth_stencil_layout: vk::ImageLayout::GENERAL,
+                    multiview: desc.multiview.is_some(),
+                    set: None,
+                    read: false,
+                });
 
         for cat in desc.color_attachments {
             if let Some(cat) = cat.as_ref() {
@@ -800,8 +1150,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
                         texture: cat.target.view.raw_texture,
                         texture_identity: cat.target.view.texture_identity,
                         format: cat.target.view.raw_format,
+                        aspect: vk::ImageAspectFlags::COLOR,
                         mip_level: cat.target.view.base_mip_level,
-                        depth_slice: cat.depth_slice.unwrap(),
+                        first_layer: cat.depth_slice.unwrap(),
+                        layer_count: 1,
                     };
                     self.make_temp_texture_view(key)?
                 } else {
@@ -821,6 +1173,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
 
                 rp_key.colors.push(Some(color));
                 fb_key.push_view(color_view);
+                if let Some(ref mut input_attachments) = input_attachments {
+                    input_attachments.colors.push(Some(color_view.raw));
+                }
                 if let Some(ref at) = cat.resolve_target {
                     vk_clear_values.push(unsafe { mem::zeroed() });
                     fb_key.push_view(at.view.identified_raw_view());
@@ -836,6 +1191,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 }
             } else {
                 rp_key.colors.push(None);
+                if let Some(ref mut input_attachments) = input_attachments {
+                    input_attachments.colors.push(None);
+                }
             }
         }
         if let Some(ref ds) = desc.depth_stencil_attachment {
@@ -845,8 +1203,34 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     stencil: ds.clear_value.1,
                 },
             });
+            let base = ds.target.make_attachment_key(ds.depth_ops);
+            if let Some(ref mut input_attachments) = input_attachments {
+                if base.layout == vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL {
+                    input_attachments.depth_stencil_layout = base.layout;
+                }
+                // Input attachments read a single aspect.
+                let view = &ds.target.view;
+                let mut aspect_view = |aspect| {
+                    self.make_temp_texture_view(super::TempTextureViewKey {
+                        texture: view.raw_texture,
+                        texture_identity: view.texture_identity,
+                        format: view.raw_format,
+                        aspect,
+                        mip_level: view.base_mip_level,
+                        first_layer: view.base_array_layer,
+                        layer_count: view.layers.get(),
+                    })
+                    .map(|view| view.raw)
+                };
+                if view.format.has_depth_aspect() {
+                    input_attachments.depth = Some(aspect_view(vk::ImageAspectFlags::DEPTH)?);
+                }
+                if view.format.has_stencil_aspect() {
+                    input_attachments.stencil = Some(aspect_view(vk::ImageAspectFlags::STENCIL)?);
+                }
+            }
             rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
-                base: ds.target.make_attachment_key(ds.depth_ops),
+                base,
                 stencil_ops: ds.stencil_ops,
             });
             fb_key.push_view(ds.target.view.identified_raw_view());
@@ -902,12 +1286,8 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
 
         unsafe {
-            self.device
-                .raw
-                .cmd_set_viewport(self.active, 0, &vk_viewports);
-            self.device
-                .raw
-                .cmd_set_scissor(self.active, 0, &[render_area]);
+            self.set_viewports(&vk_viewports);
+            self.set_scissors(&[render_area]);
             self.device.raw.cmd_begin_render_pass(
                 self.active,
                 &vk_info,
@@ -916,6 +1296,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         };
 
         self.bind_point = vk::PipelineBindPoint::GRAPHICS;
+        self.rpass_input_attachments = input_attachments;
 
         Ok(())
     }
@@ -923,6 +1304,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unsafe {
             self.device.raw.cmd_end_render_pass(self.active);
         }
+        self.rpass_input_attachments = None;
 
         // After all other commands but before debug marker, so this is still seen as part of this pass.
         self.write_pass_end_timestamp_if_requested();
@@ -993,11 +1375,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_render_pipeline(&mut self, pipeline: &super::RenderPipeline) {
+        unsafe { self.bind_input_attachments(pipeline) };
+        if let Some(ref shader_objects) = pipeline.shader_objects {
+            unsafe { shader_objects.bind(&self.device, self.active) };
+            return;
+        }
         unsafe {
             self.device.raw.cmd_bind_pipeline(
                 self.active,
                 vk::PipelineBindPoint::GRAPHICS,
-                pipeline.raw,
+                pipeline.current(),
             )
         };
     }
@@ -1038,11 +1425,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             min_depth: depth_range.start,
             max_depth: depth_range.end,
         }];
-        unsafe {
-            self.device
-                .raw
-                .cmd_set_viewport(self.active, 0, &vk_viewports)
-        };
+        unsafe { self.set_viewports(&vk_viewports) };
     }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
         let vk_scissors = [vk::Rect2D {
@@ -1055,11 +1438,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 height: rect.h,
             },
         }];
-        unsafe {
-            self.device
-                .raw
-                .cmd_set_scissor(self.active, 0, &vk_scissors)
-        };
+        unsafe { self.set_scissors(&vk_scissors) };
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         unsafe {
@@ -1081,6 +1460,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         first_instance: u32,
         instance_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         unsafe {
             self.device.raw.cmd_draw(
                 self.active,
@@ -1099,6 +1479,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         first_instance: u32,
         instance_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         unsafe {
             self.device.raw.cmd_draw_indexed(
                 self.active,
@@ -1110,12 +1491,24 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         };
     }
+    unsafe fn execute_indirect(
+        &mut self,
+        _layout: &wgt::IndirectCommandLayout,
+        _pipeline_layout: &super::PipelineLayout,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _max_count: u32,
+        _count_buffer: Option<(&super::Buffer, wgt::BufferAddress)>,
+    ) {
+        unreachable!("`Features::INDIRECT_COMMANDS` is not exposed on Vulkan")
+    }
     unsafe fn draw_mesh_tasks(
         &mut self,
         group_count_x: u32,
         group_count_y: u32,
         group_count_z: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         if let Some(ref t) = self.device.extension_fns.mesh_shading {
             unsafe {
                 t.cmd_draw_mesh_tasks(self.active, group_count_x, group_count_y, group_count_z);
@@ -1130,6 +1523,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         unsafe {
             self.device.raw.cmd_draw_indirect(
                 self.active,
@@ -1146,6 +1540,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         unsafe {
             self.device.raw.cmd_draw_indexed_indirect(
                 self.active,
@@ -1162,6 +1557,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         if let Some(ref t) = self.device.extension_fns.mesh_shading {
             unsafe {
                 t.cmd_draw_mesh_tasks_indirect(
@@ -1184,6 +1580,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         let stride = size_of::<wgt::DrawIndirectArgs>() as u32;
         match self.device.extension_fns.draw_indirect_count {
             Some(ref t) => {
@@ -1210,6 +1607,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         let stride = size_of::<wgt::DrawIndexedIndirectArgs>() as u32;
         match self.device.extension_fns.draw_indirect_count {
             Some(ref t) => {
@@ -1236,6 +1634,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        unsafe { self.framebuffer_fetch_barrier() };
         if self.device.extension_fns.draw_indirect_count.is_none() {
             panic!("Feature `DRAW_INDIRECT_COUNT` not enabled");
         }
@@ -259,6 +259,9 @@ pub fn map_texture_usage(usage: wgt::TextureUses) -> vk::ImageUsageFlags {
     ) {
         flags |= vk::ImageUsageFlags::STORAGE;
     }
+    if usage.contains(wgt::TextureUses::TRANSIENT) {
+        flags |= vk::ImageUsageFlags::TRANSIENT_ATTACHMENT;
+    }
     flags
 }
 
@@ -1022,3 +1025,70 @@ pub fn map_acceleration_structure_usage_to_barrier(
 
     (stages, access)
 }
+
+/// The sharing mode of a resource created with `memory_flags`, used by the
+/// queues of `queue_family_indices`.
+///
+/// Resources created with [`crate::MemoryFlags::QUEUE_EXCLUSIVE`] belong to one
+/// family at a time, and move between them with ownership transfers. The others
+/// are shared concurrently between the families.
+pub fn map_sharing_mode(
+    memory_flags: crate::MemoryFlags,
+    queue_family_indices: &[u32],
+) -> vk::SharingMode {
+    if queue_family_indices.len() > 1 && !memory_flags.contains(crate::MemoryFlags::QUEUE_EXCLUSIVE)
+    {
+        vk::SharingMode::CONCURRENT
+    } else {
+        vk::SharingMode::EXCLUSIVE
+    }
+}
+
+/// Restricts the stages and accesses of a barrier to those supported by the
+/// queues of `queue_type`.
+///
+/// The usages of a resource on other queues are made available by the
+/// semaphores the submissions of the queue wait for, so the barriers of a
+/// queue only need to order its own commands.
+pub fn map_barrier_to_queue(
+    queue_type: wgt::QueueType,
+    (stages, access): (vk::PipelineStageFlags, vk::AccessFlags),
+) -> (vk::PipelineStageFlags, vk::AccessFlags) {
+    let common_stages = vk::PipelineStageFlags::TOP_OF_PIPE
+        | vk::PipelineStageFlags::BOTTOM_OF_PIPE
+        | vk::PipelineStageFlags::TRANSFER
+        | vk::PipelineStageFlags::HOST
+        | vk::PipelineStageFlags::ALL_COMMANDS;
+    let common_access = vk::AccessFlags::TRANSFER_READ
+        | vk::AccessFlags::TRANSFER_WRITE
+        | vk::AccessFlags::HOST_READ
+        | vk::AccessFlags::HOST_WRITE
+        | vk::AccessFlags::MEMORY_READ
+        | vk::AccessFlags::MEMORY_WRITE;
+    let (supported_stages, supported_access) = match queue_type {
+        wgt::QueueType::Graphics => return (stages, access),
+        wgt::QueueType::Compute => (
+            common_stages
+                | vk::PipelineStageFlags::DRAW_INDIRECT
+                | vk::PipelineStageFlags::COMPUTE_SHADER
+                | vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR,
+            common_access
+                | vk::AccessFlags::INDIRECT_COMMAND_READ
+                | vk::AccessFlags::UNIFORM_READ
+                | vk::AccessFlags::SHADER_READ
+                | vk::AccessFlags::SHADER_WRITE
+                | vk::AccessFlags::ACCELERATION_STRUCTURE_READ_KHR
+                | vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR,
+        ),
+        wgt::QueueType::Transfer => (common_stages, common_access),
+    };
+    let stages = stages & supported_stages;
+    // Order the barrier with all commands of the queue when it supports none of
+    // the stages, so that it still chains with the barriers around it.
+    let stages = if stages.is_empty() {
+        vk::PipelineStageFlags::ALL_COMMANDS
+    } else {
+        stages
+    };
+    (stages, access & supported_access)
+}
@@ -1,5 +1,6 @@
 use alloc::{
     borrow::{Cow, ToOwned as _},
+    boxed::Box,
     collections::BTreeMap,
     ffi::CString,
     sync::Arc,
@@ -78,6 +79,36 @@ impl super::DeviceShared {
         };
     }
 
+    /// Returns the dynamic states render pipelines use for their viewport and scissor rectangle.
+    ///
+    /// When shader objects are used, these are set along with their count, as shader objects
+    /// require, and pipelines must then use the same states to be bound in the same passes.
+    pub(super) fn viewport_dynamic_states(&self) -> [vk::DynamicState; 2] {
+        if self.private_caps.shader_object {
+            [
+                vk::DynamicState::VIEWPORT_WITH_COUNT,
+                vk::DynamicState::SCISSOR_WITH_COUNT,
+            ]
+        } else {
+            [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR]
+        }
+    }
+
+    /// Maps `usage` to image usage flags, including input attachment usage
+    /// for attachments read with framebuffer fetch.
+    pub(super) fn map_texture_usage(&self, usage: wgt::TextureUses) -> vk::ImageUsageFlags {
+        let mut flags = conv::map_texture_usage(usage);
+        if self.input_attachment_set_layout.is_some()
+            && flags.intersects(
+                vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+            )
+        {
+            flags |= vk::ImageUsageFlags::INPUT_ATTACHMENT;
+        }
+        flags
+    }
+
     pub fn make_render_pass(
         &self,
         key: super::RenderPassKey,
@@ -96,6 +127,9 @@ impl super::DeviceShared {
                 let mut color_refs = Vec::with_capacity(colors.len());
                 let mut resolve_refs = Vec::with_capacity(color_refs.capacity());
                 let mut ds_ref = None;
+                // Attachments read with framebuffer fetch are input attachments
+                // too, in a layout that allows both.
+                let framebuffer_fetch = self.input_attachment_set_layout.is_some();
                 let samples = vk::SampleCountFlags::from_raw(sample_count);
                 let unused = vk::AttachmentReference {
                     attachment: vk::ATTACHMENT_UNUSED,
@@ -112,7 +146,11 @@ impl super::DeviceShared {
 
                             let color_ref = vk::AttachmentReference {
                                 attachment: vk_attachments.len() as u32,
-                                layout,
+                                layout: if framebuffer_fetch {
+                                    vk::ImageLayout::GENERAL
+                                } else {
+                                    layout
+                                },
                             };
                             vk_attachments.push({
                                 let (load_op, store_op) = conv::map_attachment_ops(ops);
@@ -172,7 +210,13 @@ impl super::DeviceShared {
 
                     ds_ref = Some(vk::AttachmentReference {
                         attachment: vk_attachments.len() as u32,
-                        layout,
+                        layout: if framebuffer_fetch
+                            && layout != vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
+                        {
+                            vk::ImageLayout::GENERAL
+                        } else {
+                            layout
+                        },
                     });
                     let (load_op, store_op) = conv::map_attachment_ops(ops);
                     let (stencil_load_op, stencil_store_op) = conv::map_attachment_ops(stencil_ops);
@@ -188,10 +232,24 @@ impl super::DeviceShared {
                     vk_attachments.push(vk_attachment);
                 }
 
+                // The color attachments, followed by the depth and stencil
+                // aspects, at the indices naga reads them from.
+                let mut input_refs = Vec::new();
+                if framebuffer_fetch {
+                    input_refs.extend_from_slice(&color_refs);
+                    input_refs.resize(
+                        naga::back::spv::DEPTH_INPUT_ATTACHMENT_INDEX as usize,
+                        unused,
+                    );
+                    let ds_input_ref = ds_ref.unwrap_or(unused);
+                    input_refs.extend([ds_input_ref, ds_input_ref]);
+                }
+
                 let vk_subpasses = [{
                     let mut vk_subpass = vk::SubpassDescription::default()
                         .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
                         .color_attachments(&color_refs)
+                        .input_attachments(&input_refs)
                         .resolve_attachments(&resolve_refs);
 
                     if self
@@ -208,9 +266,27 @@ impl super::DeviceShared {
                     vk_subpass
                 }];
 
+                // Lets draws make their attachment writes visible to the input
+                // attachment reads of later draws, with a barrier in the pass.
+                let mut dependency_flags = vk::DependencyFlags::BY_REGION;
+                if multiview.is_some() {
+                    dependency_flags |= vk::DependencyFlags::VIEW_LOCAL;
+                }
+                let self_dependency = [vk::SubpassDependency::default()
+                    .src_subpass(0)
+                    .dst_subpass(0)
+                    .src_stage_mask(super::FRAMEBUFFER_FETCH_SRC_STAGES)
+                    .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                    .src_access_mask(super::FRAMEBUFFER_FETCH_SRC_ACCESS)
+                    .dst_access_mask(vk::AccessFlags::INPUT_ATTACHMENT_READ)
+                    .dependency_flags(dependency_flags)];
+
                 let mut vk_info = vk::RenderPassCreateInfo::default()
                     .attachments(&vk_attachments)
                     .subpasses(&vk_subpasses);
+                if framebuffer_fetch {
+                    vk_info = vk_info.dependencies(&self_dependency);
+                }
 
                 let mut multiview_info;
                 let mask;
@@ -487,6 +563,44 @@ impl
     }
 }
 
+/// Returns whether the fragment `stage` reads attachments with framebuffer fetch.
+///
+/// Only shaders naga compiles can do so.
+fn reads_framebuffer(stage: &crate::ProgrammableStage<super::ShaderModule>) -> bool {
+    let super::ShaderModule::Intermediate {
+        ref naga_shader, ..
+    } = *stage.module
+    else {
+        return false;
+    };
+    let module = &naga_shader.module;
+    let fetches = |binding: &Option<naga::Binding>| {
+        matches!(
+            *binding,
+            Some(
+                naga::Binding::FramebufferFetch { .. }
+                    | naga::Binding::BuiltIn(
+                        naga::BuiltIn::LastFragDepth | naga::BuiltIn::LastFragStencil
+                    )
+            )
+        )
+    };
+    module
+        .entry_points
+        .iter()
+        .filter(|ep| ep.stage == naga::ShaderStage::Fragment && ep.name == stage.entry_point)
+        .flat_map(|ep| &ep.function.arguments)
+        .any(|arg| {
+            fetches(&arg.binding)
+                || match module.types[arg.ty].inner {
+                    naga::TypeInner::Struct { ref members, .. } => {
+                        members.iter().any(|member| fetches(&member.binding))
+                    }
+                    _ => false,
+                }
+        })
+}
+
 struct CompiledStage {
     create_info: vk::PipelineShaderStageCreateInfo<'static>,
     _entry_point: CString,
@@ -529,6 +643,27 @@ impl super::Device {
             raw_view_formats.push(original_format);
         }
 
+        let usage = self.shared.map_texture_usage(config.usage);
+        if usage.contains(vk::ImageUsageFlags::INPUT_ATTACHMENT) {
+            // Every render pass reads its attachments as input attachments
+            // with framebuffer fetch, including the surface's.
+            let caps = unsafe {
+                surface.functor.get_physical_device_surface_capabilities(
+                    self.shared.physical_device,
+                    surface.raw,
+                )
+            }
+            .map_err(super::map_host_device_oom_and_lost_err)?;
+            if !caps
+                .supported_usage_flags
+                .contains(vk::ImageUsageFlags::INPUT_ATTACHMENT)
+            {
+                return Err(crate::SurfaceError::Other(
+                    "Surface textures can't be input attachments, which framebuffer fetch needs",
+                ));
+            }
+        }
+
         let mut info = vk::SwapchainCreateInfoKHR::default()
             .flags(raw_flags)
             .surface(surface.raw)
@@ -540,7 +675,7 @@ impl super::Device {
                 height: config.extent.height,
             })
             .image_array_layers(config.extent.depth_or_array_layers)
-            .image_usage(conv::map_texture_usage(config.usage))
+            .image_usage(usage)
             .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
             .pre_transform(vk::SurfaceTransformFlagsKHR::IDENTITY)
             .composite_alpha(conv::map_composite_alpha_mode(config.composite_alpha_mode))
@@ -651,11 +786,12 @@ impl super::Device {
             format: desc.format,
             copy_size: desc.copy_extent(),
             identity,
+            video_view: None,
+            sparse: None,
         }
     }
 
-    #[cfg(windows)]
-    fn find_memory_type_index(
+    pub(super) fn find_memory_type_index(
         &self,
         type_bits_req: u32,
         flags_req: vk::MemoryPropertyFlags,
@@ -680,6 +816,116 @@ impl super::Device {
         None
     }
 
+    /// # Safety
+    ///
+    /// - `image` must have been created from `desc` and have no memory bound
+    /// - `mem_type_index` must be one of the memory types allowed by `image`
+    unsafe fn create_lazily_allocated_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        image: ImageWithoutMemory,
+        mem_type_index: usize,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let memory_allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(image.requirements.size)
+            .memory_type_index(mem_type_index as _);
+        let memory = unsafe { self.shared.raw.allocate_memory(&memory_allocate_info, None) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| {
+                unsafe { self.shared.raw.destroy_image(image.raw, None) };
+            })?;
+
+        unsafe { self.shared.raw.bind_image_memory(image.raw, memory, 0) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_image(image.raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        let identity = self.shared.texture_identity_factory.next();
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: Some(memory),
+            block: None,
+            format: desc.format,
+            copy_size: image.copy_size,
+            identity,
+            video_view: None,
+            sparse: None,
+        })
+    }
+
+    /// Allocates and binds device memory for `image`, and wraps it into a texture.
+    ///
+    /// # Safety
+    ///
+    /// - `image` must have been created from `desc` and have no memory bound
+    pub(super) unsafe fn allocate_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        image: ImageWithoutMemory,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        self.error_if_would_oom_on_resource_allocation(false, image.requirements.size)
+            .inspect_err(|_| {
+                unsafe { self.shared.raw.destroy_image(image.raw, None) };
+            })?;
+
+        let block = unsafe {
+            self.mem_allocator.lock().alloc(
+                &*self.shared,
+                gpu_alloc::Request {
+                    size: image.requirements.size,
+                    align_mask: image.requirements.alignment - 1,
+                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                    memory_types: image.requirements.memory_type_bits & self.valid_ash_memory_types,
+                },
+            )
+        }
+        .inspect_err(|_| {
+            unsafe { self.shared.raw.destroy_image(image.raw, None) };
+        })?;
+
+        self.counters.texture_memory.add(block.size() as isize);
+
+        unsafe {
+            self.shared
+                .raw
+                .bind_image_memory(image.raw, *block.memory(), block.offset())
+        }
+        .map_err(super::map_host_device_oom_err)
+        .inspect_err(|_| {
+            unsafe { self.shared.raw.destroy_image(image.raw, None) };
+        })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        let identity = self.shared.texture_identity_factory.next();
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: None,
+            block: Some(block),
+            format: desc.format,
+            copy_size: image.copy_size,
+            identity,
+            video_view: None,
+            sparse: None,
+        })
+    }
+
     fn create_image_without_memory(
         &self,
         desc: &crate::TextureDescriptor,
@@ -696,6 +942,10 @@ impl super::Device {
         if desc.is_cube_compatible() {
             raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
         }
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            raw_flags |=
+                vk::ImageCreateFlags::SPARSE_BINDING | vk::ImageCreateFlags::SPARSE_RESIDENCY;
+        }
 
         let original_format = self.shared.private_caps.map_texture_format(desc.format);
         let mut vk_view_formats = vec![];
@@ -715,6 +965,7 @@ impl super::Device {
             raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
         }
 
+        let queue_family_indices = self.shared.queue_family_indices();
         let mut vk_info = vk::ImageCreateInfo::default()
             .flags(raw_flags)
             .image_type(conv::map_texture_dimension(desc.dimension))
@@ -724,8 +975,12 @@ impl super::Device {
             .array_layers(desc.array_layer_count())
             .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
             .tiling(vk::ImageTiling::OPTIMAL)
-            .usage(conv::map_texture_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .usage(self.shared.map_texture_usage(desc.usage))
+            .sharing_mode(conv::map_sharing_mode(
+                desc.memory_flags,
+                &queue_family_indices,
+            ))
+            .queue_family_indices(&queue_family_indices)
             .initial_layout(vk::ImageLayout::UNDEFINED);
 
         let mut format_list_info = vk::ImageFormatListCreateInfo::default();
@@ -826,6 +1081,8 @@ impl super::Device {
             format: desc.format,
             copy_size: image.copy_size,
             identity,
+            video_view: None,
+            sparse: None,
         })
     }
 
@@ -852,11 +1109,95 @@ impl super::Device {
         Ok(raw)
     }
 
+    /// Translates a naga stage to SPIR-V, honoring the runtime checks of the module
+    /// and the per-stage options of the pipeline.
+    pub(super) fn compile_naga_stage(
+        &self,
+        stage: &crate::ProgrammableStage<super::ShaderModule>,
+        naga_stage: naga::ShaderStage,
+        naga_shader: &crate::NagaShader,
+        runtime_checks: wgt::ShaderRuntimeChecks,
+        layout: &super::PipelineLayout,
+    ) -> Result<Vec<u32>, crate::PipelineError> {
+        let binding_map = &layout.binding_arrays;
+        // Fragment shaders read framebuffer fetches from the input attachments.
+        let input_attachment_set = match naga_stage {
+            naga::ShaderStage::Fragment => layout.input_attachment_set,
+            _ => None,
+        };
+        let stage_flags = crate::auxil::map_naga_stage(naga_stage);
+        let pipeline_options = naga::back::spv::PipelineOptions {
+            entry_point: stage.entry_point.to_owned(),
+            shader_stage: naga_stage,
+        };
+        let bounds_report = naga_shader.bounds_report_options(naga_stage, stage.entry_point);
+        let needs_temp_options = !runtime_checks.bounds_checks
+            || !runtime_checks.force_loop_bounding
+            || !binding_map.is_empty()
+            || naga_shader.debug_source.is_some()
+            || !stage.zero_initialize_workgroup_memory
+            || bounds_report.is_some()
+            || input_attachment_set.is_some();
+        let mut temp_options;
+        let options = if needs_temp_options {
+            temp_options = self.naga_options.clone();
+            if !runtime_checks.bounds_checks {
+                temp_options.bounds_check_policies = naga::proc::BoundsCheckPolicies {
+                    index: naga::proc::BoundsCheckPolicy::Unchecked,
+                    buffer: naga::proc::BoundsCheckPolicy::Unchecked,
+                    image_load: naga::proc::BoundsCheckPolicy::Unchecked,
+                    binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
+                };
+            }
+            if !runtime_checks.force_loop_bounding {
+                temp_options.force_loop_bounding = false;
+            }
+            if !binding_map.is_empty() {
+                temp_options.binding_map = binding_map.clone();
+            }
+
+            if let Some(ref debug) = naga_shader.debug_source {
+                temp_options.debug_info = Some(naga::back::spv::DebugInfo {
+                    source_code: &debug.source_code,
+                    file_name: debug.file_name.as_ref().into(),
+                    language: naga::back::spv::SourceLanguage::WGSL,
+                })
+            }
+            if !stage.zero_initialize_workgroup_memory {
+                temp_options.zero_initialize_workgroup_memory =
+                    naga::back::spv::ZeroInitializeWorkgroupMemoryMode::None;
+            }
+            if bounds_report.is_some() {
+                crate::auxil::report_out_of_bounds_accesses(
+                    &mut temp_options.bounds_check_policies,
+                );
+                temp_options.bounds_report = bounds_report;
+            }
+            temp_options.input_attachment_descriptor_set = input_attachment_set;
+
+            &temp_options
+        } else {
+            &self.naga_options
+        };
+
+        let (module, info) = naga::back::pipeline_constants::process_overrides(
+            &naga_shader.module,
+            &naga_shader.info,
+            Some((naga_stage, stage.entry_point)),
+            stage.constants,
+        )
+        .map_err(|e| crate::PipelineError::PipelineConstants(stage_flags, format!("{e}")))?;
+
+        profiling::scope!("naga::spv::write_vec");
+        naga::back::spv::write_vec(&module, &info, options, Some(&pipeline_options))
+            .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))
+    }
+
     fn compile_stage(
         &self,
         stage: &crate::ProgrammableStage<super::ShaderModule>,
         naga_stage: naga::ShaderStage,
-        binding_map: &naga::back::spv::BindingMap,
+        layout: &super::PipelineLayout,
     ) -> Result<CompiledStage, crate::PipelineError> {
         let stage_flags = crate::auxil::map_naga_stage(naga_stage);
         let vk_module = match *stage.module {
@@ -865,65 +1206,13 @@ impl super::Device {
                 ref naga_shader,
                 runtime_checks,
             } => {
-                let pipeline_options = naga::back::spv::PipelineOptions {
-                    entry_point: stage.entry_point.to_owned(),
-                    shader_stage: naga_stage,
-                };
-                let needs_temp_options = !runtime_checks.bounds_checks
-                    || !runtime_checks.force_loop_bounding
-                    || !binding_map.is_empty()
-                    || naga_shader.debug_source.is_some()
-                    || !stage.zero_initialize_workgroup_memory;
-                let mut temp_options;
-                let options = if needs_temp_options {
-                    temp_options = self.naga_options.clone();
-                    if !runtime_checks.bounds_checks {
-                        temp_options.bounds_check_policies = naga::proc::BoundsCheckPolicies {
-                            index: naga::proc::BoundsCheckPolicy::Unchecked,
-                            buffer: naga::proc::BoundsCheckPolicy::Unchecked,
-                            image_load: naga::proc::BoundsCheckPolicy::Unchecked,
-                            binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
-                        };
-                    }
-                    if !runtime_checks.force_loop_bounding {
-                        temp_options.force_loop_bounding = false;
-                    }
-                    if !binding_map.is_empty() {
-                        temp_options.binding_map = binding_map.clone();
-                    }
-
-                    if let Some(ref debug) = naga_shader.debug_source {
-                        temp_options.debug_info = Some(naga::back::spv::DebugInfo {
-                            source_code: &debug.source_code,
-                            file_name: debug.file_name.as_ref().into(),
-                            language: naga::back::spv::SourceLanguage::WGSL,
-                        })
-                    }
-                    if !stage.zero_initialize_workgroup_memory {
-                        temp_options.zero_initialize_workgroup_memory =
-                            naga::back::spv::ZeroInitializeWorkgroupMemoryMode::None;
-                    }
-
-                    &temp_options
-                } else {
-                    &self.naga_options
-                };
-
-                let (module, info) = naga::back::pipeline_constants::process_overrides(
-                    &naga_shader.module,
-                    &naga_shader.info,
-                    Some((naga_stage, stage.entry_point)),
-                    stage.constants,
-                )
-                .map_err(|e| {
-                    crate::PipelineError::PipelineConstants(stage_flags, format!("{e}"))
-                })?;
-
-                let spv = {
-                    profiling::scope!("naga::spv::write_vec");
-                    naga::back::spv::write_vec(&module, &info, options, Some(&pipeline_options))
-                }
-                .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
+                let spv = self.compile_naga_stage(
+                    stage,
+                    naga_stage,
+                    naga_shader,
+                    runtime_checks,
+                    layout,
+                )?;
                 self.create_shader_module_impl(&spv)?
             }
         };
@@ -981,30 +1270,32 @@ impl super::Device {
         &self.shared.enabled_extensions
     }
 
+    /// Returns `true` if render pipelines are made of `VK_EXT_shader_object` shaders where possible.
+    ///
+    /// This is the case if the device supports `VK_EXT_shader_object` on Vulkan 1.3.
+    pub fn shader_objects_enabled(&self) -> bool {
+        self.shader_objects.is_some()
+    }
+
     pub fn shared_instance(&self) -> &super::InstanceShared {
         &self.shared.instance
     }
 
-    fn error_if_would_oom_on_resource_allocation(
+    /// Query the properties of the memory heaps along with their current budget and usage.
+    ///
+    /// Returns `None` if `VK_EXT_memory_budget` isn't enabled.
+    fn query_memory_budget(
         &self,
-        needs_host_access: bool,
-        size: u64,
-    ) -> Result<(), crate::DeviceError> {
-        let Some(threshold) = self
-            .shared
-            .instance
-            .memory_budget_thresholds
-            .for_resource_creation
-        else {
-            return Ok(());
-        };
-
+    ) -> Option<(
+        vk::PhysicalDeviceMemoryProperties,
+        vk::PhysicalDeviceMemoryBudgetPropertiesEXT<'static>,
+    )> {
         if !self
             .shared
             .enabled_extensions
             .contains(&ext::memory_budget::NAME)
         {
-            return Ok(());
+            return None;
         }
 
         let get_physical_device_properties = self
@@ -1026,11 +1317,32 @@ impl super::Device {
             );
         }
 
+        let memory_properties = memory_properties.memory_properties;
+
+        Some((memory_properties, memory_budget_properties))
+    }
+
+    fn error_if_would_oom_on_resource_allocation(
+        &self,
+        needs_host_access: bool,
+        size: u64,
+    ) -> Result<(), crate::DeviceError> {
+        let Some(threshold) = self
+            .shared
+            .instance
+            .memory_budget_thresholds
+            .for_resource_creation
+        else {
+            return Ok(());
+        };
+
+        let Some((memory_properties, memory_budget_properties)) = self.query_memory_budget() else {
+            return Ok(());
+        };
+
         let mut host_visible_heaps = [false; vk::MAX_MEMORY_HEAPS];
         let mut device_local_heaps = [false; vk::MAX_MEMORY_HEAPS];
 
-        let memory_properties = memory_properties.memory_properties;
-
         for i in 0..memory_properties.memory_type_count {
             let memory_type = memory_properties.memory_types[i as usize];
             let flags = memory_type.property_flags;
@@ -1084,10 +1396,19 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::BufferDescriptor,
     ) -> Result<super::Buffer, crate::DeviceError> {
+        let queue_family_indices = self.shared.queue_family_indices();
         let vk_info = vk::BufferCreateInfo::default()
             .size(desc.size)
             .usage(conv::map_buffer_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            .sharing_mode(conv::map_sharing_mode(
+                desc.memory_flags,
+                &queue_family_indices,
+            ))
+            .queue_family_indices(&queue_family_indices);
+
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            return unsafe { self.create_sparse_buffer(desc, vk_info) };
+        }
 
         let raw = unsafe {
             self.shared
@@ -1164,10 +1485,14 @@ impl crate::Device for super::Device {
         Ok(super::Buffer {
             raw,
             block: Some(Mutex::new(super::BufferMemoryBacking::Managed(block))),
+            sparse: None,
         })
     }
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
         unsafe { self.shared.raw.destroy_buffer(buffer.raw, None) };
+        if let Some(sparse) = buffer.sparse {
+            unsafe { self.destroy_sparse_buffer(sparse) };
+        }
         if let Some(block) = buffer.block {
             let block = block.into_inner();
             self.counters.buffer_memory.sub(block.size() as isize);
@@ -1188,6 +1513,14 @@ impl crate::Device for super::Device {
         self.counters.buffers.add(1);
     }
 
+    unsafe fn get_buffer_device_address(
+        &self,
+        _buffer: &super::Buffer,
+    ) -> Option<wgt::BufferAddress> {
+        // Indirect commands are not exposed, and don't need addresses.
+        None
+    }
+
     unsafe fn map_buffer(
         &self,
         buffer: &super::Buffer,
@@ -1259,63 +1592,39 @@ impl crate::Device for super::Device {
     ) -> Result<super::Texture, crate::DeviceError> {
         let image = self.create_image_without_memory(desc, None)?;
 
-        self.error_if_would_oom_on_resource_allocation(false, image.requirements.size)
-            .inspect_err(|_| {
-                unsafe { self.shared.raw.destroy_image(image.raw, None) };
-            })?;
-
-        let block = unsafe {
-            self.mem_allocator.lock().alloc(
-                &*self.shared,
-                gpu_alloc::Request {
-                    size: image.requirements.size,
-                    align_mask: image.requirements.alignment - 1,
-                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
-                    memory_types: image.requirements.memory_type_bits & self.valid_ash_memory_types,
-                },
-            )
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            return unsafe { self.create_sparse_texture(desc, image) };
         }
-        .inspect_err(|_| {
-            unsafe { self.shared.raw.destroy_image(image.raw, None) };
-        })?;
-
-        self.counters.texture_memory.add(block.size() as isize);
 
-        unsafe {
-            self.shared
-                .raw
-                .bind_image_memory(image.raw, *block.memory(), block.offset())
-        }
-        .map_err(super::map_host_device_oom_err)
-        .inspect_err(|_| {
-            unsafe { self.shared.raw.destroy_image(image.raw, None) };
-        })?;
-
-        if let Some(label) = desc.label {
-            unsafe { self.shared.set_object_name(image.raw, label) };
+        // Transient attachments are placed in lazily allocated memory when the
+        // device has it, which tilers may never back with physical pages.
+        // `gpu_alloc` doesn't handle those memory types, so allocate them directly.
+        if desc.usage.contains(wgt::TextureUses::TRANSIENT) {
+            if let Some(mem_type_index) = self.find_memory_type_index(
+                image.requirements.memory_type_bits & self.valid_ash_memory_types,
+                vk::MemoryPropertyFlags::LAZILY_ALLOCATED,
+            ) {
+                return unsafe {
+                    self.create_lazily_allocated_texture(desc, image, mem_type_index)
+                };
+            }
         }
 
-        let identity = self.shared.texture_identity_factory.next();
-
-        self.counters.textures.add(1);
-
-        Ok(super::Texture {
-            raw: image.raw,
-            drop_guard: None,
-            external_memory: None,
-            block: Some(block),
-            format: desc.format,
-            copy_size: image.copy_size,
-            identity,
-        })
+        unsafe { self.allocate_texture(desc, image) }
     }
     unsafe fn destroy_texture(&self, texture: super::Texture) {
+        if let Some(view) = texture.video_view {
+            unsafe { self.shared.raw.destroy_image_view(view, None) };
+        }
         if texture.drop_guard.is_none() {
             unsafe { self.shared.raw.destroy_image(texture.raw, None) };
         }
         if let Some(memory) = texture.external_memory {
             unsafe { self.shared.raw.free_memory(memory, None) };
         }
+        if let Some(sparse) = texture.sparse {
+            unsafe { self.destroy_sparse_texture(sparse) };
+        }
         if let Some(block) = texture.block {
             self.counters.texture_memory.sub(block.size() as isize);
 
@@ -1347,8 +1656,8 @@ impl crate::Device for super::Device {
 
         let mut image_view_info;
         if self.shared.private_caps.image_view_usage && !desc.usage.is_empty() {
-            image_view_info =
-                vk::ImageViewUsageCreateInfo::default().usage(conv::map_texture_usage(desc.usage));
+            image_view_info = vk::ImageViewUsageCreateInfo::default()
+                .usage(self.shared.map_texture_usage(desc.usage));
             vk_info = vk_info.push_next(&mut image_view_info);
         }
 
@@ -1366,6 +1675,7 @@ impl crate::Device for super::Device {
         Ok(super::TextureView {
             raw_texture: texture.raw,
             raw,
+            base_array_layer: subresource_range.base_array_layer,
             layers,
             format: desc.format,
             raw_format,
@@ -1447,9 +1757,14 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::CommandEncoderDescriptor<super::Queue>,
     ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        let flags = if desc.reusable {
+            vk::CommandPoolCreateFlags::empty()
+        } else {
+            vk::CommandPoolCreateFlags::TRANSIENT
+        };
         let vk_info = vk::CommandPoolCreateInfo::default()
             .queue_family_index(desc.queue.family_index)
-            .flags(vk::CommandPoolCreateFlags::TRANSIENT);
+            .flags(flags);
 
         let raw = unsafe {
             self.shared
@@ -1464,6 +1779,7 @@ impl crate::Device for super::Device {
             raw,
             device: Arc::clone(&self.shared),
             active: vk::CommandBuffer::null(),
+            queue_type: desc.queue.queue_type,
             bind_point: vk::PipelineBindPoint::default(),
             temp: super::Temp::default(),
             free: Vec::new(),
@@ -1472,6 +1788,9 @@ impl crate::Device for super::Device {
             end_of_pass_timer_query: None,
             framebuffers: Default::default(),
             temp_texture_views: Default::default(),
+            rpass_input_attachments: None,
+            input_attachment_pools: Vec::new(),
+            reusable: desc.reusable,
             counters: Arc::clone(&self.counters),
         })
     }
@@ -1623,11 +1942,16 @@ impl crate::Device for super::Device {
         desc: &crate::PipelineLayoutDescriptor<super::BindGroupLayout>,
     ) -> Result<super::PipelineLayout, crate::DeviceError> {
         //Note: not bothering with on stack array here as it's low frequency
-        let vk_set_layouts = desc
+        let mut vk_set_layouts = desc
             .bind_group_layouts
             .iter()
             .map(|bgl| bgl.raw)
             .collect::<Vec<_>>();
+        // Input attachments are bound after the bind groups.
+        let input_attachment_set = self.shared.input_attachment_set_layout.map(|layout| {
+            vk_set_layouts.push(layout);
+            vk_set_layouts.len() as u32 - 1
+        });
         let vk_push_constant_ranges = desc
             .push_constant_ranges
             .iter()
@@ -1677,9 +2001,18 @@ impl crate::Device for super::Device {
         Ok(super::PipelineLayout {
             raw,
             binding_arrays,
+            set_layouts: vk_set_layouts,
+            push_constant_ranges: vk_push_constant_ranges,
+            input_attachment_set,
         })
     }
     unsafe fn destroy_pipeline_layout(&self, pipeline_layout: super::PipelineLayout) {
+        if let Some(ref libraries) = self.pipeline_libraries {
+            unsafe { libraries.evict_pipeline_layout(&self.shared.raw, pipeline_layout.raw) };
+        }
+        if let Some(ref shader_objects) = self.shader_objects {
+            shader_objects.evict_pipeline_layout(pipeline_layout.raw);
+        }
         unsafe {
             self.shared
                 .raw
@@ -1906,6 +2239,8 @@ impl crate::Device for super::Device {
                     .workarounds
                     .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
                     || !naga_shader.module.overrides.is_empty()
+                    // Each entry point records its own shader id.
+                    || naga_shader.bounds_report.is_some()
                 {
                     return Ok(super::ShaderModule::Intermediate {
                         naga_shader,
@@ -1948,6 +2283,9 @@ impl crate::Device for super::Device {
         };
 
         let raw = self.create_shader_module_impl(&spv)?;
+        if let Some(ref shader_objects) = self.shader_objects {
+            shader_objects.add_module(raw, &spv);
+        }
 
         if let Some(label) = desc.label {
             unsafe { self.shared.set_object_name(raw, label) };
@@ -1961,6 +2299,12 @@ impl crate::Device for super::Device {
     unsafe fn destroy_shader_module(&self, module: super::ShaderModule) {
         match module {
             super::ShaderModule::Raw(raw) => {
+                if let Some(ref libraries) = self.pipeline_libraries {
+                    unsafe { libraries.evict_shader_module(&self.shared.raw, raw) };
+                }
+                if let Some(ref shader_objects) = self.shader_objects {
+                    shader_objects.remove_module(raw);
+                }
                 unsafe { self.shared.raw.destroy_shader_module(raw, None) };
             }
             super::ShaderModule::Intermediate { .. } => {}
@@ -1977,9 +2321,43 @@ impl crate::Device for super::Device {
             super::PipelineCache,
         >,
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        let input_attachments = desc
+            .layout
+            .input_attachment_set
+            .filter(|_| desc.fragment_stage.as_ref().is_some_and(reads_framebuffer))
+            .map(|set| (desc.layout.raw, set));
+
+        // Multisampled attachments would need `SubpassDataMS` reads of the
+        // current sample.
+        if input_attachments.is_some() && desc.multisample.count > 1 {
+            return Err(crate::PipelineError::Linkage(
+                wgt::ShaderStages::FRAGMENT,
+                "framebuffer fetch of multisampled attachments is not supported".to_owned(),
+            ));
+        }
+
+        // Shader objects render without render passes, so they can't read
+        // input attachments.
+        if let Some(shader_objects) = self
+            .shader_objects
+            .as_ref()
+            .filter(|_| input_attachments.is_none())
+        {
+            if let Some(pipeline) = unsafe { shader_objects.create_pipeline(self, desc) }? {
+                self.counters.render_pipelines.add(1);
+                return Ok(super::RenderPipeline {
+                    raw: vk::Pipeline::null(),
+                    optimized: None,
+                    shader_objects: Some(Box::new(pipeline)),
+                    input_attachments: None,
+                });
+            }
+        }
+
+        let [viewport_state, scissor_state] = self.shared.viewport_dynamic_states();
         let dynamic_states = [
-            vk::DynamicState::VIEWPORT,
-            vk::DynamicState::SCISSOR,
+            viewport_state,
+            scissor_state,
             vk::DynamicState::BLEND_CONSTANTS,
             vk::DynamicState::STENCIL_REFERENCE,
         ];
@@ -2037,7 +2415,7 @@ impl crate::Device for super::Device {
                 compiled_vs = Some(self.compile_stage(
                     vertex_stage,
                     naga::ShaderStage::Vertex,
-                    &desc.layout.binding_arrays,
+                    desc.layout,
                 )?);
                 stages.push(compiled_vs.as_ref().unwrap().create_info);
             }
@@ -2046,28 +2424,19 @@ impl crate::Device for super::Device {
                 mesh_stage,
             } => {
                 if let Some(t) = task_stage.as_ref() {
-                    compiled_ts = Some(self.compile_stage(
-                        t,
-                        naga::ShaderStage::Task,
-                        &desc.layout.binding_arrays,
-                    )?);
+                    compiled_ts =
+                        Some(self.compile_stage(t, naga::ShaderStage::Task, desc.layout)?);
                     stages.push(compiled_ts.as_ref().unwrap().create_info);
                 }
-                compiled_ms = Some(self.compile_stage(
-                    mesh_stage,
-                    naga::ShaderStage::Mesh,
-                    &desc.layout.binding_arrays,
-                )?);
+                compiled_ms =
+                    Some(self.compile_stage(mesh_stage, naga::ShaderStage::Mesh, desc.layout)?);
                 stages.push(compiled_ms.as_ref().unwrap().create_info);
             }
         }
         let compiled_fs = match desc.fragment_stage {
             Some(ref stage) => {
-                let compiled = self.compile_stage(
-                    stage,
-                    naga::ShaderStage::Fragment,
-                    &desc.layout.binding_arrays,
-                )?;
+                let compiled =
+                    self.compile_stage(stage, naga::ShaderStage::Fragment, desc.layout)?;
                 stages.push(compiled.create_info);
                 Some(compiled)
             }
@@ -2129,10 +2498,16 @@ impl crate::Device for super::Device {
             }
         }
 
+        // The counts are dynamic along with the viewports when shader objects are used.
+        let viewport_count = if self.shared.private_caps.shader_object {
+            0
+        } else {
+            1
+        };
         let vk_viewport = vk::PipelineViewportStateCreateInfo::default()
             .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1);
+            .scissor_count(viewport_count)
+            .viewport_count(viewport_count);
 
         let vk_sample_mask = [
             desc.multisample.mask as u32,
@@ -2188,39 +2563,72 @@ impl crate::Device for super::Device {
 
         let raw_pass = self.shared.make_render_pass(compatible_rp_key)?;
 
-        let vk_infos = [{
-            vk::GraphicsPipelineCreateInfo::default()
-                .layout(desc.layout.raw)
-                .stages(&stages)
-                .vertex_input_state(&vk_vertex_input)
-                .input_assembly_state(&vk_input_assembly)
-                .rasterization_state(&vk_rasterization)
-                .viewport_state(&vk_viewport)
-                .multisample_state(&vk_multisample)
-                .depth_stencil_state(&vk_depth_stencil)
-                .color_blend_state(&vk_color_blend)
-                .dynamic_state(&vk_dynamic_state)
-                .render_pass(raw_pass)
-        }];
-
         let pipeline_cache = desc
             .cache
             .map(|it| it.raw)
             .unwrap_or(vk::PipelineCache::null());
 
-        let mut raw_vec = {
-            profiling::scope!("vkCreateGraphicsPipelines");
-            unsafe {
-                self.shared
-                    .raw
-                    .create_graphics_pipelines(pipeline_cache, &vk_infos, None)
-                    .map_err(|(_, e)| super::map_pipeline_err(e))
-            }?
+        let libraries = self.pipeline_libraries.as_ref().and_then(|libraries| {
+            Some((
+                libraries,
+                super::pipeline_library::LibraryKeys::new(desc, raw_pass)?,
+            ))
+        });
+        let mut pipeline = if let Some((libraries, keys)) = libraries {
+            let (vertex_stages, fragment_stages) =
+                stages.split_at(stages.len() - usize::from(compiled_fs.is_some()));
+            let parts = super::pipeline_library::PipelineParts {
+                layout: desc.layout.raw,
+                render_pass: raw_pass,
+                cache: pipeline_cache,
+                label: desc.label,
+                vertex_input: &vk_vertex_input,
+                input_assembly: &vk_input_assembly,
+                vertex_stages,
+                viewport: &vk_viewport,
+                rasterization: &vk_rasterization,
+                fragment_stages,
+                depth_stencil: &vk_depth_stencil,
+                multisample: &vk_multisample,
+                color_blend: &vk_color_blend,
+            };
+            unsafe { libraries.create_pipeline(&self.shared, keys, &parts) }?
+        } else {
+            let vk_infos = [{
+                vk::GraphicsPipelineCreateInfo::default()
+                    .layout(desc.layout.raw)
+                    .stages(&stages)
+                    .vertex_input_state(&vk_vertex_input)
+                    .input_assembly_state(&vk_input_assembly)
+                    .rasterization_state(&vk_rasterization)
+                    .viewport_state(&vk_viewport)
+                    .multisample_state(&vk_multisample)
+                    .depth_stencil_state(&vk_depth_stencil)
+                    .color_blend_state(&vk_color_blend)
+                    .dynamic_state(&vk_dynamic_state)
+                    .render_pass(raw_pass)
+            }];
+
+            let mut raw_vec = {
+                profiling::scope!("vkCreateGraphicsPipelines");
+                unsafe {
+                    self.shared
+                        .raw
+                        .create_graphics_pipelines(pipeline_cache, &vk_infos, None)
+                        .map_err(|(_, e)| super::map_pipeline_err(e))
+                }?
+            };
+            super::RenderPipeline {
+                raw: raw_vec.pop().unwrap(),
+                optimized: None,
+                shader_objects: None,
+                input_attachments: None,
+            }
         };
+        pipeline.input_attachments = input_attachments;
 
-        let raw = raw_vec.pop().unwrap();
         if let Some(label) = desc.label {
-            unsafe { self.shared.set_object_name(raw, label) };
+            unsafe { self.shared.set_object_name(pipeline.raw, label) };
         }
 
         if let Some(CompiledStage {
@@ -2254,11 +2662,17 @@ impl crate::Device for super::Device {
 
         self.counters.render_pipelines.add(1);
 
-        Ok(super::RenderPipeline { raw })
+        Ok(pipeline)
     }
 
     unsafe fn destroy_render_pipeline(&self, pipeline: super::RenderPipeline) {
-        unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
+        if let Some(optimized) = pipeline.optimized.and_then(|it| it.abandon()) {
+            unsafe { self.shared.raw.destroy_pipeline(optimized, None) };
+        }
+        // Shader objects are destroyed along with the last pipeline using them.
+        if pipeline.shader_objects.is_none() {
+            unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
+        }
 
         self.counters.render_pipelines.sub(1);
     }
@@ -2271,11 +2685,7 @@ impl crate::Device for super::Device {
             super::PipelineCache,
         >,
     ) -> Result<super::ComputePipeline, crate::PipelineError> {
-        let compiled = self.compile_stage(
-            &desc.stage,
-            naga::ShaderStage::Compute,
-            &desc.layout.binding_arrays,
-        )?;
+        let compiled = self.compile_stage(&desc.stage, naga::ShaderStage::Compute, desc.layout)?;
 
         let vk_infos = [{
             vk::ComputePipelineCreateInfo::default()
@@ -2336,6 +2746,9 @@ impl crate::Device for super::Device {
         Some(self.shared.pipeline_cache_validation_key)
     }
     unsafe fn destroy_pipeline_cache(&self, cache: super::PipelineCache) {
+        if let Some(ref libraries) = self.pipeline_libraries {
+            libraries.wait_for_relinks_with_cache(cache.raw);
+        }
         unsafe { self.shared.raw.destroy_pipeline_cache(cache.raw, None) }
     }
     unsafe fn create_query_set(
@@ -2659,13 +3072,19 @@ impl crate::Device for super::Device {
             .as_ref()
             .expect("Feature `RAY_TRACING` not enabled");
 
+        let queue_family_indices = self.shared.queue_family_indices();
         let vk_buffer_info = vk::BufferCreateInfo::default()
             .size(desc.size)
             .usage(
                 vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR
                     | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
             )
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            // Acceleration structures have no ownership transfers.
+            .sharing_mode(conv::map_sharing_mode(
+                crate::MemoryFlags::empty(),
+                &queue_family_indices,
+            ))
+            .queue_family_indices(&queue_family_indices);
 
         unsafe {
             let raw_buffer = self
@@ -2784,6 +3203,61 @@ impl crate::Device for super::Device {
         }
     }
 
+    fn video_decode_capabilities(
+        &self,
+        codec: wgt::VideoCodec,
+        format: wgt::TextureFormat,
+    ) -> Option<wgt::VideoDecodeCapabilities> {
+        super::video::capabilities(&self.shared, codec, format)
+    }
+
+    unsafe fn create_video_decoder(
+        &self,
+        desc: &crate::VideoDecoderDescriptor,
+    ) -> Result<super::VideoDecoder, crate::DeviceError> {
+        unsafe { super::VideoDecoder::new(self, desc) }
+    }
+
+    unsafe fn destroy_video_decoder(&self, decoder: super::VideoDecoder) {
+        unsafe { decoder.destroy(&self.shared) };
+    }
+
+    unsafe fn create_video_picture(
+        &self,
+        decoder: &super::VideoDecoder,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unsafe { decoder.create_picture(self, desc) }
+    }
+
+    unsafe fn create_queue(
+        &self,
+        queue_type: wgt::QueueType,
+        index: u32,
+    ) -> Result<super::Queue, crate::DeviceError> {
+        let family = match queue_type {
+            wgt::QueueType::Graphics => None,
+            wgt::QueueType::Compute => self.shared.compute_queues,
+            wgt::QueueType::Transfer => self.shared.transfer_queues,
+        }
+        .filter(|family| index < family.queue_count)
+        .ok_or(crate::DeviceError::Unexpected)?;
+
+        let raw = unsafe { self.shared.raw.get_device_queue(family.index, index) };
+        let relay_semaphores = super::RelaySemaphores::new(&self.shared)?;
+
+        Ok(super::Queue {
+            raw,
+            swapchain_fn: khr::swapchain::Device::new(&self.shared.instance.raw, &self.shared.raw),
+            device: Arc::clone(&self.shared),
+            family_index: family.index,
+            queue_type,
+            relay_semaphores: Mutex::new(relay_semaphores),
+            signal_semaphores: Default::default(),
+            wait_semaphores: Default::default(),
+        })
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters {
         self.counters
             .memory_allocations
@@ -2804,6 +3278,24 @@ impl crate::Device for super::Device {
         bytemuck::bytes_of(&temp).to_vec()
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        let (memory_properties, memory_budget_properties) = self.query_memory_budget()?;
+
+        let heaps = memory_properties.memory_heaps_as_slice();
+        let heaps = heaps
+            .iter()
+            .zip(memory_budget_properties.heap_budget)
+            .zip(memory_budget_properties.heap_usage)
+            .map(|((heap, budget), usage)| wgt::MemoryHeapBudget {
+                device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                budget,
+                usage,
+            })
+            .collect();
+
+        Some(wgt::MemoryBudgetReport { heaps })
+    }
+
     fn check_if_oom(&self) -> Result<(), crate::DeviceError> {
         let Some(threshold) = self
             .shared
@@ -2814,34 +3306,9 @@ impl crate::Device for super::Device {
             return Ok(());
         };
 
-        if !self
-            .shared
-            .enabled_extensions
-            .contains(&ext::memory_budget::NAME)
-        {
+        let Some((memory_properties, memory_budget_properties)) = self.query_memory_budget() else {
             return Ok(());
-        }
-
-        let get_physical_device_properties = self
-            .shared
-            .instance
-            .get_physical_device_properties
-            .as_ref()
-            .unwrap();
-
-        let mut memory_budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
-
-        let mut memory_properties =
-            vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut memory_budget_properties);
-
-        unsafe {
-            get_physical_device_properties.get_physical_device_memory_properties2(
-                self.shared.physical_device,
-                &mut memory_properties,
-            );
-        }
-
-        let memory_properties = memory_properties.memory_properties;
+        };
 
         for i in 0..memory_properties.memory_heap_count {
             let heap_usage = memory_budget_properties.heap_usage[i as usize];
@@ -2857,6 +3324,34 @@ impl crate::Device for super::Device {
 }
 
 impl super::DeviceShared {
+    /// The families of the queues the device was created with, other than the
+    /// video decode queue.
+    ///
+    /// Buffers and images not created with [`crate::MemoryFlags::QUEUE_EXCLUSIVE`]
+    /// are shared between the families concurrently when there is more than
+    /// one, so that they can be used by any queue without ownership transfers.
+    pub(super) fn queue_family_indices(&self) -> ArrayVec<u32, 3> {
+        let mut indices = ArrayVec::new();
+        indices.push(self.family_index);
+        for family in [self.compute_queues, self.transfer_queues]
+            .into_iter()
+            .flatten()
+        {
+            indices.push(family.index);
+        }
+        indices
+    }
+
+    /// The family of the queues of `queue_type`.
+    pub(super) fn queue_family_index(&self, queue_type: wgt::QueueType) -> u32 {
+        match queue_type {
+            wgt::QueueType::Graphics => None,
+            wgt::QueueType::Compute => self.compute_queues,
+            wgt::QueueType::Transfer => self.transfer_queues,
+        }
+        .map_or(self.family_index, |family| family.index)
+    }
+
     pub(super) fn new_binary_semaphore(
         &self,
         name: &str,
@@ -2967,8 +3462,8 @@ fn handle_unexpected(err: vk::Result) -> ! {
     panic!("Unexpected Vulkan error: `{err}`")
 }
 
-struct ImageWithoutMemory {
-    raw: vk::Image,
-    requirements: vk::MemoryRequirements,
-    copy_size: crate::CopyExtent,
+pub(super) struct ImageWithoutMemory {
+    pub raw: vk::Image,
+    pub requirements: vk::MemoryRequirements,
+    pub copy_size: crate::CopyExtent,
 }
@@ -1166,6 +1166,8 @@ impl crate::Surface for super::Surface {
                     depth: 1,
                 },
                 identity,
+                video_view: None,
+                sparse: None,
             },
             acquire_semaphores: acquire_semaphore_arc,
             present_semaphores: present_semaphore_arc,
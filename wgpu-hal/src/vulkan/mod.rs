@@ -17,6 +17,25 @@ any of the image views (they have) gets removed.
 If Vulkan supports image-less framebuffers,
 then the actual views are excluded from the framebuffer key.
 
+## Pipeline libraries
+
+If `VK_EXT_graphics_pipeline_library` is available, render pipelines are linked
+from separately built vertex input, pre-rasterization, fragment shader and
+fragment output libraries. The libraries are cached on the device, so that a
+shader shared between pipelines is only compiled once. Pipelines are linked
+without link-time optimization first, and relinked with it on a background
+thread, replacing the original pipeline once done. See [`pipeline_library`].
+
+## Shader objects
+
+If the device supports `VK_EXT_shader_object`, render pipelines are made of
+its shaders instead of pipeline objects, and the rest of their state is set
+dynamically when they are bound. Shaders are cached on the device, so that
+pipelines only differing in their fixed-function state share them. Pipelines
+whose shaders can't be created fall back to pipeline objects. The viewport and
+scissor rectangle are then always set with their count, so that they stay
+valid across pipeline objects and shader objects. See [`shader_object`].
+
 ## Fences
 
 If timeline semaphores are available, they are used 1:1 with wgpu-hal fences.
@@ -30,10 +49,15 @@ mod conv;
 mod device;
 mod drm;
 mod instance;
+mod pipeline_library;
 mod sampler;
 mod semaphore_list;
+mod shader_object;
+mod sparse;
+mod video;
 
 pub use adapter::PhysicalDeviceFeatures;
+pub use video::VideoDecoder;
 
 use alloc::{boxed::Box, ffi::CString, sync::Arc, vec::Vec};
 use core::{borrow::Borrow, ffi::CStr, fmt, marker::PhantomData, mem, num::NonZeroU32};
@@ -51,6 +75,21 @@ use semaphore_list::SemaphoreList;
 
 const MILLIS_TO_NANOS: u64 = 1_000_000;
 const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 1;
+/// The number of input attachments read with framebuffer fetch: the color
+/// attachments, followed by the depth and stencil aspects.
+const FRAMEBUFFER_FETCH_INPUT_ATTACHMENTS: u32 =
+    naga::back::spv::STENCIL_INPUT_ATTACHMENT_INDEX + 1;
+/// The stages that write attachments read with framebuffer fetch.
+const FRAMEBUFFER_FETCH_SRC_STAGES: vk::PipelineStageFlags = vk::PipelineStageFlags::from_raw(
+    vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT.as_raw()
+        | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS.as_raw()
+        | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS.as_raw(),
+);
+/// The accesses that write attachments read with framebuffer fetch.
+const FRAMEBUFFER_FETCH_SRC_ACCESS: vk::AccessFlags = vk::AccessFlags::from_raw(
+    vk::AccessFlags::COLOR_ATTACHMENT_WRITE.as_raw()
+        | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE.as_raw(),
+);
 
 #[derive(Clone, Debug)]
 pub struct Api;
@@ -83,6 +122,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type VideoDecoder = VideoDecoder;
 }
 
 crate::impl_dyn_resource!(
@@ -107,7 +147,8 @@ crate::impl_dyn_resource!(
     Surface,
     SurfaceTexture,
     Texture,
-    TextureView
+    TextureView,
+    VideoDecoder
 );
 
 struct DebugUtils {
@@ -556,6 +597,7 @@ struct DeviceExtensionFunctions {
     timeline_semaphore: Option<ExtensionFn<khr::timeline_semaphore::Device>>,
     ray_tracing: Option<RayTracingDeviceExtensionFunctions>,
     mesh_shading: Option<ext::mesh_shader::Device>,
+    shader_object: Option<ext::shader_object::Device>,
 }
 
 struct RayTracingDeviceExtensionFunctions {
@@ -632,6 +674,46 @@ struct PrivateCapabilities {
     /// [`VK_KHR_shader_float16_int8`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_shader_float16_int8.html
     /// [see spec]: https://registry.khronos.org/vulkan/specs/latest/man/html/VkPhysicalDeviceShaderFloat16Int8Features.html#extension-features-shaderInt8
     shader_int8: bool,
+
+    /// True if this adapter supports the [`graphicsPipelineLibrary`] feature of
+    /// [`VK_EXT_graphics_pipeline_library`].
+    ///
+    /// [`graphicsPipelineLibrary`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VkPhysicalDeviceGraphicsPipelineLibraryFeaturesEXT.html
+    /// [`VK_EXT_graphics_pipeline_library`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_graphics_pipeline_library.html
+    graphics_pipeline_library: bool,
+
+    /// True if linking pipeline libraries without link-time optimization is fast,
+    /// so that it's worth relinking them with it in the background.
+    graphics_pipeline_library_fast_linking: bool,
+
+    /// True if render pipelines should use shader objects, which requires Vulkan 1.3
+    /// and the [`shaderObject`] feature.
+    ///
+    /// [`shaderObject`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VkPhysicalDeviceShaderObjectFeaturesEXT.html
+    shader_object: bool,
+
+    /// The queue family pictures can be decoded on, if the adapter supports
+    /// [`VK_KHR_video_decode_queue`] and is Vulkan 1.3.
+    ///
+    /// [`VK_KHR_video_decode_queue`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_video_decode_queue.html
+    video_decode: Option<video::VideoDecodeFamily>,
+
+    /// The queue family without graphics that compute queues are opened from,
+    /// if the adapter has one.
+    compute_queue_family: Option<DedicatedQueueFamily>,
+
+    /// The queue family with neither graphics nor compute that transfer queues
+    /// are opened from, if the adapter has one.
+    transfer_queue_family: Option<DedicatedQueueFamily>,
+}
+
+/// A queue family dedicated to compute or transfer work.
+#[derive(Clone, Copy, Debug)]
+struct DedicatedQueueFamily {
+    index: u32,
+    /// The number of queues of the family, or the number of them a device was
+    /// created with.
+    queue_count: u32,
 }
 
 bitflags::bitflags!(
@@ -733,10 +815,28 @@ struct DeviceShared {
     texture_identity_factory: ResourceIdentityFactory<vk::Image>,
     /// As above, for texture views.
     texture_view_identity_factory: ResourceIdentityFactory<vk::ImageView>,
+    /// The video decode queue, if [`wgt::Features::VIDEO_DECODE`] is enabled.
+    video: Option<video::VideoQueue>,
+    /// The family and number of the compute queues the device was created with.
+    compute_queues: Option<DedicatedQueueFamily>,
+    /// The family and number of the transfer queues the device was created with.
+    transfer_queues: Option<DedicatedQueueFamily>,
+    /// The layout of the descriptor set of input attachments, if framebuffer
+    /// fetch is enabled.
+    ///
+    /// Every pipeline layout ends with it, and every render pass declares its
+    /// attachments as input attachments then.
+    input_attachment_set_layout: Option<vk::DescriptorSetLayout>,
 }
 
 impl Drop for DeviceShared {
     fn drop(&mut self) {
+        if let Some(ref video) = self.video {
+            unsafe { self.raw.destroy_semaphore(video.semaphore, None) };
+        }
+        if let Some(layout) = self.input_attachment_set_layout {
+            unsafe { self.raw.destroy_descriptor_set_layout(layout, None) };
+        }
         for &raw in self.render_passes.lock().values() {
             unsafe { self.raw.destroy_render_pass(raw, None) };
         }
@@ -756,10 +856,17 @@ pub struct Device {
     #[cfg(feature = "renderdoc")]
     render_doc: crate::auxil::renderdoc::RenderDoc,
     counters: Arc<wgt::HalCounters>,
+    /// Cache of render pipeline parts, if `VK_EXT_graphics_pipeline_library` is enabled.
+    pipeline_libraries: Option<pipeline_library::PipelineLibraries>,
+    /// Cache of shaders of render pipelines, if `VK_EXT_shader_object` is used.
+    shader_objects: Option<shader_object::ShaderObjects>,
 }
 
 impl Drop for Device {
     fn drop(&mut self) {
+        if let Some(libraries) = self.pipeline_libraries.take() {
+            unsafe { libraries.destroy(&self.shared.raw) };
+        }
         unsafe { self.mem_allocator.lock().cleanup(&*self.shared) };
         unsafe { self.desc_allocator.lock().cleanup(&*self.shared) };
     }
@@ -845,8 +952,12 @@ pub struct Queue {
     swapchain_fn: khr::swapchain::Device,
     device: Arc<DeviceShared>,
     family_index: u32,
+    queue_type: wgt::QueueType,
     relay_semaphores: Mutex<RelaySemaphores>,
     signal_semaphores: Mutex<SemaphoreList>,
+    /// The timeline semaphores of other queues, and the values the next
+    /// submission waits for them to reach.
+    wait_semaphores: Mutex<Vec<(vk::Semaphore, u64)>>,
 }
 
 impl Queue {
@@ -893,6 +1004,8 @@ impl BufferMemoryBacking {
 pub struct Buffer {
     raw: vk::Buffer,
     block: Option<Mutex<BufferMemoryBacking>>,
+    /// The pages bound to the buffer, if it was created with [`crate::MemoryFlags::SPARSE`].
+    sparse: Option<Mutex<sparse::SparseBuffer>>,
 }
 impl Buffer {
     /// # Safety
@@ -903,6 +1016,7 @@ impl Buffer {
         Self {
             raw: vk_buffer,
             block: None,
+            sparse: None,
         }
     }
     /// # Safety
@@ -922,6 +1036,7 @@ impl Buffer {
                 offset,
                 size,
             })),
+            sparse: None,
         }
     }
 }
@@ -947,6 +1062,10 @@ pub struct Texture {
     format: wgt::TextureFormat,
     copy_size: crate::CopyExtent,
     identity: ResourceIdentity<vk::Image>,
+    /// The view video decodes are done through, if this is a video picture.
+    video_view: Option<vk::ImageView>,
+    /// The pages bound to the texture, if it was created with [`crate::MemoryFlags::SPARSE`].
+    sparse: Option<Mutex<sparse::SparseTexture>>,
 }
 
 impl crate::DynTexture for Texture {}
@@ -964,6 +1083,7 @@ impl Texture {
 pub struct TextureView {
     raw_texture: vk::Image,
     raw: vk::ImageView,
+    base_array_layer: u32,
     layers: NonZeroU32,
     format: wgt::TextureFormat,
     raw_format: vk::Format,
@@ -1015,6 +1135,12 @@ impl crate::DynBindGroupLayout for BindGroupLayout {}
 pub struct PipelineLayout {
     raw: vk::PipelineLayout,
     binding_arrays: naga::back::spv::BindingMap,
+    /// Interface of the layout, that shader objects are created with.
+    set_layouts: Vec<vk::DescriptorSetLayout>,
+    push_constant_ranges: Vec<vk::PushConstantRange>,
+    /// The index of the descriptor set of input attachments, after the bind
+    /// groups, if framebuffer fetch is enabled.
+    input_attachment_set: Option<u32>,
 }
 
 impl crate::DynPipelineLayout for PipelineLayout {}
@@ -1141,8 +1267,27 @@ struct TempTextureViewKey {
     /// even if the Image handles are the same between different images.
     texture_identity: ResourceIdentity<vk::Image>,
     format: vk::Format,
+    aspect: vk::ImageAspectFlags,
     mip_level: u32,
-    depth_slice: u32,
+    /// The first array layer, or the depth slice of a 3D texture.
+    first_layer: u32,
+    layer_count: u32,
+}
+
+/// The attachments of the current render pass, as input attachments.
+struct RenderPassInputAttachments {
+    colors: ArrayVec<Option<vk::ImageView>, { crate::MAX_COLOR_ATTACHMENTS }>,
+    depth: Option<vk::ImageView>,
+    stencil: Option<vk::ImageView>,
+    /// The layout of the depth/stencil attachment in the pass.
+    depth_stencil_layout: vk::ImageLayout,
+    multiview: bool,
+    /// The descriptor set of the input attachments, allocated once a pipeline
+    /// reads them.
+    set: Option<vk::DescriptorSet>,
+    /// Whether the current pipeline reads them, which makes the writes of
+    /// earlier draws visible before each draw.
+    read: bool,
 }
 
 pub struct CommandEncoder {
@@ -1157,6 +1302,10 @@ pub struct CommandEncoder {
     /// If non-`null`, the buffer is in the Vulkan "recording" state.
     active: vk::CommandBuffer,
 
+    /// The type of the queue the command buffers are submitted to, which
+    /// restricts the stages of their barriers.
+    queue_type: wgt::QueueType,
+
     /// What kind of pass we are currently within: compute or render.
     bind_point: vk::PipelineBindPoint,
 
@@ -1184,6 +1333,19 @@ pub struct CommandEncoder {
     framebuffers: FastHashMap<FramebufferKey, vk::Framebuffer>,
     temp_texture_views: FastHashMap<TempTextureViewKey, IdentifiedTextureView>,
 
+    /// The attachments of the current render pass, if framebuffer fetch is enabled.
+    rpass_input_attachments: Option<RenderPassInputAttachments>,
+    /// Pools of the descriptor sets of [`RenderPassInputAttachments`], which
+    /// are reset along with the command buffers. Sets are allocated from the
+    /// last one.
+    input_attachment_pools: Vec<vk::DescriptorPool>,
+
+    /// If true, command buffers are recorded with
+    /// `VK_COMMAND_BUFFER_USAGE_SIMULTANEOUS_USE_BIT` instead of
+    /// `VK_COMMAND_BUFFER_USAGE_ONE_TIME_SUBMIT_BIT`, so they can be submitted
+    /// again while pending.
+    reusable: bool,
+
     counters: Arc<wgt::HalCounters>,
 }
 
@@ -1214,6 +1376,10 @@ impl Drop for CommandEncoder {
             unsafe { self.device.raw.destroy_image_view(view.raw, None) };
         }
 
+        for pool in self.input_attachment_pools.drain(..) {
+            unsafe { self.device.raw.destroy_descriptor_pool(pool, None) };
+        }
+
         self.counters.command_encoders.sub(1);
     }
 }
@@ -1257,6 +1423,23 @@ impl crate::DynShaderModule for ShaderModule {}
 #[derive(Debug)]
 pub struct RenderPipeline {
     raw: vk::Pipeline,
+    /// Link-time optimized replacement of `raw`, once it's been linked in the background.
+    optimized: Option<Arc<pipeline_library::OptimizedPipeline>>,
+    /// The shaders and state to bind instead of `raw`, which is then null.
+    shader_objects: Option<Box<shader_object::ShaderObjectPipeline>>,
+    /// The layout to bind the input attachments with, and their set index, if
+    /// the fragment shader reads attachments with framebuffer fetch.
+    input_attachments: Option<(vk::PipelineLayout, u32)>,
+}
+
+impl RenderPipeline {
+    /// Returns the best version of the pipeline that is available.
+    fn current(&self) -> vk::Pipeline {
+        self.optimized
+            .as_ref()
+            .and_then(|optimized| optimized.get())
+            .unwrap_or(self.raw)
+    }
 }
 
 impl crate::DynRenderPipeline for RenderPipeline {}
@@ -1506,6 +1689,30 @@ impl crate::Queue for Queue {
 
         signal_semaphores.push_binary(semaphore_state.signal);
 
+        // Wait for the pictures decoded since the last submission.
+        let mut wait_values = Vec::new();
+        if let Some(video) = self
+            .device
+            .video
+            .as_ref()
+            .filter(|_| self.queue_type == wgt::QueueType::Graphics)
+        {
+            if let Some(value) = video.take_wait_value() {
+                wait_values.resize(wait_semaphores.len(), !0);
+                wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+                wait_semaphores.push(video.semaphore);
+                wait_values.push(value);
+            }
+        }
+
+        // Wait for the submissions of other queues this one depends on.
+        for (semaphore, value) in self.wait_semaphores.lock().drain(..) {
+            wait_values.resize(wait_semaphores.len(), !0);
+            wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+            wait_semaphores.push(semaphore);
+            wait_values.push(value);
+        }
+
         // We need to signal our wgpu::Fence if we have one, this adds it to the signal list.
         signal_fence.maintain(&self.device.raw)?;
         match *signal_fence {
@@ -1542,7 +1749,7 @@ impl crate::Queue for Queue {
             .wait_dst_stage_mask(&wait_stage_masks);
 
         let mut vk_timeline_info = mem::MaybeUninit::uninit();
-        vk_info = signal_semaphores.add_to_submit(vk_info, &mut vk_timeline_info);
+        vk_info = signal_semaphores.add_to_submit(vk_info, &mut vk_timeline_info, &wait_values);
 
         profiling::scope!("vkQueueSubmit");
         unsafe {
@@ -1627,6 +1834,38 @@ impl crate::Queue for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.device.timestamp_period
     }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            Fence::TimelineSemaphore(raw) => {
+                self.wait_semaphores.lock().push((raw, value));
+                Ok(())
+            }
+            // Multiple queues are only exposed with timeline semaphores.
+            Fence::FencePool { .. } => Err(crate::DeviceError::Unexpected),
+        }
+    }
+
+    unsafe fn decode_video(
+        &self,
+        decoder: &VideoDecoder,
+        desc: &crate::VideoDecodeDescriptor<Texture>,
+        wait_fence: (&Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unsafe { decoder.decode(&self.device, desc, wait_fence) }
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_, Buffer>],
+        texture_binds: &[crate::SparseTextureBind<'_, Texture>],
+    ) -> Result<(), crate::DeviceError> {
+        unsafe { self.bind_sparse(buffer_binds, texture_binds) }
+    }
 }
 
 impl Queue {
@@ -0,0 +1,925 @@
+//! Render pipelines linked from graphics pipeline libraries.
+//!
+//! With `VK_EXT_graphics_pipeline_library`, the state of a render pipeline is split into four
+//! parts that are compiled into libraries of their own: the vertex input interface, the
+//! pre-rasterization shaders, the fragment shader and the fragment output interface. Libraries
+//! are cached on the device by the state they're built from, so that pipelines sharing a vertex
+//! shader only compile it once, and a full pipeline is linked from four libraries.
+//!
+//! Linking without link-time optimization gives slower pipelines, but is cheap on
+//! implementations advertising `graphicsPipelineLibraryFastLinking`. There, pipelines are linked
+//! without it first, and linked again with it on a background thread. The optimized pipeline is
+//! then bound instead of the original one, see [`RenderPipeline::current`].
+//!
+//! Libraries built from shader modules that are compiled for each pipeline can't be shared,
+//! so they're destroyed once linked.
+//!
+//! Destroying a shader module or pipeline layout evicts the libraries built from it. They are
+//! destroyed once the relinks using them are done, without waiting for the other relinks.
+//!
+//! [`RenderPipeline::current`]: super::RenderPipeline::current
+
+use alloc::{borrow::ToOwned, string::String, sync::Arc, vec::Vec};
+use core::{
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::{sync::mpsc, thread};
+
+use ash::vk::{self, Handle as _};
+use naga::FastHashMap;
+use parking_lot::{Condvar, Mutex};
+
+/// Shader stage a library is built from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct StageKey {
+    module: vk::ShaderModule,
+    entry_point: String,
+    zero_initialize_workgroup_memory: bool,
+}
+
+impl StageKey {
+    /// Returns `None` if `stage` is compiled for each pipeline, so its library can't be shared.
+    fn new(stage: &crate::ProgrammableStage<super::ShaderModule>) -> Option<Self> {
+        match *stage.module {
+            super::ShaderModule::Raw(module) => Some(Self {
+                module,
+                entry_point: stage.entry_point.to_owned(),
+                zero_initialize_workgroup_memory: stage.zero_initialize_workgroup_memory,
+            }),
+            super::ShaderModule::Intermediate { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct VertexInputKey {
+    buffers: Vec<(
+        wgt::BufferAddress,
+        wgt::VertexStepMode,
+        Vec<wgt::VertexAttribute>,
+    )>,
+    topology: wgt::PrimitiveTopology,
+    primitive_restart: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct PreRasterizationKey {
+    layout: vk::PipelineLayout,
+    render_pass: vk::RenderPass,
+    vertex_stage: StageKey,
+    /// The rasterization state, without the fields belonging to the vertex input.
+    primitive: wgt::PrimitiveState,
+    depth_bias: Option<wgt::DepthBiasState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct FragmentShaderKey {
+    layout: vk::PipelineLayout,
+    render_pass: vk::RenderPass,
+    fragment_stage: Option<StageKey>,
+    depth_stencil: Option<wgt::DepthStencilState>,
+    multisample: wgt::MultisampleState,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct FragmentOutputKey {
+    render_pass: vk::RenderPass,
+    color_targets: Vec<Option<wgt::ColorTargetState>>,
+    multisample: wgt::MultisampleState,
+}
+
+/// The state each library of a render pipeline is built from.
+///
+/// Libraries without a key aren't cached.
+pub(super) struct LibraryKeys {
+    vertex_input: VertexInputKey,
+    pre_rasterization: Option<PreRasterizationKey>,
+    fragment_shader: Option<FragmentShaderKey>,
+    fragment_output: FragmentOutputKey,
+}
+
+impl LibraryKeys {
+    /// Returns `None` if the pipeline can't be linked from libraries.
+    pub fn new(
+        desc: &crate::RenderPipelineDescriptor<
+            super::PipelineLayout,
+            super::ShaderModule,
+            super::PipelineCache,
+        >,
+        render_pass: vk::RenderPass,
+    ) -> Option<Self> {
+        let crate::VertexProcessor::Standard {
+            vertex_buffers,
+            ref vertex_stage,
+        } = desc.vertex_processor
+        else {
+            return None;
+        };
+        let layout = desc.layout.raw;
+
+        let vertex_input = VertexInputKey {
+            buffers: vertex_buffers
+                .iter()
+                .map(|vb| (vb.array_stride, vb.step_mode, vb.attributes.to_vec()))
+                .collect(),
+            topology: desc.primitive.topology,
+            primitive_restart: desc.primitive.strip_index_format.is_some(),
+        };
+        let pre_rasterization =
+            StageKey::new(vertex_stage).map(|vertex_stage| PreRasterizationKey {
+                layout,
+                render_pass,
+                vertex_stage,
+                primitive: wgt::PrimitiveState {
+                    topology: Default::default(),
+                    strip_index_format: None,
+                    ..desc.primitive
+                },
+                depth_bias: desc.depth_stencil.as_ref().map(|ds| ds.bias),
+            });
+        let fragment_stage = match desc.fragment_stage {
+            Some(ref stage) => StageKey::new(stage).map(Some),
+            None => Some(None),
+        };
+        let fragment_shader = fragment_stage.map(|fragment_stage| FragmentShaderKey {
+            layout,
+            render_pass,
+            fragment_stage,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+        });
+        let fragment_output = FragmentOutputKey {
+            render_pass,
+            color_targets: desc.color_targets.to_vec(),
+            multisample: desc.multisample,
+        };
+
+        Some(Self {
+            vertex_input,
+            pre_rasterization,
+            fragment_shader,
+            fragment_output,
+        })
+    }
+}
+
+/// State of a render pipeline, as it's split between the libraries.
+pub(super) struct PipelineParts<'a> {
+    pub layout: vk::PipelineLayout,
+    pub render_pass: vk::RenderPass,
+    pub cache: vk::PipelineCache,
+    pub label: Option<&'a str>,
+    pub vertex_input: &'a vk::PipelineVertexInputStateCreateInfo<'a>,
+    pub input_assembly: &'a vk::PipelineInputAssemblyStateCreateInfo<'a>,
+    pub vertex_stages: &'a [vk::PipelineShaderStageCreateInfo<'a>],
+    pub viewport: &'a vk::PipelineViewportStateCreateInfo<'a>,
+    pub rasterization: &'a vk::PipelineRasterizationStateCreateInfo<'a>,
+    pub fragment_stages: &'a [vk::PipelineShaderStageCreateInfo<'a>],
+    pub depth_stencil: &'a vk::PipelineDepthStencilStateCreateInfo<'a>,
+    pub multisample: &'a vk::PipelineMultisampleStateCreateInfo<'a>,
+    pub color_blend: &'a vk::PipelineColorBlendStateCreateInfo<'a>,
+}
+
+/// Libraries for one part of the pipeline state, by the state they were built from.
+struct LibraryCache<K>(Mutex<FastHashMap<K, vk::Pipeline>>);
+
+impl<K> Default for LibraryCache<K> {
+    fn default() -> Self {
+        Self(Mutex::new(FastHashMap::default()))
+    }
+}
+
+impl<K: Eq + Hash> LibraryCache<K> {
+    /// Returns the library for `key`, building it with `create` if it isn't cached yet.
+    ///
+    /// Libraries without a key are pushed to `temporary` instead of being cached.
+    fn get_or_create(
+        &self,
+        raw: &ash::Device,
+        key: Option<K>,
+        temporary: &mut Vec<vk::Pipeline>,
+        create: impl FnOnce() -> Result<vk::Pipeline, crate::DeviceError>,
+    ) -> Result<vk::Pipeline, crate::DeviceError> {
+        let Some(key) = key else {
+            let library = create()?;
+            temporary.push(library);
+            return Ok(library);
+        };
+        if let Some(&library) = self.0.lock().get(&key) {
+            return Ok(library);
+        }
+
+        // Don't hold the lock while compiling, so that other pipelines can be created meanwhile.
+        let library = create()?;
+        let mut libraries = self.0.lock();
+        if let Some(&existing) = libraries.get(&key) {
+            unsafe { raw.destroy_pipeline(library, None) };
+            return Ok(existing);
+        }
+        libraries.insert(key, library);
+        Ok(library)
+    }
+
+    /// Removes the libraries whose key matches `predicate` from the cache, and pushes them
+    /// to `taken`.
+    fn take(&self, predicate: impl Fn(&K) -> bool, taken: &mut Vec<vk::Pipeline>) {
+        self.0.lock().retain(|key, &mut library| {
+            if predicate(key) {
+                taken.push(library);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    unsafe fn destroy(self, raw: &ash::Device) {
+        for library in self.0.into_inner().into_values() {
+            unsafe { raw.destroy_pipeline(library, None) };
+        }
+    }
+}
+
+/// The link-time optimized version of a render pipeline, once it's been linked.
+#[derive(Debug, Default)]
+pub(super) struct OptimizedPipeline(AtomicU64);
+
+/// Value of an [`OptimizedPipeline`] whose render pipeline has been destroyed.
+const ABANDONED: u64 = u64::MAX;
+
+impl OptimizedPipeline {
+    pub fn get(&self) -> Option<vk::Pipeline> {
+        match self.0.load(Ordering::Acquire) {
+            0 | ABANDONED => None,
+            raw => Some(vk::Pipeline::from_raw(raw)),
+        }
+    }
+
+    /// Marks the render pipeline as destroyed, returning the optimized pipeline to destroy with it.
+    pub fn abandon(&self) -> Option<vk::Pipeline> {
+        match self.0.swap(ABANDONED, Ordering::AcqRel) {
+            0 | ABANDONED => None,
+            raw => Some(vk::Pipeline::from_raw(raw)),
+        }
+    }
+
+    fn is_abandoned(&self) -> bool {
+        self.0.load(Ordering::Acquire) == ABANDONED
+    }
+
+    /// Stores `pipeline`, or returns `false` if the render pipeline has been destroyed.
+    fn fulfill(&self, pipeline: vk::Pipeline) -> bool {
+        self.0
+            .compare_exchange(0, pipeline.as_raw(), Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+}
+
+/// The objects a [`RelinkJob`] uses, which can't be destroyed until it's run.
+#[derive(Clone, Copy, Debug)]
+struct RelinkUses {
+    libraries: [vk::Pipeline; 4],
+    layout: vk::PipelineLayout,
+    cache: vk::PipelineCache,
+}
+
+#[derive(Debug, Default)]
+struct PendingJobs {
+    next_id: u64,
+    uses: Vec<(u64, RelinkUses)>,
+}
+
+/// The relink jobs that haven't been run yet, by the objects they use.
+#[derive(Debug, Default)]
+struct PendingRelinks {
+    jobs: Mutex<PendingJobs>,
+    done: Condvar,
+}
+
+impl PendingRelinks {
+    /// Registers a job using `uses`, and returns the ID to [`Self::finish()`] it with.
+    fn add(&self, uses: RelinkUses) -> u64 {
+        let mut jobs = self.jobs.lock();
+        let id = jobs.next_id;
+        jobs.next_id += 1;
+        jobs.uses.push((id, uses));
+        id
+    }
+
+    fn finish(&self, id: u64) {
+        self.jobs.lock().uses.retain(|&(job, _)| job != id);
+        self.done.notify_all();
+    }
+
+    /// Waits until no pending job uses what `uses` matches.
+    fn wait_while(&self, uses: impl Fn(&RelinkUses) -> bool) {
+        let mut jobs = self.jobs.lock();
+        while jobs.uses.iter().any(|(_, job_uses)| uses(job_uses)) {
+            self.done.wait(&mut jobs);
+        }
+    }
+}
+
+/// A render pipeline to link again with link-time optimization.
+struct RelinkJob {
+    libraries: [vk::Pipeline; 4],
+    layout: vk::PipelineLayout,
+    cache: vk::PipelineCache,
+    /// Libraries that were built for this pipeline only, to destroy once it's linked.
+    temporary: Vec<vk::Pipeline>,
+    label: Option<String>,
+    target: Arc<OptimizedPipeline>,
+}
+
+impl RelinkJob {
+    fn uses(&self) -> RelinkUses {
+        RelinkUses {
+            libraries: self.libraries,
+            layout: self.layout,
+            cache: self.cache,
+        }
+    }
+
+    unsafe fn run(self, shared: &super::DeviceShared) {
+        if !self.target.is_abandoned() {
+            profiling::scope!("Relink render pipeline");
+            match unsafe { link(&shared.raw, self.cache, self.layout, &self.libraries, true) } {
+                Ok(raw) => {
+                    if let Some(ref label) = self.label {
+                        unsafe { shared.set_object_name(raw, label) };
+                    }
+                    if !self.target.fulfill(raw) {
+                        unsafe { shared.raw.destroy_pipeline(raw, None) };
+                    }
+                }
+                // The pipeline linked without optimization keeps being used.
+                Err(err) => log::warn!("Failed to relink render pipeline: {err}"),
+            }
+        }
+        for library in self.temporary {
+            unsafe { shared.raw.destroy_pipeline(library, None) };
+        }
+    }
+}
+
+/// Thread linking render pipelines with link-time optimization in the background.
+struct Relinker {
+    sender: mpsc::Sender<(u64, RelinkJob)>,
+    pending: Arc<PendingRelinks>,
+    thread: thread::JoinHandle<()>,
+}
+
+impl Relinker {
+    fn new(shared: Arc<super::DeviceShared>) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel::<(u64, RelinkJob)>();
+        let pending = Arc::new(PendingRelinks::default());
+        let thread_pending = Arc::clone(&pending);
+        let thread = thread::Builder::new()
+            .name("wgpu-hal pipeline relinker".into())
+            .spawn(move || {
+                for (id, job) in receiver {
+                    unsafe { job.run(&shared) };
+                    thread_pending.finish(id);
+                }
+            });
+        match thread {
+            Ok(thread) => Some(Self {
+                sender,
+                pending,
+                thread,
+            }),
+            Err(err) => {
+                log::warn!("Failed to spawn the pipeline relinking thread: {err}");
+                None
+            }
+        }
+    }
+
+    fn submit(&self, shared: &super::DeviceShared, job: RelinkJob) {
+        let id = self.pending.add(job.uses());
+        if let Err(mpsc::SendError((id, job))) = self.sender.send((id, job)) {
+            unsafe { job.run(shared) };
+            self.pending.finish(id);
+        }
+    }
+
+    fn stop(self) {
+        drop(self.sender);
+        if self.thread.join().is_err() {
+            log::error!("The pipeline relinking thread panicked");
+        }
+    }
+}
+
+/// Libraries render pipelines are linked from, cached on the device.
+pub(super) struct PipelineLibraries {
+    vertex_input: LibraryCache<VertexInputKey>,
+    pre_rasterization: LibraryCache<PreRasterizationKey>,
+    fragment_shader: LibraryCache<FragmentShaderKey>,
+    fragment_output: LibraryCache<FragmentOutputKey>,
+    /// Links optimized pipelines in the background, if linking without optimization is fast.
+    relinker: Option<Relinker>,
+}
+
+impl PipelineLibraries {
+    pub fn new(shared: &Arc<super::DeviceShared>) -> Self {
+        let relinker = if shared.private_caps.graphics_pipeline_library_fast_linking {
+            Relinker::new(Arc::clone(shared))
+        } else {
+            None
+        };
+        Self {
+            vertex_input: LibraryCache::default(),
+            pre_rasterization: LibraryCache::default(),
+            fragment_shader: LibraryCache::default(),
+            fragment_output: LibraryCache::default(),
+            relinker,
+        }
+    }
+
+    /// Links a render pipeline from the libraries for `keys`, building the ones that aren't
+    /// cached from `parts`.
+    pub unsafe fn create_pipeline(
+        &self,
+        shared: &super::DeviceShared,
+        keys: LibraryKeys,
+        parts: &PipelineParts<'_>,
+    ) -> Result<super::RenderPipeline, crate::DeviceError> {
+        let mut temporary = Vec::new();
+        let linked = unsafe { self.link_libraries(shared, keys, parts, &mut temporary) };
+        let (raw, libraries) = match linked {
+            Ok(linked) => linked,
+            Err(err) => {
+                for library in temporary {
+                    unsafe { shared.raw.destroy_pipeline(library, None) };
+                }
+                return Err(err);
+            }
+        };
+
+        let Some(ref relinker) = self.relinker else {
+            for library in temporary {
+                unsafe { shared.raw.destroy_pipeline(library, None) };
+            }
+            return Ok(super::RenderPipeline {
+                raw,
+                optimized: None,
+                shader_objects: None,
+                input_attachments: None,
+            });
+        };
+        let target = Arc::new(OptimizedPipeline::default());
+        let job = RelinkJob {
+            libraries,
+            layout: parts.layout,
+            cache: parts.cache,
+            temporary,
+            label: parts.label.map(ToOwned::to_owned),
+            target: Arc::clone(&target),
+        };
+        relinker.submit(shared, job);
+        Ok(super::RenderPipeline {
+            raw,
+            optimized: Some(target),
+            shader_objects: None,
+            input_attachments: None,
+        })
+    }
+
+    unsafe fn link_libraries(
+        &self,
+        shared: &super::DeviceShared,
+        keys: LibraryKeys,
+        parts: &PipelineParts<'_>,
+        temporary: &mut Vec<vk::Pipeline>,
+    ) -> Result<(vk::Pipeline, [vk::Pipeline; 4]), crate::DeviceError> {
+        use vk::GraphicsPipelineLibraryFlagsEXT as Flags;
+
+        let raw = &shared.raw;
+        let vertex_input =
+            self.vertex_input
+                .get_or_create(raw, Some(keys.vertex_input), temporary, || {
+                    let info = vk::GraphicsPipelineCreateInfo::default()
+                        .vertex_input_state(parts.vertex_input)
+                        .input_assembly_state(parts.input_assembly);
+                    unsafe { create_library(raw, parts.cache, Flags::VERTEX_INPUT_INTERFACE, info) }
+                })?;
+        let pre_rasterization =
+            self.pre_rasterization
+                .get_or_create(raw, keys.pre_rasterization, temporary, || {
+                    let dynamic_states = shared.viewport_dynamic_states();
+                    let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+                        .dynamic_states(&dynamic_states);
+                    let info = vk::GraphicsPipelineCreateInfo::default()
+                        .layout(parts.layout)
+                        .stages(parts.vertex_stages)
+                        .viewport_state(parts.viewport)
+                        .rasterization_state(parts.rasterization)
+                        .dynamic_state(&dynamic_state)
+                        .render_pass(parts.render_pass);
+                    unsafe {
+                        create_library(raw, parts.cache, Flags::PRE_RASTERIZATION_SHADERS, info)
+                    }
+                })?;
+        let fragment_shader =
+            self.fragment_shader
+                .get_or_create(raw, keys.fragment_shader, temporary, || {
+                    let dynamic_states = [vk::DynamicState::STENCIL_REFERENCE];
+                    let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+                        .dynamic_states(&dynamic_states);
+                    let info = vk::GraphicsPipelineCreateInfo::default()
+                        .layout(parts.layout)
+                        .stages(parts.fragment_stages)
+                        .depth_stencil_state(parts.depth_stencil)
+                        .multisample_state(parts.multisample)
+                        .dynamic_state(&dynamic_state)
+                        .render_pass(parts.render_pass);
+                    unsafe { create_library(raw, parts.cache, Flags::FRAGMENT_SHADER, info) }
+                })?;
+        let fragment_output = self.fragment_output.get_or_create(
+            raw,
+            Some(keys.fragment_output),
+            temporary,
+            || {
+                let dynamic_states = [vk::DynamicState::BLEND_CONSTANTS];
+                let dynamic_state =
+                    vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
+                let info = vk::GraphicsPipelineCreateInfo::default()
+                    .color_blend_state(parts.color_blend)
+                    .multisample_state(parts.multisample)
+                    .dynamic_state(&dynamic_state)
+                    .render_pass(parts.render_pass);
+                unsafe { create_library(raw, parts.cache, Flags::FRAGMENT_OUTPUT_INTERFACE, info) }
+            },
+        )?;
+
+        let libraries = [
+            vertex_input,
+            pre_rasterization,
+            fragment_shader,
+            fragment_output,
+        ];
+        // Without fast linking, there's no point in linking twice.
+        let optimize = self.relinker.is_none();
+        let pipeline = unsafe { link(raw, parts.cache, parts.layout, &libraries, optimize) }
+            .map_err(super::map_pipeline_err)?;
+        Ok((pipeline, libraries))
+    }
+
+    /// Waits until the pipelines being relinked in the background with what `uses` matches
+    /// are done.
+    fn wait_for_relinks(&self, uses: impl Fn(&RelinkUses) -> bool) {
+        if let Some(ref relinker) = self.relinker {
+            relinker.pending.wait_while(uses);
+        }
+    }
+
+    /// Waits until the pipelines being relinked in the background with `cache` are done.
+    ///
+    /// This must be called before destroying `cache`.
+    pub fn wait_for_relinks_with_cache(&self, cache: vk::PipelineCache) {
+        self.wait_for_relinks(|uses| uses.cache == cache);
+    }
+
+    /// Removes the libraries built from `module` from the cache, and returns them.
+    fn take_shader_module(&self, module: vk::ShaderModule) -> Vec<vk::Pipeline> {
+        let uses_module = |stage: Option<&StageKey>| stage.is_some_and(|s| s.module == module);
+        let mut taken = Vec::new();
+        self.pre_rasterization
+            .take(|key| uses_module(Some(&key.vertex_stage)), &mut taken);
+        self.fragment_shader
+            .take(|key| uses_module(key.fragment_stage.as_ref()), &mut taken);
+        taken
+    }
+
+    /// Removes the libraries built with `layout` from the cache, and returns them.
+    fn take_pipeline_layout(&self, layout: vk::PipelineLayout) -> Vec<vk::Pipeline> {
+        let mut taken = Vec::new();
+        self.pre_rasterization
+            .take(|key| key.layout == layout, &mut taken);
+        self.fragment_shader
+            .take(|key| key.layout == layout, &mut taken);
+        taken
+    }
+
+    /// Destroys `libraries` once the relinks using them are done.
+    unsafe fn destroy_libraries(&self, raw: &ash::Device, libraries: Vec<vk::Pipeline>) {
+        if libraries.is_empty() {
+            return;
+        }
+        self.wait_for_relinks(|uses| uses.libraries.iter().any(|l| libraries.contains(l)));
+        for library in libraries {
+            unsafe { raw.destroy_pipeline(library, None) };
+        }
+    }
+
+    /// Destroys the libraries built from `module`.
+    ///
+    /// Relinks don't use shader modules themselves, only the libraries built from them.
+    pub unsafe fn evict_shader_module(&self, raw: &ash::Device, module: vk::ShaderModule) {
+        let libraries = self.take_shader_module(module);
+        unsafe { self.destroy_libraries(raw, libraries) };
+    }
+
+    /// Destroys the libraries built with `layout`, and waits until the relinks with `layout`
+    /// are done.
+    ///
+    /// This must be called before destroying `layout`.
+    pub unsafe fn evict_pipeline_layout(&self, raw: &ash::Device, layout: vk::PipelineLayout) {
+        let libraries = self.take_pipeline_layout(layout);
+        self.wait_for_relinks(|uses| uses.layout == layout);
+        unsafe { self.destroy_libraries(raw, libraries) };
+    }
+
+    pub unsafe fn destroy(self, raw: &ash::Device) {
+        if let Some(relinker) = self.relinker {
+            relinker.stop();
+        }
+        unsafe {
+            self.vertex_input.destroy(raw);
+            self.pre_rasterization.destroy(raw);
+            self.fragment_shader.destroy(raw);
+            self.fragment_output.destroy(raw);
+        }
+    }
+}
+
+unsafe fn create_library(
+    raw: &ash::Device,
+    cache: vk::PipelineCache,
+    flags: vk::GraphicsPipelineLibraryFlagsEXT,
+    info: vk::GraphicsPipelineCreateInfo<'_>,
+) -> Result<vk::Pipeline, crate::DeviceError> {
+    let mut library_info = vk::GraphicsPipelineLibraryCreateInfoEXT::default().flags(flags);
+    let info = info
+        .flags(
+            vk::PipelineCreateFlags::LIBRARY_KHR
+                | vk::PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO_EXT,
+        )
+        .push_next(&mut library_info);
+
+    profiling::scope!("vkCreateGraphicsPipelines");
+    let mut raw_vec = unsafe { raw.create_graphics_pipelines(cache, &[info], None) }
+        .map_err(|(_, e)| super::map_pipeline_err(e))?;
+    Ok(raw_vec.pop().unwrap())
+}
+
+unsafe fn link(
+    raw: &ash::Device,
+    cache: vk::PipelineCache,
+    layout: vk::PipelineLayout,
+    libraries: &[vk::Pipeline],
+    optimize: bool,
+) -> Result<vk::Pipeline, vk::Result> {
+    let mut library_info = vk::PipelineLibraryCreateInfoKHR::default().libraries(libraries);
+    let flags = if optimize {
+        vk::PipelineCreateFlags::LINK_TIME_OPTIMIZATION_EXT
+    } else {
+        vk::PipelineCreateFlags::empty()
+    };
+    let info = vk::GraphicsPipelineCreateInfo::default()
+        .flags(flags)
+        .layout(layout)
+        .push_next(&mut library_info);
+
+    profiling::scope!("vkCreateGraphicsPipelines");
+    let mut raw_vec =
+        unsafe { raw.create_graphics_pipelines(cache, &[info], None) }.map_err(|(_, e)| e)?;
+    Ok(raw_vec.pop().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::Cow, vec};
+    use core::{sync::atomic::AtomicBool, time::Duration};
+
+    use super::*;
+
+    fn module(raw: u64) -> super::super::ShaderModule {
+        super::super::ShaderModule::Raw(vk::ShaderModule::from_raw(raw))
+    }
+
+    fn layout(raw: u64) -> super::super::PipelineLayout {
+        super::super::PipelineLayout {
+            raw: vk::PipelineLayout::from_raw(raw),
+            binding_arrays: Default::default(),
+            set_layouts: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            input_attachment_set: None,
+        }
+    }
+
+    /// The keys of a pipeline drawing triangles with `vertex` and `fragment`.
+    fn keys(
+        layout: &super::super::PipelineLayout,
+        vertex: &super::super::ShaderModule,
+        fragment: &super::super::ShaderModule,
+        primitive: wgt::PrimitiveState,
+        blend: Option<wgt::BlendState>,
+    ) -> LibraryKeys {
+        let constants = Default::default();
+        let stage = |module, entry_point| crate::ProgrammableStage {
+            module,
+            entry_point,
+            constants: &constants,
+            zero_initialize_workgroup_memory: true,
+        };
+        let desc = crate::RenderPipelineDescriptor {
+            label: None,
+            layout,
+            vertex_processor: crate::VertexProcessor::Standard {
+                vertex_buffers: &[],
+                vertex_stage: stage(vertex, "vs"),
+            },
+            primitive,
+            depth_stencil: None,
+            multisample: Default::default(),
+            fragment_stage: Some(stage(fragment, "fs")),
+            color_targets: &[Some(wgt::ColorTargetState {
+                format: wgt::TextureFormat::Rgba8Unorm,
+                blend,
+                write_mask: wgt::ColorWrites::ALL,
+            })],
+            multiview: None,
+            cache: None,
+        };
+        LibraryKeys::new(&desc, vk::RenderPass::from_raw(1)).unwrap()
+    }
+
+    #[test]
+    fn library_keys() {
+        let layout = layout(1);
+        let (vertex, fragment, other) = (module(1), module(2), module(3));
+        let base = keys(&layout, &vertex, &fragment, Default::default(), None);
+
+        // Only the fragment shader library depends on the fragment shader.
+        let other_fragment = keys(&layout, &vertex, &other, Default::default(), None);
+        assert_eq!(base.vertex_input, other_fragment.vertex_input);
+        assert_eq!(base.pre_rasterization, other_fragment.pre_rasterization);
+        assert_ne!(base.fragment_shader, other_fragment.fragment_shader);
+        assert_eq!(base.fragment_output, other_fragment.fragment_output);
+
+        // The topology belongs to the vertex input, and the culling to the rasterization.
+        let lines = keys(
+            &layout,
+            &vertex,
+            &fragment,
+            wgt::PrimitiveState {
+                topology: wgt::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            None,
+        );
+        assert_ne!(base.vertex_input, lines.vertex_input);
+        assert_eq!(base.pre_rasterization, lines.pre_rasterization);
+        let culled = keys(
+            &layout,
+            &vertex,
+            &fragment,
+            wgt::PrimitiveState {
+                cull_mode: Some(wgt::Face::Back),
+                ..Default::default()
+            },
+            None,
+        );
+        assert_eq!(base.vertex_input, culled.vertex_input);
+        assert_ne!(base.pre_rasterization, culled.pre_rasterization);
+
+        let blended = keys(
+            &layout,
+            &vertex,
+            &fragment,
+            Default::default(),
+            Some(wgt::BlendState::ALPHA_BLENDING),
+        );
+        assert_eq!(base.fragment_shader, blended.fragment_shader);
+        assert_ne!(base.fragment_output, blended.fragment_output);
+
+        // Modules compiled for each pipeline aren't cached.
+        let module = naga::Module::default();
+        let info = naga::valid::Validator::new(Default::default(), Default::default())
+            .validate(&module)
+            .unwrap();
+        let intermediate = super::super::ShaderModule::Intermediate {
+            naga_shader: crate::NagaShader {
+                module: Cow::Owned(module),
+                info,
+                debug_source: None,
+                bounds_report: None,
+            },
+            runtime_checks: Default::default(),
+        };
+        let uncached = keys(&layout, &intermediate, &fragment, Default::default(), None);
+        assert_eq!(base.vertex_input, uncached.vertex_input);
+        assert_eq!(uncached.pre_rasterization, None);
+        assert_eq!(base.fragment_shader, uncached.fragment_shader);
+    }
+
+    /// Caches a library for `key` if there isn't one yet, numbering libraries in order.
+    fn insert<K: Eq + Hash>(cache: &LibraryCache<K>, key: K, next_library: &mut u64) {
+        *next_library += 1;
+        cache
+            .0
+            .lock()
+            .entry(key)
+            .or_insert(vk::Pipeline::from_raw(*next_library));
+    }
+
+    #[test]
+    fn evict() {
+        let libraries = PipelineLibraries {
+            vertex_input: LibraryCache::default(),
+            pre_rasterization: LibraryCache::default(),
+            fragment_shader: LibraryCache::default(),
+            fragment_output: LibraryCache::default(),
+            relinker: None,
+        };
+        let (first_layout, second_layout) = (layout(1), layout(2));
+        let (vertex, fragment, other) = (module(1), module(2), module(3));
+        let raw_module = vk::ShaderModule::from_raw;
+        let mut next_library = 0;
+        for (layout, fragment) in [
+            (&first_layout, &fragment),
+            (&first_layout, &other),
+            (&second_layout, &fragment),
+        ] {
+            let keys = keys(layout, &vertex, fragment, Default::default(), None);
+            insert(
+                &libraries.pre_rasterization,
+                keys.pre_rasterization.unwrap(),
+                &mut next_library,
+            );
+            insert(
+                &libraries.fragment_shader,
+                keys.fragment_shader.unwrap(),
+                &mut next_library,
+            );
+        }
+        let library = vk::Pipeline::from_raw;
+        assert_eq!(libraries.pre_rasterization.0.lock().len(), 2);
+        assert_eq!(libraries.fragment_shader.0.lock().len(), 3);
+
+        let mut taken = libraries.take_shader_module(raw_module(3));
+        assert_eq!(taken, [library(4)]);
+        taken = libraries.take_shader_module(raw_module(1));
+        taken.sort_unstable();
+        assert_eq!(taken, [library(1), library(5)]);
+        assert!(libraries.pre_rasterization.0.lock().is_empty());
+        assert_eq!(libraries.fragment_shader.0.lock().len(), 2);
+
+        taken = libraries.take_pipeline_layout(second_layout.raw);
+        assert_eq!(taken, [library(6)]);
+        let remaining: Vec<_> = libraries
+            .fragment_shader
+            .0
+            .lock()
+            .values()
+            .copied()
+            .collect();
+        assert_eq!(remaining, [library(2)]);
+    }
+
+    #[test]
+    fn wait_for_relinks_using() {
+        let uses = |library, layout, cache| RelinkUses {
+            libraries: [vk::Pipeline::from_raw(library); 4],
+            layout: vk::PipelineLayout::from_raw(layout),
+            cache: vk::PipelineCache::from_raw(cache),
+        };
+        let pending = Arc::new(PendingRelinks::default());
+        let first = pending.add(uses(1, 1, 1));
+        let second = pending.add(uses(2, 2, 1));
+
+        // Relinks that don't use what's destroyed don't hold it up.
+        pending.wait_while(|uses| uses.layout.as_raw() == 3);
+        pending.wait_while(|uses| uses.libraries.contains(&vk::Pipeline::from_raw(3)));
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let (pending, finished) = (Arc::clone(&pending), Arc::clone(&finished));
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                finished.store(true, Ordering::Release);
+                pending.finish(first);
+            })
+        };
+        pending.wait_while(|uses| uses.layout.as_raw() == 1);
+        assert!(finished.load(Ordering::Acquire));
+        thread.join().unwrap();
+
+        // Both relinks use the cache.
+        assert_eq!(
+            pending
+                .jobs
+                .lock()
+                .uses
+                .iter()
+                .map(|&(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![second]
+        );
+        pending.finish(second);
+        pending.wait_while(|uses| uses.cache.as_raw() == 1);
+    }
+}
@@ -45,21 +45,30 @@ impl SemaphoreList {
     /// - Set `submit_info`'s `pSignalSemaphores` list to this list's
     ///   semaphores.
     ///
-    /// - If this list contains any timeline semaphores, then initialize
-    ///   `timeline_info`, set its `pSignalSemaphoreValues` to this
-    ///   list's values, and add it to `submit_info`s extension chain.
+    /// - If this list contains any timeline semaphores, or `wait_values`
+    ///   isn't empty, then initialize `timeline_info`, set its
+    ///   `pSignalSemaphoreValues` to this list's values and its
+    ///   `pWaitSemaphoreValues` to `wait_values`, and add it to
+    ///   `submit_info`s extension chain.
+    ///
+    /// `wait_values` holds the values of the semaphores `submit_info` waits
+    /// for, with dummy values for binary semaphores, or is empty if none of
+    /// them is a timeline semaphore.
     ///
     /// Return the revised `submit_info` value.
     pub fn add_to_submit<'i, 's: 'i>(
         &'s self,
         submit_info: vk::SubmitInfo<'i>,
         timeline_info: &'i mut MaybeUninit<vk::TimelineSemaphoreSubmitInfo<'i>>,
+        wait_values: &'i [u64],
     ) -> vk::SubmitInfo<'i> {
         self.check();
         let mut submit_info = submit_info.signal_semaphores(&self.semaphores);
-        if !self.values.is_empty() {
+        if !self.values.is_empty() || !wait_values.is_empty() {
             let timeline_info = timeline_info.write(
-                vk::TimelineSemaphoreSubmitInfo::default().signal_semaphore_values(&self.values),
+                vk::TimelineSemaphoreSubmitInfo::default()
+                    .signal_semaphore_values(&self.values)
+                    .wait_semaphore_values(wait_values),
             );
             submit_info = submit_info.push_next(timeline_info);
         }
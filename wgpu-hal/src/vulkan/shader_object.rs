@@ -0,0 +1,537 @@
+//! Render pipelines made of shader objects.
+//!
+//! With `VK_EXT_shader_object`, shaders are created on their own rather than compiled into
+//! pipeline objects, and all of the fixed-function state is dynamic. A render pipeline is then
+//! just the shaders to bind and the state to set along with them, see
+//! [`ShaderObjectPipeline::bind`]. Creating one doesn't compile anything for fixed-function
+//! state, which makes it cheap for workloads creating many pipelines from the same shaders.
+//!
+//! Shader objects are created from SPIR-V rather than from shader modules, so the code of shader
+//! modules is kept around while shader objects are enabled. Shaders are cached on the device by
+//! module, entry point and layout, and are destroyed once the last pipeline using them is.
+//! Shaders compiled for each pipeline from naga IR aren't cached.
+//!
+//! Shader objects are used whenever the device supports them, see [`is_supported`]. Mesh
+//! pipelines, pipelines using shader modules created outside of `wgpu-hal`, and pipelines whose
+//! shaders the driver fails to create use pipeline objects instead.
+
+use alloc::{
+    borrow::ToOwned,
+    ffi::CString,
+    string::String,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::fmt;
+
+use arrayvec::ArrayVec;
+use ash::{ext, vk};
+use naga::FastHashMap;
+use parking_lot::Mutex;
+
+use super::conv;
+
+/// Graphics stages a render pipeline binds shaders to, including the ones bound to null.
+const MAX_BOUND_STAGES: usize = 5;
+
+/// Returns `true` if render pipelines can be made of shader objects on a device of `api_version`,
+/// with the given `VK_EXT_shader_object` features.
+pub(super) fn is_supported(
+    api_version: u32,
+    features: Option<&vk::PhysicalDeviceShaderObjectFeaturesEXT>,
+) -> bool {
+    api_version >= vk::API_VERSION_1_3
+        && features.is_some_and(|features| features.shader_object != 0)
+}
+
+/// Returns `true` if a pipeline should be created as a pipeline object after creating one of its
+/// shader objects failed with `err`.
+///
+/// Drivers may reject shaders that they compile fine into pipelines, so only a lost device, which
+/// pipeline creation would fail with too, is reported.
+fn falls_back(err: vk::Result) -> bool {
+    err != vk::Result::ERROR_DEVICE_LOST
+}
+
+/// A shader object, destroyed once the last pipeline using it is.
+struct ShaderObject {
+    raw: vk::ShaderEXT,
+    fns: Arc<ext::shader_object::Device>,
+}
+
+impl fmt::Debug for ShaderObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShaderObject")
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+impl Drop for ShaderObject {
+    fn drop(&mut self) {
+        unsafe { self.fns.destroy_shader(self.raw, None) };
+    }
+}
+
+/// What a cached shader object is created from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ShaderKey {
+    module: vk::ShaderModule,
+    entry_point: String,
+    stage: vk::ShaderStageFlags,
+    layout: vk::PipelineLayout,
+}
+
+/// Shader objects of a device, and the code they're created from.
+pub(super) struct ShaderObjects {
+    fns: Arc<ext::shader_object::Device>,
+    flags: vk::ShaderCreateFlagsEXT,
+    /// Stages whose features are enabled on the device, which must be bound to null.
+    null_stages: ArrayVec<vk::ShaderStageFlags, 3>,
+    /// Whether `VK_EXT_conservative_rasterization` is enabled, whose state must be set then.
+    conservative_rasterization: bool,
+    /// SPIR-V of the shader modules of the device.
+    code: Mutex<FastHashMap<vk::ShaderModule, Arc<[u32]>>>,
+    shaders: Mutex<FastHashMap<ShaderKey, Weak<ShaderObject>>>,
+}
+
+impl ShaderObjects {
+    pub fn new(fns: ext::shader_object::Device, features: wgt::Features) -> Self {
+        let mut flags = vk::ShaderCreateFlagsEXT::empty();
+        if features.contains(wgt::Features::SUBGROUP) {
+            flags |= vk::ShaderCreateFlagsEXT::ALLOW_VARYING_SUBGROUP_SIZE;
+        }
+        let mut null_stages = ArrayVec::new();
+        if features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX) {
+            null_stages.push(vk::ShaderStageFlags::GEOMETRY);
+        }
+        if features.contains(wgt::Features::EXPERIMENTAL_MESH_SHADER) {
+            null_stages.push(vk::ShaderStageFlags::TASK_EXT);
+            null_stages.push(vk::ShaderStageFlags::MESH_EXT);
+        }
+        Self {
+            fns: Arc::new(fns),
+            flags,
+            null_stages,
+            conservative_rasterization: features
+                .contains(wgt::Features::CONSERVATIVE_RASTERIZATION),
+            code: Mutex::default(),
+            shaders: Mutex::default(),
+        }
+    }
+
+    /// Records the SPIR-V `module` was created from.
+    pub fn add_module(&self, module: vk::ShaderModule, code: &[u32]) {
+        self.code.lock().insert(module, Arc::from(code));
+    }
+
+    /// Forgets about `module` before it's destroyed.
+    ///
+    /// Shaders created from it stay alive as long as pipelines use them.
+    pub fn remove_module(&self, module: vk::ShaderModule) {
+        self.code.lock().remove(&module);
+        self.shaders.lock().retain(|key, _| key.module != module);
+    }
+
+    /// Forgets about the shaders created for `layout` before it's destroyed.
+    pub fn evict_pipeline_layout(&self, layout: vk::PipelineLayout) {
+        self.shaders.lock().retain(|key, _| key.layout != layout);
+    }
+
+    /// Creates a render pipeline made of shader objects.
+    ///
+    /// Returns `None` if the pipeline can't be made of shader objects, and must be created as a
+    /// pipeline object instead.
+    pub unsafe fn create_pipeline(
+        &self,
+        device: &super::Device,
+        desc: &crate::RenderPipelineDescriptor<
+            super::PipelineLayout,
+            super::ShaderModule,
+            super::PipelineCache,
+        >,
+    ) -> Result<Option<ShaderObjectPipeline>, crate::PipelineError> {
+        let crate::VertexProcessor::Standard {
+            vertex_buffers,
+            ref vertex_stage,
+        } = desc.vertex_processor
+        else {
+            return Ok(None);
+        };
+
+        let vertex =
+            unsafe { self.shader(device, vertex_stage, naga::ShaderStage::Vertex, desc.layout) }?;
+        let Some(vertex) = vertex else {
+            return Ok(None);
+        };
+        let fragment = match desc.fragment_stage {
+            Some(ref stage) => {
+                match unsafe {
+                    self.shader(device, stage, naga::ShaderStage::Fragment, desc.layout)
+                }? {
+                    Some(shader) => Some(shader),
+                    None => return Ok(None),
+                }
+            }
+            None => None,
+        };
+
+        let mut stages = ArrayVec::new();
+        let mut shaders = ArrayVec::new();
+        stages.push(vk::ShaderStageFlags::VERTEX);
+        shaders.push(vertex.raw);
+        stages.push(vk::ShaderStageFlags::FRAGMENT);
+        shaders.push(
+            fragment
+                .as_ref()
+                .map_or(vk::ShaderEXT::null(), |shader| shader.raw),
+        );
+        for &stage in self.null_stages.iter() {
+            stages.push(stage);
+            shaders.push(vk::ShaderEXT::null());
+        }
+
+        let mut vertex_bindings = Vec::with_capacity(vertex_buffers.len());
+        let mut vertex_attributes = Vec::new();
+        for (i, vb) in vertex_buffers.iter().enumerate() {
+            vertex_bindings.push(
+                vk::VertexInputBindingDescription2EXT::default()
+                    .binding(i as u32)
+                    .stride(vb.array_stride as u32)
+                    .input_rate(match vb.step_mode {
+                        wgt::VertexStepMode::Vertex => vk::VertexInputRate::VERTEX,
+                        wgt::VertexStepMode::Instance => vk::VertexInputRate::INSTANCE,
+                    })
+                    .divisor(1),
+            );
+            for at in vb.attributes {
+                vertex_attributes.push(
+                    vk::VertexInputAttributeDescription2EXT::default()
+                        .location(at.shader_location)
+                        .binding(i as u32)
+                        .format(conv::map_vertex_format(at.format))
+                        .offset(at.offset as u32),
+                );
+            }
+        }
+
+        let mut depth_stencil = DepthStencilState::default();
+        if let Some(ref ds) = desc.depth_stencil {
+            if ds.is_depth_enabled() {
+                depth_stencil.depth_test = true;
+                depth_stencil.depth_write = ds.depth_write_enabled;
+                depth_stencil.depth_compare = conv::map_comparison(ds.depth_compare);
+            }
+            if ds.stencil.is_enabled() {
+                let s = &ds.stencil;
+                depth_stencil.stencil = Some((
+                    conv::map_stencil_face(&s.front, s.read_mask, s.write_mask),
+                    conv::map_stencil_face(&s.back, s.read_mask, s.write_mask),
+                ));
+            }
+            if ds.bias.is_enabled() {
+                depth_stencil.bias =
+                    Some([ds.bias.constant as f32, ds.bias.clamp, ds.bias.slope_scale]);
+            }
+        }
+
+        let mut blend_enables = Vec::with_capacity(desc.color_targets.len());
+        let mut blend_equations = Vec::with_capacity(desc.color_targets.len());
+        let mut write_masks = Vec::with_capacity(desc.color_targets.len());
+        for cat in desc.color_targets {
+            let mut equation = vk::ColorBlendEquationEXT::default();
+            let (blend_enable, write_mask) = match *cat {
+                Some(ref cat) => {
+                    if let Some(ref blend) = cat.blend {
+                        let (color_op, color_src, color_dst) =
+                            conv::map_blend_component(&blend.color);
+                        let (alpha_op, alpha_src, alpha_dst) =
+                            conv::map_blend_component(&blend.alpha);
+                        equation = equation
+                            .color_blend_op(color_op)
+                            .src_color_blend_factor(color_src)
+                            .dst_color_blend_factor(color_dst)
+                            .alpha_blend_op(alpha_op)
+                            .src_alpha_blend_factor(alpha_src)
+                            .dst_alpha_blend_factor(alpha_dst);
+                    }
+                    (
+                        cat.blend.is_some(),
+                        vk::ColorComponentFlags::from_raw(cat.write_mask.bits()),
+                    )
+                }
+                None => (false, vk::ColorComponentFlags::empty()),
+            };
+            blend_enables.push(vk::Bool32::from(blend_enable));
+            blend_equations.push(equation);
+            write_masks.push(write_mask);
+        }
+
+        Ok(Some(ShaderObjectPipeline {
+            stages,
+            shaders,
+            _shaders: [Some(vertex), fragment],
+            vertex_bindings,
+            vertex_attributes,
+            topology: conv::map_topology(desc.primitive.topology),
+            primitive_restart: desc.primitive.strip_index_format.is_some(),
+            polygon_mode: conv::map_polygon_mode(desc.primitive.polygon_mode),
+            cull_mode: desc
+                .primitive
+                .cull_mode
+                .map_or(vk::CullModeFlags::NONE, conv::map_cull_face),
+            front_face: conv::map_front_face(desc.primitive.front_face),
+            depth_clamp: desc.primitive.unclipped_depth,
+            conservative: self.conservative_rasterization.then_some(
+                if desc.primitive.conservative {
+                    vk::ConservativeRasterizationModeEXT::OVERESTIMATE
+                } else {
+                    vk::ConservativeRasterizationModeEXT::DISABLED
+                },
+            ),
+            samples: vk::SampleCountFlags::from_raw(desc.multisample.count),
+            sample_mask: [
+                desc.multisample.mask as u32,
+                (desc.multisample.mask >> 32) as u32,
+            ],
+            alpha_to_coverage: desc.multisample.alpha_to_coverage_enabled,
+            depth_stencil,
+            blend_enables,
+            blend_equations,
+            write_masks,
+        }))
+    }
+
+    /// Returns the shader object of `stage`, or `None` if it can't be created from its module or
+    /// the driver fails to create it.
+    unsafe fn shader(
+        &self,
+        device: &super::Device,
+        stage: &crate::ProgrammableStage<super::ShaderModule>,
+        naga_stage: naga::ShaderStage,
+        layout: &super::PipelineLayout,
+    ) -> Result<Option<Arc<ShaderObject>>, crate::PipelineError> {
+        let stage_flags = conv::map_shader_stage(crate::auxil::map_naga_stage(naga_stage));
+        match *stage.module {
+            super::ShaderModule::Raw(module) => {
+                let Some(code) = self.code.lock().get(&module).cloned() else {
+                    return Ok(None);
+                };
+                let key = ShaderKey {
+                    module,
+                    entry_point: stage.entry_point.to_owned(),
+                    stage: stage_flags,
+                    layout: layout.raw,
+                };
+                if let Some(shader) = self.shaders.lock().get(&key).and_then(Weak::upgrade) {
+                    return Ok(Some(shader));
+                }
+
+                let Some(shader) =
+                    unsafe { self.create_shader(&code, stage.entry_point, stage_flags, layout) }?
+                else {
+                    return Ok(None);
+                };
+                let shader = Arc::new(shader);
+                let mut shaders = self.shaders.lock();
+                shaders.retain(|_, shader| shader.strong_count() != 0);
+                shaders.insert(key, Arc::downgrade(&shader));
+                Ok(Some(shader))
+            }
+            super::ShaderModule::Intermediate {
+                ref naga_shader,
+                runtime_checks,
+            } => {
+                let code = device.compile_naga_stage(
+                    stage,
+                    naga_stage,
+                    naga_shader,
+                    runtime_checks,
+                    layout,
+                )?;
+                let shader =
+                    unsafe { self.create_shader(&code, stage.entry_point, stage_flags, layout) }?;
+                Ok(shader.map(Arc::new))
+            }
+        }
+    }
+
+    /// Creates a shader object, or returns `None` if the pipeline should fall back to a pipeline
+    /// object, see [`falls_back`].
+    unsafe fn create_shader(
+        &self,
+        code: &[u32],
+        entry_point: &str,
+        stage: vk::ShaderStageFlags,
+        layout: &super::PipelineLayout,
+    ) -> Result<Option<ShaderObject>, crate::PipelineError> {
+        let next_stage = if stage == vk::ShaderStageFlags::VERTEX {
+            vk::ShaderStageFlags::FRAGMENT
+        } else {
+            vk::ShaderStageFlags::empty()
+        };
+        let entry_point = CString::new(entry_point).unwrap();
+        let info = vk::ShaderCreateInfoEXT::default()
+            .flags(self.flags)
+            .stage(stage)
+            .next_stage(next_stage)
+            .code_type(vk::ShaderCodeTypeEXT::SPIRV)
+            .code(bytemuck::cast_slice(code))
+            .name(&entry_point)
+            .set_layouts(&layout.set_layouts)
+            .push_constant_ranges(&layout.push_constant_ranges);
+
+        let result = {
+            profiling::scope!("vkCreateShadersEXT");
+            unsafe { self.fns.create_shaders(&[info], None) }
+        };
+        match result {
+            Ok(mut raw_vec) => Ok(Some(ShaderObject {
+                raw: raw_vec.pop().unwrap(),
+                fns: Arc::clone(&self.fns),
+            })),
+            Err((_, err)) if falls_back(err) => {
+                log::warn!("Failed to create {stage:?} shader object, using a pipeline: {err}");
+                Ok(None)
+            }
+            Err((_, err)) => Err(super::map_pipeline_err(err).into()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct DepthStencilState {
+    depth_test: bool,
+    depth_write: bool,
+    depth_compare: vk::CompareOp,
+    /// Front and back faces, if the stencil test is enabled.
+    stencil: Option<(vk::StencilOpState, vk::StencilOpState)>,
+    /// Constant, clamp and slope factors, if depth bias is enabled.
+    bias: Option<[f32; 3]>,
+}
+
+/// The shaders and state of a render pipeline made of shader objects.
+#[derive(Debug)]
+pub(super) struct ShaderObjectPipeline {
+    stages: ArrayVec<vk::ShaderStageFlags, MAX_BOUND_STAGES>,
+    shaders: ArrayVec<vk::ShaderEXT, MAX_BOUND_STAGES>,
+    /// Keeps the vertex and fragment shaders alive.
+    _shaders: [Option<Arc<ShaderObject>>; 2],
+    vertex_bindings: Vec<vk::VertexInputBindingDescription2EXT<'static>>,
+    vertex_attributes: Vec<vk::VertexInputAttributeDescription2EXT<'static>>,
+    topology: vk::PrimitiveTopology,
+    primitive_restart: bool,
+    polygon_mode: vk::PolygonMode,
+    cull_mode: vk::CullModeFlags,
+    front_face: vk::FrontFace,
+    depth_clamp: bool,
+    /// Set if `VK_EXT_conservative_rasterization` is enabled.
+    conservative: Option<vk::ConservativeRasterizationModeEXT>,
+    samples: vk::SampleCountFlags,
+    sample_mask: [u32; 2],
+    alpha_to_coverage: bool,
+    depth_stencil: DepthStencilState,
+    blend_enables: Vec<vk::Bool32>,
+    blend_equations: Vec<vk::ColorBlendEquationEXT>,
+    write_masks: Vec<vk::ColorComponentFlags>,
+}
+
+impl ShaderObjectPipeline {
+    /// Binds the shaders, and sets all of the state that isn't set by the render pass encoder.
+    pub unsafe fn bind(&self, device: &super::DeviceShared, cmd: vk::CommandBuffer) {
+        let fns = device.extension_fns.shader_object.as_ref().unwrap();
+        unsafe {
+            fns.cmd_bind_shaders(cmd, &self.stages, &self.shaders);
+
+            fns.cmd_set_rasterizer_discard_enable(cmd, false);
+            fns.cmd_set_primitive_topology(cmd, self.topology);
+            fns.cmd_set_primitive_restart_enable(cmd, self.primitive_restart);
+            fns.cmd_set_vertex_input(cmd, &self.vertex_bindings, &self.vertex_attributes);
+
+            fns.cmd_set_polygon_mode(cmd, self.polygon_mode);
+            fns.cmd_set_cull_mode(cmd, self.cull_mode);
+            fns.cmd_set_front_face(cmd, self.front_face);
+            device.raw.cmd_set_line_width(cmd, 1.0);
+            fns.cmd_set_depth_clamp_enable(cmd, self.depth_clamp);
+            if let Some(mode) = self.conservative {
+                fns.cmd_set_conservative_rasterization_mode(cmd, mode);
+            }
+
+            let ds = &self.depth_stencil;
+            fns.cmd_set_depth_bias_enable(cmd, ds.bias.is_some());
+            if let Some([constant, clamp, slope]) = ds.bias {
+                device.raw.cmd_set_depth_bias(cmd, constant, clamp, slope);
+            }
+
+            fns.cmd_set_rasterization_samples(cmd, self.samples);
+            let mask_len = (self.samples.as_raw() as usize).div_ceil(32);
+            fns.cmd_set_sample_mask(cmd, self.samples, &self.sample_mask[..mask_len]);
+            fns.cmd_set_alpha_to_coverage_enable(cmd, self.alpha_to_coverage);
+
+            fns.cmd_set_depth_test_enable(cmd, ds.depth_test);
+            fns.cmd_set_depth_write_enable(cmd, ds.depth_write);
+            fns.cmd_set_depth_compare_op(cmd, ds.depth_compare);
+            fns.cmd_set_depth_bounds_test_enable(cmd, false);
+            fns.cmd_set_stencil_test_enable(cmd, ds.stencil.is_some());
+            if let Some((ref front, ref back)) = ds.stencil {
+                for (face, state) in [
+                    (vk::StencilFaceFlags::FRONT, front),
+                    (vk::StencilFaceFlags::BACK, back),
+                ] {
+                    fns.cmd_set_stencil_op(
+                        cmd,
+                        face,
+                        state.fail_op,
+                        state.pass_op,
+                        state.depth_fail_op,
+                        state.compare_op,
+                    );
+                    device
+                        .raw
+                        .cmd_set_stencil_compare_mask(cmd, face, state.compare_mask);
+                    device
+                        .raw
+                        .cmd_set_stencil_write_mask(cmd, face, state.write_mask);
+                }
+            }
+
+            if !self.write_masks.is_empty() {
+                fns.cmd_set_color_blend_enable(cmd, 0, &self.blend_enables);
+                fns.cmd_set_color_blend_equation(cmd, 0, &self.blend_equations);
+                fns.cmd_set_color_write_mask(cmd, 0, &self.write_masks);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ash::vk;
+
+    use super::{falls_back, is_supported};
+
+    #[test]
+    fn supported() {
+        let enabled = vk::PhysicalDeviceShaderObjectFeaturesEXT::default().shader_object(true);
+        let disabled = vk::PhysicalDeviceShaderObjectFeaturesEXT::default();
+
+        assert!(is_supported(vk::API_VERSION_1_3, Some(&enabled)));
+        assert!(is_supported(
+            vk::make_api_version(0, 1, 4, 0),
+            Some(&enabled)
+        ));
+        // The extension isn't exposed, or doesn't support the feature.
+        assert!(!is_supported(vk::API_VERSION_1_3, None));
+        assert!(!is_supported(vk::API_VERSION_1_3, Some(&disabled)));
+        // Dynamic rendering and the rest of the state shader objects rely on need Vulkan 1.3.
+        assert!(!is_supported(vk::API_VERSION_1_2, Some(&enabled)));
+    }
+
+    #[test]
+    fn fall_back_unless_lost() {
+        assert!(falls_back(vk::Result::ERROR_INITIALIZATION_FAILED));
+        assert!(falls_back(vk::Result::ERROR_OUT_OF_HOST_MEMORY));
+        assert!(falls_back(vk::Result::ERROR_UNKNOWN));
+        assert!(!falls_back(vk::Result::ERROR_DEVICE_LOST));
+    }
+}
@@ -0,0 +1,473 @@
+//! Sparse buffers and textures.
+//!
+//! Sparse resources are created without memory. Pages of memory are bound to
+//! them on the main queue with `vkQueueBindSparse`, from chunks each resource
+//! allocates for itself, so that the memory type of the pages always suits the
+//! resource. Unbound pages return to the resource's free list, and chunks are
+//! freed along with the resource.
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use ash::vk;
+use parking_lot::Mutex;
+
+use naga::FastHashMap;
+
+/// The number of pages in each chunk of memory.
+const PAGES_PER_CHUNK: u64 = 64;
+
+/// Pages of memory bound to a sparse resource, keyed by `K`.
+#[derive(Debug)]
+pub(super) struct SparseMemory<K> {
+    memory_type_index: u32,
+    chunks: Vec<vk::DeviceMemory>,
+    free_pages: Vec<(vk::DeviceMemory, u64)>,
+    bound_pages: FastHashMap<K, (vk::DeviceMemory, u64)>,
+}
+
+impl<K: Eq + Hash> SparseMemory<K> {
+    fn new(memory_type_index: u32) -> Self {
+        Self {
+            memory_type_index,
+            chunks: Vec::new(),
+            free_pages: Vec::new(),
+            bound_pages: FastHashMap::default(),
+        }
+    }
+
+    /// Returns the memory and offset of a page to bind at `key`, or `None` if
+    /// a page is already bound there.
+    fn bind(
+        &mut self,
+        device: &super::DeviceShared,
+        key: K,
+    ) -> Result<Option<(vk::DeviceMemory, u64)>, crate::DeviceError> {
+        if self.bound_pages.contains_key(&key) {
+            return Ok(None);
+        }
+        if self.free_pages.is_empty() {
+            let info = vk::MemoryAllocateInfo::default()
+                .allocation_size(PAGES_PER_CHUNK * wgt::SPARSE_PAGE_SIZE)
+                .memory_type_index(self.memory_type_index);
+            let chunk = unsafe { device.raw.allocate_memory(&info, None) }
+                .map_err(super::map_host_device_oom_err)?;
+            self.chunks.push(chunk);
+            self.free_pages.extend(
+                (0..PAGES_PER_CHUNK)
+                    .rev()
+                    .map(|page| (chunk, page * wgt::SPARSE_PAGE_SIZE)),
+            );
+        }
+        let page = self.free_pages.pop().unwrap();
+        self.bound_pages.insert(key, page);
+        Ok(Some(page))
+    }
+
+    /// Returns whether a page was bound at `key`, making it free for later binds.
+    fn unbind(&mut self, key: &K) -> bool {
+        match self.bound_pages.remove(key) {
+            Some(page) => {
+                self.free_pages.push(page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    unsafe fn destroy(self, device: &ash::Device) {
+        for chunk in self.chunks {
+            unsafe { device.free_memory(chunk, None) };
+        }
+    }
+}
+
+/// The pages of a sparse buffer, keyed by their index.
+pub(super) type SparseBuffer = SparseMemory<u64>;
+
+/// The pages and mip tails of a sparse texture.
+#[derive(Debug)]
+pub(super) struct SparseTexture {
+    /// Pages keyed by mip level, array layer, and their column and row.
+    memory: SparseMemory<[u32; 4]>,
+    size: vk::Extent2D,
+    page_extent: vk::Extent2D,
+    requirements: vk::SparseImageMemoryRequirements,
+    tail_memory_type_index: u32,
+    /// The memory of the mip tail of each array layer that is resident, or of
+    /// layer 0 only if the image has a single mip tail.
+    bound_tails: FastHashMap<u32, vk::DeviceMemory>,
+    /// Memory of unbound mip tails, kept for later binds.
+    free_tails: Vec<vk::DeviceMemory>,
+}
+
+impl SparseTexture {
+    unsafe fn destroy(self, device: &ash::Device) {
+        unsafe { self.memory.destroy(device) };
+        for memory in self.bound_tails.into_values().chain(self.free_tails) {
+            unsafe { device.free_memory(memory, None) };
+        }
+    }
+}
+
+impl super::Device {
+    /// Creates a buffer without memory, whose pages are bound with
+    /// [`crate::Queue::bind_sparse_memory`].
+    pub(super) unsafe fn create_sparse_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        vk_info: vk::BufferCreateInfo<'_>,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let vk_info = vk_info
+            .flags(vk::BufferCreateFlags::SPARSE_BINDING | vk::BufferCreateFlags::SPARSE_RESIDENCY);
+        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None) }
+            .map_err(super::map_host_device_oom_and_ioca_err)?;
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+
+        let memory_type_index = match self.find_memory_type_index(
+            req.memory_type_bits & self.valid_ash_memory_types,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        ) {
+            Some(index) if wgt::SPARSE_PAGE_SIZE % req.alignment == 0 => index,
+            _ => {
+                log::error!("Sparse buffer requirements {req:?} don't fit pages");
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                return Err(crate::DeviceError::Unexpected);
+            }
+        };
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        self.counters.buffers.add(1);
+
+        Ok(super::Buffer {
+            raw,
+            block: None,
+            sparse: Some(Mutex::new(SparseMemory::new(memory_type_index as u32))),
+        })
+    }
+
+    /// Creates a texture without memory, whose pages are bound with
+    /// [`crate::Queue::bind_sparse_memory`].
+    ///
+    /// # Safety
+    ///
+    /// - `image` must have been created from `desc` with sparse residency, and
+    ///   have no memory bound
+    pub(super) unsafe fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        image: super::device::ImageWithoutMemory,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let requirements = unsafe {
+            self.shared
+                .raw
+                .get_image_sparse_memory_requirements(image.raw)
+        }
+        .into_iter()
+        .find(|req| {
+            req.format_properties
+                .aspect_mask
+                .contains(vk::ImageAspectFlags::COLOR)
+        });
+        let memory_type_index = self.find_memory_type_index(
+            image.requirements.memory_type_bits & self.valid_ash_memory_types,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        );
+
+        // Pages and mip tails must be where `wgt` says they are.
+        let page_extent = desc.format.sparse_page_dimensions();
+        let tail_start = desc.sparse_mip_tail_start();
+        let (requirements, memory_type_index) = match (requirements, memory_type_index) {
+            (Some(req), Some(index))
+                if image.requirements.alignment == wgt::SPARSE_PAGE_SIZE
+                    && page_extent
+                        == Some((
+                            req.format_properties.image_granularity.width,
+                            req.format_properties.image_granularity.height,
+                        ))
+                    && req.image_mip_tail_first_lod.min(desc.mip_level_count) == tail_start =>
+            {
+                (req, index as u32)
+            }
+            (req, _) => {
+                log::error!("Sparse image requirements {req:?} don't fit pages");
+                unsafe { self.shared.raw.destroy_image(image.raw, None) };
+                return Err(crate::DeviceError::Unexpected);
+            }
+        };
+        let (page_width, page_height) = page_extent.unwrap();
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        let identity = self.shared.texture_identity_factory.next();
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: None,
+            block: None,
+            format: desc.format,
+            copy_size: image.copy_size,
+            identity,
+            video_view: None,
+            sparse: Some(Mutex::new(SparseTexture {
+                memory: SparseMemory::new(memory_type_index),
+                size: vk::Extent2D {
+                    width: desc.size.width,
+                    height: desc.size.height,
+                },
+                page_extent: vk::Extent2D {
+                    width: page_width,
+                    height: page_height,
+                },
+                requirements,
+                tail_memory_type_index: memory_type_index,
+                bound_tails: FastHashMap::default(),
+                free_tails: Vec::new(),
+            })),
+        })
+    }
+
+    pub(super) unsafe fn destroy_sparse_buffer(&self, sparse: Mutex<SparseBuffer>) {
+        unsafe { sparse.into_inner().destroy(&self.shared.raw) };
+    }
+
+    pub(super) unsafe fn destroy_sparse_texture(&self, sparse: Mutex<SparseTexture>) {
+        unsafe { sparse.into_inner().destroy(&self.shared.raw) };
+    }
+}
+
+impl super::Queue {
+    pub(super) unsafe fn bind_sparse(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_, super::Buffer>],
+        texture_binds: &[crate::SparseTextureBind<'_, super::Texture>],
+    ) -> Result<(), crate::DeviceError> {
+        let device = &*self.device;
+
+        let mut buffer_memory_binds = Vec::with_capacity(buffer_binds.len());
+        for bind in buffer_binds {
+            let mut sparse = bind.buffer.sparse.as_ref().unwrap().lock();
+            let mut binds = Vec::new();
+            for offset in bind.range.clone().step_by(wgt::SPARSE_PAGE_SIZE as usize) {
+                let key = offset / wgt::SPARSE_PAGE_SIZE;
+                let page = if bind.resident {
+                    match sparse.bind(device, key)? {
+                        Some(page) => page,
+                        None => continue,
+                    }
+                } else if sparse.unbind(&key) {
+                    (vk::DeviceMemory::null(), 0)
+                } else {
+                    continue;
+                };
+                binds.push(
+                    vk::SparseMemoryBind::default()
+                        .resource_offset(offset)
+                        .size(wgt::SPARSE_PAGE_SIZE)
+                        .memory(page.0)
+                        .memory_offset(page.1),
+                );
+            }
+            if !binds.is_empty() {
+                buffer_memory_binds.push((bind.buffer.raw, binds));
+            }
+        }
+
+        let mut image_memory_binds = Vec::with_capacity(texture_binds.len());
+        let mut tail_memory_binds = Vec::new();
+        for bind in texture_binds {
+            let mut sparse = bind.texture.sparse.as_ref().unwrap().lock();
+            let layers = bind.origin.z..bind.origin.z + bind.size.depth_or_array_layers;
+            if bind.mip_level >= sparse.requirements.image_mip_tail_first_lod {
+                let binds = sparse.bind_tails(device, layers, bind.resident)?;
+                if !binds.is_empty() {
+                    tail_memory_binds.push((bind.texture.raw, binds));
+                }
+                continue;
+            }
+
+            let page = sparse.page_extent;
+            let mip_width = (sparse.size.width >> bind.mip_level).max(1);
+            let mip_height = (sparse.size.height >> bind.mip_level).max(1);
+            let mut binds = Vec::new();
+            for array_layer in layers {
+                for y in
+                    (bind.origin.y..bind.origin.y + bind.size.height).step_by(page.height as usize)
+                {
+                    for x in (bind.origin.x..bind.origin.x + bind.size.width)
+                        .step_by(page.width as usize)
+                    {
+                        let key = [bind.mip_level, array_layer, x / page.width, y / page.height];
+                        let memory = if bind.resident {
+                            match sparse.memory.bind(device, key)? {
+                                Some(memory) => memory,
+                                None => continue,
+                            }
+                        } else if sparse.memory.unbind(&key) {
+                            (vk::DeviceMemory::null(), 0)
+                        } else {
+                            continue;
+                        };
+                        binds.push(
+                            vk::SparseImageMemoryBind::default()
+                                .subresource(vk::ImageSubresource {
+                                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                                    mip_level: bind.mip_level,
+                                    array_layer,
+                                })
+                                .offset(vk::Offset3D {
+                                    x: x as i32,
+                                    y: y as i32,
+                                    z: 0,
+                                })
+                                .extent(vk::Extent3D {
+                                    width: page.width.min(mip_width - x),
+                                    height: page.height.min(mip_height - y),
+                                    depth: 1,
+                                })
+                                .memory(memory.0)
+                                .memory_offset(memory.1),
+                        );
+                    }
+                }
+            }
+            if !binds.is_empty() {
+                image_memory_binds.push((bind.texture.raw, binds));
+            }
+        }
+
+        if buffer_memory_binds.is_empty()
+            && image_memory_binds.is_empty()
+            && tail_memory_binds.is_empty()
+        {
+            return Ok(());
+        }
+
+        let buffer_infos = buffer_memory_binds
+            .iter()
+            .map(|(buffer, binds)| {
+                vk::SparseBufferMemoryBindInfo::default()
+                    .buffer(*buffer)
+                    .binds(binds)
+            })
+            .collect::<Vec<_>>();
+        let image_infos = image_memory_binds
+            .iter()
+            .map(|(image, binds)| {
+                vk::SparseImageMemoryBindInfo::default()
+                    .image(*image)
+                    .binds(binds)
+            })
+            .collect::<Vec<_>>();
+        let opaque_infos = tail_memory_binds
+            .iter()
+            .map(|(image, binds)| {
+                vk::SparseImageOpaqueMemoryBindInfo::default()
+                    .image(*image)
+                    .binds(binds)
+            })
+            .collect::<Vec<_>>();
+
+        // Binding is ordered with submissions through the relay semaphores,
+        // and waits for other queues like a submission.
+        let semaphore_state = self.relay_semaphores.lock().advance(device)?;
+        let mut wait_semaphores = Vec::new();
+        let mut wait_values = Vec::new();
+        if let Some(sem) = semaphore_state.wait {
+            wait_semaphores.push(sem);
+        }
+        for (semaphore, value) in self.wait_semaphores.lock().drain(..) {
+            wait_values.resize(wait_semaphores.len(), !0);
+            wait_semaphores.push(semaphore);
+            wait_values.push(value);
+        }
+        let signal_semaphores = [semaphore_state.signal];
+
+        let mut vk_info = vk::BindSparseInfo::default()
+            .wait_semaphores(&wait_semaphores)
+            .buffer_binds(&buffer_infos)
+            .image_opaque_binds(&opaque_infos)
+            .image_binds(&image_infos)
+            .signal_semaphores(&signal_semaphores);
+        let mut vk_timeline_info =
+            vk::TimelineSemaphoreSubmitInfo::default().wait_semaphore_values(&wait_values);
+        if !wait_values.is_empty() {
+            vk_info = vk_info.push_next(&mut vk_timeline_info);
+        }
+
+        profiling::scope!("vkQueueBindSparse");
+        unsafe {
+            device
+                .raw
+                .queue_bind_sparse(self.raw, &[vk_info], vk::Fence::null())
+        }
+        .map_err(super::map_host_device_oom_and_lost_err)
+    }
+}
+
+impl SparseTexture {
+    /// Returns the binds of the mip tails of `layers`.
+    fn bind_tails(
+        &mut self,
+        device: &super::DeviceShared,
+        layers: core::ops::Range<u32>,
+        resident: bool,
+    ) -> Result<Vec<vk::SparseMemoryBind>, crate::DeviceError> {
+        let req = self.requirements;
+        let layers = if req
+            .format_properties
+            .flags
+            .contains(vk::SparseImageFormatFlags::SINGLE_MIPTAIL)
+        {
+            0..1
+        } else {
+            layers
+        };
+
+        let mut binds = Vec::new();
+        for layer in layers {
+            let memory = if resident {
+                if self.bound_tails.contains_key(&layer) {
+                    continue;
+                }
+                let memory = match self.free_tails.pop() {
+                    Some(memory) => memory,
+                    None => {
+                        let info = vk::MemoryAllocateInfo::default()
+                            .allocation_size(req.image_mip_tail_size)
+                            .memory_type_index(self.tail_memory_type_index);
+                        unsafe { device.raw.allocate_memory(&info, None) }
+                            .map_err(super::map_host_device_oom_err)?
+                    }
+                };
+                self.bound_tails.insert(layer, memory);
+                memory
+            } else {
+                match self.bound_tails.remove(&layer) {
+                    Some(memory) => {
+                        self.free_tails.push(memory);
+                        vk::DeviceMemory::null()
+                    }
+                    None => continue,
+                }
+            };
+            binds.push(
+                vk::SparseMemoryBind::default()
+                    .resource_offset(
+                        req.image_mip_tail_offset + u64::from(layer) * req.image_mip_tail_stride,
+                    )
+                    .size(req.image_mip_tail_size)
+                    .memory(memory),
+            );
+        }
+        Ok(binds)
+    }
+}
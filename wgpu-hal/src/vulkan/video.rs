@@ -0,0 +1,2087 @@
+//! Video decoding, with `VK_KHR_video_decode_queue`.
+//!
+//! Pictures are decoded on a queue of their own, from a family other than the one of the main
+//! queue. Every decode is a submission to that queue, which waits for the fence of the main queue
+//! to reach the value it's given, and signals the timeline semaphore of the [`VideoQueue`]. The
+//! next submission to the main queue waits for that semaphore, so that decoded pictures can be
+//! used like any other texture from then on.
+//!
+//! Pictures are images shared by both queue families, which also make up the decoded picture
+//! buffer: sessions are created for implementations where the output of a decode coincides with
+//! its reconstructed picture. The parameter sets of the pictures are turned into a session
+//! parameters object, which is recreated whenever they change. Compressed data is copied into
+//! host-visible buffers, which are reused once the decodes reading them complete.
+
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+use core::{
+    ffi::CStr,
+    mem, ptr, slice,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use arrayvec::ArrayVec;
+use ash::{khr, vk, vk::native};
+use parking_lot::Mutex;
+use wgt::video::{av1, h264, h265};
+
+use super::conv;
+
+/// The codecs that can be decoded, with their operations and extensions.
+const CODECS: [(wgt::VideoCodec, vk::VideoCodecOperationFlagsKHR, &CStr); 3] = [
+    (
+        wgt::VideoCodec::H264,
+        vk::VideoCodecOperationFlagsKHR::DECODE_H264,
+        khr::video_decode_h264::NAME,
+    ),
+    (
+        wgt::VideoCodec::H265,
+        vk::VideoCodecOperationFlagsKHR::DECODE_H265,
+        khr::video_decode_h265::NAME,
+    ),
+    (
+        wgt::VideoCodec::Av1,
+        vk::VideoCodecOperationFlagsKHR::DECODE_AV1,
+        khr::video_decode_av1::NAME,
+    ),
+];
+
+/// The smallest buffer compressed data is copied into.
+const MIN_BITSTREAM_BUFFER_SIZE: u64 = 1 << 16;
+
+const COLOR_RANGE: vk::ImageSubresourceRange = vk::ImageSubresourceRange {
+    aspect_mask: vk::ImageAspectFlags::COLOR,
+    base_mip_level: 0,
+    level_count: 1,
+    base_array_layer: 0,
+    layer_count: 1,
+};
+
+/// The queue family pictures are decoded on, and the codecs it can decode.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct VideoDecodeFamily {
+    pub index: u32,
+    /// The codec operations of the family whose extensions are supported.
+    pub operations: vk::VideoCodecOperationFlagsKHR,
+}
+
+impl VideoDecodeFamily {
+    /// Finds a family that can decode video, which requires a Vulkan 1.3 device.
+    ///
+    /// The main queue is the first queue of family 0, so only the other families are
+    /// considered.
+    pub fn find(
+        instance: &super::InstanceShared,
+        phd: vk::PhysicalDevice,
+        phd_capabilities: &super::adapter::PhysicalDeviceProperties,
+    ) -> Option<Self> {
+        if instance.instance_api_version < vk::API_VERSION_1_1
+            || !phd_capabilities.supports_extension(khr::video_queue::NAME)
+            || !phd_capabilities.supports_extension(khr::video_decode_queue::NAME)
+        {
+            return None;
+        }
+        let supported_operations = CODECS
+            .iter()
+            .filter(|&&(_, _, extension)| phd_capabilities.supports_extension(extension))
+            .fold(
+                vk::VideoCodecOperationFlagsKHR::empty(),
+                |operations, &(_, operation, _)| operations | operation,
+            );
+
+        let count = unsafe {
+            instance
+                .raw
+                .get_physical_device_queue_family_properties2_len(phd)
+        };
+        let mut video_properties = vec![vk::QueueFamilyVideoPropertiesKHR::default(); count];
+        let mut properties = video_properties
+            .iter_mut()
+            .map(|video| vk::QueueFamilyProperties2::default().push_next(video))
+            .collect::<Vec<_>>();
+        unsafe {
+            instance
+                .raw
+                .get_physical_device_queue_family_properties2(phd, &mut properties)
+        };
+        let queue_flags = properties
+            .iter()
+            .map(|properties| properties.queue_family_properties.queue_flags)
+            .collect::<Vec<_>>();
+
+        queue_flags
+            .iter()
+            .zip(&video_properties)
+            .enumerate()
+            .skip(1)
+            .find_map(|(index, (flags, video))| {
+                let operations = video.video_codec_operations & supported_operations;
+                (flags.contains(vk::QueueFlags::VIDEO_DECODE_KHR) && !operations.is_empty())
+                    .then_some(Self {
+                        index: index as u32,
+                        operations,
+                    })
+            })
+    }
+
+    /// The extensions decoding on this family requires.
+    pub fn extensions(&self) -> Vec<&'static CStr> {
+        let mut extensions = vec![khr::video_queue::NAME, khr::video_decode_queue::NAME];
+        extensions.extend(
+            CODECS
+                .iter()
+                .filter(|&&(_, operation, _)| self.operations.contains(operation))
+                .map(|&(_, _, extension)| extension),
+        );
+        extensions
+    }
+}
+
+/// The video decode queue of a device.
+pub(super) struct VideoQueue {
+    pub family_index: u32,
+    operations: vk::VideoCodecOperationFlagsKHR,
+    /// The queue, locked while submitting to it.
+    raw: Mutex<vk::Queue>,
+    instance_fns: khr::video_queue::Instance,
+    fns: khr::video_queue::Device,
+    decode_fns: khr::video_decode_queue::Device,
+    /// Timeline semaphore signalled by every decode, with the value after the one of the
+    /// previous decode.
+    pub semaphore: vk::Semaphore,
+    /// The value signalled by the last decode.
+    last_value: AtomicU64,
+    /// The value the main queue last waited for.
+    waited_value: AtomicU64,
+}
+
+impl VideoQueue {
+    /// # Safety
+    ///
+    /// - `raw_device` must have been created with a queue of `family`, and the extensions
+    ///   of [`VideoDecodeFamily::extensions`].
+    pub unsafe fn new(
+        instance: &super::InstanceShared,
+        raw_device: &ash::Device,
+        family: VideoDecodeFamily,
+    ) -> Result<Self, crate::DeviceError> {
+        let mut semaphore_type_info =
+            vk::SemaphoreTypeCreateInfo::default().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let semaphore_info = vk::SemaphoreCreateInfo::default().push_next(&mut semaphore_type_info);
+        let semaphore = unsafe { raw_device.create_semaphore(&semaphore_info, None) }
+            .map_err(super::map_host_device_oom_err)?;
+
+        Ok(Self {
+            family_index: family.index,
+            operations: family.operations,
+            raw: Mutex::new(unsafe { raw_device.get_device_queue(family.index, 0) }),
+            instance_fns: khr::video_queue::Instance::new(&instance.entry, &instance.raw),
+            fns: khr::video_queue::Device::new(&instance.raw, raw_device),
+            decode_fns: khr::video_decode_queue::Device::new(&instance.raw, raw_device),
+            semaphore,
+            last_value: AtomicU64::new(0),
+            waited_value: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the value of [`semaphore`] the next submission to the main queue should wait
+    /// for, if pictures were decoded since the last one.
+    ///
+    /// [`semaphore`]: Self::semaphore
+    pub fn take_wait_value(&self) -> Option<u64> {
+        let last_value = self.last_value.load(Ordering::Acquire);
+        let waited_value = self.waited_value.fetch_max(last_value, Ordering::AcqRel);
+        (last_value > waited_value).then_some(last_value)
+    }
+
+    /// Queries how the device can decode `profile`.
+    ///
+    /// Returns `None` if it can't, or if decoded pictures can't be their own reconstructed
+    /// pictures.
+    fn capabilities(
+        &self,
+        device: &super::DeviceShared,
+        profile: Profile,
+    ) -> Option<SessionCapabilities> {
+        if !self.operations.contains(profile.operation()) {
+            return None;
+        }
+        profile.with(|info| {
+            let mut decode = vk::VideoDecodeCapabilitiesKHR::default();
+            let mut h264 = vk::VideoDecodeH264CapabilitiesKHR::default();
+            let mut h265 = vk::VideoDecodeH265CapabilitiesKHR::default();
+            let mut av1 = vk::VideoDecodeAV1CapabilitiesKHR::default();
+            let mut capabilities = vk::VideoCapabilitiesKHR::default().push_next(&mut decode);
+            capabilities = match profile.codec {
+                wgt::VideoCodec::H264 => capabilities.push_next(&mut h264),
+                wgt::VideoCodec::H265 => capabilities.push_next(&mut h265),
+                wgt::VideoCodec::Av1 => capabilities.push_next(&mut av1),
+            };
+            let result = unsafe {
+                (self
+                    .instance_fns
+                    .fp()
+                    .get_physical_device_video_capabilities_khr)(
+                    device.physical_device,
+                    info,
+                    &mut capabilities,
+                )
+            };
+            if result != vk::Result::SUCCESS {
+                return None;
+            }
+            let session = SessionCapabilities {
+                min_coded_extent: capabilities.min_coded_extent,
+                max_coded_extent: capabilities.max_coded_extent,
+                max_dpb_slots: capabilities.max_dpb_slots,
+                max_active_reference_pictures: capabilities.max_active_reference_pictures,
+                bitstream_size_alignment: capabilities.min_bitstream_buffer_size_alignment,
+                std_header_version: capabilities.std_header_version,
+            };
+
+            if !decode
+                .flags
+                .contains(vk::VideoDecodeCapabilityFlagsKHR::DPB_AND_OUTPUT_COINCIDE)
+            {
+                return None;
+            }
+            let format = device.private_caps.map_texture_format(profile.format);
+            self.supports_picture_format(device, info, format)
+                .then_some(session)
+        })
+    }
+
+    /// Returns true if pictures of `profile` can be images of `format` that can also be
+    /// sampled and copied from.
+    fn supports_picture_format(
+        &self,
+        device: &super::DeviceShared,
+        profile: &vk::VideoProfileInfoKHR<'_>,
+        format: vk::Format,
+    ) -> bool {
+        let mut profile_list =
+            vk::VideoProfileListInfoKHR::default().profiles(slice::from_ref(profile));
+        let format_info = vk::PhysicalDeviceVideoFormatInfoKHR::default()
+            .image_usage(
+                vk::ImageUsageFlags::VIDEO_DECODE_DST_KHR
+                    | vk::ImageUsageFlags::VIDEO_DECODE_DPB_KHR,
+            )
+            .push_next(&mut profile_list);
+        let get_properties = self
+            .instance_fns
+            .fp()
+            .get_physical_device_video_format_properties_khr;
+
+        let mut count = 0;
+        let result = unsafe {
+            get_properties(
+                device.physical_device,
+                &format_info,
+                &mut count,
+                ptr::null_mut(),
+            )
+        };
+        if result != vk::Result::SUCCESS {
+            return false;
+        }
+        let mut properties = vec![vk::VideoFormatPropertiesKHR::default(); count as usize];
+        let result = unsafe {
+            get_properties(
+                device.physical_device,
+                &format_info,
+                &mut count,
+                properties.as_mut_ptr(),
+            )
+        };
+        if result != vk::Result::SUCCESS {
+            return false;
+        }
+        properties.truncate(count as usize);
+
+        properties.iter().any(|properties| {
+            properties.format == format
+                && properties.image_tiling == vk::ImageTiling::OPTIMAL
+                && properties
+                    .image_create_flags
+                    .contains(vk::ImageCreateFlags::MUTABLE_FORMAT)
+                && properties
+                    .image_usage_flags
+                    .contains(vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC)
+        })
+    }
+}
+
+/// What a session of a profile can be created with.
+struct SessionCapabilities {
+    min_coded_extent: vk::Extent2D,
+    max_coded_extent: vk::Extent2D,
+    max_dpb_slots: u32,
+    max_active_reference_pictures: u32,
+    bitstream_size_alignment: u64,
+    std_header_version: vk::ExtensionProperties,
+}
+
+pub(super) fn capabilities(
+    device: &super::DeviceShared,
+    codec: wgt::VideoCodec,
+    format: wgt::TextureFormat,
+) -> Option<wgt::VideoDecodeCapabilities> {
+    let video = device.video.as_ref()?;
+    let capabilities = video.capabilities(device, Profile::new(codec, format)?)?;
+    let extent = |extent: vk::Extent2D| wgt::Extent3d {
+        width: extent.width,
+        height: extent.height,
+        depth_or_array_layers: 1,
+    };
+    Some(wgt::VideoDecodeCapabilities {
+        min_coded_extent: extent(capabilities.min_coded_extent),
+        max_coded_extent: extent(capabilities.max_coded_extent),
+        max_pictures: capabilities.max_dpb_slots,
+        max_reference_pictures: capabilities.max_active_reference_pictures,
+    })
+}
+
+/// The video profile a decoder decodes, which is the main profile of its codec for the bit
+/// depth of its format.
+#[derive(Clone, Copy, Debug)]
+struct Profile {
+    codec: wgt::VideoCodec,
+    format: wgt::TextureFormat,
+}
+
+impl Profile {
+    fn new(codec: wgt::VideoCodec, format: wgt::TextureFormat) -> Option<Self> {
+        match (codec, format) {
+            (_, wgt::TextureFormat::NV12)
+            | (wgt::VideoCodec::H265 | wgt::VideoCodec::Av1, wgt::TextureFormat::P010) => {
+                Some(Self { codec, format })
+            }
+            _ => None,
+        }
+    }
+
+    fn operation(&self) -> vk::VideoCodecOperationFlagsKHR {
+        CODECS
+            .iter()
+            .find(|&&(codec, _, _)| codec == self.codec)
+            .map(|&(_, operation, _)| operation)
+            .unwrap()
+    }
+
+    /// Calls `f` with the description of the profile.
+    fn with<R>(self, f: impl FnOnce(&vk::VideoProfileInfoKHR<'_>) -> R) -> R {
+        let ten_bit = self.format == wgt::TextureFormat::P010;
+        let bit_depth = if ten_bit {
+            vk::VideoComponentBitDepthFlagsKHR::TYPE_10
+        } else {
+            vk::VideoComponentBitDepthFlagsKHR::TYPE_8
+        };
+
+        // High profile decoders also decode the Constrained Baseline and Main profiles.
+        let mut h264 = vk::VideoDecodeH264ProfileInfoKHR::default()
+            .std_profile_idc(native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH)
+            .picture_layout(vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE);
+        let mut h265 = vk::VideoDecodeH265ProfileInfoKHR::default().std_profile_idc(if ten_bit {
+            native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN_10
+        } else {
+            native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN
+        });
+        let mut av1 = vk::VideoDecodeAV1ProfileInfoKHR::default()
+            .std_profile(native::StdVideoAV1Profile_STD_VIDEO_AV1_PROFILE_MAIN)
+            .film_grain_support(false);
+
+        let info = vk::VideoProfileInfoKHR::default()
+            .video_codec_operation(self.operation())
+            .chroma_subsampling(vk::VideoChromaSubsamplingFlagsKHR::TYPE_420)
+            .luma_bit_depth(bit_depth)
+            .chroma_bit_depth(bit_depth);
+        let info = match self.codec {
+            wgt::VideoCodec::H264 => info.push_next(&mut h264),
+            wgt::VideoCodec::H265 => info.push_next(&mut h265),
+            wgt::VideoCodec::Av1 => info.push_next(&mut av1),
+        };
+        f(&info)
+    }
+}
+
+/// A buffer compressed data is copied into.
+#[derive(Debug)]
+struct Bitstream {
+    buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    size: u64,
+}
+
+/// The parameter sets a session parameters object was created from.
+#[derive(Debug, PartialEq)]
+enum ParameterSets {
+    H264(Box<(h264::SequenceParameterSet, h264::PictureParameterSet)>),
+    H265(
+        Box<(
+            h265::VideoParameterSet,
+            h265::SequenceParameterSet,
+            h265::PictureParameterSet,
+        )>,
+    ),
+    Av1(Box<av1::SequenceHeader>),
+}
+
+impl ParameterSets {
+    fn new(picture: &wgt::VideoPictureInfo<'_>) -> Self {
+        match *picture {
+            wgt::VideoPictureInfo::H264(ref picture) => {
+                Self::H264(Box::new((picture.sps.clone(), picture.pps.clone())))
+            }
+            wgt::VideoPictureInfo::H265(ref picture) => Self::H265(Box::new((
+                picture.vps.clone(),
+                picture.sps.clone(),
+                picture.pps.clone(),
+            ))),
+            wgt::VideoPictureInfo::Av1(ref picture) => {
+                Self::Av1(Box::new(picture.sequence_header.clone()))
+            }
+        }
+    }
+
+    fn matches(&self, picture: &wgt::VideoPictureInfo<'_>) -> bool {
+        match (self, picture) {
+            (Self::H264(sets), wgt::VideoPictureInfo::H264(picture)) => {
+                sets.0 == *picture.sps && sets.1 == *picture.pps
+            }
+            (Self::H265(sets), wgt::VideoPictureInfo::H265(picture)) => {
+                sets.0 == *picture.vps && sets.1 == *picture.sps && sets.2 == *picture.pps
+            }
+            (Self::Av1(header), wgt::VideoPictureInfo::Av1(picture)) => {
+                **header == *picture.sequence_header
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A decode that may still be running.
+#[derive(Debug)]
+struct InFlightDecode {
+    /// The value of the semaphore of the video queue the decode signals.
+    value: u64,
+    command_buffer: vk::CommandBuffer,
+    bitstream: Bitstream,
+}
+
+#[derive(Debug)]
+struct DecoderState {
+    command_pool: vk::CommandPool,
+    free_command_buffers: Vec<vk::CommandBuffer>,
+    free_bitstreams: Vec<Bitstream>,
+    in_flight: VecDeque<InFlightDecode>,
+    parameters: Option<(vk::VideoSessionParametersKHR, ParameterSets)>,
+    /// Session parameters that were replaced, with the value of the last decode using them.
+    retired_parameters: Vec<(u64, vk::VideoSessionParametersKHR)>,
+    /// True once the session was reset, which the first decode does.
+    reset: bool,
+}
+
+#[derive(Debug)]
+pub struct VideoDecoder {
+    session: vk::VideoSessionKHR,
+    memory: Vec<vk::DeviceMemory>,
+    profile: Profile,
+    bitstream_size_alignment: u64,
+    state: Mutex<DecoderState>,
+}
+
+impl crate::DynVideoDecoder for VideoDecoder {}
+
+impl VideoDecoder {
+    pub(super) unsafe fn new(
+        device: &super::Device,
+        desc: &crate::VideoDecoderDescriptor,
+    ) -> Result<Self, crate::DeviceError> {
+        let shared = &*device.shared;
+        let video = shared
+            .video
+            .as_ref()
+            .ok_or(crate::DeviceError::Unexpected)?;
+        let profile =
+            Profile::new(desc.codec, desc.format).ok_or(crate::DeviceError::Unexpected)?;
+        let capabilities = video
+            .capabilities(shared, profile)
+            .ok_or(crate::DeviceError::Unexpected)?;
+
+        let format = shared.private_caps.map_texture_format(desc.format);
+        let max_coded_extent = vk::Extent2D {
+            width: desc.max_coded_extent.width,
+            height: desc.max_coded_extent.height,
+        };
+        let session = profile.with(|info| {
+            let create_info = vk::VideoSessionCreateInfoKHR::default()
+                .queue_family_index(video.family_index)
+                .video_profile(info)
+                .picture_format(format)
+                .max_coded_extent(max_coded_extent)
+                .reference_picture_format(format)
+                .max_dpb_slots(desc.max_pictures)
+                .max_active_reference_pictures(desc.max_reference_pictures)
+                .std_header_version(&capabilities.std_header_version);
+            let mut session = vk::VideoSessionKHR::null();
+            unsafe {
+                (video.fns.fp().create_video_session_khr)(
+                    shared.raw.handle(),
+                    &create_info,
+                    ptr::null(),
+                    &mut session,
+                )
+            }
+            .result_with_success(session)
+            .map_err(super::map_host_device_oom_err)
+        })?;
+        if let Some(label) = desc.label {
+            unsafe { shared.set_object_name(session, label) };
+        }
+
+        let mut decoder = Self {
+            session,
+            memory: Vec::new(),
+            profile,
+            bitstream_size_alignment: capabilities.bitstream_size_alignment.max(1),
+            state: Mutex::new(DecoderState {
+                command_pool: vk::CommandPool::null(),
+                free_command_buffers: Vec::new(),
+                free_bitstreams: Vec::new(),
+                in_flight: VecDeque::new(),
+                parameters: None,
+                retired_parameters: Vec::new(),
+                reset: false,
+            }),
+        };
+        match unsafe { decoder.bind_memory(shared, video) } {
+            Ok(()) => Ok(decoder),
+            Err(err) => {
+                unsafe { decoder.destroy(shared) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Binds memory to the session, and creates the command pool of the decoder.
+    ///
+    unsafe fn bind_memory(
+        &mut self,
+        device: &super::DeviceShared,
+        video: &VideoQueue,
+    ) -> Result<(), crate::DeviceError> {
+        let get_requirements = video.fns.fp().get_video_session_memory_requirements_khr;
+        let mut count = 0;
+        let result = unsafe {
+            get_requirements(
+                device.raw.handle(),
+                self.session,
+                &mut count,
+                ptr::null_mut(),
+            )
+        };
+        if let Err(err) = result.result() {
+            return Err(super::map_host_device_oom_err(err));
+        }
+        let mut requirements =
+            vec![vk::VideoSessionMemoryRequirementsKHR::default(); count as usize];
+        let result = unsafe {
+            get_requirements(
+                device.raw.handle(),
+                self.session,
+                &mut count,
+                requirements.as_mut_ptr(),
+            )
+        };
+        if let Err(err) = result.result() {
+            return Err(super::map_host_device_oom_err(err));
+        }
+
+        let mut bind_infos = Vec::with_capacity(requirements.len());
+        for requirement in requirements.iter().take(count as usize) {
+            let memory_requirements = requirement.memory_requirements;
+            let Some(memory_type_index) = find_memory_type(
+                device,
+                memory_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )
+            .or_else(|| {
+                find_memory_type(
+                    device,
+                    memory_requirements.memory_type_bits,
+                    vk::MemoryPropertyFlags::empty(),
+                )
+            }) else {
+                return Err(crate::DeviceError::Unexpected);
+            };
+            let allocate_info = vk::MemoryAllocateInfo::default()
+                .allocation_size(memory_requirements.size)
+                .memory_type_index(memory_type_index);
+            let memory = match unsafe { device.raw.allocate_memory(&allocate_info, None) } {
+                Ok(memory) => memory,
+                Err(err) => return Err(super::map_host_device_oom_err(err)),
+            };
+            self.memory.push(memory);
+            bind_infos.push(
+                vk::BindVideoSessionMemoryInfoKHR::default()
+                    .memory_bind_index(requirement.memory_bind_index)
+                    .memory(memory)
+                    .memory_size(memory_requirements.size),
+            );
+        }
+        let result = unsafe {
+            (video.fns.fp().bind_video_session_memory_khr)(
+                device.raw.handle(),
+                self.session,
+                bind_infos.len() as u32,
+                bind_infos.as_ptr(),
+            )
+        };
+        if let Err(err) = result.result() {
+            return Err(super::map_host_device_oom_err(err));
+        }
+
+        let pool_info = vk::CommandPoolCreateInfo::default()
+            .queue_family_index(video.family_index)
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
+        self.state.get_mut().command_pool =
+            unsafe { device.raw.create_command_pool(&pool_info, None) }
+                .map_err(super::map_host_device_oom_err)?;
+        Ok(())
+    }
+
+    /// Destroys the decoder, once its decodes complete.
+    pub(super) unsafe fn destroy(self, device: &super::DeviceShared) {
+        let Some(video) = device.video.as_ref() else {
+            return;
+        };
+        let state = self.state.into_inner();
+
+        if let Some(last) = state.in_flight.back() {
+            let semaphores = [video.semaphore];
+            let values = [last.value];
+            let wait_info = vk::SemaphoreWaitInfo::default()
+                .semaphores(&semaphores)
+                .values(&values);
+            if let Err(err) = unsafe { device.raw.wait_semaphores(&wait_info, u64::MAX) } {
+                log::error!("Failed to wait for the decodes of a video decoder: {err}");
+            }
+        }
+
+        let destroy_parameters = video.fns.fp().destroy_video_session_parameters_khr;
+        for decode in state.in_flight {
+            unsafe { destroy_bitstream(device, decode.bitstream) };
+        }
+        for (_, parameters) in state.retired_parameters {
+            unsafe { destroy_parameters(device.raw.handle(), parameters, ptr::null()) };
+        }
+        for bitstream in state.free_bitstreams {
+            unsafe { destroy_bitstream(device, bitstream) };
+        }
+        if let Some((parameters, _)) = state.parameters {
+            unsafe { destroy_parameters(device.raw.handle(), parameters, ptr::null()) };
+        }
+        unsafe {
+            device.raw.destroy_command_pool(state.command_pool, None);
+            (video.fns.fp().destroy_video_session_khr)(
+                device.raw.handle(),
+                self.session,
+                ptr::null(),
+            );
+        }
+        for memory in self.memory {
+            unsafe { device.raw.free_memory(memory, None) };
+        }
+    }
+
+    /// Creates a picture of the decoder, as a texture with a view to decode into.
+    pub(super) unsafe fn create_picture(
+        &self,
+        device: &super::Device,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let shared = &*device.shared;
+        let video = shared
+            .video
+            .as_ref()
+            .ok_or(crate::DeviceError::Unexpected)?;
+
+        let format = shared.private_caps.map_texture_format(desc.format);
+        let copy_size = desc.copy_extent();
+        let mut queue_family_indices = ArrayVec::<u32, 4>::new();
+        queue_family_indices.extend(shared.queue_family_indices());
+        queue_family_indices.push(video.family_index);
+        let raw = self.profile.with(|profile| {
+            let mut profile_list =
+                vk::VideoProfileListInfoKHR::default().profiles(slice::from_ref(profile));
+            let info = vk::ImageCreateInfo::default()
+                .flags(vk::ImageCreateFlags::MUTABLE_FORMAT)
+                .image_type(vk::ImageType::TYPE_2D)
+                .format(format)
+                .extent(conv::map_copy_extent(&copy_size))
+                .mip_levels(1)
+                .array_layers(1)
+                .samples(vk::SampleCountFlags::TYPE_1)
+                .tiling(vk::ImageTiling::OPTIMAL)
+                .usage(
+                    conv::map_texture_usage(desc.usage)
+                        | vk::ImageUsageFlags::VIDEO_DECODE_DST_KHR
+                        | vk::ImageUsageFlags::VIDEO_DECODE_DPB_KHR,
+                )
+                // Pictures move between the queues without ownership transfers.
+                .sharing_mode(vk::SharingMode::CONCURRENT)
+                .queue_family_indices(&queue_family_indices)
+                .initial_layout(vk::ImageLayout::UNDEFINED)
+                .push_next(&mut profile_list);
+            unsafe { shared.raw.create_image(&info, None) }
+                .map_err(super::map_host_device_oom_and_ioca_err)
+        })?;
+        let image = super::device::ImageWithoutMemory {
+            raw,
+            requirements: unsafe { shared.raw.get_image_memory_requirements(raw) },
+            copy_size,
+        };
+        let mut texture = unsafe { device.allocate_texture(desc, image) }?;
+
+        // The view decodes are done through. It isn't sampled, so it doesn't need a sampler
+        // Y'CbCr conversion.
+        let mut usage_info = vk::ImageViewUsageCreateInfo::default().usage(
+            vk::ImageUsageFlags::VIDEO_DECODE_DST_KHR | vk::ImageUsageFlags::VIDEO_DECODE_DPB_KHR,
+        );
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(texture.raw)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(COLOR_RANGE)
+            .push_next(&mut usage_info);
+        match unsafe { shared.raw.create_image_view(&view_info, None) } {
+            Ok(view) => {
+                texture.video_view = Some(view);
+                Ok(texture)
+            }
+            Err(err) => {
+                unsafe { crate::Device::destroy_texture(device, texture) };
+                Err(super::map_host_device_oom_err(err))
+            }
+        }
+    }
+
+    /// Decodes a picture, as described by [`crate::Queue::decode_video`].
+    pub(super) unsafe fn decode(
+        &self,
+        device: &super::DeviceShared,
+        desc: &crate::VideoDecodeDescriptor<super::Texture>,
+        (wait_fence, wait_value): (&super::Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        let video = device
+            .video
+            .as_ref()
+            .ok_or(crate::DeviceError::Unexpected)?;
+        let super::Fence::TimelineSemaphore(wait_semaphore) = *wait_fence else {
+            return Err(crate::DeviceError::Unexpected);
+        };
+
+        let mut state = self.state.lock();
+        unsafe { self.retire(device, video, &mut state) }?;
+
+        let bitstream = unsafe { self.write_bitstream(device, &mut state, desc.bitstream) }?;
+        let command_buffer = match state.free_command_buffers.pop() {
+            Some(command_buffer) => command_buffer,
+            None => {
+                let allocate_info = vk::CommandBufferAllocateInfo::default()
+                    .command_pool(state.command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1);
+                match unsafe { device.raw.allocate_command_buffers(&allocate_info) } {
+                    Ok(command_buffers) => command_buffers[0],
+                    Err(err) => {
+                        state.free_bitstreams.push(bitstream);
+                        return Err(super::map_host_device_oom_err(err));
+                    }
+                }
+            }
+        };
+
+        let result = unsafe {
+            self.record_and_submit(
+                device,
+                video,
+                &mut state,
+                desc,
+                command_buffer,
+                &bitstream,
+                (wait_semaphore, wait_value),
+            )
+        };
+        match result {
+            Ok(value) => {
+                state.in_flight.push_back(InFlightDecode {
+                    value,
+                    command_buffer,
+                    bitstream,
+                });
+                Ok(())
+            }
+            Err(err) => {
+                state.free_command_buffers.push(command_buffer);
+                state.free_bitstreams.push(bitstream);
+                Err(err)
+            }
+        }
+    }
+
+    /// Frees the resources of the decodes that completed.
+    unsafe fn retire(
+        &self,
+        device: &super::DeviceShared,
+        video: &VideoQueue,
+        state: &mut DecoderState,
+    ) -> Result<(), crate::DeviceError> {
+        if state.in_flight.is_empty() && state.retired_parameters.is_empty() {
+            return Ok(());
+        }
+        let completed = unsafe { device.raw.get_semaphore_counter_value(video.semaphore) }
+            .map_err(super::map_host_device_oom_and_lost_err)?;
+        while state
+            .in_flight
+            .front()
+            .is_some_and(|decode| decode.value <= completed)
+        {
+            let decode = state.in_flight.pop_front().unwrap();
+            state.free_command_buffers.push(decode.command_buffer);
+            state.free_bitstreams.push(decode.bitstream);
+        }
+        state.retired_parameters.retain(|&(value, parameters)| {
+            let in_use = value > completed;
+            if !in_use {
+                unsafe {
+                    (video.fns.fp().destroy_video_session_parameters_khr)(
+                        device.raw.handle(),
+                        parameters,
+                        ptr::null(),
+                    )
+                };
+            }
+            in_use
+        });
+        Ok(())
+    }
+
+    /// Copies `data` into a free bitstream buffer, creating one if none is large enough.
+    unsafe fn write_bitstream(
+        &self,
+        device: &super::DeviceShared,
+        state: &mut DecoderState,
+        data: &[u8],
+    ) -> Result<Bitstream, crate::DeviceError> {
+        let size = (data.len() as u64).next_multiple_of(self.bitstream_size_alignment);
+        let bitstream = match state
+            .free_bitstreams
+            .iter()
+            .position(|bitstream| bitstream.size >= size)
+        {
+            Some(index) => state.free_bitstreams.swap_remove(index),
+            None => unsafe {
+                self.create_bitstream(
+                    device,
+                    size.next_power_of_two().max(MIN_BITSTREAM_BUFFER_SIZE),
+                )
+            }?,
+        };
+
+        let mapped = unsafe {
+            device
+                .raw
+                .map_memory(bitstream.memory, 0, size, vk::MemoryMapFlags::empty())
+        };
+        let mapped = match mapped {
+            Ok(mapped) => mapped.cast::<u8>(),
+            Err(err) => {
+                state.free_bitstreams.push(bitstream);
+                return Err(super::map_host_device_oom_err(err));
+            }
+        };
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), mapped, data.len());
+            ptr::write_bytes(mapped.add(data.len()), 0, size as usize - data.len());
+            device.raw.unmap_memory(bitstream.memory);
+        }
+        Ok(bitstream)
+    }
+
+    unsafe fn create_bitstream(
+        &self,
+        device: &super::DeviceShared,
+        size: u64,
+    ) -> Result<Bitstream, crate::DeviceError> {
+        let buffer = self.profile.with(|profile| {
+            let mut profile_list =
+                vk::VideoProfileListInfoKHR::default().profiles(slice::from_ref(profile));
+            let info = vk::BufferCreateInfo::default()
+                .size(size)
+                .usage(vk::BufferUsageFlags::VIDEO_DECODE_SRC_KHR)
+                .sharing_mode(vk::SharingMode::EXCLUSIVE)
+                .push_next(&mut profile_list);
+            unsafe { device.raw.create_buffer(&info, None) }
+                .map_err(super::map_host_device_oom_and_ioca_err)
+        })?;
+
+        let requirements = unsafe { device.raw.get_buffer_memory_requirements(buffer) };
+        let memory = find_memory_type(
+            device,
+            requirements.memory_type_bits,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )
+        .ok_or(crate::DeviceError::Unexpected)
+        .and_then(|memory_type_index| {
+            let allocate_info = vk::MemoryAllocateInfo::default()
+                .allocation_size(requirements.size)
+                .memory_type_index(memory_type_index);
+            unsafe { device.raw.allocate_memory(&allocate_info, None) }
+                .map_err(super::map_host_device_oom_err)
+        })
+        .inspect_err(|_| unsafe { device.raw.destroy_buffer(buffer, None) })?;
+
+        unsafe { device.raw.bind_buffer_memory(buffer, memory, 0) }
+            .map_err(super::map_host_device_oom_and_ioca_err)
+            .inspect_err(|_| unsafe {
+                device.raw.destroy_buffer(buffer, None);
+                device.raw.free_memory(memory, None);
+            })?;
+
+        Ok(Bitstream {
+            buffer,
+            memory,
+            size,
+        })
+    }
+
+    /// Returns the session parameters of `picture`, creating them if its parameter sets
+    /// changed.
+    unsafe fn update_parameters(
+        &self,
+        device: &super::DeviceShared,
+        video: &VideoQueue,
+        state: &mut DecoderState,
+        picture: &wgt::VideoPictureInfo<'_>,
+    ) -> Result<vk::VideoSessionParametersKHR, crate::DeviceError> {
+        if let Some((parameters, ref sets)) = state.parameters {
+            if sets.matches(picture) {
+                return Ok(parameters);
+            }
+        }
+
+        let create_info =
+            vk::VideoSessionParametersCreateInfoKHR::default().video_session(self.session);
+        let parameters = match *picture {
+            wgt::VideoPictureInfo::H264(ref picture) => {
+                let sps_scaling_lists = picture.sps.scaling_lists.as_ref().map(h264_scaling_lists);
+                let pps_scaling_lists = picture.pps.scaling_lists.as_ref().map(h264_scaling_lists);
+                let sps = h264_sps(picture.sps, sps_scaling_lists.as_ref());
+                let pps = h264_pps(picture.pps, pps_scaling_lists.as_ref());
+                let add_info = vk::VideoDecodeH264SessionParametersAddInfoKHR::default()
+                    .std_sp_ss(slice::from_ref(&sps))
+                    .std_pp_ss(slice::from_ref(&pps));
+                let mut codec_info = vk::VideoDecodeH264SessionParametersCreateInfoKHR::default()
+                    .max_std_sps_count(1)
+                    .max_std_pps_count(1)
+                    .parameters_add_info(&add_info);
+                unsafe { create_parameters(device, video, &create_info.push_next(&mut codec_info)) }
+            }
+            wgt::VideoPictureInfo::H265(ref picture) => {
+                let (vps, sps, pps) = (picture.vps, picture.sps, picture.pps);
+                let vps_profile_tier_level = h265_profile_tier_level(&vps.profile_tier_level);
+                let vps_dec_pic_buf_mgr = h265_dec_pic_buf_mgr(&vps.dec_pic_buf_mgr);
+                let std_vps = h265_vps(vps, &vps_profile_tier_level, &vps_dec_pic_buf_mgr);
+
+                let sps_parts = H265SpsParts {
+                    profile_tier_level: h265_profile_tier_level(&sps.profile_tier_level),
+                    dec_pic_buf_mgr: h265_dec_pic_buf_mgr(&sps.dec_pic_buf_mgr),
+                    scaling_lists: sps.scaling_lists.as_ref().map(h265_scaling_lists),
+                    short_term_ref_pic_sets: sps
+                        .short_term_ref_pic_sets
+                        .iter()
+                        .map(h265_short_term_ref_pic_set)
+                        .collect(),
+                    long_term_ref_pics_sps: native::StdVideoH265LongTermRefPicsSps {
+                        used_by_curr_pic_lt_sps_flag: sps
+                            .long_term_ref_pics_sps
+                            .used_by_curr_pic_lt_sps_flag,
+                        lt_ref_pic_poc_lsb_sps: sps.long_term_ref_pics_sps.lt_ref_pic_poc_lsb_sps,
+                    },
+                };
+                let std_sps = h265_sps(sps, &sps_parts);
+
+                let pps_scaling_lists = pps.scaling_lists.as_ref().map(h265_scaling_lists);
+                let std_pps = h265_pps(
+                    pps,
+                    sps.sps_video_parameter_set_id,
+                    pps_scaling_lists.as_ref(),
+                );
+
+                let add_info = vk::VideoDecodeH265SessionParametersAddInfoKHR::default()
+                    .std_vp_ss(slice::from_ref(&std_vps))
+                    .std_sp_ss(slice::from_ref(&std_sps))
+                    .std_pp_ss(slice::from_ref(&std_pps));
+                let mut codec_info = vk::VideoDecodeH265SessionParametersCreateInfoKHR::default()
+                    .max_std_vps_count(1)
+                    .max_std_sps_count(1)
+                    .max_std_pps_count(1)
+                    .parameters_add_info(&add_info);
+                unsafe { create_parameters(device, video, &create_info.push_next(&mut codec_info)) }
+            }
+            wgt::VideoPictureInfo::Av1(ref picture) => {
+                let header = picture.sequence_header;
+                let color_config = av1_color_config(&header.color_config);
+                let timing_info = header.timing_info.as_ref().map(av1_timing_info);
+                let std_header = av1_sequence_header(header, &color_config, timing_info.as_ref());
+                let mut codec_info = vk::VideoDecodeAV1SessionParametersCreateInfoKHR::default()
+                    .std_sequence_header(&std_header);
+                unsafe { create_parameters(device, video, &create_info.push_next(&mut codec_info)) }
+            }
+        }?;
+
+        // The replaced parameters are destroyed once the decodes using them complete.
+        if let Some((retired, _)) = state
+            .parameters
+            .replace((parameters, ParameterSets::new(picture)))
+        {
+            let last_value = state.in_flight.back().map_or(0, |decode| decode.value);
+            state.retired_parameters.push((last_value, retired));
+        }
+        Ok(parameters)
+    }
+
+    /// Records the decode into `command_buffer` and submits it to the video queue.
+    ///
+    /// Returns the value of the semaphore of the video queue the decode signals.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn record_and_submit(
+        &self,
+        device: &super::DeviceShared,
+        video: &VideoQueue,
+        state: &mut DecoderState,
+        desc: &crate::VideoDecodeDescriptor<super::Texture>,
+        command_buffer: vk::CommandBuffer,
+        bitstream: &Bitstream,
+        (wait_semaphore, wait_value): (vk::Semaphore, u64),
+    ) -> Result<u64, crate::DeviceError> {
+        let parameters = unsafe { self.update_parameters(device, video, state, &desc.picture) }?;
+
+        let coded_extent = desc.picture.coded_extent();
+        let coded_extent = vk::Extent2D {
+            width: coded_extent.width,
+            height: coded_extent.height,
+        };
+        // As decoded pictures coincide with reconstructed ones, the target is in the layout of
+        // the decoded picture buffer even when it isn't a reference.
+        let pictures = desc
+            .references
+            .iter()
+            .map(|reference| &reference.picture)
+            .chain([&desc.target])
+            .collect::<Vec<_>>();
+
+        let recording = Recording {
+            command_buffer,
+            parameters,
+            reset: !state.reset,
+            bitstream: bitstream.buffer,
+            bitstream_range: (desc.bitstream.len() as u64)
+                .next_multiple_of(self.bitstream_size_alignment),
+            resources: pictures
+                .iter()
+                .map(|picture| {
+                    vk::VideoPictureResourceInfoKHR::default()
+                        .coded_extent(coded_extent)
+                        .image_view_binding(
+                            picture
+                                .texture
+                                .video_view
+                                .expect("texture is not a video picture"),
+                        )
+                })
+                .collect(),
+            slots: pictures.iter().map(|picture| picture.slot as i32).collect(),
+            target_is_reference: desc.target_reference.is_some(),
+        };
+
+        let barriers_before = pictures
+            .iter()
+            .map(|picture| {
+                let texture = picture.texture;
+                picture_barrier(
+                    texture,
+                    conv::derive_image_layout(picture.usage.from, texture.format),
+                    vk::ImageLayout::VIDEO_DECODE_DPB_KHR,
+                )
+            })
+            .collect::<Vec<_>>();
+        let barriers_after = pictures
+            .iter()
+            .map(|picture| {
+                let texture = picture.texture;
+                picture_barrier(
+                    texture,
+                    vk::ImageLayout::VIDEO_DECODE_DPB_KHR,
+                    conv::derive_image_layout(picture.usage.to, texture.format),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        unsafe { device.raw.begin_command_buffer(command_buffer, &begin_info) }
+            .map_err(super::map_host_device_oom_err)?;
+        unsafe {
+            device.raw.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &barriers_before,
+            )
+        };
+
+        let reference_slots = desc
+            .references
+            .iter()
+            .map(|reference| reference.picture.slot)
+            .collect::<Vec<_>>();
+        match desc.picture {
+            wgt::VideoPictureInfo::H264(ref picture) => {
+                let std_picture = h264_picture_info(picture);
+                let mut picture_info = vk::VideoDecodeH264PictureInfoKHR::default()
+                    .std_picture_info(&std_picture)
+                    .slice_offsets(picture.slice_offsets);
+                let std_references = reference_infos(desc)
+                    .map(|info| match *info {
+                        wgt::VideoReferenceInfo::H264(ref info) => h264_reference_info(info),
+                        _ => panic!("reference picture of another codec"),
+                    })
+                    .collect::<Vec<_>>();
+                let mut dpb_slot_infos = std_references
+                    .iter()
+                    .map(|info| {
+                        vk::VideoDecodeH264DpbSlotInfoKHR::default().std_reference_info(info)
+                    })
+                    .collect::<Vec<_>>();
+                unsafe { self.record(video, &recording, &mut picture_info, &mut dpb_slot_infos) };
+            }
+            wgt::VideoPictureInfo::H265(ref picture) => {
+                let std_picture = h265_picture_info(picture, &reference_slots);
+                let mut picture_info = vk::VideoDecodeH265PictureInfoKHR::default()
+                    .std_picture_info(&std_picture)
+                    .slice_segment_offsets(picture.slice_segment_offsets);
+                let std_references = reference_infos(desc)
+                    .map(|info| match *info {
+                        wgt::VideoReferenceInfo::H265(ref info) => h265_reference_info(info),
+                        _ => panic!("reference picture of another codec"),
+                    })
+                    .collect::<Vec<_>>();
+                let mut dpb_slot_infos = std_references
+                    .iter()
+                    .map(|info| {
+                        vk::VideoDecodeH265DpbSlotInfoKHR::default().std_reference_info(info)
+                    })
+                    .collect::<Vec<_>>();
+                unsafe { self.record(video, &recording, &mut picture_info, &mut dpb_slot_infos) };
+            }
+            wgt::VideoPictureInfo::Av1(ref picture) => {
+                let tile_info = av1_tile_info(&picture.tile_info);
+                let quantization = av1_quantization(picture.quantization);
+                let segmentation = native::StdVideoAV1Segmentation {
+                    FeatureEnabled: picture.segmentation.feature_enabled,
+                    FeatureData: picture.segmentation.feature_data,
+                };
+                let loop_filter = av1_loop_filter(picture.loop_filter);
+                let cdef = av1_cdef(picture.cdef);
+                let loop_restoration = native::StdVideoAV1LoopRestoration {
+                    FrameRestorationType: picture
+                        .loop_restoration
+                        .frame_restoration_type
+                        .map(u32::from),
+                    LoopRestorationSize: picture.loop_restoration.loop_restoration_size,
+                };
+                let global_motion = native::StdVideoAV1GlobalMotion {
+                    GmType: picture.global_motion.gm_type,
+                    gm_params: picture.global_motion.gm_params,
+                };
+                let mut std_picture = av1_picture_info(picture);
+                std_picture.pTileInfo = &tile_info;
+                std_picture.pQuantization = &quantization;
+                std_picture.pSegmentation = &segmentation;
+                std_picture.pLoopFilter = &loop_filter;
+                std_picture.pCDEF = &cdef;
+                std_picture.pLoopRestoration = &loop_restoration;
+                std_picture.pGlobalMotion = &global_motion;
+
+                let reference_name_slot_indices = picture.reference_name_indices.map(|index| {
+                    index.map_or(-1, |index| reference_slots[usize::from(index)] as i32)
+                });
+                let mut picture_info = vk::VideoDecodeAV1PictureInfoKHR::default()
+                    .std_picture_info(&std_picture)
+                    .reference_name_slot_indices(reference_name_slot_indices)
+                    .frame_header_offset(picture.frame_header_offset)
+                    .tile_offsets(picture.tile_offsets)
+                    .tile_sizes(picture.tile_sizes);
+                let std_references = reference_infos(desc)
+                    .map(|info| match *info {
+                        wgt::VideoReferenceInfo::Av1(ref info) => av1_reference_info(info),
+                        _ => panic!("reference picture of another codec"),
+                    })
+                    .collect::<Vec<_>>();
+                let mut dpb_slot_infos = std_references
+                    .iter()
+                    .map(|info| {
+                        vk::VideoDecodeAV1DpbSlotInfoKHR::default().std_reference_info(info)
+                    })
+                    .collect::<Vec<_>>();
+                unsafe { self.record(video, &recording, &mut picture_info, &mut dpb_slot_infos) };
+            }
+        }
+
+        unsafe {
+            device.raw.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &barriers_after,
+            )
+        };
+        unsafe { device.raw.end_command_buffer(command_buffer) }
+            .map_err(super::map_host_device_oom_err)?;
+
+        let raw_queue = video.raw.lock();
+        let value = video.last_value.load(Ordering::Relaxed) + 1;
+        let wait_semaphores = [wait_semaphore];
+        let wait_values = [wait_value];
+        let signal_semaphores = [video.semaphore];
+        let signal_values = [value];
+        let command_buffers = [command_buffer];
+        let mut timeline_info = vk::TimelineSemaphoreSubmitInfo::default()
+            .wait_semaphore_values(&wait_values)
+            .signal_semaphore_values(&signal_values);
+        let submit_info = vk::SubmitInfo::default()
+            .wait_semaphores(&wait_semaphores)
+            .wait_dst_stage_mask(&[vk::PipelineStageFlags::ALL_COMMANDS])
+            .command_buffers(&command_buffers)
+            .signal_semaphores(&signal_semaphores)
+            .push_next(&mut timeline_info);
+        profiling::scope!("vkQueueSubmit");
+        unsafe {
+            device
+                .raw
+                .queue_submit(*raw_queue, &[submit_info], vk::Fence::null())
+        }
+        .map_err(super::map_host_device_oom_and_lost_err)?;
+        video.last_value.store(value, Ordering::Release);
+        drop(raw_queue);
+
+        state.reset = true;
+        Ok(value)
+    }
+
+    /// Records the video coding scope of a decode.
+    ///
+    /// `dpb_slot_infos` hold the codec-specific information of the references, followed by
+    /// the one of the target if it's a reference.
+    unsafe fn record<P, D>(
+        &self,
+        video: &VideoQueue,
+        recording: &Recording<'_>,
+        picture_info: &mut P,
+        dpb_slot_infos: &mut [D],
+    ) where
+        P: vk::ExtendsVideoDecodeInfoKHR,
+        D: vk::ExtendsVideoReferenceSlotInfoKHR,
+    {
+        let command_buffer = recording.command_buffer;
+        let slots = dpb_slot_infos
+            .iter_mut()
+            .zip(&recording.resources)
+            .zip(&recording.slots)
+            .map(|((dpb_slot_info, resource), &slot)| {
+                vk::VideoReferenceSlotInfoKHR::default()
+                    .slot_index(slot)
+                    .picture_resource(resource)
+                    .push_next(dpb_slot_info)
+            })
+            .collect::<Vec<_>>();
+
+        // The target is bound without a slot, which the decode then activates.
+        let mut begin_slots = slots.clone();
+        if recording.target_is_reference {
+            begin_slots.last_mut().unwrap().slot_index = -1;
+        }
+        let begin_info = vk::VideoBeginCodingInfoKHR::default()
+            .video_session(self.session)
+            .video_session_parameters(recording.parameters)
+            .reference_slots(&begin_slots);
+        unsafe { (video.fns.fp().cmd_begin_video_coding_khr)(command_buffer, &begin_info) };
+
+        if recording.reset {
+            let control_info = vk::VideoCodingControlInfoKHR::default()
+                .flags(vk::VideoCodingControlFlagsKHR::RESET);
+            unsafe { (video.fns.fp().cmd_control_video_coding_khr)(command_buffer, &control_info) };
+        }
+
+        let (reference_slots, setup_slot) = match slots.split_last() {
+            Some((setup_slot, reference_slots)) if recording.target_is_reference => {
+                (reference_slots, Some(setup_slot))
+            }
+            _ => (&slots[..], None),
+        };
+        let mut decode_info = vk::VideoDecodeInfoKHR::default()
+            .src_buffer(recording.bitstream)
+            .src_buffer_offset(0)
+            .src_buffer_range(recording.bitstream_range)
+            .dst_picture_resource(*recording.resources.last().unwrap())
+            .reference_slots(reference_slots)
+            .push_next(picture_info);
+        if let Some(setup_slot) = setup_slot {
+            decode_info = decode_info.setup_reference_slot(setup_slot);
+        }
+        unsafe { (video.decode_fns.fp().cmd_decode_video_khr)(command_buffer, &decode_info) };
+
+        let end_info = vk::VideoEndCodingInfoKHR::default();
+        unsafe { (video.fns.fp().cmd_end_video_coding_khr)(command_buffer, &end_info) };
+    }
+}
+
+/// What a decode is recorded with, besides its codec-specific information.
+struct Recording<'a> {
+    command_buffer: vk::CommandBuffer,
+    parameters: vk::VideoSessionParametersKHR,
+    /// True if the session must be reset before the decode.
+    reset: bool,
+    bitstream: vk::Buffer,
+    bitstream_range: u64,
+    /// The pictures of the references, followed by the one of the target.
+    resources: Vec<vk::VideoPictureResourceInfoKHR<'a>>,
+    /// The slots of the references, followed by the one of the target.
+    slots: Vec<i32>,
+    target_is_reference: bool,
+}
+
+/// The information of the references of `desc`, followed by the one of its target if it's
+/// a reference.
+fn reference_infos<'a>(
+    desc: &'a crate::VideoDecodeDescriptor<'a, super::Texture>,
+) -> impl Iterator<Item = &'a wgt::VideoReferenceInfo> {
+    desc.references
+        .iter()
+        .map(|reference| &reference.info)
+        .chain(desc.target_reference.as_ref())
+}
+
+fn picture_barrier(
+    texture: &super::Texture,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+) -> vk::ImageMemoryBarrier<'static> {
+    vk::ImageMemoryBarrier::default()
+        .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+        .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(texture.raw)
+        .subresource_range(COLOR_RANGE)
+}
+
+unsafe fn create_parameters(
+    device: &super::DeviceShared,
+    video: &VideoQueue,
+    create_info: &vk::VideoSessionParametersCreateInfoKHR<'_>,
+) -> Result<vk::VideoSessionParametersKHR, crate::DeviceError> {
+    let mut parameters = vk::VideoSessionParametersKHR::null();
+    unsafe {
+        (video.fns.fp().create_video_session_parameters_khr)(
+            device.raw.handle(),
+            create_info,
+            ptr::null(),
+            &mut parameters,
+        )
+    }
+    .result_with_success(parameters)
+    .map_err(super::map_host_device_oom_err)
+}
+
+unsafe fn destroy_bitstream(device: &super::DeviceShared, bitstream: Bitstream) {
+    unsafe {
+        device.raw.destroy_buffer(bitstream.buffer, None);
+        device.raw.free_memory(bitstream.memory, None);
+    }
+}
+
+fn find_memory_type(
+    device: &super::DeviceShared,
+    type_bits: u32,
+    flags: vk::MemoryPropertyFlags,
+) -> Option<u32> {
+    let properties = unsafe {
+        device
+            .instance
+            .raw
+            .get_physical_device_memory_properties(device.physical_device)
+    };
+    properties
+        .memory_types_as_slice()
+        .iter()
+        .enumerate()
+        .position(|(index, memory_type)| {
+            type_bits & (1 << index) != 0 && memory_type.property_flags.contains(flags)
+        })
+        .map(|index| index as u32)
+}
+
+// Conversions of the parameters of each codec into the structures of the Vulkan Video
+// headers. Those have no `Default`, so they are zeroed before their flags are set.
+
+/// `level_idc` of each `StdVideoH264LevelIdc`.
+const H264_LEVELS: [u8; 19] = [
+    10, 11, 12, 13, 20, 21, 22, 30, 31, 32, 40, 41, 42, 50, 51, 52, 60, 61, 62,
+];
+
+/// `general_level_idc` of each `StdVideoH265LevelIdc`.
+const H265_LEVELS: [u8; 13] = [30, 60, 63, 90, 93, 120, 123, 150, 153, 156, 180, 183, 186];
+
+/// Maps a level to its index in `levels`, or to the highest level if it isn't one of them.
+fn map_level(levels: &[u8], level: u8) -> u32 {
+    levels
+        .iter()
+        .position(|&known| known == level)
+        .unwrap_or(levels.len() - 1) as u32
+}
+
+fn h264_scaling_lists(lists: &h264::ScalingLists) -> native::StdVideoH264ScalingLists {
+    native::StdVideoH264ScalingLists {
+        scaling_list_present_mask: lists.scaling_list_present_mask,
+        use_default_scaling_matrix_mask: lists.use_default_scaling_matrix_mask,
+        ScalingList4x4: lists.scaling_list_4x4,
+        ScalingList8x8: lists.scaling_list_8x8,
+    }
+}
+
+fn h264_sps(
+    sps: &h264::SequenceParameterSet,
+    scaling_lists: Option<&native::StdVideoH264ScalingLists>,
+) -> native::StdVideoH264SequenceParameterSet {
+    let mut flags: native::StdVideoH264SpsFlags = unsafe { mem::zeroed() };
+    flags.set_constraint_set0_flag(sps.constraint_set0_flag.into());
+    flags.set_constraint_set1_flag(sps.constraint_set1_flag.into());
+    flags.set_constraint_set2_flag(sps.constraint_set2_flag.into());
+    flags.set_constraint_set3_flag(sps.constraint_set3_flag.into());
+    flags.set_constraint_set4_flag(sps.constraint_set4_flag.into());
+    flags.set_constraint_set5_flag(sps.constraint_set5_flag.into());
+    flags.set_direct_8x8_inference_flag(sps.direct_8x8_inference_flag.into());
+    flags.set_mb_adaptive_frame_field_flag(sps.mb_adaptive_frame_field_flag.into());
+    flags.set_frame_mbs_only_flag(sps.frame_mbs_only_flag.into());
+    flags.set_delta_pic_order_always_zero_flag(sps.delta_pic_order_always_zero_flag.into());
+    flags.set_separate_colour_plane_flag(sps.separate_colour_plane_flag.into());
+    flags.set_gaps_in_frame_num_value_allowed_flag(sps.gaps_in_frame_num_value_allowed_flag.into());
+    flags.set_qpprime_y_zero_transform_bypass_flag(sps.qpprime_y_zero_transform_bypass_flag.into());
+    flags.set_frame_cropping_flag(sps.frame_crop_offsets.is_some().into());
+    flags.set_seq_scaling_matrix_present_flag(scaling_lists.is_some().into());
+
+    let [left, right, top, bottom] = sps.frame_crop_offsets.unwrap_or_default();
+    native::StdVideoH264SequenceParameterSet {
+        flags,
+        profile_idc: sps.profile_idc.into(),
+        level_idc: map_level(&H264_LEVELS, sps.level_idc),
+        chroma_format_idc: sps.chroma_format_idc.into(),
+        seq_parameter_set_id: sps.seq_parameter_set_id,
+        bit_depth_luma_minus8: sps.bit_depth_luma_minus8,
+        bit_depth_chroma_minus8: sps.bit_depth_chroma_minus8,
+        log2_max_frame_num_minus4: sps.log2_max_frame_num_minus4,
+        pic_order_cnt_type: sps.pic_order_cnt_type.into(),
+        offset_for_non_ref_pic: sps.offset_for_non_ref_pic,
+        offset_for_top_to_bottom_field: sps.offset_for_top_to_bottom_field,
+        log2_max_pic_order_cnt_lsb_minus4: sps.log2_max_pic_order_cnt_lsb_minus4,
+        num_ref_frames_in_pic_order_cnt_cycle: sps.offset_for_ref_frame.len() as u8,
+        max_num_ref_frames: sps.max_num_ref_frames,
+        reserved1: 0,
+        pic_width_in_mbs_minus1: sps.pic_width_in_mbs_minus1,
+        pic_height_in_map_units_minus1: sps.pic_height_in_map_units_minus1,
+        frame_crop_left_offset: left,
+        frame_crop_right_offset: right,
+        frame_crop_top_offset: top,
+        frame_crop_bottom_offset: bottom,
+        reserved2: 0,
+        pOffsetForRefFrame: sps.offset_for_ref_frame.as_ptr(),
+        pScalingLists: scaling_lists.map_or(ptr::null(), ptr::from_ref),
+        pSequenceParameterSetVui: ptr::null(),
+    }
+}
+
+fn h264_pps(
+    pps: &h264::PictureParameterSet,
+    scaling_lists: Option<&native::StdVideoH264ScalingLists>,
+) -> native::StdVideoH264PictureParameterSet {
+    let mut flags: native::StdVideoH264PpsFlags = unsafe { mem::zeroed() };
+    flags.set_transform_8x8_mode_flag(pps.transform_8x8_mode_flag.into());
+    flags.set_redundant_pic_cnt_present_flag(pps.redundant_pic_cnt_present_flag.into());
+    flags.set_constrained_intra_pred_flag(pps.constrained_intra_pred_flag.into());
+    flags.set_deblocking_filter_control_present_flag(
+        pps.deblocking_filter_control_present_flag.into(),
+    );
+    flags.set_weighted_pred_flag(pps.weighted_pred_flag.into());
+    flags.set_bottom_field_pic_order_in_frame_present_flag(
+        pps.bottom_field_pic_order_in_frame_present_flag.into(),
+    );
+    flags.set_entropy_coding_mode_flag(pps.entropy_coding_mode_flag.into());
+    flags.set_pic_scaling_matrix_present_flag(scaling_lists.is_some().into());
+
+    native::StdVideoH264PictureParameterSet {
+        flags,
+        seq_parameter_set_id: pps.seq_parameter_set_id,
+        pic_parameter_set_id: pps.pic_parameter_set_id,
+        num_ref_idx_l0_default_active_minus1: pps.num_ref_idx_l0_default_active_minus1,
+        num_ref_idx_l1_default_active_minus1: pps.num_ref_idx_l1_default_active_minus1,
+        weighted_bipred_idc: pps.weighted_bipred_idc.into(),
+        pic_init_qp_minus26: pps.pic_init_qp_minus26,
+        pic_init_qs_minus26: pps.pic_init_qs_minus26,
+        chroma_qp_index_offset: pps.chroma_qp_index_offset,
+        second_chroma_qp_index_offset: pps.second_chroma_qp_index_offset,
+        pScalingLists: scaling_lists.map_or(ptr::null(), ptr::from_ref),
+    }
+}
+
+fn h264_picture_info(picture: &h264::PictureInfo<'_>) -> native::StdVideoDecodeH264PictureInfo {
+    let mut flags: native::StdVideoDecodeH264PictureInfoFlags = unsafe { mem::zeroed() };
+    flags.set_is_intra(picture.is_intra.into());
+    flags.set_IdrPicFlag(picture.idr_pic_flag.into());
+    flags.set_is_reference(picture.is_reference.into());
+
+    native::StdVideoDecodeH264PictureInfo {
+        flags,
+        seq_parameter_set_id: picture.sps.seq_parameter_set_id,
+        pic_parameter_set_id: picture.pps.pic_parameter_set_id,
+        reserved1: 0,
+        reserved2: 0,
+        frame_num: picture.frame_num,
+        idr_pic_id: picture.idr_pic_id,
+        PicOrderCnt: picture.pic_order_cnt,
+    }
+}
+
+fn h264_reference_info(info: &h264::ReferenceInfo) -> native::StdVideoDecodeH264ReferenceInfo {
+    let mut flags: native::StdVideoDecodeH264ReferenceInfoFlags = unsafe { mem::zeroed() };
+    flags.set_used_for_long_term_reference(info.used_for_long_term_reference.into());
+    flags.set_is_non_existing(info.is_non_existing.into());
+
+    native::StdVideoDecodeH264ReferenceInfo {
+        flags,
+        FrameNum: info.frame_num,
+        reserved: 0,
+        PicOrderCnt: info.pic_order_cnt,
+    }
+}
+
+fn h265_profile_tier_level(
+    profile_tier_level: &h265::ProfileTierLevel,
+) -> native::StdVideoH265ProfileTierLevel {
+    let mut flags: native::StdVideoH265ProfileTierLevelFlags = unsafe { mem::zeroed() };
+    flags.set_general_tier_flag(profile_tier_level.general_tier_flag.into());
+    flags.set_general_progressive_source_flag(
+        profile_tier_level.general_progressive_source_flag.into(),
+    );
+    flags.set_general_interlaced_source_flag(
+        profile_tier_level.general_interlaced_source_flag.into(),
+    );
+    flags.set_general_non_packed_constraint_flag(
+        profile_tier_level.general_non_packed_constraint_flag.into(),
+    );
+    flags.set_general_frame_only_constraint_flag(
+        profile_tier_level.general_frame_only_constraint_flag.into(),
+    );
+
+    native::StdVideoH265ProfileTierLevel {
+        flags,
+        general_profile_idc: profile_tier_level.general_profile_idc.into(),
+        general_level_idc: map_level(&H265_LEVELS, profile_tier_level.general_level_idc),
+    }
+}
+
+fn h265_dec_pic_buf_mgr(dec_pic_buf_mgr: &h265::DecPicBufMgr) -> native::StdVideoH265DecPicBufMgr {
+    native::StdVideoH265DecPicBufMgr {
+        max_latency_increase_plus1: dec_pic_buf_mgr.max_latency_increase_plus1,
+        max_dec_pic_buffering_minus1: dec_pic_buf_mgr.max_dec_pic_buffering_minus1,
+        max_num_reorder_pics: dec_pic_buf_mgr.max_num_reorder_pics,
+    }
+}
+
+fn h265_scaling_lists(lists: &h265::ScalingLists) -> native::StdVideoH265ScalingLists {
+    native::StdVideoH265ScalingLists {
+        ScalingList4x4: lists.scaling_list_4x4,
+        ScalingList8x8: lists.scaling_list_8x8,
+        ScalingList16x16: lists.scaling_list_16x16,
+        ScalingList32x32: lists.scaling_list_32x32,
+        ScalingListDCCoef16x16: lists.scaling_list_dc_coef_16x16,
+        ScalingListDCCoef32x32: lists.scaling_list_dc_coef_32x32,
+    }
+}
+
+fn h265_short_term_ref_pic_set(
+    set: &h265::ShortTermRefPicSet,
+) -> native::StdVideoH265ShortTermRefPicSet {
+    let mut flags: native::StdVideoH265ShortTermRefPicSetFlags = unsafe { mem::zeroed() };
+    flags.set_inter_ref_pic_set_prediction_flag(set.inter_ref_pic_set_prediction_flag.into());
+    flags.set_delta_rps_sign(set.delta_rps_sign.into());
+
+    native::StdVideoH265ShortTermRefPicSet {
+        flags,
+        delta_idx_minus1: set.delta_idx_minus1,
+        use_delta_flag: set.use_delta_flag,
+        abs_delta_rps_minus1: set.abs_delta_rps_minus1,
+        used_by_curr_pic_flag: set.used_by_curr_pic_flag,
+        used_by_curr_pic_s0_flag: set.used_by_curr_pic_s0_flag,
+        used_by_curr_pic_s1_flag: set.used_by_curr_pic_s1_flag,
+        reserved1: 0,
+        reserved2: 0,
+        reserved3: 0,
+        num_negative_pics: set.num_negative_pics,
+        num_positive_pics: set.num_positive_pics,
+        delta_poc_s0_minus1: set.delta_poc_s0_minus1,
+        delta_poc_s1_minus1: set.delta_poc_s1_minus1,
+    }
+}
+
+fn h265_vps(
+    vps: &h265::VideoParameterSet,
+    profile_tier_level: &native::StdVideoH265ProfileTierLevel,
+    dec_pic_buf_mgr: &native::StdVideoH265DecPicBufMgr,
+) -> native::StdVideoH265VideoParameterSet {
+    let mut flags: native::StdVideoH265VpsFlags = unsafe { mem::zeroed() };
+    flags.set_vps_temporal_id_nesting_flag(vps.vps_temporal_id_nesting_flag.into());
+    flags.set_vps_sub_layer_ordering_info_present_flag(
+        vps.vps_sub_layer_ordering_info_present_flag.into(),
+    );
+    flags.set_vps_timing_info_present_flag(vps.vps_timing_info_present_flag.into());
+    flags.set_vps_poc_proportional_to_timing_flag(vps.vps_poc_proportional_to_timing_flag.into());
+
+    native::StdVideoH265VideoParameterSet {
+        flags,
+        vps_video_parameter_set_id: vps.vps_video_parameter_set_id,
+        vps_max_sub_layers_minus1: vps.vps_max_sub_layers_minus1,
+        reserved1: 0,
+        reserved2: 0,
+        vps_num_units_in_tick: vps.vps_num_units_in_tick,
+        vps_time_scale: vps.vps_time_scale,
+        vps_num_ticks_poc_diff_one_minus1: vps.vps_num_ticks_poc_diff_one_minus1,
+        reserved3: 0,
+        pDecPicBufMgr: dec_pic_buf_mgr,
+        pHrdParameters: ptr::null(),
+        pProfileTierLevel: profile_tier_level,
+    }
+}
+
+/// The structures a `StdVideoH265SequenceParameterSet` points to.
+struct H265SpsParts {
+    profile_tier_level: native::StdVideoH265ProfileTierLevel,
+    dec_pic_buf_mgr: native::StdVideoH265DecPicBufMgr,
+    scaling_lists: Option<native::StdVideoH265ScalingLists>,
+    short_term_ref_pic_sets: Vec<native::StdVideoH265ShortTermRefPicSet>,
+    long_term_ref_pics_sps: native::StdVideoH265LongTermRefPicsSps,
+}
+
+fn h265_sps(
+    sps: &h265::SequenceParameterSet,
+    parts: &H265SpsParts,
+) -> native::StdVideoH265SequenceParameterSet {
+    let mut flags: native::StdVideoH265SpsFlags = unsafe { mem::zeroed() };
+    flags.set_sps_temporal_id_nesting_flag(sps.sps_temporal_id_nesting_flag.into());
+    flags.set_separate_colour_plane_flag(sps.separate_colour_plane_flag.into());
+    flags.set_conformance_window_flag(sps.conf_win_offsets.is_some().into());
+    flags.set_sps_sub_layer_ordering_info_present_flag(
+        sps.sps_sub_layer_ordering_info_present_flag.into(),
+    );
+    flags.set_scaling_list_enabled_flag(sps.scaling_list_enabled_flag.into());
+    flags.set_sps_scaling_list_data_present_flag(parts.scaling_lists.is_some().into());
+    flags.set_amp_enabled_flag(sps.amp_enabled_flag.into());
+    flags.set_sample_adaptive_offset_enabled_flag(sps.sample_adaptive_offset_enabled_flag.into());
+    flags.set_pcm_enabled_flag(sps.pcm_enabled_flag.into());
+    flags.set_pcm_loop_filter_disabled_flag(sps.pcm_loop_filter_disabled_flag.into());
+    flags.set_long_term_ref_pics_present_flag(sps.long_term_ref_pics_present_flag.into());
+    flags.set_sps_temporal_mvp_enabled_flag(sps.sps_temporal_mvp_enabled_flag.into());
+    flags.set_strong_intra_smoothing_enabled_flag(sps.strong_intra_smoothing_enabled_flag.into());
+
+    let [left, right, top, bottom] = sps.conf_win_offsets.unwrap_or_default();
+    native::StdVideoH265SequenceParameterSet {
+        flags,
+        chroma_format_idc: sps.chroma_format_idc.into(),
+        pic_width_in_luma_samples: sps.pic_width_in_luma_samples,
+        pic_height_in_luma_samples: sps.pic_height_in_luma_samples,
+        sps_video_parameter_set_id: sps.sps_video_parameter_set_id,
+        sps_max_sub_layers_minus1: sps.sps_max_sub_layers_minus1,
+        sps_seq_parameter_set_id: sps.sps_seq_parameter_set_id,
+        bit_depth_luma_minus8: sps.bit_depth_luma_minus8,
+        bit_depth_chroma_minus8: sps.bit_depth_chroma_minus8,
+        log2_max_pic_order_cnt_lsb_minus4: sps.log2_max_pic_order_cnt_lsb_minus4,
+        log2_min_luma_coding_block_size_minus3: sps.log2_min_luma_coding_block_size_minus3,
+        log2_diff_max_min_luma_coding_block_size: sps.log2_diff_max_min_luma_coding_block_size,
+        log2_min_luma_transform_block_size_minus2: sps.log2_min_luma_transform_block_size_minus2,
+        log2_diff_max_min_luma_transform_block_size: sps
+            .log2_diff_max_min_luma_transform_block_size,
+        max_transform_hierarchy_depth_inter: sps.max_transform_hierarchy_depth_inter,
+        max_transform_hierarchy_depth_intra: sps.max_transform_hierarchy_depth_intra,
+        num_short_term_ref_pic_sets: parts.short_term_ref_pic_sets.len() as u8,
+        num_long_term_ref_pics_sps: sps.num_long_term_ref_pics_sps,
+        pcm_sample_bit_depth_luma_minus1: sps.pcm_sample_bit_depth_luma_minus1,
+        pcm_sample_bit_depth_chroma_minus1: sps.pcm_sample_bit_depth_chroma_minus1,
+        log2_min_pcm_luma_coding_block_size_minus3: sps.log2_min_pcm_luma_coding_block_size_minus3,
+        log2_diff_max_min_pcm_luma_coding_block_size: sps
+            .log2_diff_max_min_pcm_luma_coding_block_size,
+        reserved1: 0,
+        reserved2: 0,
+        palette_max_size: 0,
+        delta_palette_max_predictor_size: 0,
+        motion_vector_resolution_control_idc: 0,
+        sps_num_palette_predictor_initializers_minus1: 0,
+        conf_win_left_offset: left,
+        conf_win_right_offset: right,
+        conf_win_top_offset: top,
+        conf_win_bottom_offset: bottom,
+        pProfileTierLevel: &parts.profile_tier_level,
+        pDecPicBufMgr: &parts.dec_pic_buf_mgr,
+        pScalingLists: parts
+            .scaling_lists
+            .as_ref()
+            .map_or(ptr::null(), ptr::from_ref),
+        pShortTermRefPicSet: parts.short_term_ref_pic_sets.as_ptr(),
+        pLongTermRefPicsSps: &parts.long_term_ref_pics_sps,
+        pSequenceParameterSetVui: ptr::null(),
+        pPredictorPaletteEntries: ptr::null(),
+    }
+}
+
+fn h265_pps(
+    pps: &h265::PictureParameterSet,
+    sps_video_parameter_set_id: u8,
+    scaling_lists: Option<&native::StdVideoH265ScalingLists>,
+) -> native::StdVideoH265PictureParameterSet {
+    let mut flags: native::StdVideoH265PpsFlags = unsafe { mem::zeroed() };
+    flags.set_dependent_slice_segments_enabled_flag(
+        pps.dependent_slice_segments_enabled_flag.into(),
+    );
+    flags.set_output_flag_present_flag(pps.output_flag_present_flag.into());
+    flags.set_sign_data_hiding_enabled_flag(pps.sign_data_hiding_enabled_flag.into());
+    flags.set_cabac_init_present_flag(pps.cabac_init_present_flag.into());
+    flags.set_constrained_intra_pred_flag(pps.constrained_intra_pred_flag.into());
+    flags.set_transform_skip_enabled_flag(pps.transform_skip_enabled_flag.into());
+    flags.set_cu_qp_delta_enabled_flag(pps.cu_qp_delta_enabled_flag.into());
+    flags.set_pps_slice_chroma_qp_offsets_present_flag(
+        pps.pps_slice_chroma_qp_offsets_present_flag.into(),
+    );
+    flags.set_weighted_pred_flag(pps.weighted_pred_flag.into());
+    flags.set_weighted_bipred_flag(pps.weighted_bipred_flag.into());
+    flags.set_transquant_bypass_enabled_flag(pps.transquant_bypass_enabled_flag.into());
+    flags.set_tiles_enabled_flag(pps.tiles_enabled_flag.into());
+    flags.set_entropy_coding_sync_enabled_flag(pps.entropy_coding_sync_enabled_flag.into());
+    flags.set_uniform_spacing_flag(pps.uniform_spacing_flag.into());
+    flags.set_loop_filter_across_tiles_enabled_flag(
+        pps.loop_filter_across_tiles_enabled_flag.into(),
+    );
+    flags.set_pps_loop_filter_across_slices_enabled_flag(
+        pps.pps_loop_filter_across_slices_enabled_flag.into(),
+    );
+    flags.set_deblocking_filter_control_present_flag(
+        pps.deblocking_filter_control_present_flag.into(),
+    );
+    flags.set_deblocking_filter_override_enabled_flag(
+        pps.deblocking_filter_override_enabled_flag.into(),
+    );
+    flags.set_pps_deblocking_filter_disabled_flag(pps.pps_deblocking_filter_disabled_flag.into());
+    flags.set_pps_scaling_list_data_present_flag(scaling_lists.is_some().into());
+    flags.set_lists_modification_present_flag(pps.lists_modification_present_flag.into());
+    flags.set_slice_segment_header_extension_present_flag(
+        pps.slice_segment_header_extension_present_flag.into(),
+    );
+
+    let mut std_pps: native::StdVideoH265PictureParameterSet = unsafe { mem::zeroed() };
+    std_pps.flags = flags;
+    std_pps.pps_pic_parameter_set_id = pps.pps_pic_parameter_set_id;
+    std_pps.pps_seq_parameter_set_id = pps.pps_seq_parameter_set_id;
+    std_pps.sps_video_parameter_set_id = sps_video_parameter_set_id;
+    std_pps.num_extra_slice_header_bits = pps.num_extra_slice_header_bits;
+    std_pps.num_ref_idx_l0_default_active_minus1 = pps.num_ref_idx_l0_default_active_minus1;
+    std_pps.num_ref_idx_l1_default_active_minus1 = pps.num_ref_idx_l1_default_active_minus1;
+    std_pps.init_qp_minus26 = pps.init_qp_minus26;
+    std_pps.diff_cu_qp_delta_depth = pps.diff_cu_qp_delta_depth;
+    std_pps.pps_cb_qp_offset = pps.pps_cb_qp_offset;
+    std_pps.pps_cr_qp_offset = pps.pps_cr_qp_offset;
+    std_pps.pps_beta_offset_div2 = pps.pps_beta_offset_div2;
+    std_pps.pps_tc_offset_div2 = pps.pps_tc_offset_div2;
+    std_pps.log2_parallel_merge_level_minus2 = pps.log2_parallel_merge_level_minus2;
+    std_pps.num_tile_columns_minus1 = pps.num_tile_columns_minus1;
+    std_pps.num_tile_rows_minus1 = pps.num_tile_rows_minus1;
+    std_pps.column_width_minus1 = pps.column_width_minus1;
+    std_pps.row_height_minus1 = pps.row_height_minus1;
+    std_pps.pScalingLists = scaling_lists.map_or(ptr::null(), ptr::from_ref);
+    std_pps
+}
+
+fn h265_picture_info(
+    picture: &h265::PictureInfo<'_>,
+    reference_slots: &[u32],
+) -> native::StdVideoDecodeH265PictureInfo {
+    let mut flags: native::StdVideoDecodeH265PictureInfoFlags = unsafe { mem::zeroed() };
+    flags.set_IrapPicFlag(picture.irap_pic_flag.into());
+    flags.set_IdrPicFlag(picture.idr_pic_flag.into());
+    flags.set_IsReference(picture.is_reference.into());
+    flags.set_short_term_ref_pic_set_sps_flag(picture.short_term_ref_pic_set_sps_flag.into());
+
+    // The reference picture sets hold the slots of the references, and 0xff past their end.
+    let slots = |indices: &[u8]| {
+        let mut slots = [0xff; 8];
+        for (slot, &index) in slots.iter_mut().zip(indices) {
+            *slot = reference_slots[usize::from(index)] as u8;
+        }
+        slots
+    };
+    native::StdVideoDecodeH265PictureInfo {
+        flags,
+        sps_video_parameter_set_id: picture.vps.vps_video_parameter_set_id,
+        pps_seq_parameter_set_id: picture.sps.sps_seq_parameter_set_id,
+        pps_pic_parameter_set_id: picture.pps.pps_pic_parameter_set_id,
+        NumDeltaPocsOfRefRpsIdx: picture.num_delta_pocs_of_ref_rps_idx,
+        PicOrderCntVal: picture.pic_order_cnt_val,
+        NumBitsForSTRefPicSetInSlice: picture.num_bits_for_st_ref_pic_set_in_slice,
+        reserved: 0,
+        RefPicSetStCurrBefore: slots(picture.ref_pic_set_st_curr_before),
+        RefPicSetStCurrAfter: slots(picture.ref_pic_set_st_curr_after),
+        RefPicSetLtCurr: slots(picture.ref_pic_set_lt_curr),
+    }
+}
+
+fn h265_reference_info(info: &h265::ReferenceInfo) -> native::StdVideoDecodeH265ReferenceInfo {
+    let mut flags: native::StdVideoDecodeH265ReferenceInfoFlags = unsafe { mem::zeroed() };
+    flags.set_used_for_long_term_reference(info.used_for_long_term_reference.into());
+
+    native::StdVideoDecodeH265ReferenceInfo {
+        flags,
+        PicOrderCntVal: info.pic_order_cnt_val,
+    }
+}
+
+fn av1_color_config(config: &av1::ColorConfig) -> native::StdVideoAV1ColorConfig {
+    let mut flags: native::StdVideoAV1ColorConfigFlags = unsafe { mem::zeroed() };
+    flags.set_mono_chrome(config.mono_chrome.into());
+    flags.set_color_range(config.color_range.into());
+    flags.set_separate_uv_delta_q(config.separate_uv_delta_q.into());
+    flags.set_color_description_present_flag(config.color_description_present_flag.into());
+
+    native::StdVideoAV1ColorConfig {
+        flags,
+        BitDepth: config.bit_depth,
+        subsampling_x: config.subsampling_x.into(),
+        subsampling_y: config.subsampling_y.into(),
+        reserved1: 0,
+        color_primaries: config.color_primaries.into(),
+        transfer_characteristics: config.transfer_characteristics.into(),
+        matrix_coefficients: config.matrix_coefficients.into(),
+        chroma_sample_position: config.chroma_sample_position.into(),
+    }
+}
+
+fn av1_timing_info(info: &av1::TimingInfo) -> native::StdVideoAV1TimingInfo {
+    let mut flags: native::StdVideoAV1TimingInfoFlags = unsafe { mem::zeroed() };
+    flags.set_equal_picture_interval(info.equal_picture_interval.into());
+
+    native::StdVideoAV1TimingInfo {
+        flags,
+        num_units_in_display_tick: info.num_units_in_display_tick,
+        time_scale: info.time_scale,
+        num_ticks_per_picture_minus_1: info.num_ticks_per_picture_minus_1,
+    }
+}
+
+fn av1_sequence_header(
+    header: &av1::SequenceHeader,
+    color_config: &native::StdVideoAV1ColorConfig,
+    timing_info: Option<&native::StdVideoAV1TimingInfo>,
+) -> native::StdVideoAV1SequenceHeader {
+    let mut flags: native::StdVideoAV1SequenceHeaderFlags = unsafe { mem::zeroed() };
+    flags.set_still_picture(header.still_picture.into());
+    flags.set_reduced_still_picture_header(header.reduced_still_picture_header.into());
+    flags.set_use_128x128_superblock(header.use_128x128_superblock.into());
+    flags.set_enable_filter_intra(header.enable_filter_intra.into());
+    flags.set_enable_intra_edge_filter(header.enable_intra_edge_filter.into());
+    flags.set_enable_interintra_compound(header.enable_interintra_compound.into());
+    flags.set_enable_masked_compound(header.enable_masked_compound.into());
+    flags.set_enable_warped_motion(header.enable_warped_motion.into());
+    flags.set_enable_dual_filter(header.enable_dual_filter.into());
+    flags.set_enable_order_hint(header.enable_order_hint.into());
+    flags.set_enable_jnt_comp(header.enable_jnt_comp.into());
+    flags.set_enable_ref_frame_mvs(header.enable_ref_frame_mvs.into());
+    flags.set_frame_id_numbers_present_flag(header.frame_id_numbers_present_flag.into());
+    flags.set_enable_superres(header.enable_superres.into());
+    flags.set_enable_cdef(header.enable_cdef.into());
+    flags.set_enable_restoration(header.enable_restoration.into());
+    flags.set_film_grain_params_present(header.film_grain_params_present.into());
+    flags.set_timing_info_present_flag(timing_info.is_some().into());
+    flags.set_initial_display_delay_present_flag(header.initial_display_delay_present_flag.into());
+
+    native::StdVideoAV1SequenceHeader {
+        flags,
+        seq_profile: header.seq_profile.into(),
+        frame_width_bits_minus_1: header.frame_width_bits_minus_1,
+        frame_height_bits_minus_1: header.frame_height_bits_minus_1,
+        max_frame_width_minus_1: header.max_frame_width_minus_1,
+        max_frame_height_minus_1: header.max_frame_height_minus_1,
+        delta_frame_id_length_minus_2: header.delta_frame_id_length_minus_2,
+        additional_frame_id_length_minus_1: header.additional_frame_id_length_minus_1,
+        order_hint_bits_minus_1: header.order_hint_bits_minus_1,
+        seq_force_integer_mv: header.seq_force_integer_mv,
+        seq_force_screen_content_tools: header.seq_force_screen_content_tools,
+        reserved1: [0; 5],
+        pColorConfig: color_config,
+        pTimingInfo: timing_info.map_or(ptr::null(), ptr::from_ref),
+    }
+}
+
+fn av1_tile_info(info: &av1::TileInfo<'_>) -> native::StdVideoAV1TileInfo {
+    let mut flags: native::StdVideoAV1TileInfoFlags = unsafe { mem::zeroed() };
+    flags.set_uniform_tile_spacing_flag(info.uniform_tile_spacing_flag.into());
+
+    native::StdVideoAV1TileInfo {
+        flags,
+        TileCols: info.tile_cols,
+        TileRows: info.tile_rows,
+        context_update_tile_id: info.context_update_tile_id,
+        tile_size_bytes_minus_1: info.tile_size_bytes_minus_1,
+        reserved1: [0; 7],
+        pMiColStarts: info.mi_col_starts.as_ptr(),
+        pMiRowStarts: info.mi_row_starts.as_ptr(),
+        pWidthInSbsMinus1: info.width_in_sbs_minus_1.as_ptr(),
+        pHeightInSbsMinus1: info.height_in_sbs_minus_1.as_ptr(),
+    }
+}
+
+fn av1_quantization(quantization: &av1::Quantization) -> native::StdVideoAV1Quantization {
+    let mut flags: native::StdVideoAV1QuantizationFlags = unsafe { mem::zeroed() };
+    flags.set_using_qmatrix(quantization.using_qmatrix.into());
+    flags.set_diff_uv_delta(quantization.diff_uv_delta.into());
+
+    native::StdVideoAV1Quantization {
+        flags,
+        base_q_idx: quantization.base_q_idx,
+        DeltaQYDc: quantization.delta_q_y_dc,
+        DeltaQUDc: quantization.delta_q_u_dc,
+        DeltaQUAc: quantization.delta_q_u_ac,
+        DeltaQVDc: quantization.delta_q_v_dc,
+        DeltaQVAc: quantization.delta_q_v_ac,
+        qm_y: quantization.qm_y,
+        qm_u: quantization.qm_u,
+        qm_v: quantization.qm_v,
+    }
+}
+
+fn av1_loop_filter(loop_filter: &av1::LoopFilter) -> native::StdVideoAV1LoopFilter {
+    let mut flags: native::StdVideoAV1LoopFilterFlags = unsafe { mem::zeroed() };
+    flags.set_loop_filter_delta_enabled(loop_filter.loop_filter_delta_enabled.into());
+    flags.set_loop_filter_delta_update(loop_filter.loop_filter_delta_update.into());
+
+    native::StdVideoAV1LoopFilter {
+        flags,
+        loop_filter_level: loop_filter.loop_filter_level,
+        loop_filter_sharpness: loop_filter.loop_filter_sharpness,
+        update_ref_delta: loop_filter.update_ref_delta,
+        loop_filter_ref_deltas: loop_filter.loop_filter_ref_deltas,
+        update_mode_delta: loop_filter.update_mode_delta,
+        loop_filter_mode_deltas: loop_filter.loop_filter_mode_deltas,
+    }
+}
+
+fn av1_cdef(cdef: &av1::Cdef) -> native::StdVideoAV1CDEF {
+    native::StdVideoAV1CDEF {
+        cdef_damping_minus_3: cdef.cdef_damping_minus_3,
+        cdef_bits: cdef.cdef_bits,
+        cdef_y_pri_strength: cdef.cdef_y_pri_strength,
+        cdef_y_sec_strength: cdef.cdef_y_sec_strength,
+        cdef_uv_pri_strength: cdef.cdef_uv_pri_strength,
+        cdef_uv_sec_strength: cdef.cdef_uv_sec_strength,
+    }
+}
+
+/// Converts the frame header of `picture`, leaving the pointers to its parts null.
+fn av1_picture_info(picture: &av1::PictureInfo<'_>) -> native::StdVideoDecodeAV1PictureInfo {
+    let restoration_types = picture.loop_restoration.frame_restoration_type;
+    let mut flags: native::StdVideoDecodeAV1PictureInfoFlags = unsafe { mem::zeroed() };
+    flags.set_error_resilient_mode(picture.error_resilient_mode.into());
+    flags.set_disable_cdf_update(picture.disable_cdf_update.into());
+    flags.set_use_superres(picture.use_superres.into());
+    flags.set_render_and_frame_size_different(picture.render_and_frame_size_different.into());
+    flags.set_allow_screen_content_tools(picture.allow_screen_content_tools.into());
+    flags.set_is_filter_switchable(picture.is_filter_switchable.into());
+    flags.set_force_integer_mv(picture.force_integer_mv.into());
+    flags.set_frame_size_override_flag(picture.frame_size_override_flag.into());
+    flags.set_buffer_removal_time_present_flag(picture.buffer_removal_time_present_flag.into());
+    flags.set_allow_intrabc(picture.allow_intrabc.into());
+    flags.set_frame_refs_short_signaling(picture.frame_refs_short_signaling.into());
+    flags.set_allow_high_precision_mv(picture.allow_high_precision_mv.into());
+    flags.set_is_motion_mode_switchable(picture.is_motion_mode_switchable.into());
+    flags.set_use_ref_frame_mvs(picture.use_ref_frame_mvs.into());
+    flags.set_disable_frame_end_update_cdf(picture.disable_frame_end_update_cdf.into());
+    flags.set_allow_warped_motion(picture.allow_warped_motion.into());
+    flags.set_reduced_tx_set(picture.reduced_tx_set.into());
+    flags.set_reference_select(picture.reference_select.into());
+    flags.set_skip_mode_present(picture.skip_mode_present.into());
+    flags.set_delta_q_present(picture.delta_q_present.into());
+    flags.set_delta_lf_present(picture.delta_lf_present.into());
+    flags.set_delta_lf_multi(picture.delta_lf_multi.into());
+    flags.set_segmentation_enabled(picture.segmentation.segmentation_enabled.into());
+    flags.set_segmentation_update_map(picture.segmentation.segmentation_update_map.into());
+    flags
+        .set_segmentation_temporal_update(picture.segmentation.segmentation_temporal_update.into());
+    flags.set_segmentation_update_data(picture.segmentation.segmentation_update_data.into());
+    flags.set_UsesLr(restoration_types.iter().any(|&ty| ty != 0).into());
+    flags.set_usesChromaLr(restoration_types[1..].iter().any(|&ty| ty != 0).into());
+
+    native::StdVideoDecodeAV1PictureInfo {
+        flags,
+        frame_type: picture.frame_type.into(),
+        current_frame_id: picture.current_frame_id,
+        OrderHint: picture.order_hint,
+        primary_ref_frame: picture.primary_ref_frame,
+        refresh_frame_flags: picture.refresh_frame_flags,
+        reserved1: 0,
+        interpolation_filter: picture.interpolation_filter.into(),
+        TxMode: picture.tx_mode.into(),
+        delta_q_res: picture.delta_q_res,
+        delta_lf_res: picture.delta_lf_res,
+        SkipModeFrame: picture.skip_mode_frame,
+        coded_denom: picture.coded_denom,
+        reserved2: [0; 3],
+        OrderHints: picture.order_hints,
+        expectedFrameId: picture.expected_frame_id,
+        pTileInfo: ptr::null(),
+        pQuantization: ptr::null(),
+        pSegmentation: ptr::null(),
+        pLoopFilter: ptr::null(),
+        pCDEF: ptr::null(),
+        pLoopRestoration: ptr::null(),
+        pGlobalMotion: ptr::null(),
+        pFilmGrain: ptr::null(),
+    }
+}
+
+fn av1_reference_info(info: &av1::ReferenceInfo) -> native::StdVideoDecodeAV1ReferenceInfo {
+    let mut flags: native::StdVideoDecodeAV1ReferenceInfoFlags = unsafe { mem::zeroed() };
+    flags.set_disable_frame_end_update_cdf(info.disable_frame_end_update_cdf.into());
+    flags.set_segmentation_enabled(info.segmentation_enabled.into());
+
+    native::StdVideoDecodeAV1ReferenceInfo {
+        flags,
+        frame_type: info.frame_type,
+        RefFrameSignBias: info.ref_frame_sign_bias,
+        OrderHint: info.order_hint,
+        SavedOrderHints: info.saved_order_hints,
+    }
+}
@@ -0,0 +1,170 @@
+use std::io;
+
+use hashbrown::HashMap;
+use serde::Deserialize;
+use wgpu::{
+    DownlevelFlags, Features, Limits, TextureFormat, TextureFormatFeatureFlags, TextureUsages,
+};
+
+use crate::{
+    report::{AdapterReport, GpuReport},
+    texture,
+};
+
+/// Requirements that the adapters of a report are checked against.
+///
+/// Everything is optional, and uses the same representation as the JSON report:
+///
+/// ```json
+/// {
+///     "features": "TEXTURE_COMPRESSION_BC | SHADER_F16",
+///     "limits": { "maxTextureDimension2D": 16384 },
+///     "downlevel_flags": "COMPUTE_SHADERS",
+///     "formats": {
+///         "rgba16float": { "usages": "STORAGE_BINDING", "flags": "FILTERABLE" }
+///     }
+/// }
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Requirements {
+    #[serde(default)]
+    pub features: Features,
+    /// The limits that are required, by their name in the report.
+    ///
+    /// Maximum limits must be at least and minimum limits at most the given value.
+    #[serde(default)]
+    pub limits: serde_json::Map<String, serde_json::Value>,
+    #[serde(default = "DownlevelFlags::empty")]
+    pub downlevel_flags: DownlevelFlags,
+    #[serde(default)]
+    pub formats: HashMap<TextureFormat, FormatRequirements>,
+}
+
+/// Usages and flags that a texture format must support.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatRequirements {
+    #[serde(default = "TextureUsages::empty")]
+    pub usages: TextureUsages,
+    #[serde(default = "TextureFormatFeatureFlags::empty")]
+    pub flags: TextureFormatFeatureFlags,
+}
+
+impl Requirements {
+    pub fn from_json(file: &str) -> anyhow::Result<Self> {
+        let requirements: Self = serde_json::from_str(file)?;
+        let known_limits = serde_json::to_value(Limits::default())?;
+        for name in requirements.limits.keys() {
+            if known_limits.get(name).is_none() {
+                anyhow::bail!("Unknown limit \"{name}\"");
+            }
+        }
+        // Surface limits with invalid values now rather than for every adapter.
+        requirements.required_limits(&Limits::default())?;
+        Ok(requirements)
+    }
+
+    /// Returns `adapter_limits` with the required limits applied on top.
+    fn required_limits(&self, adapter_limits: &Limits) -> serde_json::Result<Limits> {
+        let mut limits = serde_json::to_value(adapter_limits)?;
+        let object = limits.as_object_mut().unwrap();
+        for (name, value) in &self.limits {
+            object.insert(name.clone(), value.clone());
+        }
+        serde_json::from_value(limits)
+    }
+
+    /// Returns the reasons the adapter doesn't satisfy the requirements, which are empty if it does.
+    pub fn unsatisfied(&self, adapter: &AdapterReport) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        for (name, _) in self.features.difference(adapter.features).iter_names() {
+            reasons.push(format!("missing feature {name}"));
+        }
+
+        // Limits the requirements don't mention are the adapter's own, which always pass.
+        let required_limits = self.required_limits(&adapter.limits).unwrap();
+        required_limits.check_limits_with_fail_fn(
+            &adapter.limits,
+            false,
+            |name, requested, allowed| {
+                reasons.push(format!(
+                    "limit {}: requires {requested}, adapter allows {allowed}",
+                    camel_case(name)
+                ));
+            },
+        );
+
+        let missing_flags = self
+            .downlevel_flags
+            .difference(adapter.downlevel_caps.flags);
+        for (name, _) in missing_flags.iter_names() {
+            reasons.push(format!("missing downlevel flag {name}"));
+        }
+
+        for format in texture::TEXTURE_FORMAT_LIST {
+            let Some(required) = self.formats.get(&format) else {
+                continue;
+            };
+            let format_name = texture::texture_format_json_name(format);
+            let Some(features) = adapter.texture_format_features.get(&format) else {
+                reasons.push(format!("{format_name}: not reported"));
+                continue;
+            };
+            for (name, _) in required
+                .usages
+                .difference(features.allowed_usages)
+                .iter_names()
+            {
+                reasons.push(format!("{format_name}: missing usage {name}"));
+            }
+            for (name, _) in required.flags.difference(features.flags).iter_names() {
+                reasons.push(format!("{format_name}: missing flag {name}"));
+            }
+        }
+
+        reasons
+    }
+}
+
+/// Converts the name of a limit field to its name in the report.
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut result = String::from(words.next().unwrap_or_default());
+    for word in words {
+        let mut chars = word.chars();
+        result.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        result.push_str(chars.as_str());
+    }
+    result
+        .replace("1d", "1D")
+        .replace("2d", "2D")
+        .replace("3d", "3D")
+}
+
+/// Prints whether each adapter satisfies the requirements, returning the number that do.
+pub fn print_check(
+    output: &mut impl io::Write,
+    report: &GpuReport,
+    requirements: &Requirements,
+) -> io::Result<usize> {
+    let mut satisfied = 0;
+    for (idx, adapter) in report.devices.iter().enumerate() {
+        let name = format!(
+            "Adapter {idx}: {} ({:?})",
+            adapter.info.name, adapter.info.backend
+        );
+        let reasons = requirements.unsatisfied(adapter);
+        if reasons.is_empty() {
+            satisfied += 1;
+            writeln!(output, "{name}: satisfies the requirements")?;
+        } else {
+            writeln!(output, "{name}: does not satisfy the requirements")?;
+            for reason in reasons {
+                writeln!(output, "\t{reason}")?;
+            }
+        }
+    }
+    Ok(satisfied)
+}
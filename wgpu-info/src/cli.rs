@@ -1,11 +1,27 @@
-use std::{io, process::exit};
+use std::{
+    io::{self, Write},
+    process::exit,
+};
 
 use anyhow::Context;
 
-use crate::human::PrintingVerbosity;
+use crate::{
+    human::PrintingVerbosity,
+    report::{AdapterReport, GpuReport},
+};
 
 const HELP: &str = "\
 Usage: wgpu-info [--input <PATH>] [--output <PATH>] [-q/-v/-vv/--json]
+       wgpu-info diff <LEFT> [<RIGHT>] [--left-adapter <INDEX>] [--right-adapter <INDEX>] [--output <PATH>]
+       wgpu-info check <REQUIREMENTS> [--input <PATH>] [--output <PATH>]
+
+Commands:
+  [default]           Print information about the adapters.
+  diff                Print the differences in features, limits, downlevel properties and
+                      texture format capabilities between two adapters of the json reports
+                      LEFT and RIGHT. If RIGHT isn't given, both adapters are from LEFT.
+  check               Print which adapters satisfy the json requirements in REQUIREMENTS, and why
+                      the others don't. Exits with an error if none of them do.
 
 Information Density:
   These settings have no effect on the JSON output. The highest verbosity
@@ -21,6 +37,10 @@ Options:
   -i, --input <PATH>  Read a json report to make it human readable. (\"-\" reads from stdin)
   -o, --output <PATH> Destination to write output to. (\"-\" writes to stdout)
   -j, --json          Output JSON information instead of human-readable text.
+
+Diff Options:
+  --left-adapter <INDEX>   Index of the adapter in LEFT to compare. [default: 0]
+  --right-adapter <INDEX>  Index of the adapter in RIGHT to compare. [default: 0, or 1 without RIGHT]
 ";
 
 fn exit_with_help() {
@@ -38,8 +58,19 @@ pub fn main() -> anyhow::Result<()> {
     }
 
     // Argument parsing
-    let input_path: Option<String> = args.opt_value_from_str(["-i", "--input"]).unwrap();
+    let command: Option<String> = args.subcommand().unwrap();
     let output_path: Option<String> = args.opt_value_from_str(["-o", "--output"]).unwrap();
+    match command.as_deref() {
+        None => {}
+        Some("diff") => return diff(args, output_path.as_deref()),
+        Some("check") => return check(args, output_path.as_deref()),
+        Some(command) => {
+            eprint!("Unknown command: \"{command}\"\n\n");
+            exit_with_help();
+        }
+    }
+
+    let input_path: Option<String> = args.opt_value_from_str(["-i", "--input"]).unwrap();
     let json = args.contains(["-j", "--json"]);
 
     let verbosity = if args.contains("-vv") {
@@ -59,6 +90,102 @@ pub fn main() -> anyhow::Result<()> {
         );
     }
 
+    finish_args(args);
+
+    env_logger::init();
+
+    let report = read_report(input_path.as_deref())?;
+
+    write_output(output_path.as_deref(), |output| {
+        if json {
+            report.into_json(output)?;
+        } else {
+            crate::human::print_adapters(output, &report, verbosity)?;
+        }
+        Ok(())
+    })
+}
+
+/// Compares two adapters of one or two reports.
+fn diff(mut args: pico_args::Arguments, output_path: Option<&str>) -> anyhow::Result<()> {
+    let left_adapter: Option<usize> = args.opt_value_from_str("--left-adapter").unwrap();
+    let right_adapter: Option<usize> = args.opt_value_from_str("--right-adapter").unwrap();
+    let Ok(left_path) = args.free_from_str::<String>() else {
+        eprint!("Missing report to diff\n\n");
+        exit_with_help();
+        return Ok(());
+    };
+    let right_path: Option<String> = args.opt_free_from_str().unwrap();
+    finish_args(args);
+
+    env_logger::init();
+
+    let left_report = read_report(Some(&left_path))?;
+    let right_report = match &right_path {
+        Some(path) => Some(read_report(Some(path))?),
+        None => None,
+    };
+    let left_adapter = left_adapter.unwrap_or(0);
+    // Without a second report, compare the first two adapters by default.
+    let right_adapter = right_adapter.unwrap_or(usize::from(right_report.is_none()));
+    let right_path = right_path.as_deref().unwrap_or(&left_path);
+    let right_report = right_report.as_ref().unwrap_or(&left_report);
+
+    let left = select_adapter(&left_report, &left_path, left_adapter)?;
+    let right = select_adapter(right_report, right_path, right_adapter)?;
+
+    write_output(output_path, |output| {
+        crate::diff::print_diff(
+            output,
+            &format!("{left_path} adapter {left_adapter}"),
+            left,
+            &format!("{right_path} adapter {right_adapter}"),
+            right,
+        )?;
+        Ok(())
+    })
+}
+
+fn select_adapter<'a>(
+    report: &'a GpuReport,
+    path: &str,
+    index: usize,
+) -> anyhow::Result<&'a AdapterReport> {
+    report
+        .devices
+        .get(index)
+        .with_context(|| format!("Report \"{path}\" has no adapter {index}"))
+}
+
+/// Checks which adapters satisfy a requirements file.
+fn check(mut args: pico_args::Arguments, output_path: Option<&str>) -> anyhow::Result<()> {
+    let input_path: Option<String> = args.opt_value_from_str(["-i", "--input"]).unwrap();
+    let Ok(requirements_path) = args.free_from_str::<String>() else {
+        eprint!("Missing requirements to check\n\n");
+        exit_with_help();
+        return Ok(());
+    };
+    finish_args(args);
+
+    env_logger::init();
+
+    let requirements = std::fs::read_to_string(&requirements_path)
+        .with_context(|| format!("Could not read from file \"{requirements_path}\""))?;
+    let requirements = crate::check::Requirements::from_json(&requirements)
+        .context("Could not parse requirements")?;
+    let report = read_report(input_path.as_deref())?;
+
+    let satisfied = write_output(output_path, |output| {
+        Ok(crate::check::print_check(output, &report, &requirements)?)
+    })?;
+    if satisfied == 0 {
+        anyhow::bail!("No adapter satisfies the requirements");
+    }
+    Ok(())
+}
+
+/// Exits with the help message if any arguments weren't used.
+fn finish_args(args: pico_args::Arguments) {
     let remaining = args.finish();
     if !remaining.is_empty() {
         eprint!("Unknown argument(s): ");
@@ -68,11 +195,11 @@ pub fn main() -> anyhow::Result<()> {
         eprint!("\n\n");
         exit_with_help();
     }
+}
 
-    env_logger::init();
-
-    // Generate or load report
-    let report = match input_path.as_deref() {
+/// Reads the report at `path`, or generates it natively if there is no path.
+fn read_report(path: Option<&str>) -> anyhow::Result<GpuReport> {
+    match path {
         // Pull report from stdin or file
         Some(path) => {
             let json = if "-" == path {
@@ -81,16 +208,22 @@ pub fn main() -> anyhow::Result<()> {
                 std::fs::read_to_string(path)
                     .with_context(|| format!("Could not read from file \"{path}\""))?
             };
-            crate::report::GpuReport::from_json(&json).context("Could not parse JSON")?
+            GpuReport::from_json(&json).context("Could not parse JSON")
         }
         // Generate the report natively
-        None => crate::report::GpuReport::generate(),
-    };
+        None => Ok(GpuReport::generate()),
+    }
+}
 
+/// Calls `write` with the output at `output_path`, or stdout if there is no path.
+fn write_output<T>(
+    output_path: Option<&str>,
+    write: impl FnOnce(&mut io::BufWriter<&mut dyn io::Write>) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
     // Setup output writer
     let mut file_handle;
     let mut std_handle;
-    let output: &mut dyn io::Write = match output_path.as_deref() {
+    let output: &mut dyn io::Write = match output_path {
         None | Some("-") => {
             std_handle = io::stdout();
             &mut std_handle
@@ -103,16 +236,10 @@ pub fn main() -> anyhow::Result<()> {
     };
     let mut output = io::BufWriter::new(output);
 
-    let output_name = output_path.as_deref().unwrap_or("stdout");
+    let output_name = output_path.unwrap_or("stdout");
 
-    if json {
-        report
-            .into_json(output)
-            .with_context(|| format!("Failed to write to output: {output_name}"))?;
-    } else {
-        crate::human::print_adapters(&mut output, &report, verbosity)
-            .with_context(|| format!("Failed to write to output: {output_name}"))?;
-    }
-
-    Ok(())
+    let result = write(&mut output);
+    result
+        .and_then(|value| Ok(output.flush().map(|()| value)?))
+        .with_context(|| format!("Failed to write to output: {output_name}"))
 }
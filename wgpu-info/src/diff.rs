@@ -0,0 +1,145 @@
+use std::{fmt::Debug, io};
+
+use bitflags::Flags;
+use wgpu::{TextureFormatFeatureFlags, TextureFormatFeatures, TextureUsages};
+
+use crate::{report::AdapterReport, texture};
+
+/// Lines of one section of a diff.
+///
+/// Lines only in the left adapter start with `-`, lines only in the right one with `+`,
+/// and values that changed between them with a space.
+#[derive(Default)]
+struct Section {
+    lines: Vec<String>,
+}
+
+impl Section {
+    fn changed(&mut self, name: &str, left: impl Debug, right: impl Debug) {
+        self.lines.push(format!("  {name}: {left:?} -> {right:?}"));
+    }
+
+    fn flags<F: Flags + Copy>(&mut self, prefix: &str, left: F, right: F) {
+        for (name, _) in left.difference(right).iter_names() {
+            self.lines.push(format!("- {prefix}{name}"));
+        }
+        for (name, _) in right.difference(left).iter_names() {
+            self.lines.push(format!("+ {prefix}{name}"));
+        }
+    }
+
+    fn print(&self, output: &mut impl io::Write, title: &str) -> io::Result<()> {
+        if self.lines.is_empty() {
+            return Ok(());
+        }
+        writeln!(output, "{title}:")?;
+        for line in &self.lines {
+            writeln!(output, "\t{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the differences between the capabilities of two adapters.
+///
+/// The adapters are described by `left_name` and `right_name` in the header.
+pub fn print_diff(
+    output: &mut impl io::Write,
+    left_name: &str,
+    left: &AdapterReport,
+    right_name: &str,
+    right: &AdapterReport,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "--- {left_name}: {} ({:?})",
+        left.info.name, left.info.backend
+    )?;
+    writeln!(
+        output,
+        "+++ {right_name}: {} ({:?})",
+        right.info.name, right.info.backend
+    )?;
+
+    let mut info = Section::default();
+    macro_rules! compare_info {
+        ($($field:ident: $name:literal),*) => {$(
+            if left.info.$field != right.info.$field {
+                info.changed($name, &left.info.$field, &right.info.$field);
+            }
+        )*};
+    }
+    compare_info!(
+        name: "Name",
+        vendor: "VendorID",
+        device: "DeviceID",
+        device_type: "Type",
+        driver: "Driver",
+        driver_info: "DriverInfo",
+        backend: "Backend"
+    );
+
+    let mut features = Section::default();
+    features.flags("", left.features, right.features);
+
+    // Limits are compared through the report's representation so that every one is covered.
+    let mut limits = Section::default();
+    let left_limits = serde_json::to_value(&left.limits).unwrap();
+    let right_limits = serde_json::to_value(&right.limits).unwrap();
+    for (name, left_value) in left_limits.as_object().unwrap() {
+        let right_value = &right_limits[name];
+        if left_value != right_value {
+            limits
+                .lines
+                .push(format!("  {name}: {left_value} -> {right_value}"));
+        }
+    }
+
+    let mut downlevel = Section::default();
+    downlevel.flags("", left.downlevel_caps.flags, right.downlevel_caps.flags);
+    if left.downlevel_caps.shader_model != right.downlevel_caps.shader_model {
+        downlevel.changed(
+            "Shader Model",
+            left.downlevel_caps.shader_model,
+            right.downlevel_caps.shader_model,
+        );
+    }
+
+    let mut formats = Section::default();
+    let unsupported = TextureFormatFeatures {
+        allowed_usages: TextureUsages::empty(),
+        flags: TextureFormatFeatureFlags::empty(),
+    };
+    for format in texture::TEXTURE_FORMAT_LIST {
+        let left = left
+            .texture_format_features
+            .get(&format)
+            .unwrap_or(&unsupported);
+        let right = right
+            .texture_format_features
+            .get(&format)
+            .unwrap_or(&unsupported);
+        let format_name = texture::texture_format_json_name(format);
+        formats.flags(
+            &format!("{format_name}: usage "),
+            left.allowed_usages,
+            right.allowed_usages,
+        );
+        formats.flags(&format!("{format_name}: flag "), left.flags, right.flags);
+    }
+
+    let sections = [
+        (info, "Info"),
+        (features, "Features"),
+        (limits, "Limits"),
+        (downlevel, "Downlevel Properties"),
+        (formats, "Texture Formats"),
+    ];
+    if sections.iter().all(|(section, _)| section.lines.is_empty()) {
+        writeln!(output, "No differences")?;
+    }
+    for (section, title) in &sections {
+        section.print(output, title)?;
+    }
+    Ok(())
+}
@@ -152,3 +152,11 @@ pub fn texture_format_name(format: wgpu::TextureFormat) -> String {
         }
     }
 }
+
+/// Returns the name of the format in JSON reports, such as `rgba8unorm`.
+pub fn texture_format_json_name(format: wgpu::TextureFormat) -> String {
+    match serde_json::to_value(format) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => format!("{format:?}"),
+    }
+}
@@ -135,7 +135,15 @@ pub struct HalCounters {
 /// `wgpu-core`'s part of [`InternalCounters`].
 #[derive(Clone, Default)]
 pub struct CoreCounters {
-    // TODO    #[cfg(features=)]
+    /// Number of buffers placed in a shared backend buffer, see
+    /// [`InstanceFlags::SUBALLOCATE_SMALL_BUFFERS`](crate::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS).
+    pub suballocated_buffers: InternalCounter,
+    /// Amount of memory used by sub-allocated buffers in shared backend buffers, in bytes.
+    pub suballocated_buffer_memory: InternalCounter,
+    /// Number of shared backend buffers holding sub-allocated buffers.
+    pub buffer_blocks: InternalCounter,
+    /// Size of all shared backend buffers holding sub-allocated buffers, in bytes.
+    pub buffer_block_memory: InternalCounter,
 }
 
 /// All internal counters, exposed for debugging purposes.
@@ -184,6 +192,52 @@ pub struct AllocatorReport {
     pub total_reserved_bytes: u64,
 }
 
+/// The budget and usage of a single memory heap, as part of a [`MemoryBudgetReport`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MemoryHeapBudget {
+    /// Whether this heap is local to the device (video memory), as opposed to
+    /// host memory visible to the device.
+    pub device_local: bool,
+    /// The amount of memory in bytes this process can use from the heap before
+    /// allocations are likely to fail or cause resources to be evicted.
+    ///
+    /// The budget is an estimate provided by the driver or the operating system,
+    /// and changes over time as other processes allocate and release memory.
+    pub budget: u64,
+    /// The amount of memory in bytes this process currently uses from the heap.
+    pub usage: u64,
+}
+
+impl MemoryHeapBudget {
+    /// Returns `true` if the usage of this heap is at or above `percent` of its budget.
+    #[must_use]
+    pub fn exceeds(&self, percent: u8) -> bool {
+        u128::from(self.usage) * 100 >= u128::from(self.budget) * u128::from(percent)
+    }
+}
+
+/// The current memory budget and usage of each memory heap of a device.
+///
+/// Obtain this from
+/// [`Device::get_memory_budget()`](../wgpu/struct.Device.html#method.get_memory_budget).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryBudgetReport {
+    /// The memory heaps of the device.
+    pub heaps: Vec<MemoryHeapBudget>,
+}
+
+impl fmt::Debug for MemoryHeapBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryHeapBudget")
+            .field("device_local", &self.device_local)
+            .field(
+                "usage",
+                &core::format_args!("{} / {}", FmtBytes(self.usage), FmtBytes(self.budget)),
+            )
+            .finish()
+    }
+}
+
 impl fmt::Debug for AllocationReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = if !self.name.is_empty() {
@@ -236,3 +290,24 @@ impl fmt::Display for FmtBytes {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_heap_budget_exceeds() {
+        let heap = |budget, usage| MemoryHeapBudget {
+            device_local: true,
+            budget,
+            usage,
+        };
+        // `budget / 100` would round down to 0 here.
+        assert!(!heap(99, 0).exceeds(50));
+        assert!(!heap(99, 49).exceeds(50));
+        assert!(heap(99, 50).exceeds(50));
+        assert!(heap(0, 0).exceeds(100));
+        assert!(!heap(u64::MAX, u64::MAX - 1).exceeds(100));
+        assert!(heap(u64::MAX, u64::MAX).exceeds(100));
+    }
+}
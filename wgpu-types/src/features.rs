@@ -1238,6 +1238,149 @@ bitflags_array! {
         ///
         /// [`Device::create_shader_module_passthrough`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_shader_module_passthrough
         const EXPERIMENTAL_PASSTHROUGH_SHADERS = 1 << 52;
+
+        /// Allows recording command buffers that can be submitted more than once.
+        ///
+        /// Allows using [`Device::create_reusable_command_encoder`]. The commands of a
+        /// reusable command buffer are validated once, when it is finished, and only
+        /// the resources it uses are checked again on each submission.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - OpenGL
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Device::create_reusable_command_encoder`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_reusable_command_encoder
+        const REUSABLE_COMMAND_BUFFERS = 1 << 53;
+
+        /// Allows textures to be created with [`TextureUsages::TRANSIENT`].
+        ///
+        /// Transient textures are only used as render pass attachments and are never
+        /// stored, which lets multisampled color targets and depth buffers live in tile
+        /// memory without backing allocations.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - Metal (Apple GPUs)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`TextureUsages::TRANSIENT`]: super::TextureUsages::TRANSIENT
+        const TRANSIENT_ATTACHMENTS = 1 << 54;
+
+        /// Allows fragment shaders to read the current value of color attachments.
+        ///
+        /// Fragment inputs declared with `@color(n)` in WGSL read the texel of color
+        /// attachment `n` at the current fragment, before this fragment's output is
+        /// written. The render pipeline must have a color target at every fetched
+        /// location whose format matches the scalar type of the input.
+        ///
+        /// On Vulkan, the attachments are read as input attachments. While this feature
+        /// is enabled, every render pass declares its attachments as input attachments in
+        /// the `GENERAL` layout, and a fragment shader only sees what earlier draw calls
+        /// wrote, not the output of other fragments of the same draw call. Multisampled
+        /// attachments can't be fetched, and surfaces must support input attachments.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with at least 10 input attachments per stage and one descriptor set more
+        ///   than [`Limits::max_bind_groups`])
+        /// - Metal (Apple GPUs)
+        /// - OpenGL (with `GL_EXT_shader_framebuffer_fetch`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Limits::max_bind_groups`]: super::Limits::max_bind_groups
+        const FRAMEBUFFER_FETCH = 1 << 55;
+
+        /// Allows the use of [`RenderPass::execute_indirect`] and
+        /// [`RenderPass::execute_indirect_count`].
+        ///
+        /// These execute a stream of draws generated on the GPU, where every draw can
+        /// switch vertex buffers, the index buffer and push constants before drawing.
+        /// The layout of each record in the stream is described by an
+        /// [`IndirectCommandLayout`].
+        ///
+        /// Supported platforms:
+        /// - DX12
+        ///
+        /// Metal indirect command buffers and `VK_EXT_device_generated_commands` are
+        /// not used, so Metal and Vulkan don't support this feature.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::execute_indirect`]: ../wgpu/struct.RenderPass.html#method.execute_indirect
+        /// [`RenderPass::execute_indirect_count`]: ../wgpu/struct.RenderPass.html#method.execute_indirect_count
+        /// [`IndirectCommandLayout`]: super::IndirectCommandLayout
+        const INDIRECT_COMMANDS = 1 << 56;
+
+        /// Allows decoding video with [`Device::create_video_decoder`] and
+        /// [`Queue::decode_video`].
+        ///
+        /// Pictures are decoded into [`TextureFormat::NV12`] or [`TextureFormat::P010`]
+        /// textures, whose planes can be sampled directly or through an `ExternalTexture`.
+        /// Which codecs are supported can be queried with
+        /// [`Device::video_decode_capabilities`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `VK_KHR_video_decode_queue` and the extension of each codec)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Device::create_video_decoder`]: ../wgpu/struct.Device.html#method.create_video_decoder
+        /// [`Device::video_decode_capabilities`]: ../wgpu/struct.Device.html#method.video_decode_capabilities
+        /// [`Queue::decode_video`]: ../wgpu/struct.Queue.html#method.decode_video
+        /// [`TextureFormat::NV12`]: super::TextureFormat::NV12
+        /// [`TextureFormat::P010`]: super::TextureFormat::P010
+        const VIDEO_DECODE = 1 << 57;
+
+        /// Allows creating buffers with [`BufferUsages::SPARSE`] and textures with
+        /// [`TextureUsages::SPARSE`], whose memory is bound one page at a time with
+        /// [`Queue::bind_sparse`].
+        ///
+        /// Shaders can check whether the texels they sample are resident with the
+        /// `textureSparseSample*` functions of WGSL and `textureSparseIsResident`.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `sparseResidencyImage2D`, `sparseResidencyBuffer` and the standard
+        ///   block shapes)
+        /// - DX12 (with tiled resources tier 2)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`BufferUsages::SPARSE`]: super::BufferUsages::SPARSE
+        /// [`TextureUsages::SPARSE`]: super::TextureUsages::SPARSE
+        /// [`Queue::bind_sparse`]: ../wgpu/struct.Queue.html#method.bind_sparse
+        const SPARSE_RESOURCES = 1 << 58;
+
+        /// Allows fragment shaders to read the current value of the depth and stencil
+        /// attachments.
+        ///
+        /// Fragment inputs declared with `@builtin(last_frag_depth)` (an `f32`) and
+        /// `@builtin(last_frag_stencil)` (a `u32`) in WGSL read the depth and stencil
+        /// values at the current fragment. The render pipeline's depth-stencil format must
+        /// have the aspect that is read. This works like [`Features::FRAMEBUFFER_FETCH`],
+        /// and has the same caveats on Vulkan.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with the same requirements as [`Features::FRAMEBUFFER_FETCH`])
+        /// - OpenGL ES (with `GL_ARM_shader_framebuffer_fetch_depth_stencil`)
+        ///
+        /// This is a native only feature.
+        const FRAMEBUFFER_FETCH_DEPTH_STENCIL = 1 << 59;
+
+        /// Allows render bundles to inherit bind groups from the render pass that
+        /// executes them, through
+        /// [`RenderBundleEncoderDescriptor::inherited_bind_group_layouts`].
+        ///
+        /// Supported platforms:
+        /// - All native backends, since bundles are replayed into the pass
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderBundleEncoderDescriptor::inherited_bind_group_layouts`]: ../wgpu/struct.RenderBundleEncoderDescriptor.html#structfield.inherited_bind_group_layouts
+        const RENDER_BUNDLE_INHERITANCE = 1 << 60;
     }
 
     /// Features that are not guaranteed to be supported.
@@ -137,13 +137,15 @@ bitflags::bitflags! {
         ///
         /// - When calling `dispatch_workgroups_indirect`, all 3 indirect arguments encoded in the buffer
         /// must be less than the `max_compute_workgroups_per_dimension` device limit.
-        /// - When calling `draw_indirect`/`draw_indexed_indirect`/`multi_draw_indirect`/`multi_draw_indexed_indirect`:
+        /// - When calling `draw_indirect`/`draw_indexed_indirect`/`multi_draw_indirect`/`multi_draw_indexed_indirect`/`multi_draw_indirect_count`/`multi_draw_indexed_indirect_count`:
         ///   - If `Features::INDIRECT_FIRST_INSTANCE` is not enabled on the device, the `first_instance` indirect argument must be 0.
         ///   - The `first_instance` & `instance_count` indirect arguments must form a range that fits within all bound vertex buffers with `step_mode` set to `Instance`.
-        /// - When calling `draw_indirect`/`multi_draw_indirect`:
+        /// - When calling `draw_indirect`/`multi_draw_indirect`/`multi_draw_indirect_count`:
         ///   - The `first_vertex` & `vertex_count` indirect arguments must form a range that fits within all bound vertex buffers with `step_mode` set to `Vertex`.
-        /// - When calling `draw_indexed_indirect`/`multi_draw_indexed_indirect`:
+        /// - When calling `draw_indexed_indirect`/`multi_draw_indexed_indirect`/`multi_draw_indexed_indirect_count`:
         ///   - The `first_index` & `index_count` indirect arguments must form a range that fits within the bound index buffer.
+        /// - When calling `multi_draw_indirect_count`/`multi_draw_indexed_indirect_count`:
+        ///   - The count read from the count buffer is clamped to `max_count`.
         ///
         /// __Behavior is undefined if this validation is disabled and the rules above are not satisfied.__
         ///
@@ -167,6 +169,51 @@ bitflags::bitflags! {
         ///
         /// [rqs]: ../wgpu/struct.CommandEncoder.html#method.resolve_query_set
         const AUTOMATIC_TIMESTAMP_NORMALIZATION = 1 << 6;
+
+        /// Place small buffers in larger, shared backend buffers instead of creating a backend
+        /// buffer for each of them.
+        ///
+        /// This applies to buffers of at most 16 KiB whose usages are limited to `VERTEX`,
+        /// `INDEX`, `UNIFORM` and `COPY_DST`. Creating many such buffers, as is common for
+        /// per-object uniforms, then uses less memory and fewer backend allocations.
+        ///
+        /// Sub-allocated buffers can't be accessed with `Buffer::as_hal`. This has no effect on
+        /// the DX12 backend.
+        ///
+        /// When `Self::from_env()` is used takes value from `WGPU_SUBALLOCATE_SMALL_BUFFERS` environment variable.
+        const SUBALLOCATE_SMALL_BUFFERS = 1 << 7;
+
+        /// Report out-of-bounds accesses made by shaders as validation errors.
+        ///
+        /// Shaders are compiled to record their out-of-bounds array, vector and matrix
+        /// accesses in a buffer that is read back once each submission is done. The first
+        /// recorded access of a submission, with its entry point and its location in the
+        /// shader source, is then raised as a validation error the next time the device is
+        /// polled or its queue is submitted to. The accesses themselves are restricted to
+        /// the last element, whatever the [`ShaderRuntimeChecks`](crate::ShaderRuntimeChecks).
+        ///
+        /// The report takes the bind group following the last one allowed by the device's
+        /// `max_bind_groups` limit, and a storage buffer binding in each reporting stage.
+        /// Nothing is reported if the adapter's limits leave no room for them. Compute and
+        /// fragment shaders report their accesses if they can write to storage buffers,
+        /// vertex shaders only with `Features::VERTEX_WRITABLE_STORAGE`.
+        ///
+        /// This is slow, and meant for debugging.
+        ///
+        /// When `Self::from_env()` is used takes value from `WGPU_REPORT_SHADER_OUT_OF_BOUNDS` environment variable.
+        const REPORT_SHADER_OUT_OF_BOUNDS = 1 << 8;
+
+        /// Keep a report of the resource transitions inserted into each command buffer.
+        ///
+        /// While a command encoder is recorded, the barriers that wgpu-core inserts between
+        /// its commands are logged with the resource they apply to, its states before and
+        /// after, and the index of the command that caused them. The report of a finished
+        /// command buffer is returned by `CommandBuffer::transition_report`.
+        ///
+        /// This is meant for debugging, and makes recording slower.
+        ///
+        /// When `Self::from_env()` is used takes value from `WGPU_REPORT_TRANSITIONS` environment variable.
+        const REPORT_TRANSITIONS = 1 << 9;
     }
 }
 
@@ -224,6 +271,9 @@ impl InstanceFlags {
     /// - `WGPU_ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER`
     /// - `WGPU_GPU_BASED_VALIDATION`
     /// - `WGPU_VALIDATION_INDIRECT_CALL`
+    /// - `WGPU_SUBALLOCATE_SMALL_BUFFERS`
+    /// - `WGPU_REPORT_SHADER_OUT_OF_BOUNDS`
+    /// - `WGPU_REPORT_TRANSITIONS`
     #[must_use]
     pub fn with_env(mut self) -> Self {
         fn env(key: &str) -> Option<bool> {
@@ -252,6 +302,15 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_VALIDATION_INDIRECT_CALL") {
             self.set(Self::VALIDATION_INDIRECT_CALL, bit);
         }
+        if let Some(bit) = env("WGPU_SUBALLOCATE_SMALL_BUFFERS") {
+            self.set(Self::SUBALLOCATE_SMALL_BUFFERS, bit);
+        }
+        if let Some(bit) = env("WGPU_REPORT_SHADER_OUT_OF_BOUNDS") {
+            self.set(Self::REPORT_SHADER_OUT_OF_BOUNDS, bit);
+        }
+        if let Some(bit) = env("WGPU_REPORT_TRANSITIONS") {
+            self.set(Self::REPORT_TRANSITIONS, bit);
+        }
 
         self
     }
@@ -323,6 +382,8 @@ pub struct GlBackendOptions {
     pub gles_minor_version: Gles3MinorVersion,
     /// Behavior of OpenGL fences. Affects how `on_completed_work_done` and `device.poll` behave.
     pub fence_behavior: GlFenceBehavior,
+    /// Which EGL platform to create the display with.
+    pub platform: GlPlatform,
 }
 
 impl GlBackendOptions {
@@ -332,9 +393,11 @@ impl GlBackendOptions {
     #[must_use]
     pub fn from_env_or_default() -> Self {
         let gles_minor_version = Gles3MinorVersion::from_env().unwrap_or_default();
+        let platform = GlPlatform::from_env().unwrap_or_default();
         Self {
             gles_minor_version,
             fence_behavior: GlFenceBehavior::Normal,
+            platform,
         }
     }
 
@@ -345,9 +408,11 @@ impl GlBackendOptions {
     pub fn with_env(self) -> Self {
         let gles_minor_version = self.gles_minor_version.with_env();
         let short_circuit_fences = self.fence_behavior.with_env();
+        let platform = self.platform.with_env();
         Self {
             gles_minor_version,
             fence_behavior: short_circuit_fences,
+            platform,
         }
     }
 }
@@ -404,6 +469,13 @@ pub struct NoopBackendOptions {
     /// it must not be used when not expected. Therefore, it will not be used unless explicitly
     /// enabled.
     pub enable: bool,
+
+    /// The budget in bytes the noop backend reports for its single memory heap.
+    ///
+    /// The noop backend counts the memory of its buffers against this budget, which
+    /// allows testing code reacting to memory pressure. If `None`, a budget of 1 GiB
+    /// is reported.
+    pub memory_budget: Option<u64>,
 }
 
 impl NoopBackendOptions {
@@ -415,6 +487,7 @@ impl NoopBackendOptions {
     pub fn from_env_or_default() -> Self {
         Self {
             enable: Self::enable_from_env().unwrap_or(false),
+            memory_budget: None,
         }
     }
 
@@ -426,6 +499,7 @@ impl NoopBackendOptions {
     pub fn with_env(self) -> Self {
         Self {
             enable: Self::enable_from_env().unwrap_or(self.enable),
+            ..self
         }
     }
 
@@ -625,6 +699,58 @@ impl Gles3MinorVersion {
     }
 }
 
+/// Selects which EGL platform the OpenGL backend creates its display with.
+///
+/// The platforms other than `Automatic` don't use a window system, so they work on machines
+/// without X11 or Wayland, such as headless servers. Surfaces can't be created with them;
+/// render to textures instead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum GlPlatform {
+    /// Use the window system that is available, falling back to the surfaceless platform
+    /// and then to the default display.
+    #[default]
+    Automatic,
+
+    /// Use the `EGL_MESA_platform_surfaceless` platform, which exposes a single adapter.
+    Surfaceless,
+
+    /// Use the `EGL_EXT_platform_device` platform, which exposes an adapter for every device
+    /// listed by `EGL_EXT_device_enumeration`.
+    Device,
+}
+
+impl GlPlatform {
+    /// Choose which EGL platform to use from the environment variable `WGPU_GL_PLATFORM`.
+    ///
+    /// Possible values are `automatic`, `surfaceless` or `device`. Case insensitive.
+    ///
+    /// Use with `unwrap_or_default()` to get the default value if the environment variable is not set.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let value = crate::env::var("WGPU_GL_PLATFORM")
+            .as_deref()?
+            .to_lowercase();
+        match value.as_str() {
+            "automatic" => Some(Self::Automatic),
+            "surfaceless" => Some(Self::Surfaceless),
+            "device" => Some(Self::Device),
+            _ => None,
+        }
+    }
+
+    /// Takes the given platform, modifies it based on the `WGPU_GL_PLATFORM` environment variable, and returns the result.
+    ///
+    /// See `from_env` for more information.
+    #[must_use]
+    pub fn with_env(self) -> Self {
+        if let Some(platform) = Self::from_env() {
+            platform
+        } else {
+            self
+        }
+    }
+}
+
 /// Dictate the behavior of fences in OpenGL.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum GlFenceBehavior {
@@ -686,3 +812,56 @@ impl GlFenceBehavior {
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    /// The only test that sets `WGPU_GL_PLATFORM`, so other tests don't race with it.
+    #[test]
+    fn gl_platform_from_env() {
+        let from_env = |value: Option<&str>| {
+            match value {
+                Some(value) => std::env::set_var("WGPU_GL_PLATFORM", value),
+                None => std::env::remove_var("WGPU_GL_PLATFORM"),
+            }
+            (
+                GlPlatform::from_env(),
+                GlPlatform::Surfaceless.with_env(),
+                GlBackendOptions::from_env_or_default().platform,
+            )
+        };
+
+        assert_eq!(
+            from_env(Some("device")),
+            (
+                Some(GlPlatform::Device),
+                GlPlatform::Device,
+                GlPlatform::Device
+            )
+        );
+        assert_eq!(
+            from_env(Some("SurfaceLess")),
+            (
+                Some(GlPlatform::Surfaceless),
+                GlPlatform::Surfaceless,
+                GlPlatform::Surfaceless
+            )
+        );
+        assert_eq!(
+            from_env(Some("automatic")),
+            (
+                Some(GlPlatform::Automatic),
+                GlPlatform::Automatic,
+                GlPlatform::Automatic
+            )
+        );
+        // Unknown values and unset variables leave the platform as it is.
+        for value in [Some("wayland"), Some(""), None] {
+            assert_eq!(
+                from_env(value),
+                (None, GlPlatform::Surfaceless, GlPlatform::Automatic)
+            );
+        }
+    }
+}
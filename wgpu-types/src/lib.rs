@@ -41,12 +41,21 @@ pub mod error;
 mod features;
 pub mod instance;
 pub mod math;
+mod sparse;
+#[cfg(feature = "texel")]
+pub mod texel;
 mod transfers;
+pub mod video;
 
 pub use counters::*;
 pub use features::*;
 pub use instance::*;
+pub use sparse::*;
 pub use transfers::*;
+pub use video::{
+    VideoCodec, VideoDecodeCapabilities, VideoDecodeDescriptor, VideoDecoderDescriptor,
+    VideoPictureDescriptor, VideoPictureInfo, VideoReference, VideoReferenceInfo,
+};
 
 /// Integral type used for [`Buffer`] offsets and sizes.
 ///
@@ -522,6 +531,9 @@ macro_rules! with_limits {
         $macro_name!(max_blas_primitive_count, Ordering::Less);
         $macro_name!(max_blas_geometry_count, Ordering::Less);
         $macro_name!(max_tlas_instance_count, Ordering::Less);
+
+        $macro_name!(max_compute_queues, Ordering::Less);
+        $macro_name!(max_transfer_queues, Ordering::Less);
     };
 }
 
@@ -710,6 +722,21 @@ pub struct Limits {
     /// Requesting more than 0 during device creation only makes sense if [`Features::EXPERIMENTAL_RAY_QUERY`]
     /// is enabled.
     pub max_acceleration_structures_per_shader_stage: u32,
+
+    /// The maximum number of compute queues that can be created with
+    /// `Device::create_queue`, besides the queue returned with the device.
+    ///
+    /// Compute queues run compute passes, copies and clears alongside the work of the other
+    /// queues. This is 0 unless the adapter has queues dedicated to compute work, and the
+    /// queues are only created if the limit is requested at device creation.
+    pub max_compute_queues: u32,
+    /// The maximum number of transfer queues that can be created with
+    /// `Device::create_queue`, besides the queue returned with the device.
+    ///
+    /// Transfer queues only run copies and clears, usually on dedicated copy engines. This is
+    /// 0 unless the adapter has queues dedicated to transfers, and the queues are only created
+    /// if the limit is requested at device creation.
+    pub max_transfer_queues: u32,
 }
 
 impl Default for Limits {
@@ -770,6 +797,8 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_compute_queues: 0,
+    ///     max_transfer_queues: 0,
     /// });
     /// ```
     ///
@@ -824,6 +853,8 @@ impl Limits {
             max_blas_geometry_count: 0,
             max_tlas_instance_count: 0,
             max_acceleration_structures_per_shader_stage: 0,
+            max_compute_queues: 0,
+            max_transfer_queues: 0,
         }
     }
 
@@ -879,6 +910,8 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_compute_queues: 0,
+    ///     max_transfer_queues: 0,
     /// });
     /// ```
     #[must_use]
@@ -954,6 +987,8 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_compute_queues: 0,
+    ///     max_transfer_queues: 0,
     /// });
     /// ```
     #[must_use]
@@ -1323,6 +1358,11 @@ bitflags::bitflags! {
         /// Not Supported by:
         /// - GL ES / WebGL
         const NONBLOCKING_QUERY_RESOLVE = 1 << 22;
+
+        /// Supports copies from textures of compressed formats to buffers.
+        ///
+        /// GLES/WebGL don't support this.
+        const COMPRESSED_TEXTURE_TO_BUFFER_COPIES = 1 << 23;
     }
 }
 
@@ -1483,6 +1523,58 @@ impl<L> DeviceDescriptor<L> {
     }
 }
 
+/// The kind of work a [`Queue`](../wgpu/struct.Queue.html) can run.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QueueType {
+    /// Runs any work. This is the type of the queue returned with the device.
+    #[default]
+    Graphics,
+    /// Runs compute passes, copies and clears.
+    ///
+    /// There can be up to [`Limits::max_compute_queues`] compute queues.
+    Compute,
+    /// Runs copies and clears.
+    ///
+    /// There can be up to [`Limits::max_transfer_queues`] transfer queues.
+    Transfer,
+}
+
+impl QueueType {
+    /// Returns the maximum number of queues of this type that can be created besides the queue
+    /// returned with the device, given the `limits` of the device.
+    #[must_use]
+    pub fn max_count(self, limits: &Limits) -> u32 {
+        match self {
+            Self::Graphics => 0,
+            Self::Compute => limits.max_compute_queues,
+            Self::Transfer => limits.max_transfer_queues,
+        }
+    }
+}
+
+/// Describes a [`Queue`](../wgpu/struct.Queue.html) created besides the queue of a device.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueueDescriptor<L> {
+    /// Debug label for the queue.
+    pub label: L,
+    /// The kind of work the queue runs.
+    pub queue_type: QueueType,
+}
+
+impl<L> QueueDescriptor<L> {
+    /// Takes a closure and maps the label of the queue descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> QueueDescriptor<K> {
+        QueueDescriptor {
+            label: fun(&self.label),
+            queue_type: self.queue_type,
+        }
+    }
+}
+
 /// Controls API call tracing and specifies where the trace is written.
 ///
 /// **Note:** Tracing is currently unavailable.
@@ -1496,11 +1588,75 @@ pub enum Trace {
     #[default]
     Off,
 
-    /// Tracing enabled.
+    /// Tracing enabled, writing a human-readable RON trace into the given directory.
+    ///
+    /// Every data blob is written to a separate file next to the trace.
     #[cfg(feature = "trace")]
     // This must be owned rather than `&'a Path`, because if it were that, then the lifetime
     // parameter would be unused when the "trace" feature is disabled, which is prohibited.
     Directory(std::path::PathBuf),
+
+    /// Tracing enabled, writing a compact binary trace into the given directory.
+    ///
+    /// Actions and data blobs are streamed into a single file as they happen, and
+    /// identical data blobs are only stored once. This is better suited to long-running
+    /// applications than [`Trace::Directory`].
+    #[cfg(feature = "trace")]
+    Binary(std::path::PathBuf),
+
+    /// Tracing enabled, configured with [`TraceOptions`].
+    #[cfg(feature = "trace")]
+    Options(TraceOptions),
+}
+
+/// Configuration of a trace, used with [`Trace::Options`].
+#[cfg(feature = "trace")]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct TraceOptions {
+    /// Directory the trace is written into.
+    pub path: std::path::PathBuf,
+    /// Whether to write a compact binary trace, as with [`Trace::Binary`], rather than
+    /// a RON trace, as with [`Trace::Directory`].
+    pub binary: bool,
+    /// Whether to record the contents of buffers the application maps for reading, and of
+    /// the frames it presents, so that a replay of the trace can be checked against them.
+    ///
+    /// Presented frames are only recorded for surfaces the application configured with
+    /// [`TextureUsages::COPY_SRC`], since they have to be read back. Surface
+    /// configurations are never changed to include it.
+    ///
+    /// Outputs are also recorded, for any trace, when the `WGPU_TRACE_OUTPUTS`
+    /// environment variable is set to anything but `0`.
+    pub record_outputs: bool,
+}
+
+#[cfg(feature = "trace")]
+impl TraceOptions {
+    /// Returns options for a RON trace in the directory `path`, without outputs.
+    #[must_use]
+    pub fn new(path: std::path::PathBuf) -> Self {
+        Self {
+            path,
+            binary: false,
+            record_outputs: false,
+        }
+    }
+
+    /// Sets [`Self::binary`].
+    #[must_use]
+    pub fn binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// Sets [`Self::record_outputs`].
+    #[must_use]
+    pub fn record_outputs(mut self, record_outputs: bool) -> Self {
+        self.record_outputs = record_outputs;
+        self
+    }
 }
 
 bitflags::bitflags! {
@@ -5288,6 +5444,13 @@ bitflags::bitflags! {
         const BLAS_INPUT = 1 << 10;
         /// Allows a buffer to be used as input for a top level acceleration structure build
         const TLAS_INPUT = 1 << 11;
+        /// Allows a buffer to have its memory bound one page at a time with
+        /// [`Queue::bind_sparse`]. Requires [`Features::SPARSE_RESOURCES`].
+        ///
+        /// Can't be combined with [`BufferUsages::MAP_READ`] or [`BufferUsages::MAP_WRITE`].
+        ///
+        /// [`Queue::bind_sparse`]: ../wgpu/struct.Queue.html#method.bind_sparse
+        const SPARSE = 1 << 12;
     }
 }
 
@@ -5584,6 +5747,20 @@ bitflags::bitflags! {
         //
         /// Allows a texture to be used with image atomics. Requires [`Features::TEXTURE_ATOMIC`].
         const STORAGE_ATOMIC = 1 << 16;
+        /// Allows a texture to only ever be used as an attachment of a single render pass, so that
+        /// its contents never need to be backed by memory. Requires [`Features::TRANSIENT_ATTACHMENTS`].
+        ///
+        /// Must be combined with [`TextureUsages::RENDER_ATTACHMENT`] and no other usage. Every
+        /// render pass using the texture must clear it and use [`StoreOp::Discard`].
+        const TRANSIENT = 1 << 17;
+        /// Allows a texture to have its memory bound one page at a time with
+        /// [`Queue::bind_sparse`]. Requires [`Features::SPARSE_RESOURCES`].
+        ///
+        /// The texture must be a single-sampled 2d texture whose format has
+        /// [`TextureFormat::sparse_page_dimensions`].
+        ///
+        /// [`Queue::bind_sparse`]: ../wgpu/struct.Queue.html#method.bind_sparse
+        const SPARSE = 1 << 18;
     }
 }
 
@@ -5638,6 +5815,9 @@ bitflags::bitflags! {
         /// Flag used by the wgpu-core texture tracker to say that the tracker does not know the state of the sub-resource.
         /// This is different from UNINITIALIZED as that says the tracker does know, but the texture has not been initialized.
         const UNKNOWN = 1 << 13;
+        /// The texture is only used as a transient render pass attachment and does not need to be backed by memory.
+        /// Only used when creating a texture, never as a state.
+        const TRANSIENT = 1 << 14;
     }
 }
 
@@ -5663,6 +5843,42 @@ pub struct TextureSelector {
     pub layers: Range<u32>,
 }
 
+/// A transition that wgpu inserted into a command buffer, as reported by
+/// `CommandBuffer::transition_report` when [`InstanceFlags::REPORT_TRANSITIONS`] is set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransitionReport {
+    /// The label of the transitioned resource.
+    pub label: String,
+    /// The transitioned resource and its states.
+    pub resource: TransitionedResource,
+    /// The index of the command that caused the transition.
+    ///
+    /// Commands are counted in the order they were recorded on the command encoder, with
+    /// each pass counting as a single command.
+    pub command_index: u32,
+}
+
+/// The resource of a [`TransitionReport`], with the states it transitioned between.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransitionedResource {
+    /// A buffer transition.
+    Buffer {
+        /// The state before the transition.
+        from: BufferUses,
+        /// The state after the transition.
+        to: BufferUses,
+    },
+    /// A texture transition.
+    Texture {
+        /// The transitioned subresources.
+        selector: TextureSelector,
+        /// The state before the transition.
+        from: TextureUses,
+        /// The state after the transition.
+        to: TextureUses,
+    },
+}
+
 /// Defines the capabilities of a given surface and adapter.
 #[derive(Debug)]
 pub struct SurfaceCapabilities {
@@ -7764,6 +7980,115 @@ impl DispatchIndirectArgs {
     }
 }
 
+/// Describes the records of a GPU-generated command stream for `execute_indirect`.
+///
+/// Every record switches some state and then draws. It is made of `u32`s in this order:
+///
+/// 1. `push_constant_size / 4` push constant values, written to the push constants of
+///    `push_constant_stages` at `push_constant_offset`.
+/// 2. An [`IndirectVertexBufferArgs`] for every slot set in `vertex_buffers`, from the
+///    lowest slot to the highest.
+/// 3. An [`IndirectIndexBufferArgs`], if `index_buffer` is `true`.
+/// 4. A [`DrawIndexedIndirectArgs`] if `indexed` is `true`, or a [`DrawIndirectArgs`].
+///
+/// Requires [`Features::INDIRECT_COMMANDS`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IndirectCommandLayout {
+    /// Bit mask of the vertex buffer slots switched by every record.
+    pub vertex_buffers: u32,
+    /// Whether every record switches the index buffer. Requires `indexed`.
+    pub index_buffer: bool,
+    /// Stages of the push constants written by every record.
+    pub push_constant_stages: ShaderStages,
+    /// Byte offset of the push constants written by every record. Must be a multiple of 4.
+    pub push_constant_offset: u32,
+    /// Byte size of the push constants written by every record. Must be a multiple of 4.
+    ///
+    /// Writing push constants requires [`Features::PUSH_CONSTANTS`].
+    pub push_constant_size: u32,
+    /// Whether every record ends with an indexed draw.
+    pub indexed: bool,
+}
+
+impl IndirectCommandLayout {
+    /// Returns the size in bytes of a single record.
+    #[must_use]
+    pub const fn stride(&self) -> BufferAddress {
+        let draw_size = if self.indexed {
+            size_of::<DrawIndexedIndirectArgs>()
+        } else {
+            size_of::<DrawIndirectArgs>()
+        };
+        let index_buffer_size = if self.index_buffer {
+            size_of::<IndirectIndexBufferArgs>()
+        } else {
+            0
+        };
+        self.push_constant_size as BufferAddress
+            + self.vertex_buffers.count_ones() as BufferAddress
+                * size_of::<IndirectVertexBufferArgs>() as BufferAddress
+            + index_buffer_size as BufferAddress
+            + draw_size as BufferAddress
+    }
+}
+
+/// Argument buffer layout for a vertex buffer switched by `execute_indirect`.
+///
+/// The vertex buffer is a range of the data buffer passed to `execute_indirect`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Pod, Zeroable)]
+pub struct IndirectVertexBufferArgs {
+    /// Byte offset of the vertex buffer in the data buffer. Must be a multiple of 4.
+    pub offset: u32,
+    /// Byte size of the vertex buffer, or 0 for the rest of the data buffer.
+    pub size: u32,
+}
+
+impl IndirectVertexBufferArgs {
+    /// Returns the bytes representation of the struct, ready to be written in a buffer.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// Argument buffer layout for an index buffer switched by `execute_indirect`.
+///
+/// The index buffer is a range of the data buffer passed to `execute_indirect`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Pod, Zeroable)]
+pub struct IndirectIndexBufferArgs {
+    /// Byte offset of the index buffer in the data buffer. Must be a multiple of the index size.
+    pub offset: u32,
+    /// Byte size of the index buffer, or 0 for the rest of the data buffer.
+    pub size: u32,
+    /// The format of the indices: 0 for [`IndexFormat::Uint16`], 1 for [`IndexFormat::Uint32`].
+    pub format: u32,
+}
+
+impl IndirectIndexBufferArgs {
+    /// Creates the arguments of an index buffer with the given format.
+    #[must_use]
+    pub fn new(offset: u32, size: u32, format: IndexFormat) -> Self {
+        let format = match format {
+            IndexFormat::Uint16 => 0,
+            IndexFormat::Uint32 => 1,
+        };
+        Self {
+            offset,
+            size,
+            format,
+        }
+    }
+
+    /// Returns the bytes representation of the struct, ready to be written in a buffer.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
 /// Describes how shader bound checks should be performed.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -0,0 +1,111 @@
+//! Types for binding memory to sparse buffers and textures.
+//!
+//! Buffers and textures created with [`BufferUsages::SPARSE`] or [`TextureUsages::SPARSE`] have no
+//! memory of their own. Memory is bound to them one [page](SPARSE_PAGE_SIZE) at a time by the
+//! queue, and shaders can read from the pages that are not resident: loads return zero and stores
+//! are discarded.
+//!
+//! Requires [`Features::SPARSE_RESOURCES`].
+//!
+//! [`BufferUsages::SPARSE`]: crate::BufferUsages::SPARSE
+//! [`TextureUsages::SPARSE`]: crate::TextureUsages::SPARSE
+//! [`Features::SPARSE_RESOURCES`]: crate::Features::SPARSE_RESOURCES
+
+use crate::{BufferAddress, Extent3d, Origin3d, TextureDescriptor, TextureFormat};
+
+/// Size in bytes of the pages memory is bound to sparse buffers and textures in.
+pub const SPARSE_PAGE_SIZE: BufferAddress = 64 << 10;
+
+/// Binds or unbinds the memory of pages of a sparse buffer.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseBufferBind<B> {
+    /// The buffer to bind memory to.
+    pub buffer: B,
+    /// The offset of the first page to bind, a multiple of [`SPARSE_PAGE_SIZE`].
+    pub offset: BufferAddress,
+    /// The size of the range to bind, a multiple of [`SPARSE_PAGE_SIZE`] unless the range ends
+    /// at the end of the buffer.
+    pub size: BufferAddress,
+    /// Whether the pages become resident, or lose their memory.
+    ///
+    /// Pages that become resident are zeroed, pages that already were keep their contents.
+    pub resident: bool,
+}
+
+/// Binds or unbinds the memory of pages of a sparse texture.
+///
+/// Every mip level from [`TextureDescriptor::sparse_mip_tail_start`] on is smaller than a page,
+/// and these levels of an array layer, called its mip tail, are bound all at once.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparseTextureBind<T> {
+    /// The texture to bind memory to.
+    pub texture: T,
+    /// The mip level of the pages.
+    pub mip_level: u32,
+    /// The first texel of the region to bind, and in `z` its first array layer.
+    ///
+    /// `x` and `y` must be multiples of the [page dimensions] of the format, and zero if
+    /// `mip_level` is in the mip tail.
+    ///
+    /// [page dimensions]: TextureFormat::sparse_page_dimensions
+    pub origin: Origin3d,
+    /// The size of the region to bind, and in `depth_or_array_layers` its number of array layers.
+    ///
+    /// `width` and `height` must be multiples of the [page dimensions] of the format unless the
+    /// region ends at the edge of the mip level, and cover the whole mip level if it is in the
+    /// mip tail.
+    ///
+    /// [page dimensions]: TextureFormat::sparse_page_dimensions
+    pub size: Extent3d,
+    /// Whether the pages become resident, or lose their memory.
+    ///
+    /// Pages that become resident are zeroed, pages that already were keep their contents.
+    pub resident: bool,
+}
+
+impl TextureFormat {
+    /// Returns the width and height in texels of the region of a 2d texture of this format that
+    /// a page of a sparse texture holds.
+    ///
+    /// Returns `None` for formats that can't be used by sparse textures: depth, stencil and
+    /// multi-planar formats.
+    #[must_use]
+    pub fn sparse_page_dimensions(&self) -> Option<(u32, u32)> {
+        if self.is_depth_stencil_format() || self.is_multi_planar_format() {
+            return None;
+        }
+        let (width, height) = match self.block_copy_size(None)? {
+            1 => (256, 256),
+            2 => (256, 128),
+            4 => (128, 128),
+            8 => (128, 64),
+            16 => (64, 64),
+            _ => return None,
+        };
+        let (block_width, block_height) = self.block_dimensions();
+        Some((width * block_width, height * block_height))
+    }
+}
+
+impl<L, V> TextureDescriptor<L, V> {
+    /// Returns the first mip level of the mip tail of a sparse texture with this descriptor.
+    ///
+    /// This is the first level smaller than a page in either dimension, or the mip level count
+    /// if there is no such level.
+    #[must_use]
+    pub fn sparse_mip_tail_start(&self) -> u32 {
+        let Some((page_width, page_height)) = self.format.sparse_page_dimensions() else {
+            return 0;
+        };
+        (0..self.mip_level_count)
+            .find(|&level| {
+                let size = self.size.mip_level_size(level, self.dimension);
+                size.width < page_width || size.height < page_height
+            })
+            .unwrap_or(self.mip_level_count)
+    }
+}
@@ -0,0 +1,1154 @@
+//! Decoder for the ASTC formats, as described by the [Khronos Data Format Specification].
+//!
+//! [Khronos Data Format Specification]: https://registry.khronos.org/DataFormat/specs/1.3/dataformat.1.3.html#ASTC
+
+use super::{f16_to_f32, BitReader};
+use crate::AstcChannel;
+
+#[derive(Clone, Copy)]
+enum Encoding {
+    Bits,
+    Trits,
+    Quints,
+}
+
+/// The integer sequence encodings of the quantization ranges, from 2 to 256 levels, as
+/// `(encoding, bits)`.
+const RANGES: [(Encoding, u32); 21] = [
+    (Encoding::Bits, 1),
+    (Encoding::Trits, 0),
+    (Encoding::Bits, 2),
+    (Encoding::Quints, 0),
+    (Encoding::Trits, 1),
+    (Encoding::Bits, 3),
+    (Encoding::Quints, 1),
+    (Encoding::Trits, 2),
+    (Encoding::Bits, 4),
+    (Encoding::Quints, 2),
+    (Encoding::Trits, 3),
+    (Encoding::Bits, 5),
+    (Encoding::Quints, 3),
+    (Encoding::Trits, 4),
+    (Encoding::Bits, 6),
+    (Encoding::Quints, 4),
+    (Encoding::Trits, 5),
+    (Encoding::Bits, 7),
+    (Encoding::Quints, 5),
+    (Encoding::Trits, 6),
+    (Encoding::Bits, 8),
+];
+
+/// The smallest range that color endpoints may use, with 6 levels.
+const MIN_COLOR_RANGE: usize = 4;
+
+/// Returns the number of bits used to encode `count` values of `range`.
+fn ise_bits(count: u32, range: usize) -> u32 {
+    let (encoding, bits) = RANGES[range];
+    count * bits
+        + match encoding {
+            Encoding::Bits => 0,
+            Encoding::Trits => (8 * count).div_ceil(5),
+            Encoding::Quints => (7 * count).div_ceil(3),
+        }
+}
+
+/// Unpacks the high parts of a group of trits or quints.
+type Unpack = fn(u32) -> [u32; 5];
+
+/// Decodes the integer sequence of `values.len()` values of `range`.
+fn decode_ise(reader: &mut BitReader, range: usize, values: &mut [u32]) {
+    let (encoding, bits) = RANGES[range];
+    let (group_size, packed_bits, unpack): (usize, &[u32], Unpack) = match encoding {
+        Encoding::Bits => {
+            for value in values {
+                *value = reader.read(bits);
+            }
+            return;
+        }
+        Encoding::Trits => (5, &[2, 2, 1, 2, 1], decode_trits),
+        Encoding::Quints => (3, &[3, 2, 2], decode_quints),
+    };
+    for group in values.chunks_mut(group_size) {
+        let mut packed = 0;
+        let mut shift = 0;
+        for (value, &count) in group.iter_mut().zip(packed_bits) {
+            *value = reader.read(bits);
+            packed |= reader.read(count) << shift;
+            shift += count;
+        }
+        for (value, high) in group.iter_mut().zip(unpack(packed)) {
+            *value |= high << bits;
+        }
+    }
+}
+
+fn bit(value: u32, index: u32) -> u32 {
+    (value >> index) & 1
+}
+
+fn bits(value: u32, high: u32, low: u32) -> u32 {
+    (value >> low) & ((1 << (high - low + 1)) - 1)
+}
+
+/// Unpacks the five trits packed into 8 bits.
+fn decode_trits(packed: u32) -> [u32; 5] {
+    let (c, t4, t3);
+    if bits(packed, 4, 2) == 7 {
+        c = bits(packed, 7, 5) << 2 | bits(packed, 1, 0);
+        (t4, t3) = (2, 2);
+    } else {
+        c = bits(packed, 4, 0);
+        if bits(packed, 6, 5) == 3 {
+            (t4, t3) = (2, bit(packed, 7));
+        } else {
+            (t4, t3) = (bit(packed, 7), bits(packed, 6, 5));
+        }
+    }
+    let (t2, t1, t0);
+    if bits(c, 1, 0) == 3 {
+        (t2, t1) = (2, bit(c, 4));
+        t0 = bit(c, 3) << 1 | (bit(c, 2) & !bit(c, 3) & 1);
+    } else if bits(c, 3, 2) == 3 {
+        (t2, t1, t0) = (2, 2, bits(c, 1, 0));
+    } else {
+        (t2, t1) = (bit(c, 4), bits(c, 3, 2));
+        t0 = bit(c, 1) << 1 | (bit(c, 0) & !bit(c, 1) & 1);
+    }
+    [t0, t1, t2, t3, t4]
+}
+
+/// Unpacks the three quints packed into 7 bits.
+fn decode_quints(packed: u32) -> [u32; 5] {
+    let (q0, q1, q2);
+    if bits(packed, 2, 1) == 3 && bits(packed, 6, 5) == 0 {
+        let not_q0 = !bit(packed, 0) & 1;
+        q2 = bit(packed, 0) << 2 | (bit(packed, 4) & not_q0) << 1 | (bit(packed, 3) & not_q0);
+        (q1, q0) = (4, 4);
+    } else {
+        let c;
+        if bits(packed, 2, 1) == 3 {
+            q2 = 4;
+            c = bits(packed, 4, 3) << 3 | (!bits(packed, 6, 5) & 3) << 1 | bit(packed, 0);
+        } else {
+            q2 = bits(packed, 6, 5);
+            c = bits(packed, 4, 0);
+        }
+        if bits(c, 2, 0) == 5 {
+            (q1, q0) = (4, bits(c, 4, 3));
+        } else {
+            (q1, q0) = (bits(c, 4, 3), bits(c, 2, 0));
+        }
+    }
+    [q0, q1, q2, 0, 0]
+}
+
+/// Replicates the `bits` low bits of `value` to fill `target` bits.
+fn replicate(value: u32, bits: u32, target: u32) -> u32 {
+    let mut result = 0;
+    let mut shift = target as i32 - bits as i32;
+    while shift > -(bits as i32) {
+        result |= if shift >= 0 {
+            value << shift
+        } else {
+            value >> -shift
+        };
+        shift -= bits as i32;
+    }
+    result & ((1 << target) - 1)
+}
+
+/// Unquantizes a color endpoint value to 8 bits.
+fn unquantize_color(value: u32, range: usize) -> u32 {
+    let (encoding, bits) = RANGES[range];
+    if let Encoding::Bits = encoding {
+        return replicate(value, bits, 8);
+    }
+    let low = value & ((1 << bits) - 1);
+    let d = value >> bits;
+    let a = if low & 1 == 1 { 0x1ff } else { 0 };
+    let [b1, b2, b3, b4, b5] = [1, 2, 3, 4, 5].map(|i| bit(low, i));
+    let (c, b) = match (encoding, bits) {
+        (Encoding::Trits, 1) => (204, 0),
+        (Encoding::Quints, 1) => (113, 0),
+        (Encoding::Trits, 2) => (93, b1 * 0b100010110),
+        (Encoding::Quints, 2) => (54, b1 * 0b100001100),
+        (Encoding::Trits, 3) => (44, b2 * 0b100001010 + b1 * 0b010000101),
+        (Encoding::Quints, 3) => (26, b2 * 0b100000101 + b1 * 0b010000010),
+        (Encoding::Trits, 4) => (22, b3 * 0b100000100 + b2 * 0b010000010 + b1 * 0b001000001),
+        (Encoding::Quints, 4) => (13, b3 * 0b100000010 + b2 * 0b010000001 + b1 * 0b001000000),
+        (Encoding::Trits, 5) => (
+            11,
+            b4 * 0b100000010 + b3 * 0b010000001 + b2 * 0b001000000 + b1 * 0b000100000,
+        ),
+        (Encoding::Quints, 5) => (
+            6,
+            b4 * 0b100000001 + b3 * 0b010000000 + b2 * 0b001000000 + b1 * 0b000100000,
+        ),
+        (Encoding::Trits, 6) => (
+            5,
+            b5 * 0b100000001
+                + b4 * 0b010000000
+                + b3 * 0b001000000
+                + b2 * 0b000100000
+                + b1 * 0b000010000,
+        ),
+        _ => unreachable!(),
+    };
+    let t = (d * c + b) ^ a;
+    (a & 0x80) | (t >> 2)
+}
+
+/// Unquantizes a weight to the range `0..=64`.
+fn unquantize_weight(value: u32, range: usize) -> u32 {
+    let (encoding, bits) = RANGES[range];
+    let unquantized = match (encoding, bits) {
+        (Encoding::Bits, _) => replicate(value, bits, 6),
+        (Encoding::Trits, 0) => [0, 32, 63][value as usize],
+        (Encoding::Quints, 0) => [0, 16, 32, 47, 63][value as usize],
+        _ => {
+            let low = value & ((1 << bits) - 1);
+            let d = value >> bits;
+            let a = if low & 1 == 1 { 0x7f } else { 0 };
+            let (c, b) = match (encoding, bits) {
+                (Encoding::Trits, 1) => (50, 0),
+                (Encoding::Quints, 1) => (28, 0),
+                (Encoding::Trits, 2) => (23, bit(low, 1) * 0b1000101),
+                (Encoding::Quints, 2) => (13, bit(low, 1) * 0b1000010),
+                (Encoding::Trits, 3) => (11, bit(low, 2) * 0b1000010 + bit(low, 1) * 0b0100001),
+                _ => unreachable!(),
+            };
+            let t = (d * c + b) ^ a;
+            (a & 0x20) | (t >> 2)
+        }
+    };
+    if unquantized > 32 {
+        unquantized + 1
+    } else {
+        unquantized
+    }
+}
+
+struct BlockMode {
+    grid_width: usize,
+    grid_height: usize,
+    dual_plane: bool,
+    weight_range: usize,
+}
+
+fn decode_block_mode(mode: u32) -> Option<BlockMode> {
+    let a = bits(mode, 6, 5) as usize;
+    let (grid_width, grid_height, range, high_precision, dual_plane);
+    if bits(mode, 1, 0) != 0 {
+        range = bit(mode, 4) | bits(mode, 1, 0) << 1;
+        let b = bits(mode, 8, 7) as usize;
+        (grid_width, grid_height) = match bits(mode, 3, 2) {
+            0 => (b + 4, a + 2),
+            1 => (b + 8, a + 2),
+            2 => (a + 2, b + 8),
+            _ if bit(mode, 8) == 0 => (a + 2, (b & 1) + 6),
+            _ => ((b & 1) + 2, a + 2),
+        };
+        (high_precision, dual_plane) = (bit(mode, 9), bit(mode, 10));
+    } else {
+        range = bit(mode, 4) | bits(mode, 3, 2) << 1;
+        (high_precision, dual_plane) = (bit(mode, 9), bit(mode, 10));
+        match bits(mode, 8, 7) {
+            0 => (grid_width, grid_height) = (12, a + 2),
+            1 => (grid_width, grid_height) = (a + 2, 12),
+            2 => {
+                let b = bits(mode, 10, 9) as usize;
+                (grid_width, grid_height) = (a + 6, b + 6);
+                return (range >= 2).then(|| BlockMode {
+                    grid_width,
+                    grid_height,
+                    dual_plane: false,
+                    weight_range: range as usize - 2,
+                });
+            }
+            _ => {
+                (grid_width, grid_height) = match a {
+                    0 => (6, 10),
+                    1 => (10, 6),
+                    _ => return None,
+                }
+            }
+        }
+    }
+    (range >= 2).then(|| BlockMode {
+        grid_width,
+        grid_height,
+        dual_plane: dual_plane == 1,
+        weight_range: (range - 2 + 6 * high_precision) as usize,
+    })
+}
+
+/// The endpoints of a partition.
+///
+/// Components are 8-bit values, or 16-bit logarithmic values if they are HDR.
+struct Endpoints {
+    endpoints: [[i32; 4]; 2],
+    hdr_rgb: bool,
+    hdr_alpha: bool,
+}
+
+/// The HDR value of an alpha of `1.0`.
+const HDR_ONE: i32 = 0x7800;
+
+fn bit_transfer_signed(a: i32, b: i32) -> (i32, i32) {
+    let b = (b >> 1) | (a & 0x80);
+    let a = (a >> 1) & 0x3f;
+    let a = if a & 0x20 != 0 { a - 0x40 } else { a };
+    (a, b)
+}
+
+fn blue_contract([r, g, b, a]: [i32; 4]) -> [i32; 4] {
+    [(r + b) >> 1, (g + b) >> 1, b, a]
+}
+
+fn decode_endpoints(mode: u32, v: &[i32]) -> Endpoints {
+    let ldr = |e0: [i32; 4], e1: [i32; 4]| Endpoints {
+        endpoints: [e0.map(|c| c.clamp(0, 255)), e1.map(|c| c.clamp(0, 255))],
+        hdr_rgb: false,
+        hdr_alpha: false,
+    };
+    match mode {
+        0 => ldr([v[0], v[0], v[0], 255], [v[1], v[1], v[1], 255]),
+        1 => {
+            let l0 = (v[0] >> 2) | (v[1] & 0xc0);
+            let l1 = (l0 + (v[1] & 0x3f)).min(255);
+            ldr([l0, l0, l0, 255], [l1, l1, l1, 255])
+        }
+        2 => {
+            let (y0, y1) = if v[1] >= v[0] {
+                (v[0] << 4, v[1] << 4)
+            } else {
+                ((v[1] << 4) + 8, (v[0] << 4) - 8)
+            };
+            hdr_luminance(y0, y1)
+        }
+        3 => {
+            let (y0, d) = if v[0] & 0x80 != 0 {
+                (
+                    ((v[1] & 0xe0) << 4) | ((v[0] & 0x7f) << 2),
+                    (v[1] & 0x1f) << 2,
+                )
+            } else {
+                (
+                    ((v[1] & 0xf0) << 4) | ((v[0] & 0x7f) << 1),
+                    (v[1] & 0x0f) << 1,
+                )
+            };
+            hdr_luminance(y0, (y0 + d).min(0xfff))
+        }
+        4 => ldr([v[0], v[0], v[0], v[2]], [v[1], v[1], v[1], v[3]]),
+        5 => {
+            let (d0, b0) = bit_transfer_signed(v[1], v[0]);
+            let (d1, b1) = bit_transfer_signed(v[3], v[2]);
+            ldr([b0, b0, b0, b1], [b0 + d0, b0 + d0, b0 + d0, b1 + d1])
+        }
+        6 => ldr(
+            [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                255,
+            ],
+            [v[0], v[1], v[2], 255],
+        ),
+        7 => hdr_rgb_scale(v),
+        8 | 12 => {
+            let (a0, a1) = if mode == 12 { (v[6], v[7]) } else { (255, 255) };
+            if v[1] + v[3] + v[5] >= v[0] + v[2] + v[4] {
+                ldr([v[0], v[2], v[4], a0], [v[1], v[3], v[5], a1])
+            } else {
+                ldr(
+                    blue_contract([v[1], v[3], v[5], a1]),
+                    blue_contract([v[0], v[2], v[4], a0]),
+                )
+            }
+        }
+        9 | 13 => {
+            let (d0, b0) = bit_transfer_signed(v[1], v[0]);
+            let (d1, b1) = bit_transfer_signed(v[3], v[2]);
+            let (d2, b2) = bit_transfer_signed(v[5], v[4]);
+            let (d3, b3) = if mode == 13 {
+                bit_transfer_signed(v[7], v[6])
+            } else {
+                (0, 255)
+            };
+            let base = [b0, b1, b2, b3];
+            let offset = [b0 + d0, b1 + d1, b2 + d2, b3 + d3];
+            if d0 + d1 + d2 >= 0 {
+                ldr(base, offset)
+            } else {
+                ldr(blue_contract(offset), blue_contract(base))
+            }
+        }
+        10 => ldr(
+            [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                v[4],
+            ],
+            [v[0], v[1], v[2], v[5]],
+        ),
+        11 => hdr_rgb(v),
+        14 => {
+            let mut endpoints = hdr_rgb(v);
+            endpoints.endpoints[0][3] = v[6];
+            endpoints.endpoints[1][3] = v[7];
+            endpoints.hdr_alpha = false;
+            endpoints
+        }
+        _ => {
+            let mut endpoints = hdr_rgb(v);
+            let (a0, a1) = hdr_alpha(v[6], v[7]);
+            endpoints.endpoints[0][3] = a0;
+            endpoints.endpoints[1][3] = a1;
+            endpoints
+        }
+    }
+}
+
+fn hdr_luminance(y0: i32, y1: i32) -> Endpoints {
+    Endpoints {
+        endpoints: [
+            [y0 << 4, y0 << 4, y0 << 4, HDR_ONE],
+            [y1 << 4, y1 << 4, y1 << 4, HDR_ONE],
+        ],
+        hdr_rgb: true,
+        hdr_alpha: true,
+    }
+}
+
+/// Decodes HDR RGB base and scale endpoints.
+fn hdr_rgb_scale(v: &[i32]) -> Endpoints {
+    let mode_value = ((v[0] & 0xc0) >> 6) | ((v[1] & 0x80) >> 5) | ((v[2] & 0x80) >> 4);
+    let (major, mode) = if mode_value & 0xc != 0xc {
+        (mode_value >> 2, mode_value & 3)
+    } else if mode_value != 0xf {
+        (mode_value & 3, 4)
+    } else {
+        (0, 5)
+    };
+
+    let mut red = v[0] & 0x3f;
+    let mut green = v[1] & 0x1f;
+    let mut blue = v[2] & 0x1f;
+    let mut scale = v[3] & 0x1f;
+
+    let bit0 = (v[1] >> 6) & 1;
+    let bit1 = (v[1] >> 5) & 1;
+    let bit2 = (v[2] >> 6) & 1;
+    let bit3 = (v[2] >> 5) & 1;
+    let bit4 = (v[3] >> 7) & 1;
+    let bit5 = (v[3] >> 6) & 1;
+    let bit6 = (v[3] >> 5) & 1;
+
+    let one_hot = 1 << mode;
+    if one_hot & 0x30 != 0 {
+        green |= bit0 << 6;
+        blue |= bit2 << 6;
+    }
+    if one_hot & 0x3a != 0 {
+        green |= bit1 << 5;
+        blue |= bit3 << 5;
+    }
+    if one_hot & 0x3d != 0 {
+        scale |= bit6 << 5;
+    }
+    if one_hot & 0x2d != 0 {
+        scale |= bit5 << 6;
+    }
+    if one_hot & 0x04 != 0 {
+        scale |= bit4 << 7;
+    }
+    if one_hot & 0x3b != 0 {
+        red |= bit4 << 6;
+    }
+    if one_hot & 0x04 != 0 {
+        red |= bit3 << 6;
+    }
+    if one_hot & 0x10 != 0 {
+        red |= bit5 << 7;
+    }
+    if one_hot & 0x0f != 0 {
+        red |= bit2 << 7;
+    }
+    if one_hot & 0x05 != 0 {
+        red |= bit1 << 8;
+    }
+    if one_hot & 0x0a != 0 {
+        red |= bit0 << 8;
+    }
+    if one_hot & 0x05 != 0 {
+        red |= bit0 << 9;
+    }
+    if one_hot & 0x02 != 0 {
+        red |= bit6 << 9;
+    }
+    if one_hot & 0x01 != 0 {
+        red |= bit3 << 10;
+    }
+    if one_hot & 0x02 != 0 {
+        red |= bit5 << 10;
+    }
+
+    let shift = [1, 1, 2, 3, 4, 5][mode as usize];
+    red <<= shift;
+    green <<= shift;
+    blue <<= shift;
+    scale <<= shift;
+    if mode != 5 {
+        green = red - green;
+        blue = red - blue;
+    }
+    match major {
+        1 => core::mem::swap(&mut red, &mut green),
+        2 => core::mem::swap(&mut red, &mut blue),
+        _ => {}
+    }
+
+    let e1 = [red, green, blue].map(|c| c.max(0) << 4);
+    let e0 = [red - scale, green - scale, blue - scale].map(|c| c.max(0) << 4);
+    Endpoints {
+        endpoints: [
+            [e0[0], e0[1], e0[2], HDR_ONE],
+            [e1[0], e1[1], e1[2], HDR_ONE],
+        ],
+        hdr_rgb: true,
+        hdr_alpha: true,
+    }
+}
+
+/// Decodes HDR RGB direct endpoints.
+fn hdr_rgb(v: &[i32]) -> Endpoints {
+    let mode = ((v[1] & 0x80) >> 7) | ((v[2] & 0x80) >> 6) | ((v[3] & 0x80) >> 5);
+    let major = ((v[4] & 0x80) >> 7) | ((v[5] & 0x80) >> 6);
+    if major == 3 {
+        return Endpoints {
+            endpoints: [
+                [v[0] << 8, v[2] << 8, (v[4] & 0x7f) << 9, HDR_ONE],
+                [v[1] << 8, v[3] << 8, (v[5] & 0x7f) << 9, HDR_ONE],
+            ],
+            hdr_rgb: true,
+            hdr_alpha: true,
+        };
+    }
+
+    let mut a = v[0] | ((v[1] & 0x40) << 2);
+    let mut b0 = v[2] & 0x3f;
+    let mut b1 = v[3] & 0x3f;
+    let mut c = v[1] & 0x3f;
+    let mut d0 = v[4] & 0x7f;
+    let mut d1 = v[5] & 0x7f;
+
+    let bit0 = (v[2] >> 6) & 1;
+    let bit1 = (v[3] >> 6) & 1;
+    let bit2 = (v[4] >> 6) & 1;
+    let bit3 = (v[5] >> 6) & 1;
+    let bit4 = (v[4] >> 5) & 1;
+    let bit5 = (v[5] >> 5) & 1;
+
+    let one_hot = 1 << mode;
+    if one_hot & 0xa4 != 0 {
+        a |= bit0 << 9;
+    }
+    if one_hot & 0x08 != 0 {
+        a |= bit2 << 9;
+    }
+    if one_hot & 0x50 != 0 {
+        a |= bit4 << 9;
+        a |= bit5 << 10;
+    }
+    if one_hot & 0xa0 != 0 {
+        a |= bit1 << 10;
+    }
+    if one_hot & 0xc0 != 0 {
+        a |= bit2 << 11;
+    }
+    if one_hot & 0x04 != 0 {
+        c |= bit1 << 6;
+    }
+    if one_hot & 0xe8 != 0 {
+        c |= bit3 << 6;
+    }
+    if one_hot & 0x20 != 0 {
+        c |= bit2 << 7;
+    }
+    if one_hot & 0x5b != 0 {
+        b0 |= bit0 << 6;
+        b1 |= bit1 << 6;
+    }
+    if one_hot & 0x12 != 0 {
+        b0 |= bit2 << 7;
+        b1 |= bit3 << 7;
+    }
+    if one_hot & 0xaf != 0 {
+        d0 |= bit4 << 5;
+        d1 |= bit5 << 5;
+    }
+    if one_hot & 0x05 != 0 {
+        d0 |= bit2 << 6;
+        d1 |= bit3 << 6;
+    }
+
+    let d_bits = [7, 6, 7, 6, 5, 6, 5, 6][mode as usize];
+    let d0 = (d0 << (32 - d_bits)) >> (32 - d_bits);
+    let d1 = (d1 << (32 - d_bits)) >> (32 - d_bits);
+
+    let shift = (mode >> 1) ^ 3;
+    let [a, b0, b1, c, d0, d1] = [a, b0, b1, c, d0, d1].map(|value| value << shift);
+
+    let mut e1 = [a, a - b0, a - b1].map(|value| value.clamp(0, 0xfff));
+    let mut e0 = [a - c, a - b0 - c - d0, a - b1 - c - d1].map(|value| value.clamp(0, 0xfff));
+    match major {
+        1 => {
+            e0.swap(0, 1);
+            e1.swap(0, 1);
+        }
+        2 => {
+            e0.swap(0, 2);
+            e1.swap(0, 2);
+        }
+        _ => {}
+    }
+    Endpoints {
+        endpoints: [
+            [e0[0] << 4, e0[1] << 4, e0[2] << 4, HDR_ONE],
+            [e1[0] << 4, e1[1] << 4, e1[2] << 4, HDR_ONE],
+        ],
+        hdr_rgb: true,
+        hdr_alpha: true,
+    }
+}
+
+/// Decodes HDR alpha endpoints.
+fn hdr_alpha(v6: i32, v7: i32) -> (i32, i32) {
+    let selector = ((v6 >> 7) & 1) | ((v7 >> 6) & 2);
+    let (mut v6, mut v7) = (v6 & 0x7f, v7 & 0x7f);
+    if selector == 3 {
+        v6 <<= 5;
+        v7 <<= 5;
+    } else {
+        v6 |= (v7 << (selector + 1)) & 0x780;
+        v7 &= 0x3f >> selector;
+        v7 ^= 32 >> selector;
+        v7 -= 32 >> selector;
+        v6 <<= 4 - selector;
+        v7 <<= 4 - selector;
+        v7 = (v7 + v6).clamp(0, 0xfff);
+    }
+    (v6 << 4, v7 << 4)
+}
+
+/// Converts a 16-bit logarithmic HDR value to a half float.
+fn lns_to_f16(value: u32) -> u16 {
+    let exponent = value >> 11;
+    let mantissa = value & 0x7ff;
+    let mantissa = if mantissa < 512 {
+        3 * mantissa
+    } else if mantissa < 1536 {
+        4 * mantissa - 512
+    } else {
+        5 * mantissa - 2048
+    };
+    ((exponent << 10) | (mantissa >> 3)).min(0x7bff) as u16
+}
+
+fn hash52(mut p: u32) -> u32 {
+    p ^= p >> 15;
+    p = p.wrapping_sub(p << 17);
+    p = p.wrapping_add(p << 7);
+    p = p.wrapping_add(p << 4);
+    p ^= p >> 5;
+    p = p.wrapping_add(p << 16);
+    p ^= p >> 7;
+    p ^= p >> 3;
+    p ^= p << 6;
+    p ^= p >> 17;
+    p
+}
+
+/// Returns the partition of the texel at `(x, y)`.
+fn select_partition(seed: u32, x: u32, y: u32, partitions: u32, small_block: bool) -> usize {
+    let (x, y) = if small_block {
+        (x << 1, y << 1)
+    } else {
+        (x, y)
+    };
+    let seed = seed + (partitions - 1) * 1024;
+    let rnum = hash52(seed);
+    let mut seeds: [u32; 8] = core::array::from_fn(|i| (rnum >> (4 * i)) & 0xf);
+    for seed in &mut seeds {
+        *seed *= *seed;
+    }
+    let (sh1, sh2) = if seed & 1 != 0 {
+        (
+            if seed & 2 != 0 { 4 } else { 5 },
+            if partitions == 3 { 6 } else { 5 },
+        )
+    } else {
+        (
+            if partitions == 3 { 6 } else { 5 },
+            if seed & 2 != 0 { 4 } else { 5 },
+        )
+    };
+    for (i, seed) in seeds.iter_mut().enumerate() {
+        *seed >>= if i % 2 == 0 { sh1 } else { sh2 };
+    }
+
+    // The z coordinate is always 0, so the seeds that multiply it are unused.
+    let a = (seeds[0] * x + seeds[1] * y + (rnum >> 14)) & 0x3f;
+    let b = (seeds[2] * x + seeds[3] * y + (rnum >> 10)) & 0x3f;
+    let c = match partitions {
+        3.. => (seeds[4] * x + seeds[5] * y + (rnum >> 6)) & 0x3f,
+        _ => 0,
+    };
+    let d = match partitions {
+        4 => (seeds[6] * x + seeds[7] * y + (rnum >> 2)) & 0x3f,
+        _ => 0,
+    };
+    if a >= b && a >= c && a >= d {
+        0
+    } else if b >= c && b >= d {
+        1
+    } else if c >= d {
+        2
+    } else {
+        3
+    }
+}
+
+/// Decodes an ASTC block of `width` by `height` texels.
+pub(super) fn decode(
+    block: &[u8],
+    values: &mut [[f32; 4]],
+    width: usize,
+    height: usize,
+    channel: AstcChannel,
+) {
+    let hdr = channel == AstcChannel::Hdr;
+    let srgb = channel == AstcChannel::UnormSrgb;
+    if decode_block(block, values, width, height, hdr, srgb).is_none() {
+        values.fill(match hdr {
+            true => [f32::NAN; 4],
+            false => [1.0, 0.0, 1.0, 1.0],
+        });
+    }
+}
+
+/// Decodes a block, returning `None` if it's an error block.
+fn decode_block(
+    block: &[u8],
+    values: &mut [[f32; 4]],
+    width: usize,
+    height: usize,
+    hdr: bool,
+    srgb: bool,
+) -> Option<()> {
+    let block = u128::from_le_bytes(block.try_into().unwrap());
+    let field = |low: u32, count: u32| (block >> low) as u32 & ((1u64 << count) - 1) as u32;
+
+    if field(0, 9) == 0x1fc {
+        return decode_void_extent(block, values, hdr, srgb);
+    }
+
+    let mode = decode_block_mode(field(0, 11))?;
+    if mode.grid_width > width || mode.grid_height > height {
+        return None;
+    }
+    let planes = if mode.dual_plane { 2 } else { 1 };
+    let weight_count = mode.grid_width * mode.grid_height * planes;
+    if weight_count > 64 {
+        return None;
+    }
+    let weight_bits = ise_bits(weight_count as u32, mode.weight_range);
+    if !(24..=96).contains(&weight_bits) {
+        return None;
+    }
+
+    let partitions = field(11, 2) + 1;
+    if partitions == 4 && mode.dual_plane {
+        return None;
+    }
+    let mut modes = [0; 4];
+    let (color_start, extra_mode_bits) = if partitions == 1 {
+        modes[0] = field(13, 4);
+        (17, 0)
+    } else if field(23, 2) == 0 {
+        modes = [field(25, 4); 4];
+        (29, 0)
+    } else {
+        let extra = 3 * partitions - 4;
+        let encoded = field(25, 4) | field(128 - weight_bits - extra, extra) << 4;
+        let class = field(23, 2) - 1;
+        for (i, mode) in modes.iter_mut().take(partitions as usize).enumerate() {
+            let i = i as u32;
+            *mode = (class + bit(encoded, i)) << 2
+                | bits(encoded, partitions + 2 * i + 1, partitions + 2 * i);
+        }
+        (29, extra)
+    };
+    let modes = &modes[..partitions as usize];
+    let below_weights = 128 - weight_bits - extra_mode_bits;
+    let color_end = below_weights - if mode.dual_plane { 2 } else { 0 };
+    let component_plane = field(below_weights - 2, 2) as usize;
+
+    if !hdr
+        && modes
+            .iter()
+            .any(|&mode| matches!(mode, 2 | 3 | 7 | 11 | 14 | 15))
+    {
+        return None;
+    }
+    let color_count: u32 = modes.iter().map(|&mode| ((mode >> 2) + 1) * 2).sum();
+    if color_count > 18 || color_end < color_start {
+        return None;
+    }
+    let color_range = (0..RANGES.len())
+        .rev()
+        .find(|&range| ise_bits(color_count, range) <= color_end - color_start)?;
+    if color_range < MIN_COLOR_RANGE {
+        return None;
+    }
+
+    let mut reader = BitReader::from_bits(block);
+    reader.read(color_start);
+    let mut colors = [0; 18];
+    let colors = &mut colors[..color_count as usize];
+    decode_ise(&mut reader, color_range, colors);
+    let mut unquantized = [0; 18];
+    for (color, &value) in unquantized.iter_mut().zip(colors.iter()) {
+        *color = unquantize_color(value, color_range) as i32;
+    }
+    let colors = unquantized;
+    let mut endpoints = [const { None }; 4];
+    let mut offset = 0;
+    for (endpoints, &mode) in endpoints.iter_mut().zip(modes) {
+        let count = ((mode >> 2) as usize + 1) * 2;
+        *endpoints = Some(decode_endpoints(mode, &colors[offset..offset + count]));
+        offset += count;
+    }
+
+    let mut reader = BitReader::from_bits(block.reverse_bits());
+    let mut weights = [0; 64];
+    let weights = &mut weights[..weight_count];
+    decode_ise(&mut reader, mode.weight_range, weights);
+    for weight in weights.iter_mut() {
+        *weight = unquantize_weight(*weight, mode.weight_range);
+    }
+
+    let seed = field(13, 10);
+    let small_block = width * height < 31;
+    let (grid_width, grid_height) = (mode.grid_width, mode.grid_height);
+    let ds = (1024 + width / 2) / (width - 1);
+    let dt = (1024 + height / 2) / (height - 1);
+    for (i, value) in values.iter_mut().enumerate() {
+        let (x, y) = (i % width, i / width);
+
+        // Bilinearly infill the weight grid.
+        let gs = (ds * x * (grid_width - 1) + 32) >> 6;
+        let gt = (dt * y * (grid_height - 1) + 32) >> 6;
+        let (js, fs) = (gs >> 4, (gs & 0xf) as u32);
+        let (jt, ft) = (gt >> 4, (gt & 0xf) as u32);
+        let w11 = (fs * ft + 8) >> 4;
+        let factors = [16 + w11 - fs - ft, fs - w11, ft - w11, w11];
+        let texel_weight = |plane: usize| {
+            let grid_weight = |gx: usize, gy: usize| match gx < grid_width && gy < grid_height {
+                true => weights[(gy * grid_width + gx) * planes + plane],
+                false => 0,
+            };
+            let samples = [
+                grid_weight(js, jt),
+                grid_weight(js + 1, jt),
+                grid_weight(js, jt + 1),
+                grid_weight(js + 1, jt + 1),
+            ];
+            (samples.iter().zip(factors).map(|(s, f)| s * f).sum::<u32>() + 8) >> 4
+        };
+        let texel_weights = [texel_weight(0), texel_weight(planes - 1)];
+
+        let partition = match partitions {
+            1 => 0,
+            _ => select_partition(seed, x as u32, y as u32, partitions, small_block),
+        };
+        let Endpoints {
+            endpoints: [e0, e1],
+            hdr_rgb,
+            hdr_alpha,
+        } = endpoints[partition].as_ref().unwrap();
+        for (c, component) in value.iter_mut().enumerate() {
+            let plane = usize::from(mode.dual_plane && c == component_plane);
+            let weight = texel_weights[plane];
+            let interpolate = |e0: u32, e1: u32| (e0 * (64 - weight) + e1 * weight + 32) >> 6;
+            let (e0, e1) = (e0[c] as u32, e1[c] as u32);
+            let is_hdr = if c < 3 { *hdr_rgb } else { *hdr_alpha };
+            *component = if is_hdr {
+                f16_to_f32(lns_to_f16(interpolate(e0, e1)))
+            } else if srgb {
+                (interpolate(e0 << 8 | 0x80, e1 << 8 | 0x80) >> 8) as f32 / 255.0
+            } else {
+                interpolate(e0 * 257, e1 * 257) as f32 / 65535.0
+            };
+        }
+    }
+    Some(())
+}
+
+/// Decodes a block of a single color.
+fn decode_void_extent(block: u128, values: &mut [[f32; 4]], hdr: bool, srgb: bool) -> Option<()> {
+    let field = |low: u32, count: u32| (block >> low) as u32 & ((1u64 << count) - 1) as u32;
+    let hdr_color = field(9, 1) == 1;
+    if field(10, 2) != 3 || (hdr_color && !hdr) {
+        return None;
+    }
+    let coordinates = [field(12, 13), field(25, 13), field(38, 13), field(51, 13)];
+    if coordinates != [0x1fff; 4]
+        && (coordinates[0] >= coordinates[1] || coordinates[2] >= coordinates[3])
+    {
+        return None;
+    }
+    let color: [f32; 4] = core::array::from_fn(|i| {
+        let value = field(64 + 16 * i as u32, 16);
+        if hdr_color {
+            f16_to_f32(value as u16)
+        } else if srgb {
+            (value >> 8) as f32 / 255.0
+        } else {
+            value as f32 / 65535.0
+        }
+    });
+    values.fill(color);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    const FOOTPRINTS: [(usize, usize); 14] = [
+        (4, 4),
+        (5, 4),
+        (5, 5),
+        (6, 5),
+        (6, 6),
+        (8, 5),
+        (8, 6),
+        (8, 8),
+        (10, 5),
+        (10, 6),
+        (10, 8),
+        (10, 10),
+        (12, 10),
+        (12, 12),
+    ];
+
+    /// A block with a 4x2 grid of 3 bit weights `[0, 7, 3, 5, 6, 1, 2, 4]` and direct RGB
+    /// endpoints `(0, 40, 220)` and `(255, 200, 30)`.
+    const GRID_4X2: [u8; 16] = [
+        0x13, 0x00, 0x01, 0xfe, 0x51, 0x90, 0xb9, 0x3d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x57,
+        0x1f,
+    ];
+
+    fn decode_block(
+        block: &[u8],
+        width: usize,
+        height: usize,
+        channel: AstcChannel,
+    ) -> Vec<[f32; 4]> {
+        let mut values = vec![[f32::NAN; 4]; width * height];
+        decode(block, &mut values, width, height, channel);
+        values
+    }
+
+    fn unorm16(values: &[[f32; 4]]) -> Vec<[u16; 4]> {
+        values
+            .iter()
+            .map(|texel| texel.map(|c| (c * 65535.0).round() as u16))
+            .collect()
+    }
+
+    fn void_extent(hdr: bool, coordinates: [u32; 4], color: [u16; 4]) -> [u8; 16] {
+        let mut block = 0xdfc | u128::from(hdr) << 9;
+        for (i, coordinate) in coordinates.into_iter().enumerate() {
+            block |= u128::from(coordinate) << (12 + 13 * i);
+        }
+        for (i, component) in color.into_iter().enumerate() {
+            block |= u128::from(component) << (64 + 16 * i);
+        }
+        block.to_le_bytes()
+    }
+
+    #[test]
+    fn void_extent_ldr() {
+        let color = [0x1234, 0x8000, 0xffff, 0x0000];
+        for coordinates in [[0x1fff; 4], [0, 0x10, 0x20, 0x1ffe]] {
+            let block = void_extent(false, coordinates, color);
+            let values = decode_block(&block, 6, 5, AstcChannel::Unorm);
+            assert_eq!(unorm16(&values), vec![color; 30]);
+
+            // sRGB blocks only use the top 8 bits.
+            let values = decode_block(&block, 6, 5, AstcChannel::UnormSrgb);
+            let expected = [0x12 as f32 / 255.0, 128.0 / 255.0, 1.0, 0.0];
+            assert_eq!(values, vec![expected; 30]);
+
+            let values = decode_block(&block, 6, 5, AstcChannel::Hdr);
+            assert_eq!(unorm16(&values), vec![color; 30]);
+        }
+    }
+
+    #[test]
+    fn void_extent_hdr() {
+        let block = void_extent(true, [0x1fff; 4], [0x3c00, 0x4500, 0x0000, 0x3800]);
+        let values = decode_block(&block, 4, 4, AstcChannel::Hdr);
+        assert_eq!(values, vec![[1.0, 5.0, 0.0, 0.5]; 16]);
+
+        // HDR colors are an error without the HDR profile.
+        for channel in [AstcChannel::Unorm, AstcChannel::UnormSrgb] {
+            let values = decode_block(&block, 4, 4, channel);
+            assert_eq!(values, vec![[1.0, 0.0, 1.0, 1.0]; 16]);
+        }
+    }
+
+    #[test]
+    fn void_extent_errors() {
+        let color = [0xffff; 4];
+        // Empty extents.
+        let block = void_extent(false, [0x10, 0x10, 0, 0x1ffe], color);
+        let values = decode_block(&block, 4, 4, AstcChannel::Unorm);
+        assert_eq!(values, vec![[1.0, 0.0, 1.0, 1.0]; 16]);
+        // Bits 10 and 11 must be set.
+        let mut block = void_extent(false, [0x1fff; 4], color);
+        block[1] &= !0x04;
+        let values = decode_block(&block, 4, 4, AstcChannel::Unorm);
+        assert_eq!(values, vec![[1.0, 0.0, 1.0, 1.0]; 16]);
+        let values = decode_block(&block, 4, 4, AstcChannel::Hdr);
+        assert!(values.iter().flatten().all(|c| c.is_nan()));
+    }
+
+    #[test]
+    fn footprints() {
+        // The corners of the block sample the corners of the weight grid, which are weights
+        // 0, 7, 6 and 4 after unquantization to 0, 64, 55 and 37.
+        let corners = [
+            [0, 10280, 56540, 65535],
+            [47103, 39835, 21443, 65535],
+            [56319, 45618, 14577, 65535],
+            [37887, 34053, 28310, 65535],
+        ];
+        for (width, height) in FOOTPRINTS {
+            let values = unorm16(&decode_block(&GRID_4X2, width, height, AstcChannel::Unorm));
+            let decoded = [
+                values[0],
+                values[width - 1],
+                values[(height - 1) * width],
+                values[width * height - 1],
+            ];
+            assert_eq!(decoded, corners, "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn weight_infill() {
+        let values = unorm16(&decode_block(&GRID_4X2, 8, 5, AstcChannel::Unorm));
+        let red: Vec<u16> = values.iter().map(|texel| texel[0]).collect();
+        #[rustfmt::skip]
+        let expected = [
+            0, 28672, 57343, 53247, 39935, 29696, 38911, 47103,
+            14336, 28672, 43007, 43007, 33791, 27648, 35839, 45055,
+            28672, 28672, 35839, 34815, 28672, 25600, 33791, 43007,
+            41983, 35839, 22528, 23552, 20480, 23552, 31744, 39935,
+            56319, 35839, 15360, 12288, 15360, 20480, 29696, 37887,
+        ];
+        assert_eq!(red, expected);
+    }
+
+    /// Expands `palette` to the texels of the 4x4 blocks below, whose 4x4 grids of 2 bit weights
+    /// are `[0, 1, 2, 3, 3, 2, 1, 0, 1, 3, 0, 2, 2, 0, 3, 1]`.
+    fn expand_palette<T: Copy>(palette: [T; 4]) -> Vec<T> {
+        [0, 1, 2, 3, 3, 2, 1, 0, 1, 3, 0, 2, 2, 0, 3, 1]
+            .map(|i| palette[i])
+            .to_vec()
+    }
+
+    #[test]
+    fn ldr_endpoints() {
+        // Direct RGBA endpoints (10, 20, 30, 255) and (250, 240, 230, 0).
+        let block = [
+            0x42, 0x80, 0x15, 0xf4, 0x29, 0xe0, 0x3d, 0xcc, 0xff, 0x01, 0x00, 0x00, 0x4e, 0xb1,
+            0xd8, 0x27,
+        ];
+        let values = decode_block(&block, 4, 4, AstcChannel::Unorm);
+        let expected = expand_palette([
+            [2570, 5140, 7710, 65535],
+            [22809, 23692, 24576, 44031],
+            [44011, 43128, 42244, 21504],
+            [64250, 61680, 59110, 0],
+        ]);
+        assert_eq!(unorm16(&values), expected);
+        // LDR endpoints decode the same way with the HDR profile.
+        let values = decode_block(&block, 4, 4, AstcChannel::Hdr);
+        assert_eq!(unorm16(&values), expected);
+
+        let values = decode_block(&block, 4, 4, AstcChannel::UnormSrgb);
+        let expected = expand_palette([
+            [10, 20, 30, 255],
+            [89, 92, 96, 171],
+            [171, 168, 164, 84],
+            [250, 240, 230, 0],
+        ]);
+        let values: Vec<[u8; 4]> = values
+            .iter()
+            .map(|texel| texel.map(|c| (c * 255.0).round() as u8))
+            .collect();
+        assert_eq!(values, expected);
+
+        // Direct RGB endpoints (200, 180, 160) and (10, 20, 100) are swapped and blue
+        // contracted to (55, 60, 100) and (180, 170, 160).
+        let block = [
+            0x42, 0x00, 0x91, 0x15, 0x68, 0x29, 0x40, 0xc9, 0x00, 0x00, 0x00, 0x00, 0x4e, 0xb1,
+            0xd8, 0x27,
+        ];
+        let values = decode_block(&block, 4, 4, AstcChannel::Unorm);
+        let expected = expand_palette([
+            [14135, 15420, 25700, 65535],
+            [24676, 24696, 30760, 65535],
+            [35719, 34414, 36060, 65535],
+            [46260, 43690, 41120, 65535],
+        ]);
+        assert_eq!(unorm16(&values), expected);
+    }
+
+    #[test]
+    fn hdr_endpoints() {
+        // HDR luminance endpoints 100 and 200.
+        let block = [
+            0x42, 0x40, 0xc8, 0x90, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4e, 0xb1,
+            0xd8, 0x27,
+        ];
+        let values = decode_block(&block, 4, 4, AstcChannel::Hdr);
+        let expected = expand_palette([0x31c0, 0x4228, 0x537e, 0x6400].map(|luminance| {
+            let luminance = f16_to_f32(luminance);
+            [luminance, luminance, luminance, 1.0]
+        }));
+        assert_eq!(values, expected);
+
+        // HDR endpoints are an error without the HDR profile.
+        let values = decode_block(&block, 4, 4, AstcChannel::Unorm);
+        assert_eq!(values, vec![[1.0, 0.0, 1.0, 1.0]; 16]);
+    }
+
+    #[test]
+    fn reserved_block_mode() {
+        for (width, height) in FOOTPRINTS {
+            let values = decode_block(&[0; 16], width, height, AstcChannel::Unorm);
+            assert_eq!(values, vec![[1.0, 0.0, 1.0, 1.0]; width * height]);
+            let values = decode_block(&[0; 16], width, height, AstcChannel::Hdr);
+            assert!(values.iter().flatten().all(|c| c.is_nan()));
+        }
+    }
+}
@@ -0,0 +1,1138 @@
+//! Decoders for the BC formats, as described by the [Khronos Data Format Specification].
+//!
+//! [Khronos Data Format Specification]: https://registry.khronos.org/DataFormat/specs/1.3/dataformat.1.3.html#S3TC
+
+use super::{f16_to_f32, BitReader};
+
+/// Expands a 5:6:5 color to 8 bits per component.
+fn rgb565(color: u16) -> [f32; 3] {
+    let color = u32::from(color);
+    let (r, g, b) = (color >> 11, (color >> 5) & 0x3f, color & 0x1f);
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+    .map(|c| c as f32)
+}
+
+/// Decodes the color block shared by BC1, BC2 and BC3.
+///
+/// Only BC1 has the three color mode with transparent black.
+fn decode_color(block: &[u8], values: &mut [[f32; 4]], bc1: bool) {
+    let color0 = u16::from_le_bytes([block[0], block[1]]);
+    let color1 = u16::from_le_bytes([block[2], block[3]]);
+    let (c0, c1) = (rgb565(color0), rgb565(color1));
+    let mix = |w0: f32, w1: f32, divisor: f32| {
+        let [r, g, b] = core::array::from_fn(|i| (w0 * c0[i] + w1 * c1[i]) / divisor / 255.0);
+        [r, g, b, 1.0]
+    };
+    let palette = if color0 > color1 || !bc1 {
+        [
+            mix(1.0, 0.0, 1.0),
+            mix(0.0, 1.0, 1.0),
+            mix(2.0, 1.0, 3.0),
+            mix(1.0, 2.0, 3.0),
+        ]
+    } else {
+        [
+            mix(1.0, 0.0, 1.0),
+            mix(0.0, 1.0, 1.0),
+            mix(1.0, 1.0, 2.0),
+            [0.0; 4],
+        ]
+    };
+    let indices = u32::from_le_bytes(block[4..8].try_into().unwrap());
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = palette[((indices >> (2 * i)) & 3) as usize];
+    }
+}
+
+/// Decodes the single channel block shared by BC3, BC4 and BC5 into `channel` of `values`.
+fn decode_channel(block: &[u8], values: &mut [[f32; 4]], channel: usize, signed: bool) {
+    let (e0, e1, swap, min, max) = if signed {
+        let e0 = (block[0] as i8).max(-127);
+        let e1 = (block[1] as i8).max(-127);
+        (
+            f32::from(e0) / 127.0,
+            f32::from(e1) / 127.0,
+            e0 > e1,
+            -1.0,
+            1.0,
+        )
+    } else {
+        let (e0, e1) = (block[0], block[1]);
+        (
+            f32::from(e0) / 255.0,
+            f32::from(e1) / 255.0,
+            e0 > e1,
+            0.0,
+            1.0,
+        )
+    };
+    let mut palette = [e0, e1, 0.0, 0.0, 0.0, 0.0, min, max];
+    if swap {
+        for (i, entry) in palette.iter_mut().enumerate().skip(2) {
+            *entry = ((8 - i) as f32 * e0 + (i - 1) as f32 * e1) / 7.0;
+        }
+    } else {
+        for (i, entry) in palette.iter_mut().enumerate().take(6).skip(2) {
+            *entry = ((6 - i) as f32 * e0 + (i - 1) as f32 * e1) / 5.0;
+        }
+    }
+    let mut indices = [0; 8];
+    indices[..6].copy_from_slice(&block[2..8]);
+    let indices = u64::from_le_bytes(indices);
+    for (i, value) in values.iter_mut().enumerate() {
+        value[channel] = palette[((indices >> (3 * i)) & 7) as usize];
+    }
+}
+
+pub(super) fn decode_bc1(block: &[u8], values: &mut [[f32; 4]]) {
+    decode_color(block, values, true);
+}
+
+pub(super) fn decode_bc2(block: &[u8], values: &mut [[f32; 4]]) {
+    decode_color(&block[8..], values, false);
+    let alpha = u64::from_le_bytes(block[..8].try_into().unwrap());
+    for (i, value) in values.iter_mut().enumerate() {
+        value[3] = ((alpha >> (4 * i)) & 0xf) as f32 / 15.0;
+    }
+}
+
+pub(super) fn decode_bc3(block: &[u8], values: &mut [[f32; 4]]) {
+    decode_color(&block[8..], values, false);
+    decode_channel(&block[..8], values, 3, false);
+}
+
+pub(super) fn decode_bc4(block: &[u8], values: &mut [[f32; 4]], signed: bool) {
+    values.fill([0.0, 0.0, 0.0, 1.0]);
+    decode_channel(block, values, 0, signed);
+}
+
+pub(super) fn decode_bc5(block: &[u8], values: &mut [[f32; 4]], signed: bool) {
+    values.fill([0.0, 0.0, 0.0, 1.0]);
+    decode_channel(&block[..8], values, 0, signed);
+    decode_channel(&block[8..], values, 1, signed);
+}
+
+/// Interpolation weights for 2, 3 and 4 bit indices of BC6H and BC7.
+const WEIGHTS: [&[u32]; 3] = [
+    &[0, 21, 43, 64],
+    &[0, 9, 18, 27, 37, 46, 55, 64],
+    &[0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64],
+];
+
+/// The subset of each texel for the two subset partitions of BC6H and BC7.
+#[rustfmt::skip]
+const PARTITIONS_2: [[u8; 16]; 64] = [
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1],
+    [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1],
+    [0, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 1, 1],
+    [0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1],
+    [0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1, 1],
+    [0, 1, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 0],
+    [0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0],
+    [0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 0, 0],
+    [0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 1],
+    [0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0],
+    [0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 0, 0],
+    [0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0],
+    [0, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1, 0, 0],
+    [0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 0, 0, 0],
+    [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0],
+    [0, 1, 1, 1, 0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 1, 0],
+    [0, 0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 0],
+    [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1],
+    [0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1],
+    [0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0],
+    [0, 0, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 0],
+    [0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0],
+    [0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 1, 0, 1, 0, 1, 0],
+    [0, 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0, 1],
+    [0, 1, 0, 1, 1, 0, 1, 0, 1, 0, 1, 0, 0, 1, 0, 1],
+    [0, 1, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0],
+    [0, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0, 0, 0],
+    [0, 0, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    [0, 0, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 0, 0],
+    [0, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0, 1, 0, 1, 1, 0],
+    [0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1],
+    [0, 1, 1, 0, 0, 1, 1, 0, 1, 0, 0, 1, 1, 0, 0, 1],
+    [0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0],
+    [0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0],
+    [0, 0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0],
+    [0, 0, 0, 0, 0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0, 0],
+    [0, 1, 1, 0, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 1, 1, 0, 1, 1, 0, 0, 1, 0, 0, 1],
+    [0, 1, 1, 0, 0, 0, 1, 1, 1, 0, 0, 1, 1, 1, 0, 0],
+    [0, 0, 1, 1, 1, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 0],
+    [0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 0, 0, 1],
+    [0, 1, 1, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 0, 1],
+    [0, 1, 1, 1, 1, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1],
+    [0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 1, 0, 0, 1, 1, 1],
+    [0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0],
+    [0, 0, 1, 0, 0, 0, 1, 0, 1, 1, 1, 0, 1, 1, 1, 0],
+    [0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1, 1, 1],
+];
+
+/// The subset of each texel for the three subset partitions of BC7.
+#[rustfmt::skip]
+const PARTITIONS_3: [[u8; 16]; 64] = [
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 1, 2, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 2, 0, 0, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2],
+    [0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0, 2, 2, 2, 0],
+    [0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2],
+    [0, 1, 1, 1, 0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0],
+    [0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1],
+    [0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2, 0, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 0, 1, 2, 2, 2, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 0, 0, 1, 1, 0, 0, 2, 2, 1, 0, 2, 2, 1, 0],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1, 0, 0, 0, 0],
+    [0, 0, 1, 2, 0, 0, 1, 2, 1, 1, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1, 0, 1, 1, 0],
+    [0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1],
+    [0, 0, 2, 2, 1, 1, 0, 2, 1, 1, 0, 2, 0, 0, 2, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 0, 0, 2, 2, 2, 2, 2],
+    [0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 0, 0, 2, 0, 0, 0, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 2, 0, 0, 2, 2, 0, 2, 2, 2],
+    [0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0],
+    [0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0],
+    [0, 1, 2, 0, 2, 0, 1, 2, 1, 2, 0, 1, 0, 1, 2, 0],
+    [0, 0, 1, 1, 2, 2, 0, 0, 1, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0, 1, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 0, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 1, 1],
+    [0, 2, 2, 0, 1, 2, 2, 1, 0, 2, 2, 0, 1, 2, 2, 1],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 0, 1, 0, 1],
+    [0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 2, 2, 2, 0, 1, 1, 1],
+    [0, 0, 0, 2, 1, 1, 1, 2, 0, 0, 0, 2, 1, 1, 1, 2],
+    [0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2],
+    [0, 0, 0, 2, 1, 1, 1, 2, 1, 1, 1, 2, 0, 0, 0, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2],
+    [0, 0, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2],
+    [0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1],
+    [0, 2, 2, 2, 1, 2, 2, 2, 0, 2, 2, 2, 1, 2, 2, 2],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 1, 2, 0, 1, 1, 2, 2, 0, 1, 2, 2, 2, 0],
+];
+
+/// The anchor texel of the second subset of the two subset partitions.
+#[rustfmt::skip]
+const ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15,
+    15, 2, 8, 2, 2, 8, 8, 15, 2, 8, 2, 2, 8, 8, 2, 2,
+    15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6,
+    6, 2, 6, 8, 15, 15, 2, 2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// The anchor texels of the second and third subsets of the three subset partitions.
+#[rustfmt::skip]
+const ANCHORS_3: [[u8; 64]; 2] = [
+    [
+        3, 3, 15, 15, 8, 3, 15, 15, 8, 8, 6, 6, 6, 5, 3, 3,
+        3, 3, 8, 15, 3, 3, 6, 10, 5, 8, 8, 6, 8, 5, 15, 15,
+        8, 15, 3, 5, 6, 10, 8, 15, 15, 3, 15, 5, 15, 15, 15, 15,
+        3, 15, 5, 5, 5, 8, 5, 10, 5, 10, 8, 13, 15, 12, 3, 3,
+    ],
+    [
+        15, 8, 8, 3, 15, 15, 3, 8, 15, 15, 15, 15, 15, 15, 15, 8,
+        15, 8, 15, 3, 15, 8, 15, 8, 3, 15, 6, 10, 15, 15, 10, 8,
+        15, 3, 15, 10, 10, 8, 9, 10, 6, 15, 8, 15, 3, 6, 6, 8,
+        15, 3, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 3, 15, 15, 8,
+    ],
+];
+
+fn interpolate(e0: i32, e1: i32, weight: u32) -> i32 {
+    let weight = weight as i32;
+    ((64 - weight) * e0 + weight * e1 + 32) >> 6
+}
+
+fn sign_extend(value: i32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    (value << shift) >> shift
+}
+
+/// Fields of the BC6H endpoints: red, green and blue of the four endpoints, then the partition.
+const R0: u8 = 0;
+const G0: u8 = 1;
+const B0: u8 = 2;
+const R1: u8 = 3;
+const G1: u8 = 4;
+const B1: u8 = 5;
+const R2: u8 = 6;
+const G2: u8 = 7;
+const B2: u8 = 8;
+const R3: u8 = 9;
+const G3: u8 = 10;
+const B3: u8 = 11;
+const D: u8 = 12;
+
+/// A BC6H mode.
+struct Bc6hMode {
+    /// Whether endpoints other than the first are stored as deltas from it.
+    transformed: bool,
+    endpoint_bits: u32,
+    delta_bits: [u32; 3],
+    /// The bits of the block after the mode, as `(field, a, b)` for `field[a:b]`, starting with
+    /// bit `b`.
+    layout: &'static [(u8, u8, u8)],
+}
+
+#[rustfmt::skip]
+const BC6H_MODES: [Bc6hMode; 14] = [
+    Bc6hMode { transformed: true, endpoint_bits: 10, delta_bits: [5, 5, 5], layout: &[
+        (G2, 4, 4), (B2, 4, 4), (B3, 4, 4), (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 4, 0),
+        (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B3, 1, 1),
+        (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 7, delta_bits: [6, 6, 6], layout: &[
+        (G2, 5, 5), (G3, 4, 4), (G3, 5, 5), (R0, 6, 0), (B3, 0, 0), (B3, 1, 1), (B2, 4, 4),
+        (G0, 6, 0), (B2, 5, 5), (B3, 2, 2), (G2, 4, 4), (B0, 6, 0), (B3, 3, 3), (B3, 5, 5),
+        (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0), (B1, 5, 0), (B2, 3, 0),
+        (R2, 5, 0), (R3, 5, 0), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [5, 4, 4], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 4, 0), (R0, 10, 10), (G2, 3, 0), (G1, 3, 0),
+        (G0, 10, 10), (B3, 0, 0), (G3, 3, 0), (B1, 3, 0), (B0, 10, 10), (B3, 1, 1), (B2, 3, 0),
+        (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [4, 5, 4], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 10), (G3, 4, 4), (G2, 3, 0),
+        (G1, 4, 0), (G0, 10, 10), (G3, 3, 0), (B1, 3, 0), (B0, 10, 10), (B3, 1, 1), (B2, 3, 0),
+        (R2, 3, 0), (B3, 0, 0), (B3, 2, 2), (R3, 3, 0), (G2, 4, 4), (B3, 3, 3), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [4, 4, 5], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 10), (B2, 4, 4), (G2, 3, 0),
+        (G1, 3, 0), (G0, 10, 10), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B0, 10, 10), (B2, 3, 0),
+        (R2, 3, 0), (B3, 1, 1), (B3, 2, 2), (R3, 3, 0), (B3, 4, 4), (B3, 3, 3), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 9, delta_bits: [5, 5, 5], layout: &[
+        (R0, 8, 0), (B2, 4, 4), (G0, 8, 0), (G2, 4, 4), (B0, 8, 0), (B3, 4, 4), (R1, 4, 0),
+        (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0), (B1, 4, 0), (B3, 1, 1),
+        (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [6, 5, 5], layout: &[
+        (R0, 7, 0), (G3, 4, 4), (B2, 4, 4), (G0, 7, 0), (B3, 2, 2), (G2, 4, 4), (B0, 7, 0),
+        (B3, 3, 3), (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0), (G3, 3, 0),
+        (B1, 4, 0), (B3, 1, 1), (B2, 3, 0), (R2, 5, 0), (R3, 5, 0), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [5, 6, 5], layout: &[
+        (R0, 7, 0), (B3, 0, 0), (B2, 4, 4), (G0, 7, 0), (G2, 5, 5), (G2, 4, 4), (B0, 7, 0),
+        (G3, 5, 5), (B3, 4, 4), (R1, 4, 0), (G3, 4, 4), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0),
+        (B1, 4, 0), (B3, 1, 1), (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+        (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 8, delta_bits: [5, 5, 6], layout: &[
+        (R0, 7, 0), (B3, 1, 1), (B2, 4, 4), (G0, 7, 0), (B2, 5, 5), (G2, 4, 4), (B0, 7, 0),
+        (B3, 5, 5), (B3, 4, 4), (R1, 4, 0), (G3, 4, 4), (G2, 3, 0), (G1, 4, 0), (B3, 0, 0),
+        (G3, 3, 0), (B1, 5, 0), (B2, 3, 0), (R2, 4, 0), (B3, 2, 2), (R3, 4, 0), (B3, 3, 3),
+        (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: false, endpoint_bits: 6, delta_bits: [6, 6, 6], layout: &[
+        (R0, 5, 0), (G3, 4, 4), (B3, 0, 0), (B3, 1, 1), (B2, 4, 4), (G0, 5, 0), (G2, 5, 5),
+        (B2, 5, 5), (B3, 2, 2), (G2, 4, 4), (B0, 5, 0), (G3, 5, 5), (B3, 3, 3), (B3, 5, 5),
+        (B3, 4, 4), (R1, 5, 0), (G2, 3, 0), (G1, 5, 0), (G3, 3, 0), (B1, 5, 0), (B2, 3, 0),
+        (R2, 5, 0), (R3, 5, 0), (D, 4, 0),
+    ] },
+    Bc6hMode { transformed: false, endpoint_bits: 10, delta_bits: [10, 10, 10], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 9, 0), (G1, 9, 0), (B1, 9, 0),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 11, delta_bits: [9, 9, 9], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 8, 0), (R0, 10, 10), (G1, 8, 0), (G0, 10, 10),
+        (B1, 8, 0), (B0, 10, 10),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 12, delta_bits: [8, 8, 8], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 7, 0), (R0, 10, 11), (G1, 7, 0), (G0, 10, 11),
+        (B1, 7, 0), (B0, 10, 11),
+    ] },
+    Bc6hMode { transformed: true, endpoint_bits: 16, delta_bits: [4, 4, 4], layout: &[
+        (R0, 9, 0), (G0, 9, 0), (B0, 9, 0), (R1, 3, 0), (R0, 10, 15), (G1, 3, 0), (G0, 10, 15),
+        (B1, 3, 0), (B0, 10, 15),
+    ] },
+];
+
+pub(super) fn decode_bc6h(block: &[u8], values: &mut [[f32; 4]], signed: bool) {
+    let mut bits = BitReader::new(block);
+    let mode = match bits.read(2) {
+        mode @ (0 | 1) => mode as usize,
+        low => match bits.read(3) << 2 | low {
+            2 => 2,
+            6 => 3,
+            10 => 4,
+            14 => 5,
+            18 => 6,
+            22 => 7,
+            26 => 8,
+            30 => 9,
+            3 => 10,
+            7 => 11,
+            11 => 12,
+            15 => 13,
+            _ => {
+                // Reserved modes decode to black.
+                values.fill([0.0, 0.0, 0.0, 1.0]);
+                return;
+            }
+        },
+    };
+    let subsets = if mode < 10 { 2 } else { 1 };
+    let mode = &BC6H_MODES[mode];
+
+    let mut fields = [0i32; 13];
+    for &(field, a, b) in mode.layout {
+        let field = &mut fields[usize::from(field)];
+        if a >= b {
+            for bit in b..=a {
+                *field |= (bits.read(1) as i32) << bit;
+            }
+        } else {
+            for bit in (a..=b).rev() {
+                *field |= (bits.read(1) as i32) << bit;
+            }
+        }
+    }
+    let partition = fields[usize::from(D)] as usize;
+    let endpoints = &mut fields[..6 * subsets];
+
+    let endpoint_bits = mode.endpoint_bits;
+    if signed {
+        for value in &mut endpoints[..3] {
+            *value = sign_extend(*value, endpoint_bits);
+        }
+    }
+    if mode.transformed {
+        for i in 3..endpoints.len() {
+            let delta = sign_extend(endpoints[i], mode.delta_bits[i % 3]);
+            endpoints[i] = (endpoints[i % 3] + delta) & ((1 << endpoint_bits) - 1);
+            if signed {
+                endpoints[i] = sign_extend(endpoints[i], endpoint_bits);
+            }
+        }
+    } else if signed {
+        for value in &mut endpoints[3..] {
+            *value = sign_extend(*value, endpoint_bits);
+        }
+    }
+
+    for value in endpoints.iter_mut() {
+        *value = unquantize_bc6h(*value, endpoint_bits, signed);
+    }
+
+    let index_bits = if subsets == 2 { 3 } else { 4 };
+    for (i, value) in values.iter_mut().enumerate() {
+        let subset = match subsets {
+            2 => usize::from(PARTITIONS_2[partition][i]),
+            _ => 0,
+        };
+        let anchor = i == 0 || (subsets == 2 && i == usize::from(ANCHORS_2[partition]));
+        let index = bits.read(index_bits - u32::from(anchor));
+        let weight = WEIGHTS[index_bits as usize - 2][index as usize];
+        for (channel, component) in value[..3].iter_mut().enumerate() {
+            let e0 = endpoints[subset * 6 + channel];
+            let e1 = endpoints[subset * 6 + 3 + channel];
+            *component = f16_to_f32(finish_unquantize_bc6h(interpolate(e0, e1, weight), signed));
+        }
+        value[3] = 1.0;
+    }
+}
+
+fn unquantize_bc6h(value: i32, bits: u32, signed: bool) -> i32 {
+    if !signed {
+        if bits >= 15 {
+            value
+        } else if value == 0 {
+            0
+        } else if value == (1 << bits) - 1 {
+            0xffff
+        } else {
+            ((value << 16) + 0x8000) >> bits
+        }
+    } else if bits >= 16 {
+        value
+    } else {
+        let magnitude = value.abs();
+        let unquantized = if magnitude == 0 {
+            0
+        } else if magnitude >= (1 << (bits - 1)) - 1 {
+            0x7fff
+        } else {
+            ((magnitude << 15) + 0x4000) >> (bits - 1)
+        };
+        if value < 0 {
+            -unquantized
+        } else {
+            unquantized
+        }
+    }
+}
+
+/// Scales an interpolated value to the bits of a half float.
+fn finish_unquantize_bc6h(value: i32, signed: bool) -> u16 {
+    if !signed {
+        ((value * 31) >> 6) as u16
+    } else if value < 0 {
+        0x8000 | (((-value) * 31) >> 5) as u16
+    } else {
+        ((value * 31) >> 5) as u16
+    }
+}
+
+/// A BC7 mode.
+struct Bc7Mode {
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    /// Whether each endpoint has a p-bit.
+    endpoint_p_bits: bool,
+    /// Whether each subset has a p-bit shared by its endpoints.
+    shared_p_bits: bool,
+    index_bits: u32,
+    secondary_index_bits: u32,
+}
+
+const BC7_MODES: [Bc7Mode; 8] = [
+    Bc7Mode {
+        subsets: 3,
+        partition_bits: 4,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 4,
+        alpha_bits: 0,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 3,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 6,
+        alpha_bits: 0,
+        endpoint_p_bits: false,
+        shared_p_bits: true,
+        index_bits: 3,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 3,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 5,
+        alpha_bits: 0,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 0,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 2,
+        index_selection_bits: 1,
+        color_bits: 5,
+        alpha_bits: 6,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 3,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 2,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 8,
+        endpoint_p_bits: false,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 2,
+    },
+    Bc7Mode {
+        subsets: 1,
+        partition_bits: 0,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 7,
+        alpha_bits: 7,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 4,
+        secondary_index_bits: 0,
+    },
+    Bc7Mode {
+        subsets: 2,
+        partition_bits: 6,
+        rotation_bits: 0,
+        index_selection_bits: 0,
+        color_bits: 5,
+        alpha_bits: 5,
+        endpoint_p_bits: true,
+        shared_p_bits: false,
+        index_bits: 2,
+        secondary_index_bits: 0,
+    },
+];
+
+pub(super) fn decode_bc7(block: &[u8], values: &mut [[f32; 4]]) {
+    let mode = block[0].trailing_zeros() as usize;
+    if mode >= BC7_MODES.len() {
+        values.fill([0.0; 4]);
+        return;
+    }
+    let mut bits = BitReader::new(block);
+    bits.read(mode as u32 + 1);
+    let mode = &BC7_MODES[mode];
+
+    let partition = bits.read(mode.partition_bits) as usize;
+    let rotation = bits.read(mode.rotation_bits);
+    let index_selection = bits.read(mode.index_selection_bits);
+
+    let endpoint_count = mode.subsets * 2;
+    let mut endpoints = [[0u32; 4]; 6];
+    let mut channel_bits = [
+        mode.color_bits,
+        mode.color_bits,
+        mode.color_bits,
+        mode.alpha_bits,
+    ];
+    for (channel, &count) in channel_bits.iter().enumerate() {
+        for endpoint in &mut endpoints[..endpoint_count] {
+            endpoint[channel] = bits.read(count);
+        }
+    }
+    if mode.endpoint_p_bits || mode.shared_p_bits {
+        let p_bits: [u32; 6] = match mode.endpoint_p_bits {
+            true => core::array::from_fn(|i| if i < endpoint_count { bits.read(1) } else { 0 }),
+            false => {
+                let shared: [u32; 3] =
+                    core::array::from_fn(|i| if i < mode.subsets { bits.read(1) } else { 0 });
+                core::array::from_fn(|i| shared[i / 2])
+            }
+        };
+        for (endpoint, p_bit) in endpoints.iter_mut().zip(p_bits) {
+            for (value, &count) in endpoint.iter_mut().zip(&channel_bits) {
+                if count > 0 {
+                    *value = *value << 1 | p_bit;
+                }
+            }
+        }
+        for count in &mut channel_bits {
+            if *count > 0 {
+                *count += 1;
+            }
+        }
+    }
+    for endpoint in &mut endpoints[..endpoint_count] {
+        for (value, &count) in endpoint.iter_mut().zip(&channel_bits) {
+            *value = match count {
+                0 => 0xff,
+                _ => (*value << (8 - count)) | (*value >> (2 * count - 8)),
+            };
+        }
+    }
+
+    let subset_of = |i: usize| match mode.subsets {
+        2 => usize::from(PARTITIONS_2[partition][i]),
+        3 => usize::from(PARTITIONS_3[partition][i]),
+        _ => 0,
+    };
+    let is_anchor = |i: usize| match mode.subsets {
+        2 => i == 0 || i == usize::from(ANCHORS_2[partition]),
+        3 => {
+            i == 0
+                || i == usize::from(ANCHORS_3[0][partition])
+                || i == usize::from(ANCHORS_3[1][partition])
+        }
+        _ => i == 0,
+    };
+    let indices: [u32; 16] =
+        core::array::from_fn(|i| bits.read(mode.index_bits - u32::from(is_anchor(i))));
+    let secondary_indices: [u32; 16] = core::array::from_fn(|i| match mode.secondary_index_bits {
+        0 => 0,
+        count => bits.read(count - u32::from(i == 0)),
+    });
+
+    for (i, value) in values.iter_mut().enumerate() {
+        let subset = subset_of(i);
+        let (e0, e1) = (endpoints[subset * 2], endpoints[subset * 2 + 1]);
+        let primary = WEIGHTS[mode.index_bits as usize - 2][indices[i] as usize];
+        let (color_weight, alpha_weight) = match mode.secondary_index_bits {
+            0 => (primary, primary),
+            count => {
+                let secondary = WEIGHTS[count as usize - 2][secondary_indices[i] as usize];
+                match index_selection {
+                    0 => (primary, secondary),
+                    _ => (secondary, primary),
+                }
+            }
+        };
+        let mut texel: [i32; 4] = core::array::from_fn(|channel| {
+            let weight = if channel == 3 {
+                alpha_weight
+            } else {
+                color_weight
+            };
+            interpolate(e0[channel] as i32, e1[channel] as i32, weight)
+        });
+        if rotation > 0 {
+            texel.swap(rotation as usize - 1, 3);
+        }
+        *value = texel.map(|c| c as f32 / 255.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The indices of a BC4 block that uses palette entries 0 to 7, then 7 to 0.
+    const CHANNEL_INDICES: [u8; 6] = [0x88, 0xc6, 0xfa, 0x77, 0x39, 0x05];
+
+    fn decode(decoder: impl Fn(&[u8], &mut [[f32; 4]]), block: &[u8]) -> [[f32; 4]; 16] {
+        let mut values = [[f32::NAN; 4]; 16];
+        decoder(block, &mut values);
+        values
+    }
+
+    fn channel_block(e0: u8, e1: u8) -> [u8; 8] {
+        let mut block = [e0, e1, 0, 0, 0, 0, 0, 0];
+        block[2..].copy_from_slice(&CHANNEL_INDICES);
+        block
+    }
+
+    fn assert_channel(values: &[[f32; 4]; 16], channel: usize, palette: [f32; 8]) {
+        for (i, value) in values.iter().enumerate() {
+            let expected = palette[if i < 8 { i } else { 15 - i }];
+            assert!(
+                (value[channel] - expected).abs() < 1e-6,
+                "texel {i}: {value:?}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn bc4_unorm() {
+        let values = decode(|b, v| decode_bc4(b, v, false), &channel_block(210, 140));
+        let palette = [210.0, 140.0, 200.0, 190.0, 180.0, 170.0, 160.0, 150.0];
+        assert_channel(&values, 0, palette.map(|c| c / 255.0));
+        assert!(values.iter().all(|v| v[1..] == [0.0, 0.0, 1.0]));
+
+        let values = decode(|b, v| decode_bc4(b, v, false), &channel_block(100, 200));
+        let palette = [100.0, 200.0, 120.0, 140.0, 160.0, 180.0, 0.0, 255.0];
+        assert_channel(&values, 0, palette.map(|c| c / 255.0));
+    }
+
+    #[test]
+    fn bc4_snorm() {
+        // -128 is clamped to -127, so this block uses the six value palette.
+        let values = decode(|b, v| decode_bc4(b, v, true), &channel_block(0x80, 127));
+        assert_channel(&values, 0, [-1.0, 1.0, -0.6, -0.2, 0.2, 0.6, -1.0, 1.0]);
+
+        let values = decode(|b, v| decode_bc4(b, v, true), &channel_block(127, 0x81));
+        let palette = [7.0, -7.0, 5.0, 3.0, 1.0, -1.0, -3.0, -5.0];
+        assert_channel(&values, 0, palette.map(|c| c / 7.0));
+    }
+
+    #[test]
+    fn bc5() {
+        let mut block = [0; 16];
+        block[..8].copy_from_slice(&channel_block(210, 140));
+        block[8..].copy_from_slice(&channel_block(100, 200));
+        let values = decode(|b, v| decode_bc5(b, v, false), &block);
+        let red = [210.0, 140.0, 200.0, 190.0, 180.0, 170.0, 160.0, 150.0];
+        let green = [100.0, 200.0, 120.0, 140.0, 160.0, 180.0, 0.0, 255.0];
+        assert_channel(&values, 0, red.map(|c| c / 255.0));
+        assert_channel(&values, 1, green.map(|c| c / 255.0));
+        assert!(values.iter().all(|v| v[2..] == [0.0, 1.0]));
+
+        block[..8].copy_from_slice(&channel_block(0x80, 127));
+        block[8..].copy_from_slice(&channel_block(127, 0x81));
+        let values = decode(|b, v| decode_bc5(b, v, true), &block);
+        let green = [7.0, -7.0, 5.0, 3.0, 1.0, -1.0, -3.0, -5.0];
+        assert_channel(&values, 0, [-1.0, 1.0, -0.6, -0.2, 0.2, 0.6, -1.0, 1.0]);
+        assert_channel(&values, 1, green.map(|c| c / 7.0));
+    }
+
+    fn assert_bc6h(signed: bool, block: [u8; 16], expected: [[u16; 3]; 16]) {
+        let values = decode(|b, v| decode_bc6h(b, v, signed), &block);
+        for (i, (value, expected)) in values.iter().zip(expected).enumerate() {
+            let expected = expected.map(f16_to_f32);
+            assert_eq!(value[..3], expected, "{block:02x?}, texel {i}");
+            assert_eq!(value[3], 1.0);
+        }
+    }
+
+    #[test]
+    fn bc6h_unsigned() {
+        // Mode 11: one region, 10 bit endpoints without deltas.
+        assert_bc6h(
+            false,
+            [
+                0x03, 0x00, 0x00, 0xff, 0xff, 0x9f, 0x0c, 0x00, 0x10, 0x32, 0x54, 0x76, 0x98, 0xba,
+                0xdc, 0xfe,
+            ],
+            [
+                [0x0000, 0x3e0f, 0x7bff],
+                [0x07c0, 0x3af1, 0x743f],
+                [0x1170, 0x370b, 0x6a8f],
+                [0x1930, 0x33ed, 0x62cf],
+                [0x20f0, 0x30ce, 0x5b0f],
+                [0x28b0, 0x2db0, 0x534f],
+                [0x3260, 0x29ca, 0x499f],
+                [0x3a20, 0x26ac, 0x41df],
+                [0x41df, 0x238e, 0x3a20],
+                [0x499f, 0x2070, 0x3260],
+                [0x534f, 0x1c8a, 0x28b0],
+                [0x5b0f, 0x196c, 0x20f0],
+                [0x62cf, 0x164d, 0x1930],
+                [0x6a8f, 0x132f, 0x1170],
+                [0x743f, 0x0f49, 0x07c0],
+                [0x7bff, 0x0c2b, 0x0000],
+            ],
+        );
+        // Mode 14: one region, 16 bit endpoints with 4 bit deltas.
+        assert_bc6h(
+            false,
+            [
+                0x8f, 0x46, 0xf8, 0x01, 0x38, 0x04, 0x7f, 0x08, 0xf0, 0xde, 0xbc, 0x9a, 0x78, 0x56,
+                0x34, 0x12,
+            ],
+            [
+                [0x08d1, 0x7bf8, 0x3e00],
+                [0x08d4, 0x7bf4, 0x3e00],
+                [0x08d4, 0x7bf4, 0x3e00],
+                [0x08d4, 0x7bf4, 0x3e00],
+                [0x08d4, 0x7bf5, 0x3e00],
+                [0x08d3, 0x7bf5, 0x3e00],
+                [0x08d3, 0x7bf5, 0x3e00],
+                [0x08d3, 0x7bf5, 0x3e00],
+                [0x08d3, 0x7bf6, 0x3e00],
+                [0x08d2, 0x7bf6, 0x3e00],
+                [0x08d2, 0x7bf6, 0x3e00],
+                [0x08d2, 0x7bf6, 0x3e00],
+                [0x08d2, 0x7bf7, 0x3e00],
+                [0x08d1, 0x7bf7, 0x3e00],
+                [0x08d1, 0x7bf7, 0x3e00],
+                [0x08d1, 0x7bf8, 0x3e00],
+            ],
+        );
+        // Mode 1: two regions, 10 bit endpoints with 5 bit deltas.
+        assert_bc6h(
+            false,
+            [
+                0x94, 0x25, 0x5e, 0xd1, 0x7f, 0x16, 0xf6, 0x1f, 0x8a, 0x08, 0x10, 0x8d, 0xf5, 0xef,
+                0x72, 0xca,
+            ],
+            [
+                [0x2463, 0x54d3, 0x7927],
+                [0x24a4, 0x548d, 0x7923],
+                [0x2450, 0x54de, 0x78b6],
+                [0x23f8, 0x5530, 0x787d],
+                [0x2570, 0x53b4, 0x7915],
+                [0x25b1, 0x536f, 0x7911],
+                [0x22e9, 0x5632, 0x77cd],
+                [0x2292, 0x5685, 0x7794],
+                [0x2634, 0x52e3, 0x7908],
+                [0x25f3, 0x5329, 0x790c],
+                [0x2340, 0x55df, 0x7805],
+                [0x2398, 0x558d, 0x783e],
+                [0x2527, 0x5402, 0x791a],
+                [0x24e6, 0x5448, 0x791e],
+                [0x24a7, 0x548b, 0x78ee],
+                [0x23f8, 0x5530, 0x787d],
+            ],
+        );
+        // Mode 10: two regions, 6 bit endpoints without deltas.
+        assert_bc6h(
+            false,
+            [
+                0xfe, 0x4f, 0x80, 0xc1, 0x07, 0xe8, 0x97, 0xcf, 0x15, 0x14, 0x10, 0x8d, 0xf5, 0xef,
+                0x72, 0x0a,
+            ],
+            [
+                [0x7bff, 0x0000, 0x3ef8],
+                [0x6a8f, 0x1170, 0x3eb2],
+                [0x24b1, 0x3811, 0x4b71],
+                [0x2cdd, 0x403d, 0x539d],
+                [0x3450, 0x47af, 0x3dd9],
+                [0x22e0, 0x591f, 0x3d93],
+                [0x464b, 0x59ab, 0x6d0b],
+                [0x4e78, 0x61d8, 0x7538],
+                [0x0000, 0x7bff, 0x3d08],
+                [0x1170, 0x6a8f, 0x3d4d],
+                [0x3e1f, 0x517f, 0x64df],
+                [0x35f2, 0x4952, 0x5cb2],
+                [0x47af, 0x3450, 0x3e26],
+                [0x591f, 0x22e0, 0x3e6c],
+                [0x1c84, 0x2fe4, 0x4344],
+                [0x1458, 0x27b8, 0x3b18],
+            ],
+        );
+        // Mode 19 is reserved.
+        assert_bc6h(false, [0x13; 16], [[0; 3]; 16]);
+    }
+
+    #[test]
+    fn bc6h_signed() {
+        assert_bc6h(
+            true,
+            [
+                0xe3, 0x7f, 0x00, 0xff, 0x0b, 0x20, 0xe0, 0x7f, 0x10, 0x32, 0x54, 0x76, 0x98, 0xba,
+                0xdc, 0xfe,
+            ],
+            [
+                [0x805d, 0xfbff, 0x7bff],
+                [0x8051, 0xf81d, 0x781d],
+                [0x8042, 0xf343, 0x7343],
+                [0x8037, 0xef61, 0x6f61],
+                [0x802b, 0xeb7f, 0x6b7f],
+                [0x801f, 0xe79d, 0x679d],
+                [0x8011, 0xe2c2, 0x62c2],
+                [0x8005, 0xdee0, 0x5ee0],
+                [0x0005, 0xdaff, 0x5aff],
+                [0x0011, 0xd71d, 0x571d],
+                [0x001f, 0xd242, 0x5242],
+                [0x002b, 0xce60, 0x4e60],
+                [0x0037, 0xca7e, 0x4a7e],
+                [0x0042, 0xc69c, 0x469c],
+                [0x0051, 0xc1c2, 0x41c2],
+                [0x005d, 0xbde1, 0x3de1],
+            ],
+        );
+        assert_bc6h(
+            true,
+            [
+                0x0f, 0x00, 0x00, 0x00, 0x78, 0xfe, 0xc6, 0x00, 0xfe, 0xde, 0xbc, 0x9a, 0x78, 0x56,
+                0x34, 0x12,
+            ],
+            [
+                [0x3a20, 0xba1d, 0x0000],
+                [0x3a1f, 0xba19, 0x0000],
+                [0x3a1f, 0xba19, 0x0000],
+                [0x3a1f, 0xba1a, 0x0000],
+                [0x3a1f, 0xba1a, 0x0000],
+                [0x3a1f, 0xba1b, 0x0000],
+                [0x3a1f, 0xba1b, 0x0000],
+                [0x3a1f, 0xba1c, 0x0000],
+                [0x3a1f, 0xba1c, 0x0000],
+                [0x3a20, 0xba1d, 0x0000],
+                [0x3a20, 0xba1d, 0x0000],
+                [0x3a20, 0xba1e, 0x0000],
+                [0x3a20, 0xba1e, 0x0000],
+                [0x3a20, 0xba1f, 0x0000],
+                [0x3a20, 0xba1f, 0x0000],
+                [0x3a20, 0xba20, 0x0000],
+            ],
+        );
+        assert_bc6h(
+            true,
+            [
+                0x14, 0x7e, 0x08, 0x00, 0x7c, 0x16, 0xf6, 0x1f, 0x8a, 0x08, 0x10, 0x8d, 0xf5, 0xef,
+                0x72, 0xca,
+            ],
+            [
+                [0x83ff, 0x03ff, 0xfbff],
+                [0x837c, 0x036f, 0xd91f],
+                [0x8425, 0x0414, 0xb719],
+                [0x84d4, 0x04b9, 0x94a6],
+                [0x81e5, 0x01b0, 0x1360],
+                [0x8162, 0x011f, 0x3640],
+                [0x86f2, 0x06bd, 0x5686],
+                [0x87a1, 0x0763, 0x78f9],
+                [0x805d, 0x0000, 0x7bff],
+                [0x80df, 0x0090, 0x591f],
+                [0x8644, 0x0617, 0x3412],
+                [0x8595, 0x0572, 0x11a0],
+                [0x8276, 0x024f, 0x9360],
+                [0x82f9, 0x02df, 0xb640],
+                [0x8377, 0x036e, 0xd98c],
+                [0x84d4, 0x04b9, 0x94a6],
+            ],
+        );
+        assert_bc6h(
+            true,
+            [
+                0xfe, 0x4f, 0x80, 0xc1, 0x07, 0xe8, 0x97, 0xcf, 0x15, 0x14, 0x10, 0x8d, 0xf5, 0xef,
+                0x72, 0x0a,
+            ],
+            [
+                [0x85d0, 0x0000, 0xfbff],
+                [0x84fe, 0x80d1, 0xd91f],
+                [0x028b, 0x294b, 0x500b],
+                [0x9087, 0x1638, 0x3cf8],
+                [0x8273, 0x835c, 0x1360],
+                [0x81a2, 0x842d, 0x3640],
+                [0xcbdd, 0xa51d, 0x01a2],
+                [0xdef0, 0xb830, 0x9170],
+                [0x0000, 0x85d0, 0x7bff],
+                [0x80d1, 0x84fe, 0x591f],
+                [0xb8cb, 0x920b, 0x14b5],
+                [0xa5b8, 0x0107, 0x27c7],
+                [0x835c, 0x8273, 0x9360],
+                [0x842d, 0x81a2, 0xb640],
+                [0x159d, 0x3c5d, 0x631d],
+                [0x28b0, 0x4f70, 0x7630],
+            ],
+        );
+    }
+
+    /// Decodes a BC7 block to 8 bit RGBA texels packed as `0xRRGGBBAA`.
+    fn decode_bc7_packed(block: [u8; 16]) -> [u32; 16] {
+        decode(decode_bc7, &block)
+            .map(|texel| u32::from_be_bytes(texel.map(|c| (c * 255.0).round() as u8)))
+    }
+
+    #[test]
+    fn bc7_modes() {
+        #[rustfmt::skip]
+        let cases: [([u8; 16], [u32; 16]); 9] = [
+            // Mode 0: three subsets, unique P-bits.
+            (
+                [0x21, 0x9e, 0x12, 0x54, 0x1c, 0xeb, 0x67, 0x9a, 0xe3, 0x2c, 0x23, 0x9a, 0xf5, 0x11, 0x8d, 0xf5],
+                [
+                    0x182939ff, 0x37444fff, 0x5b7895ff, 0x68727dff, 0x999794ff, 0xb8b2aaff, 0x8f6030ff, 0x9c5a18ff,
+                    0x182939ff, 0x1ee278ff, 0x34c574ff, 0x68727dff, 0x63886dff, 0x796b6aff, 0x8f4e66ff, 0x4aa871ff,
+                ],
+            ),
+            // Mode 1: two subsets, shared P-bits.
+            (
+                [0x02, 0x05, 0x1f, 0x02, 0x28, 0x15, 0xfe, 0x3f, 0x10, 0x2a, 0xf1, 0x29, 0x2e, 0x35, 0xd6, 0xcf],
+                [
+                    0x16a3ffff, 0xf35202ff, 0x4db85eff, 0x60a76bff, 0x3598dbff, 0xd45d26ff, 0x25db42ff, 0x38ca4fff,
+                    0x548cb8ff, 0x738194ff, 0x38ca4fff, 0x25db42ff, 0xd45d26ff, 0xf35202ff, 0x729678ff, 0x4db85eff,
+                ],
+            ),
+            // Mode 2: three subsets, no P-bits.
+            (
+                [0x04, 0xc0, 0x57, 0xfe, 0xe4, 0x0f, 0x28, 0x3d, 0x19, 0x22, 0xaf, 0x4e, 0x96, 0x77, 0x72, 0xf2],
+                [
+                    0x00ff84ff, 0x54ab6eff, 0xc1a588ff, 0x88a5c1ff, 0xff0042ff, 0xab5458ff, 0xf7a552ff, 0x52a5f7ff,
+                    0x54ab6eff, 0x9d9d9dff, 0xcececeff, 0x52a5f7ff, 0x6a6a6aff, 0x9d9d9dff, 0xcececeff, 0x6a6a6aff,
+                ],
+            ),
+            // Mode 3: two subsets, 7 bit colors with unique P-bits.
+            (
+                [0x08, 0x90, 0x01, 0x0c, 0x3c, 0xe0, 0x2f, 0x72, 0xcb, 0x80, 0x38, 0x72, 0x3b, 0x8d, 0xc7, 0x72],
+                [
+                    0xc90165ff, 0x00fe80ff, 0x60778eff, 0xaaabacff, 0x42ab77ff, 0x87546eff, 0x194571ff, 0xf1ddc9ff,
+                    0x00fe80ff, 0xc90165ff, 0xaaabacff, 0x60778eff, 0x87546eff, 0x42ab77ff, 0xf1ddc9ff, 0x194571ff,
+                ],
+            ),
+            // Mode 4: red rotated into alpha, 2 bit color indices.
+            (
+                [0x30, 0x1f, 0x80, 0xaf, 0x68, 0xc1, 0xcb, 0xc9, 0xc9, 0xc9, 0xbf, 0xcb, 0x29, 0x88, 0xc6, 0xfa],
+                [
+                    0x720052ff, 0xf3546dab, 0xd4ab8a54, 0xb4ffa500, 0x950052ff, 0x72546dab, 0x53ab8a54, 0x33ffa500,
+                    0x140052ff, 0x33546dab, 0x53ab8a54, 0x72ffa500, 0x950052ff, 0xb4546dab, 0xd4ab8a54, 0xf3ffa500,
+                ],
+            ),
+            // Mode 4: no rotation, 3 bit color indices.
+            (
+                [0x90, 0x1f, 0x80, 0xaf, 0x68, 0xc1, 0x77, 0x72, 0x72, 0x72, 0xb8, 0xcb, 0x29, 0x88, 0xc6, 0xfa],
+                [
+                    0xff00525d, 0x00ffa5aa, 0x24db99f3, 0x48b78e14, 0x6c93825d, 0x936c75aa, 0xb74869f3, 0xdb245e14,
+                    0xff00525d, 0xdb245eaa, 0xb74869f3, 0x936c7514, 0x6c93825d, 0x48b78eaa, 0x24db99f3, 0x00ffa514,
+                ],
+            ),
+            // Mode 5: green rotated into alpha.
+            (
+                [0xa0, 0x7f, 0x00, 0xe0, 0x0f, 0x04, 0xfd, 0x03, 0xc8, 0x37, 0x4a, 0x99, 0x93, 0x93, 0x6f, 0x90],
+                [
+                    0xffab8100, 0xabff6c54, 0x54ab55ab, 0x005440ff, 0x000040ff, 0x54ff55ab, 0xabab6c54, 0xff548100,
+                    0xab006c54, 0xab006c54, 0x545455ab, 0x54ab55ab, 0xffff8100, 0x00ff40ff, 0xffab8100, 0x005440ff,
+                ],
+            ),
+            // Mode 6: one subset, 4 bit indices.
+            (
+                [0x40, 0x85, 0x9b, 0xa2, 0xf5, 0x18, 0xff, 0x80, 0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe],
+                [
+                    0x15293dff, 0x213242ef, 0x313d48db, 0x3d454dcb, 0x4a4e52bb, 0x565757ab, 0x66615d97, 0x726a6287,
+                    0x7f736778, 0x8b7c6c68, 0x9b867254, 0xa78f7744, 0xb4987c34, 0xc0a08124, 0xd0ab8710, 0xdcb48c00,
+                ],
+            ),
+            // Mode 7: two subsets with alpha.
+            (
+                [0x80, 0xc0, 0x07, 0x00, 0x82, 0x0f, 0x20, 0x00, 0x1f, 0xfe, 0x07, 0x7e, 0xca, 0x37, 0x72, 0x9c],
+                [
+                    0xff0404ff, 0xab5503d3, 0x5a5aacab, 0x868686ff, 0x00fb0079, 0x54aa01a5, 0x2c2cd554, 0x0000fb00,
+                    0xab5503d3, 0x54aa01a5, 0x868686ff, 0x0000fb00, 0x54aa01a5, 0x00fb0079, 0x0000fb00, 0x2c2cd554,
+                ],
+            ),
+        ];
+        for (block, expected) in cases {
+            assert_eq!(
+                decode_bc7_packed(block),
+                expected,
+                "mode {}",
+                block[0].trailing_zeros()
+            );
+        }
+
+        // A first byte of zero is not a valid mode.
+        assert_eq!(decode_bc7_packed([0; 16]), [0; 16]);
+    }
+}
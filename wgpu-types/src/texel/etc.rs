@@ -0,0 +1,499 @@
+//! Decoders for the ETC2 and EAC formats, as described by the [Khronos Data Format Specification].
+//!
+//! Both store texel indices in column-major order.
+//!
+//! [Khronos Data Format Specification]: https://registry.khronos.org/DataFormat/specs/1.3/dataformat.1.3.html#ETC2
+
+/// Modifiers of the individual and differential modes, for the small and large index.
+const MODIFIERS: [[i32; 2]; 8] = [
+    [2, 8],
+    [5, 17],
+    [9, 29],
+    [13, 42],
+    [18, 60],
+    [24, 80],
+    [33, 106],
+    [47, 183],
+];
+
+/// Distances between the paint colors of the T and H modes.
+const DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+/// Modifiers of EAC blocks.
+#[rustfmt::skip]
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+fn extend4(value: u64) -> i32 {
+    (value << 4 | value) as i32
+}
+
+fn extend5(value: i32) -> i32 {
+    value << 3 | value >> 2
+}
+
+fn extend6(value: u64) -> i32 {
+    (value << 2 | value >> 4) as i32
+}
+
+fn extend7(value: u64) -> i32 {
+    (value << 1 | value >> 6) as i32
+}
+
+/// Returns the `count` bits of `bits` starting at bit `low`.
+fn field(bits: u64, low: u32, count: u32) -> u64 {
+    (bits >> low) & ((1 << count) - 1)
+}
+
+fn to_float(color: [i32; 3]) -> [f32; 4] {
+    let [r, g, b] = color.map(|c| c.clamp(0, 255) as f32 / 255.0);
+    [r, g, b, 1.0]
+}
+
+fn offset(color: [i32; 3], offset: i32) -> [i32; 3] {
+    color.map(|c| c + offset)
+}
+
+/// Decodes an ETC2 color block.
+///
+/// With `punch_through`, the differential bit of the block is the opaque bit instead, and blocks
+/// that aren't opaque have transparent texels.
+pub(super) fn decode_etc2_rgb(block: &[u8], values: &mut [[f32; 4]], punch_through: bool) {
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let differential = !punch_through && field(bits, 33, 1) == 1;
+    let transparent = punch_through && field(bits, 33, 1) == 0;
+    let index = |texel: usize| {
+        let bit = ((texel % 4) * 4 + texel / 4) as u32;
+        (field(bits, bit + 16, 1) << 1 | field(bits, bit, 1)) as usize
+    };
+
+    if !punch_through && !differential {
+        let base = [
+            [field(bits, 60, 4), field(bits, 52, 4), field(bits, 44, 4)].map(extend4),
+            [field(bits, 56, 4), field(bits, 48, 4), field(bits, 40, 4)].map(extend4),
+        ];
+        decode_subblocks(bits, values, base, false, index);
+        return;
+    }
+
+    let red = field(bits, 59, 5) as i32;
+    let green = field(bits, 51, 5) as i32;
+    let blue = field(bits, 43, 5) as i32;
+    let delta = |low| ((field(bits, low, 3) as i32) << 29) >> 29;
+    let (red2, green2, blue2) = (red + delta(56), green + delta(48), blue + delta(40));
+
+    if !(0..32).contains(&red2) {
+        // T mode
+        let color0 = [
+            field(bits, 59, 2) << 2 | field(bits, 56, 2),
+            field(bits, 52, 4),
+            field(bits, 48, 4),
+        ]
+        .map(extend4);
+        let color1 = [field(bits, 44, 4), field(bits, 40, 4), field(bits, 36, 4)].map(extend4);
+        let distance = DISTANCES[(field(bits, 34, 2) << 1 | field(bits, 32, 1)) as usize];
+        let paint = [
+            color0,
+            offset(color1, distance),
+            color1,
+            offset(color1, -distance),
+        ];
+        decode_paint(values, paint, transparent, index);
+    } else if !(0..32).contains(&green2) {
+        // H mode
+        let color0 = [
+            field(bits, 59, 4),
+            field(bits, 56, 3) << 1 | field(bits, 52, 1),
+            field(bits, 51, 1) << 3 | field(bits, 47, 3),
+        ];
+        let color1 = [field(bits, 43, 4), field(bits, 39, 4), field(bits, 35, 4)];
+        let ordering = (color0[0] << 8 | color0[1] << 4 | color0[2])
+            >= (color1[0] << 8 | color1[1] << 4 | color1[2]);
+        let distance = DISTANCES
+            [(field(bits, 34, 1) << 2 | field(bits, 32, 1) << 1 | u64::from(ordering)) as usize];
+        let (color0, color1) = (color0.map(extend4), color1.map(extend4));
+        let paint = [
+            offset(color0, distance),
+            offset(color0, -distance),
+            offset(color1, distance),
+            offset(color1, -distance),
+        ];
+        decode_paint(values, paint, transparent, index);
+    } else if !(0..32).contains(&blue2) {
+        // Planar mode
+        let origin = [
+            extend6(field(bits, 57, 6)),
+            extend7(field(bits, 56, 1) << 6 | field(bits, 49, 6)),
+            extend6(field(bits, 48, 1) << 5 | field(bits, 43, 2) << 3 | field(bits, 39, 3)),
+        ];
+        let horizontal = [
+            extend6(field(bits, 34, 5) << 1 | field(bits, 32, 1)),
+            extend7(field(bits, 25, 7)),
+            extend6(field(bits, 19, 6)),
+        ];
+        let vertical = [
+            extend6(field(bits, 13, 6)),
+            extend7(field(bits, 6, 7)),
+            extend6(field(bits, 0, 6)),
+        ];
+        for (i, value) in values.iter_mut().enumerate() {
+            let (x, y) = ((i % 4) as i32, (i / 4) as i32);
+            *value = to_float(core::array::from_fn(|c| {
+                (x * (horizontal[c] - origin[c])
+                    + y * (vertical[c] - origin[c])
+                    + 4 * origin[c]
+                    + 2)
+                    >> 2
+            }));
+        }
+    } else {
+        let base = [
+            [red, green, blue].map(extend5),
+            [red2, green2, blue2].map(extend5),
+        ];
+        decode_subblocks(bits, values, base, transparent, index);
+    }
+}
+
+/// Decodes the two subblocks of the individual and differential modes.
+fn decode_subblocks(
+    bits: u64,
+    values: &mut [[f32; 4]],
+    base: [[i32; 3]; 2],
+    transparent: bool,
+    index: impl Fn(usize) -> usize,
+) {
+    let flip = field(bits, 32, 1) == 1;
+    let tables = [field(bits, 37, 3), field(bits, 34, 3)];
+    for (i, value) in values.iter_mut().enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let subblock = usize::from(if flip { y >= 2 } else { x >= 2 });
+        let [small, large] = MODIFIERS[tables[subblock] as usize];
+        let modifier = match (index(i), transparent) {
+            (0, false) => small,
+            (0, true) => 0,
+            (1, _) => large,
+            (2, false) => -small,
+            (2, true) => {
+                *value = [0.0; 4];
+                continue;
+            }
+            _ => -large,
+        };
+        *value = to_float(offset(base[subblock], modifier));
+    }
+}
+
+/// Decodes the paint colors of the T and H modes.
+fn decode_paint(
+    values: &mut [[f32; 4]],
+    paint: [[i32; 3]; 4],
+    transparent: bool,
+    index: impl Fn(usize) -> usize,
+) {
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = match index(i) {
+            2 if transparent => [0.0; 4],
+            index => to_float(paint[index]),
+        };
+    }
+}
+
+pub(super) fn decode_etc2_rgba(block: &[u8], values: &mut [[f32; 4]]) {
+    decode_etc2_rgb(&block[8..], values, false);
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let base = field(bits, 56, 8) as i32;
+    let multiplier = field(bits, 52, 4) as i32;
+    let modifiers = EAC_MODIFIERS[field(bits, 48, 4) as usize];
+    for (i, value) in values.iter_mut().enumerate() {
+        let modifier = modifiers[eac_index(bits, i)];
+        value[3] = (base + modifier * multiplier).clamp(0, 255) as f32 / 255.0;
+    }
+}
+
+fn eac_index(bits: u64, texel: usize) -> usize {
+    let position = ((texel % 4) * 4 + texel / 4) as u32;
+    field(bits, 45 - 3 * position, 3) as usize
+}
+
+/// Decodes an 11-bit EAC block into `channel` of `values`.
+fn decode_eac_11(block: &[u8], values: &mut [[f32; 4]], channel: usize, signed: bool) {
+    let bits = u64::from_be_bytes(block[..8].try_into().unwrap());
+    let multiplier = field(bits, 52, 4) as i32;
+    let modifiers = EAC_MODIFIERS[field(bits, 48, 4) as usize];
+    let base = match signed {
+        true => i32::from((field(bits, 56, 8) as u8 as i8).max(-127)) * 8,
+        false => field(bits, 56, 8) as i32 * 8 + 4,
+    };
+    for (i, value) in values.iter_mut().enumerate() {
+        let modifier = modifiers[eac_index(bits, i)];
+        let offset = match multiplier {
+            0 => modifier,
+            _ => modifier * multiplier * 8,
+        };
+        value[channel] = match signed {
+            true => (base + offset).clamp(-1023, 1023) as f32 / 1023.0,
+            false => (base + offset).clamp(0, 2047) as f32 / 2047.0,
+        };
+    }
+}
+
+pub(super) fn decode_eac_r11(block: &[u8], values: &mut [[f32; 4]], signed: bool) {
+    values.fill([0.0, 0.0, 0.0, 1.0]);
+    decode_eac_11(block, values, 0, signed);
+}
+
+pub(super) fn decode_eac_rg11(block: &[u8], values: &mut [[f32; 4]], signed: bool) {
+    values.fill([0.0, 0.0, 0.0, 1.0]);
+    decode_eac_11(&block[..8], values, 0, signed);
+    decode_eac_11(&block[8..], values, 1, signed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes to 8 bit RGBA texels packed as `0xRRGGBBAA`.
+    fn decode_packed(decoder: impl Fn(&[u8], &mut [[f32; 4]]), block: &[u8]) -> [u32; 16] {
+        let mut values = [[f32::NAN; 4]; 16];
+        decoder(block, &mut values);
+        values.map(|texel| u32::from_be_bytes(texel.map(|c| (c * 255.0).round() as u8)))
+    }
+
+    #[test]
+    fn etc2_rgb() {
+        #[rustfmt::skip]
+        let cases: [(&str, [u8; 8], [u32; 16]); 6] = [
+            (
+                "individual",
+                [0xa3, 0x5c, 0xf0, 0x58, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xb35effff, 0xc772ffff, 0x12ab00ff, 0x006200ff, 0x8d38e2ff, 0xa14cf6ff, 0x9dff6aff, 0x54ed21ff,
+                    0xc772ffff, 0x8d38e2ff, 0x54ed21ff, 0x12ab00ff, 0xa14cf6ff, 0xb35effff, 0x006200ff, 0x9dff6aff,
+                ],
+            ),
+            (
+                "differential",
+                [0xa5, 0x2a, 0xfc, 0x1f, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xa72bffff, 0xad31ffff, 0xa327fdff, 0x9d21f7ff, 0x9d21f7ff, 0xa327fdff, 0xad31ffff, 0xa72bffff,
+                    0xfff0ffff, 0x000027ff, 0xbb68ffff, 0x5d0aafff, 0x5d0aafff, 0xbb68ffff, 0x000027ff, 0xfff0ffff,
+                ],
+            ),
+            (
+                "T",
+                [0xfa, 0x38, 0x49, 0xdb, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xee3388ff, 0x64b9fdff, 0x4499ddff, 0x2479bdff, 0x2479bdff, 0x4499ddff, 0x64b9fdff, 0xee3388ff,
+                    0x64b9fdff, 0x2479bdff, 0xee3388ff, 0x4499ddff, 0x4499ddff, 0xee3388ff, 0x2479bdff, 0x64b9fdff,
+                ],
+            ),
+            (
+                "H, first color smaller",
+                [0x95, 0xf3, 0x60, 0xce, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0x39d27dff, 0x0ba44fff, 0xe328b0ff, 0xb50082ff, 0xb50082ff, 0xe328b0ff, 0x0ba44fff, 0x39d27dff,
+                    0x0ba44fff, 0xb50082ff, 0x39d27dff, 0xe328b0ff, 0xe328b0ff, 0x39d27dff, 0xb50082ff, 0x0ba44fff,
+                ],
+            ),
+            (
+                "H, first color larger",
+                [0x6b, 0xf2, 0x1f, 0x0b, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xed8754ff, 0xcd6734ff, 0x43fe21ff, 0x23de01ff, 0x23de01ff, 0x43fe21ff, 0xcd6734ff, 0xed8754ff,
+                    0xcd6734ff, 0x23de01ff, 0xed8754ff, 0x43fe21ff, 0x43fe21ff, 0xed8754ff, 0x23de01ff, 0xcd6734ff,
+                ],
+            ),
+            (
+                "planar",
+                [0x54, 0x2b, 0x15, 0x8b, 0xfe, 0x87, 0xe0, 0x21],
+                [
+                    0xaa2acfff, 0x855facff, 0x5f9588ff, 0x3aca65ff, 0xbf20bdff, 0x9a5599ff, 0x748a76ff, 0x4fbf52ff,
+                    0xd515abff, 0xaf4a87ff, 0x8a8064ff, 0x64b540ff, 0xea0b98ff, 0xc44075ff, 0x9f7551ff, 0x79aa2eff,
+                ],
+            ),
+        ];
+        for (mode, block, expected) in cases {
+            let decoded = decode_packed(|b, v| decode_etc2_rgb(b, v, false), &block);
+            assert_eq!(decoded, expected, "{mode}");
+        }
+    }
+
+    #[test]
+    fn etc2_punch_through() {
+        #[rustfmt::skip]
+        let cases: [(&str, [u8; 8], [u32; 16]); 4] = [
+            (
+                "differential, opaque",
+                [0xa5, 0x2a, 0xfc, 0x1f, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xa72bffff, 0xad31ffff, 0xa327fdff, 0x9d21f7ff, 0x9d21f7ff, 0xa327fdff, 0xad31ffff, 0xa72bffff,
+                    0xfff0ffff, 0x000027ff, 0xbb68ffff, 0x5d0aafff, 0x5d0aafff, 0xbb68ffff, 0x000027ff, 0xfff0ffff,
+                ],
+            ),
+            (
+                "differential",
+                [0xa5, 0x2a, 0xfc, 0x1d, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xa529ffff, 0xad31ffff, 0x00000000, 0x9d21f7ff, 0x9d21f7ff, 0x00000000, 0xad31ffff, 0xa529ffff,
+                    0xfff0ffff, 0x000027ff, 0x8c39deff, 0x00000000, 0x00000000, 0x8c39deff, 0x000027ff, 0xfff0ffff,
+                ],
+            ),
+            (
+                "T",
+                [0xfa, 0x38, 0x49, 0xd9, 0x59, 0x6a, 0x9a, 0x56],
+                [
+                    0xee3388ff, 0x64b9fdff, 0x00000000, 0x2479bdff, 0x2479bdff, 0x00000000, 0x64b9fdff, 0xee3388ff,
+                    0x64b9fdff, 0x2479bdff, 0xee3388ff, 0x00000000, 0x00000000, 0xee3388ff, 0x2479bdff, 0x64b9fdff,
+                ],
+            ),
+            // Planar blocks are opaque even without the opaque bit.
+            (
+                "planar",
+                [0x54, 0x2b, 0x15, 0x89, 0xfe, 0x87, 0xe0, 0x21],
+                [
+                    0xaa2acfff, 0x855facff, 0x5f9588ff, 0x3aca65ff, 0xbf20bdff, 0x9a5599ff, 0x748a76ff, 0x4fbf52ff,
+                    0xd515abff, 0xaf4a87ff, 0x8a8064ff, 0x64b540ff, 0xea0b98ff, 0xc44075ff, 0x9f7551ff, 0x79aa2eff,
+                ],
+            ),
+        ];
+        for (mode, block, expected) in cases {
+            let decoded = decode_packed(|b, v| decode_etc2_rgb(b, v, true), &block);
+            assert_eq!(decoded, expected, "{mode}");
+        }
+    }
+
+    #[test]
+    fn etc2_rgba() {
+        let mut block = [0; 16];
+        block[..8].copy_from_slice(&[0xc8, 0x42, 0x13, 0xb3, 0x72, 0x5a, 0x97, 0xe0]);
+        block[8..].copy_from_slice(&[0xfa, 0x38, 0x49, 0xdb, 0x59, 0x6a, 0x9a, 0x56]);
+        let decoded = decode_packed(decode_etc2_rgba, &block);
+        let alpha = [
+            192, 180, 168, 148, 204, 216, 228, 248, 248, 228, 216, 204, 148, 168, 180, 192,
+        ];
+        let color = decode_packed(|b, v| decode_etc2_rgb(b, v, false), &block[8..]);
+        for i in 0..16 {
+            assert_eq!(decoded[i], color[i] & !0xff | alpha[i], "texel {i}");
+        }
+    }
+
+    /// An EAC block with `base`, `multiplier` and `table` that uses modifiers 0 to 7, then 7 to 0.
+    fn eac_block(base: u8, multiplier: u8, table: u8) -> [u8; 8] {
+        [
+            base,
+            multiplier << 4 | table,
+            0x13,
+            0xb3,
+            0x72,
+            0x5a,
+            0x97,
+            0xe0,
+        ]
+    }
+
+    fn decode_eac(decoder: impl Fn(&[u8], &mut [[f32; 4]]), block: &[u8]) -> [[f32; 4]; 16] {
+        let mut values = [[f32::NAN; 4]; 16];
+        decoder(block, &mut values);
+        values
+    }
+
+    #[test]
+    fn eac_r11_unorm() {
+        let cases = [
+            (
+                eac_block(0x80, 3, 5),
+                [
+                    956, 860, 812, 764, 1076, 1172, 1220, 1268, 1268, 1220, 1172, 1076, 764, 812,
+                    860, 956,
+                ],
+            ),
+            // A multiplier of zero adds the modifiers unscaled.
+            (
+                eac_block(0x02, 0, 13),
+                [
+                    19, 18, 17, 10, 20, 21, 22, 29, 29, 22, 21, 20, 10, 17, 18, 19,
+                ],
+            ),
+            (
+                eac_block(0xfe, 15, 0),
+                [
+                    1676, 1316, 956, 236, 2047, 2047, 2047, 2047, 2047, 2047, 2047, 2047, 236, 956,
+                    1316, 1676,
+                ],
+            ),
+        ];
+        for (block, expected) in cases {
+            let values = decode_eac(|b, v| decode_eac_r11(b, v, false), &block);
+            let expected = expected.map(|r| [r as f32 / 2047.0, 0.0, 0.0, 1.0]);
+            assert_eq!(values, expected, "{block:02x?}");
+        }
+    }
+
+    #[test]
+    fn eac_r11_snorm() {
+        let cases = [
+            // A base of -128 is clamped to -127.
+            (
+                eac_block(0x80, 2, 9),
+                [
+                    -1023, -1023, -1023, -1023, -1000, -952, -904, -872, -872, -904, -952, -1000,
+                    -1023, -1023, -1023, -1023,
+                ],
+            ),
+            (
+                eac_block(0x40, 0, 3),
+                [
+                    510, 508, 506, 499, 513, 515, 517, 524, 524, 517, 515, 513, 499, 506, 508, 510,
+                ],
+            ),
+            (
+                eac_block(0x7f, 11, 14),
+                [
+                    664, 488, 312, 224, 1023, 1023, 1023, 1023, 1023, 1023, 1023, 1023, 224, 312,
+                    488, 664,
+                ],
+            ),
+        ];
+        for (block, expected) in cases {
+            let values = decode_eac(|b, v| decode_eac_r11(b, v, true), &block);
+            let expected = expected.map(|r| [r as f32 / 1023.0, 0.0, 0.0, 1.0]);
+            assert_eq!(values, expected, "{block:02x?}");
+        }
+    }
+
+    #[test]
+    fn eac_rg11() {
+        let mut block = [0; 16];
+        block[..8].copy_from_slice(&eac_block(0x80, 3, 5));
+        block[8..].copy_from_slice(&eac_block(0xfe, 15, 0));
+        let values = decode_eac(|b, v| decode_eac_rg11(b, v, false), &block);
+        let red = decode_eac(|b, v| decode_eac_r11(b, v, false), &block[..8]);
+        let green = decode_eac(|b, v| decode_eac_r11(b, v, false), &block[8..]);
+        for i in 0..16 {
+            assert_eq!(values[i], [red[i][0], green[i][0], 0.0, 1.0], "texel {i}");
+        }
+
+        block[..8].copy_from_slice(&eac_block(0x40, 0, 3));
+        block[8..].copy_from_slice(&eac_block(0x80, 2, 9));
+        let values = decode_eac(|b, v| decode_eac_rg11(b, v, true), &block);
+        assert_eq!(values[0], [510.0 / 1023.0, -1.0, 0.0, 1.0]);
+        assert_eq!(values[7], [524.0 / 1023.0, -872.0 / 1023.0, 0.0, 1.0]);
+    }
+}
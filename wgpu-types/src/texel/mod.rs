@@ -0,0 +1,899 @@
+//! Encoding and decoding of texels on the CPU.
+//!
+//! [`TextureFormat::decode_texel_block`] converts the bytes of one [texel block] of any format to
+//! the values a shader would read from it, and [`TextureFormat::encode_texel`] does the reverse for
+//! uncompressed formats. [`TextureFormat::decode_texels`] and [`TextureFormat::encode_texels`] do
+//! the same for a whole image.
+//!
+//! This is useful for software implementations of texture operations, and for checking the
+//! contents of textures read back from the GPU.
+//!
+//! [texel block]: https://gpuweb.github.io/gpuweb/#texel-block
+
+use alloc::{vec, vec::Vec};
+
+use crate::{AstcChannel, TextureAspect, TextureFormat};
+
+mod astc;
+mod bc;
+mod etc;
+
+/// The value of a texel, as seen by a shader reading it.
+///
+/// Components missing from the format read as `0`, except for alpha, which reads as `1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Texel {
+    /// A texel of a float, normalized or depth format.
+    ///
+    /// Texels of sRGB formats are converted to linear values.
+    Float([f32; 4]),
+    /// A texel of an unsigned integer format or a stencil aspect.
+    ///
+    /// [`TextureFormat::R64Uint`] texels hold the low and high halves of the value in the red
+    /// and green components.
+    Uint([u32; 4]),
+    /// A texel of a signed integer format.
+    Sint([i32; 4]),
+}
+
+/// Error encoding or decoding texels.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum TexelError {
+    /// The format has no aspect with a defined memory layout for the requested aspect.
+    #[cfg_attr(
+        feature = "std",
+        error("Aspect {aspect:?} of format {format:?} doesn't have a defined memory layout")
+    )]
+    UnsupportedAspect {
+        /// The format of the texels.
+        format: TextureFormat,
+        /// The requested aspect.
+        aspect: TextureAspect,
+    },
+    /// Texels can only be encoded to uncompressed formats.
+    #[cfg_attr(
+        feature = "std",
+        error("Texels can't be encoded to compressed format {0:?}")
+    )]
+    CompressedFormat(TextureFormat),
+    /// The texel doesn't have the type of the texels of the format.
+    #[cfg_attr(
+        feature = "std",
+        error("Texel {texel:?} doesn't match the texel type of format {format:?}")
+    )]
+    TexelType {
+        /// The format the texel was encoded to.
+        format: TextureFormat,
+        /// The texel.
+        texel: Texel,
+    },
+    /// The data doesn't have the size required by the operation.
+    #[cfg_attr(
+        feature = "std",
+        error("Expected {expected} bytes of texel data, got {actual}")
+    )]
+    DataSize {
+        /// The number of bytes required.
+        expected: usize,
+        /// The number of bytes given.
+        actual: usize,
+    },
+}
+
+impl TextureFormat {
+    /// Decodes one [texel block](https://gpuweb.github.io/gpuweb/#texel-block) of the given
+    /// aspect of the format.
+    ///
+    /// `block` must hold exactly [`Self::block_copy_size`] bytes. The texels of the block are
+    /// written to the start of `texels` in row-major order, so `texels` must have room for the
+    /// number of texels given by [`Self::block_dimensions`].
+    ///
+    /// Compressed blocks that are invalid decode to the error color of their format: magenta for
+    /// ASTC LDR formats, NaN for ASTC HDR formats and transparent black for BC7.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `texels` is too short to hold a block.
+    pub fn decode_texel_block(
+        &self,
+        aspect: TextureAspect,
+        block: &[u8],
+        texels: &mut [Texel],
+    ) -> Result<(), TexelError> {
+        let format = self.texel_format(aspect)?;
+        let block_size = format.block_copy_size(None).unwrap() as usize;
+        if block.len() != block_size {
+            return Err(TexelError::DataSize {
+                expected: block_size,
+                actual: block.len(),
+            });
+        }
+        let (block_width, block_height) = format.block_dimensions();
+        let texels = &mut texels[..(block_width * block_height) as usize];
+
+        if !format.is_compressed() {
+            texels[0] = decode_uncompressed(format, block);
+            return Ok(());
+        }
+
+        let mut values = [[0.0; 4]; 144];
+        let values = &mut values[..texels.len()];
+        let srgb = decode_compressed(format, block, values);
+        for (texel, mut value) in texels.iter_mut().zip(values.iter().copied()) {
+            if srgb {
+                for component in &mut value[..3] {
+                    *component = srgb_to_linear(*component);
+                }
+            }
+            *texel = Texel::Float(value);
+        }
+        Ok(())
+    }
+
+    /// Decodes the texels of an image of the given aspect of the format.
+    ///
+    /// `data` holds rows of texel blocks, with each row starting `bytes_per_row` bytes after the
+    /// previous one. The returned texels are in row-major order, with `width * height` texels.
+    pub fn decode_texels(
+        &self,
+        aspect: TextureAspect,
+        data: &[u8],
+        bytes_per_row: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<Texel>, TexelError> {
+        let format = self.texel_format(aspect)?;
+        let block_size = format.block_copy_size(None).unwrap() as usize;
+        let (block_width, block_height) = format.block_dimensions();
+        let blocks_x = width.div_ceil(block_width) as usize;
+        let blocks_y = height.div_ceil(block_height) as usize;
+        let row_size = blocks_x * block_size;
+        let bytes_per_row = bytes_per_row as usize;
+        if blocks_y > 1 && bytes_per_row < row_size {
+            return Err(TexelError::DataSize {
+                expected: row_size,
+                actual: bytes_per_row,
+            });
+        }
+        let expected = match blocks_y {
+            0 => 0,
+            _ => (blocks_y - 1) * bytes_per_row + row_size,
+        };
+        if data.len() < expected {
+            return Err(TexelError::DataSize {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        let (width, height) = (width as usize, height as usize);
+        let (block_width, block_height) = (block_width as usize, block_height as usize);
+        let mut texels = vec![Texel::Float([0.0; 4]); width * height];
+        let mut block_texels = vec![Texel::Float([0.0; 4]); block_width * block_height];
+        for block_y in 0..blocks_y {
+            for block_x in 0..blocks_x {
+                let offset = block_y * bytes_per_row + block_x * block_size;
+                format.decode_texel_block(
+                    TextureAspect::All,
+                    &data[offset..offset + block_size],
+                    &mut block_texels,
+                )?;
+                for y in 0..block_height.min(height - block_y * block_height) {
+                    let row = (block_y * block_height + y) * width + block_x * block_width;
+                    let count = block_width.min(width - block_x * block_width);
+                    texels[row..row + count]
+                        .copy_from_slice(&block_texels[y * block_width..][..count]);
+                }
+            }
+        }
+        Ok(texels)
+    }
+
+    /// Encodes one texel of the given aspect of the format.
+    ///
+    /// `out` must hold exactly [`Self::block_copy_size`] bytes. Values outside of the range of
+    /// the format are clamped to it.
+    ///
+    /// Only uncompressed formats can be encoded; compressed formats return
+    /// [`TexelError::CompressedFormat`].
+    pub fn encode_texel(
+        &self,
+        aspect: TextureAspect,
+        texel: Texel,
+        out: &mut [u8],
+    ) -> Result<(), TexelError> {
+        let format = self.texel_format(aspect)?;
+        if format.is_compressed() {
+            return Err(TexelError::CompressedFormat(format));
+        }
+        let block_size = format.block_copy_size(None).unwrap() as usize;
+        if out.len() != block_size {
+            return Err(TexelError::DataSize {
+                expected: block_size,
+                actual: out.len(),
+            });
+        }
+        encode_uncompressed(format, texel, out)
+    }
+
+    /// Encodes texels of the given aspect of the format into tightly packed bytes.
+    ///
+    /// See [`Self::encode_texel`].
+    pub fn encode_texels(
+        &self,
+        aspect: TextureAspect,
+        texels: &[Texel],
+    ) -> Result<Vec<u8>, TexelError> {
+        let format = self.texel_format(aspect)?;
+        if format.is_compressed() {
+            return Err(TexelError::CompressedFormat(format));
+        }
+        let block_size = format.block_copy_size(None).unwrap() as usize;
+        let mut data = vec![0; texels.len() * block_size];
+        for (&texel, out) in texels.iter().zip(data.chunks_exact_mut(block_size)) {
+            encode_uncompressed(format, texel, out)?;
+        }
+        Ok(data)
+    }
+
+    /// Resolves `aspect` to a format with a defined memory layout.
+    fn texel_format(&self, aspect: TextureAspect) -> Result<Self, TexelError> {
+        match self.aspect_specific_format(aspect) {
+            Some(format)
+                if format != Self::Depth24Plus && !format.is_combined_depth_stencil_format() =>
+            {
+                Ok(format)
+            }
+            _ => Err(TexelError::UnsupportedAspect {
+                format: *self,
+                aspect,
+            }),
+        }
+    }
+}
+
+/// Reads little-endian bit fields from a 128-bit block, starting at the least significant bit.
+struct BitReader {
+    bits: u128,
+    position: u32,
+}
+
+impl BitReader {
+    fn new(block: &[u8]) -> Self {
+        Self::from_bits(u128::from_le_bytes(block.try_into().unwrap()))
+    }
+
+    fn from_bits(bits: u128) -> Self {
+        Self { bits, position: 0 }
+    }
+
+    fn read(&mut self, count: u32) -> u32 {
+        if count == 0 || self.position >= 128 {
+            self.position += count;
+            return 0;
+        }
+        let value = (self.bits >> self.position) as u32 & (u32::MAX >> (32 - count));
+        self.position += count;
+        value
+    }
+}
+
+/// The storage of one component of an uncompressed format.
+#[derive(Clone, Copy)]
+enum Component {
+    Unorm8,
+    Snorm8,
+    Uint8,
+    Sint8,
+    Unorm16,
+    Snorm16,
+    Uint16,
+    Sint16,
+    Float16,
+    Uint32,
+    Sint32,
+    Float32,
+}
+
+impl Component {
+    fn size(self) -> usize {
+        match self {
+            Self::Unorm8 | Self::Snorm8 | Self::Uint8 | Self::Sint8 => 1,
+            Self::Unorm16 | Self::Snorm16 | Self::Uint16 | Self::Sint16 | Self::Float16 => 2,
+            Self::Uint32 | Self::Sint32 | Self::Float32 => 4,
+        }
+    }
+}
+
+/// How the texels of an uncompressed format are stored.
+enum Layout {
+    /// Components of the same type, in RGBA order.
+    Plain(Component, usize),
+    /// [`TextureFormat::Rgba8UnormSrgb`].
+    Srgb8,
+    /// [`TextureFormat::Bgra8Unorm`] and [`TextureFormat::Bgra8UnormSrgb`].
+    Bgra8 {
+        srgb: bool,
+    },
+    Rgb9e5,
+    Rgb10a2 {
+        uint: bool,
+    },
+    Rg11b10,
+    R64,
+}
+
+fn layout(format: TextureFormat) -> Layout {
+    use Component as C;
+    use TextureFormat as Tf;
+
+    let (component, count) = match format {
+        Tf::Rgba8UnormSrgb => return Layout::Srgb8,
+        Tf::Bgra8Unorm => return Layout::Bgra8 { srgb: false },
+        Tf::Bgra8UnormSrgb => return Layout::Bgra8 { srgb: true },
+        Tf::Rgb9e5Ufloat => return Layout::Rgb9e5,
+        Tf::Rgb10a2Unorm => return Layout::Rgb10a2 { uint: false },
+        Tf::Rgb10a2Uint => return Layout::Rgb10a2 { uint: true },
+        Tf::Rg11b10Ufloat => return Layout::Rg11b10,
+        Tf::R64Uint => return Layout::R64,
+
+        Tf::R8Unorm => (C::Unorm8, 1),
+        Tf::R8Snorm => (C::Snorm8, 1),
+        Tf::R8Uint | Tf::Stencil8 => (C::Uint8, 1),
+        Tf::R8Sint => (C::Sint8, 1),
+        Tf::R16Unorm | Tf::Depth16Unorm => (C::Unorm16, 1),
+        Tf::R16Snorm => (C::Snorm16, 1),
+        Tf::R16Uint => (C::Uint16, 1),
+        Tf::R16Sint => (C::Sint16, 1),
+        Tf::R16Float => (C::Float16, 1),
+        Tf::R32Uint => (C::Uint32, 1),
+        Tf::R32Sint => (C::Sint32, 1),
+        Tf::R32Float | Tf::Depth32Float => (C::Float32, 1),
+        Tf::Rg8Unorm => (C::Unorm8, 2),
+        Tf::Rg8Snorm => (C::Snorm8, 2),
+        Tf::Rg8Uint => (C::Uint8, 2),
+        Tf::Rg8Sint => (C::Sint8, 2),
+        Tf::Rg16Unorm => (C::Unorm16, 2),
+        Tf::Rg16Snorm => (C::Snorm16, 2),
+        Tf::Rg16Uint => (C::Uint16, 2),
+        Tf::Rg16Sint => (C::Sint16, 2),
+        Tf::Rg16Float => (C::Float16, 2),
+        Tf::Rg32Uint => (C::Uint32, 2),
+        Tf::Rg32Sint => (C::Sint32, 2),
+        Tf::Rg32Float => (C::Float32, 2),
+        Tf::Rgba8Unorm => (C::Unorm8, 4),
+        Tf::Rgba8Snorm => (C::Snorm8, 4),
+        Tf::Rgba8Uint => (C::Uint8, 4),
+        Tf::Rgba8Sint => (C::Sint8, 4),
+        Tf::Rgba16Unorm => (C::Unorm16, 4),
+        Tf::Rgba16Snorm => (C::Snorm16, 4),
+        Tf::Rgba16Uint => (C::Uint16, 4),
+        Tf::Rgba16Sint => (C::Sint16, 4),
+        Tf::Rgba16Float => (C::Float16, 4),
+        Tf::Rgba32Uint => (C::Uint32, 4),
+        Tf::Rgba32Sint => (C::Sint32, 4),
+        Tf::Rgba32Float => (C::Float32, 4),
+
+        _ => unreachable!("{format:?} doesn't have an uncompressed memory layout"),
+    };
+    Layout::Plain(component, count)
+}
+
+fn read_u16(data: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes([data[index * 2], data[index * 2 + 1]])
+}
+
+fn read_u32(data: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(data[index * 4..index * 4 + 4].try_into().unwrap())
+}
+
+fn decode_uncompressed(format: TextureFormat, data: &[u8]) -> Texel {
+    match layout(format) {
+        Layout::Plain(component, count) => {
+            let mut float = [0.0, 0.0, 0.0, 1.0];
+            let mut uint = [0, 0, 0, 1];
+            let mut sint = [0, 0, 0, 1];
+            for i in 0..count {
+                match component {
+                    Component::Unorm8 => float[i] = unorm_to_float(data[i].into(), 8),
+                    Component::Snorm8 => float[i] = snorm_to_float((data[i] as i8).into(), 8),
+                    Component::Uint8 => uint[i] = data[i].into(),
+                    Component::Sint8 => sint[i] = (data[i] as i8).into(),
+                    Component::Unorm16 => float[i] = unorm_to_float(read_u16(data, i).into(), 16),
+                    Component::Snorm16 => {
+                        float[i] = snorm_to_float((read_u16(data, i) as i16).into(), 16)
+                    }
+                    Component::Uint16 => uint[i] = read_u16(data, i).into(),
+                    Component::Sint16 => sint[i] = (read_u16(data, i) as i16).into(),
+                    Component::Float16 => float[i] = f16_to_f32(read_u16(data, i)),
+                    Component::Uint32 => uint[i] = read_u32(data, i),
+                    Component::Sint32 => sint[i] = read_u32(data, i) as i32,
+                    Component::Float32 => float[i] = f32::from_bits(read_u32(data, i)),
+                }
+            }
+            match component {
+                Component::Uint8 | Component::Uint16 | Component::Uint32 => Texel::Uint(uint),
+                Component::Sint8 | Component::Sint16 | Component::Sint32 => Texel::Sint(sint),
+                _ => Texel::Float(float),
+            }
+        }
+        Layout::Srgb8 => Texel::Float(core::array::from_fn(|i| match i {
+            3 => unorm_to_float(data[3].into(), 8),
+            _ => srgb_to_linear(unorm_to_float(data[i].into(), 8)),
+        })),
+        Layout::Bgra8 { srgb } => Texel::Float(core::array::from_fn(|i| {
+            let value = unorm_to_float(data[[2, 1, 0, 3][i]].into(), 8);
+            match srgb && i < 3 {
+                true => srgb_to_linear(value),
+                false => value,
+            }
+        })),
+        Layout::Rgb9e5 => {
+            let packed = read_u32(data, 0);
+            let scale = f32::powi(2.0, (packed >> 27) as i32 - 15 - 9);
+            Texel::Float([
+                (packed & 0x1ff) as f32 * scale,
+                ((packed >> 9) & 0x1ff) as f32 * scale,
+                ((packed >> 18) & 0x1ff) as f32 * scale,
+                1.0,
+            ])
+        }
+        Layout::Rgb10a2 { uint } => {
+            let packed = read_u32(data, 0);
+            let values = [
+                packed & 0x3ff,
+                (packed >> 10) & 0x3ff,
+                (packed >> 20) & 0x3ff,
+                packed >> 30,
+            ];
+            match uint {
+                true => Texel::Uint(values),
+                false => Texel::Float([
+                    unorm_to_float(values[0], 10),
+                    unorm_to_float(values[1], 10),
+                    unorm_to_float(values[2], 10),
+                    unorm_to_float(values[3], 2),
+                ]),
+            }
+        }
+        Layout::Rg11b10 => {
+            let packed = read_u32(data, 0);
+            Texel::Float([
+                small_float_to_f32(packed & 0x7ff, 6),
+                small_float_to_f32((packed >> 11) & 0x7ff, 6),
+                small_float_to_f32(packed >> 22, 5),
+                1.0,
+            ])
+        }
+        Layout::R64 => Texel::Uint([read_u32(data, 0), read_u32(data, 1), 0, 1]),
+    }
+}
+
+fn encode_uncompressed(
+    format: TextureFormat,
+    texel: Texel,
+    out: &mut [u8],
+) -> Result<(), TexelError> {
+    let mismatch = || TexelError::TexelType { format, texel };
+    match (layout(format), texel) {
+        (Layout::Plain(component, count), texel) => {
+            let size = component.size();
+            for (i, out) in out.chunks_exact_mut(size).take(count).enumerate() {
+                match (component, texel) {
+                    (Component::Unorm8, Texel::Float(v)) => out[0] = float_to_unorm(v[i], 8) as u8,
+                    (Component::Snorm8, Texel::Float(v)) => out[0] = float_to_snorm(v[i], 8) as u8,
+                    (Component::Uint8, Texel::Uint(v)) => out[0] = v[i].min(0xff) as u8,
+                    (Component::Sint8, Texel::Sint(v)) => out[0] = v[i].clamp(-0x80, 0x7f) as u8,
+                    (Component::Unorm16, Texel::Float(v)) => {
+                        out.copy_from_slice(&(float_to_unorm(v[i], 16) as u16).to_le_bytes())
+                    }
+                    (Component::Snorm16, Texel::Float(v)) => {
+                        out.copy_from_slice(&(float_to_snorm(v[i], 16) as i16).to_le_bytes())
+                    }
+                    (Component::Uint16, Texel::Uint(v)) => {
+                        out.copy_from_slice(&(v[i].min(0xffff) as u16).to_le_bytes())
+                    }
+                    (Component::Sint16, Texel::Sint(v)) => {
+                        out.copy_from_slice(&(v[i].clamp(-0x8000, 0x7fff) as i16).to_le_bytes())
+                    }
+                    (Component::Float16, Texel::Float(v)) => {
+                        out.copy_from_slice(&f32_to_f16(v[i]).to_le_bytes())
+                    }
+                    (Component::Uint32, Texel::Uint(v)) => out.copy_from_slice(&v[i].to_le_bytes()),
+                    (Component::Sint32, Texel::Sint(v)) => out.copy_from_slice(&v[i].to_le_bytes()),
+                    (Component::Float32, Texel::Float(v)) => {
+                        out.copy_from_slice(&v[i].to_le_bytes())
+                    }
+                    _ => return Err(mismatch()),
+                }
+            }
+        }
+        (Layout::Srgb8, Texel::Float(v)) => {
+            for i in 0..4 {
+                let value = match i {
+                    3 => v[3],
+                    _ => linear_to_srgb(v[i]),
+                };
+                out[i] = float_to_unorm(value, 8) as u8;
+            }
+        }
+        (Layout::Bgra8 { srgb }, Texel::Float(v)) => {
+            for i in 0..4 {
+                let value = match srgb && i < 3 {
+                    true => linear_to_srgb(v[i]),
+                    false => v[i],
+                };
+                out[[2, 1, 0, 3][i]] = float_to_unorm(value, 8) as u8;
+            }
+        }
+        (Layout::Rgb9e5, Texel::Float(v)) => {
+            out.copy_from_slice(&encode_rgb9e5([v[0], v[1], v[2]]).to_le_bytes())
+        }
+        (Layout::Rgb10a2 { uint }, texel) => {
+            let values = match (uint, texel) {
+                (true, Texel::Uint(v)) => [
+                    v[0].min(0x3ff),
+                    v[1].min(0x3ff),
+                    v[2].min(0x3ff),
+                    v[3].min(0x3),
+                ],
+                (false, Texel::Float(v)) => [
+                    float_to_unorm(v[0], 10),
+                    float_to_unorm(v[1], 10),
+                    float_to_unorm(v[2], 10),
+                    float_to_unorm(v[3], 2),
+                ],
+                _ => return Err(mismatch()),
+            };
+            let packed = values[0] | values[1] << 10 | values[2] << 20 | values[3] << 30;
+            out.copy_from_slice(&packed.to_le_bytes());
+        }
+        (Layout::Rg11b10, Texel::Float(v)) => {
+            let packed = f32_to_small_float(v[0], 6)
+                | f32_to_small_float(v[1], 6) << 11
+                | f32_to_small_float(v[2], 5) << 22;
+            out.copy_from_slice(&packed.to_le_bytes());
+        }
+        (Layout::R64, Texel::Uint(v)) => {
+            out[..4].copy_from_slice(&v[0].to_le_bytes());
+            out[4..].copy_from_slice(&v[1].to_le_bytes());
+        }
+        _ => return Err(mismatch()),
+    }
+    Ok(())
+}
+
+/// Decodes a compressed block into `values`, returning whether the values are sRGB encoded.
+fn decode_compressed(format: TextureFormat, block: &[u8], values: &mut [[f32; 4]]) -> bool {
+    use TextureFormat as Tf;
+
+    match format {
+        Tf::Bc1RgbaUnorm | Tf::Bc1RgbaUnormSrgb => bc::decode_bc1(block, values),
+        Tf::Bc2RgbaUnorm | Tf::Bc2RgbaUnormSrgb => bc::decode_bc2(block, values),
+        Tf::Bc3RgbaUnorm | Tf::Bc3RgbaUnormSrgb => bc::decode_bc3(block, values),
+        Tf::Bc4RUnorm | Tf::Bc4RSnorm => bc::decode_bc4(block, values, format == Tf::Bc4RSnorm),
+        Tf::Bc5RgUnorm | Tf::Bc5RgSnorm => bc::decode_bc5(block, values, format == Tf::Bc5RgSnorm),
+        Tf::Bc6hRgbUfloat | Tf::Bc6hRgbFloat => {
+            bc::decode_bc6h(block, values, format == Tf::Bc6hRgbFloat)
+        }
+        Tf::Bc7RgbaUnorm | Tf::Bc7RgbaUnormSrgb => bc::decode_bc7(block, values),
+        Tf::Etc2Rgb8Unorm | Tf::Etc2Rgb8UnormSrgb => etc::decode_etc2_rgb(block, values, false),
+        Tf::Etc2Rgb8A1Unorm | Tf::Etc2Rgb8A1UnormSrgb => etc::decode_etc2_rgb(block, values, true),
+        Tf::Etc2Rgba8Unorm | Tf::Etc2Rgba8UnormSrgb => etc::decode_etc2_rgba(block, values),
+        Tf::EacR11Unorm | Tf::EacR11Snorm => {
+            etc::decode_eac_r11(block, values, format == Tf::EacR11Snorm)
+        }
+        Tf::EacRg11Unorm | Tf::EacRg11Snorm => {
+            etc::decode_eac_rg11(block, values, format == Tf::EacRg11Snorm)
+        }
+        Tf::Astc { channel, .. } => {
+            let (width, height) = format.block_dimensions();
+            astc::decode(block, values, width as usize, height as usize, channel);
+            return channel == AstcChannel::UnormSrgb;
+        }
+        _ => unreachable!("{format:?} isn't a compressed format"),
+    }
+    format.is_srgb()
+}
+
+fn unorm_to_float(value: u32, bits: u32) -> f32 {
+    value as f32 / ((1u32 << bits) - 1) as f32
+}
+
+fn snorm_to_float(value: i32, bits: u32) -> f32 {
+    (value as f32 / ((1i32 << (bits - 1)) - 1) as f32).max(-1.0)
+}
+
+fn float_to_unorm(value: f32, bits: u32) -> u32 {
+    let max = ((1u32 << bits) - 1) as f32;
+    (value.clamp(0.0, 1.0) * max).round() as u32
+}
+
+fn float_to_snorm(value: f32, bits: u32) -> i32 {
+    let max = ((1i32 << (bits - 1)) - 1) as f32;
+    (value.clamp(-1.0, 1.0) * max).round() as i32
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = u32::from(half & 0x8000) << 16;
+    let exponent = u32::from(half >> 10) & 0x1f;
+    let mantissa = u32::from(half) & 0x3ff;
+    let bits = match exponent {
+        0 => {
+            let value = mantissa as f32 * f32::powi(2.0, -24);
+            return if sign != 0 { -value } else { value };
+        }
+        0x1f => sign | 0x7f80_0000 | mantissa << 13,
+        _ => sign | (exponent + 112) << 23 | mantissa << 13,
+    };
+    f32::from_bits(bits)
+}
+
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = (bits >> 16) & 0x8000;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return (sign | 0x7c00 | nan) as u16;
+    }
+
+    // Shift the mantissa into place, rounding to nearest even. A carry out of the mantissa
+    // correctly increments the exponent, up to infinity.
+    let round = |value: u32, shift: u32| {
+        let truncated = value >> shift;
+        let remainder = value & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        match remainder > half || (remainder == half && truncated & 1 == 1) {
+            true => truncated + 1,
+            false => truncated,
+        }
+    };
+    let half_exponent = exponent - 127 + 15;
+    let magnitude = if half_exponent >= 0x1f {
+        0x7c00
+    } else if half_exponent > 0 {
+        round((half_exponent as u32) << 23 | mantissa, 13).min(0x7c00)
+    } else if half_exponent >= -10 {
+        round(mantissa | 0x80_0000, (14 - half_exponent) as u32)
+    } else {
+        0
+    };
+    (sign | magnitude) as u16
+}
+
+/// Decodes the unsigned floats of [`TextureFormat::Rg11b10Ufloat`].
+fn small_float_to_f32(value: u32, mantissa_bits: u32) -> f32 {
+    // These share the exponent bias of half floats, so widen the mantissa into one.
+    f16_to_f32((value << (10 - mantissa_bits)) as u16)
+}
+
+fn f32_to_small_float(value: f32, mantissa_bits: u32) -> u32 {
+    let shift = 10 - mantissa_bits;
+    if value.is_nan() {
+        return 0x1f << mantissa_bits | 1;
+    }
+    let half = u32::from(f32_to_f16(value.max(0.0))) & 0x7fff;
+    if half >= 0x7c00 {
+        return half >> shift;
+    }
+    let truncated = half >> shift;
+    let remainder = half & ((1 << shift) - 1);
+    let midpoint = 1 << (shift - 1);
+    match remainder > midpoint || (remainder == midpoint && truncated & 1 == 1) {
+        true => truncated + 1,
+        false => truncated,
+    }
+}
+
+/// Encodes [`TextureFormat::Rgb9e5Ufloat`], as described by the
+/// [Vulkan specification](https://registry.khronos.org/vulkan/specs/latest/html/vkspec.html#textures-sharedexp).
+fn encode_rgb9e5(rgb: [f32; 3]) -> u32 {
+    const MANTISSA_BITS: i32 = 9;
+    const BIAS: i32 = 15;
+    const MAX: f32 = 511.0 / 512.0 * 65536.0;
+
+    let rgb = rgb.map(|value| match value.is_nan() {
+        true => 0.0,
+        false => value.clamp(0.0, MAX),
+    });
+    let max = rgb[0].max(rgb[1]).max(rgb[2]);
+    let mut exponent = (-BIAS - 1).max(max.log2().floor() as i32) + 1 + BIAS;
+    let scale = |exponent: i32| f32::powi(2.0, exponent - BIAS - MANTISSA_BITS);
+    if (max / scale(exponent) + 0.5).floor() as i32 == 1 << MANTISSA_BITS {
+        exponent += 1;
+    }
+    let [r, g, b] = rgb.map(|value| (value / scale(exponent) + 0.5).floor() as u32);
+    r | g << 9 | b << 18 | (exponent as u32) << 27
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(format: TextureFormat, aspect: TextureAspect, texel: Texel) -> Texel {
+        let mut data = vec![0; format.block_copy_size(Some(aspect)).unwrap() as usize];
+        format.encode_texel(aspect, texel, &mut data).unwrap();
+        let mut decoded = [Texel::Float([0.0; 4])];
+        format
+            .decode_texel_block(aspect, &data, &mut decoded)
+            .unwrap();
+        decoded[0]
+    }
+
+    #[test]
+    fn uncompressed_round_trip() {
+        use TextureFormat as Tf;
+
+        let float = Texel::Float([0.25, -0.5, 1.0, 0.75]);
+        let uint = Texel::Uint([1, 2, 3, 1]);
+        let sint = Texel::Sint([-1, 2, -3, 1]);
+        let cases = [
+            (
+                Tf::R8Unorm,
+                float,
+                Texel::Float([64.0 / 255.0, 0.0, 0.0, 1.0]),
+            ),
+            (
+                Tf::Rg8Snorm,
+                float,
+                Texel::Float([32.0 / 127.0, -64.0 / 127.0, 0.0, 1.0]),
+            ),
+            (Tf::Rgba8Uint, uint, uint),
+            (Tf::Rgba16Sint, sint, sint),
+            (Tf::Rgba16Float, float, float),
+            (Tf::Rg32Float, float, Texel::Float([0.25, -0.5, 0.0, 1.0])),
+            (Tf::Rgba32Sint, sint, sint),
+            (Tf::R64Uint, uint, Texel::Uint([1, 2, 0, 1])),
+            (
+                Tf::Bgra8Unorm,
+                float,
+                Texel::Float([64.0 / 255.0, 0.0, 1.0, 191.0 / 255.0]),
+            ),
+            (
+                Tf::Rgb10a2Unorm,
+                float,
+                Texel::Float([256.0 / 1023.0, 0.0, 1.0, 2.0 / 3.0]),
+            ),
+            (
+                Tf::Rg11b10Ufloat,
+                float,
+                Texel::Float([0.25, 0.0, 1.0, 1.0]),
+            ),
+            (Tf::Rgb9e5Ufloat, float, Texel::Float([0.25, 0.0, 1.0, 1.0])),
+        ];
+        for (format, texel, expected) in cases {
+            let decoded = round_trip(format, TextureAspect::All, texel);
+            assert_eq!(decoded, expected, "{format:?}");
+        }
+
+        let Texel::Float(srgb) = round_trip(Tf::Rgba8UnormSrgb, TextureAspect::All, float) else {
+            unreachable!()
+        };
+        assert!((srgb[0] - 0.25).abs() < 0.005, "{srgb:?}");
+        assert_eq!(srgb[3], 191.0 / 255.0);
+    }
+
+    #[test]
+    fn aspects() {
+        use TextureFormat as Tf;
+
+        let depth = Texel::Float([0.5, 0.0, 0.0, 1.0]);
+        let stencil = Texel::Uint([7, 0, 0, 1]);
+        assert_eq!(
+            round_trip(Tf::Depth32FloatStencil8, TextureAspect::DepthOnly, depth),
+            depth
+        );
+        assert_eq!(
+            round_trip(Tf::Depth24PlusStencil8, TextureAspect::StencilOnly, stencil),
+            stencil
+        );
+        assert_eq!(
+            round_trip(Tf::NV12, TextureAspect::Plane1, Texel::Float([1.0; 4])),
+            Texel::Float([1.0, 1.0, 0.0, 1.0])
+        );
+
+        for (format, aspect) in [
+            (Tf::Depth24Plus, TextureAspect::DepthOnly),
+            (Tf::Depth24PlusStencil8, TextureAspect::DepthOnly),
+            (Tf::Depth32FloatStencil8, TextureAspect::All),
+            (Tf::NV12, TextureAspect::All),
+        ] {
+            assert_eq!(
+                format.encode_texel(aspect, depth, &mut [0; 4]),
+                Err(TexelError::UnsupportedAspect { format, aspect })
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        use TextureFormat as Tf;
+
+        assert_eq!(
+            Tf::Rgba8Uint.encode_texel(TextureAspect::All, Texel::Float([0.0; 4]), &mut [0; 4]),
+            Err(TexelError::TexelType {
+                format: Tf::Rgba8Uint,
+                texel: Texel::Float([0.0; 4])
+            })
+        );
+        assert_eq!(
+            Tf::Bc1RgbaUnorm.encode_texels(TextureAspect::All, &[]),
+            Err(TexelError::CompressedFormat(Tf::Bc1RgbaUnorm))
+        );
+        assert_eq!(
+            Tf::R32Float.decode_texels(TextureAspect::All, &[0; 12], 8, 2, 2),
+            Err(TexelError::DataSize {
+                expected: 16,
+                actual: 12
+            })
+        );
+    }
+
+    #[test]
+    fn float_conversions() {
+        for value in [
+            0.0,
+            1.0,
+            -2.5,
+            65504.0,
+            6.1035156e-5,
+            5.9604645e-8,
+            0.33325195,
+        ] {
+            assert_eq!(f16_to_f32(f32_to_f16(value)), value);
+        }
+        assert_eq!(f32_to_f16(1e6), 0x7c00);
+        assert_eq!(f32_to_f16(1e-9), 0);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+
+        assert_eq!(f32_to_small_float(-1.0, 6), 0);
+        assert_eq!(small_float_to_f32(f32_to_small_float(3.0, 5), 5), 3.0);
+        assert_eq!(
+            decode_rgb9e5(encode_rgb9e5([1.0, 0.5, 0.125])),
+            [1.0, 0.5, 0.125]
+        );
+        assert_eq!(
+            decode_rgb9e5(encode_rgb9e5([1e6, 0.0, -1.0])),
+            [65408.0, 0.0, 0.0]
+        );
+    }
+
+    fn decode_rgb9e5(packed: u32) -> [f32; 3] {
+        let Texel::Float(rgb) =
+            decode_uncompressed(TextureFormat::Rgb9e5Ufloat, &packed.to_le_bytes())
+        else {
+            unreachable!()
+        };
+        [rgb[0], rgb[1], rgb[2]]
+    }
+
+    #[test]
+    fn decode_image() {
+        // A 4x4 BC1 block whose first color is red and whose indices select it everywhere,
+        // decoded into a 3x2 image.
+        let block = [0x00, 0xf8, 0x00, 0x00, 0, 0, 0, 0];
+        let texels = TextureFormat::Bc1RgbaUnorm
+            .decode_texels(TextureAspect::All, &block, 8, 3, 2)
+            .unwrap();
+        assert_eq!(texels, vec![Texel::Float([1.0, 0.0, 0.0, 1.0]); 6]);
+    }
+}
@@ -0,0 +1,253 @@
+//! Parameters of AV1 frames.
+//!
+//! Fields are named after the syntax elements and variables of the AV1 specification, in
+//! snake case.
+
+// Fields named after syntax elements are documented by the specification.
+#![allow(missing_docs)]
+
+/// The color configuration of a sequence header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ColorConfig {
+    /// `BitDepth`.
+    pub bit_depth: u8,
+    pub mono_chrome: bool,
+    pub color_description_present_flag: bool,
+    pub color_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+    pub color_range: bool,
+    pub subsampling_x: bool,
+    pub subsampling_y: bool,
+    pub chroma_sample_position: u8,
+    pub separate_uv_delta_q: bool,
+}
+
+/// The timing information of a sequence header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TimingInfo {
+    pub num_units_in_display_tick: u32,
+    pub time_scale: u32,
+    pub equal_picture_interval: bool,
+    pub num_ticks_per_picture_minus_1: u32,
+}
+
+/// A sequence header.
+///
+/// Operating points aren't needed for decoding, and are not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequenceHeader {
+    pub seq_profile: u8,
+    pub still_picture: bool,
+    pub reduced_still_picture_header: bool,
+    /// The timing information, if `timing_info_present_flag` is set.
+    pub timing_info: Option<TimingInfo>,
+    pub initial_display_delay_present_flag: bool,
+    pub frame_width_bits_minus_1: u8,
+    pub frame_height_bits_minus_1: u8,
+    pub max_frame_width_minus_1: u16,
+    pub max_frame_height_minus_1: u16,
+    pub frame_id_numbers_present_flag: bool,
+    pub delta_frame_id_length_minus_2: u8,
+    pub additional_frame_id_length_minus_1: u8,
+    pub use_128x128_superblock: bool,
+    pub enable_filter_intra: bool,
+    pub enable_intra_edge_filter: bool,
+    pub enable_interintra_compound: bool,
+    pub enable_masked_compound: bool,
+    pub enable_warped_motion: bool,
+    pub enable_dual_filter: bool,
+    pub enable_order_hint: bool,
+    pub enable_jnt_comp: bool,
+    pub enable_ref_frame_mvs: bool,
+    pub seq_force_screen_content_tools: u8,
+    pub seq_force_integer_mv: u8,
+    pub order_hint_bits_minus_1: u8,
+    pub enable_superres: bool,
+    pub enable_cdef: bool,
+    pub enable_restoration: bool,
+    pub color_config: ColorConfig,
+    pub film_grain_params_present: bool,
+}
+
+/// The tiles of a frame.
+#[derive(Clone, Copy, Debug)]
+pub struct TileInfo<'a> {
+    pub uniform_tile_spacing_flag: bool,
+    /// `TileCols`.
+    pub tile_cols: u8,
+    /// `TileRows`.
+    pub tile_rows: u8,
+    pub context_update_tile_id: u16,
+    pub tile_size_bytes_minus_1: u8,
+    /// `MiColStarts`, whose length is `TileCols + 1`.
+    pub mi_col_starts: &'a [u16],
+    /// `MiRowStarts`, whose length is `TileRows + 1`.
+    pub mi_row_starts: &'a [u16],
+    /// `width_in_sbs_minus_1` of every tile column.
+    pub width_in_sbs_minus_1: &'a [u16],
+    /// `height_in_sbs_minus_1` of every tile row.
+    pub height_in_sbs_minus_1: &'a [u16],
+}
+
+/// The quantization parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Quantization {
+    pub base_q_idx: u8,
+    /// `DeltaQYDc`.
+    pub delta_q_y_dc: i8,
+    pub diff_uv_delta: bool,
+    /// `DeltaQUDc`.
+    pub delta_q_u_dc: i8,
+    /// `DeltaQUAc`.
+    pub delta_q_u_ac: i8,
+    /// `DeltaQVDc`.
+    pub delta_q_v_dc: i8,
+    /// `DeltaQVAc`.
+    pub delta_q_v_ac: i8,
+    pub using_qmatrix: bool,
+    pub qm_y: u8,
+    pub qm_u: u8,
+    pub qm_v: u8,
+}
+
+/// The segmentation parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Segmentation {
+    pub segmentation_enabled: bool,
+    pub segmentation_update_map: bool,
+    pub segmentation_temporal_update: bool,
+    pub segmentation_update_data: bool,
+    /// Bit `j` of element `i` is `FeatureEnabled[i][j]`.
+    pub feature_enabled: [u8; 8],
+    /// `FeatureData`.
+    pub feature_data: [[i16; 8]; 8],
+}
+
+/// The loop filter parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LoopFilter {
+    pub loop_filter_level: [u8; 4],
+    pub loop_filter_sharpness: u8,
+    pub loop_filter_delta_enabled: bool,
+    pub loop_filter_delta_update: bool,
+    /// Bit `i` is `update_ref_delta` of `loop_filter_ref_deltas[i]`.
+    pub update_ref_delta: u8,
+    pub loop_filter_ref_deltas: [i8; 8],
+    /// Bit `i` is `update_mode_delta` of `loop_filter_mode_deltas[i]`.
+    pub update_mode_delta: u8,
+    pub loop_filter_mode_deltas: [i8; 2],
+}
+
+/// The constrained directional enhancement filter parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cdef {
+    pub cdef_damping_minus_3: u8,
+    pub cdef_bits: u8,
+    pub cdef_y_pri_strength: [u8; 8],
+    pub cdef_y_sec_strength: [u8; 8],
+    pub cdef_uv_pri_strength: [u8; 8],
+    pub cdef_uv_sec_strength: [u8; 8],
+}
+
+/// The loop restoration parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LoopRestoration {
+    /// `FrameRestorationType` of every plane.
+    pub frame_restoration_type: [u8; 3],
+    /// `LoopRestorationSize` of every plane.
+    pub loop_restoration_size: [u16; 3],
+}
+
+/// The global motion parameters of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GlobalMotion {
+    /// `GmType` of every reference frame, starting with `INTRA_FRAME`.
+    pub gm_type: [u8; 8],
+    /// `gm_params` of every reference frame, starting with `INTRA_FRAME`.
+    pub gm_params: [[i32; 6]; 8],
+}
+
+/// The headers of a frame to decode.
+///
+/// The bitstream of the frame is made of its frame header OBU and tile group OBUs. Film grain
+/// parameters are ignored: pictures are decoded without film grain.
+#[derive(Clone, Copy, Debug)]
+pub struct PictureInfo<'a> {
+    /// The sequence header of the frame.
+    pub sequence_header: &'a SequenceHeader,
+    pub frame_type: u8,
+    pub current_frame_id: u32,
+    /// `UpscaledWidth`, the width of the frame after super-resolution.
+    pub upscaled_width: u32,
+    /// `FrameHeight`.
+    pub frame_height: u32,
+    /// `OrderHint`.
+    pub order_hint: u8,
+    pub primary_ref_frame: u8,
+    pub refresh_frame_flags: u8,
+    pub error_resilient_mode: bool,
+    pub disable_cdf_update: bool,
+    pub use_superres: bool,
+    pub render_and_frame_size_different: bool,
+    pub allow_screen_content_tools: bool,
+    pub is_filter_switchable: bool,
+    pub force_integer_mv: bool,
+    pub frame_size_override_flag: bool,
+    pub buffer_removal_time_present_flag: bool,
+    pub allow_intrabc: bool,
+    pub frame_refs_short_signaling: bool,
+    pub allow_high_precision_mv: bool,
+    pub is_motion_mode_switchable: bool,
+    pub use_ref_frame_mvs: bool,
+    pub disable_frame_end_update_cdf: bool,
+    pub allow_warped_motion: bool,
+    pub reduced_tx_set: bool,
+    pub reference_select: bool,
+    pub skip_mode_present: bool,
+    pub delta_q_present: bool,
+    pub delta_lf_present: bool,
+    pub delta_lf_multi: bool,
+    pub interpolation_filter: u8,
+    /// `TxMode`.
+    pub tx_mode: u8,
+    pub delta_q_res: u8,
+    pub delta_lf_res: u8,
+    /// `SkipModeFrame`.
+    pub skip_mode_frame: [u8; 2],
+    pub coded_denom: u8,
+    /// `OrderHints`, starting with `INTRA_FRAME`.
+    pub order_hints: [u8; 8],
+    /// `expectedFrameId`, starting with `INTRA_FRAME`.
+    pub expected_frame_id: [u32; 8],
+    pub tile_info: TileInfo<'a>,
+    pub quantization: &'a Quantization,
+    pub segmentation: &'a Segmentation,
+    pub loop_filter: &'a LoopFilter,
+    pub cdef: &'a Cdef,
+    pub loop_restoration: &'a LoopRestoration,
+    pub global_motion: &'a GlobalMotion,
+    /// The index into the references of the decode of the frame each of `LAST_FRAME` to
+    /// `ALTREF_FRAME` refers to, or `None` if it isn't used.
+    pub reference_name_indices: [Option<u8>; 7],
+    /// The offset of the frame header OBU in the bitstream.
+    pub frame_header_offset: u32,
+    /// The offset of every tile in the bitstream.
+    pub tile_offsets: &'a [u32],
+    /// The size of every tile in the bitstream.
+    pub tile_sizes: &'a [u32],
+}
+
+/// What a reference frame was decoded as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReferenceInfo {
+    pub disable_frame_end_update_cdf: bool,
+    pub segmentation_enabled: bool,
+    pub frame_type: u8,
+    /// `RefFrameSignBias`, with bit `i` for reference frame `i`.
+    pub ref_frame_sign_bias: u8,
+    /// `OrderHint`.
+    pub order_hint: u8,
+    /// `SavedOrderHints` of the frame.
+    pub saved_order_hints: [u8; 8],
+}
@@ -0,0 +1,124 @@
+//! Parameters of H.264 pictures.
+//!
+//! Fields are named after the syntax elements and variables of the H.264 specification
+//! (ITU-T Rec. H.264), in snake case.
+
+// Fields named after syntax elements are documented by the specification.
+#![allow(missing_docs)]
+
+use alloc::vec::Vec;
+
+/// Scaling matrices of a sequence or picture parameter set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScalingLists {
+    /// Bit `i` is `seq_scaling_list_present_flag[i]` or `pic_scaling_list_present_flag[i]`.
+    pub scaling_list_present_mask: u16,
+    /// Bit `i` is `UseDefaultScalingMatrix4x4Flag[i]` for `i < 6`, or
+    /// `UseDefaultScalingMatrix8x8Flag[i - 6]` otherwise.
+    pub use_default_scaling_matrix_mask: u16,
+    /// `ScalingList4x4`, in the order the coefficients are coded in.
+    pub scaling_list_4x4: [[u8; 16]; 6],
+    /// `ScalingList8x8`, in the order the coefficients are coded in.
+    pub scaling_list_8x8: [[u8; 64]; 6],
+}
+
+/// A sequence parameter set.
+///
+/// Video usability information isn't needed for decoding, and is not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequenceParameterSet {
+    pub profile_idc: u8,
+    pub constraint_set0_flag: bool,
+    pub constraint_set1_flag: bool,
+    pub constraint_set2_flag: bool,
+    pub constraint_set3_flag: bool,
+    pub constraint_set4_flag: bool,
+    pub constraint_set5_flag: bool,
+    pub level_idc: u8,
+    pub seq_parameter_set_id: u8,
+    pub chroma_format_idc: u8,
+    pub separate_colour_plane_flag: bool,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub qpprime_y_zero_transform_bypass_flag: bool,
+    /// The scaling lists, if `seq_scaling_matrix_present_flag` is set.
+    pub scaling_lists: Option<ScalingLists>,
+    pub log2_max_frame_num_minus4: u8,
+    pub pic_order_cnt_type: u8,
+    pub log2_max_pic_order_cnt_lsb_minus4: u8,
+    pub delta_pic_order_always_zero_flag: bool,
+    pub offset_for_non_ref_pic: i32,
+    pub offset_for_top_to_bottom_field: i32,
+    /// `offset_for_ref_frame`, whose length is `num_ref_frames_in_pic_order_cnt_cycle`.
+    pub offset_for_ref_frame: Vec<i32>,
+    pub max_num_ref_frames: u8,
+    pub gaps_in_frame_num_value_allowed_flag: bool,
+    pub pic_width_in_mbs_minus1: u32,
+    pub pic_height_in_map_units_minus1: u32,
+    pub frame_mbs_only_flag: bool,
+    pub mb_adaptive_frame_field_flag: bool,
+    pub direct_8x8_inference_flag: bool,
+    /// The frame cropping offsets, left, right, top and bottom, if `frame_cropping_flag`
+    /// is set.
+    pub frame_crop_offsets: Option<[u32; 4]>,
+}
+
+/// A picture parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PictureParameterSet {
+    pub pic_parameter_set_id: u8,
+    pub seq_parameter_set_id: u8,
+    pub entropy_coding_mode_flag: bool,
+    pub bottom_field_pic_order_in_frame_present_flag: bool,
+    pub num_ref_idx_l0_default_active_minus1: u8,
+    pub num_ref_idx_l1_default_active_minus1: u8,
+    pub weighted_pred_flag: bool,
+    pub weighted_bipred_idc: u8,
+    pub pic_init_qp_minus26: i8,
+    pub pic_init_qs_minus26: i8,
+    pub chroma_qp_index_offset: i8,
+    pub deblocking_filter_control_present_flag: bool,
+    pub constrained_intra_pred_flag: bool,
+    pub redundant_pic_cnt_present_flag: bool,
+    pub transform_8x8_mode_flag: bool,
+    /// The scaling lists, if `pic_scaling_matrix_present_flag` is set.
+    pub scaling_lists: Option<ScalingLists>,
+    /// `second_chroma_qp_index_offset`, which is `chroma_qp_index_offset` when not present.
+    pub second_chroma_qp_index_offset: i8,
+}
+
+/// The headers of a picture to decode.
+///
+/// Only frames can be decoded, fields of interlaced streams can't. The bitstream of the picture
+/// is made of its slice NAL units, each starting with a start code.
+#[derive(Clone, Copy, Debug)]
+pub struct PictureInfo<'a> {
+    /// The sequence parameter set of the picture.
+    pub sps: &'a SequenceParameterSet,
+    /// The picture parameter set of the picture.
+    pub pps: &'a PictureParameterSet,
+    /// True if the picture is an IDR picture.
+    pub idr_pic_flag: bool,
+    /// True if all the slices of the picture are I or SI slices.
+    pub is_intra: bool,
+    /// True if `nal_ref_idc` isn't zero.
+    pub is_reference: bool,
+    pub frame_num: u16,
+    pub idr_pic_id: u16,
+    /// `TopFieldOrderCnt` and `BottomFieldOrderCnt`.
+    pub pic_order_cnt: [i32; 2],
+    /// The offset of every slice in the bitstream.
+    pub slice_offsets: &'a [u32],
+}
+
+/// What a reference picture was decoded as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReferenceInfo {
+    pub used_for_long_term_reference: bool,
+    /// True if the picture was inferred for a gap in `frame_num`, and was not decoded.
+    pub is_non_existing: bool,
+    /// `FrameNum`, or `LongTermFrameIdx` for long-term references.
+    pub frame_num: u16,
+    /// `TopFieldOrderCnt` and `BottomFieldOrderCnt`.
+    pub pic_order_cnt: [i32; 2],
+}
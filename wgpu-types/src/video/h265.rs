@@ -0,0 +1,227 @@
+//! Parameters of H.265 pictures.
+//!
+//! Fields are named after the syntax elements and variables of the H.265 specification
+//! (ITU-T Rec. H.265), in snake case.
+
+// Fields named after syntax elements are documented by the specification.
+#![allow(missing_docs)]
+
+use alloc::vec::Vec;
+
+/// The general profile, tier and level of a video or sequence parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ProfileTierLevel {
+    pub general_tier_flag: bool,
+    pub general_profile_idc: u8,
+    pub general_progressive_source_flag: bool,
+    pub general_interlaced_source_flag: bool,
+    pub general_non_packed_constraint_flag: bool,
+    pub general_frame_only_constraint_flag: bool,
+    pub general_level_idc: u8,
+}
+
+/// The decoded picture buffer sizes of each sub-layer of a video or sequence parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DecPicBufMgr {
+    pub max_dec_pic_buffering_minus1: [u8; 7],
+    pub max_num_reorder_pics: [u8; 7],
+    pub max_latency_increase_plus1: [u32; 7],
+}
+
+/// Scaling matrices of a sequence or picture parameter set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScalingLists {
+    /// `ScalingList[0]`, in the order the coefficients are coded in.
+    pub scaling_list_4x4: [[u8; 16]; 6],
+    /// `ScalingList[1]`, in the order the coefficients are coded in.
+    pub scaling_list_8x8: [[u8; 64]; 6],
+    /// `ScalingList[2]`, in the order the coefficients are coded in.
+    pub scaling_list_16x16: [[u8; 64]; 6],
+    /// `ScalingList[3]`, in the order the coefficients are coded in.
+    pub scaling_list_32x32: [[u8; 64]; 2],
+    /// `scaling_list_dc_coef_minus8 + 8` of the 16x16 blocks.
+    pub scaling_list_dc_coef_16x16: [u8; 6],
+    /// `scaling_list_dc_coef_minus8 + 8` of the 32x32 blocks.
+    pub scaling_list_dc_coef_32x32: [u8; 2],
+}
+
+/// A short-term reference picture set of a sequence parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ShortTermRefPicSet {
+    pub inter_ref_pic_set_prediction_flag: bool,
+    pub delta_idx_minus1: u32,
+    pub delta_rps_sign: bool,
+    pub abs_delta_rps_minus1: u16,
+    /// Bit `j` is `used_by_curr_pic_flag[j]`.
+    pub used_by_curr_pic_flag: u16,
+    /// Bit `j` is `use_delta_flag[j]`.
+    pub use_delta_flag: u16,
+    pub num_negative_pics: u8,
+    pub num_positive_pics: u8,
+    pub delta_poc_s0_minus1: [u16; 16],
+    /// Bit `i` is `used_by_curr_pic_s0_flag[i]`.
+    pub used_by_curr_pic_s0_flag: u16,
+    pub delta_poc_s1_minus1: [u16; 16],
+    /// Bit `i` is `used_by_curr_pic_s1_flag[i]`.
+    pub used_by_curr_pic_s1_flag: u16,
+}
+
+/// The long-term reference pictures of a sequence parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LongTermRefPicsSps {
+    pub lt_ref_pic_poc_lsb_sps: [u32; 32],
+    /// Bit `i` is `used_by_curr_pic_lt_sps_flag[i]`.
+    pub used_by_curr_pic_lt_sps_flag: u32,
+}
+
+/// A video parameter set.
+///
+/// Hypothetical reference decoder parameters aren't needed for decoding, and are
+/// not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct VideoParameterSet {
+    pub vps_video_parameter_set_id: u8,
+    pub vps_max_sub_layers_minus1: u8,
+    pub vps_temporal_id_nesting_flag: bool,
+    pub profile_tier_level: ProfileTierLevel,
+    pub vps_sub_layer_ordering_info_present_flag: bool,
+    pub dec_pic_buf_mgr: DecPicBufMgr,
+    pub vps_timing_info_present_flag: bool,
+    pub vps_num_units_in_tick: u32,
+    pub vps_time_scale: u32,
+    pub vps_poc_proportional_to_timing_flag: bool,
+    pub vps_num_ticks_poc_diff_one_minus1: u32,
+}
+
+/// A sequence parameter set.
+///
+/// Video usability information isn't needed for decoding, and is not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequenceParameterSet {
+    pub sps_video_parameter_set_id: u8,
+    pub sps_max_sub_layers_minus1: u8,
+    pub sps_temporal_id_nesting_flag: bool,
+    pub profile_tier_level: ProfileTierLevel,
+    pub sps_seq_parameter_set_id: u8,
+    pub chroma_format_idc: u8,
+    pub separate_colour_plane_flag: bool,
+    pub pic_width_in_luma_samples: u32,
+    pub pic_height_in_luma_samples: u32,
+    /// The conformance window offsets, left, right, top and bottom, if
+    /// `conformance_window_flag` is set.
+    pub conf_win_offsets: Option<[u32; 4]>,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub log2_max_pic_order_cnt_lsb_minus4: u8,
+    pub sps_sub_layer_ordering_info_present_flag: bool,
+    pub dec_pic_buf_mgr: DecPicBufMgr,
+    pub log2_min_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_luma_coding_block_size: u8,
+    pub log2_min_luma_transform_block_size_minus2: u8,
+    pub log2_diff_max_min_luma_transform_block_size: u8,
+    pub max_transform_hierarchy_depth_inter: u8,
+    pub max_transform_hierarchy_depth_intra: u8,
+    pub scaling_list_enabled_flag: bool,
+    /// The scaling lists, if `sps_scaling_list_data_present_flag` is set.
+    pub scaling_lists: Option<ScalingLists>,
+    pub amp_enabled_flag: bool,
+    pub sample_adaptive_offset_enabled_flag: bool,
+    pub pcm_enabled_flag: bool,
+    pub pcm_sample_bit_depth_luma_minus1: u8,
+    pub pcm_sample_bit_depth_chroma_minus1: u8,
+    pub log2_min_pcm_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_pcm_luma_coding_block_size: u8,
+    pub pcm_loop_filter_disabled_flag: bool,
+    /// The short-term reference picture sets, whose length is `num_short_term_ref_pic_sets`.
+    pub short_term_ref_pic_sets: Vec<ShortTermRefPicSet>,
+    pub long_term_ref_pics_present_flag: bool,
+    pub num_long_term_ref_pics_sps: u8,
+    pub long_term_ref_pics_sps: LongTermRefPicsSps,
+    pub sps_temporal_mvp_enabled_flag: bool,
+    pub strong_intra_smoothing_enabled_flag: bool,
+}
+
+/// A picture parameter set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PictureParameterSet {
+    pub pps_pic_parameter_set_id: u8,
+    pub pps_seq_parameter_set_id: u8,
+    pub dependent_slice_segments_enabled_flag: bool,
+    pub output_flag_present_flag: bool,
+    pub num_extra_slice_header_bits: u8,
+    pub sign_data_hiding_enabled_flag: bool,
+    pub cabac_init_present_flag: bool,
+    pub num_ref_idx_l0_default_active_minus1: u8,
+    pub num_ref_idx_l1_default_active_minus1: u8,
+    pub init_qp_minus26: i8,
+    pub constrained_intra_pred_flag: bool,
+    pub transform_skip_enabled_flag: bool,
+    pub cu_qp_delta_enabled_flag: bool,
+    pub diff_cu_qp_delta_depth: u8,
+    pub pps_cb_qp_offset: i8,
+    pub pps_cr_qp_offset: i8,
+    pub pps_slice_chroma_qp_offsets_present_flag: bool,
+    pub weighted_pred_flag: bool,
+    pub weighted_bipred_flag: bool,
+    pub transquant_bypass_enabled_flag: bool,
+    pub tiles_enabled_flag: bool,
+    pub entropy_coding_sync_enabled_flag: bool,
+    pub num_tile_columns_minus1: u8,
+    pub num_tile_rows_minus1: u8,
+    pub uniform_spacing_flag: bool,
+    pub column_width_minus1: [u16; 19],
+    pub row_height_minus1: [u16; 21],
+    pub loop_filter_across_tiles_enabled_flag: bool,
+    pub pps_loop_filter_across_slices_enabled_flag: bool,
+    pub deblocking_filter_control_present_flag: bool,
+    pub deblocking_filter_override_enabled_flag: bool,
+    pub pps_deblocking_filter_disabled_flag: bool,
+    pub pps_beta_offset_div2: i8,
+    pub pps_tc_offset_div2: i8,
+    /// The scaling lists, if `pps_scaling_list_data_present_flag` is set.
+    pub scaling_lists: Option<ScalingLists>,
+    pub lists_modification_present_flag: bool,
+    pub log2_parallel_merge_level_minus2: u8,
+    pub slice_segment_header_extension_present_flag: bool,
+}
+
+/// The headers of a picture to decode.
+///
+/// The bitstream of the picture is made of its slice segment NAL units, each starting with a
+/// start code.
+#[derive(Clone, Copy, Debug)]
+pub struct PictureInfo<'a> {
+    /// The video parameter set of the picture.
+    pub vps: &'a VideoParameterSet,
+    /// The sequence parameter set of the picture.
+    pub sps: &'a SequenceParameterSet,
+    /// The picture parameter set of the picture.
+    pub pps: &'a PictureParameterSet,
+    /// True if the picture is an intra random access point picture.
+    pub irap_pic_flag: bool,
+    /// True if the picture is an IDR picture.
+    pub idr_pic_flag: bool,
+    /// True if the picture can be referenced by later pictures.
+    pub is_reference: bool,
+    pub short_term_ref_pic_set_sps_flag: bool,
+    /// The number of bits of `short_term_ref_pic_set()` in the slice segment headers.
+    pub num_bits_for_st_ref_pic_set_in_slice: u16,
+    /// `NumDeltaPocs[RefRpsIdx]`.
+    pub num_delta_pocs_of_ref_rps_idx: u8,
+    pub pic_order_cnt_val: i32,
+    /// `RefPicSetStCurrBefore`, as indices into the references of the decode.
+    pub ref_pic_set_st_curr_before: &'a [u8],
+    /// `RefPicSetStCurrAfter`, as indices into the references of the decode.
+    pub ref_pic_set_st_curr_after: &'a [u8],
+    /// `RefPicSetLtCurr`, as indices into the references of the decode.
+    pub ref_pic_set_lt_curr: &'a [u8],
+    /// The offset of every slice segment in the bitstream.
+    pub slice_segment_offsets: &'a [u32],
+}
+
+/// What a reference picture was decoded as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReferenceInfo {
+    pub used_for_long_term_reference: bool,
+    pub pic_order_cnt_val: i32,
+}
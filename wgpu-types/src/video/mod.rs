@@ -0,0 +1,219 @@
+//! Types for decoding video.
+//!
+//! A [`VideoDecoderDescriptor`] describes a decoder for one codec, and the pictures it decodes
+//! into. Pictures are [`TextureFormat::NV12`] or [`TextureFormat::P010`] textures created by the
+//! decoder, which also hold the decoded pictures that later pictures are predicted from.
+//!
+//! Decoding a picture doesn't parse the bitstream: the syntax elements of the parameter sets and
+//! of the picture headers are given next to the compressed data, in the structures of the
+//! [`h264`], [`h265`] and [`av1`] modules. Their fields are named after the syntax elements and
+//! variables of each codec's specification, and hold the values those define.
+//!
+//! Requires [`Features::VIDEO_DECODE`].
+//!
+//! [`Features::VIDEO_DECODE`]: crate::Features::VIDEO_DECODE
+
+use crate::{Extent3d, TextureFormat, TextureUsages};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub mod av1;
+pub mod h264;
+pub mod h265;
+
+/// A video compression format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VideoCodec {
+    /// H.264, also known as Advanced Video Coding.
+    ///
+    /// Progressive streams of the Constrained Baseline, Main and High profiles can be decoded.
+    H264,
+    /// H.265, also known as High Efficiency Video Coding.
+    ///
+    /// Streams of the Main profile can be decoded into [`TextureFormat::NV12`] pictures, and
+    /// streams of the Main 10 profile into [`TextureFormat::P010`] pictures.
+    H265,
+    /// AV1.
+    ///
+    /// Streams of the Main profile can be decoded. Film grain is not applied to the pictures.
+    Av1,
+}
+
+/// What a device can decode with a codec and picture format.
+///
+/// Returned by [`Device::video_decode_capabilities`].
+///
+/// [`Device::video_decode_capabilities`]: ../wgpu/struct.Device.html#method.video_decode_capabilities
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VideoDecodeCapabilities {
+    /// The smallest size pictures can be coded with.
+    pub min_coded_extent: Extent3d,
+    /// The largest size pictures can be coded with.
+    pub max_coded_extent: Extent3d,
+    /// The maximum number of pictures a decoder can have at once.
+    pub max_pictures: u32,
+    /// The maximum number of pictures a picture can be predicted from.
+    pub max_reference_pictures: u32,
+}
+
+/// Describes a [`VideoDecoder`](../wgpu/struct.VideoDecoder.html).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VideoDecoderDescriptor<L> {
+    /// Debug label of the decoder. This will show up in graphics debuggers for easy
+    /// identification.
+    pub label: L,
+    /// The codec of the streams to decode.
+    pub codec: VideoCodec,
+    /// The format of the pictures, either [`TextureFormat::NV12`] for 8-bit streams or
+    /// [`TextureFormat::P010`] for 10-bit streams.
+    pub format: TextureFormat,
+    /// The size of the pictures, which must be at least the coded size of every picture
+    /// of the streams.
+    pub max_coded_extent: Extent3d,
+    /// The maximum number of pictures the decoder can have at once.
+    ///
+    /// This must be enough for the pictures of the stream that are referenced by later
+    /// pictures, plus the picture being decoded and the pictures waiting to be displayed.
+    pub max_pictures: u32,
+    /// The maximum number of pictures a picture can be predicted from.
+    pub max_reference_pictures: u32,
+}
+
+impl<L> VideoDecoderDescriptor<L> {
+    /// Takes a closure and maps the label of the video decoder descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> VideoDecoderDescriptor<K> {
+        VideoDecoderDescriptor {
+            label: fun(&self.label),
+            codec: self.codec,
+            format: self.format,
+            max_coded_extent: self.max_coded_extent,
+            max_pictures: self.max_pictures,
+            max_reference_pictures: self.max_reference_pictures,
+        }
+    }
+}
+
+/// Describes a picture created by a [`VideoDecoder`](../wgpu/struct.VideoDecoder.html).
+///
+/// The picture is a 2D texture of the decoder's `format` and `max_coded_extent`, with a single
+/// mip level and array layer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VideoPictureDescriptor<L> {
+    /// Debug label of the picture. This will show up in graphics debuggers for easy
+    /// identification.
+    pub label: L,
+    /// The ways the picture can be used besides decoding, which can only include
+    /// [`TextureUsages::TEXTURE_BINDING`] and [`TextureUsages::COPY_SRC`].
+    pub usage: TextureUsages,
+}
+
+impl<L> VideoPictureDescriptor<L> {
+    /// Takes a closure and maps the label of the video picture descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> VideoPictureDescriptor<K> {
+        VideoPictureDescriptor {
+            label: fun(&self.label),
+            usage: self.usage,
+        }
+    }
+}
+
+/// The headers of a picture to decode, for the codec of the decoder.
+#[derive(Clone, Copy, Debug)]
+pub enum VideoPictureInfo<'a> {
+    /// An H.264 picture.
+    H264(h264::PictureInfo<'a>),
+    /// An H.265 picture.
+    H265(h265::PictureInfo<'a>),
+    /// An AV1 frame.
+    Av1(av1::PictureInfo<'a>),
+}
+
+impl VideoPictureInfo<'_> {
+    /// The codec of the picture.
+    pub fn codec(&self) -> VideoCodec {
+        match *self {
+            Self::H264(_) => VideoCodec::H264,
+            Self::H265(_) => VideoCodec::H265,
+            Self::Av1(_) => VideoCodec::Av1,
+        }
+    }
+
+    /// The size the picture is coded with, as given by its parameter sets or frame header.
+    pub fn coded_extent(&self) -> Extent3d {
+        let (width, height) = match *self {
+            Self::H264(ref picture) => {
+                let sps = picture.sps;
+                let map_unit_height = if sps.frame_mbs_only_flag { 16 } else { 32 };
+                (
+                    (sps.pic_width_in_mbs_minus1 + 1) * 16,
+                    (sps.pic_height_in_map_units_minus1 + 1) * map_unit_height,
+                )
+            }
+            Self::H265(ref picture) => (
+                picture.sps.pic_width_in_luma_samples,
+                picture.sps.pic_height_in_luma_samples,
+            ),
+            Self::Av1(ref picture) => (picture.upscaled_width, picture.frame_height),
+        };
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        }
+    }
+}
+
+/// What a picture being referenced by another was decoded as, for the codec of the decoder.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VideoReferenceInfo {
+    /// An H.264 reference picture.
+    H264(h264::ReferenceInfo),
+    /// An H.265 reference picture.
+    H265(h265::ReferenceInfo),
+    /// An AV1 reference frame.
+    Av1(av1::ReferenceInfo),
+}
+
+impl VideoReferenceInfo {
+    /// The codec of the reference picture.
+    pub fn codec(&self) -> VideoCodec {
+        match *self {
+            Self::H264(_) => VideoCodec::H264,
+            Self::H265(_) => VideoCodec::H265,
+            Self::Av1(_) => VideoCodec::Av1,
+        }
+    }
+}
+
+/// A picture the picture being decoded is predicted from.
+#[derive(Clone, Copy, Debug)]
+pub struct VideoReference<T> {
+    /// The picture, created by the same decoder as the picture being decoded.
+    pub picture: T,
+    /// What the picture was decoded as.
+    pub info: VideoReferenceInfo,
+}
+
+/// Describes a call to [`Queue::decode_video`].
+///
+/// [`Queue::decode_video`]: ../wgpu/struct.Queue.html#method.decode_video
+#[derive(Clone, Copy, Debug)]
+pub struct VideoDecodeDescriptor<'a, T> {
+    /// The compressed data of the picture.
+    pub bitstream: &'a [u8],
+    /// The picture to decode into.
+    pub target: T,
+    /// What the target is decoded as, if later pictures can be predicted from it.
+    pub target_reference: Option<VideoReferenceInfo>,
+    /// The pictures the picture is predicted from. None of them can be the target.
+    pub references: &'a [VideoReference<T>],
+    /// The headers of the picture.
+    pub picture: VideoPictureInfo<'a>,
+}
@@ -229,6 +229,8 @@ impl Buffer {
     /// - The buffer is not from the backend specified by `A`.
     /// - The buffer is from the `webgpu` or `custom` backend.
     /// - The buffer has had [`Self::destroy()`] called on it.
+    /// - The buffer shares its backend buffer with other buffers, see
+    ///   [`InstanceFlags::SUBALLOCATE_SMALL_BUFFERS`](crate::InstanceFlags::SUBALLOCATE_SMALL_BUFFERS).
     ///
     /// # Safety
     ///
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::*;
 
 /// Handle to a command buffer on the GPU.
@@ -15,9 +17,49 @@ pub struct CommandBuffer {
 static_assertions::assert_impl_all!(CommandBuffer: Send, Sync);
 
 impl CommandBuffer {
+    /// Returns the transitions that were inserted between the commands of this command
+    /// buffer while they were recorded, ordered by the index of the command that caused them.
+    ///
+    /// The report is empty unless the instance was created with
+    /// [`InstanceFlags::REPORT_TRANSITIONS`], and on the WebGPU backend. It doesn't include
+    /// the transitions inserted when the command buffer is submitted, which bring its
+    /// resources from the states they were left in by earlier submissions.
+    pub fn transition_report(&self) -> Vec<TransitionReport> {
+        self.buffer.transition_report()
+    }
+
     #[cfg(custom)]
     /// Returns custom implementation of CommandBuffer (if custom backend and is internally T)
     pub fn as_custom<T: custom::CommandBufferInterface>(&self) -> Option<&T> {
         self.buffer.as_custom()
     }
 }
+
+/// Handle to a command buffer that can be submitted more than once.
+///
+/// A `ReusableCommandBuffer` is obtained by recording a series of commands to a
+/// [`ReusableCommandEncoder`] and then calling [`ReusableCommandEncoder::finish`].
+/// Its commands are validated once, and each [`CommandBuffer`] returned by
+/// [`ReusableCommandBuffer::to_command_buffer`] submits them again, only
+/// checking that the resources they use are still valid and not mapped.
+///
+/// Requires [`Features::REUSABLE_COMMAND_BUFFERS`].
+#[derive(Debug, Clone)]
+pub struct ReusableCommandBuffer {
+    pub(crate) buffer: dispatch::DispatchCommandBuffer,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ReusableCommandBuffer: Send, Sync);
+
+impl ReusableCommandBuffer {
+    /// Returns a [`CommandBuffer`] that submits these commands when passed to
+    /// [`Queue::submit`].
+    ///
+    /// It can be submitted along with other command buffers, including other
+    /// submissions of this one.
+    pub fn to_command_buffer(&self) -> CommandBuffer {
+        CommandBuffer {
+            buffer: self.buffer.clone(),
+        }
+    }
+}
@@ -50,6 +50,45 @@ pub type TexelCopyTextureInfo<'a> = TexelCopyTextureInfoBase<&'a Texture>;
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(TexelCopyTextureInfo<'_>: Send, Sync);
 
+/// Encodes a series of GPU operations into a command buffer that can be
+/// submitted more than once.
+///
+/// This dereferences to a [`CommandEncoder`] to record commands. When finished
+/// recording, call [`ReusableCommandEncoder::finish`] to obtain a
+/// [`ReusableCommandBuffer`].
+///
+/// Created with [`Device::create_reusable_command_encoder`], which requires
+/// [`Features::REUSABLE_COMMAND_BUFFERS`].
+#[derive(Debug)]
+pub struct ReusableCommandEncoder {
+    pub(crate) encoder: CommandEncoder,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ReusableCommandEncoder: Send, Sync);
+
+impl ReusableCommandEncoder {
+    /// Finishes recording and returns a [`ReusableCommandBuffer`].
+    pub fn finish(mut self) -> ReusableCommandBuffer {
+        let buffer = self.encoder.inner.finish();
+
+        ReusableCommandBuffer { buffer }
+    }
+}
+
+impl core::ops::Deref for ReusableCommandEncoder {
+    type Target = CommandEncoder;
+
+    fn deref(&self) -> &Self::Target {
+        &self.encoder
+    }
+}
+
+impl core::ops::DerefMut for ReusableCommandEncoder {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.encoder
+    }
+}
+
 impl CommandEncoder {
     /// Finishes recording and returns a [`CommandBuffer`] that can be submitted for execution.
     pub fn finish(mut self) -> CommandBuffer {
@@ -63,7 +63,10 @@ impl Device {
         let instance = Instance::new(&InstanceDescriptor {
             backends: Backends::NOOP,
             backend_options: BackendOptions {
-                noop: NoopBackendOptions { enable: true },
+                noop: NoopBackendOptions {
+                    enable: true,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -201,6 +204,32 @@ impl Device {
         CommandEncoder { inner: encoder }
     }
 
+    /// Creates an additional [`Queue`] of the given [`QueueType`].
+    ///
+    /// At most [`Limits::max_compute_queues`] compute queues and
+    /// [`Limits::max_transfer_queues`] transfer queues can be alive at the same time.
+    /// Command buffers submitted to it must be encoded with
+    /// [`Queue::create_command_encoder`] on a queue of the same type.
+    pub fn create_queue(&self, desc: &QueueDescriptor<'_>) -> Result<Queue, Error> {
+        let queue = self.inner.create_queue(desc)?;
+        Ok(Queue { inner: queue })
+    }
+
+    /// Creates an empty [`ReusableCommandEncoder`], recording a command buffer
+    /// that can be submitted more than once.
+    ///
+    /// Requires [`Features::REUSABLE_COMMAND_BUFFERS`].
+    #[must_use]
+    pub fn create_reusable_command_encoder(
+        &self,
+        desc: &CommandEncoderDescriptor<'_>,
+    ) -> ReusableCommandEncoder {
+        let encoder = self.inner.create_reusable_command_encoder(desc);
+        ReusableCommandEncoder {
+            encoder: CommandEncoder { inner: encoder },
+        }
+    }
+
     /// Creates an empty [`RenderBundleEncoder`].
     #[must_use]
     pub fn create_render_bundle_encoder<'a>(
@@ -259,6 +288,40 @@ impl Device {
         ComputePipeline { inner: pipeline }
     }
 
+    /// Creates a [`RenderPipeline`] without blocking the calling thread.
+    ///
+    /// Shader translation and pipeline compilation happen in the background, and
+    /// the returned future resolves once the pipeline is ready. Errors are
+    /// returned by the future instead of being reported to the error scopes or
+    /// the uncaptured error handler.
+    ///
+    /// On native, the future only resolves after the pipeline is ready and the
+    /// device has been polled with [`Device::poll`]. Polling with a wait blocks
+    /// until all pending pipelines of the device are ready.
+    ///
+    /// Corresponds to [WebGPU `GPUDevice.createRenderPipelineAsync`](https://gpuweb.github.io/gpuweb/#dom-gpudevice-createrenderpipelineasync).
+    pub fn create_render_pipeline_async(
+        &self,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> impl Future<Output = Result<RenderPipeline, Error>> + WasmNotSend {
+        let future = self.inner.create_render_pipeline_async(desc);
+        async move { future.await.map(|inner| RenderPipeline { inner }) }
+    }
+
+    /// Creates a [`ComputePipeline`] without blocking the calling thread.
+    ///
+    /// See [`Device::create_render_pipeline_async`] for how the result is
+    /// delivered.
+    ///
+    /// Corresponds to [WebGPU `GPUDevice.createComputePipelineAsync`](https://gpuweb.github.io/gpuweb/#dom-gpudevice-createcomputepipelineasync).
+    pub fn create_compute_pipeline_async(
+        &self,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> impl Future<Output = Result<ComputePipeline, Error>> + WasmNotSend {
+        let future = self.inner.create_compute_pipeline_async(desc);
+        async move { future.await.map(|inner| ComputePipeline { inner }) }
+    }
+
     /// Creates a [`Buffer`].
     #[must_use]
     pub fn create_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
@@ -400,6 +463,36 @@ impl Device {
         Sampler { inner: sampler }
     }
 
+    /// Query the sizes and picture counts that `codec` can be decoded with into pictures
+    /// of `format`.
+    ///
+    /// Returns `None` if the device can't decode `codec` into `format`, which is always the
+    /// case without [`Features::VIDEO_DECODE`].
+    #[must_use]
+    pub fn video_decode_capabilities(
+        &self,
+        codec: VideoCodec,
+        format: TextureFormat,
+    ) -> Option<VideoDecodeCapabilities> {
+        self.inner.video_decode_capabilities(codec, format)
+    }
+
+    /// Creates a new [`VideoDecoder`].
+    ///
+    /// `desc` must be within the [`Device::video_decode_capabilities`] of its codec and
+    /// format. Requires [`Features::VIDEO_DECODE`].
+    #[must_use]
+    pub fn create_video_decoder(&self, desc: &VideoDecoderDescriptor<'_>) -> VideoDecoder {
+        let decoder = self.inner.create_video_decoder(desc);
+        VideoDecoder {
+            inner: decoder,
+            descriptor: VideoDecoderDescriptor {
+                label: None,
+                ..desc.clone()
+            },
+        }
+    }
+
     /// Creates a new [`QuerySet`].
     #[must_use]
     pub fn create_query_set(&self, desc: &QuerySetDescriptor<'_>) -> QuerySet {
@@ -504,6 +597,36 @@ impl Device {
         self.inner.generate_allocator_report()
     }
 
+    /// Query the current budget and usage of each memory heap of the device.
+    ///
+    /// This is supported on Vulkan with `VK_EXT_memory_budget`, on DX12, and on the
+    /// noop backend, which simulates a budget (see [`NoopBackendOptions::memory_budget`]).
+    /// Other backends return `None`.
+    #[must_use]
+    pub fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        self.inner.get_memory_budget()
+    }
+
+    /// Call `callback` when the memory usage of a heap reaches `threshold` percent of
+    /// its budget, replacing any callback set previously.
+    ///
+    /// This allows releasing memory, for example by evicting streamed textures, before
+    /// allocations start failing. The budget is checked whenever the device is polled
+    /// or work is submitted. The callback is called once each time a heap reaches the
+    /// threshold; it isn't called again for that heap until its usage drops below the
+    /// threshold.
+    ///
+    /// The callback is never called on backends for which
+    /// [`get_memory_budget`](Self::get_memory_budget) returns `None`.
+    pub fn set_memory_budget_callback(
+        &self,
+        threshold: u8,
+        callback: impl Fn(wgt::MemoryBudgetReport) + WasmNotSendSync + 'static,
+    ) {
+        self.inner
+            .set_memory_budget_callback(threshold, Box::new(callback))
+    }
+
     /// Get the [`wgpu_hal`] device from this `Device`.
     ///
     /// Find the Api struct corresponding to the active backend in [`wgpu_hal::api`],
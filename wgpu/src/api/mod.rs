@@ -49,6 +49,7 @@ mod surface_texture;
 mod texture;
 mod texture_view;
 mod tlas;
+mod video_decoder;
 
 pub use adapter::*;
 pub use bind_group::*;
@@ -78,6 +79,7 @@ pub use surface_texture::*;
 pub use texture::*;
 pub use texture_view::*;
 pub use tlas::*;
+pub use video_decoder::*;
 
 /// Object debugging label.
 pub type Label<'a> = Option<&'a str>;
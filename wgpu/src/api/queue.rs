@@ -35,6 +35,26 @@ impl Queue {
     }
 }
 
+/// Describes a [`Queue`].
+///
+/// For use with [`Device::create_queue`].
+pub type QueueDescriptor<'a> = wgt::QueueDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(QueueDescriptor<'_>: Send, Sync);
+
+/// Binds or unbinds the memory of pages of a sparse [`Buffer`].
+///
+/// For use with [`Queue::bind_sparse`].
+pub type SparseBufferBind<'a> = wgt::SparseBufferBind<&'a Buffer>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SparseBufferBind<'_>: Send, Sync);
+
+/// Binds or unbinds the memory of pages of a sparse [`Texture`].
+///
+/// For use with [`Queue::bind_sparse`].
+pub type SparseTextureBind<'a> = wgt::SparseTextureBind<&'a Texture>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SparseTextureBind<'_>: Send, Sync);
+
 /// Identifier for a particular call to [`Queue::submit`]. Can be used
 /// as part of an argument to [`Device::poll`] to block for a particular
 /// submission to finish.
@@ -231,6 +251,43 @@ impl Queue {
         self.inner.write_texture(texture, data, data_layout, size);
     }
 
+    /// Creates an empty [`CommandEncoder`] whose command buffer can be submitted
+    /// to queues of this queue's [`QueueType`].
+    ///
+    /// Command encoders created with [`Device::create_command_encoder`] can only be
+    /// submitted to the queue returned by [`Adapter::request_device`].
+    #[must_use]
+    pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor<'_>) -> CommandEncoder {
+        let encoder = self.inner.create_command_encoder(desc);
+        CommandEncoder { inner: encoder }
+    }
+
+    /// Decodes a picture of a video stream with `decoder`, as described by `desc`.
+    ///
+    /// The decode starts once the work submitted before it is done, and the next
+    /// [`Queue::submit`] waits for it to finish, so that command buffers submitted
+    /// afterwards can sample the decoded picture. Every texture in `desc` must be a
+    /// picture created by `decoder`.
+    pub fn decode_video(&self, decoder: &VideoDecoder, desc: &VideoDecodeDescriptor<'_>) {
+        self.inner.decode_video(&decoder.inner, desc);
+    }
+
+    /// Binds memory to pages of sparse buffers and textures, or unbinds it.
+    ///
+    /// The binds are made once the work submitted before them is done, and the
+    /// next [`Queue::submit`] comes after them. Pages that become resident read
+    /// as zero until written, while reads of pages that aren't return zero and
+    /// writes to them are discarded.
+    ///
+    /// Requires [`Features::SPARSE_RESOURCES`].
+    pub fn bind_sparse(
+        &self,
+        buffer_binds: &[SparseBufferBind<'_>],
+        texture_binds: &[SparseTextureBind<'_>],
+    ) {
+        self.inner.bind_sparse(buffer_binds, texture_binds);
+    }
+
     /// Schedule a copy of data from `image` into `texture`.
     #[cfg(web)]
     pub fn copy_external_image_to_texture(
@@ -14,14 +14,16 @@ use crate::*;
 ///
 /// Corresponds to [WebGPU `GPURenderBundleEncoder`](
 /// https://gpuweb.github.io/gpuweb/#gpurenderbundleencoder).
+///
+/// Render bundle encoders don't record into a command buffer, so several bundles can be
+/// recorded in parallel on different threads, and then executed in the same render pass.
 #[derive(Debug)]
 pub struct RenderBundleEncoder<'a> {
     pub(crate) inner: dispatch::DispatchRenderBundleEncoder,
-    /// This type should be !Send !Sync, because it represents an allocation on this thread's
-    /// command buffer.
-    pub(crate) _p: PhantomData<(*const u8, &'a ())>,
+    pub(crate) _p: PhantomData<&'a ()>,
 }
-static_assertions::assert_not_impl_any!(RenderBundleEncoder<'_>: Send, Sync);
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderBundleEncoder<'_>: Send, Sync);
 
 crate::cmp::impl_eq_ord_hash_proxy!(RenderBundleEncoder<'_> => .inner);
 
@@ -46,7 +48,18 @@ pub struct RenderBundleEncoderDescriptor<'a> {
     pub sample_count: u32,
     /// If this render bundle will rendering to multiple array layers in the attachments at the same time.
     pub multiview: Option<NonZeroU32>,
+    /// The layouts of the bind groups this render bundle inherits from the render pass it is
+    /// executed in, indexed by bind group index.
+    ///
+    /// The bundle can't set bind groups at these indices itself, and its pipelines must use these
+    /// layouts there. Instead, the render pass must have a bind group with a matching layout set
+    /// at each of them when the bundle is executed. These bind groups stay set in the render pass
+    /// after the bundle is executed, so consecutive bundles can share them.
+    ///
+    /// Requires [`Features::RENDER_BUNDLE_INHERITANCE`] unless every entry is `None`.
+    pub inherited_bind_group_layouts: &'a [Option<&'a BindGroupLayout>],
 }
+#[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderBundleEncoderDescriptor<'_>: Send, Sync);
 
 impl<'a> RenderBundleEncoder<'a> {
@@ -467,6 +467,71 @@ impl RenderPass<'_> {
     }
 }
 
+/// [`Features::INDIRECT_COMMANDS`] must be enabled on the device in order to call these functions.
+impl RenderPass<'_> {
+    /// Executes up to `max_count` records of `indirect_buffer`, each of which may set push
+    /// constants, switch vertex buffers and the index buffer, and then draws.
+    ///
+    /// The records are laid out as described by `layout`, and must be tightly packed with a stride
+    /// of [`IndirectCommandLayout::stride`]. Vertex and index buffers switched by the records are
+    /// ranges of `data_buffer`, see [`IndirectVertexBufferArgs`](crate::util::IndirectVertexBufferArgs)
+    /// and [`IndirectIndexBufferArgs`](crate::util::IndirectIndexBufferArgs).
+    ///
+    /// Draws whose buffer ranges or arguments are out of bounds are skipped.
+    ///
+    /// The push constants, vertex buffers and index buffer switched by the records are left in an
+    /// unspecified state, and must be set again before the next draw that uses them.
+    pub fn execute_indirect(
+        &mut self,
+        layout: &IndirectCommandLayout,
+        data_buffer: &Buffer,
+        indirect_buffer: &Buffer,
+        indirect_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        self.inner.execute_indirect(
+            layout,
+            &data_buffer.inner,
+            &indirect_buffer.inner,
+            indirect_offset,
+            max_count,
+        );
+    }
+
+    /// Like [`RenderPass::execute_indirect`], but the number of records to execute is read from
+    /// `count_buffer`. If the count is greater than `max_count`, `max_count` will be used.
+    ///
+    /// The structure expected in `count_buffer` is the following:
+    ///
+    /// ```rust
+    /// #[repr(C)]
+    /// struct ExecuteIndirectCount {
+    ///     count: u32, // Number of records to execute.
+    /// }
+    /// ```
+    #[expect(clippy::too_many_arguments)]
+    pub fn execute_indirect_count(
+        &mut self,
+        layout: &IndirectCommandLayout,
+        data_buffer: &Buffer,
+        indirect_buffer: &Buffer,
+        indirect_offset: BufferAddress,
+        count_buffer: &Buffer,
+        count_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        self.inner.execute_indirect_count(
+            layout,
+            &data_buffer.inner,
+            &indirect_buffer.inner,
+            indirect_offset,
+            &count_buffer.inner,
+            count_offset,
+            max_count,
+        );
+    }
+}
+
 /// [`Features::PUSH_CONSTANTS`] must be enabled on the device in order to call these functions.
 impl RenderPass<'_> {
     /// Set push constant data for subsequent draw calls.
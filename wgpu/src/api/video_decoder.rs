@@ -0,0 +1,77 @@
+use crate::*;
+
+/// Handle to a video decoder.
+///
+/// A `VideoDecoder` decodes the pictures of a compressed video stream into textures it creates,
+/// called pictures, which are [`TextureFormat::NV12`] or [`TextureFormat::P010`] textures whose
+/// planes can be sampled, for example through an [`ExternalTexture`]. The decoder also predicts
+/// pictures from the pictures decoded before them, so a picture must be kept while later pictures
+/// refer to it.
+///
+/// It can be created with [`Device::create_video_decoder`], and pictures are decoded with
+/// [`Queue::decode_video`]. See the [`video`] module for how pictures are described.
+///
+/// Requires [`Features::VIDEO_DECODE`].
+#[derive(Debug, Clone)]
+pub struct VideoDecoder {
+    pub(crate) inner: dispatch::DispatchVideoDecoder,
+    pub(crate) descriptor: VideoDecoderDescriptor<'static>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(VideoDecoder: Send, Sync);
+
+crate::cmp::impl_eq_ord_hash_proxy!(VideoDecoder => .inner);
+
+impl VideoDecoder {
+    /// Creates a picture the decoder can decode into and predict from.
+    ///
+    /// The picture is a 2D texture of the decoder's `format` and `max_coded_extent`. A decoder
+    /// can have up to `max_pictures` pictures at once, and the pictures that are dropped don't
+    /// count towards it.
+    #[must_use]
+    pub fn create_picture(&self, desc: &VideoPictureDescriptor<'_>) -> Texture {
+        let texture = self.inner.create_picture(desc);
+
+        Texture {
+            inner: texture,
+            descriptor: TextureDescriptor {
+                label: None,
+                size: self.descriptor.max_coded_extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: self.descriptor.format,
+                usage: desc.usage,
+                view_formats: &[],
+            },
+        }
+    }
+
+    #[cfg(custom)]
+    /// Returns custom implementation of VideoDecoder (if custom backend and is internally T)
+    pub fn as_custom<T: custom::VideoDecoderInterface>(&self) -> Option<&T> {
+        self.inner.as_custom()
+    }
+}
+
+/// Describes a [`VideoDecoder`].
+///
+/// For use with [`Device::create_video_decoder`].
+pub type VideoDecoderDescriptor<'a> = wgt::VideoDecoderDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(VideoDecoderDescriptor<'_>: Send, Sync);
+
+/// Describes a picture of a [`VideoDecoder`].
+///
+/// For use with [`VideoDecoder::create_picture`].
+pub type VideoPictureDescriptor<'a> = wgt::VideoPictureDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(VideoPictureDescriptor<'_>: Send, Sync);
+
+/// Describes a picture to decode.
+///
+/// For use with [`Queue::decode_video`].
+pub type VideoDecodeDescriptor<'a> = wgt::VideoDecodeDescriptor<'a, &'a Texture>;
+
+/// A picture the picture being decoded is predicted from.
+///
+/// For use with [`VideoDecodeDescriptor`].
+pub type VideoReference<'a> = wgt::VideoReference<&'a Texture>;
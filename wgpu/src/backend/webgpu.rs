@@ -835,6 +835,9 @@ fn map_wgt_limits(limits: webgpu_sys::GpuSupportedLimits) -> wgt::Limits {
         max_tlas_instance_count: wgt::Limits::default().max_tlas_instance_count,
         max_acceleration_structures_per_shader_stage: wgt::Limits::default()
             .max_acceleration_structures_per_shader_stage,
+
+        max_compute_queues: wgt::Limits::default().max_compute_queues,
+        max_transfer_queues: wgt::Limits::default().max_transfer_queues,
     }
 }
 
@@ -892,6 +895,135 @@ fn map_js_sys_limits(limits: &wgt::Limits) -> js_sys::Object {
     object
 }
 
+fn map_render_pipeline_descriptor(
+    desc: &crate::RenderPipelineDescriptor<'_>,
+) -> webgpu_sys::GpuRenderPipelineDescriptor {
+    let module = desc.vertex.module.inner.as_webgpu();
+    let mapped_vertex_state = webgpu_sys::GpuVertexState::new(&module.module);
+    insert_constants_map(
+        &mapped_vertex_state,
+        desc.vertex.compilation_options.constants,
+    );
+    if let Some(ep) = desc.vertex.entry_point {
+        mapped_vertex_state.set_entry_point(ep);
+    }
+
+    let buffers = desc
+        .vertex
+        .buffers
+        .iter()
+        .map(|vbuf| {
+            let mapped_attributes = vbuf
+                .attributes
+                .iter()
+                .map(|attr| {
+                    webgpu_sys::GpuVertexAttribute::new(
+                        map_vertex_format(attr.format),
+                        attr.offset as f64,
+                        attr.shader_location,
+                    )
+                })
+                .collect::<js_sys::Array>();
+
+            let mapped_vbuf = webgpu_sys::GpuVertexBufferLayout::new(
+                vbuf.array_stride as f64,
+                &mapped_attributes,
+            );
+            mapped_vbuf.set_step_mode(map_vertex_step_mode(vbuf.step_mode));
+            mapped_vbuf
+        })
+        .collect::<js_sys::Array>();
+
+    mapped_vertex_state.set_buffers(&buffers);
+
+    let auto_layout = wasm_bindgen::JsValue::from(webgpu_sys::GpuAutoLayoutMode::Auto);
+    let mapped_desc = webgpu_sys::GpuRenderPipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout = &layout.inner.as_webgpu().inner;
+                JsValue::from(layout)
+            }
+            None => auto_layout,
+        },
+        &mapped_vertex_state,
+    );
+
+    if let Some(label) = desc.label {
+        mapped_desc.set_label(label);
+    }
+
+    if let Some(ref depth_stencil) = desc.depth_stencil {
+        mapped_desc.set_depth_stencil(&map_depth_stencil_state(depth_stencil));
+    }
+
+    if let Some(ref frag) = desc.fragment {
+        let targets = frag
+            .targets
+            .iter()
+            .map(|target| match target {
+                Some(target) => {
+                    let mapped_format = map_texture_format(target.format);
+                    let mapped_color_state = webgpu_sys::GpuColorTargetState::new(mapped_format);
+                    if let Some(ref bs) = target.blend {
+                        let alpha = map_blend_component(&bs.alpha);
+                        let color = map_blend_component(&bs.color);
+                        let mapped_blend_state = webgpu_sys::GpuBlendState::new(&alpha, &color);
+                        mapped_color_state.set_blend(&mapped_blend_state);
+                    }
+                    mapped_color_state.set_write_mask(target.write_mask.bits());
+                    wasm_bindgen::JsValue::from(mapped_color_state)
+                }
+                None => wasm_bindgen::JsValue::null(),
+            })
+            .collect::<js_sys::Array>();
+        let module = frag.module.inner.as_webgpu();
+        let mapped_fragment_desc = webgpu_sys::GpuFragmentState::new(&module.module, &targets);
+        insert_constants_map(&mapped_fragment_desc, frag.compilation_options.constants);
+        if let Some(ep) = frag.entry_point {
+            mapped_fragment_desc.set_entry_point(ep);
+        }
+        mapped_desc.set_fragment(&mapped_fragment_desc);
+    }
+
+    let mapped_multisample = webgpu_sys::GpuMultisampleState::new();
+    mapped_multisample.set_count(desc.multisample.count);
+    mapped_multisample.set_mask(desc.multisample.mask as u32);
+    mapped_multisample.set_alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
+    mapped_desc.set_multisample(&mapped_multisample);
+
+    let mapped_primitive = map_primitive_state(&desc.primitive);
+    mapped_desc.set_primitive(&mapped_primitive);
+
+    mapped_desc
+}
+
+fn map_compute_pipeline_descriptor(
+    desc: &crate::ComputePipelineDescriptor<'_>,
+) -> webgpu_sys::GpuComputePipelineDescriptor {
+    let shader_module = desc.module.inner.as_webgpu();
+    let mapped_compute_stage = webgpu_sys::GpuProgrammableStage::new(&shader_module.module);
+    insert_constants_map(&mapped_compute_stage, desc.compilation_options.constants);
+    if let Some(ep) = desc.entry_point {
+        mapped_compute_stage.set_entry_point(ep);
+    }
+    let auto_layout = wasm_bindgen::JsValue::from(webgpu_sys::GpuAutoLayoutMode::Auto);
+    let mapped_desc = webgpu_sys::GpuComputePipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout = &layout.inner.as_webgpu().inner;
+                JsValue::from(layout)
+            }
+            None => auto_layout,
+        },
+        &mapped_compute_stage,
+    );
+    if let Some(label) = desc.label {
+        mapped_desc.set_label(label);
+    }
+
+    mapped_desc
+}
+
 type JsFutureResult = Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue>;
 
 fn future_request_adapter(
@@ -952,6 +1084,56 @@ fn future_request_device(
         })
 }
 
+fn future_create_render_pipeline(
+    result: JsFutureResult,
+) -> Result<dispatch::DispatchRenderPipeline, crate::Error> {
+    result
+        .map(|js_value| {
+            WebRenderPipeline {
+                inner: webgpu_sys::GpuRenderPipeline::from(js_value),
+                ident: crate::cmp::Identifier::create(),
+            }
+            .into()
+        })
+        .map_err(map_pipeline_error)
+}
+
+fn future_create_compute_pipeline(
+    result: JsFutureResult,
+) -> Result<dispatch::DispatchComputePipeline, crate::Error> {
+    result
+        .map(|js_value| {
+            WebComputePipeline {
+                inner: webgpu_sys::GpuComputePipeline::from(js_value),
+                ident: crate::cmp::Identifier::create(),
+            }
+            .into()
+        })
+        .map_err(map_pipeline_error)
+}
+
+/// Maps the `GPUPipelineError` a pipeline creation promise is rejected with.
+fn map_pipeline_error(error_value: JsValue) -> crate::Error {
+    let property = |name: &str| {
+        js_sys::Reflect::get(&error_value, &JsValue::from_str(name))
+            .ok()
+            .and_then(|value| value.as_string())
+    };
+    let source = Box::<dyn core::error::Error + Send + Sync>::from("<WebGPU Error>");
+    // wasm-bindgen provides a reasonable error stringification via `Debug` impl
+    let description = property("message").unwrap_or_else(|| format!("{error_value:?}"));
+    match property("reason").as_deref() {
+        Some("internal") => crate::Error::Internal {
+            source,
+            description,
+        },
+        _ => crate::Error::Validation {
+            source,
+            description,
+        },
+    }
+}
+
 fn future_pop_error_scope(result: JsFutureResult) -> Option<crate::Error> {
     match result {
         Ok(js_value) if js_value.is_object() => {
@@ -1284,6 +1466,12 @@ pub struct WebTlas {
     ident: crate::cmp::Identifier,
 }
 
+#[derive(Debug)]
+pub struct WebVideoDecoder {
+    /// Unique identifier for this VideoDecoder.
+    ident: crate::cmp::Identifier,
+}
+
 #[derive(Debug)]
 pub struct WebQuerySet {
     pub(crate) inner: webgpu_sys::GpuQuerySet,
@@ -1408,6 +1596,7 @@ impl_send_sync!(WebTexture);
 impl_send_sync!(WebExternalTexture);
 impl_send_sync!(WebBlas);
 impl_send_sync!(WebTlas);
+impl_send_sync!(WebVideoDecoder);
 impl_send_sync!(WebQuerySet);
 impl_send_sync!(WebPipelineLayout);
 impl_send_sync!(WebRenderPipeline);
@@ -1438,6 +1627,7 @@ crate::cmp::impl_eq_ord_hash_proxy!(WebTexture => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebExternalTexture => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebBlas => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebTlas => .ident);
+crate::cmp::impl_eq_ord_hash_proxy!(WebVideoDecoder => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebQuerySet => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebPipelineLayout => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebRenderPipeline => .ident);
@@ -2108,104 +2298,7 @@ impl dispatch::DeviceInterface for WebDevice {
         &self,
         desc: &crate::RenderPipelineDescriptor<'_>,
     ) -> dispatch::DispatchRenderPipeline {
-        let module = desc.vertex.module.inner.as_webgpu();
-        let mapped_vertex_state = webgpu_sys::GpuVertexState::new(&module.module);
-        insert_constants_map(
-            &mapped_vertex_state,
-            desc.vertex.compilation_options.constants,
-        );
-        if let Some(ep) = desc.vertex.entry_point {
-            mapped_vertex_state.set_entry_point(ep);
-        }
-
-        let buffers = desc
-            .vertex
-            .buffers
-            .iter()
-            .map(|vbuf| {
-                let mapped_attributes = vbuf
-                    .attributes
-                    .iter()
-                    .map(|attr| {
-                        webgpu_sys::GpuVertexAttribute::new(
-                            map_vertex_format(attr.format),
-                            attr.offset as f64,
-                            attr.shader_location,
-                        )
-                    })
-                    .collect::<js_sys::Array>();
-
-                let mapped_vbuf = webgpu_sys::GpuVertexBufferLayout::new(
-                    vbuf.array_stride as f64,
-                    &mapped_attributes,
-                );
-                mapped_vbuf.set_step_mode(map_vertex_step_mode(vbuf.step_mode));
-                mapped_vbuf
-            })
-            .collect::<js_sys::Array>();
-
-        mapped_vertex_state.set_buffers(&buffers);
-
-        let auto_layout = wasm_bindgen::JsValue::from(webgpu_sys::GpuAutoLayoutMode::Auto);
-        let mapped_desc = webgpu_sys::GpuRenderPipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout = &layout.inner.as_webgpu().inner;
-                    JsValue::from(layout)
-                }
-                None => auto_layout,
-            },
-            &mapped_vertex_state,
-        );
-
-        if let Some(label) = desc.label {
-            mapped_desc.set_label(label);
-        }
-
-        if let Some(ref depth_stencil) = desc.depth_stencil {
-            mapped_desc.set_depth_stencil(&map_depth_stencil_state(depth_stencil));
-        }
-
-        if let Some(ref frag) = desc.fragment {
-            let targets = frag
-                .targets
-                .iter()
-                .map(|target| match target {
-                    Some(target) => {
-                        let mapped_format = map_texture_format(target.format);
-                        let mapped_color_state =
-                            webgpu_sys::GpuColorTargetState::new(mapped_format);
-                        if let Some(ref bs) = target.blend {
-                            let alpha = map_blend_component(&bs.alpha);
-                            let color = map_blend_component(&bs.color);
-                            let mapped_blend_state = webgpu_sys::GpuBlendState::new(&alpha, &color);
-                            mapped_color_state.set_blend(&mapped_blend_state);
-                        }
-                        mapped_color_state.set_write_mask(target.write_mask.bits());
-                        wasm_bindgen::JsValue::from(mapped_color_state)
-                    }
-                    None => wasm_bindgen::JsValue::null(),
-                })
-                .collect::<js_sys::Array>();
-            let module = frag.module.inner.as_webgpu();
-            let mapped_fragment_desc = webgpu_sys::GpuFragmentState::new(&module.module, &targets);
-            insert_constants_map(&mapped_fragment_desc, frag.compilation_options.constants);
-            if let Some(ep) = frag.entry_point {
-                mapped_fragment_desc.set_entry_point(ep);
-            }
-            mapped_desc.set_fragment(&mapped_fragment_desc);
-        }
-
-        let mapped_multisample = webgpu_sys::GpuMultisampleState::new();
-        mapped_multisample.set_count(desc.multisample.count);
-        mapped_multisample.set_mask(desc.multisample.mask as u32);
-        mapped_multisample
-            .set_alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
-        mapped_desc.set_multisample(&mapped_multisample);
-
-        let mapped_primitive = map_primitive_state(&desc.primitive);
-        mapped_desc.set_primitive(&mapped_primitive);
-
+        let mapped_desc = map_render_pipeline_descriptor(desc);
         let render_pipeline = self.inner.create_render_pipeline(&mapped_desc).unwrap();
 
         WebRenderPipeline {
@@ -2226,27 +2319,7 @@ impl dispatch::DeviceInterface for WebDevice {
         &self,
         desc: &crate::ComputePipelineDescriptor<'_>,
     ) -> dispatch::DispatchComputePipeline {
-        let shader_module = desc.module.inner.as_webgpu();
-        let mapped_compute_stage = webgpu_sys::GpuProgrammableStage::new(&shader_module.module);
-        insert_constants_map(&mapped_compute_stage, desc.compilation_options.constants);
-        if let Some(ep) = desc.entry_point {
-            mapped_compute_stage.set_entry_point(ep);
-        }
-        let auto_layout = wasm_bindgen::JsValue::from(webgpu_sys::GpuAutoLayoutMode::Auto);
-        let mapped_desc = webgpu_sys::GpuComputePipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout = &layout.inner.as_webgpu().inner;
-                    JsValue::from(layout)
-                }
-                None => auto_layout,
-            },
-            &mapped_compute_stage,
-        );
-        if let Some(label) = desc.label {
-            mapped_desc.set_label(label);
-        }
-
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
         let compute_pipeline = self.inner.create_compute_pipeline(&mapped_desc);
 
         WebComputePipeline {
@@ -2256,6 +2329,30 @@ impl dispatch::DeviceInterface for WebDevice {
         .into()
     }
 
+    fn create_render_pipeline_async(
+        &self,
+        desc: &crate::RenderPipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn dispatch::CreateRenderPipelineFuture>> {
+        let mapped_desc = map_render_pipeline_descriptor(desc);
+        let pipeline_promise = self.inner.create_render_pipeline_async(&mapped_desc);
+        Box::pin(MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(pipeline_promise),
+            future_create_render_pipeline,
+        ))
+    }
+
+    fn create_compute_pipeline_async(
+        &self,
+        desc: &crate::ComputePipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn dispatch::CreateComputePipelineFuture>> {
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
+        let pipeline_promise = self.inner.create_compute_pipeline_async(&mapped_desc);
+        Box::pin(MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(pipeline_promise),
+            future_create_compute_pipeline,
+        ))
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         _desc: &crate::PipelineCacheDescriptor<'_>,
@@ -2322,6 +2419,28 @@ impl dispatch::DeviceInterface for WebDevice {
         unimplemented!("Raytracing not implemented for web");
     }
 
+    fn video_decode_capabilities(
+        &self,
+        _codec: crate::VideoCodec,
+        _format: crate::TextureFormat,
+    ) -> Option<crate::VideoDecodeCapabilities> {
+        None
+    }
+
+    fn create_video_decoder(
+        &self,
+        _desc: &crate::VideoDecoderDescriptor<'_>,
+    ) -> dispatch::DispatchVideoDecoder {
+        unimplemented!("Video decoding not implemented for web");
+    }
+
+    fn create_queue(
+        &self,
+        _desc: &crate::QueueDescriptor<'_>,
+    ) -> Result<dispatch::DispatchQueue, crate::Error> {
+        unimplemented!("Additional queues not implemented for web");
+    }
+
     fn create_sampler(&self, desc: &crate::SamplerDescriptor<'_>) -> dispatch::DispatchSampler {
         let mapped_desc = webgpu_sys::GpuSamplerDescriptor::new();
         mapped_desc.set_address_mode_u(map_address_mode(desc.address_mode_u));
@@ -2389,19 +2508,47 @@ impl dispatch::DeviceInterface for WebDevice {
         .into()
     }
 
+    fn create_reusable_command_encoder(
+        &self,
+        _desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        unimplemented!("Reusable command buffers are not implemented for web");
+    }
+
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
     ) -> dispatch::DispatchRenderBundleEncoder {
+        // WebGPU can't inherit bind groups. Without any attachment formats,
+        // the browser reports a validation error and returns an invalid
+        // encoder, as wgpu-core does without `Features::RENDER_BUNDLE_INHERITANCE`.
+        let inherits = desc
+            .inherited_bind_group_layouts
+            .iter()
+            .any(Option::is_some);
         let mapped_color_formats = desc
             .color_formats
             .iter()
+            .filter(|_| !inherits)
             .map(|cf| match cf {
                 Some(cf) => wasm_bindgen::JsValue::from(map_texture_format(*cf)),
                 None => wasm_bindgen::JsValue::null(),
             })
             .collect::<js_sys::Array>();
         let mapped_desc = webgpu_sys::GpuRenderBundleEncoderDescriptor::new(&mapped_color_formats);
+        if inherits {
+            mapped_desc.set_label(
+                "Render bundle bind group inheritance requires `Features::RENDER_BUNDLE_INHERITANCE`",
+            );
+            return WebRenderBundleEncoder {
+                inner: self
+                    .inner
+                    .create_render_bundle_encoder(&mapped_desc)
+                    .unwrap(),
+                ident: crate::cmp::Identifier::create(),
+            }
+            .into();
+        }
         if let Some(label) = desc.label {
             mapped_desc.set_label(label);
         }
@@ -2493,6 +2640,18 @@ impl dispatch::DeviceInterface for WebDevice {
         None
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        None
+    }
+
+    fn set_memory_budget_callback(
+        &self,
+        _threshold: u8,
+        _callback: dispatch::BoxMemoryBudgetCallback,
+    ) {
+        // The memory budget is unknown, so the callback is never called.
+    }
+
     fn destroy(&self) {
         self.inner.destroy();
     }
@@ -2676,6 +2835,29 @@ impl dispatch::QueueInterface for WebQueue {
     ) -> (Option<u64>, dispatch::DispatchBlas) {
         unimplemented!("Raytracing not implemented for web")
     }
+
+    fn decode_video(
+        &self,
+        _decoder: &dispatch::DispatchVideoDecoder,
+        _desc: &crate::VideoDecodeDescriptor<'_>,
+    ) {
+        unimplemented!("Video decoding not implemented for web")
+    }
+
+    fn bind_sparse(
+        &self,
+        _buffer_binds: &[crate::SparseBufferBind<'_>],
+        _texture_binds: &[crate::SparseTextureBind<'_>],
+    ) {
+        unimplemented!("Sparse resources not implemented for web")
+    }
+
+    fn create_command_encoder(
+        &self,
+        _desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        unimplemented!("Additional queues not implemented for web")
+    }
 }
 impl Drop for WebQueue {
     fn drop(&mut self) {
@@ -2854,6 +3036,20 @@ impl Drop for WebTlas {
     }
 }
 
+impl dispatch::VideoDecoderInterface for WebVideoDecoder {
+    fn create_picture(
+        &self,
+        _desc: &crate::VideoPictureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        unimplemented!("Video decoding not implemented for web")
+    }
+}
+impl Drop for WebVideoDecoder {
+    fn drop(&mut self) {
+        // no-op
+    }
+}
+
 impl dispatch::QuerySetInterface for WebQuerySet {}
 impl Drop for WebQuerySet {
     fn drop(&mut self) {
@@ -3569,6 +3765,30 @@ impl dispatch::RenderPassInterface for WebRenderPassEncoder {
         panic!("MESH_SHADER feature must be enabled to call multi_draw_mesh_tasks_indirect_count")
     }
 
+    fn execute_indirect(
+        &mut self,
+        _layout: &crate::IndirectCommandLayout,
+        _data_buffer: &dispatch::DispatchBuffer,
+        _indirect_buffer: &dispatch::DispatchBuffer,
+        _indirect_offset: crate::BufferAddress,
+        _max_count: u32,
+    ) {
+        panic!("INDIRECT_COMMANDS feature must be enabled to call execute_indirect")
+    }
+
+    fn execute_indirect_count(
+        &mut self,
+        _layout: &crate::IndirectCommandLayout,
+        _data_buffer: &dispatch::DispatchBuffer,
+        _indirect_buffer: &dispatch::DispatchBuffer,
+        _indirect_offset: crate::BufferAddress,
+        _count_buffer: &dispatch::DispatchBuffer,
+        _count_buffer_offset: crate::BufferAddress,
+        _max_count: u32,
+    ) {
+        panic!("INDIRECT_COMMANDS feature must be enabled to call execute_indirect_count")
+    }
+
     fn insert_debug_marker(&mut self, _label: &str) {
         // Not available in gecko yet
         // self.inner.insert_debug_marker(label);
@@ -3633,7 +3853,12 @@ impl Drop for WebRenderPassEncoder {
     }
 }
 
-impl dispatch::CommandBufferInterface for WebCommandBuffer {}
+impl dispatch::CommandBufferInterface for WebCommandBuffer {
+    fn transition_report(&self) -> Vec<crate::TransitionReport> {
+        // The browser inserts its own transitions, and doesn't report them.
+        Vec::new()
+    }
+}
 impl Drop for WebCommandBuffer {
     fn drop(&mut self) {
         // no-op
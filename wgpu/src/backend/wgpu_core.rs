@@ -10,11 +10,11 @@ use alloc::{
 use core::{
     error::Error,
     fmt,
-    future::ready,
+    future::{ready, Future},
     ops::{Deref, Range},
     pin::Pin,
     ptr::NonNull,
-    slice,
+    slice, task,
 };
 
 use arrayvec::ArrayVec;
@@ -265,43 +265,61 @@ impl ContextWgpuCore {
         self.0.generate_report()
     }
 
+    /// Builds the [`crate::Error`] reported for `source`.
+    ///
+    /// Returns `None` for errors caused by a lost device, which are surfaced
+    /// through the device lost callback instead.
     #[cold]
-    #[track_caller]
     #[inline(never)]
-    fn handle_error_inner(
+    fn make_error(
         &self,
-        sink_mutex: &Mutex<ErrorSinkRaw>,
         error_type: ErrorType,
         source: ContextErrorSource,
         label: Label<'_>,
         fn_ident: &'static str,
-    ) {
+    ) -> Option<crate::Error> {
         let source: ErrorSource = Box::new(wgc::error::ContextError {
             fn_ident,
             source,
             label: label.unwrap_or_default().to_string(),
         });
-        let final_error_handling = {
-            let mut sink = sink_mutex.lock();
-            let description = || self.format_error(&*source);
-            let error = match error_type {
-                ErrorType::Internal => {
-                    let description = description();
-                    crate::Error::Internal {
-                        source,
-                        description,
-                    }
+        let description = || self.format_error(&*source);
+        Some(match error_type {
+            ErrorType::Internal => {
+                let description = description();
+                crate::Error::Internal {
+                    source,
+                    description,
                 }
-                ErrorType::OutOfMemory => crate::Error::OutOfMemory { source },
-                ErrorType::Validation => {
-                    let description = description();
-                    crate::Error::Validation {
-                        source,
-                        description,
-                    }
+            }
+            ErrorType::OutOfMemory => crate::Error::OutOfMemory { source },
+            ErrorType::Validation => {
+                let description = description();
+                crate::Error::Validation {
+                    source,
+                    description,
                 }
-                ErrorType::DeviceLost => return, // will be surfaced via callback
-            };
+            }
+            ErrorType::DeviceLost => return None,
+        })
+    }
+
+    #[cold]
+    #[track_caller]
+    #[inline(never)]
+    fn handle_error_inner(
+        &self,
+        sink_mutex: &Mutex<ErrorSinkRaw>,
+        error_type: ErrorType,
+        source: ContextErrorSource,
+        label: Label<'_>,
+        fn_ident: &'static str,
+    ) {
+        let Some(error) = self.make_error(error_type, source, label, fn_ident) else {
+            return; // will be surfaced via callback
+        };
+        let final_error_handling = {
+            let mut sink = sink_mutex.lock();
             sink.handle_error_or_return_handler(error)
         };
 
@@ -611,6 +629,13 @@ pub struct CoreTlas {
     // error_sink: ErrorSink,
 }
 
+#[derive(Debug)]
+pub struct CoreVideoDecoder {
+    pub(crate) context: ContextWgpuCore,
+    id: wgc::id::VideoDecoderId,
+    error_sink: ErrorSink,
+}
+
 #[derive(Debug)]
 pub struct CoreSurfaceOutputDetail {
     context: ContextWgpuCore,
@@ -691,6 +716,123 @@ fn default_error_handler(err: crate::Error) -> ! {
     panic!("wgpu error: {err}\n");
 }
 
+/// The result of a pipeline created in the background, shared between the
+/// creation callback and the [`CorePipelineFuture`] waiting for it.
+struct PipelineSlot<T> {
+    result: Option<Result<T, crate::Error>>,
+    waker: Option<task::Waker>,
+}
+
+/// Resolves once `wgpu-core` reports that a pipeline requested with one of the
+/// `*_async` creation functions is ready.
+struct CorePipelineFuture<T> {
+    slot: Arc<Mutex<PipelineSlot<T>>>,
+}
+
+impl<T> CorePipelineFuture<T> {
+    fn new() -> Self {
+        Self {
+            slot: Arc::new(Mutex::new(PipelineSlot {
+                result: None,
+                waker: None,
+            })),
+        }
+    }
+
+    /// Returns a function that resolves this future with its argument.
+    fn completer(&self) -> impl FnOnce(Result<T, crate::Error>) {
+        let slot = Arc::clone(&self.slot);
+        move |result| {
+            let waker = {
+                let mut slot = slot.lock();
+                slot.result = Some(result);
+                slot.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Future for CorePipelineFuture<T> {
+    type Output = Result<T, crate::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let mut slot = self.slot.lock();
+        match slot.result.take() {
+            Some(result) => task::Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                task::Poll::Pending
+            }
+        }
+    }
+}
+
+/// Builds the `wgpu-core` stage descriptor for a shader stage, owning all of
+/// its data.
+fn owned_programmable_stage(
+    module: &crate::ShaderModule,
+    entry_point: Option<&str>,
+    compilation_options: &crate::PipelineCompilationOptions<'_>,
+) -> wgc::pipeline::ProgrammableStageDescriptor<'static> {
+    wgc::pipeline::ProgrammableStageDescriptor {
+        module: module.inner.as_core().id,
+        entry_point: entry_point.map(|entry_point| Cow::Owned(String::from(entry_point))),
+        constants: compilation_options
+            .constants
+            .iter()
+            .map(|&(key, value)| (String::from(key), value))
+            .collect(),
+        zero_initialize_workgroup_memory: compilation_options.zero_initialize_workgroup_memory,
+    }
+}
+
+/// Builds the `wgpu-core` descriptor for `desc`, owning all of its data so that
+/// the pipeline can be created after this call returns.
+fn owned_render_pipeline_descriptor(
+    desc: &crate::RenderPipelineDescriptor<'_>,
+) -> wgc::pipeline::RenderPipelineDescriptor<'static> {
+    use wgc::pipeline as pipe;
+
+    pipe::RenderPipelineDescriptor {
+        label: desc.label.map(|label| Cow::Owned(String::from(label))),
+        layout: desc.layout.map(|layout| layout.inner.as_core().id),
+        vertex: pipe::VertexState {
+            stage: owned_programmable_stage(
+                desc.vertex.module,
+                desc.vertex.entry_point,
+                &desc.vertex.compilation_options,
+            ),
+            buffers: Cow::Owned(
+                desc.vertex
+                    .buffers
+                    .iter()
+                    .map(|vbuf| pipe::VertexBufferLayout {
+                        array_stride: vbuf.array_stride,
+                        step_mode: vbuf.step_mode,
+                        attributes: Cow::Owned(vbuf.attributes.to_vec()),
+                    })
+                    .collect(),
+            ),
+        },
+        primitive: desc.primitive,
+        depth_stencil: desc.depth_stencil.clone(),
+        multisample: desc.multisample,
+        fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+            stage: owned_programmable_stage(
+                frag.module,
+                frag.entry_point,
+                &frag.compilation_options,
+            ),
+            targets: Cow::Owned(frag.targets.to_vec()),
+        }),
+        multiview: desc.multiview,
+        cache: desc.cache.map(|cache| cache.inner.as_core().id),
+    }
+}
+
 impl From<CreateShaderModuleError> for CompilationInfo {
     fn from(value: CreateShaderModuleError) -> Self {
         match value {
@@ -758,6 +900,7 @@ crate::cmp::impl_eq_ord_hash_proxy!(CoreTexture => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreExternalTexture => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreBlas => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreTlas => .id);
+crate::cmp::impl_eq_ord_hash_proxy!(CoreVideoDecoder => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreQuerySet => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CorePipelineLayout => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreRenderPipeline => .id);
@@ -1526,6 +1669,111 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_render_pipeline_async(
+        &self,
+        desc: &crate::RenderPipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn dispatch::CreateRenderPipelineFuture>> {
+        let future = CorePipelineFuture::new();
+        let complete = future.completer();
+        let context = self.context.clone();
+        let error_sink = Arc::clone(&self.error_sink);
+        let label = desc.label.map(String::from);
+
+        self.context.0.device_create_render_pipeline_async(
+            self.id,
+            owned_render_pipeline_descriptor(desc),
+            None,
+            Box::new(move |id, error| {
+                if let Some(cause) = error {
+                    if let wgc::pipeline::CreateRenderPipelineError::Internal { stage, ref error } =
+                        cause
+                    {
+                        log::error!("Shader translation error for stage {stage:?}: {error}");
+                        log::error!("Please report it to https://github.com/gfx-rs/wgpu");
+                    }
+                    let error = context.make_error(
+                        cause.webgpu_error_type(),
+                        Box::new(cause),
+                        label.as_deref(),
+                        "Device::create_render_pipeline_async",
+                    );
+                    // On a lost device, resolve with an invalid pipeline, like WebGPU does.
+                    if let Some(error) = error {
+                        context.0.render_pipeline_drop(id);
+                        return complete(Err(error));
+                    }
+                }
+                complete(Ok(CoreRenderPipeline {
+                    context,
+                    id,
+                    error_sink,
+                }
+                .into()))
+            }),
+        );
+        Box::pin(future)
+    }
+
+    fn create_compute_pipeline_async(
+        &self,
+        desc: &crate::ComputePipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn dispatch::CreateComputePipelineFuture>> {
+        use wgc::pipeline as pipe;
+
+        let future = CorePipelineFuture::new();
+        let complete = future.completer();
+        let context = self.context.clone();
+        let error_sink = Arc::clone(&self.error_sink);
+        let label = desc.label.map(String::from);
+
+        let descriptor = pipe::ComputePipelineDescriptor {
+            label: label.clone().map(Cow::Owned),
+            layout: desc.layout.map(|pll| pll.inner.as_core().id),
+            stage: owned_programmable_stage(
+                desc.module,
+                desc.entry_point,
+                &desc.compilation_options,
+            ),
+            cache: desc.cache.map(|cache| cache.inner.as_core().id),
+        };
+
+        self.context.0.device_create_compute_pipeline_async(
+            self.id,
+            descriptor,
+            None,
+            Box::new(move |id, error| {
+                if let Some(cause) = error {
+                    if let wgc::pipeline::CreateComputePipelineError::Internal(ref error) = cause {
+                        log::error!(
+                            "Shader translation error for stage {:?}: {}",
+                            wgt::ShaderStages::COMPUTE,
+                            error
+                        );
+                        log::error!("Please report it to https://github.com/gfx-rs/wgpu");
+                    }
+                    let error = context.make_error(
+                        cause.webgpu_error_type(),
+                        Box::new(cause),
+                        label.as_deref(),
+                        "Device::create_compute_pipeline_async",
+                    );
+                    // On a lost device, resolve with an invalid pipeline, like WebGPU does.
+                    if let Some(error) = error {
+                        context.0.compute_pipeline_drop(id);
+                        return complete(Err(error));
+                    }
+                }
+                complete(Ok(CoreComputePipeline {
+                    context,
+                    id,
+                    error_sink,
+                }
+                .into()))
+            }),
+        );
+        Box::pin(future)
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
@@ -1668,6 +1916,41 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn video_decode_capabilities(
+        &self,
+        codec: crate::VideoCodec,
+        format: crate::TextureFormat,
+    ) -> Option<crate::VideoDecodeCapabilities> {
+        self.context
+            .0
+            .device_video_decode_capabilities(self.id, codec, format)
+    }
+
+    fn create_video_decoder(
+        &self,
+        desc: &crate::VideoDecoderDescriptor<'_>,
+    ) -> dispatch::DispatchVideoDecoder {
+        let (id, error) = self.context.0.device_create_video_decoder(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_video_decoder",
+            );
+        }
+        CoreVideoDecoder {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
     fn create_sampler(&self, desc: &crate::SamplerDescriptor<'_>) -> dispatch::DispatchSampler {
         let descriptor = wgc::resource::SamplerDescriptor {
             label: desc.label.map(Borrowed),
@@ -1748,6 +2031,67 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_queue(
+        &self,
+        desc: &crate::QueueDescriptor<'_>,
+    ) -> Result<dispatch::DispatchQueue, crate::Error> {
+        match self.context.0.device_create_queue(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        ) {
+            Ok(id) => Ok(CoreQueue {
+                context: self.context.clone(),
+                id,
+                error_sink: Arc::clone(&self.error_sink),
+            }
+            .into()),
+            Err(cause) => {
+                // There is no invalid queue to hand out, so a lost device is
+                // reported to the caller like any other failure.
+                let error_type = match cause.webgpu_error_type() {
+                    ErrorType::DeviceLost => ErrorType::Internal,
+                    error_type => error_type,
+                };
+                Err(self
+                    .context
+                    .make_error(
+                        error_type,
+                        Box::new(cause),
+                        desc.label,
+                        "Device::create_queue",
+                    )
+                    .expect("only lost devices are not reported"))
+            }
+        }
+    }
+
+    fn create_reusable_command_encoder(
+        &self,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        let (id, error) = self.context.0.device_create_reusable_command_encoder(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_reusable_command_encoder",
+            );
+        }
+
+        CoreCommandEncoder {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
@@ -1758,6 +2102,12 @@ impl dispatch::DeviceInterface for CoreDevice {
             depth_stencil: desc.depth_stencil,
             sample_count: desc.sample_count,
             multiview: desc.multiview,
+            inherited_bind_group_layouts: Cow::Owned(
+                desc.inherited_bind_group_layouts
+                    .iter()
+                    .map(|layout| layout.map(|layout| layout.inner.as_core().id))
+                    .collect(),
+            ),
         };
         let encoder = match wgc::command::RenderBundleEncoder::new(&descriptor, self.id, None) {
             Ok(encoder) => encoder,
@@ -1814,7 +2164,7 @@ impl dispatch::DeviceInterface for CoreDevice {
     }
 
     fn poll(&self, poll_type: wgt::PollType<u64>) -> Result<crate::PollStatus, crate::PollError> {
-        match self.context.0.device_poll(self.id, poll_type) {
+        let result = match self.context.0.device_poll(self.id, poll_type) {
             Ok(status) => Ok(status),
             Err(err) => {
                 if let Some(poll_error) = err.to_poll_error() {
@@ -1823,7 +2173,18 @@ impl dispatch::DeviceInterface for CoreDevice {
 
                 self.context.handle_error_fatal(err, "Device::poll")
             }
+        };
+
+        for err in self
+            .context
+            .0
+            .device_take_shader_out_of_bounds_errors(self.id)
+        {
+            self.context
+                .handle_error_nolabel(&self.error_sink, err, "Device::poll");
         }
+
+        result
     }
 
     fn get_internal_counters(&self) -> crate::InternalCounters {
@@ -1834,6 +2195,20 @@ impl dispatch::DeviceInterface for CoreDevice {
         self.context.0.device_generate_allocator_report(self.id)
     }
 
+    fn get_memory_budget(&self) -> Option<wgt::MemoryBudgetReport> {
+        self.context.0.device_get_memory_budget(self.id)
+    }
+
+    fn set_memory_budget_callback(
+        &self,
+        threshold: u8,
+        callback: dispatch::BoxMemoryBudgetCallback,
+    ) {
+        self.context
+            .0
+            .device_set_memory_budget_callback(self.id, threshold, Arc::from(callback));
+    }
+
     fn destroy(&self) {
         self.context.0.device_destroy(self.id);
     }
@@ -2015,6 +2390,15 @@ impl dispatch::QueueInterface for CoreQueue {
             }
         };
 
+        for err in self
+            .context
+            .0
+            .queue_take_shader_out_of_bounds_errors(self.id)
+        {
+            self.context
+                .handle_error_nolabel(&self.error_sink, err, "Queue::submit");
+        }
+
         drop(temp_command_buffers);
 
         index
@@ -2050,6 +2434,96 @@ impl dispatch::QueueInterface for CoreQueue {
             .into(),
         )
     }
+
+    fn decode_video(
+        &self,
+        decoder: &dispatch::DispatchVideoDecoder,
+        desc: &crate::VideoDecodeDescriptor<'_>,
+    ) {
+        let references = desc
+            .references
+            .iter()
+            .map(|reference| wgt::VideoReference {
+                picture: reference.picture.inner.as_core().id,
+                info: reference.info,
+            })
+            .collect::<Vec<_>>();
+        let desc = wgt::VideoDecodeDescriptor {
+            bitstream: desc.bitstream,
+            target: desc.target.inner.as_core().id,
+            target_reference: desc.target_reference,
+            references: &references,
+            picture: desc.picture,
+        };
+        if let Err(cause) = self
+            .context
+            .0
+            .queue_decode_video(self.id, decoder.as_core().id, &desc)
+        {
+            self.context
+                .handle_error_nolabel(&self.error_sink, cause, "Queue::decode_video");
+        }
+    }
+
+    fn bind_sparse(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_>],
+        texture_binds: &[crate::SparseTextureBind<'_>],
+    ) {
+        let buffer_binds = buffer_binds
+            .iter()
+            .map(|bind| wgt::SparseBufferBind {
+                buffer: bind.buffer.inner.as_core().id,
+                offset: bind.offset,
+                size: bind.size,
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        let texture_binds = texture_binds
+            .iter()
+            .map(|bind| wgt::SparseTextureBind {
+                texture: bind.texture.inner.as_core().id,
+                mip_level: bind.mip_level,
+                origin: bind.origin,
+                size: bind.size,
+                resident: bind.resident,
+            })
+            .collect::<Vec<_>>();
+        if let Err(cause) = self
+            .context
+            .0
+            .queue_bind_sparse(self.id, &buffer_binds, &texture_binds)
+        {
+            self.context
+                .handle_error_nolabel(&self.error_sink, cause, "Queue::bind_sparse");
+        }
+    }
+
+    fn create_command_encoder(
+        &self,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        let (id, error) = self.context.0.queue_create_command_encoder(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Queue::create_command_encoder",
+            );
+        }
+
+        CoreCommandEncoder {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
 }
 
 impl Drop for CoreQueue {
@@ -2281,6 +2755,40 @@ impl Drop for CoreTlas {
     }
 }
 
+impl dispatch::VideoDecoderInterface for CoreVideoDecoder {
+    fn create_picture(
+        &self,
+        desc: &crate::VideoPictureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        let (id, error) = self.context.0.video_decoder_create_picture(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "VideoDecoder::create_picture",
+            );
+        }
+
+        CoreTexture {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+}
+
+impl Drop for CoreVideoDecoder {
+    fn drop(&mut self) {
+        self.context.0.video_decoder_drop(self.id)
+    }
+}
+
 impl dispatch::QuerySetInterface for CoreQuerySet {}
 
 impl Drop for CoreQuerySet {
@@ -2819,7 +3327,11 @@ impl Drop for CoreCommandEncoder {
     }
 }
 
-impl dispatch::CommandBufferInterface for CoreCommandBuffer {}
+impl dispatch::CommandBufferInterface for CoreCommandBuffer {
+    fn transition_report(&self) -> Vec<crate::TransitionReport> {
+        self.context.0.command_buffer_transition_report(self.id)
+    }
+}
 
 impl Drop for CoreCommandBuffer {
     fn drop(&mut self) {
@@ -3482,6 +3994,67 @@ impl dispatch::RenderPassInterface for CoreRenderPass {
         }
     }
 
+    fn execute_indirect(
+        &mut self,
+        layout: &crate::IndirectCommandLayout,
+        data_buffer: &dispatch::DispatchBuffer,
+        indirect_buffer: &dispatch::DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        max_count: u32,
+    ) {
+        let data_buffer = data_buffer.as_core();
+        let indirect_buffer = indirect_buffer.as_core();
+
+        if let Err(cause) = self.context.0.render_pass_execute_indirect(
+            &mut self.pass,
+            *layout,
+            data_buffer.id,
+            indirect_buffer.id,
+            indirect_offset,
+            max_count,
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::execute_indirect",
+            );
+        }
+    }
+
+    fn execute_indirect_count(
+        &mut self,
+        layout: &crate::IndirectCommandLayout,
+        data_buffer: &dispatch::DispatchBuffer,
+        indirect_buffer: &dispatch::DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count_buffer: &dispatch::DispatchBuffer,
+        count_buffer_offset: crate::BufferAddress,
+        max_count: u32,
+    ) {
+        let data_buffer = data_buffer.as_core();
+        let indirect_buffer = indirect_buffer.as_core();
+        let count_buffer = count_buffer.as_core();
+
+        if let Err(cause) = self.context.0.render_pass_execute_indirect_count(
+            &mut self.pass,
+            *layout,
+            data_buffer.id,
+            indirect_buffer.id,
+            indirect_offset,
+            count_buffer.id,
+            count_buffer_offset,
+            max_count,
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::execute_indirect_count",
+            );
+        }
+    }
+
     fn insert_debug_marker(&mut self, label: &str) {
         if let Err(cause) = self
             .context
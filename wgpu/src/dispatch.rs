@@ -39,6 +39,8 @@ trait_alias!(RequestAdapterFuture: Future<Output = Result<DispatchAdapter, wgt::
 trait_alias!(RequestDeviceFuture: Future<Output = Result<(DispatchDevice, DispatchQueue), crate::RequestDeviceError>> + WasmNotSend + 'static);
 trait_alias!(PopErrorScopeFuture: Future<Output = Option<crate::Error>> + WasmNotSend + 'static);
 trait_alias!(ShaderCompilationInfoFuture: Future<Output = crate::CompilationInfo> + WasmNotSend + 'static);
+trait_alias!(CreateRenderPipelineFuture: Future<Output = Result<DispatchRenderPipeline, crate::Error>> + WasmNotSend + 'static);
+trait_alias!(CreateComputePipelineFuture: Future<Output = Result<DispatchComputePipeline, crate::Error>> + WasmNotSend + 'static);
 
 // We can't use trait aliases here, as you can't convert from a dyn Trait to dyn Supertrait _yet_.
 #[cfg(send_sync)]
@@ -46,6 +48,10 @@ pub type BoxDeviceLostCallback = Box<dyn FnOnce(crate::DeviceLostReason, String)
 #[cfg(not(send_sync))]
 pub type BoxDeviceLostCallback = Box<dyn FnOnce(crate::DeviceLostReason, String) + 'static>;
 #[cfg(send_sync)]
+pub type BoxMemoryBudgetCallback = Box<dyn Fn(crate::MemoryBudgetReport) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type BoxMemoryBudgetCallback = Box<dyn Fn(crate::MemoryBudgetReport) + 'static>;
+#[cfg(send_sync)]
 pub type BoxSubmittedWorkDoneCallback = Box<dyn FnOnce() + Send + 'static>;
 #[cfg(not(send_sync))]
 pub type BoxSubmittedWorkDoneCallback = Box<dyn FnOnce() + 'static>;
@@ -155,6 +161,14 @@ pub trait DeviceInterface: CommonTraits {
         &self,
         desc: &crate::ComputePipelineDescriptor<'_>,
     ) -> DispatchComputePipeline;
+    fn create_render_pipeline_async(
+        &self,
+        desc: &crate::RenderPipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn CreateRenderPipelineFuture>>;
+    fn create_compute_pipeline_async(
+        &self,
+        desc: &crate::ComputePipelineDescriptor<'_>,
+    ) -> Pin<Box<dyn CreateComputePipelineFuture>>;
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
@@ -172,12 +186,29 @@ pub trait DeviceInterface: CommonTraits {
         sizes: crate::BlasGeometrySizeDescriptors,
     ) -> (Option<u64>, DispatchBlas);
     fn create_tlas(&self, desc: &crate::CreateTlasDescriptor<'_>) -> DispatchTlas;
+    fn video_decode_capabilities(
+        &self,
+        codec: crate::VideoCodec,
+        format: crate::TextureFormat,
+    ) -> Option<crate::VideoDecodeCapabilities>;
+    fn create_video_decoder(
+        &self,
+        desc: &crate::VideoDecoderDescriptor<'_>,
+    ) -> DispatchVideoDecoder;
     fn create_sampler(&self, desc: &crate::SamplerDescriptor<'_>) -> DispatchSampler;
     fn create_query_set(&self, desc: &crate::QuerySetDescriptor<'_>) -> DispatchQuerySet;
     fn create_command_encoder(
         &self,
         desc: &crate::CommandEncoderDescriptor<'_>,
     ) -> DispatchCommandEncoder;
+    fn create_queue(
+        &self,
+        desc: &crate::QueueDescriptor<'_>,
+    ) -> Result<DispatchQueue, crate::Error>;
+    fn create_reusable_command_encoder(
+        &self,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> DispatchCommandEncoder;
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
@@ -196,6 +227,8 @@ pub trait DeviceInterface: CommonTraits {
 
     fn get_internal_counters(&self) -> crate::InternalCounters;
     fn generate_allocator_report(&self) -> Option<crate::AllocatorReport>;
+    fn get_memory_budget(&self) -> Option<crate::MemoryBudgetReport>;
+    fn set_memory_budget_callback(&self, threshold: u8, callback: BoxMemoryBudgetCallback);
 
     fn destroy(&self);
 }
@@ -238,6 +271,19 @@ pub trait QueueInterface: CommonTraits {
     fn on_submitted_work_done(&self, callback: BoxSubmittedWorkDoneCallback);
 
     fn compact_blas(&self, blas: &DispatchBlas) -> (Option<u64>, DispatchBlas);
+
+    fn decode_video(&self, decoder: &DispatchVideoDecoder, desc: &crate::VideoDecodeDescriptor<'_>);
+
+    fn bind_sparse(
+        &self,
+        buffer_binds: &[crate::SparseBufferBind<'_>],
+        texture_binds: &[crate::SparseTextureBind<'_>],
+    );
+
+    fn create_command_encoder(
+        &self,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> DispatchCommandEncoder;
 }
 
 pub trait ShaderModuleInterface: CommonTraits {
@@ -274,6 +320,9 @@ pub trait BlasInterface: CommonTraits {
     fn ready_for_compaction(&self) -> bool;
 }
 pub trait TlasInterface: CommonTraits {}
+pub trait VideoDecoderInterface: CommonTraits {
+    fn create_picture(&self, desc: &crate::VideoPictureDescriptor<'_>) -> DispatchTexture;
+}
 pub trait QuerySetInterface: CommonTraits {}
 pub trait PipelineLayoutInterface: CommonTraits {}
 pub trait RenderPipelineInterface: CommonTraits {
@@ -483,6 +532,24 @@ pub trait RenderPassInterface: CommonTraits {
         count_buffer_offset: crate::BufferAddress,
         max_count: u32,
     );
+    fn execute_indirect(
+        &mut self,
+        layout: &crate::IndirectCommandLayout,
+        data_buffer: &DispatchBuffer,
+        indirect_buffer: &DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        max_count: u32,
+    );
+    fn execute_indirect_count(
+        &mut self,
+        layout: &crate::IndirectCommandLayout,
+        data_buffer: &DispatchBuffer,
+        indirect_buffer: &DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count_buffer: &DispatchBuffer,
+        count_buffer_offset: crate::BufferAddress,
+        max_count: u32,
+    );
 
     fn insert_debug_marker(&mut self, label: &str);
     fn push_debug_group(&mut self, group_label: &str);
@@ -541,7 +608,9 @@ pub trait RenderBundleEncoderInterface: CommonTraits {
         Self: Sized;
 }
 
-pub trait CommandBufferInterface: CommonTraits {}
+pub trait CommandBufferInterface: CommonTraits {
+    fn transition_report(&self) -> Vec<crate::TransitionReport>;
+}
 pub trait RenderBundleInterface: CommonTraits {}
 
 pub trait SurfaceInterface: CommonTraits {
@@ -875,6 +944,7 @@ dispatch_types! {ref type DispatchTexture: TextureInterface = CoreTexture, WebTe
 dispatch_types! {ref type DispatchExternalTexture: ExternalTextureInterface = CoreExternalTexture, WebExternalTexture, DynExternalTexture}
 dispatch_types! {ref type DispatchBlas: BlasInterface = CoreBlas, WebBlas, DynBlas}
 dispatch_types! {ref type DispatchTlas: TlasInterface = CoreTlas, WebTlas, DynTlas}
+dispatch_types! {ref type DispatchVideoDecoder: VideoDecoderInterface = CoreVideoDecoder, WebVideoDecoder, DynVideoDecoder}
 dispatch_types! {ref type DispatchQuerySet: QuerySetInterface = CoreQuerySet, WebQuerySet, DynQuerySet}
 dispatch_types! {ref type DispatchPipelineLayout: PipelineLayoutInterface = CorePipelineLayout, WebPipelineLayout, DynPipelineLayout}
 dispatch_types! {ref type DispatchRenderPipeline: RenderPipelineInterface = CoreRenderPipeline, WebRenderPipeline, DynRenderPipeline}
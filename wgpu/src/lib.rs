@@ -93,25 +93,33 @@ pub use wgt::{
     DownlevelCapabilities, DownlevelFlags, DownlevelLimits, Dx12BackendOptions, Dx12Compiler,
     DxcShaderModel, DynamicOffset, Extent3d, ExternalTextureFormat,
     ExternalTextureTransferFunction, Face, Features, FeaturesWGPU, FeaturesWebGPU, FilterMode,
-    FrontFace, GlBackendOptions, GlFenceBehavior, Gles3MinorVersion, HalCounters,
-    ImageSubresourceRange, IndexFormat, InstanceDescriptor, InstanceFlags, InternalCounters,
-    Limits, MemoryBudgetThresholds, MemoryHints, MultisampleState, NoopBackendOptions, Origin2d,
-    Origin3d, PipelineStatisticsTypes, PollError, PollStatus, PolygonMode, PowerPreference,
-    PredefinedColorSpace, PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology,
-    PushConstantRange, QueryType, RenderBundleDepthStencil, RequestAdapterError,
-    SamplerBindingType, SamplerBorderColor, ShaderLocation, ShaderModel, ShaderRuntimeChecks,
-    ShaderStages, StencilFaceState, StencilOperation, StencilState, StorageTextureAccess,
-    SurfaceCapabilities, SurfaceStatus, TexelCopyBufferLayout, TextureAspect, TextureDimension,
-    TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType,
-    TextureTransition, TextureUsages, TextureUses, TextureViewDimension, Trace, VertexAttribute,
-    VertexFormat, VertexStepMode, WasmNotSend, WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT,
-    COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT, PUSH_CONSTANT_ALIGNMENT,
-    QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE, VERTEX_ALIGNMENT,
+    FrontFace, GlBackendOptions, GlFenceBehavior, GlPlatform, Gles3MinorVersion, HalCounters,
+    ImageSubresourceRange, IndexFormat, IndirectCommandLayout, InstanceDescriptor, InstanceFlags,
+    InternalCounters, Limits, MemoryBudgetReport, MemoryBudgetThresholds, MemoryHeapBudget,
+    MemoryHints, MultisampleState, NoopBackendOptions, Origin2d, Origin3d, PipelineStatisticsTypes,
+    PollError, PollStatus, PolygonMode, PowerPreference, PredefinedColorSpace, PresentMode,
+    PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange, QueryType,
+    QueueType, RenderBundleDepthStencil, RequestAdapterError, SamplerBindingType,
+    SamplerBorderColor, ShaderLocation, ShaderModel, ShaderRuntimeChecks, ShaderStages,
+    StencilFaceState, StencilOperation, StencilState, StorageTextureAccess, SurfaceCapabilities,
+    SurfaceStatus, TexelCopyBufferLayout, TextureAspect, TextureDimension, TextureFormat,
+    TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType, TextureSelector,
+    TextureTransition, TextureUsages, TextureUses, TextureViewDimension, Trace, TransitionReport,
+    TransitionedResource, VertexAttribute, VertexFormat, VertexStepMode, VideoCodec,
+    VideoDecodeCapabilities, VideoPictureInfo, VideoReferenceInfo, WasmNotSend, WasmNotSendSync,
+    WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT,
+    PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE,
+    SPARSE_PAGE_SIZE, VERTEX_ALIGNMENT,
 };
 
 #[expect(deprecated)]
 pub use wgt::VERTEX_STRIDE_ALIGNMENT;
 
+#[cfg(feature = "texel")]
+pub use wgt::texel;
+
+pub use wgt::video;
+
 // wasm-only types, we try to keep as many types non-platform
 // specific, but these need to depend on web-sys.
 #[cfg(web)]
@@ -6,6 +6,8 @@ use alloc::vec::Vec;
 use core::fmt;
 use std::sync::mpsc;
 
+use super::exclusive::Exclusive;
+
 /// Efficiently performs many buffer writes by sharing and reusing temporary buffers.
 ///
 /// Internally it uses a ring-buffer of staging buffers that are sub-allocated.
@@ -241,24 +243,3 @@ impl Chunk {
         alloc_start
     }
 }
-
-use exclusive::Exclusive;
-mod exclusive {
-    /// `Sync` wrapper that works by providing only exclusive access.
-    ///
-    /// See <https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html>
-    pub(super) struct Exclusive<T>(T);
-
-    /// Safety: `&Exclusive` has no operations.
-    unsafe impl<T> Sync for Exclusive<T> {}
-
-    impl<T> Exclusive<T> {
-        pub fn new(value: T) -> Self {
-            Self(value)
-        }
-
-        pub fn get_mut(&mut self) -> &mut T {
-            &mut self.0
-        }
-    }
-}
@@ -0,0 +1,17 @@
+/// `Sync` wrapper that works by providing only exclusive access.
+///
+/// See <https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html>
+pub(crate) struct Exclusive<T>(T);
+
+/// Safety: `&Exclusive` has no operations.
+unsafe impl<T> Sync for Exclusive<T> {}
+
+impl<T> Exclusive<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -9,8 +9,14 @@
 mod belt;
 mod device;
 mod encoder;
+#[cfg(std)]
+mod exclusive;
 mod init;
 mod mutex;
+#[cfg(std)]
+mod profiler;
+#[cfg(feature = "render-graph")]
+mod render_graph;
 mod texture_blitter;
 
 use alloc::{borrow::Cow, format, string::String, vec};
@@ -21,10 +27,21 @@ pub use belt::StagingBelt;
 pub use device::{BufferInitDescriptor, DeviceExt};
 pub use encoder::RenderEncoder;
 pub use init::*;
+#[cfg(std)]
+pub use profiler::{
+    chrome_trace, write_chrome_trace, GpuProfiler, GpuProfilerPass, GpuProfilerScope,
+    GpuProfilerSettings,
+};
+#[cfg(feature = "render-graph")]
+pub use render_graph::{
+    CompiledRenderGraph, RenderGraph, RenderGraphBuffer, RenderGraphError, RenderGraphPass,
+    RenderGraphPassBuilder, RenderGraphResource, RenderGraphResources, RenderGraphTexture,
+};
 #[cfg(feature = "wgsl")]
 pub use texture_blitter::{TextureBlitter, TextureBlitterBuilder};
 pub use wgt::{
-    math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs, TextureDataOrder,
+    math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs,
+    IndirectIndexBufferArgs, IndirectVertexBufferArgs, TextureDataOrder,
 };
 
 pub(crate) use mutex::Mutex;
@@ -0,0 +1,728 @@
+use crate::{
+    util::align_to, Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder,
+    ComputePass, ComputePassTimestampWrites, Device, Features, MapMode, PipelineStatisticsTypes,
+    QuerySet, QuerySetDescriptor, QueryType, Queue, RenderPass, RenderPassTimestampWrites,
+    QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SIZE,
+};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{fmt, fmt::Write as _, ops::Range};
+use std::sync::mpsc;
+
+use super::exclusive::Exclusive;
+
+/// Settings of a [`GpuProfiler`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GpuProfilerSettings {
+    /// The maximum number of scopes that are timed in a single frame.
+    ///
+    /// Scopes started after this many are not part of the results.
+    pub max_scopes_per_frame: u32,
+    /// The maximum number of frames whose results haven't been received by
+    /// [`GpuProfiler::process_finished_frame()`] yet.
+    ///
+    /// Frames ended while this many are in flight are not profiled.
+    pub max_frames_in_flight: usize,
+    /// The pipeline statistics to query for scopes inside of passes.
+    ///
+    /// If not empty, [`Features::PIPELINE_STATISTICS_QUERY`] must be enabled.
+    pub pipeline_statistics: PipelineStatisticsTypes,
+}
+
+impl Default for GpuProfilerSettings {
+    fn default() -> Self {
+        Self {
+            max_scopes_per_frame: 256,
+            max_frames_in_flight: 3,
+            pipeline_statistics: PipelineStatisticsTypes::empty(),
+        }
+    }
+}
+
+/// The results of a scope recorded by a [`GpuProfiler`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpuProfilerScope {
+    /// The label the scope was started with.
+    pub label: Cow<'static, str>,
+    /// The start and end timestamps of the scope, in nanoseconds.
+    ///
+    /// Absolute values have no meaning, but timestamps of scopes within a
+    /// frame can be compared with each other.
+    pub time: Range<f64>,
+    /// The pipeline statistics of the scope, in the order of the bits of
+    /// [`GpuProfilerSettings::pipeline_statistics`].
+    ///
+    /// This is only available for the outermost pass scopes, see
+    /// [`GpuProfiler::begin_pass_scope()`].
+    pub pipeline_statistics: Option<Vec<u64>>,
+    /// The scopes that were started while this one was open.
+    pub nested_scopes: Vec<GpuProfilerScope>,
+}
+
+impl GpuProfilerScope {
+    /// Returns how long the scope took, in nanoseconds.
+    pub fn duration_ns(&self) -> f64 {
+        self.time.end - self.time.start
+    }
+}
+
+/// A pass in which a [`GpuProfiler`] can record scopes.
+///
+/// This is implemented by [`RenderPass`] and [`ComputePass`].
+pub trait GpuProfilerPass {
+    /// See [`RenderPass::write_timestamp()`].
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32);
+    /// See [`RenderPass::begin_pipeline_statistics_query()`].
+    fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32);
+    /// See [`RenderPass::end_pipeline_statistics_query()`].
+    fn end_pipeline_statistics_query(&mut self);
+}
+
+impl GpuProfilerPass for RenderPass<'_> {
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        RenderPass::write_timestamp(self, query_set, query_index);
+    }
+
+    fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
+        RenderPass::begin_pipeline_statistics_query(self, query_set, query_index);
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        RenderPass::end_pipeline_statistics_query(self);
+    }
+}
+
+impl GpuProfilerPass for ComputePass<'_> {
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        ComputePass::write_timestamp(self, query_set, query_index);
+    }
+
+    fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
+        ComputePass::begin_pipeline_statistics_query(self, query_set, query_index);
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        ComputePass::end_pipeline_statistics_query(self);
+    }
+}
+
+/// Measures how long the GPU takes to execute nested, labeled scopes of commands.
+///
+/// Each scope is timed with a pair of timestamp queries, which
+/// [`Features::TIMESTAMP_QUERY`] must be enabled for. Query sets and buffers are
+/// kept per frame in flight and reused once their results were read back.
+///
+/// Using a profiler generally goes as follows:
+/// 1. Record scopes with [`GpuProfiler::scope()`], [`GpuProfiler::begin_scope()`],
+///    [`GpuProfiler::begin_pass_scope()`] or the timestamp writes of
+///    [`GpuProfiler::render_pass_timestamp_writes()`].
+/// 2. Call [`GpuProfiler::resolve_queries()`] on the last command encoder of the frame.
+/// 3. Submit all command encoders that were used in steps 1 and 2.
+/// 4. Call [`GpuProfiler::end_frame()`].
+/// 5. Call [`GpuProfiler::process_finished_frame()`] after polling the device, to
+///    receive the results of the oldest frame whose results are available.
+///
+/// Timestamps are scaled by [`Queue::get_timestamp_period()`], which is always `1.0`
+/// when timestamps are normalized on the GPU with
+/// [`InstanceFlags::AUTOMATIC_TIMESTAMP_NORMALIZATION`](crate::InstanceFlags::AUTOMATIC_TIMESTAMP_NORMALIZATION).
+pub struct GpuProfiler {
+    device: Device,
+    settings: GpuProfilerSettings,
+    timestamp_period: f32,
+    /// The frame scopes are being recorded in, or `None` if the current
+    /// frame is not profiled because too many frames are in flight.
+    current_frame: Option<Frame>,
+    /// Scopes of the current frame that haven't been ended, innermost last.
+    open_scopes: Vec<OpenScope>,
+    /// Frames whose results were read back, ready to be reused.
+    free_frames: Vec<Frame>,
+    frames_in_flight: usize,
+    /// When the buffers of ended frames are mapped, the map callback sends them here.
+    sender: Exclusive<mpsc::Sender<(Frame, Result<(), BufferAsyncError>)>>,
+    /// Mapped frames are received here by [`GpuProfiler::process_finished_frame()`].
+    receiver: Exclusive<mpsc::Receiver<(Frame, Result<(), BufferAsyncError>)>>,
+}
+
+impl GpuProfiler {
+    /// Creates a new profiler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Features::TIMESTAMP_QUERY`] is not enabled on `device`, or if
+    /// [`GpuProfilerSettings::pipeline_statistics`] is not empty and
+    /// [`Features::PIPELINE_STATISTICS_QUERY`] is not enabled.
+    pub fn new(device: &Device, queue: &Queue, settings: GpuProfilerSettings) -> Self {
+        let features = device.features();
+        assert!(
+            features.contains(Features::TIMESTAMP_QUERY),
+            "GpuProfiler requires Features::TIMESTAMP_QUERY"
+        );
+        assert!(
+            settings.pipeline_statistics.is_empty()
+                || features.contains(Features::PIPELINE_STATISTICS_QUERY),
+            "GpuProfiler requires Features::PIPELINE_STATISTICS_QUERY to query pipeline statistics"
+        );
+        assert!(
+            settings.max_frames_in_flight > 0,
+            "GpuProfiler needs at least one frame in flight"
+        );
+
+        let (sender, receiver) = mpsc::channel();
+        let mut profiler = Self {
+            device: device.clone(),
+            settings,
+            timestamp_period: queue.get_timestamp_period(),
+            current_frame: None,
+            open_scopes: Vec::new(),
+            free_frames: Vec::new(),
+            frames_in_flight: 0,
+            sender: Exclusive::new(sender),
+            receiver: Exclusive::new(receiver),
+        };
+        profiler.start_frame();
+        profiler
+    }
+
+    /// Returns the settings the profiler was created with.
+    pub fn settings(&self) -> &GpuProfilerSettings {
+        &self.settings
+    }
+
+    /// Starts a scope that is timed with timestamps written by `encoder`.
+    ///
+    /// [`Features::TIMESTAMP_QUERY_INSIDE_ENCODERS`] must be enabled. The scope must be
+    /// ended with [`GpuProfiler::end_scope()`] on the same encoder.
+    pub fn begin_scope(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        encoder: &mut CommandEncoder,
+    ) {
+        let scope = self.add_scope(label.into(), false);
+        if let Some(frame) = self.current_frame.as_ref() {
+            if let Some(query) = frame.scopes[scope].timestamp_query {
+                encoder.write_timestamp(&frame.timestamp_queries, query);
+            }
+        }
+        self.open_scopes.push(OpenScope {
+            index: scope,
+            pass: false,
+        });
+    }
+
+    /// Ends the innermost scope started with [`GpuProfiler::begin_scope()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the innermost open scope is not such a scope.
+    pub fn end_scope(&mut self, encoder: &mut CommandEncoder) {
+        let scope = self.pop_scope(false);
+        if let Some(frame) = self.current_frame.as_ref() {
+            if let Some(query) = frame.scopes[scope].timestamp_query {
+                encoder.write_timestamp(&frame.timestamp_queries, query + 1);
+            }
+        }
+    }
+
+    /// Records the commands of `f` in a scope started with [`GpuProfiler::begin_scope()`].
+    pub fn scope<R>(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        encoder: &mut CommandEncoder,
+        f: impl FnOnce(&mut Self, &mut CommandEncoder) -> R,
+    ) -> R {
+        self.begin_scope(label, encoder);
+        let result = f(self, encoder);
+        self.end_scope(encoder);
+        result
+    }
+
+    /// Starts a scope that is timed with timestamps written by `pass`.
+    ///
+    /// [`Features::TIMESTAMP_QUERY_INSIDE_PASSES`] must be enabled. The scope must be
+    /// ended with [`GpuProfiler::end_pass_scope()`] on the same pass.
+    ///
+    /// If [`GpuProfilerSettings::pipeline_statistics`] is not empty and the scope is
+    /// not nested in another pass scope, its pipeline statistics are queried too.
+    pub fn begin_pass_scope(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        pass: &mut impl GpuProfilerPass,
+    ) {
+        let with_statistics = !self.settings.pipeline_statistics.is_empty()
+            && !self.open_scopes.iter().any(|scope| scope.pass);
+        let scope = self.add_scope(label.into(), with_statistics);
+        if let Some(frame) = self.current_frame.as_ref() {
+            let record = &frame.scopes[scope];
+            if let Some(query) = record.timestamp_query {
+                pass.write_timestamp(&frame.timestamp_queries, query);
+            }
+            if let (Some(query), Some(statistics)) =
+                (record.statistics_query, frame.statistics.as_ref())
+            {
+                pass.begin_pipeline_statistics_query(&statistics.query_set, query);
+            }
+        }
+        self.open_scopes.push(OpenScope {
+            index: scope,
+            pass: true,
+        });
+    }
+
+    /// Ends the innermost scope started with [`GpuProfiler::begin_pass_scope()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the innermost open scope is not such a scope.
+    pub fn end_pass_scope(&mut self, pass: &mut impl GpuProfilerPass) {
+        let scope = self.pop_scope(true);
+        if let Some(frame) = self.current_frame.as_ref() {
+            let record = &frame.scopes[scope];
+            if record.statistics_query.is_some() {
+                pass.end_pipeline_statistics_query();
+            }
+            if let Some(query) = record.timestamp_query {
+                pass.write_timestamp(&frame.timestamp_queries, query + 1);
+            }
+        }
+    }
+
+    /// Adds a scope timed by the beginning and end of a render pass, and returns
+    /// the timestamp writes to create the pass with.
+    ///
+    /// Returns `None` if the scope isn't timed, because the current frame isn't
+    /// profiled or ran out of scopes.
+    pub fn render_pass_timestamp_writes(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+    ) -> Option<RenderPassTimestampWrites<'_>> {
+        let (query_set, query) = self.add_pass_timestamp_writes(label.into())?;
+        Some(RenderPassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some(query),
+            end_of_pass_write_index: Some(query + 1),
+        })
+    }
+
+    /// Adds a scope timed by the beginning and end of a compute pass, and returns
+    /// the timestamp writes to create the pass with.
+    ///
+    /// Returns `None` if the scope isn't timed, because the current frame isn't
+    /// profiled or ran out of scopes.
+    pub fn compute_pass_timestamp_writes(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+    ) -> Option<ComputePassTimestampWrites<'_>> {
+        let (query_set, query) = self.add_pass_timestamp_writes(label.into())?;
+        Some(ComputePassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some(query),
+            end_of_pass_write_index: Some(query + 1),
+        })
+    }
+
+    /// Resolves the queries of the current frame.
+    ///
+    /// This must be called after all scopes of the frame were recorded, on an
+    /// encoder that is submitted after all encoders the scopes were recorded on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are scopes that haven't been ended.
+    pub fn resolve_queries(&mut self, encoder: &mut CommandEncoder) {
+        assert!(
+            self.open_scopes.is_empty(),
+            "GpuProfiler has {} scopes that haven't been ended",
+            self.open_scopes.len()
+        );
+        let Some(frame) = self.current_frame.as_mut() else {
+            return;
+        };
+        if frame.resolved {
+            return;
+        }
+        frame.resolved = true;
+
+        if frame.next_timestamp_query != 0 {
+            encoder.resolve_query_set(
+                &frame.timestamp_queries,
+                0..frame.next_timestamp_query,
+                &frame.resolve_buffer,
+                0,
+            );
+        }
+        if let Some(ref statistics) = frame.statistics {
+            if statistics.next_query != 0 {
+                encoder.resolve_query_set(
+                    &statistics.query_set,
+                    0..statistics.next_query,
+                    &frame.resolve_buffer,
+                    frame.statistics_offset,
+                );
+            }
+        }
+        encoder.copy_buffer_to_buffer(
+            &frame.resolve_buffer,
+            0,
+            &frame.read_buffer,
+            0,
+            frame.resolve_buffer.size(),
+        );
+    }
+
+    /// Ends the current frame and starts reading back its results.
+    ///
+    /// This must be called after the encoder passed to
+    /// [`GpuProfiler::resolve_queries()`] was submitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the queries of the frame have scopes but weren't resolved.
+    pub fn end_frame(&mut self) {
+        if let Some(frame) = self.current_frame.take() {
+            if frame.scopes.is_empty() && !frame.resolved {
+                self.free_frames.push(frame);
+            } else {
+                assert!(
+                    frame.resolved,
+                    "GpuProfiler::resolve_queries() must be called before ending the frame"
+                );
+                self.frames_in_flight += 1;
+                let sender = self.sender.get_mut().clone();
+                frame
+                    .read_buffer
+                    .clone()
+                    .slice(..)
+                    .map_async(MapMode::Read, move |result| {
+                        let _ = sender.send((frame, result));
+                    });
+            }
+        }
+        self.start_frame();
+    }
+
+    /// Returns the scopes of the oldest ended frame whose results are available.
+    ///
+    /// The results become available once the GPU finished executing the frame
+    /// and the device was polled. Returns `None` if there is no such frame, or
+    /// if its results couldn't be read back.
+    pub fn process_finished_frame(&mut self) -> Option<Vec<GpuProfilerScope>> {
+        let (mut frame, result) = self.receiver.get_mut().try_recv().ok()?;
+        self.frames_in_flight -= 1;
+        let scopes = result.ok().map(|()| {
+            let scopes = frame.read_scopes(self.timestamp_period, &self.settings);
+            frame.read_buffer.unmap();
+            scopes
+        });
+        frame.reset();
+        self.free_frames.push(frame);
+        scopes
+    }
+
+    fn start_frame(&mut self) {
+        self.open_scopes.clear();
+        self.current_frame = if self.frames_in_flight < self.settings.max_frames_in_flight {
+            Some(
+                self.free_frames
+                    .pop()
+                    .unwrap_or_else(|| Frame::new(&self.device, &self.settings)),
+            )
+        } else {
+            None
+        };
+    }
+
+    /// Adds a scope to the current frame and returns its index.
+    fn add_scope(&mut self, label: Cow<'static, str>, with_statistics: bool) -> usize {
+        let parent = self.open_scopes.last().map(|scope| scope.index);
+        let max_scopes = self.settings.max_scopes_per_frame;
+        let Some(frame) = self.current_frame.as_mut() else {
+            return 0;
+        };
+        assert!(
+            !frame.resolved,
+            "GpuProfiler can't record scopes after resolving the queries of the frame"
+        );
+
+        let timestamp_query =
+            (frame.next_timestamp_query < max_scopes * 2).then_some(frame.next_timestamp_query);
+        if timestamp_query.is_some() {
+            frame.next_timestamp_query += 2;
+        }
+        let statistics_query = match frame.statistics {
+            Some(ref mut statistics) if with_statistics && statistics.next_query < max_scopes => {
+                statistics.next_query += 1;
+                Some(statistics.next_query - 1)
+            }
+            _ => None,
+        };
+
+        frame.scopes.push(ScopeRecord {
+            label,
+            parent,
+            timestamp_query,
+            statistics_query,
+        });
+        frame.scopes.len() - 1
+    }
+
+    fn pop_scope(&mut self, pass: bool) -> usize {
+        let scope = self
+            .open_scopes
+            .pop()
+            .expect("GpuProfiler has no open scope");
+        assert_eq!(
+            scope.pass,
+            pass,
+            "GpuProfiler's innermost open scope was started by a {}",
+            if scope.pass {
+                "pass"
+            } else {
+                "command encoder"
+            }
+        );
+        scope.index
+    }
+
+    fn add_pass_timestamp_writes(&mut self, label: Cow<'static, str>) -> Option<(&QuerySet, u32)> {
+        let scope = self.add_scope(label, false);
+        let frame = self.current_frame.as_ref()?;
+        let query = frame.scopes[scope].timestamp_query?;
+        Some((&frame.timestamp_queries, query))
+    }
+}
+
+impl fmt::Debug for GpuProfiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuProfiler")
+            .field("settings", &self.settings)
+            .field("open_scopes", &self.open_scopes.len())
+            .field("frames_in_flight", &self.frames_in_flight)
+            .field("free_frames", &self.free_frames.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes `frames` of scopes in the [Chrome trace event format], which can be
+/// viewed in `chrome://tracing` or <https://ui.perfetto.dev>.
+///
+/// Each frame is written as a separate thread of the trace.
+///
+/// [Chrome trace event format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+pub fn write_chrome_trace<'a>(
+    writer: &mut impl fmt::Write,
+    frames: impl IntoIterator<Item = &'a [GpuProfilerScope]>,
+) -> fmt::Result {
+    fn write_scopes(
+        writer: &mut impl fmt::Write,
+        frame: usize,
+        scopes: &[GpuProfilerScope],
+        first: &mut bool,
+    ) -> fmt::Result {
+        for scope in scopes {
+            if !core::mem::take(first) {
+                writer.write_char(',')?;
+            }
+            // Chrome traces are in microseconds.
+            write!(
+                writer,
+                r#"{{"name":"{}","ph":"X","pid":0,"tid":{frame},"ts":{},"dur":{}"#,
+                JsonEscaped(&scope.label),
+                scope.time.start / 1000.0,
+                scope.duration_ns() / 1000.0,
+            )?;
+            if let Some(ref statistics) = scope.pipeline_statistics {
+                writer.write_str(r#","args":{"pipeline_statistics":["#)?;
+                for (i, value) in statistics.iter().enumerate() {
+                    if i != 0 {
+                        writer.write_char(',')?;
+                    }
+                    write!(writer, "{value}")?;
+                }
+                writer.write_str("]}")?;
+            }
+            writer.write_char('}')?;
+            write_scopes(writer, frame, &scope.nested_scopes, first)?;
+        }
+        Ok(())
+    }
+
+    writer.write_str(r#"{"traceEvents":["#)?;
+    let mut first = true;
+    for (frame, scopes) in frames.into_iter().enumerate() {
+        write_scopes(writer, frame, scopes, &mut first)?;
+    }
+    writer.write_str("]}")
+}
+
+/// Returns `frames` of scopes in the Chrome trace event format, see
+/// [`write_chrome_trace()`].
+pub fn chrome_trace<'a>(frames: impl IntoIterator<Item = &'a [GpuProfilerScope]>) -> String {
+    let mut trace = String::new();
+    write_chrome_trace(&mut trace, frames).unwrap();
+    trace
+}
+
+/// Formats a string as the contents of a JSON string literal.
+struct JsonEscaped<'a>(&'a str);
+
+impl fmt::Display for JsonEscaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct OpenScope {
+    index: usize,
+    /// Whether the scope was started by [`GpuProfiler::begin_pass_scope()`].
+    pass: bool,
+}
+
+struct ScopeRecord {
+    label: Cow<'static, str>,
+    parent: Option<usize>,
+    /// The first of the two timestamp queries of the scope, or `None` if the
+    /// frame ran out of queries.
+    timestamp_query: Option<u32>,
+    statistics_query: Option<u32>,
+}
+
+struct StatisticsQueries {
+    query_set: QuerySet,
+    next_query: u32,
+}
+
+/// The queries and buffers of one frame in flight.
+struct Frame {
+    timestamp_queries: QuerySet,
+    next_timestamp_query: u32,
+    statistics: Option<StatisticsQueries>,
+    /// The offset of the pipeline statistics in the resolve buffer.
+    statistics_offset: u64,
+    resolve_buffer: Buffer,
+    read_buffer: Buffer,
+    scopes: Vec<ScopeRecord>,
+    resolved: bool,
+}
+
+impl Frame {
+    fn new(device: &Device, settings: &GpuProfilerSettings) -> Self {
+        let timestamp_count = settings.max_scopes_per_frame * 2;
+        let timestamp_queries = device.create_query_set(&QuerySetDescriptor {
+            label: Some("(wgpu internal) GpuProfiler timestamp queries"),
+            ty: QueryType::Timestamp,
+            count: timestamp_count,
+        });
+
+        let statistics_offset = align_to(
+            u64::from(timestamp_count) * u64::from(QUERY_SIZE),
+            QUERY_RESOLVE_BUFFER_ALIGNMENT,
+        );
+        let mut size = statistics_offset;
+        let statistics = (!settings.pipeline_statistics.is_empty()).then(|| {
+            let statistics_size =
+                settings.pipeline_statistics.bits().count_ones() as u64 * u64::from(QUERY_SIZE);
+            size += statistics_size * u64::from(settings.max_scopes_per_frame);
+            StatisticsQueries {
+                query_set: device.create_query_set(&QuerySetDescriptor {
+                    label: Some("(wgpu internal) GpuProfiler pipeline statistics queries"),
+                    ty: QueryType::PipelineStatistics(settings.pipeline_statistics),
+                    count: settings.max_scopes_per_frame,
+                }),
+                next_query: 0,
+            }
+        });
+
+        let resolve_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("(wgpu internal) GpuProfiler resolve buffer"),
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("(wgpu internal) GpuProfiler read buffer"),
+            size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            timestamp_queries,
+            next_timestamp_query: 0,
+            statistics,
+            statistics_offset,
+            resolve_buffer,
+            read_buffer,
+            scopes: Vec::new(),
+            resolved: false,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.next_timestamp_query = 0;
+        if let Some(ref mut statistics) = self.statistics {
+            statistics.next_query = 0;
+        }
+        self.scopes.clear();
+        self.resolved = false;
+    }
+
+    /// Reads the scopes from the mapped read buffer.
+    fn read_scopes(
+        &mut self,
+        timestamp_period: f32,
+        settings: &GpuProfilerSettings,
+    ) -> Vec<GpuProfilerScope> {
+        let data = self.read_buffer.slice(..).get_mapped_range();
+        let read_u64 = |offset: u64| {
+            let offset = offset as usize;
+            u64::from_ne_bytes(data[offset..offset + 8].try_into().unwrap())
+        };
+        let statistics_count = settings.pipeline_statistics.bits().count_ones() as u64;
+
+        // Scopes are recorded before the scopes nested in them, so building
+        // the tree backwards moves every scope after its nested scopes.
+        let mut nested: Vec<Vec<GpuProfilerScope>> = Vec::new();
+        nested.resize_with(self.scopes.len(), Vec::new);
+        let mut roots = Vec::new();
+        for (index, record) in self.scopes.drain(..).enumerate().rev() {
+            let Some(query) = record.timestamp_query else {
+                continue;
+            };
+            let timestamp = |query: u32| {
+                read_u64(u64::from(query) * u64::from(QUERY_SIZE)) as f64
+                    * f64::from(timestamp_period)
+            };
+            let pipeline_statistics = record.statistics_query.map(|query| {
+                let base = self.statistics_offset
+                    + u64::from(query) * statistics_count * u64::from(QUERY_SIZE);
+                (0..statistics_count)
+                    .map(|i| read_u64(base + i * u64::from(QUERY_SIZE)))
+                    .collect()
+            });
+            let mut nested_scopes = core::mem::take(&mut nested[index]);
+            nested_scopes.reverse();
+            let scope = GpuProfilerScope {
+                label: record.label,
+                time: timestamp(query)..timestamp(query + 1),
+                pipeline_statistics,
+                nested_scopes,
+            };
+            match record.parent {
+                Some(parent) => nested[parent].push(scope),
+                None => roots.push(scope),
+            }
+        }
+        roots.reverse();
+        roots
+    }
+}
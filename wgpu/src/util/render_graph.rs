@@ -0,0 +1,571 @@
+use crate::{Buffer, CommandEncoder, Device, Texture, TextureDescriptor, TextureFormat};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec, vec::Vec};
+use core::{error, fmt};
+
+/// A texture used by the passes of a [`RenderGraph`].
+///
+/// Created by [`RenderGraph::create_texture()`] or [`RenderGraph::import_texture()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderGraphTexture(usize);
+
+/// A buffer used by the passes of a [`RenderGraph`].
+///
+/// Created by [`RenderGraph::import_buffer()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderGraphBuffer(usize);
+
+/// A pass of a [`RenderGraph`], returned by [`RenderGraphPassBuilder::execute()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderGraphPass(usize);
+
+/// A resource that a pass of a [`RenderGraph`] reads or writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderGraphResource {
+    /// A texture of the graph.
+    Texture(RenderGraphTexture),
+    /// A buffer of the graph.
+    Buffer(RenderGraphBuffer),
+}
+
+impl RenderGraphResource {
+    fn index(self) -> usize {
+        match self {
+            Self::Texture(RenderGraphTexture(index)) | Self::Buffer(RenderGraphBuffer(index)) => {
+                index
+            }
+        }
+    }
+}
+
+impl From<RenderGraphTexture> for RenderGraphResource {
+    fn from(texture: RenderGraphTexture) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+impl From<RenderGraphBuffer> for RenderGraphResource {
+    fn from(buffer: RenderGraphBuffer) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
+/// The descriptor of a transient texture, without its label.
+type TransientDescriptor = wgt::TextureDescriptor<(), Vec<TextureFormat>>;
+
+/// Usages that a texture is only created with on its own, so transient textures with
+/// them only share a texture with transient textures of the same usage.
+const EXCLUSIVE_USAGES: wgt::TextureUsages =
+    wgt::TextureUsages::TRANSIENT.union(wgt::TextureUsages::SPARSE);
+
+/// Returns whether a transient texture described by `desc` can be placed in a texture
+/// described by `slot`, once the usages and view formats of `desc` are added to it.
+fn is_compatible(slot: &TransientDescriptor, desc: &TransientDescriptor) -> bool {
+    slot.size == desc.size
+        && slot.mip_level_count == desc.mip_level_count
+        && slot.sample_count == desc.sample_count
+        && slot.dimension == desc.dimension
+        && slot.format == desc.format
+        && (slot.usage == desc.usage || !(slot.usage | desc.usage).intersects(EXCLUSIVE_USAGES))
+}
+
+enum ResourceKind {
+    /// A texture created by the graph, see [`RenderGraph::create_texture()`].
+    Transient(TransientDescriptor),
+    Texture(Texture),
+    Buffer(Buffer),
+}
+
+struct Resource {
+    label: String,
+    kind: ResourceKind,
+}
+
+type PassFn<'a> = Box<dyn FnOnce(&RenderGraphResources<'_>, &mut CommandEncoder) + 'a>;
+
+struct Pass<'a> {
+    label: String,
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+    keep: bool,
+    run: PassFn<'a>,
+}
+
+/// Culls the passes of a frame and places its transient textures, from the resources the
+/// passes read and write.
+///
+/// Using a graph generally goes as follows:
+/// 1. Declare the resources of the frame. Textures that only live within the frame are
+///    created with [`RenderGraph::create_texture()`], and textures and buffers that live
+///    longer, such as the surface texture or a readback buffer, are imported with
+///    [`RenderGraph::import_texture()`] and [`RenderGraph::import_buffer()`].
+/// 2. Add passes with [`RenderGraph::add_pass()`], declaring the resources each of them
+///    reads and writes, and the closure that records its commands.
+/// 3. Call [`RenderGraph::compile()`], which culls the passes and places the transient
+///    textures, and can be inspected without recording anything.
+/// 4. Call [`CompiledRenderGraph::execute()`] to record the passes on a command encoder.
+///
+/// Each pass that writes a resource makes a new version of it. A pass that reads a
+/// resource reads the version made by the last pass that writes it and was added before
+/// it. The passes that aren't culled run in the order they were added, so the passes
+/// have to be added in an order that works. Passes that don't write an imported
+/// resource, and aren't marked with [`RenderGraphPassBuilder::keep()`], are culled
+/// unless a pass that is kept reads a version they write, or writes on top of it.
+///
+/// This is built on top of the regular `wgpu` API: it doesn't insert any barriers of its
+/// own, which wgpu takes care of as usual.
+pub struct RenderGraph<'a> {
+    resources: Vec<Resource>,
+    passes: Vec<Pass<'a>>,
+}
+
+impl Default for RenderGraph<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RenderGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderGraph")
+            .field("resources", &self.resources.len())
+            .field("passes", &self.passes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RenderGraph<'a> {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self {
+            resources: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Declares a texture that is created by the graph and only used by its passes.
+    ///
+    /// Transient textures with the same size, format, dimension, mip level count and sample
+    /// count share a single texture if no pass between the first and last ones that use one
+    /// of them uses the other. The shared texture is created with the usages and view
+    /// formats of all of them, unless one of them has [`TextureUsages::TRANSIENT`] or
+    /// [`TextureUsages::SPARSE`], in which case it only shares a texture with transient
+    /// textures of the same usages.
+    ///
+    /// Their contents are undefined before they are first written, so a pass can only read
+    /// one once a pass that writes it was added. Otherwise [`RenderGraph::compile()`] fails
+    /// with [`RenderGraphError::UnwrittenTexture`].
+    ///
+    /// [`TextureUsages::TRANSIENT`]: wgt::TextureUsages::TRANSIENT
+    /// [`TextureUsages::SPARSE`]: wgt::TextureUsages::SPARSE
+    pub fn create_texture(&mut self, desc: &TextureDescriptor<'_>) -> RenderGraphTexture {
+        let label = desc.label.unwrap_or_default().to_owned();
+        let desc = desc.map_label_and_view_formats(|_| (), |formats| formats.to_vec());
+        RenderGraphTexture(self.add_resource(label, ResourceKind::Transient(desc)))
+    }
+
+    /// Declares a texture that outlives the graph.
+    ///
+    /// Passes that write imported textures are never culled.
+    pub fn import_texture(&mut self, label: &str, texture: &Texture) -> RenderGraphTexture {
+        RenderGraphTexture(self.add_resource(label, ResourceKind::Texture(texture.clone())))
+    }
+
+    /// Declares a buffer that outlives the graph.
+    ///
+    /// Passes that write imported buffers are never culled.
+    pub fn import_buffer(&mut self, label: &str, buffer: &Buffer) -> RenderGraphBuffer {
+        RenderGraphBuffer(self.add_resource(label, ResourceKind::Buffer(buffer.clone())))
+    }
+
+    fn add_resource(&mut self, label: impl Into<String>, kind: ResourceKind) -> usize {
+        self.resources.push(Resource {
+            label: label.into(),
+            kind,
+        });
+        self.resources.len() - 1
+    }
+
+    /// Starts declaring a pass, which is added to the graph by
+    /// [`RenderGraphPassBuilder::execute()`].
+    pub fn add_pass(&mut self, label: &str) -> RenderGraphPassBuilder<'_, 'a> {
+        RenderGraphPassBuilder {
+            graph: self,
+            label: label.to_owned(),
+            reads: Vec::new(),
+            writes: Vec::new(),
+            keep: false,
+        }
+    }
+
+    /// Culls the passes, and places the transient textures.
+    ///
+    /// Fails if a pass reads a transient texture before any pass that writes it was added.
+    pub fn compile(self) -> Result<CompiledRenderGraph<'a>, RenderGraphError> {
+        let Self { resources, passes } = self;
+
+        // The passes that each pass reads a version written by, or writes on top of.
+        let mut dependencies = vec![Vec::new(); passes.len()];
+        // The last pass that wrote each resource so far.
+        let mut last_writers = vec![None; resources.len()];
+        for (index, pass) in passes.iter().enumerate() {
+            for &resource in &pass.reads {
+                match last_writers[resource] {
+                    Some(writer) => dependencies[index].push(writer),
+                    // Transient textures are undefined until they are written.
+                    None if matches!(resources[resource].kind, ResourceKind::Transient(_)) => {
+                        return Err(RenderGraphError::UnwrittenTexture {
+                            pass: pass.label.clone(),
+                            texture: resources[resource].label.clone(),
+                        });
+                    }
+                    // Reading the resource as it was before the graph.
+                    None => {}
+                }
+            }
+            for &resource in &pass.writes {
+                if let Some(writer) = last_writers[resource] {
+                    dependencies[index].push(writer);
+                }
+                last_writers[resource] = Some(index);
+            }
+        }
+
+        // Keep the passes with side effects, and the passes they depend on.
+        let mut live = vec![false; passes.len()];
+        let mut stack: Vec<usize> = (0..passes.len())
+            .filter(|&index| {
+                let pass = &passes[index];
+                pass.keep
+                    || pass.writes.iter().any(|&resource| {
+                        !matches!(resources[resource].kind, ResourceKind::Transient(_))
+                    })
+            })
+            .collect();
+        while let Some(index) = stack.pop() {
+            if !live[index] {
+                live[index] = true;
+                stack.extend_from_slice(&dependencies[index]);
+            }
+        }
+
+        // Passes only depend on passes added before them, so the live passes run in the
+        // order they were added.
+        let order: Vec<usize> = (0..passes.len()).filter(|&index| live[index]).collect();
+
+        // The positions in `order` of the first and last passes using each transient texture.
+        let mut lifetimes = vec![None; resources.len()];
+        for (position, &index) in order.iter().enumerate() {
+            let pass = &passes[index];
+            for &resource in pass.reads.iter().chain(&pass.writes) {
+                if matches!(resources[resource].kind, ResourceKind::Transient(_)) {
+                    let lifetime = lifetimes[resource].get_or_insert((position, position));
+                    lifetime.1 = position;
+                }
+            }
+        }
+        let mut transients: Vec<_> = lifetimes
+            .iter()
+            .enumerate()
+            .filter_map(|(resource, lifetime)| Some((lifetime.as_ref()?.0, resource)))
+            .collect();
+        transients.sort_unstable();
+
+        // Place each transient texture in the first slot it fits in.
+        let mut texture_slots = vec![None; resources.len()];
+        let mut slots: Vec<TextureSlot> = Vec::new();
+        for (first_use, resource) in transients {
+            let ResourceKind::Transient(ref desc) = resources[resource].kind else {
+                unreachable!()
+            };
+            let last_use = lifetimes[resource].unwrap().1;
+            let slot = match slots
+                .iter()
+                .position(|slot| slot.last_use < first_use && is_compatible(&slot.desc, desc))
+            {
+                Some(slot) => {
+                    let slot_desc = &mut slots[slot].desc;
+                    slot_desc.usage |= desc.usage;
+                    for &format in &desc.view_formats {
+                        if !slot_desc.view_formats.contains(&format) {
+                            slot_desc.view_formats.push(format);
+                        }
+                    }
+                    slot
+                }
+                None => {
+                    slots.push(TextureSlot {
+                        label: resources[resource].label.clone(),
+                        desc: desc.clone(),
+                        last_use,
+                    });
+                    slots.len() - 1
+                }
+            };
+            slots[slot].last_use = last_use;
+            texture_slots[resource] = Some(slot);
+        }
+
+        Ok(CompiledRenderGraph {
+            order: order.into_iter().map(RenderGraphPass).collect(),
+            culled: (0..passes.len())
+                .filter(|&index| !live[index])
+                .map(RenderGraphPass)
+                .collect(),
+            resources,
+            passes,
+            texture_slots,
+            slots,
+        })
+    }
+}
+
+/// Declares the resources a pass of a [`RenderGraph`] reads and writes.
+///
+/// Returned by [`RenderGraph::add_pass()`].
+pub struct RenderGraphPassBuilder<'g, 'a> {
+    graph: &'g mut RenderGraph<'a>,
+    label: String,
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+    keep: bool,
+}
+
+impl fmt::Debug for RenderGraphPassBuilder<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderGraphPassBuilder")
+            .field("label", &self.label)
+            .field("keep", &self.keep)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RenderGraphPassBuilder<'_, 'a> {
+    /// Declares that the pass reads `resource`.
+    ///
+    /// The pass reads the version of `resource` written by the last pass that was added
+    /// before it and writes `resource`, which is kept as long as it is. For imported
+    /// resources that no such pass writes, it reads them as they were before the graph.
+    pub fn read(mut self, resource: impl Into<RenderGraphResource>) -> Self {
+        self.reads.push(resource.into().index());
+        self
+    }
+
+    /// Declares that the pass writes `resource`.
+    ///
+    /// The passes that were added before it and write `resource` are kept as long as it
+    /// is, since the pass may not overwrite all of it.
+    pub fn write(mut self, resource: impl Into<RenderGraphResource>) -> Self {
+        self.writes.push(resource.into().index());
+        self
+    }
+
+    /// Keeps the pass from being culled, like passes that write imported resources.
+    ///
+    /// This is meant for passes with side effects the graph doesn't know about.
+    pub fn keep(mut self) -> Self {
+        self.keep = true;
+        self
+    }
+
+    /// Adds the pass to the graph, with the closure that records its commands.
+    ///
+    /// The closure is called by [`CompiledRenderGraph::execute()`], unless the pass is
+    /// culled.
+    pub fn execute(
+        self,
+        run: impl FnOnce(&RenderGraphResources<'_>, &mut CommandEncoder) + 'a,
+    ) -> RenderGraphPass {
+        self.graph.passes.push(Pass {
+            label: self.label,
+            reads: self.reads,
+            writes: self.writes,
+            keep: self.keep,
+            run: Box::new(run),
+        });
+        RenderGraphPass(self.graph.passes.len() - 1)
+    }
+}
+
+/// A texture that transient textures of a [`RenderGraph`] are placed in.
+struct TextureSlot {
+    /// The label of the first transient texture placed in the slot.
+    label: String,
+    desc: TransientDescriptor,
+    /// The position of the last pass using the slot so far.
+    last_use: usize,
+}
+
+/// A [`RenderGraph`] whose passes were culled.
+///
+/// Returned by [`RenderGraph::compile()`].
+pub struct CompiledRenderGraph<'a> {
+    resources: Vec<Resource>,
+    passes: Vec<Pass<'a>>,
+    order: Vec<RenderGraphPass>,
+    culled: Vec<RenderGraphPass>,
+    /// The slot of each transient texture used by a pass, indexed like `resources`.
+    texture_slots: Vec<Option<usize>>,
+    slots: Vec<TextureSlot>,
+}
+
+impl fmt::Debug for CompiledRenderGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledRenderGraph")
+            .field("order", &self.order)
+            .field("culled", &self.culled)
+            .field("texture_slots", &self.texture_slots)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CompiledRenderGraph<'_> {
+    /// Returns the passes that will be executed, in order.
+    pub fn pass_order(&self) -> &[RenderGraphPass] {
+        &self.order
+    }
+
+    /// Returns the passes that were culled, because nothing uses what they write.
+    pub fn culled_passes(&self) -> &[RenderGraphPass] {
+        &self.culled
+    }
+
+    /// Returns the label `pass` was added with.
+    pub fn pass_label(&self, pass: RenderGraphPass) -> &str {
+        &self.passes[pass.0].label
+    }
+
+    /// Returns the index of the texture that a transient texture is placed in.
+    ///
+    /// Transient textures with the same index share a texture. This is `None` for imported
+    /// textures, and for transient textures that only culled passes use.
+    pub fn texture_slot(&self, texture: RenderGraphTexture) -> Option<usize> {
+        self.texture_slots[texture.0]
+    }
+
+    /// Returns the number of textures that are created for the transient textures.
+    pub fn texture_slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Creates the transient textures, and records the passes that weren't culled on
+    /// `encoder`, in order.
+    ///
+    /// The transient textures are dropped once the passes are recorded, and freed once
+    /// the commands using them are done executing.
+    pub fn execute(self, device: &Device, encoder: &mut CommandEncoder) {
+        let slot_textures: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| {
+                device.create_texture(&slot.desc.map_label_and_view_formats(
+                    |()| Some(slot.label.as_str()),
+                    |_| slot.desc.view_formats.as_slice(),
+                ))
+            })
+            .collect();
+
+        let mut passes: Vec<_> = self.passes.into_iter().map(Some).collect();
+        for pass in self.order {
+            let Pass {
+                label,
+                reads,
+                writes,
+                run,
+                ..
+            } = passes[pass.0].take().unwrap();
+            let resources = RenderGraphResources {
+                pass_label: &label,
+                reads: &reads,
+                writes: &writes,
+                resources: &self.resources,
+                texture_slots: &self.texture_slots,
+                slot_textures: &slot_textures,
+            };
+            run(&resources, encoder);
+        }
+    }
+}
+
+/// The resources available to a pass of a [`RenderGraph`] while it records its commands.
+pub struct RenderGraphResources<'r> {
+    pass_label: &'r str,
+    reads: &'r [usize],
+    writes: &'r [usize],
+    resources: &'r [Resource],
+    texture_slots: &'r [Option<usize>],
+    slot_textures: &'r [Texture],
+}
+
+impl fmt::Debug for RenderGraphResources<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderGraphResources")
+            .field("pass", &self.pass_label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RenderGraphResources<'_> {
+    /// Returns the texture behind `texture`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pass didn't declare that it reads or writes `texture`.
+    pub fn texture(&self, texture: RenderGraphTexture) -> &Texture {
+        let resource = self.check_access(texture.0);
+        match resource.kind {
+            ResourceKind::Transient(_) => {
+                &self.slot_textures[self.texture_slots[texture.0].unwrap()]
+            }
+            ResourceKind::Texture(ref texture) => texture,
+            ResourceKind::Buffer(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the buffer behind `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pass didn't declare that it reads or writes `buffer`.
+    pub fn buffer(&self, buffer: RenderGraphBuffer) -> &Buffer {
+        match self.check_access(buffer.0).kind {
+            ResourceKind::Buffer(ref buffer) => buffer,
+            _ => unreachable!(),
+        }
+    }
+
+    fn check_access(&self, index: usize) -> &Resource {
+        let resource = &self.resources[index];
+        assert!(
+            self.reads.contains(&index) || self.writes.contains(&index),
+            "Render graph pass {:?} uses {:?} without declaring it",
+            self.pass_label,
+            resource.label,
+        );
+        resource
+    }
+}
+
+/// An error returned by [`RenderGraph::compile()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenderGraphError {
+    /// A pass reads a transient texture before any pass that writes it was added.
+    UnwrittenTexture {
+        /// The label of the pass.
+        pass: String,
+        /// The label of the texture.
+        texture: String,
+    },
+}
+
+impl fmt::Display for RenderGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnwrittenTexture { pass, texture } => write!(
+                f,
+                "Render graph pass {pass:?} reads transient texture {texture:?} before any pass writes it"
+            ),
+        }
+    }
+}
+
+impl error::Error for RenderGraphError {}